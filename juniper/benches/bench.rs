@@ -1,144 +1,185 @@
-use bencher::{benchmark_group, benchmark_main, Bencher};
-use juniper::{
-    execute_sync, graphql_vars,
-    tests::fixtures::starwars::schema::{Database, Query},
-    DefaultScalarValue, EmptyMutation, EmptySubscription, RootNode,
-};
-
-fn query_type_name(b: &mut Bencher) {
-    let database = Database::new();
-    let schema: RootNode<
-        Query,
-        EmptyMutation<Database>,
-        EmptySubscription<Database>,
-        DefaultScalarValue,
-    > = RootNode::new(
-        Query,
-        EmptyMutation::<Database>::new(),
-        EmptySubscription::<Database>::new(),
-    );
-
-    let doc = r#"
-        query IntrospectionQueryTypeQuery {
-          __schema {
-            queryType {
-              name
-            }
-          }
-        }"#;
+use criterion::{criterion_group, criterion_main, Criterion};
+use juniper::bench::{self, Fixture, STARWARS_FIXTURES};
+
+use self::allocations::{Allocations, CountingAllocator};
 
-    b.iter(|| execute_sync(doc, None, &schema, &graphql_vars! {}, &database));
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator::new();
+
+fn fixture(name: &str) -> &'static Fixture {
+    STARWARS_FIXTURES
+        .iter()
+        .find(|f| f.name == name)
+        .unwrap_or_else(|| panic!("no `{}` fixture", name))
 }
 
-fn introspection_query(b: &mut Bencher) {
-    let database = Database::new();
-    let schema: RootNode<
-        Query,
-        EmptyMutation<Database>,
-        EmptySubscription<Database>,
-        DefaultScalarValue,
-    > = RootNode::new(
-        Query,
-        EmptyMutation::<Database>::new(),
-        EmptySubscription::<Database>::new(),
-    );
-
-    let doc = r#"
-  query IntrospectionQuery {
-    __schema {
-      queryType { name }
-      mutationType { name }
-      subscriptionType { name }
-      types {
-        ...FullType
-      }
-      directives {
-        name
-        description
-        locations
-        args {
-          ...InputValue
-        }
-      }
-    }
-  }
-
-  fragment FullType on __Type {
-    kind
-    name
-    description
-    fields(includeDeprecated: true) {
-      name
-      description
-      args {
-        ...InputValue
-      }
-      type {
-        ...TypeRef
-      }
-      isDeprecated
-      deprecationReason
+fn queries(c: &mut Criterion) {
+    let schema = bench::starwars_schema();
+    let database = bench::starwars_context();
+
+    let mut group = c.benchmark_group("queries");
+    for name in ["query_type_name", "introspection", "hero_friends_of_friends"] {
+        let fixture = fixture(name);
+        let variables = fixture.variables();
+
+        group.bench_function(name, |b| {
+            b.iter(|| bench::execute_sync(&schema, fixture, &variables, &database))
+        });
     }
-    inputFields {
-      ...InputValue
+    group.finish();
+}
+
+fn allocations(c: &mut Criterion<Allocations>) {
+    let schema = bench::starwars_schema();
+    let database = bench::starwars_context();
+
+    let mut group = c.benchmark_group("allocations");
+    for name in ["introspection", "hero_friends_of_friends"] {
+        let fixture = fixture(name);
+        let variables = fixture.variables();
+
+        group.bench_function(name, |b| {
+            b.iter(|| bench::execute_sync(&schema, fixture, &variables, &database))
+        });
     }
-    interfaces {
-      ...TypeRef
+    group.finish();
+}
+
+fn phases(c: &mut Criterion) {
+    let schema = bench::starwars_schema();
+    let fixture = fixture("introspection");
+    let document = bench::parse(&schema, fixture);
+
+    let mut group = c.benchmark_group("phases");
+    group.bench_function("parse_introspection", |b| {
+        b.iter(|| bench::parse(&schema, fixture))
+    });
+    group.bench_function("validate_introspection", |b| {
+        b.iter(|| bench::validate(&schema, &document))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, queries, phases);
+
+// Allocation counts don't vary between samples, which the plots of `criterion`
+// cannot cope with, so they're disabled after being configured from arguments.
+fn allocation_benches() {
+    let mut criterion = Criterion::default()
+        .with_measurement(Allocations(&ALLOCATOR))
+        .configure_from_args()
+        .without_plots();
+    allocations(&mut criterion);
+}
+
+criterion_main!(benches, allocation_benches);
+
+/// Measuring the number of heap allocations, rather than the time, performed
+/// by a benchmarked routine.
+mod allocations {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        sync::atomic::{AtomicU64, Ordering},
+    };
+
+    use criterion::{
+        measurement::{Measurement, ValueFormatter},
+        Throughput,
+    };
+
+    /// [`System`] allocator counting the allocations it performs.
+    pub struct CountingAllocator(AtomicU64);
+
+    impl CountingAllocator {
+        pub const fn new() -> Self {
+            Self(AtomicU64::new(0))
+        }
+
+        fn count(&self) -> u64 {
+            self.0.load(Ordering::SeqCst)
+        }
     }
-    enumValues(includeDeprecated: true) {
-      name
-      description
-      isDeprecated
-      deprecationReason
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout)
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            System.alloc_zeroed(layout)
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            System.realloc(ptr, layout, new_size)
+        }
     }
-    possibleTypes {
-      ...TypeRef
+
+    /// [`Measurement`] of the allocations counted by a [`CountingAllocator`].
+    pub struct Allocations(pub &'static CountingAllocator);
+
+    impl Measurement for Allocations {
+        type Intermediate = u64;
+        type Value = u64;
+
+        fn start(&self) -> u64 {
+            self.0.count()
+        }
+
+        fn end(&self, start: u64) -> u64 {
+            self.0.count() - start
+        }
+
+        fn add(&self, v1: &u64, v2: &u64) -> u64 {
+            v1 + v2
+        }
+
+        fn zero(&self) -> u64 {
+            0
+        }
+
+        fn to_f64(&self, value: &u64) -> f64 {
+            *value as f64
+        }
+
+        fn formatter(&self) -> &dyn ValueFormatter {
+            &AllocationsFormatter
+        }
     }
-  }
-
-  fragment InputValue on __InputValue {
-    name
-    description
-    type { ...TypeRef }
-    defaultValue
-  }
-
-  fragment TypeRef on __Type {
-    kind
-    name
-    ofType {
-      kind
-      name
-      ofType {
-        kind
-        name
-        ofType {
-          kind
-          name
-          ofType {
-            kind
-            name
-            ofType {
-              kind
-              name
-              ofType {
-                kind
-                name
-                ofType {
-                  kind
-                  name
+
+    struct AllocationsFormatter;
+
+    impl ValueFormatter for AllocationsFormatter {
+        fn scale_values(&self, _: f64, _: &mut [f64]) -> &'static str {
+            "allocs"
+        }
+
+        fn scale_throughputs(
+            &self,
+            _: f64,
+            throughput: &Throughput,
+            values: &mut [f64],
+        ) -> &'static str {
+            match *throughput {
+                Throughput::Bytes(n) => {
+                    values.iter_mut().for_each(|v| *v /= n as f64);
+                    "allocs/byte"
+                }
+                Throughput::Elements(n) => {
+                    values.iter_mut().for_each(|v| *v /= n as f64);
+                    "allocs/element"
                 }
-              }
             }
-          }
         }
-      }
-    }
-  }
-"#;
 
-    b.iter(|| execute_sync(doc, None, &schema, &graphql_vars! {}, &database));
+        fn scale_for_machines(&self, _: &mut [f64]) -> &'static str {
+            "allocs"
+        }
+    }
 }
-
-benchmark_group!(queries, query_type_name, introspection_query);
-benchmark_main!(queries);
@@ -45,6 +45,8 @@ pub enum InputValue<S = DefaultScalarValue> {
     Object(Vec<(Spanning<String>, Spanning<InputValue<S>>)>),
 }
 
+/// Definition of a variable of an operation.
+#[allow(missing_docs)]
 #[derive(Clone, PartialEq, Debug)]
 pub struct VariableDefinition<'a, S> {
     pub var_type: Spanning<Type<'a>>,
@@ -62,6 +64,8 @@ pub struct VariableDefinitions<'a, S> {
     pub items: Vec<(Spanning<&'a str>, VariableDefinition<'a, S>)>,
 }
 
+/// Field selected in a selection set.
+#[allow(missing_docs)]
 #[derive(Clone, PartialEq, Debug)]
 pub struct Field<'a, S> {
     pub alias: Option<Spanning<&'a str>>,
@@ -71,12 +75,16 @@ pub struct Field<'a, S> {
     pub selection_set: Option<Vec<Selection<'a, S>>>,
 }
 
+/// Spread of a named fragment in a selection set.
+#[allow(missing_docs)]
 #[derive(Clone, PartialEq, Debug)]
 pub struct FragmentSpread<'a, S> {
     pub name: Spanning<&'a str>,
     pub directives: Option<Vec<Spanning<Directive<'a, S>>>>,
 }
 
+/// Inline fragment in a selection set.
+#[allow(missing_docs)]
 #[derive(Clone, PartialEq, Debug)]
 pub struct InlineFragment<'a, S> {
     pub type_condition: Option<Spanning<&'a str>>,
@@ -107,6 +115,8 @@ pub enum Selection<'a, S = DefaultScalarValue> {
     InlineFragment(Spanning<InlineFragment<'a, S>>),
 }
 
+/// Directive applied to a part of a document.
+#[allow(missing_docs)]
 #[derive(Clone, PartialEq, Debug)]
 pub struct Directive<'a, S> {
     pub name: Spanning<&'a str>,
@@ -114,7 +124,7 @@ pub struct Directive<'a, S> {
 }
 
 #[allow(missing_docs)]
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum OperationType {
     Query,
     Mutation,
@@ -131,6 +141,8 @@ pub struct Operation<'a, S> {
     pub selection_set: Vec<Selection<'a, S>>,
 }
 
+/// Definition of a named fragment.
+#[allow(missing_docs)]
 #[derive(Clone, PartialEq, Debug)]
 pub struct Fragment<'a, S> {
     pub name: Spanning<&'a str>,
@@ -407,6 +419,87 @@ impl<S> InputValue<S> {
         }
     }
 
+    /// Iterates over the fields of this [`InputValue`], if it's an object.
+    pub fn as_object_iter(&self) -> Option<impl Iterator<Item = (&str, &Self)>> {
+        match self {
+            Self::Object(o) => Some(o.iter().map(|(k, v)| (k.item.as_str(), &v.item))),
+            _ => None,
+        }
+    }
+
+    /// Iterates over the elements of this [`InputValue`], if it's a list.
+    pub fn as_list_iter(&self) -> Option<impl Iterator<Item = &Self>> {
+        match self {
+            Self::List(l) => Some(l.iter().map(|v| &v.item)),
+            _ => None,
+        }
+    }
+
+    /// Returns the nested [`InputValue`] located by the given `path`, if any.
+    ///
+    /// The `path` consists of object keys separated by dots and list indices
+    /// in square brackets, like `a.b[2].c` or `[0][1]`. Returns [`None`] if the
+    /// `path` is malformed, or if it doesn't exist in this [`InputValue`].
+    ///
+    /// ```rust
+    /// # use juniper::{graphql_input_value, InputValue};
+    /// #
+    /// let value: InputValue = graphql_input_value!({"a": {"b": [1, 2, {"c": true}]}});
+    ///
+    /// assert_eq!(value.get_path("a.b[1]"), Some(&graphql_input_value!(2)));
+    /// assert_eq!(value.get_path("a.b[2].c"), Some(&graphql_input_value!(true)));
+    /// assert_eq!(value.get_path("a.b[3]"), None);
+    /// assert_eq!(value.get_path("a..b"), None);
+    /// ```
+    pub fn get_path(&self, path: &str) -> Option<&Self> {
+        InputPathSegment::parse(path)?
+            .into_iter()
+            .try_fold(self, |value, segment| match segment {
+                InputPathSegment::Key(key) => value
+                    .as_object_iter()?
+                    .find(|(k, _)| *k == key)
+                    .map(|(_, v)| v),
+                InputPathSegment::Index(i) => value.as_list_iter()?.nth(i),
+            })
+    }
+
+    /// Walks this [`InputValue`] recursively, calling the provided
+    /// [`InputValueVisitor`] on every nested value, in depth-first order.
+    pub fn walk<'a, V: InputValueVisitor<'a, S> + ?Sized>(&'a self, visitor: &mut V) {
+        self.walk_at(&mut Vec::new(), visitor)
+    }
+
+    fn walk_at<'a, V: InputValueVisitor<'a, S> + ?Sized>(
+        &'a self,
+        path: &mut Vec<InputPathSegment<'a>>,
+        visitor: &mut V,
+    ) {
+        match self {
+            Self::Null => visitor.visit_null(path),
+            Self::Scalar(s) => visitor.visit_scalar(path, s),
+            Self::Enum(e) => visitor.visit_enum(path, e),
+            Self::Variable(v) => visitor.visit_variable(path, v),
+            Self::List(l) => {
+                visitor.enter_list(path, l);
+                for (i, v) in l.iter().enumerate() {
+                    path.push(InputPathSegment::Index(i));
+                    v.item.walk_at(path, visitor);
+                    path.pop();
+                }
+                visitor.exit_list(path, l);
+            }
+            Self::Object(o) => {
+                visitor.enter_object(path, o);
+                for (k, v) in o {
+                    path.push(InputPathSegment::Key(&k.item));
+                    v.item.walk_at(path, visitor);
+                    path.pop();
+                }
+                visitor.exit_object(path, o);
+            }
+        }
+    }
+
     /// Recursively finds all variables
     pub fn referenced_variables(&self) -> Vec<&str> {
         match self {
@@ -450,6 +543,116 @@ impl<S> InputValue<S> {
     }
 }
 
+/// Segment of a path to a nested [`InputValue`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum InputPathSegment<'a> {
+    /// Key of an [`InputValue::Object`] field.
+    Key(&'a str),
+
+    /// Index of an [`InputValue::List`] element.
+    Index(usize),
+}
+
+impl<'a> InputPathSegment<'a> {
+    /// Parses the given `path` in the `a.b[2].c` notation into its segments.
+    ///
+    /// Returns [`None`] if the `path` is malformed.
+    fn parse(path: &'a str) -> Option<Vec<Self>> {
+        let mut segments = Vec::new();
+        let mut rest = path;
+        while !rest.is_empty() {
+            if let Some(r) = rest.strip_prefix('[') {
+                let (index, r) = r.split_once(']')?;
+                segments.push(Self::Index(index.parse().ok()?));
+                rest = r;
+            } else {
+                if !segments.is_empty() {
+                    rest = rest.strip_prefix('.')?;
+                }
+                let end = rest.find(['.', '[']).unwrap_or(rest.len());
+                if end == 0 {
+                    return None;
+                }
+                segments.push(Self::Key(&rest[..end]));
+                rest = &rest[end..];
+            }
+        }
+        Some(segments)
+    }
+}
+
+impl<'a> fmt::Display for InputPathSegment<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Key(k) => write!(f, ".{}", k),
+            Self::Index(i) => write!(f, "[{}]", i),
+        }
+    }
+}
+
+/// Visitor of the nested values of an [`InputValue`], walked via
+/// [`InputValue::walk()`].
+///
+/// Every method receives the `path` to the visited value from the walked root,
+/// and does nothing by default, so only the interesting ones need to be
+/// implemented.
+///
+/// ```rust
+/// # use juniper::{graphql_input_value, InputPathSegment, InputValue, InputValueVisitor};
+/// #
+/// /// Collects the paths to all the `null`s.
+/// #[derive(Default)]
+/// struct Nulls(Vec<String>);
+///
+/// impl<'a, S> InputValueVisitor<'a, S> for Nulls {
+///     fn visit_null(&mut self, path: &[InputPathSegment<'a>]) {
+///         self.0.push(path.iter().map(ToString::to_string).collect());
+///     }
+/// }
+///
+/// let value: InputValue = graphql_input_value!({"a": [1, null], "b": null});
+///
+/// let mut nulls = Nulls::default();
+/// value.walk(&mut nulls);
+/// assert_eq!(nulls.0, [".a[1]", ".b"]);
+/// ```
+pub trait InputValueVisitor<'a, S> {
+    /// Visits an [`InputValue::Null`].
+    fn visit_null(&mut self, _path: &[InputPathSegment<'a>]) {}
+
+    /// Visits an [`InputValue::Scalar`].
+    fn visit_scalar(&mut self, _path: &[InputPathSegment<'a>], _scalar: &'a S) {}
+
+    /// Visits an [`InputValue::Enum`].
+    fn visit_enum(&mut self, _path: &[InputPathSegment<'a>], _name: &'a str) {}
+
+    /// Visits an [`InputValue::Variable`].
+    fn visit_variable(&mut self, _path: &[InputPathSegment<'a>], _name: &'a str) {}
+
+    /// Enters an [`InputValue::List`], before visiting its elements.
+    fn enter_list(&mut self, _path: &[InputPathSegment<'a>], _list: &'a [Spanning<InputValue<S>>]) {
+    }
+
+    /// Exits an [`InputValue::List`], after visiting its elements.
+    fn exit_list(&mut self, _path: &[InputPathSegment<'a>], _list: &'a [Spanning<InputValue<S>>]) {}
+
+    /// Enters an [`InputValue::Object`], before visiting its fields.
+    fn enter_object(
+        &mut self,
+        _path: &[InputPathSegment<'a>],
+        _object: &'a [(Spanning<String>, Spanning<InputValue<S>>)],
+    ) {
+    }
+
+    /// Exits an [`InputValue::Object`], after visiting its fields.
+    fn exit_object(
+        &mut self,
+        _path: &[InputPathSegment<'a>],
+        _object: &'a [(Spanning<String>, Spanning<InputValue<S>>)],
+    ) {
+    }
+}
+
 impl<S: ScalarValue> fmt::Display for InputValue<S> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -536,6 +739,12 @@ impl<S: From<bool>> From<bool> for InputValue<S> {
     }
 }
 
+impl<S: Clone> ToInputValue<S> for InputValue<S> {
+    fn to_input_value(&self) -> InputValue<S> {
+        self.clone()
+    }
+}
+
 impl<'a, S> Arguments<'a, S> {
     pub fn into_iter(self) -> vec::IntoIter<(Spanning<&'a str>, Spanning<InputValue<S>>)> {
         self.items.into_iter()
@@ -570,9 +779,108 @@ impl<'a, S> VariableDefinitions<'a, S> {
 
 #[cfg(test)]
 mod tests {
-    use crate::graphql_input_value;
+    use crate::{graphql_input_value, parser::Spanning, DefaultScalarValue};
+
+    use super::{InputPathSegment, InputValue, InputValueVisitor};
+
+    #[test]
+    fn get_path() {
+        let value: InputValue = graphql_input_value!({
+            "a": {"b": [1, [2, 3], {"c": @var}]},
+            "d.e": null,
+        });
+
+        assert_eq!(value.get_path(""), Some(&value));
+        assert_eq!(value.get_path("a.b[0]"), Some(&graphql_input_value!(1)));
+        assert_eq!(value.get_path("a.b[1][1]"), Some(&graphql_input_value!(3)));
+        assert_eq!(
+            value.get_path("a.b[2].c"),
+            Some(&graphql_input_value!(@var)),
+        );
+        assert_eq!(value.get_path("a.b[3]"), None);
+        assert_eq!(value.get_path("a.c"), None);
+        assert_eq!(value.get_path("a[0]"), None);
+        assert_eq!(value.get_path("d.e"), None);
+
+        for malformed in [".a", "a.", "a..b", "a.b[", "a.b[x]", "a.b[-1]", "a.b[0]c"] {
+            assert_eq!(value.get_path(malformed), None, "path: {}", malformed);
+        }
+    }
+
+    #[test]
+    fn walk() {
+        #[derive(Default)]
+        struct Trace(Vec<String>);
+
+        impl<'a> InputValueVisitor<'a, DefaultScalarValue> for Trace {
+            fn visit_null(&mut self, path: &[InputPathSegment<'a>]) {
+                self.push(path, "null");
+            }
+
+            fn visit_scalar(&mut self, path: &[InputPathSegment<'a>], s: &'a DefaultScalarValue) {
+                self.push(path, &s.to_string());
+            }
+
+            fn visit_enum(&mut self, path: &[InputPathSegment<'a>], name: &'a str) {
+                self.push(path, name);
+            }
+
+            fn visit_variable(&mut self, path: &[InputPathSegment<'a>], name: &'a str) {
+                self.push(path, &format!("${}", name));
+            }
+
+            fn enter_list(&mut self, path: &[InputPathSegment<'a>], _: &'a [Spanning<InputValue>]) {
+                self.push(path, "[");
+            }
+
+            fn exit_list(&mut self, path: &[InputPathSegment<'a>], _: &'a [Spanning<InputValue>]) {
+                self.push(path, "]");
+            }
+
+            fn enter_object(
+                &mut self,
+                path: &[InputPathSegment<'a>],
+                _: &'a [(Spanning<String>, Spanning<InputValue>)],
+            ) {
+                self.push(path, "{");
+            }
+
+            fn exit_object(
+                &mut self,
+                path: &[InputPathSegment<'a>],
+                _: &'a [(Spanning<String>, Spanning<InputValue>)],
+            ) {
+                self.push(path, "}");
+            }
+        }
+
+        impl Trace {
+            fn push(&mut self, path: &[InputPathSegment<'_>], what: &str) {
+                let path = path.iter().map(ToString::to_string).collect::<String>();
+                self.0.push(format!("{} {}", path, what));
+            }
+        }
 
-    use super::InputValue;
+        let value: InputValue = graphql_input_value!({"a": [1, RED], "b": {"c": @var}, "d": null});
+
+        let mut trace = Trace::default();
+        value.walk(&mut trace);
+        assert_eq!(
+            trace.0,
+            [
+                " {",
+                ".a [",
+                ".a[0] 1",
+                ".a[1] RED",
+                ".a ]",
+                ".b {",
+                ".b.c $var",
+                ".b }",
+                ".d null",
+                " }",
+            ],
+        );
+    }
 
     #[test]
     fn test_input_value_fmt() {
@@ -0,0 +1,282 @@
+//! Fixtures and helpers for benchmarking the parser, the validation and the
+//! executor.
+//!
+//! The helpers don't depend on any benchmark harness, so can be called right
+//! inside the measured closures of `criterion` (as the benches of this crate
+//! do) or any other one.
+//! They panic on any error, as a [`Fixture`] is expected to always execute
+//! successfully, so a broken engine is detected rather than measured.
+//!
+//! ```
+//! use juniper::bench;
+//!
+//! let schema = bench::starwars_schema();
+//! let database = bench::starwars_context();
+//!
+//! for fixture in bench::STARWARS_FIXTURES {
+//!     let variables = fixture.variables();
+//!
+//!     // With `criterion`:
+//!     // c.bench_function(fixture.name, |b| b.iter(|| bench::execute_sync(...)));
+//!     bench::execute_sync(&schema, fixture, &variables, &database);
+//! }
+//! ```
+
+use crate::{
+    ast::{Document, OwnedDocument},
+    introspection::INTROSPECTION_QUERY,
+    parser::parse_document_source,
+    tests::fixtures::starwars::schema::{Database, Query},
+    validation::{visit_all_rules, ValidatorContext},
+    value::ScalarValue,
+    EmptyMutation, EmptySubscription, ExecutionError, GraphQLType, GraphQLTypeAsync, RequestError,
+    RootNode, Value, Variables,
+};
+
+/// GraphQL document to be benchmarked against a schema.
+#[derive(Clone, Copy, Debug)]
+pub struct Fixture {
+    /// Name of this [`Fixture`], suitable as a benchmark ID.
+    pub name: &'static str,
+
+    /// Source of the GraphQL document.
+    pub document: &'static str,
+
+    /// Name of the operation to execute, if the `document` contains many.
+    pub operation_name: Option<&'static str>,
+
+    /// Variables of the operation, as a JSON object.
+    pub variables: &'static str,
+}
+
+impl Fixture {
+    /// Parses the [`Variables`] of this [`Fixture`].
+    ///
+    /// # Panics
+    ///
+    /// If the [`Fixture::variables`] is not a valid JSON object.
+    pub fn variables<S: ScalarValue>(&self) -> Variables<S> {
+        serde_json::from_str(self.variables)
+            .unwrap_or_else(|e| panic!("invalid variables of `{}` fixture: {}", self.name, e))
+    }
+}
+
+/// Star Wars schema, as returned by [`starwars_schema()`].
+pub type StarWarsSchema =
+    RootNode<'static, Query, EmptyMutation<Database>, EmptySubscription<Database>>;
+
+/// [`Fixture`]s to be executed against the [`StarWarsSchema`], covering
+/// introspection, interfaces, fragments, nested lists and variables.
+pub const STARWARS_FIXTURES: &[Fixture] = &[
+    Fixture {
+        name: "query_type_name",
+        document: r#"
+            query IntrospectionQueryTypeQuery {
+                __schema {
+                    queryType {
+                        name
+                    }
+                }
+            }
+        "#,
+        operation_name: None,
+        variables: "{}",
+    },
+    Fixture {
+        name: "introspection",
+        document: INTROSPECTION_QUERY,
+        operation_name: None,
+        variables: "{}",
+    },
+    Fixture {
+        name: "hero_name",
+        document: r#"
+            query HeroNameQuery {
+                hero {
+                    name
+                }
+            }
+        "#,
+        operation_name: None,
+        variables: "{}",
+    },
+    Fixture {
+        name: "hero_friends_of_friends",
+        document: r#"
+            query HeroFriendsOfFriendsQuery {
+                hero {
+                    ...CharacterFields
+                    friends {
+                        ...CharacterFields
+                        friends {
+                            ...CharacterFields
+                            friends {
+                                ...CharacterFields
+                            }
+                        }
+                    }
+                }
+            }
+
+            fragment CharacterFields on Character {
+                __typename
+                id
+                name
+                appearsIn
+                ... on Human {
+                    homePlanet
+                }
+                ... on Droid {
+                    primaryFunction
+                }
+            }
+        "#,
+        operation_name: None,
+        variables: "{}",
+    },
+    Fixture {
+        name: "human_by_id",
+        document: r#"
+            query HeroQuery($episode: Episode) {
+                hero(episode: $episode) {
+                    name
+                }
+            }
+
+            query HumanQuery($id: String!) {
+                human(id: $id) {
+                    id
+                    name
+                    homePlanet
+                    appearsIn
+                }
+            }
+        "#,
+        operation_name: Some("HumanQuery"),
+        variables: r#"{"id": "1000"}"#,
+    },
+];
+
+/// Builds the [`StarWarsSchema`].
+pub fn starwars_schema() -> StarWarsSchema {
+    RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+}
+
+/// Builds the context of the [`StarWarsSchema`].
+pub fn starwars_context() -> Database {
+    Database::new()
+}
+
+/// Parses the document of the given [`Fixture`] against the provided `schema`.
+///
+/// # Panics
+///
+/// If the document cannot be parsed.
+pub fn parse<QueryT, MutationT, SubscriptionT, S>(
+    schema: &RootNode<QueryT, MutationT, SubscriptionT, S>,
+    fixture: &Fixture,
+) -> OwnedDocument<'static, S>
+where
+    S: ScalarValue,
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S>,
+    SubscriptionT: GraphQLType<S>,
+{
+    parse_document_source(fixture.document, &schema.schema)
+        .unwrap_or_else(|e| panic!("cannot parse `{}` fixture: {}", fixture.name, e))
+}
+
+/// Validates the already [`parse`]d `document` against the provided `schema`.
+///
+/// # Panics
+///
+/// If the `document` is not valid.
+pub fn validate<QueryT, MutationT, SubscriptionT, S>(
+    schema: &RootNode<QueryT, MutationT, SubscriptionT, S>,
+    document: &Document<'static, S>,
+) where
+    S: ScalarValue,
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S>,
+    SubscriptionT: GraphQLType<S>,
+{
+    let mut ctx = ValidatorContext::new(&schema.schema, document);
+    visit_all_rules(&mut ctx, document);
+
+    let errors = ctx.into_errors();
+    assert!(errors.is_empty(), "invalid document: {:?}", errors);
+}
+
+/// Executes the given [`Fixture`] synchronously in the provided `schema`.
+///
+/// # Panics
+///
+/// If the execution fails or produces any field errors.
+pub fn execute_sync<QueryT, MutationT, SubscriptionT, S>(
+    schema: &RootNode<QueryT, MutationT, SubscriptionT, S>,
+    fixture: &Fixture,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+) -> Value<S>
+where
+    S: ScalarValue,
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S, Context = QueryT::Context>,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+{
+    let res = crate::execute_sync(
+        fixture.document,
+        fixture.operation_name,
+        schema,
+        variables,
+        context,
+    );
+    unwrap_result(fixture, res)
+}
+
+/// Executes the given [`Fixture`] asynchronously in the provided `schema`.
+///
+/// # Panics
+///
+/// If the execution fails or produces any field errors.
+pub async fn execute<QueryT, MutationT, SubscriptionT, S>(
+    schema: &RootNode<'_, QueryT, MutationT, SubscriptionT, S>,
+    fixture: &Fixture,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+) -> Value<S>
+where
+    QueryT: GraphQLTypeAsync<S>,
+    QueryT::TypeInfo: Sync,
+    QueryT::Context: Sync,
+    MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+    MutationT::TypeInfo: Sync,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context> + Sync,
+    SubscriptionT::TypeInfo: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    let res = crate::execute(
+        fixture.document,
+        fixture.operation_name,
+        schema,
+        variables,
+        context,
+    )
+    .await;
+    unwrap_result(fixture, res)
+}
+
+fn unwrap_result<S: ScalarValue>(
+    fixture: &Fixture,
+    res: Result<(Value<S>, Vec<ExecutionError<S>>), RequestError<'_>>,
+) -> Value<S> {
+    let (value, errors) =
+        res.unwrap_or_else(|e| panic!("cannot execute `{}` fixture: {}", fixture.name, e));
+    assert!(
+        errors.is_empty(),
+        "`{}` fixture executed with errors: {:?}",
+        fixture.name,
+        errors,
+    );
+    value
+}
@@ -0,0 +1,800 @@
+//! Programmatic construction of GraphQL operations.
+
+use std::{borrow::Cow, fmt, time::Instant};
+
+use crate::{
+    ast::{
+        Arguments, Definition, Field, Fragment, FragmentSpread, InlineFragment, InputValue,
+        Operation, OperationType, OwnedDocument, Selection, Type, VariableDefinition,
+        VariableDefinitions,
+    },
+    executor::{execute_validated_query, execute_validated_query_async, ExecutionError, Variables},
+    parser::{parse_type, Lexer, Parser, Spanning, Token},
+    prepare_operation,
+    schema::model::{RootNode, SchemaType},
+    types::{async_await::GraphQLTypeAsync, base::GraphQLType},
+    validate_document,
+    validation::{visit_all_rules, RuleError, ValidatorContext},
+    value::{ScalarValue, Value},
+    RequestError,
+};
+
+/// Builder of a GraphQL operation, along with the fragments it uses.
+///
+/// Constructs an [`ast::Document`] without concatenating any strings, so it
+/// may be validated, printed (via its [`Display`] implementation) or executed
+/// right away.
+///
+/// ```
+/// # use juniper::{graphql_input_value, DefaultScalarValue, OperationBuilder};
+/// #
+/// let operation = OperationBuilder::<DefaultScalarValue>::query("UserName")
+///     .variable("id", "ID!", None)
+///     .field("user", graphql_input_value!({"id": @id}), |user| {
+///         user.leaf("name").spread("UserFriends")
+///     })
+///     .fragment("UserFriends", "User", |user| {
+///         user.field("friends", graphql_input_value!(null), |f| f.leaf("name"))
+///     });
+///
+/// assert_eq!(
+///     operation.to_string(),
+///     "query UserName($id: ID!) { user(id: $id) { name ...UserFriends } }\n\
+///      fragment UserFriends on User { friends { name } }",
+/// );
+/// ```
+///
+/// [`ast::Document`]: crate::Document
+/// [`Display`]: fmt::Display
+#[derive(Clone, Debug, PartialEq)]
+pub struct OperationBuilder<S> {
+    operation_type: OperationType,
+    name: Option<String>,
+    variables: Vec<(String, Type<'static>, Option<InputValue<S>>)>,
+    selection_set: SelectionSetBuilder<S>,
+    fragments: Vec<(String, String, SelectionSetBuilder<S>)>,
+}
+
+impl<S> OperationBuilder<S> {
+    /// Starts building a query operation with the given `name`.
+    pub fn query(name: impl Into<String>) -> Self {
+        Self::new(OperationType::Query, Some(name.into()))
+    }
+
+    /// Starts building a mutation operation with the given `name`.
+    pub fn mutation(name: impl Into<String>) -> Self {
+        Self::new(OperationType::Mutation, Some(name.into()))
+    }
+
+    /// Starts building a subscription operation with the given `name`.
+    pub fn subscription(name: impl Into<String>) -> Self {
+        Self::new(OperationType::Subscription, Some(name.into()))
+    }
+
+    /// Starts building an anonymous operation of the given `operation_type`.
+    pub fn anonymous(operation_type: OperationType) -> Self {
+        Self::new(operation_type, None)
+    }
+
+    fn new(operation_type: OperationType, name: Option<String>) -> Self {
+        Self {
+            operation_type,
+            name,
+            variables: vec![],
+            selection_set: SelectionSetBuilder::default(),
+            fragments: vec![],
+        }
+    }
+
+    /// Declares a variable of this operation with the given `name`, type
+    /// literal (like `[ID!]!`) and `default` value.
+    ///
+    /// # Panics
+    ///
+    /// If the `var_type` is not a valid GraphQL type literal.
+    #[must_use]
+    pub fn variable(
+        mut self,
+        name: impl Into<String>,
+        var_type: &str,
+        default: Option<InputValue<S>>,
+    ) -> Self {
+        let var_type = parse_type_literal(var_type)
+            .unwrap_or_else(|| panic!("invalid GraphQL type literal `{}`", var_type));
+        self.variables.push((name.into(), var_type, default));
+        self
+    }
+
+    /// Selects the field with the given `name` and `arguments` on the root
+    /// type of this operation, building its sub-selection with the given
+    /// `selection` function.
+    ///
+    /// See [`SelectionSetBuilder::field()`] for details.
+    #[must_use]
+    pub fn field<F>(
+        mut self,
+        name: impl Into<String>,
+        arguments: InputValue<S>,
+        selection: F,
+    ) -> Self
+    where
+        F: FnOnce(SelectionSetBuilder<S>) -> SelectionSetBuilder<S>,
+    {
+        self.selection_set = self.selection_set.field(name, arguments, selection);
+        self
+    }
+
+    /// Selects the field with the given `name` and `arguments` on the root
+    /// type of this operation under the given `alias`.
+    ///
+    /// See [`SelectionSetBuilder::field()`] for details.
+    #[must_use]
+    pub fn aliased_field<F>(
+        mut self,
+        alias: impl Into<String>,
+        name: impl Into<String>,
+        arguments: InputValue<S>,
+        selection: F,
+    ) -> Self
+    where
+        F: FnOnce(SelectionSetBuilder<S>) -> SelectionSetBuilder<S>,
+    {
+        self.selection_set = self
+            .selection_set
+            .aliased_field(alias, name, arguments, selection);
+        self
+    }
+
+    /// Replaces the whole selection set of this operation with the one built
+    /// by the given `selection` function.
+    #[must_use]
+    pub fn selection<F>(mut self, selection: F) -> Self
+    where
+        F: FnOnce(SelectionSetBuilder<S>) -> SelectionSetBuilder<S>,
+    {
+        self.selection_set = selection(SelectionSetBuilder::default());
+        self
+    }
+
+    /// Defines a fragment with the given `name` on the given `type_condition`
+    /// in the document of this operation, building its selection set with the
+    /// given `selection` function.
+    #[must_use]
+    pub fn fragment<F>(
+        mut self,
+        name: impl Into<String>,
+        type_condition: impl Into<String>,
+        selection: F,
+    ) -> Self
+    where
+        F: FnOnce(SelectionSetBuilder<S>) -> SelectionSetBuilder<S>,
+    {
+        self.fragments.push((
+            name.into(),
+            type_condition.into(),
+            selection(SelectionSetBuilder::default()),
+        ));
+        self
+    }
+
+    /// Returns the name of the built operation, if any.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Builds the [`ast::Document`] of this operation, borrowing all the
+    /// names from this builder.
+    ///
+    /// The built document has no source positions.
+    ///
+    /// [`ast::Document`]: crate::Document
+    pub fn to_document(&self) -> OwnedDocument<'_, S>
+    where
+        S: Clone,
+    {
+        let variable_definitions = (!self.variables.is_empty()).then(|| {
+            Spanning::unlocated(VariableDefinitions {
+                items: self
+                    .variables
+                    .iter()
+                    .map(|(name, var_type, default)| {
+                        (
+                            Spanning::unlocated(name.as_str()),
+                            VariableDefinition {
+                                var_type: Spanning::unlocated(var_type.clone()),
+                                default_value: default.clone().map(Spanning::unlocated),
+                                directives: None,
+                            },
+                        )
+                    })
+                    .collect(),
+            })
+        });
+
+        let operation = Definition::Operation(Spanning::unlocated(Operation {
+            operation_type: self.operation_type,
+            name: self.name.as_deref().map(Spanning::unlocated),
+            variable_definitions,
+            directives: None,
+            selection_set: self.selection_set.to_selections(),
+        }));
+        let fragments = self.fragments.iter().map(|(name, on, selection_set)| {
+            Definition::Fragment(Spanning::unlocated(Fragment {
+                name: Spanning::unlocated(name.as_str()),
+                type_condition: Spanning::unlocated(on.as_str()),
+                directives: None,
+                selection_set: selection_set.to_selections(),
+            }))
+        });
+
+        std::iter::once(operation).chain(fragments).collect()
+    }
+
+    /// Validates the built document against the given `schema`, returning
+    /// the found errors, if any.
+    pub fn validate(&self, schema: &SchemaType<S>) -> Vec<RuleError>
+    where
+        S: ScalarValue,
+    {
+        let document = self.to_document();
+        let mut ctx = ValidatorContext::new(schema, &document);
+        visit_all_rules(&mut ctx, &document);
+        ctx.into_errors()
+    }
+
+    /// Validates and executes the built operation synchronously in the
+    /// provided schema, the same way [`execute_sync()`] does, logging it into
+    /// the [`QueryLogger`] of the schema as printed.
+    ///
+    /// As the document is built already, it's neither parsed nor looked up in
+    /// the [`DocumentCache`] of the schema.
+    ///
+    /// [`DocumentCache`]: crate::DocumentCache
+    /// [`execute_sync()`]: crate::execute_sync
+    /// [`QueryLogger`]: crate::QueryLogger
+    pub fn execute_sync<QueryT, MutationT, SubscriptionT>(
+        &self,
+        root_node: &RootNode<QueryT, MutationT, SubscriptionT, S>,
+        variables: &Variables<S>,
+        context: &QueryT::Context,
+    ) -> Result<(Value<S>, Vec<ExecutionError<S>>), RequestError<'static>>
+    where
+        S: ScalarValue,
+        QueryT: GraphQLType<S>,
+        MutationT: GraphQLType<S, Context = QueryT::Context>,
+        SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+    {
+        let started = Instant::now();
+        let document = self.to_document();
+        let limits = root_node.schema.limits();
+        let result = validate_document(
+            &document,
+            &root_node.schema,
+            root_node.allows_introspection(context),
+            limits,
+        )
+        .and_then(|()| prepare_operation(&document, None, &root_node.schema, variables, limits))
+        .and_then(|operation| {
+            execute_validated_query(&document, operation, root_node, variables, context, limits)
+        });
+        self.log(root_node, variables, context, started, &result);
+        result
+    }
+
+    /// Validates and executes the built operation in the provided schema, the
+    /// same way [`execute()`] does, logging it into the [`QueryLogger`] of the
+    /// schema as printed.
+    ///
+    /// As the document is built already, it's neither parsed nor looked up in
+    /// the [`DocumentCache`] of the schema.
+    ///
+    /// [`DocumentCache`]: crate::DocumentCache
+    /// [`execute()`]: crate::execute
+    /// [`QueryLogger`]: crate::QueryLogger
+    pub async fn execute<'a, QueryT, MutationT, SubscriptionT>(
+        &'a self,
+        root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+        variables: &Variables<S>,
+        context: &QueryT::Context,
+    ) -> Result<(Value<S>, Vec<ExecutionError<S>>), RequestError<'static>>
+    where
+        QueryT: GraphQLTypeAsync<S>,
+        QueryT::TypeInfo: Sync,
+        QueryT::Context: Sync,
+        MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+        MutationT::TypeInfo: Sync,
+        SubscriptionT: GraphQLType<S, Context = QueryT::Context> + Sync,
+        SubscriptionT::TypeInfo: Sync,
+        S: ScalarValue + Send + Sync,
+    {
+        let started = Instant::now();
+        let document = self.to_document();
+        let limits = root_node.schema.limits();
+        let prepared = validate_document(
+            &document,
+            &root_node.schema,
+            root_node.allows_introspection(context),
+            limits,
+        )
+        .and_then(|()| prepare_operation(&document, None, &root_node.schema, variables, limits));
+        let result = match prepared {
+            Ok(operation) => {
+                execute_validated_query_async(
+                    &document, operation, root_node, variables, context, limits,
+                )
+                .await
+            }
+            Err(e) => Err(e),
+        };
+        self.log(root_node, variables, context, started, &result);
+        result
+    }
+
+    /// Logs the execution of the built operation into the given `result`, if
+    /// the schema has a [`QueryLogger`].
+    ///
+    /// [`QueryLogger`]: crate::QueryLogger
+    fn log<QueryT, MutationT, SubscriptionT>(
+        &self,
+        root_node: &RootNode<QueryT, MutationT, SubscriptionT, S>,
+        variables: &Variables<S>,
+        context: &QueryT::Context,
+        started: Instant,
+        result: &Result<(Value<S>, Vec<ExecutionError<S>>), RequestError<'_>>,
+    ) where
+        S: ScalarValue,
+        QueryT: GraphQLType<S>,
+        MutationT: GraphQLType<S, Context = QueryT::Context>,
+        SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+    {
+        if root_node.query_logger.is_some() {
+            let query = self.to_string();
+            root_node.log_query(&query, self.name(), variables, context, started, result);
+        }
+    }
+}
+
+impl<S: ScalarValue> fmt::Display for OperationBuilder<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self.operation_type {
+            OperationType::Query => "query",
+            OperationType::Mutation => "mutation",
+            OperationType::Subscription => "subscription",
+        })?;
+        if let Some(name) = &self.name {
+            write!(f, " {}", name)?;
+        }
+        if !self.variables.is_empty() {
+            f.write_str("(")?;
+            for (i, (name, var_type, default)) in self.variables.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                write!(f, "${}: {}", name, var_type)?;
+                if let Some(default) = default {
+                    f.write_str(" = ")?;
+                    write_value(f, default)?;
+                }
+            }
+            f.write_str(")")?;
+        }
+        write!(f, " {}", self.selection_set)?;
+        for (name, on, selection_set) in &self.fragments {
+            write!(f, "\nfragment {} on {} {}", name, on, selection_set)?;
+        }
+        Ok(())
+    }
+}
+
+/// Builder of a selection set of an [`OperationBuilder`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct SelectionSetBuilder<S> {
+    selections: Vec<SelectionNode<S>>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum SelectionNode<S> {
+    Field {
+        alias: Option<String>,
+        name: String,
+        arguments: Vec<(String, InputValue<S>)>,
+        selection_set: SelectionSetBuilder<S>,
+    },
+    FragmentSpread(String),
+    InlineFragment {
+        type_condition: Option<String>,
+        selection_set: SelectionSetBuilder<S>,
+    },
+}
+
+impl<S> Default for SelectionSetBuilder<S> {
+    fn default() -> Self {
+        Self { selections: vec![] }
+    }
+}
+
+impl<S> SelectionSetBuilder<S> {
+    /// Selects the field with the given `name` and `arguments`, building its
+    /// sub-selection with the given `selection` function.
+    ///
+    /// The `arguments` are an [`InputValue::Object`] (like the one constructed
+    /// with the [`graphql_input_value!`] macro), or [`InputValue::Null`] if
+    /// the field has no arguments. An empty sub-selection selects a leaf
+    /// field.
+    ///
+    /// # Panics
+    ///
+    /// If the `arguments` are neither an object nor `null`.
+    ///
+    /// [`graphql_input_value!`]: crate::graphql_input_value
+    #[must_use]
+    pub fn field<F>(self, name: impl Into<String>, arguments: InputValue<S>, selection: F) -> Self
+    where
+        F: FnOnce(Self) -> Self,
+    {
+        self.push_field(None, name.into(), arguments, selection)
+    }
+
+    /// Selects the field with the given `name` and `arguments` under the
+    /// given `alias`.
+    ///
+    /// See [`SelectionSetBuilder::field()`] for details.
+    #[must_use]
+    pub fn aliased_field<F>(
+        self,
+        alias: impl Into<String>,
+        name: impl Into<String>,
+        arguments: InputValue<S>,
+        selection: F,
+    ) -> Self
+    where
+        F: FnOnce(Self) -> Self,
+    {
+        self.push_field(Some(alias.into()), name.into(), arguments, selection)
+    }
+
+    /// Selects the leaf field with the given `name` and no arguments.
+    #[must_use]
+    pub fn leaf(self, name: impl Into<String>) -> Self {
+        self.push_field(None, name.into(), InputValue::Null, |s| s)
+    }
+
+    /// Spreads the fragment with the given `name`.
+    #[must_use]
+    pub fn spread(mut self, name: impl Into<String>) -> Self {
+        self.selections
+            .push(SelectionNode::FragmentSpread(name.into()));
+        self
+    }
+
+    /// Selects an inline fragment on the given `type_condition` (if any),
+    /// building its selection set with the given `selection` function.
+    #[must_use]
+    pub fn inline_fragment<F>(mut self, type_condition: Option<&str>, selection: F) -> Self
+    where
+        F: FnOnce(Self) -> Self,
+    {
+        self.selections.push(SelectionNode::InlineFragment {
+            type_condition: type_condition.map(Into::into),
+            selection_set: selection(Self::default()),
+        });
+        self
+    }
+
+    fn push_field<F>(
+        mut self,
+        alias: Option<String>,
+        name: String,
+        arguments: InputValue<S>,
+        selection: F,
+    ) -> Self
+    where
+        F: FnOnce(Self) -> Self,
+    {
+        let arguments = match arguments {
+            InputValue::Null => vec![],
+            InputValue::Object(o) => o.into_iter().map(|(k, v)| (k.item, v.item)).collect(),
+            _ => panic!("arguments of field `{}` must be an object or `null`", name),
+        };
+        self.selections.push(SelectionNode::Field {
+            alias,
+            name,
+            arguments,
+            selection_set: selection(Self::default()),
+        });
+        self
+    }
+
+    fn to_selections(&self) -> Vec<Selection<'_, S>>
+    where
+        S: Clone,
+    {
+        self.selections
+            .iter()
+            .map(|s| match s {
+                SelectionNode::Field {
+                    alias,
+                    name,
+                    arguments,
+                    selection_set,
+                } => Selection::Field(Spanning::unlocated(Field {
+                    alias: alias.as_deref().map(Spanning::unlocated),
+                    name: Spanning::unlocated(name.as_str()),
+                    arguments: (!arguments.is_empty()).then(|| {
+                        Spanning::unlocated(Arguments {
+                            items: arguments
+                                .iter()
+                                .map(|(k, v)| {
+                                    (
+                                        Spanning::unlocated(k.as_str()),
+                                        Spanning::unlocated(v.clone()),
+                                    )
+                                })
+                                .collect(),
+                        })
+                    }),
+                    directives: None,
+                    selection_set: (!selection_set.selections.is_empty())
+                        .then(|| selection_set.to_selections()),
+                })),
+                SelectionNode::FragmentSpread(name) => {
+                    Selection::FragmentSpread(Spanning::unlocated(FragmentSpread {
+                        name: Spanning::unlocated(name.as_str()),
+                        directives: None,
+                    }))
+                }
+                SelectionNode::InlineFragment {
+                    type_condition,
+                    selection_set,
+                } => Selection::InlineFragment(Spanning::unlocated(InlineFragment {
+                    type_condition: type_condition.as_deref().map(Spanning::unlocated),
+                    directives: None,
+                    selection_set: selection_set.to_selections(),
+                })),
+            })
+            .collect()
+    }
+}
+
+impl<S: ScalarValue> fmt::Display for SelectionSetBuilder<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("{")?;
+        for s in &self.selections {
+            match s {
+                SelectionNode::Field {
+                    alias,
+                    name,
+                    arguments,
+                    selection_set,
+                } => {
+                    f.write_str(" ")?;
+                    if let Some(alias) = alias {
+                        write!(f, "{}: ", alias)?;
+                    }
+                    f.write_str(name)?;
+                    if !arguments.is_empty() {
+                        f.write_str("(")?;
+                        for (i, (k, v)) in arguments.iter().enumerate() {
+                            if i > 0 {
+                                f.write_str(", ")?;
+                            }
+                            write!(f, "{}: ", k)?;
+                            write_value(f, v)?;
+                        }
+                        f.write_str(")")?;
+                    }
+                    if !selection_set.selections.is_empty() {
+                        write!(f, " {}", selection_set)?;
+                    }
+                }
+                SelectionNode::FragmentSpread(name) => write!(f, " ...{}", name)?,
+                SelectionNode::InlineFragment {
+                    type_condition,
+                    selection_set,
+                } => {
+                    f.write_str(" ...")?;
+                    if let Some(on) = type_condition {
+                        write!(f, "on {} ", on)?;
+                    }
+                    write!(f, "{}", selection_set)?;
+                }
+            }
+        }
+        f.write_str(" }")
+    }
+}
+
+/// Parses the given GraphQL type literal (like `[ID!]!`), if it's valid.
+fn parse_type_literal(literal: &str) -> Option<Type<'static>> {
+    fn into_owned(t: Type<'_>) -> Type<'static> {
+        match t {
+            Type::Named(n) => Type::Named(Cow::Owned(n.into_owned())),
+            Type::NonNullNamed(n) => Type::NonNullNamed(Cow::Owned(n.into_owned())),
+            Type::List(t, size) => Type::List(Box::new(into_owned(*t)), size),
+            Type::NonNullList(t, size) => Type::NonNullList(Box::new(into_owned(*t)), size),
+        }
+    }
+
+    let mut lexer = Lexer::new(literal);
+    let mut parser = Parser::new(&mut lexer).ok()?;
+    let parsed = parse_type(&mut parser).ok()?;
+    (parser.peek().item == Token::EndOfFile).then(|| into_owned(parsed.item))
+}
+
+/// Writes the given [`InputValue`] as a GraphQL literal, escaping strings.
+fn write_value<S: ScalarValue>(f: &mut fmt::Formatter<'_>, v: &InputValue<S>) -> fmt::Result {
+    match v {
+        InputValue::Scalar(s) => match s.as_str() {
+            Some(s) => {
+                f.write_str("\"")?;
+                for c in s.chars() {
+                    match c {
+                        '"' => f.write_str("\\\"")?,
+                        '\\' => f.write_str("\\\\")?,
+                        '\n' => f.write_str("\\n")?,
+                        '\r' => f.write_str("\\r")?,
+                        '\t' => f.write_str("\\t")?,
+                        c if c.is_control() => write!(f, "\\u{:04X}", c as u32)?,
+                        c => write!(f, "{}", c)?,
+                    }
+                }
+                f.write_str("\"")
+            }
+            None => write!(f, "{}", v),
+        },
+        InputValue::List(l) => {
+            f.write_str("[")?;
+            for (i, v) in l.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                write_value(f, &v.item)?;
+            }
+            f.write_str("]")
+        }
+        InputValue::Object(o) => {
+            f.write_str("{")?;
+            for (i, (k, v)) in o.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                write!(f, "{}: ", k.item)?;
+                write_value(f, &v.item)?;
+            }
+            f.write_str("}")
+        }
+        InputValue::Null | InputValue::Enum(_) | InputValue::Variable(_) => write!(f, "{}", v),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        graphql_input_value, graphql_value, graphql_vars, tests::fixtures::starwars::schema::*,
+        DefaultScalarValue, EmptyMutation, EmptySubscription, RequestError, RootNode,
+    };
+
+    use super::OperationBuilder;
+
+    fn schema<'a>() -> RootNode<'a, Query, EmptyMutation<Database>, EmptySubscription<Database>> {
+        RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        )
+    }
+
+    #[test]
+    fn prints_escaped_arguments_and_variables() {
+        let op = OperationBuilder::<DefaultScalarValue>::mutation("M")
+            .variable("ids", "[ID!]!", Some(graphql_input_value!(["1"])))
+            .aliased_field(
+                "x",
+                "create",
+                graphql_input_value!({"name": "a \"b\"\n", "ids": @ids, "kind": DROID}),
+                |f| {
+                    f.leaf("id")
+                        .inline_fragment(Some("Droid"), |f| f.leaf("name"))
+                },
+            );
+
+        assert_eq!(
+            op.to_string(),
+            "mutation M($ids: [ID!]! = [\"1\"]) { x: create(name: \"a \\\"b\\\"\\n\", \
+             ids: $ids, kind: DROID) { id ...on Droid { name } } }",
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid GraphQL type literal `[ID!`")]
+    fn panics_on_invalid_type_literal() {
+        let _ = OperationBuilder::<DefaultScalarValue>::query("Q").variable("id", "[ID!", None);
+    }
+
+    #[test]
+    fn builds_valid_document() {
+        let op = OperationBuilder::<DefaultScalarValue>::query("Q")
+            .variable("id", "String!", None)
+            .field("human", graphql_input_value!({"id": @id}), |f| {
+                f.leaf("name").spread("Friends")
+            })
+            .fragment("Friends", "Human", |f| {
+                f.field("friends", graphql_input_value!(null), |f| f.leaf("name"))
+            });
+
+        let schema = schema();
+        let source = op.to_string();
+        assert!(crate::parse_document_source(&source, &schema.schema).is_ok());
+        assert_eq!(op.to_document().len(), 2);
+        assert!(op.validate(&schema.schema).is_empty());
+    }
+
+    #[test]
+    fn validates() {
+        let op = OperationBuilder::<DefaultScalarValue>::query("Q").field(
+            "human",
+            graphql_input_value!({"id": "1000"}),
+            |f| f.leaf("unknown"),
+        );
+
+        let errors = op.validate(&schema().schema);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].message().contains("unknown"));
+    }
+
+    #[test]
+    fn executes_sync() {
+        let op = OperationBuilder::query("Q")
+            .variable("id", "String!", None)
+            .field("human", graphql_input_value!({"id": @id}), |f| {
+                f.leaf("name")
+            });
+
+        assert_eq!(
+            op.execute_sync(&schema(), &graphql_vars! {"id": "1000"}, &Database::new()),
+            Ok((
+                graphql_value!({"human": {"name": "Luke Skywalker"}}),
+                vec![],
+            )),
+        );
+        assert!(matches!(
+            op.execute_sync(&schema(), &graphql_vars! {}, &Database::new()),
+            Err(RequestError::VariableCoercion(_)),
+        ));
+    }
+
+    #[tokio::test]
+    async fn executes_async() {
+        let op = OperationBuilder::anonymous(crate::OperationType::Query).field(
+            "hero",
+            graphql_input_value!(null),
+            |f| f.leaf("name"),
+        );
+        let schema = schema();
+
+        assert_eq!(
+            op.execute(&schema, &graphql_vars! {}, &Database::new())
+                .await,
+            Ok((graphql_value!({"hero": {"name": "R2-D2"}}), vec![])),
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_disabled_introspection() {
+        let op = OperationBuilder::query("Q").field("__schema", graphql_input_value!(null), |f| {
+            f.field("queryType", graphql_input_value!(null), |f| f.leaf("name"))
+        });
+        let schema = schema().disable_introspection();
+
+        assert!(matches!(
+            op.execute_sync(&schema, &graphql_vars! {}, &Database::new()),
+            Err(RequestError::Validation(errs))
+                if errs.len() == 1 && errs[0].rule() == Some("DisableIntrospection"),
+        ));
+        assert!(matches!(
+            op.execute(&schema, &graphql_vars! {}, &Database::new()).await,
+            Err(RequestError::Validation(errs))
+                if errs.len() == 1 && errs[0].rule() == Some("DisableIntrospection"),
+        ));
+    }
+}
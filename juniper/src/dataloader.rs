@@ -0,0 +1,723 @@
+//! Batching and per-request caching of the data loaded by resolvers.
+//!
+//! Resolving a list of objects often makes each of them load some related
+//! data on its own, issuing as many queries to the database as there are
+//! objects. A [`DataLoader`] collects the keys requested by all the resolvers
+//! during a single resolution "tick" of the asynchronous executor (until all
+//! of them are waiting for something), and loads them at once with a single
+//! call to its [`Loader`], caching the loaded values until the operation is
+//! executed.
+//!
+//! A [`DataLoader`] is obtained via [`Executor::data_loader()`], so it's
+//! created for each operation on the first access, and shared by all its
+//! resolvers.
+//!
+//! ```
+//! # use std::{
+//! #     collections::HashMap,
+//! #     sync::{
+//! #         atomic::{AtomicUsize, Ordering},
+//! #         Arc,
+//! #     },
+//! # };
+//! #
+//! # use juniper::{
+//! #     dataloader::Loader,
+//! #     execute,
+//! #     futures::future::{self, BoxFuture, FutureExt as _},
+//! #     graphql_object, graphql_value, graphql_vars, DefaultScalarValue, EmptyMutation,
+//! #     EmptySubscription, Executor, FieldError, FieldResult, RootNode,
+//! # };
+//! #
+//! /// Database, counting the queries to it.
+//! #[derive(Clone, Default)]
+//! struct Database(Arc<AtomicUsize>);
+//!
+//! impl juniper::Context for Database {}
+//!
+//! struct UsersById(Database);
+//!
+//! impl Loader<i32, User> for UsersById {
+//!     type Error = FieldError;
+//!
+//!     fn load<'a>(&'a self, ids: &'a [i32]) -> BoxFuture<'a, Result<HashMap<i32, User>, FieldError>> {
+//!         (self.0).0.fetch_add(1, Ordering::Relaxed);
+//!         let users = ids.iter().map(|&id| (id, User { id })).collect();
+//!         future::ready(Ok(users)).boxed()
+//!     }
+//! }
+//!
+//! #[derive(Clone)]
+//! struct User {
+//!     id: i32,
+//! }
+//!
+//! #[graphql_object(context = Database, scalar = DefaultScalarValue)]
+//! impl User {
+//!     fn id(&self) -> i32 {
+//!         self.id
+//!     }
+//!
+//!     async fn best_friend(&self, executor: &Executor<'_, '_, Database>) -> FieldResult<Option<User>> {
+//!         let users = executor.data_loader(|| UsersById(executor.context().clone()));
+//!         users.load(self.id % 3 + 1).await
+//!     }
+//! }
+//!
+//! struct Query;
+//!
+//! #[graphql_object(context = Database, scalar = DefaultScalarValue)]
+//! impl Query {
+//!     fn users() -> Vec<User> {
+//!         (1..=3).map(|id| User { id }).collect()
+//!     }
+//! }
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! let schema = RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new());
+//! let db = Database::default();
+//!
+//! let (res, _) = execute(
+//!     "{ users { bestFriend { id } } }",
+//!     None,
+//!     &schema,
+//!     &graphql_vars! {},
+//!     &db,
+//! )
+//! .await
+//! .unwrap();
+//! assert_eq!(
+//!     res,
+//!     graphql_value!({"users": [
+//!         {"bestFriend": {"id": 2}},
+//!         {"bestFriend": {"id": 3}},
+//!         {"bestFriend": {"id": 1}},
+//!     ]}),
+//! );
+//! // All the best friends are loaded at once.
+//! assert_eq!(db.0.load(Ordering::Relaxed), 1);
+//! # }
+//! ```
+//!
+//! # Dispatching
+//!
+//! The requested keys are loaded once the whole operation is waiting, so the
+//! keys requested by the resolvers awaiting anything else first (like the
+//! ones of the mutation fields resolved with the [`Serial`] strategy) may be
+//! loaded in separate batches.
+//!
+//! Outside the execution of queries and mutations via [`execute()`],
+//! [`execute_incremental()`] and [`execute_sync_parallel()`] (like in
+//! subscriptions), the keys are loaded as soon as they're requested, along
+//! with the ones requested concurrently, and are still cached.
+//!
+//! [`execute()`]: crate::execute
+//! [`execute_incremental()`]: crate::execute_incremental
+//! [`execute_sync_parallel()`]: crate::execute_sync_parallel
+//! [`Executor::data_loader()`]: crate::Executor::data_loader
+//! [`Serial`]: crate::Serial
+
+use std::{
+    collections::HashMap,
+    fmt,
+    future::Future,
+    hash::Hash,
+    mem,
+    sync::{Arc, Mutex, PoisonError},
+    task::Poll,
+};
+
+use futures::{
+    channel::oneshot,
+    future::{self, BoxFuture, FutureExt as _},
+    stream::{FuturesUnordered, StreamExt as _},
+};
+
+/// Batch loader of the values of type `V` by their keys of type `K`, used by
+/// a [`DataLoader`].
+///
+/// See the [module documentation](self) for an example.
+pub trait Loader<K, V>: Send + Sync + 'static {
+    /// Error of loading a batch of keys, reported to all the resolvers having
+    /// requested them.
+    type Error: Clone + Send + Sync + 'static;
+
+    /// Loads the values of the given `keys`, omitting the ones not found.
+    ///
+    /// The `keys` are never empty and never repeat, but their order is
+    /// unspecified.
+    fn load<'a>(&'a self, keys: &'a [K]) -> BoxFuture<'a, Result<HashMap<K, V>, Self::Error>>;
+}
+
+/// Loader of the values of type `V` by their keys of type `K`, batching the
+/// keys requested during a single resolution "tick" into a single call to its
+/// [`Loader`] and caching the loaded values for the whole operation.
+///
+/// Obtained via [`Executor::data_loader()`]. See the
+/// [module documentation](self) for details.
+///
+/// [`Executor::data_loader()`]: crate::Executor::data_loader
+pub struct DataLoader<K, V, L: Loader<K, V>> {
+    batches: Arc<Batches<K, V, L>>,
+    driven: bool,
+}
+
+impl<K, V, L> DataLoader<K, V, L>
+where
+    K: Clone + Eq + Hash + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    L: Loader<K, V>,
+{
+    /// Creates a new [`DataLoader`] loading its values with the given
+    /// `loader`, and dispatching its batches with the given [`Dispatcher`], if
+    /// any, or immediately otherwise.
+    pub(crate) fn new(loader: L, dispatcher: Option<&Dispatcher>) -> Self {
+        let batches = Arc::new(Batches {
+            loader,
+            queue: Mutex::new(Queue::default()),
+        });
+        if let Some(dispatcher) = dispatcher {
+            dispatcher.register(Arc::clone(&batches) as Arc<dyn Dispatch>);
+        }
+        Self {
+            batches,
+            driven: dispatcher.is_some(),
+        }
+    }
+
+    /// Loads the value of the given `key`, along with the keys requested by
+    /// the other resolvers, unless it's been loaded already.
+    ///
+    /// Returns [`None`] if the [`Loader`] hasn't found the `key`.
+    pub async fn load(&self, key: K) -> Result<Option<V>, L::Error> {
+        let request = self.request(&key);
+        self.dispatch_unless_driven().await;
+        self.wait(key, request).await
+    }
+
+    /// Loads the values of the given `keys` in a single batch, along with the
+    /// keys requested by the other resolvers, unless they've been loaded
+    /// already.
+    ///
+    /// Omits the keys the [`Loader`] hasn't found.
+    pub async fn load_many(
+        &self,
+        keys: impl IntoIterator<Item = K>,
+    ) -> Result<HashMap<K, V>, L::Error> {
+        let requests = keys
+            .into_iter()
+            .map(|key| {
+                let request = self.request(&key);
+                (key, request)
+            })
+            .collect::<Vec<_>>();
+        self.dispatch_unless_driven().await;
+
+        let values = future::try_join_all(requests.into_iter().map(|(key, request)| async move {
+            let value = self.wait(key.clone(), request).await?;
+            Ok::<_, L::Error>(value.map(|v| (key, v)))
+        }))
+        .await?;
+        Ok(values.into_iter().flatten().collect())
+    }
+
+    /// Requests the value of the given `key`, unless it's been loaded
+    /// already.
+    fn request(&self, key: &K) -> Request<V, L::Error> {
+        let mut queue = self.batches.queue.lock().unwrap();
+        if let Some(value) = queue.cache.get(key) {
+            return Request::Cached(value.clone());
+        }
+        let (tx, rx) = oneshot::channel();
+        match queue.loading.get_mut(key) {
+            Some(waiting) => waiting.push(tx),
+            None => queue.pending.entry(key.clone()).or_default().push(tx),
+        }
+        Request::Waiting(rx)
+    }
+
+    /// Loads the pending keys right away, unless they're loaded at the end of
+    /// the resolution tick.
+    async fn dispatch_unless_driven(&self) {
+        if !self.driven {
+            if let Some(batch) = Arc::clone(&self.batches).dispatch() {
+                batch.await;
+            }
+        }
+    }
+
+    /// Waits for the value of the requested `key` to be loaded.
+    async fn wait(&self, key: K, mut request: Request<V, L::Error>) -> Result<Option<V>, L::Error> {
+        loop {
+            match request {
+                Request::Cached(value) => return Ok(value),
+                Request::Waiting(loaded) => {
+                    if let Ok(result) = loaded.await {
+                        return result;
+                    }
+                }
+            }
+            // The batch may be dropped before being loaded only if it's been
+            // dispatched by another dropped `load()`, so it's requested again.
+            request = self.request(&key);
+            self.dispatch_unless_driven().await;
+        }
+    }
+}
+
+impl<K, V, L: Loader<K, V>> fmt::Debug for DataLoader<K, V, L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DataLoader")
+            .field("driven", &self.driven)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Sender of the loaded value of a single requested key.
+type Sender<V, E> = oneshot::Sender<Result<Option<V>, E>>;
+
+/// Requested value of a single key.
+enum Request<V, E> {
+    /// Value loaded already, with [`None`] if the key is not found.
+    Cached(Option<V>),
+
+    /// Receiver of the value being loaded.
+    Waiting(oneshot::Receiver<Result<Option<V>, E>>),
+}
+
+/// Keys of a [`DataLoader`] being batched, along with its cache.
+struct Batches<K, V, L: Loader<K, V>> {
+    loader: L,
+    queue: Mutex<Queue<K, V, L::Error>>,
+}
+
+/// Keys requested from a [`DataLoader`] and the values loaded already.
+struct Queue<K, V, E> {
+    /// Values loaded already, with [`None`] for the keys not found.
+    cache: HashMap<K, Option<V>>,
+
+    /// Keys requested since the last dispatched batch, along with the
+    /// resolvers waiting for them.
+    pending: HashMap<K, Vec<Sender<V, E>>>,
+
+    /// Keys of the dispatched batches being loaded, along with the resolvers
+    /// waiting for them.
+    loading: HashMap<K, Vec<Sender<V, E>>>,
+}
+
+impl<K, V, E> Default for Queue<K, V, E> {
+    fn default() -> Self {
+        Self {
+            cache: HashMap::new(),
+            pending: HashMap::new(),
+            loading: HashMap::new(),
+        }
+    }
+}
+
+/// Type-erased [`Batches`] of a [`DataLoader`], dispatched by a
+/// [`Dispatcher`].
+pub(crate) trait Dispatch: Send + Sync {
+    /// Dispatches a batch of all the pending keys, if any, returning the
+    /// [`Future`] loading it.
+    fn dispatch(self: Arc<Self>) -> Option<BoxFuture<'static, ()>>;
+}
+
+impl<K, V, L> Dispatch for Batches<K, V, L>
+where
+    K: Clone + Eq + Hash + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    L: Loader<K, V>,
+{
+    fn dispatch(self: Arc<Self>) -> Option<BoxFuture<'static, ()>> {
+        let keys = {
+            let mut queue = self.queue.lock().unwrap();
+            if queue.pending.is_empty() {
+                return None;
+            }
+            let pending = mem::take(&mut queue.pending);
+            let keys = pending.keys().cloned().collect::<Vec<_>>();
+            queue.loading.extend(pending);
+            keys
+        };
+
+        Some(
+            async move {
+                let mut batch = Loading {
+                    batches: self,
+                    keys,
+                };
+                let mut result = batch.batches.loader.load(&batch.keys).await;
+
+                let keys = mem::take(&mut batch.keys);
+                let mut queue = batch.batches.queue.lock().unwrap();
+                for key in keys {
+                    let waiting = queue.loading.remove(&key).unwrap_or_default();
+                    let loaded = match &mut result {
+                        Ok(values) => {
+                            let value = values.remove(&key);
+                            queue.cache.insert(key, value.clone());
+                            Ok(value)
+                        }
+                        Err(e) => Err(e.clone()),
+                    };
+                    for tx in waiting {
+                        // The resolver may be gone already.
+                        let _ = tx.send(loaded.clone());
+                    }
+                }
+            }
+            .boxed(),
+        )
+    }
+}
+
+/// Keys of a dispatched batch being loaded.
+///
+/// Once dropped before the batch is loaded (along with the [`Future`] loading
+/// it), abandons its keys, so the resolvers waiting for them request them
+/// again, instead of waiting forever.
+struct Loading<K: Eq + Hash, V, L: Loader<K, V>> {
+    batches: Arc<Batches<K, V, L>>,
+    keys: Vec<K>,
+}
+
+impl<K: Eq + Hash, V, L: Loader<K, V>> Drop for Loading<K, V, L> {
+    fn drop(&mut self) {
+        if self.keys.is_empty() {
+            return;
+        }
+        let mut queue = self
+            .batches
+            .queue
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        for key in &self.keys {
+            // Drops the senders, waking up the waiting resolvers.
+            queue.loading.remove(key);
+        }
+    }
+}
+
+/// Dispatcher of the batches of all the [`DataLoader`]s of an operation at
+/// the end of each its resolution "tick".
+///
+/// Stored in the [`RequestState`] of the operations driven by
+/// [`Dispatcher::drive()`] only, so the [`DataLoader`]s of the other ones load
+/// their keys immediately.
+///
+/// [`RequestState`]: crate::executor::RequestState
+#[derive(Default)]
+pub(crate) struct Dispatcher(Mutex<Vec<Arc<dyn Dispatch>>>);
+
+impl Dispatcher {
+    /// Registers the [`Batches`] of a new [`DataLoader`] to be dispatched.
+    fn register(&self, batches: Arc<dyn Dispatch>) {
+        self.0.lock().unwrap().push(batches);
+    }
+
+    /// Dispatches the pending batches of all the registered [`DataLoader`]s,
+    /// returning the [`Future`]s loading them.
+    fn dispatch(&self) -> Vec<BoxFuture<'static, ()>> {
+        let all = self.0.lock().unwrap().clone();
+        all.into_iter().filter_map(Dispatch::dispatch).collect()
+    }
+
+    /// Drives the given `operation` to completion, dispatching the pending
+    /// batches of its [`DataLoader`]s each time it's waiting.
+    pub(crate) async fn drive<F: Future>(&self, operation: F) -> F::Output {
+        futures::pin_mut!(operation);
+        let mut loading = FuturesUnordered::new();
+
+        future::poll_fn(|cx| loop {
+            if let Poll::Ready(output) = operation.as_mut().poll(cx) {
+                return Poll::Ready(output);
+            }
+
+            // All the resolvers are waiting now, so the resolution tick is
+            // over, and the keys requested during it are loaded.
+            loading.extend(self.dispatch());
+            let mut loaded = false;
+            while let Poll::Ready(Some(())) = loading.poll_next_unpin(cx) {
+                loaded = true;
+            }
+            if !loaded {
+                return Poll::Pending;
+            }
+        })
+        .await
+    }
+}
+
+impl fmt::Debug for Dispatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Dispatcher").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, Mutex,
+        },
+        time::Duration,
+    };
+
+    use futures::future::{self, BoxFuture, FutureExt as _};
+    use tokio::time;
+
+    use crate::{
+        execute, graphql_object, graphql_value, graphql_vars, DefaultScalarValue, EmptyMutation,
+        EmptySubscription, Executor, FieldError, FieldResult, RootNode,
+    };
+
+    use super::{DataLoader, Loader};
+
+    /// Database, recording the batches of the loaded keys.
+    #[derive(Clone, Default)]
+    struct Database(Arc<Mutex<Vec<Vec<i32>>>>);
+
+    impl crate::Context for Database {}
+
+    impl Database {
+        fn batches(&self) -> Vec<Vec<i32>> {
+            self.0.lock().unwrap().clone()
+        }
+    }
+
+    struct UsersById(Database);
+
+    impl Loader<i32, User> for UsersById {
+        type Error = FieldError;
+
+        fn load<'a>(
+            &'a self,
+            ids: &'a [i32],
+        ) -> BoxFuture<'a, Result<HashMap<i32, User>, FieldError>> {
+            let mut batch = ids.to_vec();
+            batch.sort_unstable();
+            (self.0).0.lock().unwrap().push(batch);
+
+            let users = if ids.contains(&13) {
+                Err(FieldError::from("Database is unavailable"))
+            } else {
+                Ok(ids
+                    .iter()
+                    .filter(|&&id| (1..=3).contains(&id))
+                    .map(|&id| (id, User { id }))
+                    .collect())
+            };
+            future::ready(users).boxed()
+        }
+    }
+
+    fn users<'e>(executor: &'e Executor<'_, '_, Database>) -> &'e DataLoader<i32, User, UsersById> {
+        executor.data_loader(|| UsersById(executor.context().clone()))
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct User {
+        id: i32,
+    }
+
+    #[graphql_object(context = Database, scalar = DefaultScalarValue)]
+    impl User {
+        fn id(&self) -> i32 {
+            self.id
+        }
+
+        async fn friend(&self, executor: &Executor<'_, '_, Database>) -> FieldResult<Option<User>> {
+            users(executor).load(self.id % 3 + 1).await
+        }
+    }
+
+    struct Query;
+
+    #[graphql_object(context = Database, scalar = DefaultScalarValue)]
+    impl Query {
+        fn users() -> Vec<User> {
+            (1..=3).map(|id| User { id }).collect()
+        }
+
+        async fn user(id: i32, executor: &Executor<'_, '_, Database>) -> FieldResult<Option<User>> {
+            users(executor).load(id).await
+        }
+    }
+
+    type Schema = RootNode<'static, Query, EmptyMutation<Database>, EmptySubscription<Database>>;
+
+    fn schema() -> Schema {
+        Schema::new(Query, EmptyMutation::new(), EmptySubscription::new())
+    }
+
+    #[tokio::test]
+    async fn batches_keys_requested_during_tick() {
+        let db = Database::default();
+        let (res, errs) = execute(
+            "{ users { friend { id } } first: user(id: 1) { id } }",
+            None,
+            &schema(),
+            &graphql_vars! {},
+            &db,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(errs, []);
+        assert_eq!(
+            res,
+            graphql_value!({
+                "users": [
+                    {"friend": {"id": 2}},
+                    {"friend": {"id": 3}},
+                    {"friend": {"id": 1}},
+                ],
+                "first": {"id": 1},
+            }),
+        );
+        assert_eq!(db.batches(), [[1, 2, 3]]);
+    }
+
+    #[tokio::test]
+    async fn caches_loaded_values_per_operation() {
+        let db = Database::default();
+        let schema = schema();
+        let query = "{ user(id: 1) { friend { friend { friend { id } } } } }";
+
+        let (res, errs) = execute(query, None, &schema, &graphql_vars! {}, &db)
+            .await
+            .unwrap();
+        assert_eq!(errs, []);
+        assert_eq!(
+            res,
+            graphql_value!({"user": {"friend": {"friend": {"friend": {"id": 1}}}}}),
+        );
+        assert_eq!(db.batches(), [[1], [2], [3]]);
+
+        execute(query, None, &schema, &graphql_vars! {}, &db)
+            .await
+            .unwrap();
+        assert_eq!(db.batches(), [[1], [2], [3], [1], [2], [3]]);
+    }
+
+    #[tokio::test]
+    async fn omits_keys_not_found() {
+        let db = Database::default();
+        let (res, errs) = execute(
+            "{ user(id: 42) { id } }",
+            None,
+            &schema(),
+            &graphql_vars! {},
+            &db,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(errs, []);
+        assert_eq!(res, graphql_value!({"user": null}));
+    }
+
+    #[tokio::test]
+    async fn reports_batch_error_to_all_requesters() {
+        let db = Database::default();
+        let (res, errs) = execute(
+            "{ a: user(id: 1) { id } b: user(id: 13) { id } }",
+            None,
+            &schema(),
+            &graphql_vars! {},
+            &db,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(res, graphql_value!({"a": null, "b": null}));
+        assert_eq!(
+            errs.iter().map(|e| e.error().message()).collect::<Vec<_>>(),
+            ["Database is unavailable", "Database is unavailable"],
+        );
+        assert_eq!(db.batches(), [[1, 13]]);
+    }
+
+    #[tokio::test]
+    async fn loads_immediately_unless_driven() {
+        let db = Database::default();
+        let users = DataLoader::new(UsersById(db.clone()), None);
+
+        assert_eq!(users.load(1).await, Ok(Some(User { id: 1 })));
+        assert_eq!(users.load(1).await, Ok(Some(User { id: 1 })));
+        assert_eq!(
+            users.load_many(vec![2, 3, 42]).await,
+            Ok(vec![(2, User { id: 2 }), (3, User { id: 3 })]
+                .into_iter()
+                .collect()),
+        );
+        assert_eq!(db.batches(), [vec![1], vec![2, 3, 42]]);
+    }
+
+    #[tokio::test]
+    async fn requests_keys_again_once_dispatching_load_is_dropped() {
+        /// Loader never loading its first batch.
+        struct StallingFirst(UsersById, AtomicBool);
+
+        impl Loader<i32, User> for StallingFirst {
+            type Error = FieldError;
+
+            fn load<'a>(
+                &'a self,
+                ids: &'a [i32],
+            ) -> BoxFuture<'a, Result<HashMap<i32, User>, FieldError>> {
+                let users = self.0.load(ids);
+                if self.1.swap(false, Ordering::SeqCst) {
+                    future::pending().boxed()
+                } else {
+                    users
+                }
+            }
+        }
+
+        let db = Database::default();
+        let users = DataLoader::new(StallingFirst(UsersById(db.clone()), true.into()), None);
+
+        let mut dispatching = Box::pin(users.load(1));
+        assert!((&mut dispatching).now_or_never().is_none());
+        let mut waiting = Box::pin(users.load(1));
+        assert!((&mut waiting).now_or_never().is_none());
+        drop(dispatching);
+
+        let loaded = time::timeout(Duration::from_secs(5), waiting).await;
+        assert_eq!(loaded, Ok(Ok(Some(User { id: 1 }))));
+        let loaded = time::timeout(Duration::from_secs(5), users.load(1)).await;
+        assert_eq!(loaded, Ok(Ok(Some(User { id: 1 }))));
+        assert_eq!(db.batches(), [[1], [1]]);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn batches_keys_in_parallel_sync_execution() {
+        let db = Database::default();
+        let schema = schema();
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .unwrap();
+        let (res, errs) = pool
+            .install(|| {
+                crate::execute_sync_parallel(
+                    "{ a: user(id: 1) { id } b: user(id: 2) { id } }",
+                    None,
+                    &schema,
+                    &graphql_vars! {},
+                    &db,
+                )
+            })
+            .unwrap();
+
+        assert_eq!(errs, []);
+        assert_eq!(res, graphql_value!({"a": {"id": 1}, "b": {"id": 2}}));
+        assert_eq!(db.batches(), [[1, 2]]);
+    }
+}
@@ -0,0 +1,281 @@
+use std::{
+    fmt,
+    mem::ManuallyDrop,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    ast::{Document, OwnedDocument},
+    executor::Limits,
+    parser::parse_document_source,
+    schema::model::SchemaType,
+    util::LruMap,
+    validation::{visit_all_rules, visit_disabled_introspection, RuleError, ValidatorContext},
+    value::ScalarValue,
+};
+
+/// Cache of the documents parsed and validated against a schema, so the
+/// repeatedly executed queries skip the parsing and validation entirely.
+///
+/// Documents are keyed by their source text, or by the hex-encoded SHA-256
+/// hash of it for the [automatic persisted queries][1] executed via
+/// [`GraphQLRequest`] (with the `persisted-queries` Cargo feature), so these
+/// skip even hashing their text. Both kinds of keys never clash, as a hash
+/// alone is never a valid document.
+///
+/// Set with [`RootNode::with_document_cache()`] and consulted by
+/// [`execute_sync()`] and [`execute()`]. A cache may be shared by multiple
+/// schemas, but a document is used only by the schema it has been validated
+/// against, being a miss for the others. Only valid documents are cached, so
+/// the invalid ones are still reported with their errors. The [`Limits`] and
+/// the variables of a request are checked on every execution. Its hits and
+/// misses are recorded via [`MetricsSink::record_document_cache()`].
+///
+/// The validation of a cached document is not repeated on its hits, neither
+/// by the built-in rules nor by the custom ones (registered via
+/// [`RootNode::with_validation_rule()`]), so the custom rules should only
+/// depend on the document and the schema. The metrics of the validation
+/// (like the [timings of the rules][2]) are recorded on misses only.
+///
+/// ```
+/// # use juniper::{
+/// #     execute_sync, graphql_object, graphql_vars, DocumentCache as _, EmptyMutation,
+/// #     EmptySubscription, LruDocumentCache, RootNode,
+/// # };
+/// # use std::sync::Arc;
+/// #
+/// struct Query;
+///
+/// #[graphql_object]
+/// impl Query {
+///     fn answer() -> i32 {
+///         42
+///     }
+/// }
+///
+/// let cache = Arc::new(LruDocumentCache::new(1000));
+/// let schema = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::<()>::new())
+///     .with_document_cache(Arc::clone(&cache));
+///
+/// for _ in 0..3 {
+///     execute_sync("{ answer }", None, &schema, &graphql_vars! {}, &()).unwrap();
+/// }
+/// assert!(cache.get("{ answer }").is_some());
+/// ```
+///
+/// [1]: https://www.apollographql.com/docs/apollo-server/performance/apq
+/// [2]: crate::ExecutorOptions::time_validation_rules
+/// [`execute()`]: crate::execute
+/// [`execute_sync()`]: crate::execute_sync
+/// [`GraphQLRequest`]: crate::http::GraphQLRequest
+/// [`MetricsSink::record_document_cache()`]: super::MetricsSink::record_document_cache
+/// [`RootNode::with_document_cache()`]: crate::RootNode::with_document_cache
+/// [`RootNode::with_validation_rule()`]: crate::RootNode::with_validation_rule
+pub trait DocumentCache<S>: fmt::Debug + Send + Sync {
+    /// Returns the cached document with the given `key`, if any.
+    fn get(&self, key: &str) -> Option<Arc<CachedDocument<S>>>;
+
+    /// Caches the given `document` with the given `key`.
+    fn insert(&self, key: Arc<str>, document: Arc<CachedDocument<S>>);
+}
+
+impl<S, T: DocumentCache<S> + ?Sized> DocumentCache<S> for Arc<T> {
+    fn get(&self, key: &str) -> Option<Arc<CachedDocument<S>>> {
+        (**self).get(key)
+    }
+
+    fn insert(&self, key: Arc<str>, document: Arc<CachedDocument<S>>) {
+        (**self).insert(key, document)
+    }
+}
+
+/// In-memory [`DocumentCache`], evicting the least recently used documents
+/// once its capacity is reached.
+pub struct LruDocumentCache<S>(Mutex<LruMap<Arc<CachedDocument<S>>>>);
+
+impl<S> fmt::Debug for LruDocumentCache<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LruDocumentCache")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl<S> LruDocumentCache<S> {
+    /// Creates a new [`LruDocumentCache`] holding at most `capacity`
+    /// documents.
+    pub fn new(capacity: usize) -> Self {
+        Self(Mutex::new(LruMap::new(capacity)))
+    }
+
+    /// Returns the number of the currently cached documents.
+    pub fn len(&self) -> usize {
+        self.0.lock().unwrap().len()
+    }
+
+    /// Indicates whether no documents are currently cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<S: Send + Sync> DocumentCache<S> for LruDocumentCache<S> {
+    fn get(&self, key: &str) -> Option<Arc<CachedDocument<S>>> {
+        self.0.lock().unwrap().get(key)
+    }
+
+    fn insert(&self, key: Arc<str>, document: Arc<CachedDocument<S>>) {
+        self.0.lock().unwrap().insert(key, document)
+    }
+}
+
+/// Document parsed and validated against a schema, along with its source
+/// text, stored in a [`DocumentCache`].
+pub struct CachedDocument<S> {
+    /// Parsed document, borrowing from the `source` text.
+    ///
+    /// Dropped manually, before the `source` (see the [`Drop`] implementation),
+    /// regardless of the order of the fields.
+    document: ManuallyDrop<OwnedDocument<'static, S>>,
+
+    /// Errors of selecting the introspection fields in the `document`, being
+    /// reported for the requests not allowed to introspect the schema.
+    introspection_errors: Vec<RuleError>,
+
+    /// ID of the schema the `document` has been validated against.
+    schema_id: usize,
+
+    /// Source text of the `document`.
+    source: Arc<str>,
+}
+
+impl<S> Drop for CachedDocument<S> {
+    fn drop(&mut self) {
+        // SAFETY: The `document` is never used after being dropped here, and
+        //         is dropped while the `source` it borrows from is still
+        //         alive, as the fields are dropped only after this method.
+        unsafe { ManuallyDrop::drop(&mut self.document) }
+    }
+}
+
+impl<S> fmt::Debug for CachedDocument<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CachedDocument")
+            .field("source", &self.source)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S> CachedDocument<S> {
+    /// Returns the source text of this [`CachedDocument`].
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns the shared source text of this [`CachedDocument`], to key it
+    /// with in a [`DocumentCache`].
+    pub(crate) fn shared_source(&self) -> Arc<str> {
+        Arc::clone(&self.source)
+    }
+
+    /// Returns the parsed document, borrowing from this [`CachedDocument`].
+    pub(crate) fn document(&self) -> &Document<'_, S> {
+        &self.document
+    }
+
+    /// Returns the errors of selecting the introspection fields in this
+    /// document, reported for the requests not allowed to introspect the
+    /// schema.
+    pub(crate) fn introspection_errors(&self) -> &[RuleError] {
+        &self.introspection_errors
+    }
+
+    /// Indicates whether this document has been validated against the given
+    /// `schema`, so may be executed against it.
+    pub(crate) fn is_validated_against(&self, schema: &SchemaType<S>) -> bool {
+        self.schema_id == schema.id
+    }
+}
+
+impl<S: ScalarValue> CachedDocument<S> {
+    /// Parses and validates the given `source` text against the `schema`.
+    ///
+    /// The document is validated regardless of any [`Limits`], as these may
+    /// differ per request, so are checked on every execution instead.
+    ///
+    /// Returns [`None`] if the document is not valid, so it should be parsed
+    /// and validated the usual way, reporting its errors.
+    pub(crate) fn compile(source: &str, schema: &SchemaType<S>) -> Option<Self> {
+        let source = Arc::<str>::from(source);
+        // SAFETY: The text of an `Arc<str>` is never moved nor mutated, and
+        //         lives as long as the `source` is stored along with the
+        //         `document` borrowing from it (which is dropped first, by the
+        //         `Drop` implementation of `CachedDocument`). The `'static`
+        //         lifetime never leaks out, as the `document` is only lent for
+        //         the lifetime of its `CachedDocument`.
+        let text = unsafe { &*(&*source as *const str) };
+        let document = parse_document_source(text, schema).ok()?;
+
+        let mut ctx = ValidatorContext::new(schema, &document);
+        ctx.set_limits(&Limits::new());
+        visit_all_rules(&mut ctx, &document);
+        if ctx.has_errors() {
+            return None;
+        }
+        ctx.record_metrics();
+
+        let mut ctx = ValidatorContext::new(schema, &document);
+        visit_disabled_introspection(&mut ctx, &document);
+        let introspection_errors = ctx.into_errors();
+
+        Some(Self {
+            document: ManuallyDrop::new(document),
+            introspection_errors,
+            schema_id: schema.id,
+            source,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{schema::model::SchemaType, DefaultScalarValue, EmptyMutation, EmptySubscription};
+
+    use super::CachedDocument;
+
+    struct Query;
+
+    #[crate::graphql_object]
+    impl Query {
+        fn answer() -> i32 {
+            42
+        }
+    }
+
+    fn schema() -> SchemaType<'static, DefaultScalarValue> {
+        SchemaType::new::<Query, EmptyMutation, EmptySubscription>(&(), &(), &())
+    }
+
+    #[test]
+    fn compiles_valid_documents_only() {
+        let schema = schema();
+
+        let cached = CachedDocument::compile("{ answer }", &schema).unwrap();
+        assert_eq!(cached.source(), "{ answer }");
+        assert_eq!(cached.document().len(), 1);
+        assert!(cached.introspection_errors().is_empty());
+        assert!(cached.is_validated_against(&schema));
+        assert!(!cached.is_validated_against(&self::schema()));
+
+        assert!(CachedDocument::compile("{ answer", &schema).is_none());
+        assert!(CachedDocument::compile("{ unknown }", &schema).is_none());
+    }
+
+    #[test]
+    fn remembers_introspection_errors() {
+        let cached =
+            CachedDocument::compile("{ __schema { queryType { name } } }", &schema()).unwrap();
+
+        assert_eq!(cached.introspection_errors().len(), 1);
+    }
+}
@@ -0,0 +1,190 @@
+//! Resolution of entities by their representations, for entity-fetch style
+//! execution.
+
+use std::{collections::HashMap, fmt, sync::Arc};
+
+use futures::future::{BoxFuture, FutureExt as _};
+
+use crate::{
+    ast::{Document, Fragment, InputValue},
+    parser::Spanning,
+    schema::model::SchemaType,
+    types::{
+        async_await::GraphQLTypeAsync,
+        base::{GraphQLType, GraphQLValue},
+    },
+    value::{DefaultScalarValue, ScalarValue, Value},
+    RequestError,
+};
+
+use super::{fragment::execute_validated_fragment_async, ExecutionError, FieldResult, Variables};
+
+/// Resolver of the entities of a GraphQL object type by their representations
+/// (like `{"id": 1}`), used to execute selections on individual entities via
+/// [`execute_entity()`].
+///
+/// Registered with [`RootNode::with_entity_resolver()`].
+///
+/// ```
+/// # use juniper::{
+/// #     futures::future::{self, BoxFuture, FutureExt as _},
+/// #     graphql_object, EntityResolver, FieldResult, InputValue,
+/// # };
+/// #
+/// struct Database;
+///
+/// impl juniper::Context for Database {}
+///
+/// struct User {
+///     id: i32,
+/// }
+///
+/// #[graphql_object(context = Database)]
+/// impl User {
+///     fn id(&self) -> i32 {
+///         self.id
+///     }
+/// }
+///
+/// struct UserById;
+///
+/// impl EntityResolver for UserById {
+///     type Entity = User;
+///
+///     fn resolve_entity<'a>(
+///         &'a self,
+///         representation: &'a InputValue,
+///         _: &'a Database,
+///     ) -> BoxFuture<'a, FieldResult<Option<User>>> {
+///         let id = representation.get_path("id").and_then(InputValue::as_int_value);
+///         future::ready(Ok(id.map(|id| User { id }))).boxed()
+///     }
+/// }
+/// ```
+///
+/// [`execute_entity()`]: crate::execute_entity
+/// [`RootNode::with_entity_resolver()`]: crate::RootNode::with_entity_resolver
+pub trait EntityResolver<S = DefaultScalarValue>: Send + Sync
+where
+    S: ScalarValue + Send + Sync,
+{
+    /// Type of the resolved entities.
+    ///
+    /// Should implement [`GraphQLTypeAsync`] to be registered in a
+    /// [`RootNode`].
+    ///
+    /// [`RootNode`]: crate::RootNode
+    type Entity: GraphQLType<S, TypeInfo = ()> + Send;
+
+    /// Resolves the entity with the given `representation`, returning [`None`]
+    /// if there is no such entity.
+    fn resolve_entity<'a>(
+        &'a self,
+        representation: &'a InputValue<S>,
+        context: &'a <Self::Entity as GraphQLValue<S>>::Context,
+    ) -> BoxFuture<'a, FieldResult<Option<Self::Entity>, S>>;
+}
+
+/// Result of executing a selection on an entity.
+type EntityResult<'e, S> = Result<(Value<S>, Vec<ExecutionError<S>>), RequestError<'e>>;
+
+/// [`EntityResolver`] with its entity type erased, executing the selections
+/// on the resolved entities directly.
+pub(crate) trait DynEntityResolver<CtxT, S>: Send + Sync {
+    /// Resolves the entity with the given `representation`, and executes the
+    /// selection set of the `fragment` on it.
+    fn execute<'a, 'e: 'a>(
+        &'a self,
+        document: &'a Document<'a, S>,
+        fragment: &'a Spanning<Fragment<'a, S>>,
+        schema: &'a SchemaType<'a, S>,
+        representation: &'a InputValue<S>,
+        variables: &'a Variables<S>,
+        context: &'a CtxT,
+    ) -> BoxFuture<'a, EntityResult<'e, S>>;
+}
+
+impl<R, S> DynEntityResolver<<R::Entity as GraphQLValue<S>>::Context, S> for R
+where
+    R: EntityResolver<S>,
+    R::Entity: GraphQLTypeAsync<S>,
+    <R::Entity as GraphQLValue<S>>::Context: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    fn execute<'a, 'e: 'a>(
+        &'a self,
+        document: &'a Document<'a, S>,
+        fragment: &'a Spanning<Fragment<'a, S>>,
+        schema: &'a SchemaType<'a, S>,
+        representation: &'a InputValue<S>,
+        variables: &'a Variables<S>,
+        context: &'a <R::Entity as GraphQLValue<S>>::Context,
+    ) -> BoxFuture<'a, EntityResult<'e, S>> {
+        async move {
+            match self.resolve_entity(representation, context).await {
+                Ok(Some(entity)) => {
+                    execute_validated_fragment_async(
+                        document,
+                        fragment,
+                        schema,
+                        &entity,
+                        &(),
+                        variables,
+                        context,
+                    )
+                    .await
+                }
+                Ok(None) => Ok((Value::null(), vec![])),
+                Err(e) => Ok((Value::null(), vec![ExecutionError::at_origin(e)])),
+            }
+        }
+        .boxed()
+    }
+}
+
+/// [`EntityResolver`]s registered in a [`RootNode`], by the names of the
+/// GraphQL object types of their entities.
+///
+/// [`RootNode`]: crate::RootNode
+pub(crate) struct EntityResolvers<CtxT, S> {
+    resolvers: HashMap<String, Arc<dyn DynEntityResolver<CtxT, S>>>,
+}
+
+impl<CtxT, S> Default for EntityResolvers<CtxT, S> {
+    fn default() -> Self {
+        Self {
+            resolvers: HashMap::new(),
+        }
+    }
+}
+
+impl<CtxT, S> fmt::Debug for EntityResolvers<CtxT, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.resolvers.keys()).finish()
+    }
+}
+
+impl<CtxT, S> EntityResolvers<CtxT, S>
+where
+    S: ScalarValue + Send + Sync,
+{
+    /// Registers the given `resolver` of the entities of the GraphQL object
+    /// type `R::Entity`, replacing the previously registered one, if any.
+    pub(crate) fn register<R>(&mut self, resolver: R)
+    where
+        R: EntityResolver<S> + 'static,
+        R::Entity: GraphQLTypeAsync<S, Context = CtxT>,
+        CtxT: Sync,
+    {
+        let name = R::Entity::name(&())
+            .expect("entities should be of named GraphQL object types")
+            .to_owned();
+        self.resolvers.insert(name, Arc::new(resolver));
+    }
+
+    /// Returns the resolver of the entities of the GraphQL object type
+    /// `typename`, if any.
+    pub(crate) fn get(&self, typename: &str) -> Option<&dyn DynEntityResolver<CtxT, S>> {
+        self.resolvers.get(typename).map(|r| &**r)
+    }
+}
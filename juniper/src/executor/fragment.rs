@@ -0,0 +1,182 @@
+//! Execution of fragments against arbitrary values, rather than the roots.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use crate::{
+    ast::{Definition, Document, Fragment, OperationType, Type},
+    parser::Spanning,
+    schema::model::SchemaType,
+    types::{async_await::GraphQLTypeAsync, base::GraphQLType},
+    validation::RuleError,
+    value::{ScalarValue, Value},
+    OperationError, RequestError,
+};
+
+use super::{pool::ObjectPool, ExecutionError, Executor, FieldPath, RequestState, Variables};
+
+/// Selects the fragment with the given `fragment_name` out of the `document`,
+/// or its single fragment if no name is provided.
+#[doc(hidden)]
+pub fn get_fragment<'b, 'd, 'e, S>(
+    document: &'b Document<'d, S>,
+    fragment_name: Option<&str>,
+) -> Result<&'b Spanning<Fragment<'d, S>>, RequestError<'e>> {
+    let mut fragments = document.iter().filter_map(|def| match def {
+        Definition::Fragment(f) => Some(f),
+        Definition::Operation(_) => None,
+    });
+    match fragment_name {
+        Some(name) => fragments
+            .find(|f| f.item.name.item == name)
+            .ok_or_else(|| OperationError::UnknownOperationName.into()),
+        None => match (fragments.next(), fragments.next()) {
+            (Some(f), None) => Ok(f),
+            (Some(_), Some(_)) => Err(OperationError::MultipleOperationsProvided.into()),
+            (None, _) => Err(OperationError::NoOperationProvided.into()),
+        },
+    }
+}
+
+/// Checks whether the type condition of the `fragment` applies to values of
+/// the type with the given `type_name`.
+fn check_type_condition<S>(
+    schema: &SchemaType<S>,
+    fragment: &Spanning<Fragment<S>>,
+    type_name: &str,
+) -> Result<(), RequestError<'static>> {
+    let condition = fragment.item.type_condition.item;
+    if schema.is_named_subtype(type_name, condition) {
+        Ok(())
+    } else {
+        Err(RequestError::Validation(vec![RuleError::new(
+            &format!(
+                "Fragment \"{}\" on \"{}\" cannot be executed against a value of type \"{}\"",
+                fragment.item.name.item, condition, type_name,
+            ),
+            &[fragment.start],
+        )
+        .with_rule("FragmentTypeCondition")]))
+    }
+}
+
+/// Create new `Executor` and resolve the selection set of the `fragment`
+/// against the provided `value`, rather than against the query root.
+pub fn execute_validated_fragment<'a, 'b, T, S>(
+    document: &'b Document<S>,
+    fragment: &'b Spanning<Fragment<S>>,
+    schema: &SchemaType<S>,
+    value: &T,
+    info: &T::TypeInfo,
+    variables: &Variables<S>,
+    context: &T::Context,
+) -> Result<(Value<S>, Vec<ExecutionError<S>>), RequestError<'a>>
+where
+    S: ScalarValue,
+    T: GraphQLType<S>,
+{
+    let limits = schema.limits();
+    let type_name = T::name(info).expect("fragments can only be executed against named types");
+    check_type_condition(schema, fragment, type_name)?;
+
+    let fragments = document
+        .iter()
+        .filter_map(|def| match def {
+            Definition::Fragment(f) => Some((f.item.name.item, f.item.clone())),
+            Definition::Operation(_) => None,
+        })
+        .collect::<HashMap<_, _>>();
+    let errors = RwLock::new(Vec::new());
+    let pool = ObjectPool::default();
+
+    let value = {
+        let executor = Executor {
+            fragments: &fragments,
+            variables,
+            current_selection_set: Some(&fragment.item.selection_set[..]),
+            parent_selection_set: None,
+            current_type: schema.make_type(&Type::NonNullNamed(type_name.into())),
+            schema,
+            context,
+            errors: &errors,
+            pool: &pool,
+            deadline: limits.deadline(),
+            work_budget: limits.work_budget(),
+            operation_type: OperationType::Query,
+            field_path: Arc::new(FieldPath::Root(fragment.start)),
+            parent: None,
+            early_value: None,
+            subsequent_payload: None,
+            state: Arc::new(RequestState::new()),
+        };
+        executor.resolve_into_value(info, value)
+    };
+
+    let mut errors = errors.into_inner().unwrap();
+    errors.sort();
+
+    Ok((value, errors))
+}
+
+/// Create new `Executor` and asynchronously resolve the selection set of the
+/// `fragment` against the provided `value`, rather than against the query
+/// root.
+pub async fn execute_validated_fragment_async<'a, 'b, T, S>(
+    document: &'b Document<'_, S>,
+    fragment: &'b Spanning<Fragment<'_, S>>,
+    schema: &SchemaType<'_, S>,
+    value: &T,
+    info: &T::TypeInfo,
+    variables: &Variables<S>,
+    context: &T::Context,
+) -> Result<(Value<S>, Vec<ExecutionError<S>>), RequestError<'a>>
+where
+    S: ScalarValue + Send + Sync,
+    T: GraphQLTypeAsync<S>,
+    T::TypeInfo: Sync,
+    T::Context: Sync,
+{
+    let limits = schema.limits();
+    let type_name = T::name(info).expect("fragments can only be executed against named types");
+    check_type_condition(schema, fragment, type_name)?;
+
+    let fragments = document
+        .iter()
+        .filter_map(|def| match def {
+            Definition::Fragment(f) => Some((f.item.name.item, f.item.clone())),
+            Definition::Operation(_) => None,
+        })
+        .collect::<HashMap<_, _>>();
+    let errors = RwLock::new(Vec::new());
+    let pool = ObjectPool::default();
+
+    let value = {
+        let executor = Executor {
+            fragments: &fragments,
+            variables,
+            current_selection_set: Some(&fragment.item.selection_set[..]),
+            parent_selection_set: None,
+            current_type: schema.make_type(&Type::NonNullNamed(type_name.into())),
+            schema,
+            context,
+            errors: &errors,
+            pool: &pool,
+            deadline: limits.deadline(),
+            work_budget: limits.work_budget(),
+            operation_type: OperationType::Query,
+            field_path: Arc::new(FieldPath::Root(fragment.start)),
+            parent: None,
+            early_value: None,
+            subsequent_payload: None,
+            state: Arc::new(RequestState::new()),
+        };
+        executor.resolve_into_value_async(info, value).await
+    };
+
+    let mut errors = errors.into_inner().unwrap();
+    errors.sort();
+
+    Ok((value, errors))
+}
@@ -0,0 +1,514 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    future::Future,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    task::Poll,
+};
+
+use futures::{
+    future::{self, BoxFuture, FusedFuture},
+    stream::{self, BoxStream},
+    FutureExt as _,
+};
+
+use crate::{
+    executor::{ExecutionError, PathSegment, RequestState},
+    types::subscriptions::ExecutionOutput,
+    value::{DefaultScalarValue, Value},
+};
+
+/// Stream of the [`IncrementalPayload`]s of an operation executed via
+/// [`execute_incremental()`].
+///
+/// [`execute_incremental()`]: crate::execute_incremental
+pub type IncrementalStream<'a, S = DefaultScalarValue> = BoxStream<'a, IncrementalPayload<S>>;
+
+/// Payload of an operation delivered incrementally, as the fragments marked
+/// with the `@defer` directive and the list items streamed with the `@stream`
+/// directive are delivered separately from the rest of it.
+///
+/// [`ExecutionOutput`] is kept as is for the operations delivered at once, so
+/// its users are not required to handle the subsequent payloads.
+#[derive(Clone, Debug)]
+pub enum IncrementalPayload<S = DefaultScalarValue> {
+    /// Initial payload, with the deferred fragments and the streamed list
+    /// items omitted from its data.
+    Initial(ExecutionOutput<S>),
+
+    /// Subsequent payload of a single deferred fragment.
+    Deferred(DeferredPayload<S>),
+
+    /// Subsequent payload of the streamed list items.
+    Streamed(StreamedPayload<S>),
+}
+
+/// Data of a fragment marked with the `@defer` directive, delivered as a
+/// subsequent [`IncrementalPayload`] of its operation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DeferredPayload<S = DefaultScalarValue> {
+    /// Fields selected by the deferred fragment, to be merged into the object
+    /// at the `path`.
+    ///
+    /// [`Value::Null`] if the fragment failed to resolve.
+    pub data: Value<S>,
+
+    /// Path of the object the deferred fragment is selected on.
+    pub path: Vec<PathSegment>,
+
+    /// `label` argument of the `@defer` directive, if any.
+    pub label: Option<String>,
+
+    /// Errors occurred while resolving the deferred fragment.
+    pub errors: Vec<ExecutionError<S>>,
+}
+
+/// Items of a list field marked with the `@stream` directive, delivered as a
+/// subsequent [`IncrementalPayload`] of its operation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StreamedPayload<S = DefaultScalarValue> {
+    /// Streamed items, to be appended to the list.
+    ///
+    /// Empty if an item failed to resolve into a non-null value, as the list
+    /// it belongs to can't be nulled anymore.
+    pub items: Vec<Value<S>>,
+
+    /// Path of the first of the `items` in the list.
+    pub path: Vec<PathSegment>,
+
+    /// `label` argument of the `@stream` directive, if any.
+    pub label: Option<String>,
+
+    /// Errors occurred while resolving the `items`.
+    pub errors: Vec<ExecutionError<S>>,
+}
+
+/// Subsequent [`IncrementalPayload`]s of an operation delivered incrementally,
+/// queued for the delivery as soon as they're resolved.
+///
+/// Stored in the [`RequestState`] of such operations only, so the `@defer` and
+/// `@stream` directives are ignored otherwise, and everything is resolved in
+/// place.
+#[derive(Debug)]
+pub(crate) struct SubsequentPayloads<S>(Mutex<Queue<S>>);
+
+impl<S> Default for SubsequentPayloads<S> {
+    fn default() -> Self {
+        Self(Mutex::new(Queue {
+            started: false,
+            reserved: Vec::new(),
+            waiting: HashMap::new(),
+            ready: VecDeque::new(),
+        }))
+    }
+}
+
+impl<S> SubsequentPayloads<S> {
+    /// Reserves the payloads of a deferred fragment or a streamed list item
+    /// about to be resolved, to be delivered once the reserved payloads with
+    /// the given `after` ID (the one it's nested inside) are delivered, or
+    /// once the initial payload is, if [`None`].
+    pub(crate) fn reserve(&self, after: Option<usize>) -> Reservation<'_, S> {
+        let mut queue = self.0.lock().unwrap();
+        queue.reserved.push(Reserved {
+            after,
+            state: ReservedState::Pending,
+        });
+        Reservation {
+            payloads: self,
+            id: queue.reserved.len() - 1,
+        }
+    }
+
+    /// Queues the initial payload of the operation, followed by the subsequent
+    /// ones resolved before it, unless it's queued already.
+    pub(crate) fn start(&self, output: ExecutionOutput<S>) {
+        let mut queue = self.0.lock().unwrap();
+        if !queue.started {
+            queue.started = true;
+            queue.ready.push_back(IncrementalPayload::Initial(output));
+            queue.release_waiting(None);
+        }
+    }
+
+    /// Indicates whether the initial payload is queued already.
+    pub(crate) fn is_started(&self) -> bool {
+        self.0.lock().unwrap().started
+    }
+
+    /// Takes the next payload ready for the delivery, if any.
+    pub(crate) fn next(&self) -> Option<IncrementalPayload<S>> {
+        self.0.lock().unwrap().ready.pop_front()
+    }
+}
+
+/// Queue of [`SubsequentPayloads`].
+#[derive(Debug)]
+struct Queue<S> {
+    /// Indicates whether the initial payload is queued already, so the
+    /// subsequent ones may follow it.
+    started: bool,
+
+    /// [`Reserved`] payloads, by their IDs.
+    reserved: Vec<Reserved>,
+
+    /// Payloads resolved before the ones they're nested inside are delivered,
+    /// by the IDs of the latter ([`None`] for the initial payload).
+    waiting: HashMap<Option<usize>, Vec<Delivered<S>>>,
+
+    /// Payloads ready for the delivery, in their delivery order.
+    ready: VecDeque<IncrementalPayload<S>>,
+}
+
+/// Resolved payloads, along with the ID they're
+/// [reserved](SubsequentPayloads::reserve) with.
+type Delivered<S> = (usize, Vec<IncrementalPayload<S>>);
+
+impl<S> Queue<S> {
+    /// Delivers the resolved `payloads` reserved with the given `id`, unless
+    /// the ones they're nested inside are still pending.
+    fn deliver(&mut self, id: usize, payloads: Vec<IncrementalPayload<S>>) {
+        let after = self.reserved[id].after;
+        let state = match after {
+            Some(after) => self.reserved[after].state,
+            None if self.started => ReservedState::Delivered,
+            None => ReservedState::Waiting,
+        };
+        match state {
+            ReservedState::Delivered => self.release(id, payloads),
+            ReservedState::Pending | ReservedState::Waiting => {
+                self.reserved[id].state = ReservedState::Waiting;
+                self.waiting.entry(after).or_default().push((id, payloads));
+            }
+            ReservedState::Cancelled => self.cancel(id),
+        }
+    }
+
+    /// Queues the `payloads` reserved with the given `id`, along with the
+    /// waiting ones nested inside them.
+    fn release(&mut self, id: usize, payloads: Vec<IncrementalPayload<S>>) {
+        self.reserved[id].state = ReservedState::Delivered;
+        self.ready.extend(payloads);
+        self.release_waiting(Some(id));
+    }
+
+    /// Queues the payloads waiting for the ones with the given `after` ID, in
+    /// their reservation order.
+    fn release_waiting(&mut self, after: Option<usize>) {
+        if let Some(mut waiting) = self.waiting.remove(&after) {
+            waiting.sort_by_key(|(id, _)| *id);
+            for (id, payloads) in waiting {
+                self.release(id, payloads);
+            }
+        }
+    }
+
+    /// Cancels the payloads reserved with the given `id`, along with the
+    /// waiting ones nested inside them, as their data is discarded.
+    fn cancel(&mut self, id: usize) {
+        self.reserved[id].state = ReservedState::Cancelled;
+        if let Some(waiting) = self.waiting.remove(&Some(id)) {
+            for (id, _) in waiting {
+                self.cancel(id);
+            }
+        }
+    }
+}
+
+/// Payloads [reserved](SubsequentPayloads::reserve) in [`SubsequentPayloads`].
+#[derive(Clone, Copy, Debug)]
+struct Reserved {
+    after: Option<usize>,
+    state: ReservedState,
+}
+
+/// State of the [`Reserved`] payloads.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ReservedState {
+    Pending,
+    Waiting,
+    Delivered,
+    Cancelled,
+}
+
+/// Payloads of a deferred fragment or a streamed list item
+/// [reserved](SubsequentPayloads::reserve) in [`SubsequentPayloads`], being
+/// cancelled if dropped before they're delivered.
+#[derive(Debug)]
+pub(crate) struct Reservation<'p, S> {
+    payloads: &'p SubsequentPayloads<S>,
+    id: usize,
+}
+
+impl<'p, S> Reservation<'p, S> {
+    /// Returns the ID of the reserved payloads, to reserve the ones nested
+    /// inside them after.
+    pub(crate) fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Delivers the resolved `payloads`.
+    pub(crate) fn deliver(&self, payloads: impl IntoIterator<Item = IncrementalPayload<S>>) {
+        let mut queue = self.payloads.0.lock().unwrap();
+        queue.deliver(self.id, payloads.into_iter().collect());
+    }
+}
+
+impl<'p, S> Drop for Reservation<'p, S> {
+    fn drop(&mut self) {
+        if let Ok(mut queue) = self.payloads.0.lock() {
+            if queue.reserved[self.id].state == ReservedState::Pending {
+                queue.cancel(self.id);
+            }
+        }
+    }
+}
+
+/// Arguments of the `@stream` directive a list field is marked with.
+#[derive(Clone, Debug)]
+pub(crate) struct StreamArgs {
+    /// `label` argument, if any.
+    pub(crate) label: Option<String>,
+
+    /// Number of the list items resolved in place, rather than streamed.
+    pub(crate) initial_count: usize,
+}
+
+/// Slot of a value (of a field, a list item or a fragment) resolved as a part
+/// of an operation delivered incrementally, the value being published into as
+/// soon as it's known, while the deferred fragments and the streamed list
+/// items nested inside it are still being resolved.
+#[derive(Debug)]
+pub(crate) struct EarlyValue<S> {
+    value: Mutex<Option<Value<S>>>,
+    stream: Option<StreamArgs>,
+}
+
+impl<S> EarlyValue<S> {
+    /// Creates a slot of the value of a list field marked with the `@stream`
+    /// directive with the given arguments, or of any other value, if
+    /// [`None`].
+    pub(crate) fn new(stream: Option<StreamArgs>) -> Self {
+        Self {
+            value: Mutex::new(None),
+            stream,
+        }
+    }
+
+    /// Returns the arguments of the `@stream` directive the list field of this
+    /// slot is marked with, if any.
+    pub(crate) fn stream(&self) -> Option<&StreamArgs> {
+        self.stream.as_ref()
+    }
+
+    /// Publishes the known `value`.
+    pub(crate) fn publish(&self, value: Value<S>) {
+        *self.value.lock().unwrap() = Some(value);
+    }
+}
+
+/// Value of a resolution, known either as it's finished, or as it's published
+/// into its [`EarlyValue`] slot before.
+pub(crate) enum Known<T, S> {
+    Finished(T),
+    Early(Value<S>),
+}
+
+/// Drives the given `resolution`, passing its value to the `known` callback as
+/// soon as it's known, either as the `resolution` is finished, or as the value
+/// is published into the `early` slot (if any) before, and resolving the rest
+/// of it afterwards.
+pub(crate) async fn resolve_known<F, S>(
+    early: Option<&EarlyValue<S>>,
+    resolution: F,
+    known: impl FnOnce(Known<F::Output, S>),
+) where
+    F: Future,
+{
+    futures::pin_mut!(resolution);
+
+    let early = match early {
+        Some(early) => early,
+        None => return known(Known::Finished(resolution.await)),
+    };
+    let value = future::poll_fn(|cx| match resolution.as_mut().poll(cx) {
+        Poll::Ready(output) => Poll::Ready(Known::Finished(output)),
+        Poll::Pending => match early.value.lock().unwrap().take() {
+            Some(value) => Poll::Ready(Known::Early(value)),
+            None => Poll::Pending,
+        },
+    })
+    .await;
+
+    let finished = matches!(value, Known::Finished(_));
+    known(value);
+    if !finished {
+        resolution.await;
+    }
+}
+
+/// Values of the selections of an object or of the items of a list, each one
+/// known as soon as it's resolved or published early, while the resolution of
+/// all of them is still in progress.
+#[derive(Debug)]
+pub(crate) struct KnownValues<T> {
+    values: Mutex<Vec<Option<T>>>,
+    unknown: AtomicUsize,
+}
+
+impl<T> Default for KnownValues<T> {
+    fn default() -> Self {
+        Self {
+            values: Mutex::new(Vec::new()),
+            unknown: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl<T> KnownValues<T> {
+    /// Reserves an unknown value, returning its index.
+    pub(crate) fn reserve(&self) -> usize {
+        let mut values = self.values.lock().unwrap();
+        values.push(None);
+        self.unknown.fetch_add(1, Ordering::Relaxed);
+        values.len() - 1
+    }
+
+    /// Sets the known `value` at the given `index`.
+    pub(crate) fn set(&self, index: usize, value: T) {
+        self.values.lock().unwrap()[index] = Some(value);
+        self.unknown.fetch_sub(1, Ordering::Release);
+    }
+
+    /// Takes the value at the given `index`, if it's known already.
+    pub(crate) fn take(&self, index: usize) -> Option<T> {
+        self.values.lock().unwrap()[index].take()
+    }
+
+    /// Takes all the values, if all of them are known already.
+    pub(crate) fn take_all(&self) -> Option<Vec<T>> {
+        if self.unknown.load(Ordering::Acquire) > 0 {
+            return None;
+        }
+        let values = std::mem::take(&mut *self.values.lock().unwrap());
+        Some(values.into_iter().flatten().collect())
+    }
+}
+
+/// Drives the given `resolution` until the value returned by the `known`
+/// closure is known.
+///
+/// # Panics
+///
+/// If the value is still unknown once the `resolution` is finished.
+pub(crate) async fn resolve_until<F, T>(
+    resolution: &mut F,
+    mut known: impl FnMut() -> Option<T>,
+) -> T
+where
+    F: FusedFuture<Output = ()> + Unpin,
+{
+    future::poll_fn(|cx| {
+        if !resolution.is_terminated() {
+            let _ = resolution.poll_unpin(cx);
+        }
+        match known() {
+            Some(value) => Poll::Ready(value),
+            None if resolution.is_terminated() => panic!("Value is unknown after its resolution"),
+            None => Poll::Pending,
+        }
+    })
+    .await
+}
+
+/// Creates an [`IncrementalStream`] of the payloads queued in the
+/// [`SubsequentPayloads`] of the given `state`, driving the `execution` of
+/// their operation (unless it's finished already) to queue them.
+pub(crate) fn incremental_stream<'a, S>(
+    mut execution: Option<BoxFuture<'a, ()>>,
+    state: Arc<RequestState>,
+) -> IncrementalStream<'a, S>
+where
+    S: Send + Sync + 'static,
+{
+    Box::pin(stream::poll_fn(move |cx| {
+        if let Some(e) = &mut execution {
+            if e.poll_unpin(cx).is_ready() {
+                execution = None;
+            }
+        }
+        match state.get::<SubsequentPayloads<S>>().and_then(|p| p.next()) {
+            Some(payload) => Poll::Ready(Some(payload)),
+            None if execution.is_none() => Poll::Ready(None),
+            None => Poll::Pending,
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        executor::PathSegment, types::subscriptions::ExecutionOutput, value::Value,
+        DefaultScalarValue,
+    };
+
+    use super::{DeferredPayload, IncrementalPayload, SubsequentPayloads};
+
+    fn payload(label: &str) -> IncrementalPayload<DefaultScalarValue> {
+        IncrementalPayload::Deferred(DeferredPayload {
+            data: Value::null(),
+            path: vec![PathSegment::Field("hero".into())],
+            label: Some(label.into()),
+            errors: vec![],
+        })
+    }
+
+    fn labels(payloads: &SubsequentPayloads<DefaultScalarValue>) -> Vec<String> {
+        std::iter::from_fn(|| payloads.next())
+            .map(|p| match p {
+                IncrementalPayload::Initial(_) => "initial".into(),
+                IncrementalPayload::Deferred(p) => p.label.unwrap(),
+                IncrementalPayload::Streamed(_) => unreachable!(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn delivers_inner_payloads_after_outer_ones() {
+        let payloads = SubsequentPayloads::default();
+
+        let outer = payloads.reserve(None);
+        let sibling = payloads.reserve(None);
+        let inner = payloads.reserve(Some(outer.id()));
+        inner.deliver(Some(payload("inner")));
+        sibling.deliver(Some(payload("sibling")));
+        assert!(labels(&payloads).is_empty());
+
+        payloads.start(ExecutionOutput::from_data(Value::null()));
+        assert_eq!(labels(&payloads), ["initial", "sibling"]);
+
+        outer.deliver(vec![payload("outer 1"), payload("outer 2")]);
+        assert_eq!(labels(&payloads), ["outer 1", "outer 2", "inner"]);
+
+        let late = payloads.reserve(Some(outer.id()));
+        late.deliver(Some(payload("late")));
+        assert_eq!(labels(&payloads), ["late"]);
+    }
+
+    #[test]
+    fn discards_payloads_nested_inside_cancelled_ones() {
+        let payloads = SubsequentPayloads::default();
+        payloads.start(ExecutionOutput::from_data(Value::null()));
+
+        let outer = payloads.reserve(None);
+        let inner = payloads.reserve(Some(outer.id()));
+        let innermost = payloads.reserve(Some(inner.id()));
+        innermost.deliver(Some(payload("innermost")));
+        drop(outer);
+        inner.deliver(Some(payload("inner")));
+
+        assert_eq!(labels(&payloads), ["initial"]);
+    }
+}
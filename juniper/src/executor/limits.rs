@@ -0,0 +1,332 @@
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    ast::{Definition, Document, Fragment, Operation, Selection},
+    parser::Spanning,
+    schema::model::SchemaType,
+    validation::{operation_cost, RuleError},
+    value::{ScalarValue, Value},
+    Variables,
+};
+
+use super::metrics::{MetricsSink, OperationMeasure};
+
+/// Limits of a single operation execution, protecting a server from overly
+/// expensive operations.
+///
+/// Default [`Limits`] may be set for a whole schema with
+/// [`RootNode::with_limits()`], and overridden for a single request (for
+/// example, depending on the API key or tenant performing it) by executing the
+/// operation with [`execute_with_limits()`], [`execute_sync_with_limits()`] or
+/// [`resolve_into_stream_with_limits()`].
+///
+/// The depth, aliases and cost limits are checked before the execution, along
+/// with the validation, so the operation exceeding them is not executed at
+/// all. The depth and aliases limits are checked by the `MaxDepth` and
+/// `MaxAliases` validation rules, visited by the [`visit_all_rules()`]. Fields
+/// excluded by `@skip`/`@include` directives are counted too. The measured
+/// values may be monitored via a [`MetricsSink`].
+///
+/// The timeout and work limits are checked during the execution instead, as
+/// a runtime backstop for the operations the cost of which has been estimated
+/// wrongly (like ones returning much longer lists than expected).
+///
+/// [`execute_with_limits()`]: crate::execute_with_limits
+/// [`execute_sync_with_limits()`]: crate::execute_sync_with_limits
+/// [`resolve_into_stream_with_limits()`]: crate::resolve_into_stream_with_limits
+/// [`RootNode::with_limits()`]: crate::RootNode::with_limits
+/// [`visit_all_rules()`]: crate::validation::visit_all_rules
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Limits {
+    depth: Option<usize>,
+    aliases: Option<usize>,
+    cost: Option<usize>,
+    timeout: Option<Duration>,
+    work: Option<usize>,
+}
+
+impl Limits {
+    /// Creates new [`Limits`] not limiting anything.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits the nesting depth of fields in an operation, where the root
+    /// fields have the depth of `1`.
+    ///
+    /// Checked by the `MaxDepth` validation rule, reporting the first field
+    /// nested deeper than the limit.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.depth = Some(depth);
+        self
+    }
+
+    /// Limits the number of aliased fields in an operation, with the aliases
+    /// of fragments counted once per their spread.
+    ///
+    /// Checked by the `MaxAliases` validation rule, reporting the first
+    /// aliased field exceeding the limit.
+    pub fn max_aliases(mut self, aliases: usize) -> Self {
+        self.aliases = Some(aliases);
+        self
+    }
+
+    /// Limits the cost of an operation, being the sum of the costs of the
+    /// fields it selects, with the fields of fragments counted once per their
+    /// spread.
+    ///
+    /// Each field costs `1` plus the cost of its selection set, unless it
+    /// declares its own [`Complexity`] (via a `#[graphql(complexity = ...)]`
+    /// attribute argument, [`Field::complexity()`] or
+    /// [`Registry::set_field_complexity()`]), which may depend on the field
+    /// arguments (like a page size).
+    ///
+    /// [`Complexity`]: crate::meta::Complexity
+    /// [`Field::complexity()`]: crate::meta::Field::complexity
+    /// [`Registry::set_field_complexity()`]: crate::Registry::set_field_complexity
+    pub fn max_cost(mut self, cost: usize) -> Self {
+        self.cost = Some(cost);
+        self
+    }
+
+    /// Limits the time of an operation execution.
+    ///
+    /// Once the `timeout` elapses, the fields not resolved yet are resolved
+    /// with an error instead. The fields being resolved at that moment are not
+    /// interrupted, so the actual execution may take longer.
+    ///
+    /// A `timeout` too large for its deadline to be represented doesn't limit
+    /// anything.
+    ///
+    /// Not applied to subscriptions.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Limits the time of an operation execution to the given `timeout`, unless
+    /// a shorter [`Limits::timeout()`] is set already.
+    ///
+    /// As with [`Limits::timeout()`], a `timeout` too large for its deadline
+    /// to be represented doesn't limit anything.
+    pub fn timeout_at_most(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(self.timeout.map_or(timeout, |t| t.min(timeout)));
+        self
+    }
+
+    /// Limits the work of an operation execution, being the number of the
+    /// fields it resolves plus the number of elements in the lists it
+    /// resolves, so roughly the number of nodes in its response.
+    ///
+    /// Once the `budget` is exceeded, the fields not resolved yet are resolved
+    /// with an error instead, having the `WORK_BUDGET_EXCEEDED` code and the
+    /// `budget` in its extensions.
+    ///
+    /// Not applied to subscriptions.
+    pub fn max_work(mut self, budget: usize) -> Self {
+        self.work = Some(budget);
+        self
+    }
+
+    /// Returns the maximum nesting depth of fields in an operation, if any.
+    pub(crate) fn depth_limit(&self) -> Option<usize> {
+        self.depth
+    }
+
+    /// Returns the maximum number of aliased fields in an operation, if any.
+    pub(crate) fn alias_limit(&self) -> Option<usize> {
+        self.aliases
+    }
+
+    /// Returns the work budget of an operation execution, if any.
+    pub(crate) fn work_budget(&self) -> Option<usize> {
+        self.work
+    }
+
+    /// Returns the moment the execution of an operation started now should be
+    /// timed out at, if any.
+    ///
+    /// [`None`] if the timeout is too large for the moment to be represented.
+    pub(crate) fn deadline(&self) -> Option<Instant> {
+        self.timeout.and_then(|t| Instant::now().checked_add(t))
+    }
+
+    /// Checks the given `operation` of the `document` against these
+    /// [`Limits`], returning the violated ones as [`RuleError`]s.
+    ///
+    /// The depth and aliases limits are not checked here, but by the
+    /// `MaxDepth` and `MaxAliases` validation rules instead.
+    ///
+    /// The cost of the `operation` is computed according to the fields of the
+    /// `schema`, with the provided `variables` substituted into their
+    /// arguments.
+    ///
+    /// The computed [`OperationMeasure`] is recorded into the provided
+    /// [`MetricsSink`], if any.
+    pub(crate) fn check<S>(
+        &self,
+        schema: &SchemaType<S>,
+        document: &Document<S>,
+        operation: &Spanning<Operation<S>>,
+        variables: &Variables<S>,
+        sink: Option<&dyn MetricsSink>,
+    ) -> Vec<RuleError>
+    where
+        S: ScalarValue,
+    {
+        if self.cost.is_none() && sink.is_none() {
+            return vec![];
+        }
+
+        let mut measurer = Measurer::new(document);
+        let measure = OperationMeasure {
+            cost: operation_cost(schema, document, operation, variables),
+            ..measurer.measure(&operation.item.selection_set)
+        };
+        if let Some(sink) = sink {
+            sink.record_measure(
+                operation.item.name.as_ref().map(|n| n.item),
+                operation.item.operation_type,
+                &measure,
+            );
+        }
+
+        let pos = &[operation.start];
+        let mut errors = vec![];
+        if let Some(max) = self.cost.filter(|max| measure.cost > *max) {
+            errors.push(
+                RuleError::new(
+                    &format!(
+                        "Operation has cost of {}, exceeding the maximum allowed cost of {}",
+                        measure.cost, max,
+                    ),
+                    pos,
+                )
+                .with_rule("MaxCost")
+                .with_param("max", usize_value(max))
+                .with_param("actual", usize_value(measure.cost)),
+            );
+        }
+        errors
+    }
+}
+
+/// Converts the given `usize` into a [`Value`] of a GraphQL `Int`, saturating
+/// it at [`i32::MAX`].
+pub(crate) fn usize_value(v: usize) -> Value {
+    Value::scalar(i32::try_from(v).unwrap_or(i32::MAX))
+}
+
+/// Work already spent on an operation execution, stored in its
+/// [`RequestState`].
+///
+/// [`RequestState`]: super::RequestState
+#[derive(Debug, Default)]
+pub(crate) struct WorkSpent(AtomicUsize);
+
+impl WorkSpent {
+    /// Spends the given number of work `units`, returning the total work spent
+    /// so far.
+    pub(crate) fn spend(&self, units: usize) -> usize {
+        self.0
+            .fetch_add(units, Ordering::Relaxed)
+            .saturating_add(units)
+    }
+}
+
+/// Measurer of selection sets, measuring each fragment once, so spreading
+/// the same fragment many times doesn't blow up the measuring itself.
+///
+/// Fragments spread within themselves (directly or not) are measured as
+/// empty once reached again, so the measuring stops even on fragment cycles,
+/// which are rejected by the validation anyway.
+///
+/// Doesn't measure the cost, as it depends on the schema, and is computed by
+/// the [`operation_cost()`] instead.
+pub(crate) struct Measurer<'d, 'a, S> {
+    fragments: HashMap<&'a str, &'d Fragment<'a, S>>,
+    measured: HashMap<&'a str, Option<OperationMeasure>>,
+}
+
+impl<'d, 'a, S> Measurer<'d, 'a, S> {
+    /// Creates a new [`Measurer`] of the selection sets of the given
+    /// `document`.
+    pub(crate) fn new(document: &'d Document<'a, S>) -> Self {
+        Self {
+            fragments: document
+                .iter()
+                .filter_map(|def| match def {
+                    Definition::Fragment(f) => Some((f.item.name.item, &f.item)),
+                    Definition::Operation(_) => None,
+                })
+                .collect(),
+            measured: HashMap::new(),
+        }
+    }
+
+    /// Returns the fragment of the measured document with the given `name`,
+    /// if any.
+    pub(crate) fn fragment(&self, name: &str) -> Option<&'d Fragment<'a, S>> {
+        self.fragments.get(name).copied()
+    }
+
+    /// Measures the given `selection_set`.
+    pub(crate) fn measure(&mut self, selection_set: &'d [Selection<'a, S>]) -> OperationMeasure {
+        selection_set
+            .iter()
+            .fold(OperationMeasure::default(), |acc, selection| {
+                acc.merge(self.measure_selection(selection))
+            })
+    }
+
+    /// Measures the given single `selection`, with the fragment it spreads
+    /// measured in the whole.
+    pub(crate) fn measure_selection(
+        &mut self,
+        selection: &'d Selection<'a, S>,
+    ) -> OperationMeasure {
+        match selection {
+            Selection::Field(f) => {
+                let nested = f
+                    .item
+                    .selection_set
+                    .as_deref()
+                    .map(|s| self.measure(s))
+                    .unwrap_or_default();
+                OperationMeasure {
+                    depth: nested.depth + 1,
+                    aliases: nested
+                        .aliases
+                        .saturating_add(usize::from(f.item.alias.is_some())),
+                    cost: 0,
+                }
+            }
+            Selection::InlineFragment(f) => self.measure(&f.item.selection_set),
+            Selection::FragmentSpread(s) => self.measure_fragment(s.item.name.item),
+        }
+    }
+
+    fn measure_fragment(&mut self, name: &'a str) -> OperationMeasure {
+        match self.measured.get(name) {
+            Some(measure) => {
+                // `None` means a fragment cycle, which is rejected by the
+                // validation anyway.
+                return measure.unwrap_or_default();
+            }
+            None => {
+                self.measured.insert(name, None);
+            }
+        }
+        let fragment = self.fragment(name);
+        let measure = fragment
+            .map(|f| self.measure(&f.selection_set))
+            .unwrap_or_default();
+        self.measured.insert(name, Some(measure));
+        measure
+    }
+}
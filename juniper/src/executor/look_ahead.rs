@@ -1,8 +1,9 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, hash::Hasher};
 
 use crate::{
-    ast::{Directive, Fragment, InputValue, Selection},
+    ast::{Directive, Fragment, FromInputValue, InputValue, Selection},
     parser::Spanning,
+    schema::model::SchemaType,
     value::ScalarValue,
 };
 
@@ -64,6 +65,32 @@ where
     }
 }
 
+impl<'a, S> LookAheadValue<'a, S>
+where
+    S: ScalarValue,
+{
+    /// Converts this [`LookAheadValue`] back into an [`InputValue`].
+    pub fn to_input_value(&self) -> InputValue<S> {
+        match self {
+            Self::Null => InputValue::null(),
+            Self::Scalar(s) => InputValue::scalar((*s).clone()),
+            Self::Enum(e) => InputValue::enum_value(e),
+            Self::List(items) => InputValue::list(items.iter().map(Self::to_input_value).collect()),
+            Self::Object(fields) => InputValue::parsed_object(
+                fields
+                    .iter()
+                    .map(|(name, value)| {
+                        (
+                            Spanning::unlocated((*name).to_owned()),
+                            Spanning::unlocated(value.to_input_value()),
+                        )
+                    })
+                    .collect(),
+            ),
+        }
+    }
+}
+
 /// An argument passed into the query
 #[derive(Debug, Clone, PartialEq)]
 pub struct LookAheadArgument<'a, S: 'a> {
@@ -94,6 +121,74 @@ where
     pub fn value(&'a self) -> &LookAheadValue<'a, S> {
         &self.value
     }
+
+    /// Converts the value of the argument into the Rust type `T`.
+    ///
+    /// # Errors
+    ///
+    /// If the value cannot be converted into `T`.
+    pub fn value_as<T: FromInputValue<S>>(&self) -> Result<T, T::Error> {
+        T::from_input_value(&self.value.to_input_value())
+    }
+}
+
+/// A directive placed on a selection in the query
+///
+/// Both the built-in directives (like `@skip` and `@include`) and the custom
+/// ones are exposed, with their arguments already evaluated against the
+/// variables of the query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LookAheadDirective<'a, S: 'a> {
+    name: &'a str,
+    arguments: Vec<LookAheadArgument<'a, S>>,
+}
+
+impl<'a, S> LookAheadDirective<'a, S>
+where
+    S: ScalarValue,
+{
+    fn new(directive: &'a Directive<'a, S>, vars: &'a Variables<S>) -> Self {
+        LookAheadDirective {
+            name: directive.name.item,
+            arguments: directive
+                .arguments
+                .as_ref()
+                .map(|a| {
+                    a.item
+                        .items
+                        .iter()
+                        .map(|p| LookAheadArgument::new(p, vars))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    fn from_directives(
+        directives: Option<&'a Vec<Spanning<Directive<'a, S>>>>,
+        vars: &'a Variables<S>,
+    ) -> Vec<Self> {
+        directives
+            .map(|d| d.iter().map(|d| Self::new(&d.item, vars)).collect())
+            .unwrap_or_default()
+    }
+}
+
+impl<'a, S> LookAheadDirective<'a, S> {
+    /// The directive's name (without the `@`)
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// The arguments passed to the directive
+    pub fn arguments(&self) -> &[LookAheadArgument<'a, S>] {
+        &self.arguments
+    }
+
+    /// The argument of the directive with a given name
+    pub fn argument(&self, name: &str) -> Option<&LookAheadArgument<'a, S>> {
+        self.arguments.iter().find(|a| a.name == name)
+    }
 }
 
 #[doc(hidden)]
@@ -109,6 +204,7 @@ pub struct LookAheadSelection<'a, S: 'a> {
     pub(super) name: &'a str,
     pub(super) alias: Option<&'a str>,
     pub(super) arguments: Vec<LookAheadArgument<'a, S>>,
+    pub(super) directives: Vec<LookAheadDirective<'a, S>>,
     pub(super) children: Vec<ChildSelection<'a, S>>,
 }
 
@@ -121,6 +217,7 @@ where
             name: "",
             alias: None,
             arguments: vec![],
+            directives: Vec::new(),
             children: vec![],
         }
     }
@@ -176,7 +273,7 @@ where
                             .unwrap_or(false),
                         ("skip", &None) => false,
                         ("include", &None) => true,
-                        (_, _) => unreachable!(),
+                        (_, _) => true,
                     }
                 })
             })
@@ -218,10 +315,13 @@ where
                             .collect()
                     })
                     .unwrap_or_else(Vec::new);
+                let directives =
+                    LookAheadDirective::from_directives(field.directives.as_ref(), vars);
                 let mut ret = LookAheadSelection {
                     name,
                     alias,
                     arguments,
+                    directives,
                     children: Vec::new(),
                 };
                 for c in field
@@ -301,6 +401,44 @@ where
         }
     }
 
+    /// Complements the arguments of the children of this selection, being a
+    /// field of the `type_name` type, with the default values of the
+    /// arguments omitted in the operation, recursively.
+    pub(super) fn apply_defaults(
+        &mut self,
+        schema: &'a SchemaType<'a, S>,
+        type_name: &str,
+        vars: &'a Variables<S>,
+    ) {
+        for child in &mut self.children {
+            let ty = match child.applies_for {
+                Applies::OnlyType(ty) => ty,
+                Applies::All => type_name,
+            };
+            let field = match schema
+                .concrete_type_by_name(ty)
+                .and_then(|t| t.field_by_name(child.inner.name))
+            {
+                Some(field) => field,
+                None => continue,
+            };
+
+            for arg in field.arguments.iter().flatten() {
+                if let Some(default) = &arg.default_value {
+                    if child.inner.arguments.iter().all(|a| a.name != arg.name) {
+                        child.inner.arguments.push(LookAheadArgument {
+                            name: &arg.name,
+                            value: LookAheadValue::from_input_value(default, vars),
+                        });
+                    }
+                }
+            }
+            child
+                .inner
+                .apply_defaults(schema, field.field_type.innermost_name(), vars);
+        }
+    }
+
     /// Convert a eventually type independent selection into one for a concrete type
     pub fn for_explicit_type(&self, type_name: &str) -> ConcreteLookAheadSelection<'a, S> {
         ConcreteLookAheadSelection {
@@ -318,16 +456,132 @@ where
             name: self.name,
             alias: self.alias,
             arguments: self.arguments.clone(),
+            directives: self.directives.clone(),
         }
     }
 }
 
+impl<'a, S> LookAheadSelection<'a, S>
+where
+    S: ScalarValue,
+{
+    /// Feeds the sub-selection of this [`LookAheadSelection`] into the given
+    /// `hasher`.
+    ///
+    /// Only bytes are written (with integers in little-endian), so the
+    /// resulting hash doesn't depend on the platform or on the [`Hash`]
+    /// implementations of the standard library, which are not guaranteed to be
+    /// stable.
+    ///
+    /// The `@skip` and `@include` directives are omitted, as they have
+    /// already been applied while building this [`LookAheadSelection`].
+    pub(super) fn hash_children<H: Hasher>(&self, hasher: &mut H) {
+        hasher.write(&(self.children.len() as u64).to_le_bytes());
+        for child in &self.children {
+            match child.applies_for {
+                Applies::All => hasher.write_u8(0),
+                Applies::OnlyType(ty) => {
+                    hasher.write_u8(1);
+                    hash_str(ty, hasher);
+                }
+            }
+
+            let field = &child.inner;
+            hash_str(field.name, hasher);
+            match field.alias {
+                Some(alias) => {
+                    hasher.write_u8(1);
+                    hash_str(alias, hasher);
+                }
+                None => hasher.write_u8(0),
+            }
+            hash_arguments(&field.arguments, hasher);
+            let directives = field
+                .directives
+                .iter()
+                .filter(|d| d.name != "skip" && d.name != "include")
+                .collect::<Vec<_>>();
+            hasher.write(&(directives.len() as u64).to_le_bytes());
+            for d in directives {
+                hash_str(d.name, hasher);
+                hash_arguments(&d.arguments, hasher);
+            }
+            field.hash_children(hasher);
+        }
+    }
+}
+
+impl<'a, S> LookAheadValue<'a, S>
+where
+    S: ScalarValue,
+{
+    /// Feeds this [`LookAheadValue`] into the given `hasher`.
+    fn hash<H: Hasher>(&self, hasher: &mut H) {
+        match self {
+            Self::Null => hasher.write_u8(0),
+            Self::Scalar(s) => {
+                if let Some(b) = s.as_bool() {
+                    hasher.write_u8(1);
+                    hasher.write_u8(b.into());
+                } else if let Some(i) = s.as_int() {
+                    hasher.write_u8(2);
+                    hasher.write(&i.to_le_bytes());
+                } else if let Some(f) = s.as_float() {
+                    hasher.write_u8(3);
+                    hasher.write(&f.to_bits().to_le_bytes());
+                } else if let Some(s) = s.as_str() {
+                    hasher.write_u8(4);
+                    hash_str(s, hasher);
+                } else {
+                    hasher.write_u8(5);
+                    hash_str(&s.to_string(), hasher);
+                }
+            }
+            Self::Enum(e) => {
+                hasher.write_u8(6);
+                hash_str(e, hasher);
+            }
+            Self::List(items) => {
+                hasher.write_u8(7);
+                hasher.write(&(items.len() as u64).to_le_bytes());
+                for item in items {
+                    item.hash(hasher);
+                }
+            }
+            Self::Object(fields) => {
+                hasher.write_u8(8);
+                hasher.write(&(fields.len() as u64).to_le_bytes());
+                for (name, value) in fields {
+                    hash_str(name, hasher);
+                    value.hash(hasher);
+                }
+            }
+        }
+    }
+}
+
+/// Feeds the given [`LookAheadArgument`]s into the given `hasher`.
+fn hash_arguments<S: ScalarValue, H: Hasher>(args: &[LookAheadArgument<S>], hasher: &mut H) {
+    hasher.write(&(args.len() as u64).to_le_bytes());
+    for arg in args {
+        hash_str(arg.name, hasher);
+        arg.value.hash(hasher);
+    }
+}
+
+/// Feeds the given string into the given `hasher`, prefixed with its length.
+fn hash_str<H: Hasher>(s: &str, hasher: &mut H) {
+    hasher.write(&(s.len() as u64).to_le_bytes());
+    hasher.write(s.as_bytes());
+}
+
 /// A selection performed by a query on a concrete type
 #[derive(Debug, PartialEq)]
 pub struct ConcreteLookAheadSelection<'a, S: 'a> {
     name: &'a str,
     alias: Option<&'a str>,
     arguments: Vec<LookAheadArgument<'a, S>>,
+    directives: Vec<LookAheadDirective<'a, S>>,
     children: Vec<ConcreteLookAheadSelection<'a, S>>,
 }
 
@@ -363,6 +617,32 @@ pub trait LookAheadMethods<'sel, S> {
         self.arguments().iter().find(|a| a.name == name)
     }
 
+    /// Get the top level argument with a given name from the current selection,
+    /// converted into the Rust type `T`.
+    ///
+    /// Returns [`None`] if there is no such argument.
+    fn argument_value<T>(&self, name: &str) -> Option<Result<T, T::Error>>
+    where
+        Self: Sized,
+        S: ScalarValue,
+        T: FromInputValue<S>,
+    {
+        self.argument(name).map(LookAheadArgument::value_as)
+    }
+
+    /// Get the directives placed on the current selection
+    fn directives(&self) -> &[LookAheadDirective<S>];
+
+    /// Get the directive with a given name placed on the current selection
+    fn directive(&self, name: &str) -> Option<&LookAheadDirective<S>> {
+        self.directives().iter().find(|d| d.name == name)
+    }
+
+    /// Check if a directive with a given name is placed on the current selection
+    fn has_directive(&self, name: &str) -> bool {
+        self.directive(name).is_some()
+    }
+
     /// Get the (possibly aliased) names of the top level children for the current selection
     fn child_names(&self) -> Vec<&'sel str>;
 
@@ -383,6 +663,10 @@ impl<'a, S> LookAheadMethods<'a, S> for ConcreteLookAheadSelection<'a, S> {
         &self.arguments
     }
 
+    fn directives(&self) -> &[LookAheadDirective<S>] {
+        &self.directives
+    }
+
     fn child_names(&self) -> Vec<&'a str> {
         self.children.iter().map(|c| c.field_name()).collect()
     }
@@ -416,6 +700,10 @@ impl<'a, S> LookAheadMethods<'a, S> for LookAheadSelection<'a, S> {
         &self.arguments
     }
 
+    fn directives(&self) -> &[LookAheadDirective<S>] {
+        &self.directives
+    }
+
     fn child_names(&self) -> Vec<&'a str> {
         self.children.iter().map(|c| c.inner.field_name()).collect()
     }
@@ -502,12 +790,14 @@ query Hero {
                 name: "hero",
                 alias: None,
                 arguments: Vec::new(),
+                directives: Vec::new(),
                 children: vec![
                     ChildSelection {
                         inner: LookAheadSelection {
                             name: "id",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -517,6 +807,7 @@ query Hero {
                             name: "name",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -556,12 +847,14 @@ query Hero {
                 name: "hero",
                 alias: Some("custom_hero"),
                 arguments: Vec::new(),
+                directives: Vec::new(),
                 children: vec![
                     ChildSelection {
                         inner: LookAheadSelection {
                             name: "id",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -571,6 +864,7 @@ query Hero {
                             name: "name",
                             alias: Some("my_name"),
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -614,12 +908,14 @@ query Hero {
                 name: "hero",
                 alias: None,
                 arguments: Vec::new(),
+                directives: Vec::new(),
                 children: vec![
                     ChildSelection {
                         inner: LookAheadSelection {
                             name: "id",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -629,6 +925,7 @@ query Hero {
                             name: "name",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -638,12 +935,14 @@ query Hero {
                             name: "friends",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: vec![
                                 ChildSelection {
                                     inner: LookAheadSelection {
                                         name: "name",
                                         alias: None,
                                         arguments: Vec::new(),
+                                        directives: Vec::new(),
                                         children: Vec::new(),
                                     },
                                     applies_for: Applies::All,
@@ -653,6 +952,7 @@ query Hero {
                                         name: "id",
                                         alias: None,
                                         arguments: Vec::new(),
+                                        directives: Vec::new(),
                                         children: Vec::new(),
                                     },
                                     applies_for: Applies::All,
@@ -699,12 +999,14 @@ query Hero {
                     name: "episode",
                     value: LookAheadValue::Enum("EMPIRE"),
                 }],
+                directives: Vec::new(),
                 children: vec![
                     ChildSelection {
                         inner: LookAheadSelection {
                             name: "id",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -717,6 +1019,7 @@ query Hero {
                                 name: "uppercase",
                                 value: LookAheadValue::Scalar(&DefaultScalarValue::Boolean(true)),
                             }],
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -759,12 +1062,14 @@ query Hero($episode: Episode) {
                     name: "episode",
                     value: LookAheadValue::Enum("JEDI"),
                 }],
+                directives: Vec::new(),
                 children: vec![
                     ChildSelection {
                         inner: LookAheadSelection {
                             name: "id",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -774,6 +1079,7 @@ query Hero($episode: Episode) {
                             name: "name",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -815,11 +1121,13 @@ query Hero($episode: Episode) {
                     name: "episode",
                     value: LookAheadValue::Null,
                 }],
+                directives: Vec::new(),
                 children: vec![ChildSelection {
                     inner: LookAheadSelection {
                         name: "id",
                         alias: None,
                         arguments: Vec::new(),
+                        directives: Vec::new(),
                         children: Vec::new(),
                     },
                     applies_for: Applies::All,
@@ -862,12 +1170,14 @@ fragment commonFields on Character {
                 name: "hero",
                 alias: None,
                 arguments: Vec::new(),
+                directives: Vec::new(),
                 children: vec![
                     ChildSelection {
                         inner: LookAheadSelection {
                             name: "id",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -877,6 +1187,7 @@ fragment commonFields on Character {
                             name: "name",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -886,6 +1197,7 @@ fragment commonFields on Character {
                             name: "appearsIn",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -926,12 +1238,22 @@ query Hero {
                 name: "hero",
                 alias: None,
                 arguments: Vec::new(),
+                directives: Vec::new(),
                 children: vec![
                     ChildSelection {
                         inner: LookAheadSelection {
                             name: "id",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: vec![LookAheadDirective {
+                                name: "include",
+                                arguments: vec![LookAheadArgument {
+                                    name: "if",
+                                    value: LookAheadValue::Scalar(&DefaultScalarValue::Boolean(
+                                        true,
+                                    )),
+                                }],
+                            }],
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -941,6 +1263,15 @@ query Hero {
                             name: "height",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: vec![LookAheadDirective {
+                                name: "skip",
+                                arguments: vec![LookAheadArgument {
+                                    name: "if",
+                                    value: LookAheadValue::Scalar(&DefaultScalarValue::Boolean(
+                                        false,
+                                    )),
+                                }],
+                            }],
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -984,12 +1315,14 @@ query Hero {
                 name: "hero",
                 alias: None,
                 arguments: Vec::new(),
+                directives: Vec::new(),
                 children: vec![
                     ChildSelection {
                         inner: LookAheadSelection {
                             name: "name",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -999,6 +1332,7 @@ query Hero {
                             name: "primaryFunction",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::OnlyType("Droid"),
@@ -1008,6 +1342,7 @@ query Hero {
                             name: "height",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::OnlyType("Human"),
@@ -1049,11 +1384,13 @@ query HeroAndHuman {
                 name: "hero",
                 alias: None,
                 arguments: Vec::new(),
+                directives: Vec::new(),
                 children: vec![ChildSelection {
                     inner: LookAheadSelection {
                         name: "id",
                         alias: None,
                         arguments: Vec::new(),
+                        directives: Vec::new(),
                         children: Vec::new(),
                     },
                     applies_for: Applies::All,
@@ -1071,11 +1408,13 @@ query HeroAndHuman {
                 name: "human",
                 alias: None,
                 arguments: Vec::new(),
+                directives: Vec::new(),
                 children: vec![ChildSelection {
                     inner: LookAheadSelection {
                         name: "name",
                         alias: None,
                         arguments: Vec::new(),
+                        directives: Vec::new(),
                         children: Vec::new(),
                     },
                     applies_for: Applies::All,
@@ -1131,12 +1470,14 @@ fragment comparisonFields on Character {
                     name: "id",
                     value: LookAheadValue::Scalar(&DefaultScalarValue::Int(42)),
                 }],
+                directives: Vec::new(),
                 children: vec![
                     ChildSelection {
                         inner: LookAheadSelection {
                             name: "id",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -1146,6 +1487,7 @@ fragment comparisonFields on Character {
                             name: "__typename",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -1155,6 +1497,7 @@ fragment comparisonFields on Character {
                             name: "name",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -1164,6 +1507,7 @@ fragment comparisonFields on Character {
                             name: "appearsIn",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::All,
@@ -1173,6 +1517,7 @@ fragment comparisonFields on Character {
                             name: "primaryFunction",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::OnlyType("Droid"),
@@ -1182,6 +1527,7 @@ fragment comparisonFields on Character {
                             name: "height",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: Vec::new(),
                             children: Vec::new(),
                         },
                         applies_for: Applies::OnlyType("Human"),
@@ -1191,12 +1537,22 @@ fragment comparisonFields on Character {
                             name: "friends",
                             alias: None,
                             arguments: Vec::new(),
+                            directives: vec![LookAheadDirective {
+                                name: "include",
+                                arguments: vec![LookAheadArgument {
+                                    name: "if",
+                                    value: LookAheadValue::Scalar(&DefaultScalarValue::Boolean(
+                                        true,
+                                    )),
+                                }],
+                            }],
                             children: vec![
                                 ChildSelection {
                                     inner: LookAheadSelection {
                                         name: "__typename",
                                         alias: None,
                                         arguments: Vec::new(),
+                                        directives: Vec::new(),
                                         children: Vec::new(),
                                     },
                                     applies_for: Applies::All,
@@ -1206,6 +1562,7 @@ fragment comparisonFields on Character {
                                         name: "name",
                                         alias: None,
                                         arguments: Vec::new(),
+                                        directives: Vec::new(),
                                         children: Vec::new(),
                                     },
                                     applies_for: Applies::All,
@@ -1215,6 +1572,7 @@ fragment comparisonFields on Character {
                                         name: "appearsIn",
                                         alias: None,
                                         arguments: Vec::new(),
+                                        directives: Vec::new(),
                                         children: Vec::new(),
                                     },
                                     applies_for: Applies::All,
@@ -1224,6 +1582,7 @@ fragment comparisonFields on Character {
                                         name: "primaryFunction",
                                         alias: None,
                                         arguments: Vec::new(),
+                                        directives: Vec::new(),
                                         children: Vec::new(),
                                     },
                                     applies_for: Applies::OnlyType("Droid"),
@@ -1233,6 +1592,7 @@ fragment comparisonFields on Character {
                                         name: "height",
                                         alias: None,
                                         arguments: Vec::new(),
+                                        directives: Vec::new(),
                                         children: Vec::new(),
                                     },
                                     applies_for: Applies::OnlyType("Human"),
@@ -1281,17 +1641,20 @@ query Hero {
                 name: "hero",
                 alias: None,
                 arguments: Vec::new(),
+                directives: Vec::new(),
                 children: vec![
                     ConcreteLookAheadSelection {
                         name: "name",
                         alias: None,
                         arguments: Vec::new(),
+                        directives: Vec::new(),
                         children: Vec::new(),
                     },
                     ConcreteLookAheadSelection {
                         name: "height",
                         alias: None,
                         arguments: Vec::new(),
+                        directives: Vec::new(),
                         children: Vec::new(),
                     },
                 ],
@@ -1308,12 +1671,14 @@ query Hero {
             name: "hero",
             alias: None,
             arguments: Vec::new(),
+            directives: Vec::new(),
             children: vec![
                 ChildSelection {
                     inner: LookAheadSelection {
                         name: "id",
                         alias: None,
                         arguments: Vec::new(),
+                        directives: Vec::new(),
                         children: Vec::new(),
                     },
                     applies_for: Applies::All,
@@ -1323,12 +1688,14 @@ query Hero {
                         name: "friends",
                         alias: None,
                         arguments: Vec::new(),
+                        directives: Vec::new(),
                         children: vec![
                             ChildSelection {
                                 inner: LookAheadSelection {
                                     name: "id",
                                     alias: None,
                                     arguments: Vec::new(),
+                                    directives: Vec::new(),
                                     children: Vec::new(),
                                 },
                                 applies_for: Applies::All,
@@ -1338,6 +1705,7 @@ query Hero {
                                     name: "name",
                                     alias: None,
                                     arguments: Vec::new(),
+                                    directives: Vec::new(),
                                     children: Vec::new(),
                                 },
                                 applies_for: Applies::All,
@@ -1356,6 +1724,7 @@ query Hero {
             name: "id",
             alias: None,
             arguments: Vec::new(),
+            directives: Vec::new(),
             children: Vec::new(),
         };
         assert_eq!(id, Some(&expected));
@@ -1370,12 +1739,14 @@ query Hero {
             name: "friends",
             alias: None,
             arguments: Vec::new(),
+            directives: Vec::new(),
             children: vec![
                 ChildSelection {
                     inner: LookAheadSelection {
                         name: "id",
                         alias: None,
                         arguments: Vec::new(),
+                        directives: Vec::new(),
                         children: Vec::new(),
                     },
                     applies_for: Applies::All,
@@ -1385,6 +1756,7 @@ query Hero {
                         name: "name",
                         alias: None,
                         arguments: Vec::new(),
+                        directives: Vec::new(),
                         children: Vec::new(),
                     },
                     applies_for: Applies::All,
@@ -1429,16 +1801,19 @@ fragment heroFriendNames on Hero {
                 name: "hero",
                 alias: None,
                 arguments: Vec::new(),
+                directives: Vec::new(),
                 children: vec![ChildSelection {
                     inner: LookAheadSelection {
                         name: "friends",
                         alias: None,
                         arguments: Vec::new(),
+                        directives: Vec::new(),
                         children: vec![ChildSelection {
                             inner: LookAheadSelection {
                                 name: "name",
                                 alias: None,
                                 arguments: Vec::new(),
+                                directives: Vec::new(),
                                 children: Vec::new(),
                             },
                             applies_for: Applies::All,
@@ -1542,4 +1917,67 @@ query Hero {
             panic!("No Operation found");
         }
     }
+
+    #[test]
+    fn check_directives() {
+        let docs = parse_document_source::<DefaultScalarValue>(
+            "
+query Hero($withFriends: Boolean!, $len: Int) {
+    hero {
+        name @cut(maxLen: $len) @uppercase
+        friends @include(if: $withFriends) { id }
+    }
+}",
+        )
+        .unwrap();
+        let fragments = extract_fragments(&docs);
+
+        if let crate::ast::Definition::Operation(ref op) = docs[0] {
+            let vars = graphql_vars! {
+                "withFriends": true,
+                "len": 3,
+            };
+            let look_ahead = LookAheadSelection::build_from_selection(
+                &op.item.selection_set[0],
+                &vars,
+                &fragments,
+            )
+            .unwrap();
+
+            assert!(look_ahead.directives().is_empty());
+
+            let name = look_ahead.select_child("name").unwrap();
+            assert!(name.has_directive("uppercase"));
+            assert!(!name.has_directive("include"));
+            let names = name
+                .directives()
+                .iter()
+                .map(LookAheadDirective::name)
+                .collect::<Vec<_>>();
+            assert_eq!(names, vec!["cut", "uppercase"]);
+            let cut = name.directive("cut").unwrap();
+            assert_eq!(
+                cut.argument("maxLen").map(LookAheadArgument::value),
+                Some(&LookAheadValue::Scalar(&DefaultScalarValue::Int(3))),
+            );
+            assert!(name.directive("uppercase").unwrap().arguments().is_empty());
+
+            let concrete = look_ahead.for_explicit_type("Human");
+            let friends = concrete
+                .select_child("friends")
+                .map(|f| f.directive("include").cloned());
+            assert_eq!(
+                friends,
+                Some(Some(LookAheadDirective {
+                    name: "include",
+                    arguments: vec![LookAheadArgument {
+                        name: "if",
+                        value: LookAheadValue::Scalar(&DefaultScalarValue::Boolean(true)),
+                    }],
+                })),
+            );
+        } else {
+            panic!("No Operation found");
+        }
+    }
 }
@@ -0,0 +1,251 @@
+use std::{fmt, sync::Arc, time::Duration};
+
+use crate::{ast::OperationType, validation::RuleError};
+
+use super::pii::PiiFields;
+
+/// Measure of an operation, computed while checking it against [`Limits`].
+///
+/// [`Limits`]: super::Limits
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct OperationMeasure {
+    pub(super) depth: usize,
+    pub(super) aliases: usize,
+    pub(super) cost: usize,
+}
+
+impl OperationMeasure {
+    /// Returns the nesting depth of fields in the operation, where the root
+    /// fields have the depth of `1`.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the number of aliased fields in the operation.
+    pub fn aliases(&self) -> usize {
+        self.aliases
+    }
+
+    /// Returns the cost of the operation, being the sum of the costs of the
+    /// fields it selects, with the fields of fragments counted once per their
+    /// spread.
+    ///
+    /// See [`Limits::max_cost()`] for how the cost of a field is computed.
+    ///
+    /// [`Limits::max_cost()`]: super::Limits::max_cost
+    pub fn cost(&self) -> usize {
+        self.cost
+    }
+
+    /// Returns the values of this [`OperationMeasure`] along with their
+    /// conventional names (`graphql.depth`, `graphql.aliases` and
+    /// `graphql.cost`), suitable as `tracing` span fields or metric names.
+    pub fn fields(&self) -> [(&'static str, usize); 3] {
+        [
+            ("graphql.depth", self.depth),
+            ("graphql.aliases", self.aliases),
+            ("graphql.cost", self.cost),
+        ]
+    }
+
+    /// Merges the `other` [`OperationMeasure`] of a sibling selection into
+    /// this one.
+    pub(super) fn merge(self, other: Self) -> Self {
+        Self {
+            depth: self.depth.max(other.depth),
+            aliases: self.aliases.saturating_add(other.aliases),
+            cost: self.cost.saturating_add(other.cost),
+        }
+    }
+}
+
+/// Sink receiving the [`OperationMeasure`] of every executed operation, so
+/// usage trends may be monitored, and not only the operations rejected by the
+/// [`Limits`].
+///
+/// Set with [`ExecutorOptions::metrics_sink()`]. Once set, operations are
+/// measured even if no [`Limits`] are set.
+///
+/// ```
+/// # use std::sync::atomic::{AtomicUsize, Ordering};
+/// #
+/// # use juniper::{
+/// #     execute_sync, graphql_object, graphql_vars, EmptyMutation, EmptySubscription,
+/// #     ExecutorOptions, MetricsSink, OperationMeasure, OperationType, RootNode,
+/// # };
+/// #
+/// #[derive(Debug, Default)]
+/// struct MaxCost(AtomicUsize);
+///
+/// impl MetricsSink for MaxCost {
+///     fn record_measure(&self, _: Option<&str>, _: OperationType, measure: &OperationMeasure) {
+///         self.0.fetch_max(measure.cost(), Ordering::Relaxed);
+///     }
+/// }
+///
+/// struct Query;
+///
+/// #[graphql_object]
+/// impl Query {
+///     fn answer() -> i32 {
+///         42
+///     }
+/// }
+///
+/// static MAX_COST: MaxCost = MaxCost(AtomicUsize::new(0));
+///
+/// let schema = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::<()>::new())
+///     .with_executor_options(ExecutorOptions::new().metrics_sink(&MAX_COST));
+///
+/// execute_sync("{ a: answer b: answer }", None, &schema, &graphql_vars! {}, &()).unwrap();
+/// assert_eq!(MAX_COST.0.load(Ordering::Relaxed), 2);
+/// ```
+///
+/// [`ExecutorOptions::metrics_sink()`]: super::ExecutorOptions::metrics_sink
+/// [`Limits`]: super::Limits
+pub trait MetricsSink: fmt::Debug + Send + Sync {
+    /// Records the [`OperationMeasure`] of the operation with the given
+    /// `operation_name` and `operation_type`, being about to be executed.
+    ///
+    /// Called for operations exceeding the [`Limits`] too, right before
+    /// rejecting them.
+    ///
+    /// [`Limits`]: super::Limits
+    fn record_measure(
+        &self,
+        operation_name: Option<&str>,
+        operation_type: OperationType,
+        measure: &OperationMeasure,
+    );
+
+    /// Records the [`PiiFields`] selected by the operation with the given
+    /// `operation_name` and `operation_type`, being about to be executed.
+    ///
+    /// Called only for operations selecting at least one field tagged as
+    /// containing personally identifiable information. Does nothing by
+    /// default.
+    fn record_pii_fields(
+        &self,
+        operation_name: Option<&str>,
+        operation_type: OperationType,
+        fields: &PiiFields,
+    ) {
+        let _ = (operation_name, operation_type, fields);
+    }
+
+    /// Records the number of `active` subscriptions, right after one of them
+    /// has been started or finished, along with the number of active
+    /// subscriptions sharing its `key` (as a `(key, active)` pair), if it has
+    /// any.
+    ///
+    /// Called by subscription coordinators tracking their active
+    /// subscriptions, so this may be exposed as gauges. Does nothing by
+    /// default.
+    fn record_active_subscriptions(&self, active: usize, key: Option<(&str, usize)>) {
+        let _ = (active, key);
+    }
+
+    /// Records the `warnings` found while validating a document, being the
+    /// violations of the rules downgraded to the [`RuleSeverity::Warning`].
+    ///
+    /// Called only if there are such warnings. Does nothing by default.
+    ///
+    /// [`RuleSeverity::Warning`]: crate::RuleSeverity::Warning
+    fn record_validation_warnings(&self, warnings: &[RuleError]) {
+        let _ = warnings;
+    }
+
+    /// Records the time spent in every validation rule while validating a
+    /// document, along with the rule name (like `NoFragmentCycles`, or the
+    /// type name of a custom rule).
+    ///
+    /// Called only if enabled with [`ExecutorOptions::time_validation_rules()`].
+    /// Does nothing by default.
+    ///
+    /// [`ExecutorOptions::time_validation_rules()`]: super::ExecutorOptions::time_validation_rules
+    fn record_validation_timings(&self, timings: &[(&'static str, Duration)]) {
+        let _ = timings;
+    }
+
+    /// Records a lookup of a document in the [`DocumentCache`] of a schema,
+    /// being either a `hit` or a miss, so its hit ratio may be monitored.
+    ///
+    /// Does nothing by default.
+    ///
+    /// [`DocumentCache`]: super::DocumentCache
+    fn record_document_cache(&self, hit: bool) {
+        let _ = hit;
+    }
+}
+
+impl<T: MetricsSink + ?Sized> MetricsSink for Arc<T> {
+    fn record_measure(
+        &self,
+        operation_name: Option<&str>,
+        operation_type: OperationType,
+        measure: &OperationMeasure,
+    ) {
+        (**self).record_measure(operation_name, operation_type, measure)
+    }
+
+    fn record_pii_fields(
+        &self,
+        operation_name: Option<&str>,
+        operation_type: OperationType,
+        fields: &PiiFields,
+    ) {
+        (**self).record_pii_fields(operation_name, operation_type, fields)
+    }
+
+    fn record_active_subscriptions(&self, active: usize, key: Option<(&str, usize)>) {
+        (**self).record_active_subscriptions(active, key)
+    }
+
+    fn record_validation_warnings(&self, warnings: &[RuleError]) {
+        (**self).record_validation_warnings(warnings)
+    }
+
+    fn record_validation_timings(&self, timings: &[(&'static str, Duration)]) {
+        (**self).record_validation_timings(timings)
+    }
+
+    fn record_document_cache(&self, hit: bool) {
+        (**self).record_document_cache(hit)
+    }
+}
+
+impl<T: MetricsSink + ?Sized> MetricsSink for &T {
+    fn record_measure(
+        &self,
+        operation_name: Option<&str>,
+        operation_type: OperationType,
+        measure: &OperationMeasure,
+    ) {
+        (**self).record_measure(operation_name, operation_type, measure)
+    }
+
+    fn record_pii_fields(
+        &self,
+        operation_name: Option<&str>,
+        operation_type: OperationType,
+        fields: &PiiFields,
+    ) {
+        (**self).record_pii_fields(operation_name, operation_type, fields)
+    }
+
+    fn record_active_subscriptions(&self, active: usize, key: Option<(&str, usize)>) {
+        (**self).record_active_subscriptions(active, key)
+    }
+
+    fn record_validation_warnings(&self, warnings: &[RuleError]) {
+        (**self).record_validation_warnings(warnings)
+    }
+
+    fn record_validation_timings(&self, timings: &[(&'static str, Duration)]) {
+        (**self).record_validation_timings(timings)
+    }
+
+    fn record_document_cache(&self, hit: bool) {
+        (**self).record_document_cache(hit)
+    }
+}
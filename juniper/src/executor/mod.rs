@@ -1,14 +1,19 @@
 //! Resolve the document to values
 
 use std::{
+    any::Any,
     borrow::Cow,
     cmp::Ordering,
     collections::HashMap,
+    convert::TryFrom as _,
     fmt::{Debug, Display},
+    hash::{Hash, Hasher as _},
+    iter, mem,
     sync::{Arc, RwLock},
+    time::Instant,
 };
 
-use fnv::FnvHashMap;
+use fnv::{FnvHashMap, FnvHasher};
 use futures::Stream;
 
 use crate::{
@@ -16,35 +21,93 @@ use crate::{
         Definition, Document, Fragment, FromInputValue, InputValue, Operation, OperationType,
         Selection, ToInputValue, Type,
     },
+    dataloader::{DataLoader, Dispatcher, Loader},
     parser::{SourcePosition, Spanning},
     schema::{
         meta::{
-            Argument, DeprecationStatus, EnumMeta, EnumValue, Field, InputObjectMeta,
+            Argument, Complexity, DeprecationStatus, EnumMeta, EnumValue, Field, InputObjectMeta,
             InterfaceMeta, ListMeta, MetaType, NullableMeta, ObjectMeta, PlaceholderMeta,
             ScalarMeta, UnionMeta,
         },
-        model::{RootNode, SchemaType, TypeType},
+        model::{RootNode, SchemaError, SchemaType, TypeType},
+        schema::IntrospectionRoot,
     },
     types::{
         async_await::{GraphQLTypeAsync, GraphQLValueAsync},
         base::{GraphQLType, GraphQLValue},
         name::Name,
-        subscriptions::{GraphQLSubscriptionType, GraphQLSubscriptionValue},
+        subscriptions::{ExecutionOutput, GraphQLSubscriptionType, GraphQLSubscriptionValue},
     },
     value::{DefaultScalarValue, ParseScalarValue, ScalarValue, Value},
-    GraphQLError,
+    OperationError, RequestError,
+};
+
+pub use juniper_codegen::IntoFieldError;
+
+pub(crate) use self::{
+    entity::EntityResolvers,
+    incremental::{
+        incremental_stream, resolve_known, resolve_until, EarlyValue, Known, KnownValues,
+        Reservation, StreamArgs, SubsequentPayloads,
+    },
+};
+
+#[cfg(feature = "rayon")]
+pub(crate) use self::strategy::{block_on, SyncParallel};
+
+pub(crate) use self::limits::{usize_value, Measurer};
+
+use self::{
+    limits::WorkSpent, n_plus_one::FieldCounts, pool::ObjectPool, slow_query::FieldTimings,
 };
 
 pub use self::{
+    document_cache::{CachedDocument, DocumentCache, LruDocumentCache},
+    entity::EntityResolver,
+    fragment::{execute_validated_fragment, execute_validated_fragment_async, get_fragment},
+    incremental::{DeferredPayload, IncrementalPayload, IncrementalStream, StreamedPayload},
+    limits::Limits,
     look_ahead::{
-        Applies, ChildSelection, ConcreteLookAheadSelection, LookAheadArgument, LookAheadMethods,
-        LookAheadSelection, LookAheadValue,
+        Applies, ChildSelection, ConcreteLookAheadSelection, LookAheadArgument, LookAheadDirective,
+        LookAheadMethods, LookAheadSelection, LookAheadValue,
     },
+    metrics::{MetricsSink, OperationMeasure},
+    n_plus_one::{NPlusOneHook, RepeatedField},
+    options::ExecutorOptions,
     owned_executor::OwnedExecutor,
+    parent::ParentValue,
+    pii::{PiiField, PiiFields},
+    projection::{Projection, ProjectionMap},
+    query_log::{QueryLogEntry, QueryLogger},
+    slow_query::{SlowQuery, SlowQueryHook},
+    state::RequestState,
+    strategy::{ExecutionStrategy, Parallel, Serial},
+    transform::{ResponseField, ResponseTransform},
 };
 
+#[cfg(feature = "rayon")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+pub use self::strategy::Rayon;
+
+mod document_cache;
+mod entity;
+mod fragment;
+mod incremental;
+mod limits;
 mod look_ahead;
+mod metrics;
+mod n_plus_one;
+mod options;
 mod owned_executor;
+mod parent;
+mod pii;
+mod pool;
+mod projection;
+mod query_log;
+mod slow_query;
+mod state;
+mod strategy;
+mod transform;
 
 /// A type registry used to build schemas
 ///
@@ -54,6 +117,13 @@ mod owned_executor;
 pub struct Registry<'r, S = DefaultScalarValue> {
     /// Currently registered types
     pub types: FnvHashMap<Name, MetaType<'r, S>>,
+
+    /// Paths of Rust types registered under each GraphQL type name, the first
+    /// one being the type defining it.
+    rust_types: FnvHashMap<Name, Vec<&'static str>>,
+
+    /// [`SchemaError`]s detected while registering types.
+    errors: Vec<SchemaError>,
 }
 
 #[allow(missing_docs)]
@@ -61,6 +131,7 @@ pub struct Registry<'r, S = DefaultScalarValue> {
 pub enum FieldPath<'a> {
     Root(SourcePosition),
     Field(&'a str, SourcePosition, Arc<FieldPath<'a>>),
+    Index(usize, Arc<FieldPath<'a>>),
 }
 
 /// Query execution engine
@@ -80,20 +151,61 @@ where
     schema: &'a SchemaType<'a, S>,
     context: &'a CtxT,
     errors: &'r RwLock<Vec<ExecutionError<S>>>,
+    pool: &'r ObjectPool<S>,
+    deadline: Option<Instant>,
+    work_budget: Option<usize>,
+    operation_type: OperationType,
     field_path: Arc<FieldPath<'a>>,
+    parent: Option<&'r ParentValue<'r>>,
+    early_value: Option<&'r EarlyValue<S>>,
+    subsequent_payload: Option<usize>,
+    state: Arc<RequestState>,
 }
 
 /// Error type for errors that occur during query execution
 ///
 /// All execution errors contain the source position in the query of the field
 /// that failed to resolve. It also contains the field stack.
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct ExecutionError<S> {
     location: SourcePosition,
-    path: Vec<String>,
+    path: Vec<PathSegment>,
     error: FieldError<S>,
 }
 
+/// Segment of an [`ExecutionError::path()`].
+///
+/// Serializes either as a string (for fields) or as an integer (for list
+/// indices), as required by the [GraphQL specification][1].
+///
+/// [1]: https://spec.graphql.org/October2021#sec-Errors.Error-result-format
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum PathSegment {
+    /// Response name (alias or name) of a field.
+    Field(String),
+
+    /// Index of an element in a list.
+    Index(usize),
+}
+
+impl<'a> From<&'a str> for PathSegment {
+    fn from(name: &'a str) -> Self {
+        Self::Field(name.to_owned())
+    }
+}
+
+impl From<String> for PathSegment {
+    fn from(name: String) -> Self {
+        Self::Field(name)
+    }
+}
+
+impl From<usize> for PathSegment {
+    fn from(index: usize) -> Self {
+        Self::Index(index)
+    }
+}
+
 impl<S> Eq for ExecutionError<S> where Self: PartialEq {}
 
 impl<S> ExecutionError<S> {
@@ -153,6 +265,7 @@ where
 pub struct FieldError<S = DefaultScalarValue> {
     message: String,
     extensions: Value<S>,
+    additional: Vec<FieldError<S>>,
 }
 
 impl<T: Display, S> From<T> for FieldError<S> {
@@ -160,6 +273,7 @@ impl<T: Display, S> From<T> for FieldError<S> {
         Self {
             message: e.to_string(),
             extensions: Value::Null,
+            additional: Vec::new(),
         }
     }
 }
@@ -200,6 +314,7 @@ impl<S> FieldError<S> {
         Self {
             message: e.to_string(),
             extensions,
+            additional: Vec::new(),
         }
     }
 
@@ -217,6 +332,39 @@ impl<S> FieldError<S> {
         &self.extensions
     }
 
+    /// Adds the given `error` to be reported alongside this [`FieldError`],
+    /// at the same path and location in the response.
+    ///
+    /// ```rust
+    /// use juniper::FieldError;
+    ///
+    /// # let _: FieldError =
+    /// FieldError::from("Name is too short").with_additional("Email is invalid".into());
+    /// ```
+    #[must_use]
+    pub fn with_additional(mut self, error: FieldError<S>) -> Self {
+        self.additional.extend(error.into_errors());
+        self
+    }
+
+    /// Returns the errors reported alongside this [`FieldError`].
+    #[must_use]
+    pub fn additional(&self) -> &[FieldError<S>] {
+        &self.additional
+    }
+
+    /// Splits this [`FieldError`] into the list of all the errors it reports,
+    /// starting with itself and followed by its [`FieldError::additional()`]
+    /// ones.
+    #[must_use]
+    pub fn into_errors(mut self) -> Vec<FieldError<S>> {
+        let additional = mem::take(&mut self.additional);
+        let mut errors = Vec::with_capacity(additional.len() + 1);
+        errors.push(self);
+        errors.extend(additional);
+        errors
+    }
+
     /// Maps the [`ScalarValue`] type of this [`FieldError`] into the specified
     /// one.
     #[must_use]
@@ -228,15 +376,22 @@ impl<S> FieldError<S> {
         FieldError {
             message: self.message,
             extensions: self.extensions.map_scalar_value(),
+            additional: self
+                .additional
+                .into_iter()
+                .map(FieldError::map_scalar_value)
+                .collect(),
         }
     }
 
     /// Maps the [`FieldError::message`] with the given function.
+    ///
+    /// Doesn't affect the [`FieldError::additional()`] errors.
     #[must_use]
     pub fn map_message(self, f: impl FnOnce(String) -> String) -> Self {
         Self {
             message: f(self.message),
-            extensions: self.extensions,
+            ..self
         }
     }
 }
@@ -259,6 +414,10 @@ pub type Variables<S = DefaultScalarValue> = HashMap<String, InputValue<S>>;
 ///
 /// Any custom error type should implement this trait to convert itself into a
 /// [`FieldError`].
+///
+/// A [`Vec`] of such errors is converted into a single [`FieldError`] with the
+/// [`FieldError::additional()`] ones, so a resolver may report several errors at
+/// once.
 pub trait IntoFieldError<S = DefaultScalarValue> {
     /// Performs the custom conversion into a [`FieldError`].
     #[must_use]
@@ -295,6 +454,20 @@ impl<'a, S> IntoFieldError<S> for Cow<'a, str> {
     }
 }
 
+/// Reports all the errors of the [`Vec`] at the path of the failed field, in
+/// their order.
+///
+/// An empty [`Vec`] is reported as a single `"Unknown error"`.
+impl<S, E: IntoFieldError<S>> IntoFieldError<S> for Vec<E> {
+    fn into_field_error(self) -> FieldError<S> {
+        let mut errors = self.into_iter().map(IntoFieldError::into_field_error);
+        let first = errors
+            .next()
+            .unwrap_or_else(|| FieldError::from("Unknown error"));
+        errors.fold(first, FieldError::with_additional)
+    }
+}
+
 #[doc(hidden)]
 pub trait IntoResolvable<'a, S, T, C>
 where
@@ -564,7 +737,15 @@ where
             schema: self.schema,
             context: ctx,
             errors: self.errors,
+            pool: self.pool,
+            deadline: self.deadline,
+            work_budget: self.work_budget,
+            operation_type: self.operation_type,
             field_path: self.field_path.clone(),
+            parent: self.parent,
+            early_value: self.early_value,
+            subsequent_payload: self.subsequent_payload,
+            state: Arc::clone(&self.state),
         }
     }
 
@@ -592,11 +773,42 @@ where
             schema: self.schema,
             context: self.context,
             errors: self.errors,
+            pool: self.pool,
+            deadline: self.deadline,
+            work_budget: self.work_budget,
+            operation_type: self.operation_type,
             field_path: Arc::new(FieldPath::Field(
                 field_alias,
                 location,
                 Arc::clone(&self.field_path),
             )),
+            parent: self.parent,
+            early_value: None,
+            subsequent_payload: self.subsequent_payload,
+            state: Arc::clone(&self.state),
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn index_sub_executor(&self, index: usize) -> Executor<'_, 'a, CtxT, S> {
+        Executor {
+            fragments: self.fragments,
+            variables: self.variables,
+            current_selection_set: self.current_selection_set,
+            parent_selection_set: self.parent_selection_set,
+            current_type: self.current_type.clone(),
+            schema: self.schema,
+            context: self.context,
+            errors: self.errors,
+            pool: self.pool,
+            deadline: self.deadline,
+            work_budget: self.work_budget,
+            operation_type: self.operation_type,
+            field_path: Arc::new(FieldPath::Index(index, Arc::clone(&self.field_path))),
+            parent: self.parent,
+            early_value: None,
+            subsequent_payload: self.subsequent_payload,
+            state: Arc::clone(&self.state),
         }
     }
 
@@ -618,8 +830,121 @@ where
             schema: self.schema,
             context: self.context,
             errors: self.errors,
+            pool: self.pool,
+            deadline: self.deadline,
+            work_budget: self.work_budget,
+            operation_type: self.operation_type,
             field_path: self.field_path.clone(),
+            parent: self.parent,
+            early_value: None,
+            subsequent_payload: self.subsequent_payload,
+            state: Arc::clone(&self.state),
+        }
+    }
+
+    /// Returns the [`ExecutionStrategy`] of resolving the fields of the
+    /// executed operation.
+    pub(crate) fn strategy(&self) -> &dyn ExecutionStrategy {
+        #[cfg(feature = "rayon")]
+        if self.operation_type == OperationType::Query && self.state.get::<SyncParallel>().is_some()
+        {
+            return &Rayon;
+        }
+        self.schema.executor_options().strategy(self.operation_type)
+    }
+
+    /// Returns the [`ParentValue`] of the object the field being resolved is
+    /// resolved on, if any.
+    pub(crate) fn parent_value(&self) -> Option<&'r ParentValue<'r>> {
+        self.parent
+    }
+
+    /// Makes this [`Executor`] resolve the fields of the given `parent`
+    /// object.
+    pub(crate) fn with_parent(mut self, parent: &'r ParentValue<'r>) -> Self {
+        self.parent = Some(parent);
+        self
+    }
+
+    /// Makes this [`Executor`] collect its errors into the given `errors`, so
+    /// they're reported along with a deferred fragment.
+    pub(crate) fn with_errors(mut self, errors: &'r RwLock<Vec<ExecutionError<S>>>) -> Self {
+        self.errors = errors;
+        self
+    }
+
+    /// Returns the [`SubsequentPayloads`] of the executed operation, if it's
+    /// delivered incrementally, so the `@defer` and `@stream` directives are
+    /// honored.
+    pub(crate) fn subsequent_payloads(&self) -> Option<&SubsequentPayloads<S>>
+    where
+        S: ScalarValue + Send + Sync,
+    {
+        self.state.get()
+    }
+
+    /// Makes this [`Executor`] publish the resolved value into the given
+    /// `early` slot as soon as it's known, while the deferred fragments and
+    /// the streamed list items nested inside it are still being resolved.
+    pub(crate) fn with_early_value(mut self, early: &'r EarlyValue<S>) -> Self {
+        self.early_value = Some(early);
+        self
+    }
+
+    /// Returns the slot to publish the resolved value into as soon as it's
+    /// known, if any.
+    pub(crate) fn early_value(&self) -> Option<&'r EarlyValue<S>> {
+        self.early_value
+    }
+
+    /// Makes this [`Executor`] resolve the value delivered with the reserved
+    /// subsequent payload with the given `id`, so the payloads nested inside
+    /// it follow it.
+    pub(crate) fn with_subsequent_payload(mut self, id: usize) -> Self {
+        self.subsequent_payload = Some(id);
+        self
+    }
+
+    /// Returns the ID of the reserved subsequent payload the resolved value is
+    /// delivered with, if any.
+    pub(crate) fn subsequent_payload(&self) -> Option<usize> {
+        self.subsequent_payload
+    }
+
+    /// Returns the path of the currently resolved object in the response.
+    pub(crate) fn current_path(&self) -> Vec<PathSegment> {
+        let mut path = Vec::new();
+        self.field_path.construct_path(&mut path);
+        path
+    }
+
+    /// Indicates whether the [`Limits::timeout()`] of the executed operation
+    /// has elapsed already, so no more fields should be resolved.
+    pub(crate) fn is_timed_out(&self) -> bool {
+        self.deadline.is_some_and(|d| Instant::now() >= d)
+    }
+
+    /// Spends the given number of work `units` on the executed operation,
+    /// erroring if its [`Limits::max_work()`] budget is exceeded, so no more
+    /// fields should be resolved.
+    pub(crate) fn spend_work(&self, units: usize) -> Result<(), FieldError<S>>
+    where
+        S: ScalarValue,
+    {
+        let budget = match self.work_budget {
+            Some(budget) => budget,
+            None => return Ok(()),
+        };
+        if self.state.get_or_default::<WorkSpent>().spend(units) <= budget {
+            return Ok(());
         }
+        Err(FieldError::new(
+            format!("Operation exceeded its work budget of {}", budget),
+            graphql_value!({
+                "code": "WORK_BUDGET_EXCEEDED",
+                "budget": (i32::try_from(budget).unwrap_or(i32::MAX)),
+            }),
+        ))
     }
 
     /// `Executor`'s current selection set
@@ -635,6 +960,106 @@ where
         self.context
     }
 
+    /// Returns the closest ancestor object of type `T` of the field being
+    /// resolved, including the object this field is resolved on.
+    ///
+    /// Only the objects exposing their Rust values via
+    /// [`GraphQLValue::as_any()`] may be returned, which is done for all the
+    /// non-generic [`graphql_object`]s and [`GraphQLObject`]s.
+    ///
+    /// Always returns [`None`] in the [`Executor`]s obtained from an
+    /// [`OwnedExecutor`].
+    ///
+    /// ```
+    /// # use juniper::{
+    /// #     execute_sync, graphql_object, graphql_value, graphql_vars, EmptyMutation,
+    /// #     EmptySubscription, Executor, RootNode, ScalarValue,
+    /// # };
+    /// #
+    /// struct User {
+    ///     is_admin: bool,
+    /// }
+    ///
+    /// #[graphql_object]
+    /// impl User {
+    ///     fn post() -> Post {
+    ///         Post
+    ///     }
+    /// }
+    ///
+    /// struct Post;
+    ///
+    /// #[graphql_object]
+    /// impl Post {
+    ///     // Deeply nested resolver knowing about its ancestor `User`.
+    ///     fn can_edit<S: ScalarValue>(executor: &Executor<'_, '_, (), S>) -> bool {
+    ///         executor.parent::<User>().is_some_and(|u| u.is_admin)
+    ///     }
+    /// }
+    ///
+    /// struct Query;
+    ///
+    /// #[graphql_object]
+    /// impl Query {
+    ///     fn user() -> User {
+    ///         User { is_admin: true }
+    ///     }
+    /// }
+    ///
+    /// let schema = RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new());
+    ///
+    /// assert_eq!(
+    ///     execute_sync("{ user { post { canEdit } } }", None, &schema, &graphql_vars! {}, &()),
+    ///     Ok((graphql_value!({"user": {"post": {"canEdit": true}}}), vec![])),
+    /// );
+    /// ```
+    ///
+    /// [`GraphQLObject`]: derive@crate::GraphQLObject
+    /// [`graphql_object`]: macro@crate::graphql_object
+    pub fn parent<T: Any>(&self) -> Option<&'r T> {
+        self.parents().find_map(ParentValue::downcast_ref)
+    }
+
+    /// Iterates over the ancestor objects of the field being resolved,
+    /// starting from the object this field is resolved on, and up to the root
+    /// of the operation.
+    ///
+    /// Always returns an empty [`Iterator`] in the [`Executor`]s obtained from
+    /// an [`OwnedExecutor`].
+    pub fn parents(&self) -> impl Iterator<Item = &'r ParentValue<'r>> {
+        iter::successors(self.parent, |p| p.parent())
+    }
+
+    /// Returns the [`RequestState`] of the executed operation, shared between
+    /// all its resolvers.
+    ///
+    /// Prefer it over wrapping the whole [`Context`] into a lock for keeping
+    /// the state mutated while resolving an operation, as the latter
+    /// serializes the resolution of its fields.
+    ///
+    /// [`Context`]: crate::Context
+    pub fn state(&self) -> &RequestState {
+        &self.state
+    }
+
+    /// Returns the [`DataLoader`] of the executed operation, loading its values
+    /// with the [`Loader`] created by the `init` function on the first access.
+    ///
+    /// An operation has a single [`DataLoader`] per [`Loader`] type. See the
+    /// [`dataloader`] module for details.
+    ///
+    /// [`dataloader`]: crate::dataloader
+    pub fn data_loader<K, V, L, F>(&self, init: F) -> &DataLoader<K, V, L>
+    where
+        K: Clone + Eq + Hash + Send + Sync + 'static,
+        V: Clone + Send + Sync + 'static,
+        L: Loader<K, V>,
+        F: FnOnce() -> L,
+    {
+        self.state
+            .get_or_init(|| DataLoader::new(init(), self.state.get::<Dispatcher>()))
+    }
+
     /// The currently executing schema
     pub fn schema(&self) -> &'a SchemaType<S> {
         self.schema
@@ -650,6 +1075,12 @@ where
         self.variables
     }
 
+    /// Returns the [`ObjectPool`] of the executed operation, to allocate the
+    /// resolved objects from.
+    pub(crate) fn object_pool(&self) -> &ObjectPool<S> {
+        self.pool
+    }
+
     #[doc(hidden)]
     pub fn fragment_by_name<'s>(&'s self, name: &str) -> Option<&'s Fragment<'a, S>> {
         self.fragments.get(name)
@@ -660,23 +1091,68 @@ where
         self.field_path.location()
     }
 
+    /// Counts the resolution of the current field, if it has a selection set
+    /// and N+1 loading is detected.
+    pub(crate) fn count_field_resolution(&self) {
+        if self.current_selection_set.is_none()
+            || self.schema.executor_options().n_plus_one().is_none()
+        {
+            return;
+        }
+        let mut names = Vec::new();
+        self.field_path.construct_field_names(&mut names);
+        self.state
+            .get_or_default::<FieldCounts>()
+            .increment(names.join("."));
+    }
+
+    /// Starts timing the current field, if slow queries are detected.
+    pub(crate) fn start_field_timer(&self) -> Option<Instant> {
+        self.schema
+            .executor_options()
+            .slow_query()
+            .map(|_| Instant::now())
+    }
+
+    /// Records the time elapsed since the `started` instant returned by the
+    /// [`Executor::start_field_timer()`] as the duration of the current field.
+    pub(crate) fn record_field_timer(&self, started: Option<Instant>) {
+        if let Some(started) = started {
+            self.state
+                .get_or_default::<FieldTimings>()
+                .record(started.elapsed(), || {
+                    let mut path = Vec::new();
+                    self.field_path.construct_path(&mut path);
+                    path
+                });
+        }
+    }
+
     /// Add an error to the execution engine at the current executor location
     pub fn push_error(&self, error: FieldError<S>) {
         self.push_error_at(error, *self.location());
     }
 
     /// Add an error to the execution engine at a specific location
+    ///
+    /// The [`FieldError::additional()`] errors are added as separate ones at
+    /// the same location.
     pub fn push_error_at(&self, error: FieldError<S>, location: SourcePosition) {
         let mut path = Vec::new();
         self.field_path.construct_path(&mut path);
 
         let mut errors = self.errors.write().unwrap();
 
-        errors.push(ExecutionError {
+        errors.extend(error.into_errors().into_iter().map(|error| ExecutionError {
             location,
-            path,
+            path: path.clone(),
             error,
-        });
+        }));
+    }
+
+    /// Adds the given already located `errors` to the execution engine.
+    pub(crate) fn push_errors(&self, errors: impl IntoIterator<Item = ExecutionError<S>>) {
+        self.errors.write().unwrap().extend(errors);
     }
 
     /// Returns new [`ExecutionError`] at current location
@@ -695,12 +1171,18 @@ where
     ///
     /// This allows seeing the whole selection and perform operations
     /// affecting the children.
+    ///
+    /// The arguments of the children are evaluated against the variables of
+    /// the operation, and complemented with the default values of the
+    /// arguments omitted in the operation, so they may be converted into Rust
+    /// types right away via [`LookAheadMethods::argument_value()`].
     pub fn look_ahead(&'a self) -> LookAheadSelection<'a, S> {
-        let field_name = match *self.field_path {
-            FieldPath::Field(x, ..) => x,
-            FieldPath::Root(_) => unreachable!(),
-        };
-        self.parent_selection_set
+        let field_name = self
+            .field_path
+            .field_name()
+            .expect("`look_ahead()` called outside of a field");
+        let mut selection = self
+            .parent_selection_set
             .and_then(|p| {
                 // Search the parent's fields to find this field within the set
                 let found_field = p.iter().find(|&x| {
@@ -728,6 +1210,7 @@ where
                     name: field_name,
                     alias: None,
                     arguments: Vec::new(),
+                    directives: Vec::new(),
                     children: Vec::new(),
                 };
 
@@ -743,7 +1226,33 @@ where
                     }
                 }
                 ret
-            })
+            });
+        if let Some(type_name) = self.current_type.innermost_concrete().name() {
+            selection.apply_defaults(self.schema, type_name, self.variables);
+        }
+        selection
+    }
+
+    /// Returns a stable hash of the sub-selection of the field being resolved,
+    /// suitable as a key of a cache maintained by the resolver itself.
+    ///
+    /// The hash covers the selected fields along with their aliases,
+    /// arguments (with variables substituted), type conditions and custom
+    /// directives, recursively, with fragments inlined and the fields excluded
+    /// by `@skip`/`@include` directives omitted. So the selections asking for
+    /// exactly the same data have the same hash, however they are written.
+    /// The arguments of the field itself are not covered.
+    ///
+    /// The hash is stable between runs and platforms, so it may be used as a
+    /// key of a shared cache.
+    ///
+    /// # Panics
+    ///
+    /// If called outside of a field, like [`Executor::look_ahead()`].
+    pub fn selection_hash(&'a self) -> u64 {
+        let mut hasher = FnvHasher::default();
+        self.look_ahead().hash_children(&mut hasher);
+        hasher.finish()
     }
 
     /// Create new `OwnedExecutor` and clone all current data
@@ -763,35 +1272,70 @@ where
             schema: self.schema,
             context: self.context,
             errors: RwLock::new(vec![]),
+            pool: ObjectPool::default(),
+            deadline: self.deadline,
+            work_budget: self.work_budget,
+            operation_type: self.operation_type,
             field_path: Arc::clone(&self.field_path),
+            state: Arc::clone(&self.state),
         }
     }
 }
 
 impl<'a> FieldPath<'a> {
-    fn construct_path(&self, acc: &mut Vec<String>) {
+    fn construct_path(&self, acc: &mut Vec<PathSegment>) {
         match self {
             FieldPath::Root(_) => (),
             FieldPath::Field(name, _, parent) => {
                 parent.construct_path(acc);
-                acc.push((*name).to_owned());
+                acc.push(PathSegment::Field((*name).to_owned()));
+            }
+            FieldPath::Index(idx, parent) => {
+                parent.construct_path(acc);
+                acc.push(PathSegment::Index(*idx));
             }
         }
     }
 
+    /// Collects the names of the fields this [`FieldPath`] points through,
+    /// skipping the list indices.
+    fn construct_field_names(&self, acc: &mut Vec<&'a str>) {
+        match self {
+            FieldPath::Root(_) => (),
+            FieldPath::Field(name, _, parent) => {
+                parent.construct_field_names(acc);
+                acc.push(name);
+            }
+            FieldPath::Index(_, parent) => parent.construct_field_names(acc),
+        }
+    }
+
     fn location(&self) -> &SourcePosition {
         match *self {
             FieldPath::Root(ref pos) | FieldPath::Field(_, ref pos, _) => pos,
+            FieldPath::Index(_, ref parent) => parent.location(),
+        }
+    }
+
+    /// Returns the name of the innermost field this [`FieldPath`] points into.
+    fn field_name(&self) -> Option<&'a str> {
+        match *self {
+            FieldPath::Root(_) => None,
+            FieldPath::Field(name, ..) => Some(name),
+            FieldPath::Index(_, ref parent) => parent.field_name(),
         }
     }
 }
 
 impl<S> ExecutionError<S> {
     #[doc(hidden)]
-    pub fn new(location: SourcePosition, path: &[&str], error: FieldError<S>) -> ExecutionError<S> {
+    pub fn new<P>(location: SourcePosition, path: &[P], error: FieldError<S>) -> ExecutionError<S>
+    where
+        P: Clone + Into<PathSegment>,
+    {
         ExecutionError {
             location,
-            path: path.iter().map(|s| (*s).to_owned()).collect(),
+            path: path.iter().cloned().map(Into::into).collect(),
             error,
         }
     }
@@ -806,8 +1350,9 @@ impl<S> ExecutionError<S> {
         &self.location
     }
 
-    /// The path of fields leading to the field that generated this error
-    pub fn path(&self) -> &[String] {
+    /// The path of fields and list indices leading to the field that
+    /// generated this error
+    pub fn path(&self) -> &[PathSegment] {
         &self.path
     }
 }
@@ -820,7 +1365,8 @@ pub fn execute_validated_query<'a, 'b, QueryT, MutationT, SubscriptionT, S>(
     root_node: &RootNode<QueryT, MutationT, SubscriptionT, S>,
     variables: &Variables<S>,
     context: &QueryT::Context,
-) -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+    limits: &Limits,
+) -> Result<(Value<S>, Vec<ExecutionError<S>>), RequestError<'a>>
 where
     S: ScalarValue,
     QueryT: GraphQLType<S>,
@@ -828,9 +1374,13 @@ where
     SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
 {
     if operation.item.operation_type == OperationType::Subscription {
-        return Err(GraphQLError::IsSubscription);
+        return Err(OperationError::IsSubscription.into());
     }
 
+    let deadline = limits.deadline();
+    let started = Instant::now();
+    let state = Arc::new(RequestState::new());
+
     let mut fragments = vec![];
     for def in document.iter() {
         if let Definition::Fragment(f) = def {
@@ -851,7 +1401,8 @@ where
     });
 
     let errors = RwLock::new(Vec::new());
-    let value;
+    let pool = ObjectPool::default();
+    let mut value;
 
     {
         let mut all_vars;
@@ -888,7 +1439,15 @@ where
             schema: &root_node.schema,
             context,
             errors: &errors,
+            pool: &pool,
+            deadline,
+            work_budget: limits.work_budget(),
+            operation_type: operation.item.operation_type,
             field_path: Arc::new(FieldPath::Root(operation.start)),
+            parent: None,
+            early_value: None,
+            subsequent_payload: None,
+            state: Arc::clone(&state),
         };
 
         value = match operation.item.operation_type {
@@ -900,6 +1459,23 @@ where
         };
     }
 
+    if let Some(detection) = root_node.schema.executor_options().slow_query() {
+        detection.check(
+            operation.item.name.as_ref().map(|n| n.item),
+            operation.item.operation_type,
+            started.elapsed(),
+            state.get::<FieldTimings>(),
+        );
+    }
+    if let Some(detection) = root_node.schema.executor_options().n_plus_one() {
+        detection.check(
+            operation.item.name.as_ref().map(|n| n.item),
+            state.get::<FieldCounts>(),
+        );
+    }
+
+    transform::apply(&root_node.schema, document, operation, &mut value);
+
     let mut errors = errors.into_inner().unwrap();
     errors.sort();
 
@@ -908,13 +1484,55 @@ where
 
 /// Create new `Executor` and start asynchronous query execution.
 /// Returns `IsSubscription` error if subscription is passed.
-pub async fn execute_validated_query_async<'a, 'b, QueryT, MutationT, SubscriptionT, S>(
+pub async fn execute_validated_query_async<'a, 'b, 'e, QueryT, MutationT, SubscriptionT, S>(
     document: &'b Document<'a, S>,
     operation: &'b Spanning<Operation<'_, S>>,
     root_node: &RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
     variables: &Variables<S>,
     context: &QueryT::Context,
-) -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+    limits: &Limits,
+) -> Result<(Value<S>, Vec<ExecutionError<S>>), RequestError<'e>>
+where
+    QueryT: GraphQLTypeAsync<S>,
+    QueryT::TypeInfo: Sync,
+    QueryT::Context: Sync,
+    MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+    MutationT::TypeInfo: Sync,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context> + Sync,
+    SubscriptionT::TypeInfo: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    execute_validated_query_async_with_state(
+        document,
+        operation,
+        root_node,
+        variables,
+        context,
+        limits,
+        Arc::new(RequestState::new()),
+    )
+    .await
+}
+
+/// Same as [`execute_validated_query_async()`], but executes the query with
+/// the given [`RequestState`], prepared by the caller.
+pub(crate) async fn execute_validated_query_async_with_state<
+    'a,
+    'b,
+    'e,
+    QueryT,
+    MutationT,
+    SubscriptionT,
+    S,
+>(
+    document: &'b Document<'a, S>,
+    operation: &'b Spanning<Operation<'_, S>>,
+    root_node: &RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+    limits: &Limits,
+    state: Arc<RequestState>,
+) -> Result<(Value<S>, Vec<ExecutionError<S>>), RequestError<'e>>
 where
     QueryT: GraphQLTypeAsync<S>,
     QueryT::TypeInfo: Sync,
@@ -926,9 +1544,12 @@ where
     S: ScalarValue + Send + Sync,
 {
     if operation.item.operation_type == OperationType::Subscription {
-        return Err(GraphQLError::IsSubscription);
+        return Err(OperationError::IsSubscription.into());
     }
 
+    let deadline = limits.deadline();
+    let started = Instant::now();
+
     let mut fragments = vec![];
     for def in document.iter() {
         if let Definition::Fragment(f) = def {
@@ -949,6 +1570,7 @@ where
     });
 
     let errors = RwLock::new(Vec::new());
+    let pool = ObjectPool::default();
     let value;
 
     {
@@ -974,6 +1596,11 @@ where
             OperationType::Subscription => unreachable!(),
         };
 
+        let dispatcher = state.get_or_default::<Dispatcher>();
+
+        let payloads = state.get::<SubsequentPayloads<S>>();
+        let early = payloads.map(|_| EarlyValue::new(None));
+
         let executor = Executor {
             fragments: &fragments
                 .iter()
@@ -986,22 +1613,79 @@ where
             schema: &root_node.schema,
             context,
             errors: &errors,
+            pool: &pool,
+            deadline,
+            work_budget: limits.work_budget(),
+            operation_type: operation.item.operation_type,
             field_path: Arc::new(FieldPath::Root(operation.start)),
+            parent: None,
+            early_value: early.as_ref(),
+            subsequent_payload: None,
+            state: Arc::clone(&state),
         };
 
-        value = match operation.item.operation_type {
-            OperationType::Query => {
-                executor
-                    .resolve_into_value_async(&root_node.query_info, &root_node)
-                    .await
-            }
-            OperationType::Mutation => {
-                executor
-                    .resolve_into_value_async(&root_node.mutation_info, &root_node.mutation_type)
-                    .await
-            }
-            OperationType::Subscription => unreachable!(),
+        let resolution = async {
+            let resolution = async {
+                match operation.item.operation_type {
+                    OperationType::Query => {
+                        executor
+                            .resolve_into_value_async(&root_node.query_info, &root_node)
+                            .await
+                    }
+                    OperationType::Mutation => {
+                        executor
+                            .resolve_into_value_async(
+                                &root_node.mutation_info,
+                                &root_node.mutation_type,
+                            )
+                            .await
+                    }
+                    OperationType::Subscription => unreachable!(),
+                }
+            };
+
+            // The operations delivered incrementally start with the initial
+            // payload as soon as its data is known, so nothing is left to be
+            // returned afterwards.
+            let mut value = None;
+            resolve_known(early.as_ref(), resolution, |known| match known {
+                Known::Finished(v) => value = Some(v),
+                Known::Early(mut data) => {
+                    transform::apply(&root_node.schema, document, operation, &mut data);
+                    let mut errors = mem::take(&mut *errors.write().unwrap());
+                    errors.sort();
+                    if let Some(payloads) = payloads {
+                        payloads.start(ExecutionOutput { data, errors });
+                    }
+                }
+            })
+            .await;
+            value
         };
+        value = dispatcher.drive(resolution).await;
+    }
+
+    if let Some(detection) = root_node.schema.executor_options().slow_query() {
+        detection.check(
+            operation.item.name.as_ref().map(|n| n.item),
+            operation.item.operation_type,
+            started.elapsed(),
+            state.get::<FieldTimings>(),
+        );
+    }
+    if let Some(detection) = root_node.schema.executor_options().n_plus_one() {
+        detection.check(
+            operation.item.name.as_ref().map(|n| n.item),
+            state.get::<FieldCounts>(),
+        );
+    }
+
+    let mut value = value.unwrap_or_else(Value::null);
+    if !state
+        .get::<SubsequentPayloads<S>>()
+        .is_some_and(SubsequentPayloads::is_started)
+    {
+        transform::apply(&root_node.schema, document, operation, &mut value);
     }
 
     let mut errors = errors.into_inner().unwrap();
@@ -1014,7 +1698,7 @@ where
 pub fn get_operation<'b, 'd, 'e, S>(
     document: &'b Document<'d, S>,
     operation_name: Option<&str>,
-) -> Result<&'b Spanning<Operation<'d, S>>, GraphQLError<'e>>
+) -> Result<&'b Spanning<Operation<'d, S>>, RequestError<'e>>
 where
     S: ScalarValue,
 {
@@ -1022,7 +1706,7 @@ where
     for def in document {
         if let Definition::Operation(op) = def {
             if operation_name.is_none() && operation.is_some() {
-                return Err(GraphQLError::MultipleOperationsProvided);
+                return Err(OperationError::MultipleOperationsProvided.into());
             }
 
             let move_op =
@@ -1035,7 +1719,7 @@ where
     }
     let op = match operation {
         Some(op) => op,
-        None => return Err(GraphQLError::UnknownOperationName),
+        None => return Err(OperationError::UnknownOperationName.into()),
     };
     Ok(op)
 }
@@ -1058,7 +1742,7 @@ pub async fn resolve_validated_subscription<
     root_node: &'r RootNode<'r, QueryT, MutationT, SubscriptionT, S>,
     variables: &Variables<S>,
     context: &'r QueryT::Context,
-) -> Result<(Value<ValuesStream<'r, S>>, Vec<ExecutionError<S>>), GraphQLError<'r>>
+) -> Result<(Value<ValuesStream<'r, S>>, Vec<ExecutionError<S>>), RequestError<'r>>
 where
     'r: 'exec_ref,
     'd: 'r,
@@ -1073,7 +1757,7 @@ where
     S: ScalarValue + Send + Sync,
 {
     if operation.item.operation_type != OperationType::Subscription {
-        return Err(GraphQLError::NotSubscription);
+        return Err(OperationError::NotSubscription.into());
     }
 
     let mut fragments = vec![];
@@ -1096,6 +1780,7 @@ where
     });
 
     let errors = RwLock::new(Vec::new());
+    let pool = ObjectPool::default();
     let value;
 
     {
@@ -1132,7 +1817,15 @@ where
             schema: &root_node.schema,
             context,
             errors: &errors,
+            pool: &pool,
+            deadline: None,
+            work_budget: None,
+            operation_type: operation.item.operation_type,
             field_path: Arc::new(FieldPath::Root(operation.start)),
+            parent: None,
+            early_value: None,
+            subsequent_payload: None,
+            state: Arc::new(RequestState::new()),
         };
 
         value = match operation.item.operation_type {
@@ -1151,10 +1844,78 @@ where
     Ok((value, errors))
 }
 
+/// Creates a new [`Executor`] over the `schema` itself and resolves the
+/// introspection fields of the given query `operation`, without requiring any
+/// root values or a user context.
+pub(crate) fn execute_validated_introspection<'a, S>(
+    document: &Document<'a, S>,
+    operation: &Spanning<Operation<'a, S>>,
+    schema: &'a SchemaType<'a, S>,
+    variables: &Variables<S>,
+) -> (Value<S>, Vec<ExecutionError<S>>)
+where
+    S: ScalarValue,
+{
+    let fragments = document
+        .iter()
+        .filter_map(|def| match def {
+            Definition::Fragment(f) => Some((f.item.name.item, f.item.clone())),
+            Definition::Operation(_) => None,
+        })
+        .collect();
+
+    let errors = RwLock::new(Vec::new());
+    let pool = ObjectPool::default();
+    let value = {
+        let executor = Executor {
+            fragments: &fragments,
+            variables,
+            current_selection_set: Some(&operation.item.selection_set[..]),
+            parent_selection_set: None,
+            current_type: schema.query_type(),
+            schema,
+            context: schema,
+            errors: &errors,
+            pool: &pool,
+            deadline: None,
+            work_budget: None,
+            operation_type: operation.item.operation_type,
+            field_path: Arc::new(FieldPath::Root(operation.start)),
+            parent: None,
+            early_value: None,
+            subsequent_payload: None,
+            state: Arc::new(RequestState::new()),
+        };
+
+        executor.resolve_into_value(schema, &IntrospectionRoot::new())
+    };
+
+    let mut errors = errors.into_inner().unwrap();
+    errors.sort();
+
+    (value, errors)
+}
+
 impl<'r, S: 'r> Registry<'r, S> {
     /// Constructs a new [`Registry`] out of the given `types`.
     pub fn new(types: FnvHashMap<Name, MetaType<'r, S>>) -> Self {
-        Self { types }
+        Self {
+            types,
+            rust_types: FnvHashMap::default(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Takes the [`SchemaError`]s detected while registering types in this
+    /// [`Registry`].
+    pub(crate) fn take_errors(&mut self) -> Vec<SchemaError> {
+        std::mem::take(&mut self.errors)
+    }
+
+    /// Records the given [`SchemaError`] detected while registering types in
+    /// this [`Registry`].
+    pub(crate) fn push_error(&mut self, error: SchemaError) {
+        self.errors.push(error);
     }
 
     /// Returns a [`Type`] instance for the given [`GraphQLType`], registered in
@@ -1174,8 +1935,12 @@ impl<'r, S: 'r> Registry<'r, S> {
                     validated_name.clone(),
                     Type::NonNullNamed(Cow::Owned(name.to_string())),
                 );
+                self.rust_types
+                    .insert(validated_name.clone(), vec![std::any::type_name::<T>()]);
                 let meta = T::meta(info, self);
                 self.types.insert(validated_name, meta);
+            } else {
+                self.check_name_collision::<T>(name, info);
             }
             self.types[name].as_type()
         } else {
@@ -1183,6 +1948,29 @@ impl<'r, S: 'r> Registry<'r, S> {
         }
     }
 
+    /// Declares the [`Complexity`] of the `field` of the already registered
+    /// object or interface type with the given `type_name`, overwriting the
+    /// one declared by its [`GraphQLType::meta`] (if any).
+    ///
+    /// Allows to tune the query cost analysis for types not defined in this
+    /// crate. Returns `false` if there is no such type or field.
+    pub fn set_field_complexity(
+        &mut self,
+        type_name: &str,
+        field: &str,
+        complexity: Complexity<S>,
+    ) -> bool {
+        match self.types.get_mut(type_name) {
+            Some(MetaType::Object(ObjectMeta { fields, .. }))
+            | Some(MetaType::Interface(InterfaceMeta { fields, .. })) => fields
+                .iter_mut()
+                .find(|f| f.name == field)
+                .map(|f| f.complexity = Some(complexity))
+                .is_some(),
+            _ => false,
+        }
+    }
+
     /// Creates a [`Field`] with the provided `name`.
     pub fn field<T>(&mut self, name: &str, info: &T::TypeInfo) -> Field<'r, S>
     where
@@ -1190,11 +1978,14 @@ impl<'r, S: 'r> Registry<'r, S> {
         S: ScalarValue,
     {
         Field {
-            name: smartstring::SmartString::from(name),
+            name: Name::intern(name),
             description: None,
             arguments: None,
             field_type: self.get_type::<T>(info),
             deprecation_status: DeprecationStatus::Current,
+            pii: false,
+            hidden: false,
+            complexity: None,
         }
     }
 
@@ -1209,11 +2000,14 @@ impl<'r, S: 'r> Registry<'r, S> {
         S: ScalarValue,
     {
         Field {
-            name: smartstring::SmartString::from(name),
+            name: Name::intern(name),
             description: None,
             arguments: None,
             field_type: self.get_type::<I>(info),
             deprecation_status: DeprecationStatus::Current,
+            pii: false,
+            hidden: false,
+            complexity: None,
         }
     }
 
@@ -1243,6 +2037,61 @@ impl<'r, S: 'r> Registry<'r, S> {
         Argument::new(name, self.get_type::<Option<T>>(info)).default_value(value.to_input_value())
     }
 
+    /// Creates [`Argument`]s out of the fields of the [`InputObjectMeta`] type
+    /// `T`, without registering `T` itself in this [`Registry`].
+    ///
+    /// # Panics
+    ///
+    /// If `T` is not a GraphQL input object.
+    pub fn args<T>(&mut self, info: &T::TypeInfo) -> Vec<Argument<'r, S>>
+    where
+        T: GraphQLType<S> + FromInputValue<S>,
+        S: ScalarValue,
+    {
+        match T::meta(info, self) {
+            MetaType::InputObject(meta) => meta.input_fields,
+            _ => panic!(
+                "Type `{}` must be a GraphQL input object to be used as field arguments",
+                T::name(info).unwrap_or_default(),
+            ),
+        }
+    }
+
+    /// Checks whether the Rust type `T` describes the same GraphQL type as the
+    /// one already registered under its `name`, recording a
+    /// [`SchemaError::NameCollision`] otherwise.
+    ///
+    /// Different Rust types may legitimately describe the same GraphQL type
+    /// (like [`String`] and [`str`], or `T` and [`Box`]`<T>`), so their
+    /// [`MetaType`]s are compared rather than the Rust types themselves.
+    fn check_name_collision<T>(&mut self, name: &str, info: &T::TypeInfo)
+    where
+        T: GraphQLType<S> + ?Sized,
+        S: ScalarValue,
+    {
+        let rust_type = std::any::type_name::<T>();
+        let definition = match self.rust_types.get(name) {
+            Some(known) if !known.contains(&rust_type) => known[0],
+            // Either already checked, or registered without any Rust type.
+            _ => return,
+        };
+        // The existing type may be still under construction, so there is
+        // nothing to compare with yet.
+        if matches!(self.types[name], MetaType::Placeholder(_)) {
+            return;
+        }
+
+        self.rust_types.get_mut(name).unwrap().push(rust_type);
+        let meta = T::meta(info, self);
+        if !self.types[name].has_same_shape(&meta) {
+            self.errors.push(SchemaError::NameCollision {
+                name: name.to_owned(),
+                first: definition.to_owned(),
+                second: rust_type.to_owned(),
+            });
+        }
+    }
+
     fn insert_placeholder(&mut self, name: Name, of_type: Type<'r>) {
         self.types
             .entry(name)
@@ -1258,7 +2107,7 @@ impl<'r, S: 'r> Registry<'r, S> {
     {
         let name = T::name(info).expect("Scalar types must be named. Implement `name()`");
 
-        ScalarMeta::new::<T>(Cow::Owned(name.to_string()))
+        ScalarMeta::new::<T>(name)
     }
 
     /// Creates a [`ListMeta`] type.
@@ -1302,15 +2151,11 @@ impl<'r, S: 'r> Registry<'r, S> {
 
         let mut v = fields.to_vec();
         v.push(self.field::<String>("__typename", &()));
-        ObjectMeta::new(Cow::Owned(name.to_string()), &v)
+        ObjectMeta::new(name, &v)
     }
 
     /// Creates an [`EnumMeta`] type out of the provided `values`.
-    pub fn build_enum_type<T>(
-        &mut self,
-        info: &T::TypeInfo,
-        values: &[EnumValue],
-    ) -> EnumMeta<'r, S>
+    pub fn build_enum_type<T>(&mut self, info: &T::TypeInfo, values: &[EnumValue]) -> EnumMeta<S>
     where
         T: GraphQLType<S> + FromInputValue<S>,
         T::Error: IntoFieldError<S>,
@@ -1318,7 +2163,7 @@ impl<'r, S: 'r> Registry<'r, S> {
     {
         let name = T::name(info).expect("Enum types must be named. Implement `name()`");
 
-        EnumMeta::new::<T>(Cow::Owned(name.to_string()), values)
+        EnumMeta::new::<T>(name, values)
     }
 
     /// Creates an [`InterfaceMeta`] type with the given `fields`.
@@ -1335,18 +2180,18 @@ impl<'r, S: 'r> Registry<'r, S> {
 
         let mut v = fields.to_vec();
         v.push(self.field::<String>("__typename", &()));
-        InterfaceMeta::new(Cow::Owned(name.to_string()), &v)
+        InterfaceMeta::new(name, &v)
     }
 
     /// Creates an [`UnionMeta`] type of the given `types`.
-    pub fn build_union_type<T>(&mut self, info: &T::TypeInfo, types: &[Type<'r>]) -> UnionMeta<'r>
+    pub fn build_union_type<T>(&mut self, info: &T::TypeInfo, types: &[Type<'r>]) -> UnionMeta
     where
         T: GraphQLType<S> + ?Sized,
         S: ScalarValue,
     {
         let name = T::name(info).expect("Union types must be named. Implement name()");
 
-        UnionMeta::new(Cow::Owned(name.to_string()), types)
+        UnionMeta::new(name, types)
     }
 
     /// Creates an [`InputObjectMeta`] type with the given `args`.
@@ -1362,6 +2207,6 @@ impl<'r, S: 'r> Registry<'r, S> {
     {
         let name = T::name(info).expect("Input object types must be named. Implement name()");
 
-        InputObjectMeta::new::<T>(Cow::Owned(name.to_string()), args)
+        InputObjectMeta::new::<T>(name, args)
     }
 }
@@ -0,0 +1,214 @@
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use fnv::FnvHashMap;
+
+/// Hook warned about fields resolved too many times within a single operation
+/// (like the `friends` of every user in a `{ users { friends { id } } }` list),
+/// being the typical sign of N+1 loading, which should be batched with a
+/// dataloader instead.
+///
+/// Set with [`ExecutorOptions::n_plus_one_hook()`]. Only fields with a
+/// selection set (resolving into objects, so most likely loaded from somewhere
+/// downstream) are counted, by their paths with the list indices stripped.
+///
+/// Intended for development only, so does nothing in release builds (without
+/// `debug_assertions`), even if set.
+///
+/// ```
+/// # use std::sync::Mutex;
+/// #
+/// # use juniper::{
+/// #     execute_sync, graphql_object, graphql_vars, EmptyMutation, EmptySubscription,
+/// #     ExecutorOptions, NPlusOneHook, RepeatedField, RootNode,
+/// # };
+/// #
+/// #[derive(Debug, Default)]
+/// struct Warnings(Mutex<Vec<String>>);
+///
+/// impl NPlusOneHook for Warnings {
+///     fn on_repeated_field(&self, field: &RepeatedField) {
+///         self.0.lock().unwrap().push(field.to_string());
+///     }
+/// }
+///
+/// struct User(i32);
+///
+/// #[graphql_object]
+/// impl User {
+///     fn best_friend(&self) -> User {
+///         User(self.0 + 1)
+///     }
+///
+///     fn id(&self) -> i32 {
+///         self.0
+///     }
+/// }
+///
+/// struct Query;
+///
+/// #[graphql_object]
+/// impl Query {
+///     fn users() -> Vec<User> {
+///         (0..5).map(User).collect()
+///     }
+/// }
+///
+/// static WARNINGS: Warnings = Warnings(Mutex::new(vec![]));
+///
+/// let schema = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::<()>::new())
+///     .with_executor_options(ExecutorOptions::new().n_plus_one_hook(5, &WARNINGS));
+///
+/// let query = "{ users { id bestFriend { id } } }";
+/// execute_sync(query, None, &schema, &graphql_vars! {}, &()).unwrap();
+/// # #[cfg(debug_assertions)]
+/// assert_eq!(
+///     WARNINGS.0.lock().unwrap()[0],
+///     "field `users.bestFriend` resolved 5 times, consider batching its loads",
+/// );
+/// ```
+///
+/// [`ExecutorOptions::n_plus_one_hook()`]: super::ExecutorOptions::n_plus_one_hook
+pub trait NPlusOneHook: fmt::Debug + Send + Sync {
+    /// Handles the given [`RepeatedField`], having reached the threshold.
+    fn on_repeated_field(&self, field: &RepeatedField);
+}
+
+impl<T: NPlusOneHook + ?Sized> NPlusOneHook for Arc<T> {
+    fn on_repeated_field(&self, field: &RepeatedField) {
+        (**self).on_repeated_field(field)
+    }
+}
+
+impl<T: NPlusOneHook + ?Sized> NPlusOneHook for &T {
+    fn on_repeated_field(&self, field: &RepeatedField) {
+        (**self).on_repeated_field(field)
+    }
+}
+
+/// Field resolved at least the threshold of a [`NPlusOneHook`] times within a
+/// single operation.
+///
+/// Displays as a human-readable warning.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RepeatedField {
+    operation_name: Option<String>,
+    path: String,
+    count: usize,
+}
+
+impl RepeatedField {
+    /// Returns the name of the operation resolving the field, if any.
+    pub fn operation_name(&self) -> Option<&str> {
+        self.operation_name.as_deref()
+    }
+
+    /// Returns the path of the field, as dot-separated response names with
+    /// the list indices stripped (like `users.friends`).
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns the number of times the field has been resolved.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+impl fmt::Display for RepeatedField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "field `{}` resolved {} times", self.path, self.count)?;
+        if let Some(name) = &self.operation_name {
+            write!(f, " in operation `{}`", name)?;
+        }
+        write!(f, ", consider batching its loads")
+    }
+}
+
+/// [`NPlusOneHook`] set along with its threshold.
+#[derive(Clone, Debug)]
+pub(crate) struct NPlusOneDetection {
+    pub(crate) threshold: usize,
+    pub(crate) hook: Arc<dyn NPlusOneHook>,
+}
+
+impl NPlusOneDetection {
+    /// Fires the hook for every field of the operation with the given
+    /// `operation_name` having been resolved at least the threshold times,
+    /// the most repeated first.
+    pub(crate) fn check(&self, operation_name: Option<&str>, counts: Option<&FieldCounts>) {
+        let mut repeated = counts
+            .map(|c| c.0.lock().unwrap().clone())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(_, count)| *count >= self.threshold)
+            .collect::<Vec<_>>();
+        repeated.sort_by(|(a_path, a), (b_path, b)| b.cmp(a).then_with(|| a_path.cmp(b_path)));
+        for (path, count) in repeated {
+            self.hook.on_repeated_field(&RepeatedField {
+                operation_name: operation_name.map(Into::into),
+                path,
+                count,
+            });
+        }
+    }
+}
+
+/// Numbers of resolutions of the fields of an operation by their paths, stored
+/// in its [`RequestState`].
+///
+/// [`RequestState`]: super::RequestState
+#[derive(Debug, Default)]
+pub(crate) struct FieldCounts(Mutex<FnvHashMap<String, usize>>);
+
+impl FieldCounts {
+    /// Counts one more resolution of the field at the given `path`.
+    pub(crate) fn increment(&self, path: String) {
+        *self.0.lock().unwrap().entry(path).or_default() += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::{FieldCounts, NPlusOneDetection, NPlusOneHook, RepeatedField};
+
+    #[derive(Debug, Default)]
+    struct Warnings(Mutex<Vec<String>>);
+
+    impl NPlusOneHook for Warnings {
+        fn on_repeated_field(&self, field: &RepeatedField) {
+            self.0.lock().unwrap().push(field.to_string());
+        }
+    }
+
+    #[test]
+    fn warns_about_most_repeated_first() {
+        let counts = FieldCounts::default();
+        for (path, n) in [("users", 1), ("users.friends", 3), ("users.avatar", 4)] {
+            for _ in 0..n {
+                counts.increment(path.into());
+            }
+        }
+
+        let warnings = Arc::new(Warnings::default());
+        NPlusOneDetection {
+            threshold: 3,
+            hook: warnings.clone(),
+        }
+        .check(Some("Users"), Some(&counts));
+
+        assert_eq!(
+            *warnings.0.lock().unwrap(),
+            [
+                "field `users.avatar` resolved 4 times in operation `Users`, \
+                 consider batching its loads",
+                "field `users.friends` resolved 3 times in operation `Users`, \
+                 consider batching its loads",
+            ],
+        );
+    }
+}
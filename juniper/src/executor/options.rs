@@ -0,0 +1,234 @@
+use std::{sync::Arc, time::Duration};
+
+use futures::future::BoxFuture;
+
+use crate::ast::OperationType;
+
+use super::{
+    metrics::MetricsSink,
+    n_plus_one::{NPlusOneDetection, NPlusOneHook},
+    slow_query::{SlowQueryDetection, SlowQueryHook},
+    strategy::{ExecutionStrategy, Parallel},
+};
+
+/// Options tuning how operations are executed against a schema.
+///
+/// Set with [`RootNode::with_executor_options()`].
+///
+/// [`RootNode::with_executor_options()`]: crate::RootNode::with_executor_options
+#[derive(Clone, Debug)]
+pub struct ExecutorOptions {
+    stack_growth: Option<StackGrowth>,
+    query_strategy: Arc<dyn ExecutionStrategy>,
+    mutation_strategy: Arc<dyn ExecutionStrategy>,
+    metrics_sink: Option<Arc<dyn MetricsSink>>,
+    slow_query: Option<SlowQueryDetection>,
+    n_plus_one: Option<NPlusOneDetection>,
+    time_validation_rules: bool,
+}
+
+impl Default for ExecutorOptions {
+    fn default() -> Self {
+        Self {
+            stack_growth: None,
+            query_strategy: Arc::new(Parallel),
+            mutation_strategy: Arc::new(Parallel),
+            metrics_sink: None,
+            slow_query: None,
+            n_plus_one: None,
+            time_validation_rules: false,
+        }
+    }
+}
+
+/// Parameters of growing the stack on demand while resolving nested selection
+/// sets.
+#[cfg_attr(not(feature = "stacker"), allow(dead_code))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct StackGrowth {
+    red_zone: usize,
+    stack_size: usize,
+}
+
+impl ExecutorOptions {
+    /// Creates the default [`ExecutorOptions`], resolving nested selection
+    /// sets recursively on the current stack, and resolving fields with the
+    /// [`Parallel`] [`ExecutionStrategy`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Makes the executor grow the stack on demand while resolving nested
+    /// selection sets, so extremely deep operations passing the validation
+    /// cannot overflow it.
+    ///
+    /// Whenever less than `red_zone` bytes of the stack remain before
+    /// resolving a nested selection set, a new stack segment of `stack_size`
+    /// bytes is allocated on the heap and the resolving continues there.
+    /// Something like `64 * 1024` and `1024 * 1024` is a reasonable choice.
+    ///
+    /// For asynchronous execution, the stack is grown when polling the
+    /// futures of nested selection sets.
+    #[cfg(feature = "stacker")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "stacker")))]
+    pub fn grow_stack(mut self, red_zone: usize, stack_size: usize) -> Self {
+        self.stack_growth = Some(StackGrowth {
+            red_zone,
+            stack_size,
+        });
+        self
+    }
+
+    /// Sets the [`ExecutionStrategy`] of resolving the fields of queries and
+    /// subscription events during asynchronous execution.
+    ///
+    /// [`Parallel`] by default.
+    pub fn query_strategy(mut self, strategy: impl ExecutionStrategy + 'static) -> Self {
+        self.query_strategy = Arc::new(strategy);
+        self
+    }
+
+    /// Sets the [`ExecutionStrategy`] of resolving the fields of mutations
+    /// during asynchronous execution.
+    ///
+    /// [`Parallel`] by default, for backward compatibility. Set it to
+    /// [`Serial`] to resolve the root fields of mutations serially, as required
+    /// by the [GraphQL spec][1].
+    ///
+    /// [`Serial`]: super::Serial
+    /// [1]: https://spec.graphql.org/June2018/#sec-Mutation
+    pub fn mutation_strategy(mut self, strategy: impl ExecutionStrategy + 'static) -> Self {
+        self.mutation_strategy = Arc::new(strategy);
+        self
+    }
+
+    /// Sets the [`MetricsSink`] to record the measure of every executed
+    /// operation into.
+    ///
+    /// None by default.
+    pub fn metrics_sink(mut self, sink: impl MetricsSink + 'static) -> Self {
+        self.metrics_sink = Some(Arc::new(sink));
+        self
+    }
+
+    /// Sets the [`SlowQueryHook`] to fire for every query and mutation
+    /// executing for longer than the given `threshold`.
+    ///
+    /// None by default.
+    pub fn slow_query_hook(
+        mut self,
+        threshold: Duration,
+        hook: impl SlowQueryHook + 'static,
+    ) -> Self {
+        self.slow_query = Some(SlowQueryDetection {
+            threshold,
+            hook: Arc::new(hook),
+        });
+        self
+    }
+
+    /// Returns the [`SlowQueryHook`] along with its threshold, if any.
+    pub(crate) fn slow_query(&self) -> Option<&SlowQueryDetection> {
+        self.slow_query.as_ref()
+    }
+
+    /// Sets the [`NPlusOneHook`] to warn about every field resolved at least
+    /// `threshold` times within a single query or mutation.
+    ///
+    /// None by default. Does nothing in release builds.
+    pub fn n_plus_one_hook(mut self, threshold: usize, hook: impl NPlusOneHook + 'static) -> Self {
+        self.n_plus_one = Some(NPlusOneDetection {
+            threshold,
+            hook: Arc::new(hook),
+        });
+        self
+    }
+
+    /// Returns the [`NPlusOneHook`] along with its threshold, if any and
+    /// enabled in the current build.
+    pub(crate) fn n_plus_one(&self) -> Option<&NPlusOneDetection> {
+        self.n_plus_one.as_ref().filter(|_| cfg!(debug_assertions))
+    }
+
+    /// Makes the validation measure the time spent in every validation rule,
+    /// and record it into the [`MetricsSink`], so the rules dominating the
+    /// validation latency may be spotted.
+    ///
+    /// Disabled by default, as measuring adds an overhead to every visited
+    /// node of a document.
+    pub fn time_validation_rules(mut self) -> Self {
+        self.time_validation_rules = true;
+        self
+    }
+
+    /// Indicates whether the time spent in every validation rule is measured.
+    pub(crate) fn times_validation_rules(&self) -> bool {
+        self.time_validation_rules
+    }
+
+    /// Returns the [`MetricsSink`] to record the measure of every executed
+    /// operation into, if any.
+    pub fn sink(&self) -> Option<&dyn MetricsSink> {
+        self.metrics_sink.as_deref()
+    }
+
+    /// Returns the [`ExecutionStrategy`] of resolving the fields of an
+    /// operation of the given type.
+    pub(crate) fn strategy(&self, operation_type: OperationType) -> &dyn ExecutionStrategy {
+        match operation_type {
+            OperationType::Mutation => &*self.mutation_strategy,
+            OperationType::Query | OperationType::Subscription => &*self.query_strategy,
+        }
+    }
+
+    /// Runs the given resolving function `f`, growing the stack beforehand if
+    /// required.
+    pub(crate) fn maybe_grow<R>(&self, f: impl FnOnce() -> R) -> R {
+        match self.stack_growth {
+            #[cfg(feature = "stacker")]
+            Some(StackGrowth {
+                red_zone,
+                stack_size,
+            }) => stacker::maybe_grow(red_zone, stack_size, f),
+            _ => f(),
+        }
+    }
+
+    /// Wraps the given resolving `future`, so it grows the stack if required
+    /// whenever being polled.
+    pub(crate) fn maybe_grow_future<'a, T: 'a>(
+        &self,
+        future: BoxFuture<'a, T>,
+    ) -> BoxFuture<'a, T> {
+        match self.stack_growth {
+            #[cfg(feature = "stacker")]
+            Some(growth) => Box::pin(GrowingStack { future, growth }),
+            _ => future,
+        }
+    }
+}
+
+/// [`Future`] growing the stack on demand whenever the inner one is polled.
+///
+/// [`Future`]: std::future::Future
+#[cfg(feature = "stacker")]
+struct GrowingStack<'a, T> {
+    future: BoxFuture<'a, T>,
+    growth: StackGrowth,
+}
+
+#[cfg(feature = "stacker")]
+impl<'a, T> std::future::Future for GrowingStack<'a, T> {
+    type Output = T;
+
+    fn poll(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<T> {
+        let StackGrowth {
+            red_zone,
+            stack_size,
+        } = self.growth;
+        stacker::maybe_grow(red_zone, stack_size, || self.future.as_mut().poll(cx))
+    }
+}
@@ -1,11 +1,12 @@
 use std::{
     collections::HashMap,
     sync::{Arc, RwLock},
+    time::Instant,
 };
 
 use crate::{
-    ast::Fragment,
-    executor::FieldPath,
+    ast::{Fragment, OperationType},
+    executor::{pool::ObjectPool, FieldPath, RequestState},
     parser::SourcePosition,
     schema::model::{SchemaType, TypeType},
     ExecutionError, Executor, Selection, Variables,
@@ -22,7 +23,12 @@ pub struct OwnedExecutor<'a, CtxT, S> {
     pub(super) schema: &'a SchemaType<'a, S>,
     pub(super) context: &'a CtxT,
     pub(super) errors: RwLock<Vec<ExecutionError<S>>>,
+    pub(super) pool: ObjectPool<S>,
+    pub(super) deadline: Option<Instant>,
+    pub(super) work_budget: Option<usize>,
+    pub(super) operation_type: OperationType,
     pub(super) field_path: Arc<FieldPath<'a>>,
+    pub(super) state: Arc<RequestState>,
 }
 
 impl<'a, CtxT, S> Clone for OwnedExecutor<'a, CtxT, S>
@@ -39,7 +45,12 @@ where
             schema: self.schema,
             context: self.context,
             errors: RwLock::new(vec![]),
+            pool: ObjectPool::default(),
+            deadline: self.deadline,
+            work_budget: self.work_budget,
+            operation_type: self.operation_type,
             field_path: self.field_path.clone(),
+            state: Arc::clone(&self.state),
         }
     }
 }
@@ -67,7 +78,12 @@ where
             schema: self.schema,
             context: self.context,
             errors: RwLock::new(vec![]),
+            pool: ObjectPool::default(),
+            deadline: self.deadline,
+            work_budget: self.work_budget,
+            operation_type: self.operation_type,
             field_path: self.field_path.clone(),
+            state: Arc::clone(&self.state),
         }
     }
 
@@ -101,11 +117,16 @@ where
             schema: self.schema,
             context: self.context,
             errors: RwLock::new(vec![]),
+            pool: ObjectPool::default(),
+            deadline: self.deadline,
+            work_budget: self.work_budget,
+            operation_type: self.operation_type,
             field_path: Arc::new(FieldPath::Field(
                 field_alias,
                 location,
                 Arc::clone(&self.field_path),
             )),
+            state: Arc::clone(&self.state),
         }
     }
 
@@ -120,7 +141,15 @@ where
             schema: self.schema,
             context: self.context,
             errors: &self.errors,
+            pool: &self.pool,
+            deadline: self.deadline,
+            work_budget: self.work_budget,
+            operation_type: self.operation_type,
             field_path: Arc::clone(&self.field_path),
+            parent: None,
+            early_value: None,
+            subsequent_payload: None,
+            state: Arc::clone(&self.state),
         }
     }
 }
@@ -0,0 +1,63 @@
+use std::{any::Any, fmt};
+
+/// Value of a [GraphQL object][1], whose fields are being resolved, exposed to
+/// the resolvers of its descendant fields.
+///
+/// Allows deeply nested resolvers to make decisions based on their ancestors,
+/// without threading the data through every level. Use
+/// [`Executor::parent()`] or [`Executor::parents()`] to access it.
+///
+/// [`Executor::parent()`]: crate::Executor::parent
+/// [`Executor::parents()`]: crate::Executor::parents
+/// [1]: https://spec.graphql.org/June2018/#sec-Objects
+#[derive(Clone, Copy)]
+pub struct ParentValue<'r> {
+    type_name: &'r str,
+    value: Option<&'r (dyn Any + Sync + 'static)>,
+    parent: Option<&'r ParentValue<'r>>,
+}
+
+impl<'r> ParentValue<'r> {
+    /// Creates a new [`ParentValue`] of the given `type_name`, being a child of
+    /// the given `parent`.
+    pub(crate) fn new(
+        type_name: &'r str,
+        value: Option<&'r (dyn Any + Sync + 'static)>,
+        parent: Option<&'r ParentValue<'r>>,
+    ) -> Self {
+        Self {
+            type_name,
+            value,
+            parent,
+        }
+    }
+
+    /// Returns the name of the GraphQL type of this [`ParentValue`].
+    pub fn type_name(&self) -> &'r str {
+        self.type_name
+    }
+
+    /// Returns the Rust value of this [`ParentValue`], if it's of type `T`.
+    ///
+    /// Always returns [`None`] if the value doesn't expose itself via
+    /// [`GraphQLValue::as_any()`].
+    ///
+    /// [`GraphQLValue::as_any()`]: crate::GraphQLValue::as_any
+    pub fn downcast_ref<T: Any>(&self) -> Option<&'r T> {
+        self.value.and_then(|v| (v as &dyn Any).downcast_ref())
+    }
+
+    /// Returns the [`ParentValue`] of this one, if any.
+    pub fn parent(&self) -> Option<&'r ParentValue<'r>> {
+        self.parent
+    }
+}
+
+impl<'r> fmt::Debug for ParentValue<'r> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParentValue")
+            .field("type_name", &self.type_name)
+            .field("parent", &self.parent)
+            .finish()
+    }
+}
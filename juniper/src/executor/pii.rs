@@ -0,0 +1,238 @@
+use std::collections::HashMap;
+
+use crate::{
+    ast::{Definition, Document, Fragment, Operation, OperationType, Selection},
+    parser::Spanning,
+    schema::{meta::MetaType, model::SchemaType},
+    value::{Object, Value},
+};
+
+use super::metrics::MetricsSink;
+
+/// Field tagged as containing personally identifiable information (PII),
+/// selected by an operation.
+///
+/// Fields are tagged either with the `#[graphql(pii)]` attribute in code
+/// generation, or with the [`Field::pii()`] method manually.
+///
+/// [`Field::pii()`]: crate::meta::Field::pii
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct PiiField {
+    type_name: String,
+    field_name: String,
+    path: Vec<String>,
+}
+
+impl PiiField {
+    /// Returns the name of the type (object or interface) this field belongs
+    /// to.
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    /// Returns the name of this field in the schema.
+    pub fn field_name(&self) -> &str {
+        &self.field_name
+    }
+
+    /// Returns the path of this field in the response, consisting of response
+    /// keys (aliases, if any) only, as lists are traversed transparently.
+    pub fn path(&self) -> &[String] {
+        &self.path
+    }
+}
+
+/// [`PiiField`]s selected by an operation.
+///
+/// Reported to the [`MetricsSink`] before the operation is executed, and may
+/// be used to [`strip()`] these fields from a response before logging it.
+///
+/// [`MetricsSink`]: super::MetricsSink
+/// [`strip()`]: PiiFields::strip
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PiiFields {
+    fields: Vec<PiiField>,
+}
+
+impl PiiFields {
+    /// Collects the [`PiiFields`] selected by the given `operation` of the
+    /// `document`.
+    pub(crate) fn collect<S>(
+        schema: &SchemaType<S>,
+        document: &Document<S>,
+        operation: &Spanning<Operation<S>>,
+    ) -> Self {
+        let root = match operation.item.operation_type {
+            OperationType::Query => Some(schema.concrete_query_type()),
+            OperationType::Mutation => schema.concrete_mutation_type(),
+            OperationType::Subscription => schema.concrete_subscription_type(),
+        };
+        let mut collector = Collector {
+            schema,
+            fragments: document
+                .iter()
+                .filter_map(|def| match def {
+                    Definition::Fragment(f) => Some((f.item.name.item, &f.item)),
+                    Definition::Operation(_) => None,
+                })
+                .collect(),
+            visiting: vec![],
+            path: vec![],
+            fields: vec![],
+        };
+        if let Some(root) = root {
+            collector.collect(&operation.item.selection_set, root);
+        }
+        Self {
+            fields: collector.fields,
+        }
+    }
+
+    /// Collects the [`PiiFields`] selected by the given `operation` of the
+    /// `document` and records them into the provided [`MetricsSink`], if any
+    /// and if there are such fields.
+    pub(crate) fn record<S>(
+        schema: &SchemaType<S>,
+        document: &Document<S>,
+        operation: &Spanning<Operation<S>>,
+        sink: Option<&dyn MetricsSink>,
+    ) {
+        if let Some(sink) = sink {
+            let fields = Self::collect(schema, document, operation);
+            if !fields.is_empty() {
+                sink.record_pii_fields(
+                    operation.item.name.as_ref().map(|n| n.item),
+                    operation.item.operation_type,
+                    &fields,
+                );
+            }
+        }
+    }
+
+    /// Indicates whether no [`PiiField`]s are selected.
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Returns an iterator over the selected [`PiiField`]s, in the order of
+    /// their selection.
+    pub fn iter(&self) -> impl Iterator<Item = &PiiField> {
+        self.fields.iter()
+    }
+
+    /// Returns a copy of the given response `value` with all these
+    /// [`PiiField`]s (along with their subfields) removed, suitable for debug
+    /// logging.
+    pub fn strip<S: Clone>(&self, value: &Value<S>) -> Value<S> {
+        let paths = self.fields.iter().map(|f| &f.path[..]).collect::<Vec<_>>();
+        strip(value, &paths)
+    }
+}
+
+impl<'a> IntoIterator for &'a PiiFields {
+    type Item = &'a PiiField;
+    type IntoIter = std::slice::Iter<'a, PiiField>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.fields.iter()
+    }
+}
+
+/// Removes the fields located at the given response `paths` from the `value`.
+fn strip<S: Clone>(value: &Value<S>, paths: &[&[String]]) -> Value<S> {
+    match value {
+        Value::Object(obj) => Value::Object(
+            obj.iter()
+                .filter_map(|(key, v)| {
+                    let nested = paths
+                        .iter()
+                        .filter_map(|p| p.split_first())
+                        .filter(|(first, _)| *first == key)
+                        .map(|(_, rest)| rest)
+                        .collect::<Vec<_>>();
+                    if nested.iter().any(|rest| rest.is_empty()) {
+                        None
+                    } else if nested.is_empty() {
+                        Some((key.clone(), v.clone()))
+                    } else {
+                        Some((key.clone(), strip(v, &nested)))
+                    }
+                })
+                .collect::<Object<S>>(),
+        ),
+        Value::List(list) => Value::List(list.iter().map(|v| strip(v, paths)).collect()),
+        Value::Null | Value::Scalar(_) => value.clone(),
+    }
+}
+
+/// Collector of [`PiiField`]s, walking selection sets along with the schema
+/// types they're selected on.
+struct Collector<'s, 'd, 'a, S> {
+    schema: &'s SchemaType<'s, S>,
+    fragments: HashMap<&'a str, &'d Fragment<'a, S>>,
+    visiting: Vec<&'a str>,
+    path: Vec<String>,
+    fields: Vec<PiiField>,
+}
+
+impl<'s, 'd, 'a, S> Collector<'s, 'd, 'a, S> {
+    fn collect(&mut self, selection_set: &'d [Selection<'a, S>], meta_type: &'s MetaType<'s, S>) {
+        for selection in selection_set {
+            match selection {
+                Selection::Field(f) => {
+                    let meta_field = match meta_type.field_by_name(f.item.name.item) {
+                        Some(field) => field,
+                        None => continue,
+                    };
+                    let key = f.item.alias.as_ref().unwrap_or(&f.item.name).item;
+                    self.path.push(key.to_owned());
+                    if meta_field.is_pii() {
+                        let field = PiiField {
+                            type_name: meta_type.name().unwrap_or_default().to_owned(),
+                            field_name: meta_field.name.to_string(),
+                            path: self.path.clone(),
+                        };
+                        if !self.fields.contains(&field) {
+                            self.fields.push(field);
+                        }
+                    }
+                    if let Some(selection_set) = &f.item.selection_set {
+                        let inner = meta_field.field_type.innermost_name();
+                        if let Some(inner) = self.schema.concrete_type_by_name(inner) {
+                            self.collect(selection_set, inner);
+                        }
+                    }
+                    self.path.pop();
+                }
+                Selection::InlineFragment(f) => {
+                    let meta_type = f
+                        .item
+                        .type_condition
+                        .as_ref()
+                        .and_then(|cond| self.schema.concrete_type_by_name(cond.item))
+                        .unwrap_or(meta_type);
+                    self.collect(&f.item.selection_set, meta_type);
+                }
+                Selection::FragmentSpread(s) => {
+                    let name = s.item.name.item;
+                    // Guards against fragment cycles in documents not being
+                    // validated yet.
+                    if self.visiting.contains(&name) {
+                        continue;
+                    }
+                    let fragment = match self.fragments.get(name).copied() {
+                        Some(f) => f,
+                        None => continue,
+                    };
+                    let meta_type = self
+                        .schema
+                        .concrete_type_by_name(fragment.type_condition.item)
+                        .unwrap_or(meta_type);
+                    self.visiting.push(name);
+                    self.collect(&fragment.selection_set, meta_type);
+                    self.visiting.pop();
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use std::sync::Mutex;
+
+use crate::value::Object;
+
+/// Maximum number of the buffers kept in an [`ObjectPool`].
+const MAX_POOLED: usize = 32;
+
+/// Maximum capacity of the buffers kept in an [`ObjectPool`], so the small
+/// objects don't hold onto the memory of the large ones.
+const MAX_CAPACITY: usize = 64;
+
+/// Pool of the emptied [`Object`] buffers of an executed operation.
+///
+/// The objects resolved for fragments are merged into the objects of their
+/// parent fields and dropped then, so their buffers are recycled here to be
+/// reused for the next resolved objects, allocating less per request.
+#[derive(Debug)]
+pub(crate) struct ObjectPool<S>(Mutex<Vec<Object<S>>>);
+
+impl<S> Default for ObjectPool<S> {
+    fn default() -> Self {
+        Self(Mutex::new(Vec::new()))
+    }
+}
+
+impl<S> ObjectPool<S> {
+    /// Returns an empty [`Object`] with at least the given `capacity`,
+    /// reusing a recycled buffer, if any.
+    pub(crate) fn object(&self, capacity: usize) -> Object<S> {
+        match self.0.lock().unwrap().pop() {
+            Some(mut object) => {
+                // Reserving may reallocate even if there is enough capacity.
+                if object.capacity() < capacity {
+                    object.reserve(capacity);
+                }
+                object
+            }
+            None => Object::with_capacity(capacity),
+        }
+    }
+
+    /// Empties the given `object` and keeps its buffer for reuse, unless there
+    /// are enough buffers already, or it's too small or too large to be worth
+    /// keeping.
+    pub(crate) fn recycle(&self, mut object: Object<S>) {
+        if object.capacity() == 0 || object.capacity() > MAX_CAPACITY {
+            return;
+        }
+        object.drain();
+
+        let mut pooled = self.0.lock().unwrap();
+        if pooled.len() < MAX_POOLED {
+            pooled.push(object);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{value::Object, DefaultScalarValue, Value};
+
+    use super::{ObjectPool, MAX_CAPACITY, MAX_POOLED};
+
+    #[test]
+    fn reuses_recycled_buffers() {
+        let pool = ObjectPool::<DefaultScalarValue>::default();
+
+        let mut object = pool.object(4);
+        object.add_field("a", Value::null());
+        let capacity = object.capacity();
+        pool.recycle(object);
+
+        let object = pool.object(1);
+        assert_eq!(object.field_count(), 0);
+        assert_eq!(object.capacity(), capacity);
+        assert!(pool.0.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn keeps_limited_buffers() {
+        let pool = ObjectPool::<DefaultScalarValue>::default();
+
+        pool.recycle(Object::with_capacity(0));
+        pool.recycle(Object::with_capacity(MAX_CAPACITY * 2));
+        assert!(pool.0.lock().unwrap().is_empty());
+
+        for _ in 0..MAX_POOLED + 1 {
+            pool.recycle(Object::with_capacity(1));
+        }
+        assert_eq!(pool.0.lock().unwrap().len(), MAX_POOLED);
+    }
+}
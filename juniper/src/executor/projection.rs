@@ -0,0 +1,201 @@
+use fnv::FnvHashMap;
+
+use super::LookAheadSelection;
+
+/// Mapping of the fields of a GraphQL object onto the columns and relations of
+/// a storage (a table or an entity of an ORM, like `diesel` or `sea-orm`),
+/// used to [`project`] a [`LookAheadSelection`] into the minimal [`Projection`]
+/// to be loaded.
+///
+/// Fields not mentioned in the mapping (computed ones, or `__typename`) are
+/// ignored.
+///
+/// ```
+/// # use juniper::{
+/// #     graphql_object, EmptyMutation, EmptySubscription, Executor, ProjectionMap,
+/// #     RootNode, ScalarValue,
+/// # };
+/// #
+/// # struct Post;
+/// #
+/// # #[graphql_object]
+/// # impl Post {
+/// #     fn title() -> &'static str {
+/// #         "Hello"
+/// #     }
+/// # }
+/// #
+/// struct User;
+///
+/// #[graphql_object]
+/// impl User {
+///     fn full_name() -> &'static str {
+///         "John Doe"
+///     }
+///
+///     fn posts() -> Vec<Post> {
+///         vec![]
+///     }
+/// }
+///
+/// struct Query;
+///
+/// #[graphql_object]
+/// impl Query {
+///     fn user<S: ScalarValue>(executor: &Executor<'_, '_, (), S>) -> User {
+///         let users = ProjectionMap::new()
+///             .always(&["id"])
+///             .columns("fullName", &["first_name", "last_name"])
+///             .relation("posts", "posts", ProjectionMap::new().columns("title", &["title"]));
+///
+///         let projection = users.project(&executor.look_ahead());
+///         // `SELECT id, first_name, last_name FROM users ...`
+///         assert_eq!(projection.columns(), ["id", "first_name", "last_name"]);
+///         // `SELECT title FROM posts ...`
+///         assert_eq!(projection.relation("posts").unwrap().columns(), ["title"]);
+///
+///         User
+///     }
+/// }
+/// #
+/// # let schema = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::<()>::new());
+/// # let query = "{ user { fullName posts { title } } }";
+/// # juniper::execute_sync(query, None, &schema, &juniper::graphql_vars! {}, &()).unwrap();
+/// ```
+///
+/// [`project`]: ProjectionMap::project
+#[derive(Clone, Debug, Default)]
+pub struct ProjectionMap {
+    always: Vec<&'static str>,
+    fields: FnvHashMap<&'static str, FieldMapping>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct FieldMapping {
+    columns: Vec<&'static str>,
+    relation: Option<(&'static str, ProjectionMap)>,
+}
+
+impl ProjectionMap {
+    /// Creates a new empty [`ProjectionMap`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requires the given `columns` to be always loaded, whatever is selected
+    /// (a primary key, for example).
+    #[must_use]
+    pub fn always(mut self, columns: &[&'static str]) -> Self {
+        self.always.extend_from_slice(columns);
+        self
+    }
+
+    /// Maps the GraphQL `field` onto the given `columns`.
+    ///
+    /// May be combined with a [`relation`] for the same `field`, to require
+    /// the columns the relation is joined on (a foreign key, for example).
+    ///
+    /// [`relation`]: ProjectionMap::relation
+    #[must_use]
+    pub fn columns(mut self, field: &'static str, columns: &[&'static str]) -> Self {
+        self.fields
+            .entry(field)
+            .or_default()
+            .columns
+            .extend_from_slice(columns);
+        self
+    }
+
+    /// Maps the GraphQL `field` onto the `relation` with the given name, whose
+    /// fields are mapped by the provided `map`.
+    #[must_use]
+    pub fn relation(mut self, field: &'static str, relation: &'static str, map: Self) -> Self {
+        self.fields.entry(field).or_default().relation = Some((relation, map));
+        self
+    }
+
+    /// Projects the given `selection` into the minimal [`Projection`] of the
+    /// columns and relations needed to resolve it.
+    ///
+    /// Fields selected in any type condition are taken into account, so the
+    /// [`Projection`] of an interface or union covers all its implementers.
+    /// The same field selected many times (under different aliases) is
+    /// projected once.
+    pub fn project<S>(&self, selection: &LookAheadSelection<'_, S>) -> Projection {
+        let mut projection = Projection::default();
+        projection.add_columns(&self.always);
+        self.project_into(selection, &mut projection);
+        projection
+    }
+
+    fn project_into<S>(&self, selection: &LookAheadSelection<'_, S>, into: &mut Projection) {
+        for child in &selection.children {
+            let child = &child.inner;
+            let mapping = match self.fields.get(child.name) {
+                Some(m) => m,
+                None => continue,
+            };
+            into.add_columns(&mapping.columns);
+            if let Some((relation, map)) = &mapping.relation {
+                let nested = match into.relations.iter().position(|(n, _)| n == relation) {
+                    Some(i) => &mut into.relations[i].1,
+                    None => {
+                        let mut nested = Projection::default();
+                        nested.add_columns(&map.always);
+                        into.relations.push((relation, nested));
+                        &mut into.relations.last_mut().unwrap().1
+                    }
+                };
+                map.project_into(child, nested);
+            }
+        }
+    }
+}
+
+/// Columns and relations to be loaded to resolve a [`LookAheadSelection`], as
+/// [`project`]ed by a [`ProjectionMap`].
+///
+/// Both the columns and the relations are deduplicated, and go in the order of
+/// their first appearance in the selection.
+///
+/// [`project`]: ProjectionMap::project
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Projection {
+    columns: Vec<&'static str>,
+    relations: Vec<(&'static str, Projection)>,
+}
+
+impl Projection {
+    /// Returns the columns to be loaded.
+    pub fn columns(&self) -> &[&'static str] {
+        &self.columns
+    }
+
+    /// Returns the names of the relations to be loaded along with their own
+    /// [`Projection`]s.
+    pub fn relations(&self) -> impl Iterator<Item = (&'static str, &Self)> {
+        self.relations.iter().map(|(n, p)| (*n, p))
+    }
+
+    /// Returns the [`Projection`] of the relation with the given `name`, if
+    /// it's to be loaded.
+    pub fn relation(&self, name: &str) -> Option<&Self> {
+        self.relations
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, p)| p)
+    }
+
+    /// Indicates whether nothing is to be loaded.
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty() && self.relations.is_empty()
+    }
+
+    fn add_columns(&mut self, columns: &[&'static str]) {
+        for c in columns {
+            if !self.columns.contains(c) {
+                self.columns.push(c);
+            }
+        }
+    }
+}
@@ -0,0 +1,277 @@
+use std::{fmt, hash::Hasher as _, sync::Arc, time::Duration};
+
+use fnv::FnvHasher;
+
+use crate::{
+    parser::{Lexer, ScalarToken, Token},
+    value::{ScalarValue, Value},
+    RequestError,
+};
+
+use super::{ExecutionError, Variables};
+
+/// Logger of every query and mutation executed against a schema, being a
+/// single place for structured request logging, instead of a logging shim
+/// per web framework integration.
+///
+/// Set with [`RootNode::with_query_logger()`]. Subscriptions are not logged.
+///
+/// ```
+/// # use std::sync::Mutex;
+/// #
+/// # use juniper::{
+/// #     execute_sync, graphql_object, graphql_vars, Context, EmptyMutation,
+/// #     EmptySubscription, QueryLogEntry, QueryLogger, RootNode,
+/// # };
+/// #
+/// struct Session {
+///     client: String,
+/// }
+///
+/// impl Context for Session {}
+///
+/// #[derive(Debug, Default)]
+/// struct Lines(Mutex<Vec<String>>);
+///
+/// impl QueryLogger<Session> for Lines {
+///     fn client_identity(&self, session: &Session) -> Option<String> {
+///         Some(session.client.clone())
+///     }
+///
+///     fn log(&self, entry: &QueryLogEntry<'_>) {
+///         self.0.lock().unwrap().push(format!(
+///             "{} {} {:?}",
+///             entry.client().unwrap_or("-"),
+///             entry.query().unwrap_or("-"),
+///             entry.error_codes(),
+///         ));
+///     }
+/// }
+///
+/// struct Query;
+///
+/// #[graphql_object(context = Session)]
+/// impl Query {
+///     fn greet(name: String) -> String {
+///         format!("Hello, {}!", name)
+///     }
+/// }
+///
+/// static LINES: Lines = Lines(Mutex::new(vec![]));
+///
+/// let schema = RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+///     .with_query_logger(&LINES);
+/// let session = Session { client: "web/1.2".into() };
+///
+/// execute_sync(r#"{ greet(name: "John") }"#, None, &schema, &graphql_vars! {}, &session)
+///     .unwrap();
+/// assert_eq!(LINES.0.lock().unwrap()[0], r#"web/1.2 {greet(name:"")} []"#);
+/// ```
+///
+/// [`RootNode::with_query_logger()`]: crate::RootNode::with_query_logger
+pub trait QueryLogger<CtxT: ?Sized>: fmt::Debug + Send + Sync {
+    /// Returns the identity of the client issuing a request with the given
+    /// `context` (like its name and version, or its authenticated user), to be
+    /// included into the [`QueryLogEntry`] of the request.
+    ///
+    /// Returns [`None`] by default.
+    fn client_identity(&self, context: &CtxT) -> Option<String> {
+        let _ = context;
+        None
+    }
+
+    /// Logs the given [`QueryLogEntry`] of an executed request.
+    fn log(&self, entry: &QueryLogEntry<'_>);
+}
+
+impl<CtxT: ?Sized, T: QueryLogger<CtxT> + ?Sized> QueryLogger<CtxT> for Arc<T> {
+    fn client_identity(&self, context: &CtxT) -> Option<String> {
+        (**self).client_identity(context)
+    }
+
+    fn log(&self, entry: &QueryLogEntry<'_>) {
+        (**self).log(entry)
+    }
+}
+
+impl<CtxT: ?Sized, T: QueryLogger<CtxT> + ?Sized> QueryLogger<CtxT> for &T {
+    fn client_identity(&self, context: &CtxT) -> Option<String> {
+        (**self).client_identity(context)
+    }
+
+    fn log(&self, entry: &QueryLogEntry<'_>) {
+        (**self).log(entry)
+    }
+}
+
+/// Structured entry of an executed request, passed to a [`QueryLogger`].
+///
+/// Doesn't contain any values provided by the client, so may be safely logged:
+/// literals are stripped from the query, and variables are only identified by
+/// their names and hashes of their values.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QueryLogEntry<'a> {
+    query: Option<String>,
+    operation_name: Option<&'a str>,
+    variables: Vec<(&'a str, u64)>,
+    client: Option<String>,
+    duration: Duration,
+    error_codes: Vec<String>,
+}
+
+impl<'a> QueryLogEntry<'a> {
+    /// Creates a new [`QueryLogEntry`] of the request executing the given
+    /// `query` into the given `result`.
+    pub(crate) fn new<S: ScalarValue>(
+        query: &str,
+        operation_name: Option<&'a str>,
+        variables: &'a Variables<S>,
+        client: Option<String>,
+        duration: Duration,
+        result: &Result<(Value<S>, Vec<ExecutionError<S>>), RequestError<'_>>,
+    ) -> Self {
+        let mut variables = variables
+            .iter()
+            .map(|(name, value)| {
+                let mut hasher = FnvHasher::default();
+                hasher.write(value.to_string().as_bytes());
+                (name.as_str(), hasher.finish())
+            })
+            .collect::<Vec<_>>();
+        variables.sort_unstable();
+
+        let error_codes = match result {
+            Ok((_, errors)) => errors.iter().map(error_code).collect(),
+            Err(e) => vec![request_error_code(e).into()],
+        };
+
+        Self {
+            query: normalize_query(query),
+            operation_name,
+            variables,
+            client,
+            duration,
+            error_codes,
+        }
+    }
+
+    /// Returns the normalized text of the executed query, with comments and
+    /// insignificant whitespace removed, and literals replaced with `""`, `0`
+    /// or `0.0` placeholders, so the same query is logged the same way
+    /// regardless of its formatting and inlined values.
+    ///
+    /// Returns [`None`] if the query cannot be tokenized.
+    pub fn query(&self) -> Option<&str> {
+        self.query.as_deref()
+    }
+
+    /// Returns the name of the executed operation, as requested by the client.
+    pub fn operation_name(&self) -> Option<&str> {
+        self.operation_name
+    }
+
+    /// Returns the names of the provided variables, sorted, along with the
+    /// hashes of their values, so requests with equal values may be correlated
+    /// without revealing them.
+    pub fn variables(&self) -> impl Iterator<Item = (&str, u64)> {
+        self.variables.iter().copied()
+    }
+
+    /// Returns the identity of the client, as provided by the
+    /// [`QueryLogger::client_identity()`].
+    pub fn client(&self) -> Option<&str> {
+        self.client.as_deref()
+    }
+
+    /// Returns the duration of the request, from parsing till the end of
+    /// execution.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Returns the codes of the errors of the request: a single one for the
+    /// request failed before execution (like `GRAPHQL_VALIDATION_FAILED`), or
+    /// one per field error (the `code` of its extensions, if any, or
+    /// `FIELD_ERROR` otherwise).
+    pub fn error_codes(&self) -> &[String] {
+        &self.error_codes
+    }
+}
+
+/// Returns the code of the given [`RequestError`].
+fn request_error_code(e: &RequestError<'_>) -> &'static str {
+    match e {
+        RequestError::Parse(_) => "GRAPHQL_PARSE_FAILED",
+        RequestError::Validation(_) => "GRAPHQL_VALIDATION_FAILED",
+        RequestError::VariableCoercion(_) => "BAD_USER_INPUT",
+        RequestError::Operation(_) => "OPERATION_RESOLUTION_FAILURE",
+    }
+}
+
+/// Returns the code of the given [`ExecutionError`], being the `code` of its
+/// extensions, if any.
+fn error_code<S: ScalarValue>(e: &ExecutionError<S>) -> String {
+    e.error()
+        .extensions()
+        .as_object_value()
+        .and_then(|ext| ext.get_field_value("code"))
+        .and_then(Value::as_scalar)
+        .and_then(ScalarValue::as_str)
+        .unwrap_or("FIELD_ERROR")
+        .into()
+}
+
+/// Normalizes the given `query` text by re-printing its tokens with literals
+/// replaced by placeholders.
+fn normalize_query(query: &str) -> Option<String> {
+    let mut normalized = String::with_capacity(query.len());
+    let mut prev_is_word = false;
+    for token in Lexer::new(query) {
+        let token = token.ok()?.item;
+        let is_word = matches!(token, Token::Name(_) | Token::Scalar(_));
+        if is_word && prev_is_word {
+            normalized.push(' ');
+        }
+        prev_is_word = is_word;
+        match token {
+            Token::Scalar(ScalarToken::String(_) | ScalarToken::BlockString(_)) => {
+                normalized.push_str("\"\"")
+            }
+            Token::Scalar(ScalarToken::Int(_)) => normalized.push('0'),
+            Token::Scalar(ScalarToken::Float(_)) => normalized.push_str("0.0"),
+            Token::EndOfFile => break,
+            token => normalized.push_str(&token.to_string()),
+        }
+    }
+    Some(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::normalize_query;
+
+    #[test]
+    fn normalizes_query() {
+        assert_eq!(
+            normalize_query(
+                r#"
+                # Fetches a user.
+                query User($id: ID!, $n: Int = 10) {
+                    user(id: $id, name: "John", score: 1.5) {
+                        friends(first: $n, after: 3) { ...Names }
+                    }
+                }
+                fragment Names on User { name, nicknames @include(if: true) }
+                "#,
+            )
+            .as_deref(),
+            Some(
+                "query User($id:ID!$n:Int=0){user(id:$id name:\"\" score:0.0)\
+                 {friends(first:$n after:0){...Names}}}\
+                 fragment Names on User{name nicknames@include(if:true)}",
+            ),
+        );
+        assert_eq!(normalize_query("{ a }"), normalize_query("{a}"));
+        assert_eq!(normalize_query("{ a(b: \"unterminated) }"), None);
+    }
+}
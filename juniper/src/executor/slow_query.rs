@@ -0,0 +1,258 @@
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use serde::{ser::SerializeMap as _, Serialize, Serializer};
+
+use crate::ast::OperationType;
+
+use super::PathSegment;
+
+/// Hook fired for every operation taking longer to execute than the threshold
+/// set along with it, so slow operations (like ones suffering from N+1
+/// loading) may be found in production without the overhead of full tracing.
+///
+/// Set with [`ExecutorOptions::slow_query_hook()`]. Once set, the execution of
+/// every field is timed, but only the slowest ones are remembered.
+///
+/// ```
+/// # use std::{sync::Mutex, time::Duration};
+/// #
+/// # use juniper::{
+/// #     execute_sync, graphql_object, graphql_vars, EmptyMutation, EmptySubscription,
+/// #     ExecutorOptions, RootNode, SlowQuery, SlowQueryHook,
+/// # };
+/// #
+/// #[derive(Debug, Default)]
+/// struct Reports(Mutex<Vec<String>>);
+///
+/// impl SlowQueryHook for Reports {
+///     fn on_slow_query(&self, query: &SlowQuery) {
+///         let (path, _) = query.slowest().next().unwrap();
+///         self.0.lock().unwrap().push(format!("{:?}", path));
+///     }
+/// }
+///
+/// struct Query;
+///
+/// #[graphql_object]
+/// impl Query {
+///     fn sleepy() -> i32 {
+///         std::thread::sleep(Duration::from_millis(5));
+///         42
+///     }
+/// }
+///
+/// static REPORTS: Reports = Reports(Mutex::new(vec![]));
+///
+/// let schema = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::<()>::new())
+///     .with_executor_options(
+///         ExecutorOptions::new().slow_query_hook(Duration::from_millis(1), &REPORTS),
+///     );
+///
+/// execute_sync("{ sleepy }", None, &schema, &graphql_vars! {}, &()).unwrap();
+/// assert_eq!(REPORTS.0.lock().unwrap()[0], r#"[Field("sleepy")]"#);
+/// ```
+///
+/// [`ExecutorOptions::slow_query_hook()`]: super::ExecutorOptions::slow_query_hook
+pub trait SlowQueryHook: fmt::Debug + Send + Sync {
+    /// Handles the given [`SlowQuery`], having exceeded the threshold.
+    fn on_slow_query(&self, query: &SlowQuery);
+}
+
+impl<T: SlowQueryHook + ?Sized> SlowQueryHook for Arc<T> {
+    fn on_slow_query(&self, query: &SlowQuery) {
+        (**self).on_slow_query(query)
+    }
+}
+
+impl<T: SlowQueryHook + ?Sized> SlowQueryHook for &T {
+    fn on_slow_query(&self, query: &SlowQuery) {
+        (**self).on_slow_query(query)
+    }
+}
+
+/// Snapshot of an operation having exceeded the threshold of a
+/// [`SlowQueryHook`], along with the timings of its slowest fields.
+///
+/// Serializes into a map with `operationName`, `operationType`, `durationMs`
+/// and `slowest` (a list of `path` and `durationMs` maps) entries, to be
+/// logged as a single structured record.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SlowQuery {
+    operation_name: Option<String>,
+    operation_type: OperationType,
+    duration: Duration,
+    slowest: Vec<(Vec<PathSegment>, Duration)>,
+}
+
+impl SlowQuery {
+    /// Maximum number of the slowest fields remembered per operation.
+    pub const SLOWEST_FIELDS: usize = 10;
+
+    /// Returns the name of the slow operation, if any.
+    pub fn operation_name(&self) -> Option<&str> {
+        self.operation_name.as_deref()
+    }
+
+    /// Returns the type of the slow operation.
+    pub fn operation_type(&self) -> OperationType {
+        self.operation_type
+    }
+
+    /// Returns the duration of the operation execution.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Returns the paths of at most [`SlowQuery::SLOWEST_FIELDS`] slowest
+    /// fields of the operation along with their durations, the slowest first.
+    ///
+    /// The duration of a field includes the durations of all its subfields,
+    /// so the path to a slow field usually comes along with the paths to all
+    /// its ancestors.
+    pub fn slowest(&self) -> impl Iterator<Item = (&[PathSegment], Duration)> {
+        self.slowest
+            .iter()
+            .map(|(path, duration)| (path.as_slice(), *duration))
+    }
+}
+
+impl Serialize for SlowQuery {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        struct Timing<'a>(&'a [PathSegment], Duration);
+
+        impl<'a> Serialize for Timing<'a> {
+            fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+                let mut map = ser.serialize_map(Some(2))?;
+                map.serialize_entry("path", self.0)?;
+                map.serialize_entry("durationMs", &millis(self.1))?;
+                map.end()
+            }
+        }
+
+        let mut map = ser.serialize_map(Some(4))?;
+        map.serialize_entry("operationName", &self.operation_name)?;
+        map.serialize_entry(
+            "operationType",
+            match self.operation_type {
+                OperationType::Query => "query",
+                OperationType::Mutation => "mutation",
+                OperationType::Subscription => "subscription",
+            },
+        )?;
+        map.serialize_entry("durationMs", &millis(self.duration))?;
+        map.serialize_entry(
+            "slowest",
+            &self
+                .slowest()
+                .map(|(path, duration)| Timing(path, duration))
+                .collect::<Vec<_>>(),
+        )?;
+        map.end()
+    }
+}
+
+/// Converts the given `duration` into fractional milliseconds.
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// [`SlowQueryHook`] set along with its threshold.
+#[derive(Clone, Debug)]
+pub(crate) struct SlowQueryDetection {
+    pub(crate) threshold: Duration,
+    pub(crate) hook: Arc<dyn SlowQueryHook>,
+}
+
+impl SlowQueryDetection {
+    /// Fires the hook if the operation with the given `operation_name` and
+    /// `operation_type` has been executing for longer than the threshold.
+    pub(crate) fn check(
+        &self,
+        operation_name: Option<&str>,
+        operation_type: OperationType,
+        duration: Duration,
+        timings: Option<&FieldTimings>,
+    ) {
+        if duration < self.threshold {
+            return;
+        }
+        let mut slowest = timings
+            .map(|t| t.0.lock().unwrap().clone())
+            .unwrap_or_default();
+        slowest.sort_by(|(_, a), (_, b)| b.cmp(a));
+        self.hook.on_slow_query(&SlowQuery {
+            operation_name: operation_name.map(Into::into),
+            operation_type,
+            duration,
+            slowest,
+        });
+    }
+}
+
+/// Durations of the slowest fields of an operation, stored in its
+/// [`RequestState`].
+///
+/// [`RequestState`]: super::RequestState
+#[derive(Debug, Default)]
+pub(crate) struct FieldTimings(Mutex<Vec<(Vec<PathSegment>, Duration)>>);
+
+impl FieldTimings {
+    /// Records the `duration` of the field, being one of the slowest ones, at
+    /// the lazily computed `path`.
+    pub(crate) fn record(&self, duration: Duration, path: impl FnOnce() -> Vec<PathSegment>) {
+        let mut slowest = self.0.lock().unwrap();
+        if slowest.len() < SlowQuery::SLOWEST_FIELDS {
+            slowest.push((path(), duration));
+        } else if let Some(fastest) = slowest.iter_mut().min_by_key(|(_, d)| *d) {
+            if fastest.1 < duration {
+                *fastest = (path(), duration);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{ast::OperationType, executor::PathSegment};
+
+    use super::{FieldTimings, SlowQuery};
+
+    #[test]
+    fn keeps_slowest_fields_only() {
+        let timings = FieldTimings::default();
+        for ms in 0..20 {
+            timings.record(
+                Duration::from_millis(ms),
+                || vec![format!("f{}", ms).into()],
+            );
+        }
+
+        let slowest = timings.0.into_inner().unwrap();
+        assert_eq!(slowest.len(), SlowQuery::SLOWEST_FIELDS);
+        assert!(slowest.iter().all(|(_, d)| *d >= Duration::from_millis(10)));
+    }
+
+    #[test]
+    fn serializes() {
+        let query = SlowQuery {
+            operation_name: Some("Users".into()),
+            operation_type: OperationType::Query,
+            duration: Duration::from_millis(30),
+            slowest: vec![(
+                vec![PathSegment::from("users"), PathSegment::from(1)],
+                Duration::from_micros(2500),
+            )],
+        };
+
+        assert_eq!(
+            serde_json::to_string(&query).unwrap(),
+            r#"{"operationName":"Users","operationType":"query","durationMs":30.0,"slowest":[{"path":["users",1],"durationMs":2.5}]}"#,
+        );
+    }
+}
@@ -0,0 +1,248 @@
+use std::{
+    any::{Any, TypeId},
+    fmt,
+};
+
+use once_cell::sync::OnceCell;
+
+/// Number of slots in a single [`Chunk`] of a [`RequestState`].
+const CHUNK_SIZE: usize = 8;
+
+/// Per-request state, shared between all the resolvers of a single operation.
+///
+/// Holds at most one value of each type, created lazily on the first access
+/// and living until the operation is fully executed. Use
+/// [`Executor::state()`] to access it.
+///
+/// # Concurrency
+///
+/// Resolvers of the same operation may run concurrently, so the state is
+/// shared by reference only, and values needing mutation should provide
+/// interior mutability themselves (like atomics, [`OnceCell`]s or a [`Mutex`]
+/// guarding only the data that needs it). This way, resolvers don't contend
+/// on a lock around the whole [`Context`], which would serialize their
+/// execution.
+///
+/// Accessing an already created value doesn't involve any locking. Creating a
+/// new value only synchronizes with other resolvers storing their values at
+/// the same moment, but never while they run their initialization. When
+/// several resolvers create a value of the same type simultaneously, only one
+/// of them is kept and returned to all of them, while the others are dropped.
+///
+/// ```
+/// # use std::sync::atomic::{AtomicUsize, Ordering};
+/// #
+/// # use juniper::{
+/// #     execute_sync, graphql_object, graphql_value, graphql_vars, EmptyMutation,
+/// #     EmptySubscription, Executor, RootNode, ScalarValue,
+/// # };
+/// #
+/// #[derive(Default)]
+/// struct Visits(AtomicUsize);
+///
+/// struct Query;
+///
+/// #[graphql_object]
+/// impl Query {
+///     fn visit<S: ScalarValue>(executor: &Executor<'_, '_, (), S>) -> i32 {
+///         let visits = executor.state().get_or_default::<Visits>();
+///         visits.0.fetch_add(1, Ordering::Relaxed) as i32 + 1
+///     }
+/// }
+///
+/// let schema = RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new());
+///
+/// assert_eq!(
+///     execute_sync("{ first: visit second: visit }", None, &schema, &graphql_vars! {}, &()),
+///     Ok((graphql_value!({"first": 1, "second": 2}), vec![])),
+/// );
+/// // Every request has its own state.
+/// assert_eq!(
+///     execute_sync("{ visit }", None, &schema, &graphql_vars! {}, &()),
+///     Ok((graphql_value!({"visit": 1}), vec![])),
+/// );
+/// ```
+///
+/// [`Context`]: crate::Context
+/// [`Executor::state()`]: crate::Executor::state
+/// [`Mutex`]: std::sync::Mutex
+#[derive(Default)]
+pub struct RequestState {
+    head: Chunk,
+}
+
+impl RequestState {
+    /// Creates a new empty [`RequestState`].
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the value of type `T` in this [`RequestState`], if it has been
+    /// created already.
+    pub fn get<T: Any + Send + Sync>(&self) -> Option<&T> {
+        self.entries()
+            .find(|e| e.type_id == TypeId::of::<T>())
+            .map(Entry::downcast)
+    }
+
+    /// Returns the value of type `T` in this [`RequestState`], creating it
+    /// with the provided `init` function if it doesn't exist yet.
+    ///
+    /// The `init` function may access this [`RequestState`] itself. It may be
+    /// called and its result dropped if the value is created simultaneously
+    /// by another resolver.
+    pub fn get_or_init<T, F>(&self, init: F) -> &T
+    where
+        T: Any + Send + Sync,
+        F: FnOnce() -> T,
+    {
+        if let Some(v) = self.get() {
+            return v;
+        }
+
+        let mut entry = Entry::new(init());
+        let mut chunk = &self.head;
+        loop {
+            for slot in &chunk.slots {
+                // Slots are filled strictly in order, so the first one holding
+                // a value of type `T` is the only one ever returned.
+                entry = match slot.set(entry) {
+                    Ok(()) => return slot.get().unwrap().downcast(),
+                    Err(entry) => entry,
+                };
+                let existing = slot.get().unwrap();
+                if existing.type_id == TypeId::of::<T>() {
+                    return existing.downcast();
+                }
+            }
+            chunk = chunk.next.get_or_init(Box::default);
+        }
+    }
+
+    /// Returns the value of type `T` in this [`RequestState`], creating its
+    /// [`Default`] value if it doesn't exist yet.
+    pub fn get_or_default<T: Any + Default + Send + Sync>(&self) -> &T {
+        self.get_or_init(T::default)
+    }
+
+    /// Iterates over all the created [`Entry`]s of this [`RequestState`].
+    fn entries(&self) -> impl Iterator<Item = &Entry> {
+        let mut chunk = Some(&self.head);
+        std::iter::from_fn(move || {
+            let current = chunk?;
+            chunk = current.next.get().map(AsRef::as_ref);
+            Some(current)
+        })
+        .flat_map(|c| c.slots.iter().map_while(OnceCell::get))
+    }
+}
+
+impl fmt::Debug for RequestState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestState")
+            .field("len", &self.entries().count())
+            .finish()
+    }
+}
+
+/// Fixed-size part of a [`RequestState`], linking the next one once filled up.
+#[derive(Default)]
+struct Chunk {
+    slots: [OnceCell<Entry>; CHUNK_SIZE],
+    next: OnceCell<Box<Chunk>>,
+}
+
+/// Type-erased value stored in a [`RequestState`].
+struct Entry {
+    type_id: TypeId,
+    value: Box<dyn Any + Send + Sync>,
+}
+
+impl Entry {
+    /// Type-erases the given `value` into a new [`Entry`].
+    fn new<T: Any + Send + Sync>(value: T) -> Self {
+        Self {
+            type_id: TypeId::of::<T>(),
+            value: Box::new(value),
+        }
+    }
+
+    /// Returns the value of this [`Entry`] as `T`.
+    ///
+    /// # Panics
+    ///
+    /// If this [`Entry`] doesn't hold a value of type `T`.
+    fn downcast<T: Any>(&self) -> &T {
+        self.value
+            .downcast_ref()
+            .expect("`Entry` type mismatch, this is a bug")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        thread,
+    };
+
+    use super::{RequestState, CHUNK_SIZE};
+
+    #[test]
+    fn stores_single_value_per_type() {
+        let state = RequestState::new();
+
+        assert_eq!(state.get::<i32>(), None);
+        assert_eq!(*state.get_or_init(|| 1), 1);
+        assert_eq!(*state.get_or_init(|| 2), 1);
+        assert_eq!(*state.get_or_init(|| "str"), "str");
+        assert_eq!(state.get::<i32>(), Some(&1));
+        assert_eq!(state.get::<&str>(), Some(&"str"));
+    }
+
+    #[test]
+    fn grows_beyond_single_chunk() {
+        struct Slot<const N: usize>(usize);
+
+        let state = RequestState::new();
+        macro_rules! fill {
+            ($($n:literal)*) => {$(
+                assert_eq!(state.get_or_init(|| Slot::<$n>($n)).0, $n);
+            )*};
+        }
+        fill!(0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19);
+
+        assert!(20 > 2 * CHUNK_SIZE);
+        assert_eq!(state.get::<Slot<0>>().unwrap().0, 0);
+        assert_eq!(state.get::<Slot<19>>().unwrap().0, 19);
+        assert!(state.get::<Slot<20>>().is_none());
+    }
+
+    #[test]
+    fn keeps_single_value_when_racing() {
+        let state = RequestState::new();
+        let inits = AtomicUsize::new(0);
+
+        let values = thread::scope(|s| {
+            let handles = (0..8)
+                .map(|i| {
+                    let (state, inits) = (&state, &inits);
+                    s.spawn(move || {
+                        let _ = state.get_or_default::<u8>();
+                        state.get_or_init(|| {
+                            inits.fetch_add(1, Ordering::SeqCst);
+                            AtomicUsize::new(i)
+                        }) as *const _ as usize
+                    })
+                })
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .map(|h| h.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+
+        assert!(inits.load(Ordering::SeqCst) >= 1);
+        assert!(values.windows(2).all(|w| w[0] == w[1]));
+    }
+}
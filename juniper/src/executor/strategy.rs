@@ -0,0 +1,271 @@
+use std::fmt;
+#[cfg(feature = "rayon")]
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread},
+};
+
+use futures::future::{self, BoxFuture};
+#[cfg(feature = "rayon")]
+use rayon::iter::{
+    IndexedParallelIterator as _, IntoParallelRefMutIterator as _, ParallelIterator as _,
+};
+
+/// Strategy of resolving the fields of a selection set during asynchronous
+/// execution.
+///
+/// The [GraphQL spec][1] requires the root fields of mutations to be resolved
+/// serially, while allowing the other fields to be resolved in parallel. Set
+/// with [`ExecutorOptions::query_strategy()`] and
+/// [`ExecutorOptions::mutation_strategy()`], with [`Parallel`] being the
+/// default one.
+///
+/// Synchronous execution always resolves fields serially, except the queries
+/// executed via [`execute_sync_parallel()`] (with the `rayon` [Cargo feature]).
+///
+/// ```
+/// # use juniper::{futures::future::BoxFuture, ExecutionStrategy, ExecutorOptions, Serial};
+/// #
+/// /// Resolves fields in the reverse order.
+/// #[derive(Debug)]
+/// struct Reversed;
+///
+/// impl ExecutionStrategy for Reversed {
+///     fn resolve_fields<'a>(&self, mut fields: Vec<BoxFuture<'a, ()>>) -> BoxFuture<'a, ()> {
+///         Box::pin(async move {
+///             while let Some(field) = fields.pop() {
+///                 field.await;
+///             }
+///         })
+///     }
+/// }
+///
+/// let options = ExecutorOptions::new()
+///     .query_strategy(Reversed)
+///     .mutation_strategy(Serial);
+/// ```
+///
+/// [`execute_sync_parallel()`]: crate::execute_sync_parallel
+/// [`ExecutorOptions::mutation_strategy()`]: crate::ExecutorOptions::mutation_strategy
+/// [`ExecutorOptions::query_strategy()`]: crate::ExecutorOptions::query_strategy
+/// [1]: https://spec.graphql.org/June2018/#sec-Normal-and-Serial-Execution
+/// [Cargo feature]: https://doc.rust-lang.org/cargo/reference/features.html
+pub trait ExecutionStrategy: fmt::Debug + Send + Sync {
+    /// Returns a [`Future`] resolving all the given `fields` of a selection
+    /// set, listed in the order of their selection.
+    ///
+    /// The resolved values are collected by the executor itself, so the
+    /// strategy only decides how the `fields` are driven to completion.
+    ///
+    /// [`Future`]: std::future::Future
+    fn resolve_fields<'a>(&self, fields: Vec<BoxFuture<'a, ()>>) -> BoxFuture<'a, ()>;
+}
+
+/// [`ExecutionStrategy`] resolving all the fields of a selection set
+/// concurrently.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Parallel;
+
+impl ExecutionStrategy for Parallel {
+    fn resolve_fields<'a>(&self, fields: Vec<BoxFuture<'a, ()>>) -> BoxFuture<'a, ()> {
+        Box::pin(async move {
+            future::join_all(fields).await;
+        })
+    }
+}
+
+/// [`ExecutionStrategy`] resolving the fields of a selection set one by one,
+/// in the order of their selection.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Serial;
+
+impl ExecutionStrategy for Serial {
+    fn resolve_fields<'a>(&self, fields: Vec<BoxFuture<'a, ()>>) -> BoxFuture<'a, ()> {
+        Box::pin(async move {
+            for field in fields {
+                field.await;
+            }
+        })
+    }
+}
+
+/// [`ExecutionStrategy`] resolving the fields of a selection set in parallel
+/// on the current [`rayon`] thread pool (the global one, unless another one is
+/// [installed][1]).
+///
+/// Each time the selection set is polled, its pending fields are polled in
+/// parallel on the pool, so the CPU-bound resolvers run in parallel. The fields
+/// awaiting something (like the other fields, or the keys batched by a
+/// [`DataLoader`]) never block the threads of the pool, but are polled again
+/// once woken, so even a pool of a single thread resolves them.
+///
+/// Used for the queries executed via [`execute_sync_parallel()`].
+///
+/// [`DataLoader`]: crate::dataloader::DataLoader
+/// [`execute_sync_parallel()`]: crate::execute_sync_parallel
+/// [1]: rayon::ThreadPool::install
+#[cfg(feature = "rayon")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Rayon;
+
+#[cfg(feature = "rayon")]
+impl ExecutionStrategy for Rayon {
+    fn resolve_fields<'a>(&self, fields: Vec<BoxFuture<'a, ()>>) -> BoxFuture<'a, ()> {
+        let mut fields = fields.into_iter().map(Some).collect::<Vec<_>>();
+        Box::pin(future::poll_fn(move |cx| {
+            let waker = cx.waker();
+            let poll = |field: &mut Option<BoxFuture<'a, ()>>| {
+                if let Some(f) = field {
+                    if f.as_mut().poll(&mut Context::from_waker(waker)).is_ready() {
+                        *field = None;
+                    }
+                }
+            };
+            // Polling a single field on the pool gains nothing.
+            if fields.len() < 2 {
+                fields.iter_mut().for_each(poll);
+            } else {
+                fields.par_iter_mut().with_max_len(1).for_each(poll);
+            }
+
+            fields.retain(Option::is_some);
+            if fields.is_empty() {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        }))
+    }
+}
+
+/// Runs the given `future` to completion on the current thread, parking it
+/// while the `future` is pending.
+///
+/// Used by [`execute_sync_parallel()`] only, as the [`Rayon`] strategy never
+/// blocks the threads of the pool.
+///
+/// [`execute_sync_parallel()`]: crate::execute_sync_parallel
+#[cfg(feature = "rayon")]
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    /// [`Wake`]r unparking the blocked thread.
+    struct Unparker {
+        thread: Thread,
+
+        /// Indicates whether the future has been woken, as parking may be
+        /// interrupted spuriously.
+        woken: AtomicBool,
+    }
+
+    impl Wake for Unparker {
+        fn wake(self: Arc<Self>) {
+            self.wake_by_ref();
+        }
+
+        fn wake_by_ref(self: &Arc<Self>) {
+            self.woken.store(true, Ordering::Release);
+            self.thread.unpark();
+        }
+    }
+
+    let unparker = Arc::new(Unparker {
+        thread: thread::current(),
+        woken: AtomicBool::new(false),
+    });
+    let waker = Waker::from(Arc::clone(&unparker));
+    let mut cx = Context::from_waker(&waker);
+
+    let mut future = Box::pin(future);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+        while !unparker.woken.swap(false, Ordering::Acquire) {
+            thread::park();
+        }
+    }
+}
+
+/// Marks the operations executed via [`execute_sync_parallel()`] in their
+/// [`RequestState`], so their queries are resolved with the [`Rayon`]
+/// strategy regardless of the [`ExecutorOptions::query_strategy()`].
+///
+/// [`execute_sync_parallel()`]: crate::execute_sync_parallel
+/// [`ExecutorOptions::query_strategy()`]: crate::ExecutorOptions::query_strategy
+/// [`RequestState`]: super::RequestState
+#[cfg(feature = "rayon")]
+#[derive(Debug)]
+pub(crate) struct SyncParallel;
+
+#[cfg(all(test, feature = "rayon"))]
+mod tests {
+    use std::{
+        sync::{mpsc, Arc, Barrier},
+        thread,
+        time::Duration,
+    };
+
+    use futures::{channel::oneshot, future::BoxFuture};
+
+    use super::{block_on, ExecutionStrategy as _, Rayon};
+
+    /// Resolves the given `fields` with the [`Rayon`] strategy on a new pool of
+    /// the given number of `threads`, failing if they're not resolved in time.
+    fn resolve(fields: Vec<BoxFuture<'static, ()>>, threads: usize) {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .unwrap();
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            pool.install(|| block_on(Rayon.resolve_fields(fields)));
+            tx.send(()).unwrap();
+        });
+        rx.recv_timeout(Duration::from_secs(10)).unwrap();
+    }
+
+    #[test]
+    fn resolves_fields_in_parallel() {
+        // Every field waits for all the others, so they only complete if all
+        // of them are resolved at the same time.
+        let barrier = Arc::new(Barrier::new(2));
+        let fields = (0..2)
+            .map(|_| {
+                let barrier = Arc::clone(&barrier);
+                Box::pin(async move {
+                    barrier.wait();
+                }) as BoxFuture<'_, ()>
+            })
+            .collect();
+
+        resolve(fields, 2);
+    }
+
+    #[test]
+    fn resolves_fields_awaiting_each_other_on_single_thread() {
+        let (ping_tx, ping_rx) = oneshot::channel();
+        let (pong_tx, pong_rx) = oneshot::channel();
+        let fields = vec![
+            Box::pin(async move {
+                ping_tx.send(1).unwrap();
+                assert_eq!(pong_rx.await, Ok(2));
+            }) as BoxFuture<'_, ()>,
+            Box::pin(async move {
+                let n = ping_rx.await.unwrap();
+                pong_tx.send(n + 1).unwrap();
+            }),
+        ];
+
+        resolve(fields, 1);
+    }
+
+    #[test]
+    fn blocks_on_nested_futures() {
+        assert_eq!(block_on(async { block_on(async { 1 }) + 1 }), 2);
+    }
+}
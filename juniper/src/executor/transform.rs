@@ -0,0 +1,259 @@
+use std::{fmt, sync::Arc};
+
+use crate::{
+    ast::{Definition, Document, Fragment, Operation, OperationType, Selection},
+    parser::Spanning,
+    schema::{
+        meta::{Field, MetaType},
+        model::SchemaType,
+    },
+    value::Value,
+};
+
+/// Field of a response being transformed by a [`ResponseTransform`], along
+/// with its schema metadata.
+#[derive(Debug)]
+pub struct ResponseField<'r, S> {
+    parent_type: &'r MetaType<'r, S>,
+    field: &'r Field<'r, S>,
+    path: &'r [String],
+}
+
+impl<'r, S> ResponseField<'r, S> {
+    /// Returns the metadata of the type (object or interface) this field is
+    /// selected on.
+    pub fn parent_type(&self) -> &'r MetaType<'r, S> {
+        self.parent_type
+    }
+
+    /// Returns the metadata of this field in the schema.
+    pub fn field(&self) -> &'r Field<'r, S> {
+        self.field
+    }
+
+    /// Returns the path of this field in the response, consisting of response
+    /// keys (aliases, if any) only, as lists are traversed transparently.
+    pub fn path(&self) -> &'r [String] {
+        self.path
+    }
+}
+
+/// Transform applied to the response of every executed query and mutation
+/// before it's returned, allowing to redact or normalize it in a single place
+/// (e.g. masking card numbers).
+///
+/// Set with [`RootNode::with_response_transform()`].
+///
+/// [`RootNode::with_response_transform()`]: crate::RootNode::with_response_transform
+pub trait ResponseTransform<S>: fmt::Debug + Send + Sync {
+    /// Transforms the `value` resolved for the given response `field`.
+    ///
+    /// Called for every field present in the response, after all its
+    /// subfields have been transformed. Does nothing by default.
+    fn transform_field(&self, field: &ResponseField<'_, S>, value: &mut Value<S>) {
+        let _ = (field, value);
+    }
+
+    /// Transforms the full response `data` of the operation with the given
+    /// `operation_name` and `operation_type`.
+    ///
+    /// Called once, after all the fields have been transformed with the
+    /// [`ResponseTransform::transform_field()`]. Does nothing by default.
+    fn transform_response(
+        &self,
+        operation_name: Option<&str>,
+        operation_type: OperationType,
+        data: &mut Value<S>,
+    ) {
+        let _ = (operation_name, operation_type, data);
+    }
+}
+
+impl<S, T: ResponseTransform<S> + ?Sized> ResponseTransform<S> for Arc<T> {
+    fn transform_field(&self, field: &ResponseField<'_, S>, value: &mut Value<S>) {
+        (**self).transform_field(field, value)
+    }
+
+    fn transform_response(
+        &self,
+        operation_name: Option<&str>,
+        operation_type: OperationType,
+        data: &mut Value<S>,
+    ) {
+        (**self).transform_response(operation_name, operation_type, data)
+    }
+}
+
+impl<S, T: ResponseTransform<S> + ?Sized> ResponseTransform<S> for &T {
+    fn transform_field(&self, field: &ResponseField<'_, S>, value: &mut Value<S>) {
+        (**self).transform_field(field, value)
+    }
+
+    fn transform_response(
+        &self,
+        operation_name: Option<&str>,
+        operation_type: OperationType,
+        data: &mut Value<S>,
+    ) {
+        (**self).transform_response(operation_name, operation_type, data)
+    }
+}
+
+/// Applies the [`ResponseTransform`] of the `schema` (if any) to the response
+/// `data` of the given `operation` of the `document`.
+pub(crate) fn apply<S>(
+    schema: &SchemaType<S>,
+    document: &Document<S>,
+    operation: &Spanning<Operation<S>>,
+    data: &mut Value<S>,
+) {
+    let transform = match schema.response_transform() {
+        Some(t) => t,
+        None => return,
+    };
+    let root = match operation.item.operation_type {
+        OperationType::Query => Some(schema.concrete_query_type()),
+        OperationType::Mutation => schema.concrete_mutation_type(),
+        OperationType::Subscription => schema.concrete_subscription_type(),
+    };
+    if let Some(root) = root {
+        let walker = Walker {
+            schema,
+            transform,
+            fragments: document
+                .iter()
+                .filter_map(|def| match def {
+                    Definition::Fragment(f) => Some(&f.item),
+                    Definition::Operation(_) => None,
+                })
+                .collect(),
+        };
+        walker.walk(&[&operation.item.selection_set], root, &mut vec![], data);
+    }
+    transform.transform_response(
+        operation.item.name.as_ref().map(|n| n.item),
+        operation.item.operation_type,
+        data,
+    );
+}
+
+/// Field selected on an object, with all the selection sets of its same-keyed
+/// occurrences merged.
+struct Selected<'s, 'd, 'a, S> {
+    key: &'a str,
+    parent_type: &'s MetaType<'s, S>,
+    field: &'s Field<'s, S>,
+    selection_sets: Vec<&'d [Selection<'a, S>]>,
+}
+
+/// Walker of a response [`Value`] along with the selection sets and schema
+/// types it was resolved from.
+struct Walker<'s, 'd, 'a, S> {
+    schema: &'s SchemaType<'s, S>,
+    transform: &'s dyn ResponseTransform<S>,
+    fragments: Vec<&'d Fragment<'a, S>>,
+}
+
+impl<'s, 'd, 'a, S> Walker<'s, 'd, 'a, S> {
+    fn walk(
+        &self,
+        selection_sets: &[&'d [Selection<'a, S>]],
+        meta_type: &'s MetaType<'s, S>,
+        path: &mut Vec<String>,
+        value: &mut Value<S>,
+    ) {
+        let obj = match value {
+            Value::Object(obj) => obj,
+            Value::List(list) => {
+                for item in list {
+                    self.walk(selection_sets, meta_type, path, item);
+                }
+                return;
+            }
+            Value::Null | Value::Scalar(_) => return,
+        };
+
+        let mut selected = vec![];
+        for selection_set in selection_sets {
+            self.select(selection_set, meta_type, &mut selected, &mut vec![]);
+        }
+
+        for s in selected {
+            let value = match obj.get_mut_field_value(s.key) {
+                Some(v) => v,
+                None => continue,
+            };
+            path.push(s.key.to_owned());
+            if !s.selection_sets.is_empty() {
+                let inner = s.field.field_type.innermost_name();
+                if let Some(inner) = self.schema.concrete_type_by_name(inner) {
+                    self.walk(&s.selection_sets, inner, path, value);
+                }
+            }
+            let field = ResponseField {
+                parent_type: s.parent_type,
+                field: s.field,
+                path,
+            };
+            self.transform.transform_field(&field, value);
+            path.pop();
+        }
+    }
+
+    /// Collects the fields of the `selection_set` selected on the `meta_type`
+    /// into the `selected` ones, flattening fragments.
+    fn select(
+        &self,
+        selection_set: &'d [Selection<'a, S>],
+        meta_type: &'s MetaType<'s, S>,
+        selected: &mut Vec<Selected<'s, 'd, 'a, S>>,
+        visiting: &mut Vec<&'a str>,
+    ) {
+        for selection in selection_set {
+            match selection {
+                Selection::Field(f) => {
+                    let key = f.item.alias.as_ref().unwrap_or(&f.item.name).item;
+                    let inner = f.item.selection_set.as_deref();
+                    if let Some(s) = selected.iter_mut().find(|s| s.key == key) {
+                        s.selection_sets.extend(inner);
+                        continue;
+                    }
+                    if let Some(field) = meta_type.field_by_name(f.item.name.item) {
+                        selected.push(Selected {
+                            key,
+                            parent_type: meta_type,
+                            field,
+                            selection_sets: inner.into_iter().collect(),
+                        });
+                    }
+                }
+                Selection::InlineFragment(f) => {
+                    let meta_type = f
+                        .item
+                        .type_condition
+                        .as_ref()
+                        .and_then(|cond| self.schema.concrete_type_by_name(cond.item))
+                        .unwrap_or(meta_type);
+                    self.select(&f.item.selection_set, meta_type, selected, visiting);
+                }
+                Selection::FragmentSpread(s) => {
+                    let name = s.item.name.item;
+                    if visiting.contains(&name) {
+                        continue;
+                    }
+                    let fragment = match self.fragments.iter().find(|f| f.name.item == name) {
+                        Some(f) => f,
+                        None => continue,
+                    };
+                    let meta_type = self
+                        .schema
+                        .concrete_type_by_name(fragment.type_condition.item)
+                        .unwrap_or(meta_type);
+                    visiting.push(name);
+                    self.select(&fragment.selection_set, meta_type, selected, visiting);
+                    visiting.pop();
+                }
+            }
+        }
+    }
+}
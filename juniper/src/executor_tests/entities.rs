@@ -0,0 +1,201 @@
+use futures::future::{self, BoxFuture, FutureExt as _};
+
+use crate::{
+    execute_entity, graphql_input_value, graphql_object, graphql_value, graphql_vars,
+    parser::SourcePosition,
+    schema::model::RootNode,
+    types::scalars::{EmptyMutation, EmptySubscription},
+    EntityResolver, ExecutionError, FieldError, FieldResult, InputValue, RequestError, RuleError,
+};
+
+struct Database {
+    users: Vec<&'static str>,
+}
+
+impl crate::Context for Database {}
+
+struct User {
+    id: i32,
+    name: &'static str,
+}
+
+#[graphql_object(context = Database)]
+impl User {
+    fn id(&self) -> i32 {
+        self.id
+    }
+
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn friends(&self, context: &Database) -> Vec<User> {
+        context
+            .users
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i as i32 != self.id)
+            .map(|(i, name)| User { id: i as i32, name })
+            .collect()
+    }
+}
+
+struct UserById;
+
+impl EntityResolver for UserById {
+    type Entity = User;
+
+    fn resolve_entity<'a>(
+        &'a self,
+        representation: &'a InputValue,
+        db: &'a Database,
+    ) -> BoxFuture<'a, FieldResult<Option<User>>> {
+        let res = match representation
+            .get_path("id")
+            .and_then(InputValue::as_int_value)
+        {
+            Some(id) => Ok(db.users.get(id as usize).map(|name| User { id, name })),
+            None => Err(FieldError::from("Missing `id` key")),
+        };
+        future::ready(res).boxed()
+    }
+}
+
+struct Query;
+
+#[graphql_object(context = Database)]
+impl Query {
+    fn users() -> Vec<User> {
+        vec![]
+    }
+}
+
+type Schema = RootNode<'static, Query, EmptyMutation<Database>, EmptySubscription<Database>>;
+
+fn schema() -> Schema {
+    RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+        .with_entity_resolver(UserById)
+}
+
+fn db() -> Database {
+    Database {
+        users: vec!["Alice", "Bob"],
+    }
+}
+
+#[tokio::test]
+async fn executes_selection_on_entity() {
+    let schema = schema();
+    let doc = "fragment F on User { name friends { ...Friend } } fragment Friend on User { id }";
+
+    let res = execute_entity(
+        "User",
+        &graphql_input_value!({"id": 1}),
+        doc,
+        Some("F"),
+        &schema,
+        &graphql_vars! {},
+        &db(),
+    )
+    .await;
+
+    assert_eq!(
+        res,
+        Ok((
+            graphql_value!({"name": "Bob", "friends": [{"id": 0}]}),
+            vec![],
+        )),
+    );
+}
+
+#[tokio::test]
+async fn resolves_missing_entity_into_null() {
+    let schema = schema();
+
+    let res = execute_entity(
+        "User",
+        &graphql_input_value!({"id": 5}),
+        "fragment F on User { name }",
+        None,
+        &schema,
+        &graphql_vars! {},
+        &db(),
+    )
+    .await;
+
+    assert_eq!(res, Ok((graphql_value!(null), vec![])));
+}
+
+#[tokio::test]
+async fn returns_resolver_error() {
+    let schema = schema();
+
+    let res = execute_entity(
+        "User",
+        &graphql_input_value!({"key": 1}),
+        "fragment F on User { name }",
+        None,
+        &schema,
+        &graphql_vars! {},
+        &db(),
+    )
+    .await;
+
+    assert_eq!(
+        res,
+        Ok((
+            graphql_value!(null),
+            vec![ExecutionError::at_origin(FieldError::from(
+                "Missing `id` key",
+            ))],
+        )),
+    );
+}
+
+#[tokio::test]
+async fn rejects_type_without_resolver() {
+    let schema = schema();
+
+    let res = execute_entity(
+        "Query",
+        &graphql_input_value!({"id": 1}),
+        "fragment F on Query { users { id } }",
+        None,
+        &schema,
+        &graphql_vars! {},
+        &db(),
+    )
+    .await;
+
+    assert_eq!(
+        res,
+        Err(RequestError::Validation(vec![RuleError::new(
+            "No entity resolver is registered for type \"Query\"",
+            &[SourcePosition::new(0, 0, 0)],
+        )])),
+    );
+}
+
+#[tokio::test]
+async fn rejects_mismatched_type_condition() {
+    let schema = schema();
+
+    let res = execute_entity(
+        "User",
+        &graphql_input_value!({"id": 1}),
+        "fragment F on Query { users { id } }",
+        None,
+        &schema,
+        &graphql_vars! {},
+        &db(),
+    )
+    .await;
+
+    assert_eq!(
+        res,
+        Err(RequestError::Validation(vec![RuleError::new(
+            "Fragment \"F\" on \"Query\" cannot be executed against a value of type \"User\"",
+            &[SourcePosition::new(0, 0, 0)],
+        )])),
+    );
+}
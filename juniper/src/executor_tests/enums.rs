@@ -7,7 +7,7 @@ use crate::{
     validation::RuleError,
     value::{DefaultScalarValue, Object},
     GraphQLEnum,
-    GraphQLError::ValidationError,
+    RequestError::{Validation, VariableCoercion},
 };
 
 #[derive(GraphQLEnum, Debug)]
@@ -99,7 +99,7 @@ async fn does_not_accept_string_literals() {
 
     assert_eq!(
         error,
-        ValidationError(vec![RuleError::new(
+        Validation(vec![RuleError::new(
             r#"Invalid value for argument "color", expected type "Color!""#,
             &[SourcePosition::new(18, 0, 18)],
         )])
@@ -138,7 +138,7 @@ async fn does_not_accept_incorrect_enum_name_in_variables() {
 
     assert_eq!(
         error,
-        ValidationError(vec![RuleError::new(
+        VariableCoercion(vec![RuleError::new(
             r#"Variable "$color" got invalid value. Invalid value for enum "Color"."#,
             &[SourcePosition::new(8, 0, 8)],
         )]),
@@ -162,7 +162,7 @@ async fn does_not_accept_incorrect_type_in_variables() {
 
     assert_eq!(
         error,
-        ValidationError(vec![RuleError::new(
+        VariableCoercion(vec![RuleError::new(
             r#"Variable "$color" got invalid value. Expected "Color", found not a string or enum."#,
             &[SourcePosition::new(8, 0, 8)],
         )]),
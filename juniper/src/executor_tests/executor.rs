@@ -675,12 +675,14 @@ mod dynamic_context_switching {
 
 mod propagates_errors_to_nullable_fields {
     use crate::{
-        executor::{ExecutionError, FieldError, FieldResult, IntoFieldError},
+        executor::{ExecutionError, FieldError, FieldResult, IntoFieldError, PathSegment},
         graphql_object, graphql_value, graphql_vars,
+        http::GraphQLResponse,
         parser::SourcePosition,
         schema::model::RootNode,
         types::scalars::{EmptyMutation, EmptySubscription},
         value::{ScalarValue, Value},
+        RequestError,
     };
 
     struct Schema;
@@ -688,6 +690,7 @@ mod propagates_errors_to_nullable_fields {
 
     enum CustomError {
         NotFound,
+        Forbidden,
     }
 
     impl<S> IntoFieldError<S> for CustomError
@@ -702,6 +705,12 @@ mod propagates_errors_to_nullable_fields {
                     });
                     FieldError::new("Not Found", v)
                 }
+                CustomError::Forbidden => {
+                    let v: Value<S> = graphql_value!({
+                        "type": "FORBIDDEN"
+                    });
+                    FieldError::new("Forbidden", v)
+                }
             }
         }
     }
@@ -736,6 +745,9 @@ mod propagates_errors_to_nullable_fields {
         fn custom_error_field() -> Result<&'static str, CustomError> {
             Err(CustomError::NotFound)
         }
+        fn custom_errors_field() -> Result<Option<&'static str>, Vec<CustomError>> {
+            Err(vec![CustomError::NotFound, CustomError::Forbidden])
+        }
     }
 
     #[tokio::test]
@@ -769,6 +781,46 @@ mod propagates_errors_to_nullable_fields {
         );
     }
 
+    #[test]
+    fn response_separates_execution_errors() {
+        let schema = RootNode::new(
+            Schema,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+        let vars = graphql_vars! {};
+
+        let res = GraphQLResponse::from_result(crate::execute_sync(
+            r"{ inner { nullableErrorField } }",
+            None,
+            &schema,
+            &vars,
+            &(),
+        ));
+        assert_eq!(res.request_error(), None);
+        assert_eq!(
+            res.execution_errors(),
+            [ExecutionError::new(
+                SourcePosition::new(10, 0, 10),
+                &["inner", "nullableErrorField"],
+                FieldError::new("Error for nullableErrorField", graphql_value!(null)),
+            )],
+        );
+
+        let res = GraphQLResponse::from_result(crate::execute_sync(
+            r"{ inner { unknownField } }",
+            None,
+            &schema,
+            &vars,
+            &(),
+        ));
+        assert!(matches!(
+            res.request_error(),
+            Some(RequestError::Validation(errs)) if errs.len() == 1,
+        ));
+        assert!(res.execution_errors().is_empty());
+    }
+
     #[tokio::test]
     async fn non_nullable_first_level() {
         let schema = RootNode::new(
@@ -825,6 +877,42 @@ mod propagates_errors_to_nullable_fields {
         );
     }
 
+    #[tokio::test]
+    async fn custom_errors_first_level() {
+        let schema = RootNode::new(
+            Schema,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+        let doc = r"{ inner { customErrorsField } }";
+        let vars = graphql_vars! {};
+
+        let (result, errs) = crate::execute(doc, None, &schema, &vars, &())
+            .await
+            .expect("Execution failed");
+
+        assert_eq!(
+            result,
+            graphql_value!({"inner": {"customErrorsField": null}}),
+        );
+
+        assert_eq!(
+            errs,
+            vec![
+                ExecutionError::new(
+                    SourcePosition::new(10, 0, 10),
+                    &["inner", "customErrorsField"],
+                    FieldError::new("Forbidden", graphql_value!({"type": "FORBIDDEN"})),
+                ),
+                ExecutionError::new(
+                    SourcePosition::new(10, 0, 10),
+                    &["inner", "customErrorsField"],
+                    FieldError::new("Not Found", graphql_value!({"type": "NOT_FOUND"})),
+                ),
+            ],
+        );
+    }
+
     #[tokio::test]
     async fn nullable_nested_level() {
         let schema = RootNode::new(
@@ -934,7 +1022,11 @@ mod propagates_errors_to_nullable_fields {
             errs,
             vec![ExecutionError::new(
                 SourcePosition::new(11, 0, 11),
-                &["inners", "nonNullableErrorField"],
+                &[
+                    PathSegment::from("inners"),
+                    PathSegment::Index(0),
+                    PathSegment::from("nonNullableErrorField"),
+                ],
                 FieldError::new("Error for nonNullableErrorField", graphql_value!(null)),
             )],
         );
@@ -966,27 +1058,47 @@ mod propagates_errors_to_nullable_fields {
             vec![
                 ExecutionError::new(
                     SourcePosition::new(19, 0, 19),
-                    &["nullableInners", "nonNullableErrorField"],
+                    &[
+                        PathSegment::from("nullableInners"),
+                        PathSegment::Index(0),
+                        PathSegment::from("nonNullableErrorField"),
+                    ],
                     FieldError::new("Error for nonNullableErrorField", graphql_value!(null)),
                 ),
                 ExecutionError::new(
                     SourcePosition::new(19, 0, 19),
-                    &["nullableInners", "nonNullableErrorField"],
+                    &[
+                        PathSegment::from("nullableInners"),
+                        PathSegment::Index(1),
+                        PathSegment::from("nonNullableErrorField"),
+                    ],
                     FieldError::new("Error for nonNullableErrorField", graphql_value!(null)),
                 ),
                 ExecutionError::new(
                     SourcePosition::new(19, 0, 19),
-                    &["nullableInners", "nonNullableErrorField"],
+                    &[
+                        PathSegment::from("nullableInners"),
+                        PathSegment::Index(2),
+                        PathSegment::from("nonNullableErrorField"),
+                    ],
                     FieldError::new("Error for nonNullableErrorField", graphql_value!(null)),
                 ),
                 ExecutionError::new(
                     SourcePosition::new(19, 0, 19),
-                    &["nullableInners", "nonNullableErrorField"],
+                    &[
+                        PathSegment::from("nullableInners"),
+                        PathSegment::Index(3),
+                        PathSegment::from("nonNullableErrorField"),
+                    ],
                     FieldError::new("Error for nonNullableErrorField", graphql_value!(null)),
                 ),
                 ExecutionError::new(
                     SourcePosition::new(19, 0, 19),
-                    &["nullableInners", "nonNullableErrorField"],
+                    &[
+                        PathSegment::from("nullableInners"),
+                        PathSegment::Index(4),
+                        PathSegment::from("nonNullableErrorField"),
+                    ],
                     FieldError::new("Error for nonNullableErrorField", graphql_value!(null)),
                 ),
             ],
@@ -999,7 +1111,7 @@ mod named_operations {
         graphql_object, graphql_value, graphql_vars,
         schema::model::RootNode,
         types::scalars::{EmptyMutation, EmptySubscription},
-        GraphQLError,
+        OperationError, RequestError,
     };
 
     struct Schema;
@@ -1081,7 +1193,10 @@ mod named_operations {
             .await
             .unwrap_err();
 
-        assert_eq!(err, GraphQLError::MultipleOperationsProvided);
+        assert_eq!(
+            err,
+            RequestError::Operation(OperationError::MultipleOperationsProvided)
+        );
     }
 
     #[tokio::test]
@@ -1098,6 +1213,88 @@ mod named_operations {
             .await
             .unwrap_err();
 
-        assert_eq!(err, GraphQLError::UnknownOperationName);
+        assert_eq!(
+            err,
+            RequestError::Operation(OperationError::UnknownOperationName)
+        );
+    }
+}
+
+#[cfg(feature = "stacker")]
+mod grows_stack {
+    use crate::{
+        execute, execute_sync, graphql_object, graphql_value, graphql_vars,
+        schema::model::RootNode,
+        types::scalars::{EmptyMutation, EmptySubscription},
+        ExecutorOptions,
+    };
+
+    const STACK_SIZE: usize = 64 * 1024 * 1024;
+
+    struct Node;
+
+    #[graphql_object]
+    impl Node {
+        fn child() -> Node {
+            Node
+        }
+
+        fn on_grown_stack() -> bool {
+            stacker::remaining_stack().is_some_and(|r| r > STACK_SIZE / 2)
+        }
+    }
+
+    fn schema(
+        options: ExecutorOptions,
+    ) -> RootNode<'static, Node, EmptyMutation, EmptySubscription> {
+        RootNode::new(Node, EmptyMutation::new(), EmptySubscription::new())
+            .with_executor_options(options)
+    }
+
+    const DOC: &str = "{ onGrownStack child { onGrownStack child { onGrownStack } } }";
+
+    #[test]
+    fn sync() {
+        // The red zone never fits the current stack, so each nested selection
+        // set is resolved on a freshly allocated one.
+        let grown = schema(ExecutorOptions::new().grow_stack(usize::MAX / 2, STACK_SIZE));
+        let not_grown = schema(ExecutorOptions::new());
+
+        assert_eq!(
+            execute_sync(DOC, None, &grown, &graphql_vars! {}, &()),
+            Ok((
+                graphql_value!({
+                    "onGrownStack": true,
+                    "child": {"onGrownStack": true, "child": {"onGrownStack": true}},
+                }),
+                vec![],
+            )),
+        );
+        assert_eq!(
+            execute_sync(DOC, None, &not_grown, &graphql_vars! {}, &()),
+            Ok((
+                graphql_value!({
+                    "onGrownStack": false,
+                    "child": {"onGrownStack": false, "child": {"onGrownStack": false}},
+                }),
+                vec![],
+            )),
+        );
+    }
+
+    #[tokio::test]
+    async fn r#async() {
+        let grown = schema(ExecutorOptions::new().grow_stack(usize::MAX / 2, STACK_SIZE));
+
+        assert_eq!(
+            execute(DOC, None, &grown, &graphql_vars! {}, &()).await,
+            Ok((
+                graphql_value!({
+                    "onGrownStack": true,
+                    "child": {"onGrownStack": true, "child": {"onGrownStack": true}},
+                }),
+                vec![],
+            )),
+        );
     }
 }
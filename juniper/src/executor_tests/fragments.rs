@@ -0,0 +1,211 @@
+use crate::{
+    execute_fragment, execute_fragment_sync, graphql_interface, graphql_object, graphql_value,
+    graphql_vars,
+    parser::SourcePosition,
+    schema::model::RootNode,
+    types::scalars::{EmptyMutation, EmptySubscription},
+    ExecutionError, FieldError, FieldResult, OperationError, PathSegment, RequestError, RuleError,
+};
+
+#[graphql_interface(for = User)]
+trait Node {
+    fn id(&self) -> i32;
+}
+
+struct User {
+    id: i32,
+    name: String,
+    friends: Vec<User>,
+}
+
+#[graphql_object(impl = NodeValue)]
+impl User {
+    fn id(&self) -> i32 {
+        self.id
+    }
+
+    fn name(&self, upper: Option<bool>) -> String {
+        if upper.unwrap_or_default() {
+            self.name.to_uppercase()
+        } else {
+            self.name.clone()
+        }
+    }
+
+    fn friends(&self) -> &[User] {
+        &self.friends
+    }
+
+    fn secret(&self) -> FieldResult<Option<&str>> {
+        Err("Not allowed".into())
+    }
+}
+
+struct Query;
+
+#[graphql_object]
+impl Query {
+    fn user() -> Option<User> {
+        None
+    }
+}
+
+type Schema = RootNode<'static, Query, EmptyMutation, EmptySubscription>;
+
+fn schema() -> Schema {
+    RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+}
+
+fn user() -> User {
+    User {
+        id: 1,
+        name: "Alice".into(),
+        friends: vec![User {
+            id: 2,
+            name: "Bob".into(),
+            friends: vec![],
+        }],
+    }
+}
+
+#[test]
+fn executes_fragment_against_value() {
+    let schema = schema();
+    let doc = r"
+        fragment UserCard on User {
+            name(upper: $upper)
+            friends { ...Friend }
+        }
+        fragment Friend on User { id name }
+    ";
+
+    let res = execute_fragment_sync(
+        doc,
+        Some("UserCard"),
+        &schema,
+        &user(),
+        &(),
+        &graphql_vars! {"upper": true},
+        &(),
+    );
+
+    assert_eq!(
+        res,
+        Ok((
+            graphql_value!({
+                "name": "ALICE",
+                "friends": [{"id": 2, "name": "Bob"}],
+            }),
+            vec![],
+        )),
+    );
+}
+
+#[tokio::test]
+async fn executes_fragment_against_value_async() {
+    let schema = schema();
+    let doc = "fragment UserCard on User { id friends { name } }";
+
+    let res = execute_fragment(doc, None, &schema, &user(), &(), &graphql_vars! {}, &()).await;
+
+    assert_eq!(
+        res,
+        Ok((
+            graphql_value!({"id": 1, "friends": [{"name": "Bob"}]}),
+            vec![],
+        )),
+    );
+}
+
+#[test]
+fn executes_fragment_on_interface() {
+    let schema = schema();
+    let doc = "fragment N on Node { id ... on User { name } }";
+
+    let res = execute_fragment_sync(doc, None, &schema, &user(), &(), &graphql_vars! {}, &());
+
+    assert_eq!(
+        res,
+        Ok((graphql_value!({"id": 1, "name": "Alice"}), vec![])),
+    );
+}
+
+#[test]
+fn returns_field_errors() {
+    let schema = schema();
+    let doc = "fragment F on User { friends { secret } }";
+
+    let res = execute_fragment_sync(doc, None, &schema, &user(), &(), &graphql_vars! {}, &());
+
+    assert_eq!(
+        res,
+        Ok((
+            graphql_value!({"friends": [{"secret": null}]}),
+            vec![ExecutionError::new(
+                SourcePosition::new(31, 0, 31),
+                &[PathSegment::from("friends"), 0.into(), "secret".into()],
+                FieldError::from("Not allowed"),
+            )],
+        )),
+    );
+}
+
+#[test]
+fn rejects_mismatched_type_condition() {
+    let schema = schema();
+    let doc = "fragment F on Query { user { id } }";
+
+    let res = execute_fragment_sync(doc, None, &schema, &user(), &(), &graphql_vars! {}, &());
+
+    assert_eq!(
+        res,
+        Err(RequestError::Validation(vec![RuleError::new(
+            "Fragment \"F\" on \"Query\" cannot be executed against a value of type \"User\"",
+            &[SourcePosition::new(0, 0, 0)],
+        )])),
+    );
+}
+
+#[test]
+fn rejects_invalid_fragment() {
+    let schema = schema();
+    let doc = "fragment F on User { unknown }";
+
+    let res = execute_fragment_sync(doc, None, &schema, &user(), &(), &graphql_vars! {}, &());
+
+    assert_eq!(
+        res,
+        Err(RequestError::Validation(vec![RuleError::new(
+            r#"Unknown field "unknown" on type "User""#,
+            &[SourcePosition::new(21, 0, 21)],
+        )])),
+    );
+}
+
+#[test]
+fn selects_fragment() {
+    let schema = schema();
+    let doc = "fragment A on User { id } fragment B on User { name }";
+
+    for (name, expected) in [
+        (None, Err(OperationError::MultipleOperationsProvided.into())),
+        (Some("C"), Err(OperationError::UnknownOperationName.into())),
+        (Some("B"), Ok((graphql_value!({"name": "Alice"}), vec![]))),
+    ] {
+        let res = execute_fragment_sync(doc, name, &schema, &user(), &(), &graphql_vars! {}, &());
+
+        assert_eq!(res, expected, "fragment: {:?}", name);
+    }
+
+    let res = execute_fragment_sync(
+        "{ user { id } }",
+        None,
+        &schema,
+        &user(),
+        &(),
+        &graphql_vars! {},
+        &(),
+    );
+
+    assert_eq!(res, Err(OperationError::NoOperationProvided.into()));
+}
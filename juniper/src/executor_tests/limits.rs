@@ -0,0 +1,347 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use crate::{
+    execute, execute_sync, execute_sync_with_limits, execute_with_limits, graphql_object,
+    graphql_value, graphql_vars,
+    parser::SourcePosition,
+    schema::model::RootNode,
+    types::scalars::{EmptyMutation, EmptySubscription},
+    validation::RuleError,
+    ExecutorOptions, FieldError, Limits, MetricsSink, OperationMeasure, OperationType,
+    RequestError::Validation,
+};
+
+struct Node;
+
+#[graphql_object]
+impl Node {
+    fn id() -> i32 {
+        1
+    }
+
+    fn child() -> Node {
+        Node
+    }
+
+    #[graphql(complexity = |args, child| {
+        let count = args.get::<i32>("count").ok().flatten().unwrap_or_default();
+        (count.max(0) as usize).saturating_mul(child).saturating_add(1)
+    })]
+    fn children(count: i32) -> Option<Vec<Node>> {
+        Some((0..count).map(|_| Node).collect())
+    }
+}
+
+fn schema() -> RootNode<'static, Node, EmptyMutation, EmptySubscription> {
+    RootNode::new(Node, EmptyMutation::new(), EmptySubscription::new())
+}
+
+#[tokio::test]
+async fn rejects_too_deep_operation() {
+    let schema = schema().with_limits(Limits::new().max_depth(2));
+    let doc = r"{ id child { id } alias: child { child { id } } }";
+
+    assert_eq!(
+        execute(doc, None, &schema, &graphql_vars! {}, &()).await,
+        Err(Validation(vec![RuleError::new(
+            "Operation has depth of 3, exceeding the maximum allowed depth of 2",
+            &[SourcePosition::new(41, 0, 41)],
+        )])),
+    );
+}
+
+#[tokio::test]
+async fn identifies_exceeded_limits() {
+    let doc = r"{ a: id b: id c: id d: id }";
+
+    for (limits, rule, max) in [
+        (Limits::new().max_aliases(1).max_cost(3), "MaxAliases", 1),
+        (Limits::new().max_aliases(4).max_cost(3), "MaxCost", 3),
+    ] {
+        let schema = schema().with_limits(limits);
+        let errors = match execute(doc, None, &schema, &graphql_vars! {}, &()).await {
+            Err(Validation(errors)) => errors,
+            res => panic!("unexpected result: {:?}", res),
+        };
+        assert_eq!(
+            errors
+                .iter()
+                .map(|e| (e.rule(), e.param("max"), e.param("actual")))
+                .collect::<Vec<_>>(),
+            [(
+                Some(rule),
+                Some(&graphql_value!(max)),
+                Some(&graphql_value!(4)),
+            )],
+        );
+    }
+}
+
+#[tokio::test]
+async fn overrides_schema_limits_per_request() {
+    let schema = schema().with_limits(Limits::new().max_depth(2));
+    let doc = r"{ child { child { id } } }";
+
+    assert_eq!(
+        execute_with_limits(
+            doc,
+            None,
+            &schema,
+            &graphql_vars! {},
+            &(),
+            &Limits::new().max_depth(3),
+        )
+        .await,
+        Ok((graphql_value!({"child": {"child": {"id": 1}}}), vec![])),
+    );
+    assert!(execute_sync_with_limits(
+        doc,
+        None,
+        &schema,
+        &graphql_vars! {},
+        &(),
+        &Limits::new().max_depth(1),
+    )
+    .is_err());
+}
+
+#[tokio::test]
+async fn counts_aliases_and_cost_through_fragments() {
+    let doc = r"
+        query Q { a: child { ...F } b: child { ...F } ... on Node { c: id } }
+        fragment F on Node { x: id y: id child { id } }
+    ";
+    let schema = schema();
+    let vars = graphql_vars! {};
+    let limits = Limits::new().max_aliases(7).max_cost(11);
+    assert!(execute_with_limits(doc, None, &schema, &vars, &(), &limits)
+        .await
+        .is_ok());
+
+    let limits = Limits::new().max_aliases(6).max_cost(11);
+    assert_eq!(
+        execute_with_limits(doc, None, &schema, &vars, &(), &limits)
+            .await
+            .err(),
+        Some(Validation(vec![RuleError::new(
+            "Operation has 7 aliases, exceeding the maximum allowed number of 6",
+            &[SourcePosition::new(69, 1, 68)],
+        )])),
+    );
+
+    let limits = Limits::new().max_aliases(7).max_cost(10);
+    assert_eq!(
+        execute_with_limits(doc, None, &schema, &vars, &(), &limits)
+            .await
+            .err(),
+        Some(Validation(vec![RuleError::new(
+            "Operation has cost of 11, exceeding the maximum allowed cost of 10",
+            &[SourcePosition::new(9, 1, 8)],
+        )])),
+    );
+}
+
+#[tokio::test]
+async fn computes_cost_from_field_complexity() {
+    let schema = schema().with_limits(Limits::new().max_cost(20));
+    let doc = r"query($n: Int!) { children(count: $n) { id child { id } } }";
+
+    assert!(execute(doc, None, &schema, &graphql_vars! {"n": 6}, &())
+        .await
+        .is_ok());
+    assert_eq!(
+        execute(doc, None, &schema, &graphql_vars! {"n": 7}, &()).await,
+        Err(Validation(vec![RuleError::new(
+            "Operation has cost of 22, exceeding the maximum allowed cost of 20",
+            &[SourcePosition::new(0, 0, 0)],
+        )])),
+    );
+}
+
+#[tokio::test]
+async fn resolves_fields_with_error_once_timed_out() {
+    let limits = Limits::new().timeout(Duration::ZERO);
+    let doc = r"{ id }";
+
+    let (res, errs) = execute_with_limits(doc, None, &schema(), &graphql_vars! {}, &(), &limits)
+        .await
+        .unwrap();
+
+    assert_eq!(res, graphql_value!(null));
+    assert_eq!(errs.len(), 1);
+    assert_eq!(errs[0].error(), &FieldError::from("Operation timed out"));
+
+    let (res, errs) =
+        execute_sync_with_limits(doc, None, &schema(), &graphql_vars! {}, &(), &limits).unwrap();
+
+    assert_eq!(res, graphql_value!(null));
+    assert_eq!(errs.len(), 1);
+}
+
+#[tokio::test]
+async fn ignores_too_large_timeout() {
+    let doc = r"{ id }";
+
+    for limits in [
+        Limits::new().timeout(Duration::MAX),
+        Limits::new().timeout_at_most(Duration::MAX),
+    ] {
+        assert_eq!(
+            execute_with_limits(doc, None, &schema(), &graphql_vars! {}, &(), &limits).await,
+            Ok((graphql_value!({"id": 1}), vec![])),
+        );
+        assert_eq!(
+            execute_sync_with_limits(doc, None, &schema(), &graphql_vars! {}, &(), &limits),
+            Ok((graphql_value!({"id": 1}), vec![])),
+        );
+    }
+}
+
+#[test]
+fn tightens_timeout() {
+    let (short, long) = (Duration::from_secs(1), Duration::from_secs(2));
+
+    assert_eq!(
+        Limits::new().timeout_at_most(short),
+        Limits::new().timeout(short),
+    );
+    assert_eq!(
+        Limits::new().timeout(long).timeout_at_most(short),
+        Limits::new().timeout(short),
+    );
+    assert_eq!(
+        Limits::new().timeout(short).timeout_at_most(long),
+        Limits::new().timeout(short),
+    );
+}
+
+#[test]
+fn aborts_execution_exceeding_work_budget() {
+    let doc = r"{ id children(count: 3) { id } }";
+
+    let (res, errs) = execute_sync_with_limits(
+        doc,
+        None,
+        &schema(),
+        &graphql_vars! {},
+        &(),
+        &Limits::new().max_work(4),
+    )
+    .unwrap();
+
+    assert_eq!(res, graphql_value!({"id": 1, "children": null}));
+    assert_eq!(errs.len(), 1);
+    assert_eq!(
+        errs[0].error(),
+        &FieldError::new(
+            "Operation exceeded its work budget of 4",
+            graphql_value!({"code": "WORK_BUDGET_EXCEEDED", "budget": 4}),
+        ),
+    );
+}
+
+#[tokio::test]
+async fn counts_fields_and_list_elements_as_work() {
+    let doc = r"{ id children(count: 3) { id } }";
+
+    let (res, errs) = execute_with_limits(
+        doc,
+        None,
+        &schema(),
+        &graphql_vars! {},
+        &(),
+        &Limits::new().max_work(8),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        res,
+        graphql_value!({"id": 1, "children": [{"id": 1}, {"id": 1}, {"id": 1}]}),
+    );
+    assert_eq!(errs, vec![]);
+
+    let (_, errs) = execute_with_limits(
+        doc,
+        None,
+        &schema(),
+        &graphql_vars! {},
+        &(),
+        &Limits::new().max_work(7),
+    )
+    .await
+    .unwrap();
+
+    assert!(!errs.is_empty());
+}
+
+#[derive(Debug, Default)]
+struct Recorder(Mutex<Vec<(Option<String>, OperationType, OperationMeasure)>>);
+
+impl MetricsSink for Recorder {
+    fn record_measure(
+        &self,
+        operation_name: Option<&str>,
+        operation_type: OperationType,
+        measure: &OperationMeasure,
+    ) {
+        self.0
+            .lock()
+            .unwrap()
+            .push((operation_name.map(Into::into), operation_type, *measure));
+    }
+}
+
+#[tokio::test]
+async fn records_measures_into_metrics_sink() {
+    let recorder = Arc::new(Recorder::default());
+    let schema = schema()
+        .with_limits(Limits::new().max_cost(3))
+        .with_executor_options(ExecutorOptions::new().metrics_sink(Arc::clone(&recorder)));
+
+    assert!(execute(
+        r"query Q { a: id child { id } }",
+        None,
+        &schema,
+        &graphql_vars! {},
+        &()
+    )
+    .await
+    .is_ok());
+    assert!(execute_sync(
+        r"{ id child { child { id } } }",
+        None,
+        &schema,
+        &graphql_vars! {},
+        &()
+    )
+    .is_err());
+
+    let recorded = recorder.0.lock().unwrap();
+    assert_eq!(recorded.len(), 2);
+    assert_eq!(recorded[0].0.as_deref(), Some("Q"));
+    assert_eq!(recorded[0].1, OperationType::Query);
+    assert_eq!(
+        recorded[0].2.fields(),
+        [
+            ("graphql.depth", 2),
+            ("graphql.aliases", 1),
+            ("graphql.cost", 3)
+        ],
+    );
+    assert_eq!(recorded[1].0, None);
+    assert_eq!(recorded[1].2.depth(), 3);
+}
+
+#[test]
+fn measures_without_limits_once_metrics_sink_is_set() {
+    let recorder = Arc::new(Recorder::default());
+    let schema =
+        schema().with_executor_options(ExecutorOptions::new().metrics_sink(Arc::clone(&recorder)));
+
+    assert!(execute_sync(r"{ id }", None, &schema, &graphql_vars! {}, &()).is_ok());
+    assert_eq!(recorder.0.lock().unwrap()[0].2.cost(), 1);
+}
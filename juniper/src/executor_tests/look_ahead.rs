@@ -0,0 +1,116 @@
+use crate::{
+    execute_sync, graphql_object, graphql_value, graphql_vars,
+    schema::model::RootNode,
+    types::scalars::{EmptyMutation, EmptySubscription},
+    Executor, GraphQLEnum, LookAheadMethods as _, ScalarValue,
+};
+
+#[derive(Clone, Copy, Debug, Eq, GraphQLEnum, PartialEq)]
+enum Order {
+    Asc,
+    Desc,
+}
+
+struct Query;
+
+#[graphql_object]
+impl Query {
+    /// Describes the arguments of the `friends` sub-selection, as seen by the
+    /// look-ahead.
+    fn user<S: ScalarValue>(executor: &Executor<'_, '_, (), S>) -> User {
+        let look_ahead = executor.look_ahead();
+        let friends = look_ahead.select_child("friends").unwrap();
+        User {
+            first: friends.argument_value::<i32>("first").map(Result::unwrap),
+            order: friends.argument_value::<Order>("order").map(Result::unwrap),
+            after: friends
+                .argument_value::<Option<String>>("after")
+                .map(Result::unwrap),
+        }
+    }
+}
+
+struct User {
+    first: Option<i32>,
+    order: Option<Order>,
+    after: Option<Option<String>>,
+}
+
+#[graphql_object]
+impl User {
+    fn first(&self) -> Option<i32> {
+        self.first
+    }
+
+    fn order(&self) -> Option<Order> {
+        self.order
+    }
+
+    fn after(&self) -> Option<String> {
+        self.after
+            .clone()
+            .map(|a| a.unwrap_or_else(|| "null".into()))
+    }
+
+    fn friends(
+        #[graphql(default = 10)] first: i32,
+        #[graphql(default = Order::Asc)] order: Order,
+        after: Option<String>,
+    ) -> Vec<User> {
+        let _ = (first, order, after);
+        vec![]
+    }
+}
+
+fn schema() -> RootNode<'static, Query, EmptyMutation, EmptySubscription> {
+    RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+}
+
+#[test]
+fn converts_arguments_with_variables() {
+    let doc = r#"query($n: Int!, $after: String) {
+        user { first order after friends(first: $n, order: DESC, after: $after) { first } }
+    }"#;
+
+    assert_eq!(
+        execute_sync(
+            doc,
+            None,
+            &schema(),
+            &graphql_vars! {"n": 3, "after": "x"},
+            &()
+        ),
+        Ok((
+            graphql_value!({"user": {"first": 3, "order": "DESC", "after": "x", "friends": []}}),
+            vec![],
+        )),
+    );
+}
+
+#[test]
+fn applies_default_values() {
+    let doc = r#"{ user { first order after ...F } } fragment F on User { friends { first } }"#;
+
+    assert_eq!(
+        execute_sync(doc, None, &schema(), &graphql_vars! {}, &()),
+        Ok((
+            graphql_value!({"user": {"first": 10, "order": "ASC", "after": null, "friends": []}}),
+            vec![],
+        )),
+    );
+}
+
+#[test]
+fn converts_missing_variables_to_null() {
+    let doc = r#"query($after: String) {
+        user { after friends(after: $after) { first } }
+    }"#;
+
+    assert_eq!(
+        execute_sync(doc, None, &schema(), &graphql_vars! {}, &()),
+        Ok((
+            graphql_value!({"user": {"after": "null", "friends": []}}),
+            vec![],
+        )),
+    );
+}
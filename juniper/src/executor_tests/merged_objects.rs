@@ -0,0 +1,216 @@
+use std::pin::Pin;
+
+use futures::{stream, StreamExt as _};
+
+use crate::{
+    execute, execute_sync, graphql_object, graphql_subscription, graphql_value, graphql_vars,
+    resolve_into_stream,
+    schema::model::RootNode,
+    types::scalars::{EmptyMutation, EmptySubscription},
+    MergedObject, SchemaError, Value,
+};
+
+struct UsersQuery;
+
+#[graphql_object]
+impl UsersQuery {
+    fn users() -> Vec<&'static str> {
+        vec!["John"]
+    }
+}
+
+struct OrdersQuery;
+
+#[graphql_object]
+impl OrdersQuery {
+    async fn orders() -> Vec<i32> {
+        vec![1, 2]
+    }
+}
+
+struct ItemsQuery;
+
+#[graphql_object]
+impl ItemsQuery {
+    fn items(limit: i32) -> i32 {
+        limit
+    }
+}
+
+struct UsersMutation;
+
+#[graphql_object]
+impl UsersMutation {
+    fn add_user(name: String) -> String {
+        name
+    }
+}
+
+struct OrdersMutation;
+
+#[graphql_object]
+impl OrdersMutation {
+    fn add_order(id: i32) -> i32 {
+        id
+    }
+}
+
+type Stream<I> = Pin<Box<dyn futures::Stream<Item = I> + Send>>;
+
+struct UsersSubscription;
+
+#[graphql_subscription]
+impl UsersSubscription {
+    async fn user_added() -> Stream<&'static str> {
+        Box::pin(stream::once(async { "John" }))
+    }
+}
+
+struct OrdersSubscription;
+
+#[graphql_subscription]
+impl OrdersSubscription {
+    async fn order_added() -> Stream<i32> {
+        Box::pin(stream::once(async { 42 }))
+    }
+}
+
+type Query = MergedObject<UsersQuery, MergedObject<OrdersQuery, ItemsQuery>>;
+
+fn schema(
+) -> RootNode<'static, Query, MergedObject<UsersMutation, OrdersMutation>, EmptySubscription> {
+    RootNode::new(
+        MergedObject(UsersQuery, MergedObject(OrdersQuery, ItemsQuery)),
+        MergedObject(UsersMutation, OrdersMutation),
+        EmptySubscription::new(),
+    )
+}
+
+#[tokio::test]
+async fn resolves_fields_of_all_parts() {
+    let schema = schema();
+    let doc = r"{ users orders items(limit: 3) __typename }";
+
+    assert_eq!(
+        execute(doc, None, &schema, &graphql_vars! {}, &()).await,
+        Ok((
+            graphql_value!({
+                "users": ["John"],
+                "orders": [1, 2],
+                "items": 3,
+                "__typename": "UsersQuery",
+            }),
+            vec![],
+        )),
+    );
+}
+
+#[test]
+fn resolves_sync_fields_of_all_parts() {
+    let schema = schema();
+    let doc = r"{ users items(limit: 3) }";
+
+    assert_eq!(
+        execute_sync(doc, None, &schema, &graphql_vars! {}, &()),
+        Ok((graphql_value!({"users": ["John"], "items": 3}), vec![])),
+    );
+}
+
+#[tokio::test]
+async fn resolves_mutation_fields_of_all_parts() {
+    let schema = schema();
+    let doc = r#"mutation { addUser(name: "Jane") addOrder(id: 7) }"#;
+
+    assert_eq!(
+        execute(doc, None, &schema, &graphql_vars! {}, &()).await,
+        Ok((graphql_value!({"addUser": "Jane", "addOrder": 7}), vec![])),
+    );
+}
+
+#[tokio::test]
+async fn resolves_subscription_fields_of_all_parts() {
+    let schema = RootNode::new(
+        UsersQuery,
+        EmptyMutation::new(),
+        MergedObject(UsersSubscription, OrdersSubscription),
+    );
+    let doc = r"subscription { orderAdded }";
+
+    let (res, errors) = resolve_into_stream(doc, None, &schema, &graphql_vars! {}, &())
+        .await
+        .unwrap();
+    assert!(errors.is_empty());
+
+    let mut fields = match res {
+        Value::Object(o) => o.into_iter(),
+        _ => unreachable!(),
+    };
+    let (name, value) = fields.next().unwrap();
+    assert_eq!(name, "orderAdded");
+    let stream = match value {
+        Value::Scalar(s) => s,
+        _ => unreachable!(),
+    };
+    assert_eq!(
+        stream.collect::<Vec<_>>().await,
+        vec![Ok(graphql_value!(42))],
+    );
+}
+
+#[test]
+fn merges_meta_of_all_parts() {
+    let schema = schema();
+    let query = schema.schema.concrete_query_type();
+
+    assert_eq!(query.name(), Some("UsersQuery"));
+    for field in ["users", "orders", "items", "__typename"] {
+        assert!(query.field_by_name(field).is_some(), "missing `{}`", field);
+    }
+}
+
+#[test]
+#[should_panic(
+    expected = "Field `users` of merged GraphQL object `UsersQuery` is defined by more than one \
+                of its parts"
+)]
+fn panics_on_duplicated_fields() {
+    let _ = RootNode::new(
+        MergedObject(UsersQuery, UsersQuery),
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+}
+
+#[test]
+fn errors_on_duplicated_fields() {
+    let res = RootNode::try_new(
+        MergedObject(UsersQuery, MergedObject(OrdersQuery, UsersQuery)),
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+
+    assert_eq!(
+        res.err(),
+        Some(SchemaError::DuplicateMergedField {
+            object: "UsersQuery".into(),
+            field: "users".into(),
+        }),
+    );
+}
+
+#[test]
+fn errors_on_non_object_parts() {
+    let res = RootNode::try_new(
+        MergedObject(UsersQuery, 0_i32),
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+
+    assert_eq!(
+        res.err(),
+        Some(SchemaError::InvalidMergedPart {
+            object: "UsersQuery".into(),
+            part: "Int".into(),
+        }),
+    );
+}
@@ -1,7 +1,22 @@
 mod directives;
+mod entities;
 mod enums;
 mod executor;
+mod fragments;
 mod introspection;
+mod limits;
+mod look_ahead;
+mod merged_objects;
+mod n_plus_one;
+mod parents;
+mod pii;
+mod projection;
+mod query_log;
+mod selection_hash;
+mod slow_query;
+mod state;
+mod strategies;
+mod transform;
 mod variables;
 
 mod interfaces_unions;
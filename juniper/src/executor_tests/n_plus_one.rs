@@ -0,0 +1,80 @@
+use std::sync::{Arc, Mutex};
+
+use crate::{
+    execute, execute_sync, graphql_object, graphql_vars,
+    schema::model::RootNode,
+    types::scalars::{EmptyMutation, EmptySubscription},
+    ExecutorOptions, NPlusOneHook, RepeatedField,
+};
+
+struct User(i32);
+
+#[graphql_object]
+impl User {
+    fn id(&self) -> i32 {
+        self.0
+    }
+
+    fn friends(&self) -> Vec<User> {
+        vec![User(self.0 * 10), User(self.0 * 10 + 1)]
+    }
+}
+
+struct Query;
+
+#[graphql_object]
+impl Query {
+    fn users(count: i32) -> Vec<User> {
+        (0..count).map(User).collect()
+    }
+}
+
+#[derive(Debug, Default)]
+struct Warnings(Mutex<Vec<RepeatedField>>);
+
+impl NPlusOneHook for Warnings {
+    fn on_repeated_field(&self, field: &RepeatedField) {
+        self.0.lock().unwrap().push(field.clone());
+    }
+}
+
+fn schema(warnings: Arc<Warnings>) -> RootNode<'static, Query, EmptyMutation, EmptySubscription> {
+    RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+        .with_executor_options(ExecutorOptions::new().n_plus_one_hook(3, warnings))
+}
+
+#[tokio::test]
+#[cfg_attr(not(debug_assertions), ignore = "N+1 detection is debug-only")]
+async fn warns_about_repeated_fields() {
+    let warnings = Arc::new(Warnings::default());
+    let schema = schema(Arc::clone(&warnings));
+
+    let doc = "query Users { users(count: 3) { id friends { id friends { id } } } }";
+    execute(doc, None, &schema, &graphql_vars! {}, &())
+        .await
+        .unwrap();
+
+    let warnings = warnings.0.lock().unwrap();
+    let repeated = warnings
+        .iter()
+        .map(|w| (w.operation_name(), w.path(), w.count()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        repeated,
+        [
+            (Some("Users"), "users.friends.friends", 6),
+            (Some("Users"), "users.friends", 3),
+        ],
+    );
+}
+
+#[test]
+fn ignores_fields_below_threshold() {
+    let warnings = Arc::new(Warnings::default());
+    let schema = schema(Arc::clone(&warnings));
+
+    let doc = "{ users(count: 2) { friends { id } } }";
+    execute_sync(doc, None, &schema, &graphql_vars! {}, &()).unwrap();
+
+    assert!(warnings.0.lock().unwrap().is_empty());
+}
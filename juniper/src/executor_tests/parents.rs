@@ -0,0 +1,105 @@
+use crate::{
+    execute, execute_sync, graphql_object, graphql_value, graphql_vars,
+    schema::model::RootNode,
+    types::scalars::{EmptyMutation, EmptySubscription},
+    Executor, ScalarValue,
+};
+
+struct Query;
+
+#[graphql_object]
+impl Query {
+    fn user() -> User {
+        User {
+            name: "John".into(),
+        }
+    }
+}
+
+struct User {
+    name: String,
+}
+
+#[graphql_object]
+impl User {
+    fn post() -> Post {
+        Post { id: 7 }
+    }
+}
+
+struct Post {
+    id: i32,
+}
+
+#[graphql_object]
+impl Post {
+    fn comment() -> Comment {
+        Comment
+    }
+}
+
+struct Comment;
+
+#[graphql_object]
+impl Comment {
+    fn author<S: ScalarValue>(executor: &Executor<'_, '_, (), S>) -> Option<String> {
+        executor.parent::<User>().map(|u| u.name.clone())
+    }
+
+    async fn post_id<S: ScalarValue>(executor: &Executor<'_, '_, (), S>) -> Option<i32> {
+        executor.parent::<Post>().map(|p| p.id)
+    }
+
+    fn missing<S: ScalarValue>(executor: &Executor<'_, '_, (), S>) -> bool {
+        executor.parent::<String>().is_some()
+    }
+
+    fn ancestors<S: ScalarValue>(executor: &Executor<'_, '_, (), S>) -> Vec<String> {
+        executor
+            .parents()
+            .map(|p| p.type_name().to_owned())
+            .collect()
+    }
+}
+
+fn schema() -> RootNode<'static, Query, EmptyMutation, EmptySubscription> {
+    RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+}
+
+const DOC: &str = r"{
+    user { post { comment { author postId missing ancestors } } }
+}";
+
+fn expected() -> crate::Value {
+    graphql_value!({"user": {"post": {"comment": {
+        "author": "John",
+        "postId": 7,
+        "missing": false,
+        "ancestors": ["Comment", "Post", "User", "Query"],
+    }}}})
+}
+
+#[tokio::test]
+async fn resolves_parents_async() {
+    assert_eq!(
+        execute(DOC, None, &schema(), &graphql_vars! {}, &()).await,
+        Ok((expected(), vec![])),
+    );
+}
+
+#[test]
+fn resolves_parents_sync() {
+    let doc = r"{ user { post { comment { author missing ancestors } } } }";
+
+    assert_eq!(
+        execute_sync(doc, None, &schema(), &graphql_vars! {}, &()),
+        Ok((
+            graphql_value!({"user": {"post": {"comment": {
+                "author": "John",
+                "missing": false,
+                "ancestors": ["Comment", "Post", "User", "Query"],
+            }}}}),
+            vec![],
+        )),
+    );
+}
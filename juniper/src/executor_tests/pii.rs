@@ -0,0 +1,190 @@
+use std::sync::{Arc, Mutex};
+
+use crate::{
+    execute, execute_sync, graphql_interface, graphql_object, graphql_value, graphql_vars,
+    http::GraphQLRequest,
+    schema::model::RootNode,
+    types::scalars::{EmptyMutation, EmptySubscription},
+    ExecutorOptions, GraphQLObject, MetricsSink, OperationMeasure, OperationType, PiiFields,
+};
+
+#[graphql_interface(for = User)]
+trait Contact {
+    #[graphql(pii)]
+    fn email(&self) -> &str;
+}
+
+#[derive(GraphQLObject)]
+#[graphql(impl = ContactValue)]
+struct User {
+    id: i32,
+    #[graphql(pii)]
+    email: String,
+    #[graphql(pii)]
+    phone: Option<String>,
+}
+
+struct Query;
+
+#[graphql_object]
+impl Query {
+    fn users() -> Vec<User> {
+        vec![
+            User {
+                id: 1,
+                email: "john@example.com".into(),
+                phone: None,
+            },
+            User {
+                id: 2,
+                email: "jane@example.com".into(),
+                phone: Some("+123".into()),
+            },
+        ]
+    }
+
+    fn contact() -> ContactValue {
+        User {
+            id: 3,
+            email: "joe@example.com".into(),
+            phone: None,
+        }
+        .into()
+    }
+
+    #[graphql(pii)]
+    fn owner_email() -> &'static str {
+        "owner@example.com"
+    }
+}
+
+fn schema() -> RootNode<'static, Query, EmptyMutation, EmptySubscription> {
+    RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+}
+
+#[derive(Debug, Default)]
+struct Recorder(Mutex<Vec<(Option<String>, PiiFields)>>);
+
+impl MetricsSink for Recorder {
+    fn record_measure(&self, _: Option<&str>, _: OperationType, _: &OperationMeasure) {}
+
+    fn record_pii_fields(
+        &self,
+        operation_name: Option<&str>,
+        _: OperationType,
+        fields: &PiiFields,
+    ) {
+        self.0
+            .lock()
+            .unwrap()
+            .push((operation_name.map(Into::into), fields.clone()));
+    }
+}
+
+fn paths(fields: &PiiFields) -> Vec<(&str, &str, String)> {
+    fields
+        .iter()
+        .map(|f| (f.type_name(), f.field_name(), f.path().join(".")))
+        .collect()
+}
+
+#[test]
+fn tags_fields_in_schema() {
+    let schema = schema();
+
+    let user = schema.schema.concrete_type_by_name("User").unwrap();
+    assert!(!user.field_by_name("id").unwrap().is_pii());
+    assert!(user.field_by_name("email").unwrap().is_pii());
+
+    let contact = schema.schema.concrete_type_by_name("Contact").unwrap();
+    assert!(contact.field_by_name("email").unwrap().is_pii());
+
+    let query = schema.schema.concrete_type_by_name("Query").unwrap();
+    assert!(query.field_by_name("ownerEmail").unwrap().is_pii());
+}
+
+#[tokio::test]
+async fn records_selected_pii_fields_into_metrics_sink() {
+    let recorder = Arc::new(Recorder::default());
+    let schema =
+        schema().with_executor_options(ExecutorOptions::new().metrics_sink(Arc::clone(&recorder)));
+
+    assert!(execute(
+        r"query Q {
+            users { id mail: email ...F }
+            contact { ... on User { phone } }
+            ownerEmail
+        }
+        fragment F on User { email }",
+        None,
+        &schema,
+        &graphql_vars! {},
+        &(),
+    )
+    .await
+    .is_ok());
+    assert!(execute_sync(r"{ users { id } }", None, &schema, &graphql_vars! {}, &()).is_ok());
+
+    let recorded = recorder.0.lock().unwrap();
+    assert_eq!(recorded.len(), 1, "records operations selecting PII only");
+    assert_eq!(recorded[0].0.as_deref(), Some("Q"));
+    assert_eq!(
+        paths(&recorded[0].1),
+        [
+            ("User", "email", "users.mail".into()),
+            ("User", "email", "users.email".into()),
+            ("User", "phone", "contact.phone".into()),
+            ("Query", "ownerEmail", "ownerEmail".into()),
+        ],
+    );
+}
+
+#[test]
+fn strips_pii_fields_from_response() {
+    let schema = schema();
+    let request = GraphQLRequest::new(
+        r"{ users { id mail: email phone } contact { email } ownerEmail }".into(),
+        None,
+        None,
+    );
+
+    let fields = request.pii_fields(&schema);
+    assert_eq!(
+        paths(&fields),
+        [
+            ("User", "email", "users.mail".into()),
+            ("User", "phone", "users.phone".into()),
+            ("Contact", "email", "contact.email".into()),
+            ("Query", "ownerEmail", "ownerEmail".into()),
+        ],
+    );
+
+    let (res, errs) = execute_sync(&request.query, None, &schema, &graphql_vars! {}, &()).unwrap();
+    assert_eq!(errs, []);
+    assert_eq!(
+        fields.strip(&res),
+        graphql_value!({
+            "users": [{"id": 1}, {"id": 2}],
+            "contact": {},
+        }),
+    );
+    assert_eq!(
+        res,
+        graphql_value!({
+            "users": [
+                {"id": 1, "mail": "john@example.com", "phone": null},
+                {"id": 2, "mail": "jane@example.com", "phone": "+123"},
+            ],
+            "contact": {"email": "joe@example.com"},
+            "ownerEmail": "owner@example.com",
+        }),
+        "doesn't affect the response itself",
+    );
+}
+
+#[test]
+fn collects_nothing_from_invalid_request() {
+    let request = GraphQLRequest::new("{ users { ".into(), None, None);
+
+    assert!(request.pii_fields(&schema()).is_empty());
+}
@@ -0,0 +1,188 @@
+use crate::{
+    execute_sync, graphql_interface, graphql_object, graphql_vars,
+    schema::model::RootNode,
+    types::scalars::{EmptyMutation, EmptySubscription},
+    Executor, Projection, ProjectionMap, ScalarValue, Value,
+};
+
+fn users() -> ProjectionMap {
+    ProjectionMap::new()
+        .always(&["id"])
+        .columns("name", &["name"])
+        .columns("fullName", &["first_name", "last_name"])
+        .columns("initials", &["first_name"])
+        .columns("author", &["author_id"])
+        .relation("author", "authors", ProjectionMap::new())
+        .relation(
+            "posts",
+            "posts",
+            ProjectionMap::new()
+                .always(&["id"])
+                .columns("title", &["title"])
+                .relation(
+                    "comments",
+                    "comments",
+                    ProjectionMap::new().columns("text", &["body"]),
+                ),
+        )
+}
+
+fn describe(projection: &Projection) -> String {
+    let mut out = projection.columns().join(",");
+    for (name, nested) in projection.relations() {
+        out.push_str(&format!(" {}({})", name, describe(nested)));
+    }
+    out
+}
+
+struct Query;
+
+#[graphql_object]
+impl Query {
+    fn user<S: ScalarValue>(executor: &Executor<'_, '_, (), S>) -> User {
+        User(describe(&users().project(&executor.look_ahead())))
+    }
+
+    fn node<S: ScalarValue>(executor: &Executor<'_, '_, (), S>) -> NodeValue {
+        User(describe(&users().project(&executor.look_ahead()))).into()
+    }
+}
+
+#[graphql_interface(for = User)]
+trait Node {
+    fn projection(&self) -> String;
+}
+
+struct User(String);
+
+#[graphql_object(impl = NodeValue)]
+impl User {
+    fn projection(&self) -> String {
+        self.0.clone()
+    }
+
+    fn name() -> &'static str {
+        "John"
+    }
+
+    fn full_name() -> &'static str {
+        "John Doe"
+    }
+
+    fn initials() -> &'static str {
+        "J"
+    }
+
+    fn author() -> Option<User> {
+        None
+    }
+
+    fn posts() -> Vec<Post> {
+        vec![]
+    }
+}
+
+struct Post;
+
+#[graphql_object]
+impl Post {
+    fn title() -> &'static str {
+        "Hello"
+    }
+
+    fn comments() -> Vec<Comment> {
+        vec![]
+    }
+}
+
+struct Comment;
+
+#[graphql_object]
+impl Comment {
+    fn text() -> &'static str {
+        "Hi"
+    }
+}
+
+fn projection_of(doc: &str, field: &str) -> String {
+    let schema = RootNode::new(
+        Query,
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+    let (res, errs) = execute_sync(doc, None, &schema, &graphql_vars! {}, &()).unwrap();
+    assert_eq!(errs, vec![]);
+
+    match res {
+        Value::Object(mut obj) => match obj.get_mut_field_value(field) {
+            Some(Value::Object(user)) => user
+                .get_field_value("projection")
+                .and_then(Value::as_string_value)
+                .unwrap()
+                .to_owned(),
+            _ => panic!("no `{}` in response", field),
+        },
+        _ => panic!("no data in response"),
+    }
+}
+
+#[test]
+fn projects_mapped_columns_only() {
+    assert_eq!(
+        projection_of("{ user { projection name __typename } }", "user"),
+        "id,name",
+    );
+}
+
+#[test]
+fn deduplicates_columns() {
+    assert_eq!(
+        projection_of(
+            "{ user { projection fullName initials n1: name n2: name } }",
+            "user",
+        ),
+        "id,first_name,last_name,name",
+    );
+}
+
+#[test]
+fn projects_nested_relations() {
+    assert_eq!(
+        projection_of(
+            "{ user { projection posts { title comments { text } } } }",
+            "user",
+        ),
+        "id posts(id,title comments(body))",
+    );
+}
+
+#[test]
+fn combines_relation_with_columns() {
+    assert_eq!(
+        projection_of("{ user { projection author { projection } } }", "user"),
+        "id,author_id authors()",
+    );
+}
+
+#[test]
+fn merges_aliased_relations() {
+    assert_eq!(
+        projection_of(
+            "{ user { projection a: posts { title } b: posts { comments { text } } } }",
+            "user",
+        ),
+        "id posts(id,title comments(body))",
+    );
+}
+
+#[test]
+fn projects_type_conditions() {
+    assert_eq!(
+        projection_of(
+            "{ node { projection ... on User { fullName } ...F } } \
+             fragment F on User { posts { title } }",
+            "node",
+        ),
+        "id,first_name,last_name posts(id,title)",
+    );
+}
@@ -0,0 +1,173 @@
+use std::sync::{Arc, Mutex};
+
+use crate::{
+    execute, execute_sync, graphql_input_value, graphql_object, graphql_value, graphql_vars,
+    schema::model::RootNode,
+    types::scalars::{EmptyMutation, EmptySubscription},
+    Context, DefaultScalarValue, FieldError, FieldResult, OperationBuilder, QueryLogEntry,
+    QueryLogger,
+};
+
+struct Session {
+    client: Option<&'static str>,
+}
+
+impl Context for Session {}
+
+struct Query;
+
+#[graphql_object(context = Session)]
+impl Query {
+    fn user(id: i32, name: String) -> String {
+        format!("{}:{}", id, name)
+    }
+
+    fn forbidden() -> FieldResult<Option<i32>> {
+        Err(FieldError::new(
+            "Forbidden",
+            graphql_value!({"code": "FORBIDDEN"}),
+        ))
+    }
+
+    fn broken() -> FieldResult<Option<i32>> {
+        Err("Broken".into())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Record {
+    query: Option<String>,
+    operation_name: Option<String>,
+    variables: Vec<(String, u64)>,
+    client: Option<String>,
+    error_codes: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+struct Recorder(Mutex<Vec<Record>>);
+
+impl QueryLogger<Session> for Recorder {
+    fn client_identity(&self, session: &Session) -> Option<String> {
+        session.client.map(Into::into)
+    }
+
+    fn log(&self, entry: &QueryLogEntry<'_>) {
+        self.0.lock().unwrap().push(Record {
+            query: entry.query().map(Into::into),
+            operation_name: entry.operation_name().map(Into::into),
+            variables: entry.variables().map(|(n, h)| (n.into(), h)).collect(),
+            client: entry.client().map(Into::into),
+            error_codes: entry.error_codes().to_vec(),
+        });
+    }
+}
+
+fn schema(
+    recorder: Arc<Recorder>,
+) -> RootNode<'static, Query, EmptyMutation<Session>, EmptySubscription<Session>> {
+    RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new()).with_query_logger(recorder)
+}
+
+#[tokio::test]
+async fn logs_normalized_query_with_hashed_variables() {
+    let recorder = Arc::new(Recorder::default());
+    let schema = schema(Arc::clone(&recorder));
+    let session = Session {
+        client: Some("ios/2.1"),
+    };
+
+    let query = r#"
+        query User($id: Int!) {
+            user(id: $id, name: "John")
+        }
+    "#;
+    for id in [1, 1, 2] {
+        execute(
+            query,
+            Some("User"),
+            &schema,
+            &graphql_vars! {"id": (id)},
+            &session,
+        )
+        .await
+        .unwrap();
+    }
+
+    let records = recorder.0.lock().unwrap();
+    assert_eq!(records.len(), 3);
+    assert_eq!(
+        records[0].query.as_deref(),
+        Some(r#"query User($id:Int!){user(id:$id name:"")}"#),
+    );
+    assert_eq!(records[0].operation_name.as_deref(), Some("User"));
+    assert_eq!(records[0].client.as_deref(), Some("ios/2.1"));
+    assert_eq!(records[0].error_codes, Vec::<String>::new());
+
+    assert_eq!(records[0].variables.len(), 1);
+    assert_eq!(records[0].variables[0].0, "id");
+    assert_eq!(records[0].variables, records[1].variables);
+    assert_ne!(records[0].variables, records[2].variables);
+}
+
+#[test]
+fn logs_error_codes() {
+    let recorder = Arc::new(Recorder::default());
+    let schema = schema(Arc::clone(&recorder));
+    let session = Session { client: None };
+
+    let vars = graphql_vars! {};
+    let _ = execute_sync("{ forbidden broken }", None, &schema, &vars, &session);
+    let _ = execute_sync("{ unknown }", None, &schema, &vars, &session);
+    let _ = execute_sync("{ user(", None, &schema, &vars, &session);
+    let _ = execute_sync("{ broken }", Some("Missing"), &schema, &vars, &session);
+
+    let records = recorder.0.lock().unwrap();
+    let codes = records
+        .iter()
+        .map(|r| r.error_codes.iter().map(String::as_str).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        codes,
+        [
+            vec!["FORBIDDEN", "FIELD_ERROR"],
+            vec!["GRAPHQL_VALIDATION_FAILED"],
+            vec!["GRAPHQL_PARSE_FAILED"],
+            vec!["OPERATION_RESOLUTION_FAILURE"],
+        ],
+    );
+    assert!(records.iter().all(|r| r.client.is_none()));
+}
+
+#[tokio::test]
+async fn logs_built_operations() {
+    let recorder = Arc::new(Recorder::default());
+    let schema = schema(Arc::clone(&recorder));
+    let session = Session {
+        client: Some("web"),
+    };
+
+    let op = OperationBuilder::<DefaultScalarValue>::query("User")
+        .variable("id", "Int!", None)
+        .field(
+            "user",
+            graphql_input_value!({"id": @id, "name": "John"}),
+            |f| f,
+        );
+    let vars = graphql_vars! {"id": 1};
+    op.execute_sync(&schema, &vars, &session).unwrap();
+    op.execute(&schema, &vars, &session).await.unwrap();
+    let _ = OperationBuilder::query("Broken")
+        .field("unknown", graphql_input_value!(null), |f| f)
+        .execute_sync(&schema, &vars, &session);
+
+    let records = recorder.0.lock().unwrap();
+    assert_eq!(records.len(), 3);
+    assert_eq!(
+        records[0].query.as_deref(),
+        Some(r#"query User($id:Int!){user(id:$id name:"")}"#),
+    );
+    assert_eq!(records[0], records[1]);
+    assert_eq!(records[0].client.as_deref(), Some("web"));
+    assert_eq!(records[2].operation_name.as_deref(), Some("Broken"));
+    assert_eq!(records[2].error_codes, ["GRAPHQL_VALIDATION_FAILED"]);
+}
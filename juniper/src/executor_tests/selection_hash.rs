@@ -0,0 +1,103 @@
+use crate::{
+    execute_sync, graphql_object, graphql_vars,
+    schema::model::RootNode,
+    types::scalars::{EmptyMutation, EmptySubscription},
+    Executor, ScalarValue, Value, Variables,
+};
+
+struct Query;
+
+#[graphql_object]
+impl Query {
+    fn user<S: ScalarValue>(executor: &Executor<'_, '_, (), S>) -> User {
+        User(executor.selection_hash())
+    }
+}
+
+struct User(u64);
+
+#[graphql_object]
+impl User {
+    fn hash(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn name() -> &'static str {
+        "John"
+    }
+
+    fn friends(first: i32) -> Vec<User> {
+        (0..first).map(|_| User(0)).collect()
+    }
+}
+
+fn hash_of(doc: &str, vars: &Variables) -> String {
+    let schema = RootNode::new(
+        Query,
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+    let (res, errs) = execute_sync(doc, None, &schema, vars, &()).unwrap();
+    assert_eq!(errs, vec![]);
+
+    match res {
+        Value::Object(mut obj) => match obj.get_mut_field_value("user") {
+            Some(Value::Object(user)) => user
+                .get_field_value("hash")
+                .and_then(Value::as_string_value)
+                .unwrap()
+                .to_owned(),
+            _ => panic!("no `user` in response"),
+        },
+        _ => panic!("no data in response"),
+    }
+}
+
+#[test]
+fn is_stable() {
+    assert_eq!(
+        hash_of("{ user { hash name } }", &graphql_vars! {}),
+        "4707550808351018494",
+    );
+}
+
+#[test]
+fn ignores_how_selection_is_written() {
+    let vars = graphql_vars! {"n": 2, "yes": true};
+    let plain = hash_of("{ user { hash name friends(first: 2) { name } } }", &vars);
+
+    assert_eq!(
+        hash_of(
+            "query($n: Int!) { user { hash ...F } } \
+             fragment F on User { name friends(first: $n) { name } }",
+            &vars,
+        ),
+        plain,
+    );
+    assert_eq!(
+        hash_of(
+            "query($yes: Boolean!) { user { \
+                 hash name @include(if: $yes) \
+                 friends(first: 2) { name id: name @skip(if: $yes) } \
+             } }",
+            &vars,
+        ),
+        plain,
+    );
+}
+
+#[test]
+fn differs_for_different_selections() {
+    let vars = graphql_vars! {};
+    let plain = hash_of("{ user { hash name friends(first: 2) { name } } }", &vars);
+
+    for doc in [
+        "{ user { hash friends(first: 2) { name } } }",
+        "{ user { hash name friends(first: 3) { name } } }",
+        "{ user { hash name friends(first: 2) { alias: name } } }",
+        "{ user { hash name friends(first: 2) { name hash } } }",
+        "{ user { hash friends(first: 2) { name } name } }",
+    ] {
+        assert_ne!(hash_of(doc, &vars), plain, "{}", doc);
+    }
+}
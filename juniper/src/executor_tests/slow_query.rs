@@ -0,0 +1,100 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use crate::{
+    execute, execute_sync, graphql_object, graphql_vars,
+    schema::model::RootNode,
+    types::scalars::{EmptyMutation, EmptySubscription},
+    ExecutorOptions, OperationType, PathSegment, SlowQuery, SlowQueryHook,
+};
+
+struct User(i32);
+
+#[graphql_object]
+impl User {
+    fn id(&self) -> i32 {
+        self.0
+    }
+
+    async fn friends(&self) -> Vec<User> {
+        // Only the second user is slow to load the friends of.
+        if self.0 == 2 {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        vec![User(self.0 * 10)]
+    }
+}
+
+struct Query;
+
+#[graphql_object]
+impl Query {
+    fn users() -> Vec<User> {
+        vec![User(1), User(2), User(3)]
+    }
+}
+
+#[derive(Debug, Default)]
+struct Reports(Mutex<Vec<SlowQuery>>);
+
+impl SlowQueryHook for Reports {
+    fn on_slow_query(&self, query: &SlowQuery) {
+        self.0.lock().unwrap().push(query.clone());
+    }
+}
+
+fn schema(
+    threshold: Duration,
+    reports: Arc<Reports>,
+) -> RootNode<'static, Query, EmptyMutation, EmptySubscription> {
+    RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+        .with_executor_options(ExecutorOptions::new().slow_query_hook(threshold, reports))
+}
+
+#[tokio::test]
+async fn reports_slowest_field_paths() {
+    let reports = Arc::new(Reports::default());
+    let schema = schema(Duration::from_millis(10), Arc::clone(&reports));
+
+    let doc = "query Users { users { id friends { id } } }";
+    execute(doc, None, &schema, &graphql_vars! {}, &())
+        .await
+        .unwrap();
+
+    let reports = reports.0.lock().unwrap();
+    assert_eq!(reports.len(), 1);
+
+    let report = &reports[0];
+    assert_eq!(report.operation_name(), Some("Users"));
+    assert_eq!(report.operation_type(), OperationType::Query);
+    assert!(report.duration() >= Duration::from_millis(20));
+
+    let slowest = report
+        .slowest()
+        .map(|(path, _)| path.to_vec())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        &slowest[..2],
+        [
+            vec![PathSegment::from("users")],
+            vec![
+                PathSegment::from("users"),
+                PathSegment::from(1),
+                PathSegment::from("friends"),
+            ],
+        ],
+    );
+    assert!(slowest.len() <= SlowQuery::SLOWEST_FIELDS);
+}
+
+#[test]
+fn ignores_fast_operations() {
+    let reports = Arc::new(Reports::default());
+    let schema = schema(Duration::from_secs(60), Arc::clone(&reports));
+
+    execute_sync("{ users { id } }", None, &schema, &graphql_vars! {}, &()).unwrap();
+
+    assert!(reports.0.lock().unwrap().is_empty());
+}
@@ -0,0 +1,139 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use futures::StreamExt as _;
+
+use crate::{
+    execute, execute_sync, graphql_object, graphql_subscription, graphql_value, graphql_vars,
+    resolve_into_stream,
+    schema::model::RootNode,
+    types::scalars::{EmptyMutation, EmptySubscription},
+    Executor, ScalarValue, Value,
+};
+
+#[derive(Default)]
+struct Visits(AtomicUsize);
+
+fn visit<S: ScalarValue>(executor: &Executor<'_, '_, (), S>) -> i32 {
+    let visits = executor.state().get_or_default::<Visits>();
+    visits.0.fetch_add(1, Ordering::SeqCst) as i32 + 1
+}
+
+struct Query;
+
+#[graphql_object]
+impl Query {
+    fn visit<S: ScalarValue>(executor: &Executor<'_, '_, (), S>) -> i32 {
+        visit(executor)
+    }
+
+    async fn async_visit<S: ScalarValue>(executor: &Executor<'_, '_, (), S>) -> i32 {
+        visit(executor)
+    }
+
+    fn nested() -> Nested {
+        Nested
+    }
+}
+
+struct Nested;
+
+#[graphql_object]
+impl Nested {
+    fn visit<S: ScalarValue>(executor: &Executor<'_, '_, (), S>) -> i32 {
+        visit(executor)
+    }
+}
+
+struct Subscription;
+
+type Stream = std::pin::Pin<Box<dyn futures::Stream<Item = i32> + Send>>;
+
+#[graphql_subscription]
+impl Subscription {
+    async fn visits<S: ScalarValue>(executor: &Executor<'_, '_, (), S>) -> Stream {
+        let first = visit(executor);
+        let second = visit(executor);
+        Box::pin(futures::stream::iter(vec![first, second]))
+    }
+}
+
+fn schema() -> RootNode<'static, Query, EmptyMutation, EmptySubscription> {
+    RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+}
+
+#[test]
+fn shares_state_between_nested_resolvers() {
+    assert_eq!(
+        execute_sync(
+            "{ visit nested { visit } }",
+            None,
+            &schema(),
+            &graphql_vars! {},
+            &(),
+        ),
+        Ok((graphql_value!({"visit": 1, "nested": {"visit": 2}}), vec![])),
+    );
+}
+
+#[tokio::test]
+async fn shares_state_between_concurrent_resolvers() {
+    let (res, errors) = execute(
+        "{ a: asyncVisit b: asyncVisit c: asyncVisit d: asyncVisit }",
+        None,
+        &schema(),
+        &graphql_vars! {},
+        &(),
+    )
+    .await
+    .unwrap();
+    assert!(errors.is_empty());
+
+    let mut visits = res
+        .as_object_value()
+        .unwrap()
+        .iter()
+        .map(|(_, v)| v.as_scalar_value::<i32>().copied().unwrap())
+        .collect::<Vec<_>>();
+    visits.sort_unstable();
+    assert_eq!(visits, vec![1, 2, 3, 4]);
+}
+
+#[tokio::test]
+async fn isolates_state_of_requests() {
+    let schema = schema();
+
+    for _ in 0..2 {
+        assert_eq!(
+            execute("{ asyncVisit }", None, &schema, &graphql_vars! {}, &()).await,
+            Ok((graphql_value!({"asyncVisit": 1}), vec![])),
+        );
+    }
+}
+
+#[tokio::test]
+async fn shares_state_in_subscriptions() {
+    let schema = RootNode::new(Query, EmptyMutation::new(), Subscription);
+
+    let (res, errors) = resolve_into_stream(
+        "subscription { visits }",
+        None,
+        &schema,
+        &graphql_vars! {},
+        &(),
+    )
+    .await
+    .unwrap();
+    assert!(errors.is_empty());
+
+    let stream = match res {
+        Value::Object(o) => match o.into_iter().next().unwrap().1 {
+            Value::Scalar(s) => s,
+            _ => unreachable!(),
+        },
+        _ => unreachable!(),
+    };
+    assert_eq!(
+        stream.collect::<Vec<_>>().await,
+        vec![Ok(graphql_value!(1)), Ok(graphql_value!(2))],
+    );
+}
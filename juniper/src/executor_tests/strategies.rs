@@ -0,0 +1,317 @@
+use std::sync::Mutex;
+
+use crate::{
+    execute, graphql_object, graphql_value, graphql_vars, schema::model::RootNode,
+    types::scalars::EmptySubscription, ExecutorOptions, Parallel, Serial,
+};
+
+#[derive(Default)]
+struct Log(Mutex<Vec<String>>);
+
+impl Log {
+    async fn record(&self, field: &str) {
+        self.0.lock().unwrap().push(format!("{} started", field));
+        tokio::task::yield_now().await;
+        self.0.lock().unwrap().push(format!("{} finished", field));
+    }
+
+    fn entries(&self) -> Vec<String> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+impl crate::Context for Log {}
+
+struct Query;
+
+#[graphql_object(context = Log)]
+impl Query {
+    async fn a(ctx: &Log) -> i32 {
+        ctx.record("a").await;
+        1
+    }
+
+    async fn b(ctx: &Log) -> i32 {
+        ctx.record("b").await;
+        2
+    }
+}
+
+struct Mutation;
+
+#[graphql_object(context = Log)]
+impl Mutation {
+    async fn c(ctx: &Log) -> i32 {
+        ctx.record("c").await;
+        3
+    }
+
+    async fn d(ctx: &Log) -> i32 {
+        ctx.record("d").await;
+        4
+    }
+}
+
+fn schema(options: ExecutorOptions) -> RootNode<'static, Query, Mutation, EmptySubscription<Log>> {
+    RootNode::new(Query, Mutation, EmptySubscription::new()).with_executor_options(options)
+}
+
+const SERIAL: [&str; 4] = ["a started", "a finished", "b started", "b finished"];
+const PARALLEL: [&str; 4] = ["a started", "b started", "a finished", "b finished"];
+
+#[tokio::test]
+async fn resolves_in_parallel_by_default() {
+    let log = Log::default();
+
+    assert_eq!(
+        execute(
+            "{ a b }",
+            None,
+            &schema(ExecutorOptions::new()),
+            &graphql_vars! {},
+            &log,
+        )
+        .await,
+        Ok((graphql_value!({"a": 1, "b": 2}), vec![])),
+    );
+    assert_eq!(log.entries(), PARALLEL);
+
+    let log = Log::default();
+
+    assert_eq!(
+        execute(
+            "mutation { d c }",
+            None,
+            &schema(ExecutorOptions::new()),
+            &graphql_vars! {},
+            &log,
+        )
+        .await,
+        Ok((graphql_value!({"d": 4, "c": 3}), vec![])),
+    );
+    assert_eq!(
+        log.entries(),
+        ["d started", "c started", "d finished", "c finished"],
+    );
+}
+
+#[tokio::test]
+async fn resolves_serially_with_serial_strategy() {
+    let schema = schema(ExecutorOptions::new().query_strategy(Serial));
+    let log = Log::default();
+
+    assert_eq!(
+        execute("{ a b }", None, &schema, &graphql_vars! {}, &log).await,
+        Ok((graphql_value!({"a": 1, "b": 2}), vec![])),
+    );
+    assert_eq!(log.entries(), SERIAL);
+}
+
+#[tokio::test]
+async fn uses_strategy_of_operation_type() {
+    let schema = schema(
+        ExecutorOptions::new()
+            .query_strategy(Parallel)
+            .mutation_strategy(Serial),
+    );
+
+    let log = Log::default();
+    execute("{ a b }", None, &schema, &graphql_vars! {}, &log)
+        .await
+        .unwrap();
+    assert_eq!(log.entries(), PARALLEL);
+
+    let log = Log::default();
+
+    assert_eq!(
+        execute("mutation { d c }", None, &schema, &graphql_vars! {}, &log).await,
+        Ok((graphql_value!({"d": 4, "c": 3}), vec![])),
+    );
+    assert_eq!(
+        log.entries(),
+        ["d started", "d finished", "c started", "c finished"],
+    );
+}
+
+#[cfg(feature = "rayon")]
+mod rayon {
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            mpsc, Mutex,
+        },
+        thread::{self, ThreadId},
+        time::{Duration, Instant},
+    };
+
+    use futures::channel::oneshot;
+
+    use crate::{
+        execute_sync_parallel, graphql_object, graphql_value, graphql_vars,
+        schema::model::RootNode,
+        types::scalars::{EmptyMutation, EmptySubscription},
+    };
+
+    /// Records the threads resolving the fields, and lets them wait for each
+    /// other.
+    #[derive(Default)]
+    struct Threads {
+        started: AtomicUsize,
+        ids: Mutex<Vec<ThreadId>>,
+    }
+
+    impl Threads {
+        /// Indicates whether the other field has started being resolved while
+        /// this one is still being resolved.
+        fn meet(&self) -> bool {
+            self.ids.lock().unwrap().push(thread::current().id());
+            self.started.fetch_add(1, Ordering::SeqCst);
+
+            let deadline = Instant::now() + Duration::from_secs(5);
+            while Instant::now() < deadline {
+                if self.started.load(Ordering::SeqCst) >= 2 {
+                    return true;
+                }
+                thread::yield_now();
+            }
+            false
+        }
+    }
+
+    impl crate::Context for Threads {}
+
+    struct Query;
+
+    #[graphql_object(context = Threads)]
+    impl Query {
+        fn a(ctx: &Threads) -> bool {
+            ctx.meet()
+        }
+
+        fn b(ctx: &Threads) -> bool {
+            ctx.meet()
+        }
+    }
+
+    struct Mutation;
+
+    #[graphql_object(context = Threads)]
+    impl Mutation {
+        fn c(ctx: &Threads) -> i32 {
+            ctx.ids.lock().unwrap().push(thread::current().id());
+            3
+        }
+
+        fn d(ctx: &Threads) -> i32 {
+            ctx.ids.lock().unwrap().push(thread::current().id());
+            4
+        }
+    }
+
+    /// Channels the fields `ping` and `pong` exchange a message over, so each
+    /// of them awaits the other.
+    struct Exchange {
+        ping: Mutex<Option<(oneshot::Sender<i32>, oneshot::Receiver<i32>)>>,
+        pong: Mutex<Option<(oneshot::Sender<i32>, oneshot::Receiver<i32>)>>,
+    }
+
+    impl Default for Exchange {
+        fn default() -> Self {
+            let (ping_tx, ping_rx) = oneshot::channel();
+            let (pong_tx, pong_rx) = oneshot::channel();
+            Self {
+                ping: Mutex::new(Some((ping_tx, pong_rx))),
+                pong: Mutex::new(Some((pong_tx, ping_rx))),
+            }
+        }
+    }
+
+    impl crate::Context for Exchange {}
+
+    struct Exchanging;
+
+    #[graphql_object(context = Exchange)]
+    impl Exchanging {
+        async fn ping(ctx: &Exchange) -> i32 {
+            let (tx, rx) = ctx.ping.lock().unwrap().take().unwrap();
+            tx.send(1).unwrap();
+            rx.await.unwrap()
+        }
+
+        async fn pong(ctx: &Exchange) -> i32 {
+            let (tx, rx) = ctx.pong.lock().unwrap().take().unwrap();
+            let n = rx.await.unwrap();
+            tx.send(n + 1).unwrap();
+            n
+        }
+    }
+
+    fn pool() -> ::rayon::ThreadPool {
+        ::rayon::ThreadPoolBuilder::new()
+            .num_threads(2)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn resolves_query_fields_in_parallel() {
+        let schema = RootNode::new(Query, Mutation, EmptySubscription::new());
+        let threads = Threads::default();
+
+        let res = pool().install(|| {
+            execute_sync_parallel("{ a b }", None, &schema, &graphql_vars! {}, &threads)
+        });
+
+        assert_eq!(res, Ok((graphql_value!({"a": true, "b": true}), vec![])));
+        let ids = threads.ids.into_inner().unwrap();
+        assert_ne!(ids[0], ids[1]);
+    }
+
+    #[test]
+    fn resolves_mutation_fields_on_current_thread() {
+        let schema = RootNode::new(Query, Mutation, EmptySubscription::new());
+        let threads = Threads::default();
+
+        let (res, current) = pool().install(|| {
+            let res = execute_sync_parallel(
+                "mutation { c d }",
+                None,
+                &schema,
+                &graphql_vars! {},
+                &threads,
+            );
+            (res, thread::current().id())
+        });
+
+        assert_eq!(res, Ok((graphql_value!({"c": 3, "d": 4}), vec![])));
+        assert_eq!(threads.ids.into_inner().unwrap(), [current, current]);
+    }
+
+    #[test]
+    fn resolves_fields_awaiting_each_other_on_single_thread() {
+        let schema = RootNode::new(
+            Exchanging,
+            EmptyMutation::<Exchange>::new(),
+            EmptySubscription::new(),
+        );
+        // Same as the global pool configured with `RAYON_NUM_THREADS=1`.
+        let pool = ::rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .unwrap();
+
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let exchange = Exchange::default();
+            let res = pool.install(|| {
+                execute_sync_parallel("{ ping pong }", None, &schema, &graphql_vars! {}, &exchange)
+            });
+            tx.send(res.map_err(|e| e.to_string())).unwrap();
+        });
+
+        assert_eq!(
+            rx.recv_timeout(Duration::from_secs(10)).unwrap(),
+            Ok((graphql_value!({"ping": 2, "pong": 1}), vec![])),
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use std::sync::{Arc, Mutex};
+
+use crate::{
+    execute, execute_sync, graphql_object, graphql_value, graphql_vars,
+    schema::model::RootNode,
+    types::scalars::{EmptyMutation, EmptySubscription},
+    DefaultScalarValue, GraphQLObject, OperationType, ResponseField, ResponseTransform, Value,
+};
+
+#[derive(GraphQLObject)]
+struct Card {
+    number: String,
+    holder: String,
+}
+
+#[derive(GraphQLObject)]
+struct Account {
+    id: i32,
+    cards: Vec<Card>,
+}
+
+struct Query;
+
+#[graphql_object]
+impl Query {
+    fn account() -> Account {
+        Account {
+            id: 1,
+            cards: vec![
+                Card {
+                    number: "4111111111111111".into(),
+                    holder: "John".into(),
+                },
+                Card {
+                    number: "5500000000000004".into(),
+                    holder: "Jane".into(),
+                },
+            ],
+        }
+    }
+}
+
+/// Masks all the digits of `Card.number` except the last four ones.
+#[derive(Debug, Default)]
+struct MaskCardNumbers {
+    paths: Mutex<Vec<String>>,
+    responses: Mutex<Vec<(Option<String>, Value)>>,
+}
+
+impl ResponseTransform<DefaultScalarValue> for MaskCardNumbers {
+    fn transform_field(&self, field: &ResponseField<'_, DefaultScalarValue>, value: &mut Value) {
+        self.paths.lock().unwrap().push(field.path().join("."));
+
+        if field.parent_type().name() == Some("Card") && field.field().name == "number" {
+            if let Some(number) = value.as_string_value() {
+                let masked = format!("****{}", &number[number.len() - 4..]);
+                *value = Value::scalar(masked);
+            }
+        }
+    }
+
+    fn transform_response(&self, operation_name: Option<&str>, _: OperationType, data: &mut Value) {
+        self.responses
+            .lock()
+            .unwrap()
+            .push((operation_name.map(Into::into), data.clone()));
+    }
+}
+
+fn schema(
+    transform: Arc<MaskCardNumbers>,
+) -> RootNode<'static, Query, EmptyMutation, EmptySubscription> {
+    RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+        .with_response_transform(transform)
+}
+
+#[tokio::test]
+async fn transforms_fields_with_schema_metadata() {
+    let transform = Arc::new(MaskCardNumbers::default());
+    let schema = schema(Arc::clone(&transform));
+
+    let (res, errs) = execute(
+        r"query Q {
+            account {
+                id
+                cards { num: number ...F }
+                ... on Account { cards { number } }
+            }
+        }
+        fragment F on Card { holder }",
+        None,
+        &schema,
+        &graphql_vars! {},
+        &(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(errs, []);
+    assert_eq!(
+        res,
+        graphql_value!({
+            "account": {
+                "id": 1,
+                "cards": [
+                    {"num": "****1111", "holder": "John", "number": "****1111"},
+                    {"num": "****0004", "holder": "Jane", "number": "****0004"},
+                ],
+            },
+        }),
+    );
+    assert_eq!(
+        *transform.paths.lock().unwrap(),
+        [
+            "account.id",
+            "account.cards.num",
+            "account.cards.holder",
+            "account.cards.number",
+            "account.cards.num",
+            "account.cards.holder",
+            "account.cards.number",
+            "account.cards",
+            "account",
+        ],
+        "transforms subfields before their parents",
+    );
+    assert_eq!(
+        *transform.responses.lock().unwrap(),
+        [(Some("Q".into()), res)],
+        "transforms the full response once",
+    );
+}
+
+#[test]
+fn transforms_sync_execution() {
+    let schema = schema(Arc::default());
+
+    let (res, errs) = execute_sync(
+        r"{ account { cards { number } } }",
+        None,
+        &schema,
+        &graphql_vars! {},
+        &(),
+    )
+    .unwrap();
+
+    assert_eq!(errs, []);
+    assert_eq!(
+        res,
+        graphql_value!({
+            "account": {"cards": [{"number": "****1111"}, {"number": "****0004"}]},
+        }),
+    );
+}
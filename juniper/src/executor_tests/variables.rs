@@ -6,8 +6,9 @@ use crate::{
     types::scalars::{EmptyMutation, EmptySubscription},
     validation::RuleError,
     value::{DefaultScalarValue, Object},
-    GraphQLError::ValidationError,
-    GraphQLInputObject, GraphQLScalar, InputValue, ScalarValue, Value,
+    GraphQLInputObject, GraphQLScalar, InputValue,
+    RequestError::{Validation, VariableCoercion},
+    ScalarValue, Value,
 };
 
 #[derive(Debug, GraphQLScalar)]
@@ -275,7 +276,7 @@ async fn variable_error_on_nested_non_null() {
 
     assert_eq!(
         error,
-        ValidationError(vec![RuleError::new(
+        VariableCoercion(vec![RuleError::new(
             r#"Variable "$input" got invalid value. In field "c": Expected "String!", found null."#,
             &[SourcePosition::new(8, 0, 8)],
         )]),
@@ -299,7 +300,7 @@ async fn variable_error_on_incorrect_type() {
 
     assert_eq!(
         error,
-        ValidationError(vec![RuleError::new(
+        VariableCoercion(vec![RuleError::new(
             r#"Variable "$input" got invalid value. Expected "TestInputObject", found not an object."#,
             &[SourcePosition::new(8, 0, 8)],
         )]),
@@ -328,7 +329,7 @@ async fn variable_error_on_omit_non_null() {
 
     assert_eq!(
         error,
-        ValidationError(vec![RuleError::new(
+        VariableCoercion(vec![RuleError::new(
             r#"Variable "$input" got invalid value. In field "c": Expected "String!", found null."#,
             &[SourcePosition::new(8, 0, 8)],
         )]),
@@ -357,7 +358,7 @@ async fn variable_multiple_errors_with_nesting() {
 
     assert_eq!(
         error,
-        ValidationError(vec![
+        VariableCoercion(vec![
             RuleError::new(
                 r#"Variable "$input" got invalid value. In field "na": In field "c": Expected "String!", found null."#,
                 &[SourcePosition::new(8, 0, 8)],
@@ -394,7 +395,7 @@ async fn variable_error_on_additional_field() {
 
     assert_eq!(
         error,
-        ValidationError(vec![RuleError::new(
+        VariableCoercion(vec![RuleError::new(
             r#"Variable "$input" got invalid value. In field "extra": Unknown field."#,
             &[SourcePosition::new(8, 0, 8)],
         )]),
@@ -504,7 +505,7 @@ async fn does_not_allow_non_nullable_input_to_be_omitted_in_variable() {
 
     assert_eq!(
         error,
-        ValidationError(vec![RuleError::new(
+        VariableCoercion(vec![RuleError::new(
             r#"Variable "$value" of required type "String!" was not provided."#,
             &[SourcePosition::new(8, 0, 8)],
         )]),
@@ -528,7 +529,7 @@ async fn does_not_allow_non_nullable_input_to_be_set_to_null_in_variable() {
 
     assert_eq!(
         error,
-        ValidationError(vec![RuleError::new(
+        VariableCoercion(vec![RuleError::new(
             r#"Variable "$value" of required type "String!" was not provided."#,
             &[SourcePosition::new(8, 0, 8)],
         )]),
@@ -626,7 +627,7 @@ async fn does_not_allow_non_null_lists_to_be_null() {
 
     assert_eq!(
         error,
-        ValidationError(vec![RuleError::new(
+        VariableCoercion(vec![RuleError::new(
             r#"Variable "$input" of required type "[String]!" was not provided."#,
             &[SourcePosition::new(8, 0, 8)],
         )]),
@@ -709,7 +710,7 @@ async fn does_not_allow_lists_of_non_null_to_contain_null() {
 
     assert_eq!(
         error,
-        ValidationError(vec![RuleError::new(
+        VariableCoercion(vec![RuleError::new(
             r#"Variable "$input" got invalid value. In element #1: Expected "String!", found null."#,
             &[SourcePosition::new(8, 0, 8)],
         )]),
@@ -733,7 +734,7 @@ async fn does_not_allow_non_null_lists_of_non_null_to_contain_null() {
 
     assert_eq!(
         error,
-        ValidationError(vec![RuleError::new(
+        VariableCoercion(vec![RuleError::new(
             r#"Variable "$input" got invalid value. In element #1: Expected "String!", found null."#,
             &[SourcePosition::new(8, 0, 8)],
         )]),
@@ -757,7 +758,7 @@ async fn does_not_allow_non_null_lists_of_non_null_to_be_null() {
 
     assert_eq!(
         error,
-        ValidationError(vec![RuleError::new(
+        VariableCoercion(vec![RuleError::new(
             r#"Variable "$input" of required type "[String!]!" was not provided."#,
             &[SourcePosition::new(8, 0, 8)],
         )]),
@@ -894,7 +895,7 @@ async fn does_not_allow_missing_required_field() {
 
     assert_eq!(
         error,
-        ValidationError(vec![RuleError::new(
+        Validation(vec![RuleError::new(
             r#"Invalid value for argument "arg", expected type "ExampleInputObject!""#,
             &[SourcePosition::new(20, 0, 20)],
         )]),
@@ -918,9 +919,9 @@ async fn does_not_allow_null_in_required_field() {
 
     assert_eq!(
         error,
-        ValidationError(vec![RuleError::new(
+        Validation(vec![RuleError::new(
             r#"Invalid value for argument "arg", expected type "ExampleInputObject!""#,
-            &[SourcePosition::new(20, 0, 20)],
+            &[SourcePosition::new(34, 0, 34)],
         )]),
     );
 }
@@ -942,7 +943,7 @@ async fn does_not_allow_missing_variable_for_required_field() {
 
     assert_eq!(
         error,
-        ValidationError(vec![RuleError::new(
+        VariableCoercion(vec![RuleError::new(
             r#"Variable "$var" of required type "Int!" was not provided."#,
             &[SourcePosition::new(8, 0, 8)],
         )]),
@@ -966,7 +967,7 @@ async fn does_not_allow_null_variable_for_required_field() {
 
     assert_eq!(
         error,
-        ValidationError(vec![RuleError::new(
+        VariableCoercion(vec![RuleError::new(
             r#"Variable "$var" of required type "Int!" was not provided."#,
             &[SourcePosition::new(8, 0, 8)],
         )]),
@@ -1067,7 +1068,7 @@ mod integers {
 
         assert_eq!(
             error,
-            ValidationError(vec![RuleError::new(
+            VariableCoercion(vec![RuleError::new(
                 "Variable \"$var\" got invalid value. Expected input scalar `Int`. \
                  Got: `10`. Details: Expected `Int`, found: 10.",
                 &[SourcePosition::new(8, 0, 8)],
@@ -1092,7 +1093,7 @@ mod integers {
 
         assert_eq!(
             error,
-            ValidationError(vec![RuleError::new(
+            VariableCoercion(vec![RuleError::new(
                 "Variable \"$var\" got invalid value. \
                  Expected input scalar `Int`. Got: `\"10\"`. \
                  Details: Expected `Int`, found: \"10\".",
@@ -1152,7 +1153,7 @@ mod floats {
 
         assert_eq!(
             error,
-            ValidationError(vec![RuleError::new(
+            VariableCoercion(vec![RuleError::new(
                 "Variable \"$var\" got invalid value. \
                  Expected input scalar `Float`. Got: `\"10\"`. \
                  Details: Expected `Float`, found: \"10\".",
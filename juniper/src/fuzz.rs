@@ -0,0 +1,227 @@
+//! Entry points for fuzzing the parser and the validation.
+//!
+//! These functions accept arbitrary bytes, so can be called right from a
+//! `cargo fuzz` target, and are guaranteed to never panic, whatever the input
+//! is: malformed input is reported as a [`FuzzError`] instead. Any panic (or
+//! stack overflow) inside them is a bug, and should be reported.
+//!
+//! Their signatures are considered stable, so fuzz targets built on them don't
+//! break between releases.
+//!
+//! ```
+//! use juniper::{fuzz, graphql_object, EmptyMutation, EmptySubscription, RootNode};
+//!
+//! struct Query;
+//!
+//! #[graphql_object]
+//! impl Query {
+//!     fn hello(name: Option<String>) -> String {
+//!         format!("Hello, {}!", name.as_deref().unwrap_or("world"))
+//!     }
+//! }
+//!
+//! let schema = RootNode::new(
+//!     Query,
+//!     EmptyMutation::<()>::new(),
+//!     EmptySubscription::<()>::new(),
+//! );
+//!
+//! // Inside `libfuzzer_sys::fuzz_target!(|data: &[u8]| { ... })`:
+//! let data: &[u8] = b"{ hello(name: \"fuzz\") }";
+//! let _ = fuzz::parse_document_bytes(&schema, data);
+//! let _ = fuzz::validate_arbitrary(&schema, data);
+//!
+//! assert!(fuzz::validate_arbitrary(&schema, b"{ hello }").is_ok());
+//! assert!(fuzz::validate_arbitrary(&schema, b"{ hello(name: 1) }").is_err());
+//! assert!(fuzz::validate_arbitrary(&schema, b"\xff{").is_err());
+//! ```
+
+use std::{fmt, str::Utf8Error};
+
+use crate::{
+    ast::{Definition, OwnedDocument},
+    parser::parse_document_source,
+    validation::{validate_input_values, visit_all_rules, ValidatorContext},
+    GraphQLType, RequestError, RootNode, ScalarValue, Variables,
+};
+
+/// Error of processing arbitrary input by the fuzzing entry points.
+#[derive(Debug, PartialEq)]
+pub enum FuzzError<'a> {
+    /// The input is not a valid UTF-8.
+    Utf8(Utf8Error),
+
+    /// The input has been rejected by the request pipeline.
+    Request(RequestError<'a>),
+}
+
+impl<'a> fmt::Display for FuzzError<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Utf8(e) => write!(f, "{}", e),
+            Self::Request(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl<'a> std::error::Error for FuzzError<'a> {}
+
+impl<'a> From<RequestError<'a>> for FuzzError<'a> {
+    fn from(e: RequestError<'a>) -> Self {
+        Self::Request(e)
+    }
+}
+
+/// Parses the given `data` as a GraphQL document against the provided
+/// `schema`, respecting its parser recursion limit.
+///
+/// Never panics.
+///
+/// # Errors
+///
+/// If the `data` is not a valid UTF-8, or cannot be parsed.
+pub fn parse_document_bytes<'a, QueryT, MutationT, SubscriptionT, S>(
+    schema: &RootNode<QueryT, MutationT, SubscriptionT, S>,
+    data: &'a [u8],
+) -> Result<OwnedDocument<'a, S>, FuzzError<'a>>
+where
+    S: ScalarValue,
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S>,
+    SubscriptionT: GraphQLType<S>,
+{
+    let source = std::str::from_utf8(data).map_err(FuzzError::Utf8)?;
+    parse_document_source(source, &schema.schema).map_err(|e| RequestError::Parse(e).into())
+}
+
+/// Parses the given `data` as a GraphQL document against the provided
+/// `schema`, and validates it the same way a request is validated before
+/// execution: checking all the validation rules, and then checking the
+/// [`Limits`] of the `schema` and coercing empty variables for each operation.
+///
+/// Never panics.
+///
+/// # Errors
+///
+/// If the `data` is not a valid UTF-8, cannot be parsed, or is not valid.
+///
+/// [`Limits`]: crate::Limits
+pub fn validate_arbitrary<'a, QueryT, MutationT, SubscriptionT, S>(
+    schema: &RootNode<QueryT, MutationT, SubscriptionT, S>,
+    data: &'a [u8],
+) -> Result<(), FuzzError<'a>>
+where
+    S: ScalarValue,
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S>,
+    SubscriptionT: GraphQLType<S>,
+{
+    let document = parse_document_bytes(schema, data)?;
+
+    let mut ctx = ValidatorContext::new(&schema.schema, &document);
+    visit_all_rules(&mut ctx, &document);
+    let errors = ctx.into_errors();
+    if !errors.is_empty() {
+        return Err(RequestError::Validation(errors).into());
+    }
+
+    for def in &document {
+        if let Definition::Operation(op) = def {
+            let errors = schema.schema.limits().check(
+                &schema.schema,
+                &document,
+                op,
+                &Variables::new(),
+                None,
+            );
+            if !errors.is_empty() {
+                return Err(RequestError::Validation(errors).into());
+            }
+
+            let errors = validate_input_values(&Variables::new(), op, &schema.schema);
+            if !errors.is_empty() {
+                return Err(RequestError::VariableCoercion(errors).into());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        tests::fixtures::starwars::schema::{Database, Query},
+        EmptyMutation, EmptySubscription, RootNode,
+    };
+
+    use super::{parse_document_bytes, validate_arbitrary, FuzzError};
+
+    type Schema = RootNode<'static, Query, EmptyMutation<Database>, EmptySubscription<Database>>;
+
+    fn schema() -> Schema {
+        RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+    }
+
+    #[test]
+    fn accepts_valid_document() {
+        let schema = schema();
+
+        assert!(parse_document_bytes(&schema, b"{ hero { name } }").is_ok());
+        assert_eq!(validate_arbitrary(&schema, b"{ hero { name } }"), Ok(()));
+    }
+
+    #[test]
+    fn rejects_invalid_utf8() {
+        let schema = schema();
+
+        assert!(matches!(
+            parse_document_bytes(&schema, b"{ hero { name\xc3\x28 } }"),
+            Err(FuzzError::Utf8(_)),
+        ));
+    }
+
+    #[test]
+    fn rejects_too_deep_nesting() {
+        let schema = schema();
+        let data = format!("{}{}", "{ hero ".repeat(10_000), "}".repeat(10_000));
+
+        assert!(matches!(
+            validate_arbitrary(&schema, data.as_bytes()),
+            Err(FuzzError::Request(_)),
+        ));
+    }
+
+    #[test]
+    fn rejects_missing_variables() {
+        let schema = schema();
+
+        assert!(matches!(
+            validate_arbitrary(&schema, b"query($id: String!) { human(id: $id) { name } }"),
+            Err(FuzzError::Request(crate::RequestError::VariableCoercion(_))),
+        ));
+    }
+
+    #[test]
+    fn never_panics_on_garbage() {
+        let schema = schema();
+        let alphabet = b"{}()[]:$@!.=,\"\\ \n#query fragment on ...hero name 0123456789e-+\xff";
+
+        // Simple xorshift, to stay deterministic without extra dependencies.
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        for _ in 0..2_000 {
+            let len = (next() % 64) as usize;
+            let data = (0..len)
+                .map(|_| alphabet[(next() % alphabet.len() as u64) as usize])
+                .collect::<Vec<_>>();
+            let _ = parse_document_bytes(&schema, &data);
+            let _ = validate_arbitrary(&schema, &data);
+        }
+    }
+}
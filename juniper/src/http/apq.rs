@@ -0,0 +1,392 @@
+//! [Automatic persisted queries][1] (APQ), letting clients send only the hash
+//! of a query once the server has seen its text.
+//!
+//! ```
+//! use juniper::{
+//!     http::{apq::LruPersistedQueryCache, GraphQLRequest, GraphQLResponse},
+//!     DefaultScalarValue,
+//! };
+//!
+//! let cache = LruPersistedQueryCache::new(1000);
+//!
+//! // Request sent by a client with the hash of the query only.
+//! let mut request: GraphQLRequest = serde_json::from_str(r#"{
+//!     "extensions": {
+//!         "persistedQuery": {
+//!             "version": 1,
+//!             "sha256Hash": "ecf4edb46db40b5132295c0291d62fb65d6759a9eedfa4d5d612dd5ec54a6b38"
+//!         }
+//!     }
+//! }"#).unwrap();
+//!
+//! // The query is unknown yet, so the client should retry with its text.
+//! let err = request.resolve_persisted_query(&cache).unwrap_err();
+//! let response = GraphQLResponse::<DefaultScalarValue>::from(err);
+//! # let _ = response;
+//!
+//! request.query = "{__typename}".into();
+//! request.resolve_persisted_query(&cache).unwrap();
+//!
+//! // Since now, the hash is enough.
+//! request.query = String::new();
+//! request.resolve_persisted_query(&cache).unwrap();
+//! assert_eq!(request.query, "{__typename}");
+//! ```
+//!
+//! [1]: https://www.apollographql.com/docs/apollo-server/performance/apq
+
+use std::{
+    error::Error as StdError,
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use sha2::{Digest as _, Sha256};
+
+use crate::{util::LruMap, value::ScalarValue, FieldError};
+
+use super::{GraphQLRequest, GraphQLResponse};
+
+/// Cache of the [automatic persisted queries][1] seen by a server, keyed by
+/// the hex-encoded SHA-256 hashes of their text.
+///
+/// [1]: https://www.apollographql.com/docs/apollo-server/performance/apq
+pub trait PersistedQueryCache: Send + Sync {
+    /// Returns the text of the query with the given `hash`, if it's cached.
+    fn get(&self, hash: &str) -> Option<Arc<str>>;
+
+    /// Caches the text of the `query` with the given `hash`.
+    fn insert(&self, hash: String, query: Arc<str>);
+}
+
+impl<T: PersistedQueryCache + ?Sized> PersistedQueryCache for Arc<T> {
+    fn get(&self, hash: &str) -> Option<Arc<str>> {
+        (**self).get(hash)
+    }
+
+    fn insert(&self, hash: String, query: Arc<str>) {
+        (**self).insert(hash, query)
+    }
+}
+
+/// In-memory [`PersistedQueryCache`], evicting the least recently used queries
+/// once its capacity is reached.
+#[derive(Debug)]
+pub struct LruPersistedQueryCache(Mutex<LruMap<Arc<str>>>);
+
+impl LruPersistedQueryCache {
+    /// Creates a new [`LruPersistedQueryCache`] holding at most `capacity`
+    /// queries.
+    pub fn new(capacity: usize) -> Self {
+        Self(Mutex::new(LruMap::new(capacity)))
+    }
+
+    /// Returns the number of the currently cached queries.
+    pub fn len(&self) -> usize {
+        self.0.lock().unwrap().len()
+    }
+
+    /// Indicates whether no queries are currently cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl PersistedQueryCache for LruPersistedQueryCache {
+    fn get(&self, hash: &str) -> Option<Arc<str>> {
+        self.0.lock().unwrap().get(hash)
+    }
+
+    fn insert(&self, hash: String, query: Arc<str>) {
+        self.0.lock().unwrap().insert(hash.into(), query)
+    }
+}
+
+/// Error of resolving an [automatic persisted query][1].
+///
+/// Converts into a [`GraphQLResponse`] of the shape expected by Apollo clients,
+/// so they retry the request with the query text on
+/// [`PersistedQueryError::NotFound`].
+///
+/// [1]: https://www.apollographql.com/docs/apollo-server/performance/apq
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PersistedQueryError {
+    /// Query with the requested hash is not cached.
+    NotFound,
+
+    /// Hash sent along with a query text doesn't match it.
+    HashMismatch,
+
+    /// Requested version of the protocol is not supported.
+    UnsupportedVersion(u32),
+}
+
+impl PersistedQueryError {
+    /// Returns the machine-readable code of this [`PersistedQueryError`], as
+    /// put into the `extensions` of the error response.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::NotFound => "PERSISTED_QUERY_NOT_FOUND",
+            Self::HashMismatch | Self::UnsupportedVersion(_) => "BAD_REQUEST",
+        }
+    }
+}
+
+impl fmt::Display for PersistedQueryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NotFound => write!(f, "PersistedQueryNotFound"),
+            Self::HashMismatch => write!(f, "provided sha does not match query"),
+            Self::UnsupportedVersion(v) => write!(f, "Unsupported persisted query version: {}", v),
+        }
+    }
+}
+
+impl StdError for PersistedQueryError {}
+
+impl<'a, S: ScalarValue> From<PersistedQueryError> for GraphQLResponse<'a, S> {
+    fn from(e: PersistedQueryError) -> Self {
+        let code = e.code();
+        Self::error(FieldError::new(e, crate::graphql_value!({ "code": code })))
+    }
+}
+
+impl<S> GraphQLRequest<S>
+where
+    S: ScalarValue,
+{
+    /// Resolves the [`PersistedQuery`] this request refers to, if any, using
+    /// the provided [`PersistedQueryCache`].
+    ///
+    /// If the request has no query text, it's looked up by its hash in the
+    /// `cache`. Otherwise, the query text is checked to match its hash, and is
+    /// cached for the subsequent requests.
+    ///
+    /// Does nothing for requests not referring to any [`PersistedQuery`].
+    ///
+    /// # Errors
+    ///
+    /// If the query is not cached yet, its text doesn't match its hash, or
+    /// the version of the protocol is not supported.
+    ///
+    /// [`PersistedQuery`]: super::PersistedQuery
+    pub fn resolve_persisted_query(
+        &mut self,
+        cache: &(impl PersistedQueryCache + ?Sized),
+    ) -> Result<(), PersistedQueryError> {
+        let persisted = match self.persisted_query() {
+            Some(persisted) => persisted,
+            None => return Ok(()),
+        };
+        if persisted.version != 1 {
+            return Err(PersistedQueryError::UnsupportedVersion(persisted.version));
+        }
+
+        let hash = persisted.sha256_hash.to_ascii_lowercase();
+        if self.query.is_empty() {
+            let query = cache.get(&hash).ok_or(PersistedQueryError::NotFound)?;
+            self.query = query.to_string();
+        } else {
+            if sha256_hex(&self.query) != hash {
+                return Err(PersistedQueryError::HashMismatch);
+            }
+            cache.insert(hash, self.query.as_str().into());
+        }
+        Ok(())
+    }
+}
+
+/// Returns the lowercase hex-encoded SHA-256 hash of the given `query`.
+pub(crate) fn sha256_hex(query: &str) -> String {
+    Sha256::digest(query.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        http::{GraphQLRequest, GraphQLResponse, PersistedQuery, RequestExtensions},
+        DefaultScalarValue, DocumentCache as _, EmptyMutation, EmptySubscription, LruDocumentCache,
+        RootNode,
+    };
+
+    use super::{
+        sha256_hex, LruPersistedQueryCache, PersistedQueryCache as _, PersistedQueryError,
+    };
+
+    const QUERY: &str = "{ hero { name } }";
+
+    fn request(query: &str, hash: &str) -> GraphQLRequest {
+        let mut req = GraphQLRequest::new(query.into(), None, None);
+        req.extensions = Some(RequestExtensions {
+            persisted_query: Some(PersistedQuery::new(hash)),
+        });
+        req
+    }
+
+    #[test]
+    fn hashes_query() {
+        assert_eq!(
+            sha256_hex("{__typename}"),
+            "ecf4edb46db40b5132295c0291d62fb65d6759a9eedfa4d5d612dd5ec54a6b38",
+        );
+    }
+
+    #[test]
+    fn registers_and_resolves_query() {
+        let cache = LruPersistedQueryCache::new(10);
+        let hash = sha256_hex(QUERY);
+
+        let mut req = request("", &hash);
+        assert_eq!(
+            req.resolve_persisted_query(&cache),
+            Err(PersistedQueryError::NotFound),
+        );
+
+        request(QUERY, &hash.to_uppercase())
+            .resolve_persisted_query(&cache)
+            .unwrap();
+        req.resolve_persisted_query(&cache).unwrap();
+        assert_eq!(req.query, QUERY);
+    }
+
+    #[test]
+    fn rejects_mismatched_hash_and_version() {
+        let cache = LruPersistedQueryCache::new(10);
+
+        assert_eq!(
+            request(QUERY, &sha256_hex("{ other }")).resolve_persisted_query(&cache),
+            Err(PersistedQueryError::HashMismatch),
+        );
+        assert!(cache.is_empty());
+
+        let mut req = request(QUERY, &sha256_hex(QUERY));
+        req.extensions
+            .as_mut()
+            .unwrap()
+            .persisted_query
+            .as_mut()
+            .unwrap()
+            .version = 2;
+        assert_eq!(
+            req.resolve_persisted_query(&cache),
+            Err(PersistedQueryError::UnsupportedVersion(2)),
+        );
+    }
+
+    #[test]
+    fn ignores_regular_requests() {
+        let cache = LruPersistedQueryCache::new(10);
+        let mut req = GraphQLRequest::<DefaultScalarValue>::new(QUERY.into(), None, None);
+
+        req.resolve_persisted_query(&cache).unwrap();
+        assert_eq!(req.query, QUERY);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let cache = LruPersistedQueryCache::new(2);
+        cache.insert("a".into(), "{ a }".into());
+        cache.insert("b".into(), "{ b }".into());
+        assert!(cache.get("a").is_some());
+
+        cache.insert("c".into(), "{ c }".into());
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get("b").is_none());
+        assert!(cache.get("a").is_some());
+        assert!(cache.get("c").is_some());
+    }
+
+    #[test]
+    fn deserializes_hash_only_request() {
+        let req: GraphQLRequest = serde_json::from_str(
+            r#"{"extensions": {"persistedQuery": {"version": 1, "sha256Hash": "abc"}}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(req.query, "");
+        assert_eq!(req.persisted_query(), Some(&PersistedQuery::new("abc")));
+    }
+
+    struct Query;
+
+    #[crate::graphql_object]
+    impl Query {
+        fn answer() -> i32 {
+            42
+        }
+    }
+
+    type Schema = RootNode<'static, Query, EmptyMutation, EmptySubscription>;
+
+    fn schema(cache: &Arc<LruDocumentCache<DefaultScalarValue>>) -> Schema {
+        Schema::new(Query, EmptyMutation::new(), EmptySubscription::new())
+            .with_document_cache(Arc::clone(cache))
+    }
+
+    #[test]
+    fn executes_cached_document_by_hash() {
+        let cache = Arc::new(LruDocumentCache::new(10));
+        let schema = schema(&cache);
+        let hash = sha256_hex("{ answer }");
+
+        assert!(request("{ answer }", &hash)
+            .execute_sync(&schema, &())
+            .is_ok());
+        assert!(cache.get(&hash).is_some());
+        assert!(cache.get("{ answer }").is_none());
+
+        // Once cached, the hash alone is enough.
+        assert_eq!(
+            serde_json::to_value(request("", &hash.to_uppercase()).execute_sync(&schema, &()))
+                .unwrap(),
+            serde_json::json!({"data": {"answer": 42}}),
+        );
+    }
+
+    #[test]
+    fn ignores_hash_of_other_document() {
+        let cache = Arc::new(LruDocumentCache::new(10));
+        let schema = schema(&cache);
+        let hash = sha256_hex("{ answer }");
+
+        let req = request("{ a: answer }", &hash);
+        assert_eq!(
+            serde_json::to_value(req.execute_sync(&schema, &())).unwrap(),
+            serde_json::json!({"data": {"a": 42}}),
+        );
+        assert!(cache.get(&hash).is_none());
+        assert!(cache.get("{ a: answer }").is_some());
+
+        assert!(request("{ answer }", &hash)
+            .execute_sync(&schema, &())
+            .is_ok());
+        assert_eq!(
+            serde_json::to_value(req.execute_sync(&schema, &())).unwrap(),
+            serde_json::json!({"data": {"a": 42}}),
+        );
+    }
+
+    #[test]
+    fn serializes_not_found_error() {
+        let response = GraphQLResponse::<DefaultScalarValue>::from(PersistedQueryError::NotFound);
+
+        assert_eq!(
+            serde_json::to_value(&response).unwrap(),
+            serde_json::json!({
+                "data": null,
+                "errors": [{
+                    "message": "PersistedQueryNotFound",
+                    "locations": [{"line": 1, "column": 1}],
+                    "path": [],
+                    "extensions": {"code": "PERSISTED_QUERY_NOT_FOUND"},
+                }],
+            }),
+        );
+    }
+}
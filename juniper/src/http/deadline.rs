@@ -0,0 +1,195 @@
+//! Deadlines of requests propagated from upstream services.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::Limits;
+
+/// Remaining time budget of a request, propagated from an upstream service via
+/// request headers, so the GraphQL layer doesn't keep resolving an operation
+/// its caller has already given up on.
+///
+/// Two kinds of headers are recognized:
+/// - [`x-request-deadline`][`RequestDeadline::REQUEST_DEADLINE_HEADER`],
+///   holding the absolute deadline as a Unix timestamp in milliseconds;
+/// - [`grpc-timeout`][`RequestDeadline::GRPC_TIMEOUT_HEADER`], holding the
+///   relative timeout in the [gRPC format][1] (like `250m` or `3S`).
+///
+/// The remaining budget is installed as the [`Limits::timeout()`] of the
+/// operation via [`RequestDeadline::limits()`].
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use juniper::{http::RequestDeadline, Limits};
+///
+/// let deadline = RequestDeadline::from_headers(|name| match name {
+///     RequestDeadline::GRPC_TIMEOUT_HEADER => Some("250m"),
+///     _ => None,
+/// })
+/// .unwrap();
+///
+/// assert_eq!(deadline.remaining(), Duration::from_millis(250));
+/// assert_eq!(
+///     deadline.limits(Limits::new().timeout(Duration::from_secs(1))),
+///     Limits::new().timeout(Duration::from_millis(250)),
+/// );
+/// ```
+///
+/// [1]: https://github.com/grpc/grpc/blob/master/doc/PROTOCOL-HTTP2.md#requests
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RequestDeadline {
+    remaining: Duration,
+}
+
+impl RequestDeadline {
+    /// Name of the header holding the absolute deadline of a request as a Unix
+    /// timestamp in milliseconds.
+    pub const REQUEST_DEADLINE_HEADER: &'static str = "x-request-deadline";
+
+    /// Name of the header holding the relative timeout of a request in the
+    /// [gRPC format][1].
+    ///
+    /// [1]: https://github.com/grpc/grpc/blob/master/doc/PROTOCOL-HTTP2.md#requests
+    pub const GRPC_TIMEOUT_HEADER: &'static str = "grpc-timeout";
+
+    /// Creates a new [`RequestDeadline`] with the given `remaining` budget.
+    pub fn new(remaining: Duration) -> Self {
+        Self { remaining }
+    }
+
+    /// Extracts the [`RequestDeadline`] from the request headers, looked up
+    /// by their lowercase names via the provided `header` function.
+    ///
+    /// If both headers are present, the tighter deadline wins. Malformed
+    /// headers are ignored.
+    pub fn from_headers<'h>(header: impl Fn(&str) -> Option<&'h str>) -> Option<Self> {
+        let deadline = header(Self::REQUEST_DEADLINE_HEADER).and_then(Self::parse_request_deadline);
+        let timeout = header(Self::GRPC_TIMEOUT_HEADER).and_then(Self::parse_grpc_timeout);
+        match (deadline, timeout) {
+            (Some(a), Some(b)) => Some(if a.remaining <= b.remaining { a } else { b }),
+            (a, b) => a.or(b),
+        }
+    }
+
+    /// Parses the value of an `x-request-deadline` header, being a Unix
+    /// timestamp in milliseconds.
+    ///
+    /// Deadlines in the past result in zero remaining budget.
+    pub fn parse_request_deadline(value: &str) -> Option<Self> {
+        let deadline = UNIX_EPOCH.checked_add(Duration::from_millis(value.trim().parse().ok()?))?;
+        let remaining = deadline
+            .duration_since(SystemTime::now())
+            .unwrap_or_default();
+        Some(Self::new(remaining))
+    }
+
+    /// Parses the value of a `grpc-timeout` header, being at most 8 digits
+    /// followed by a unit: `H`ours, `M`inutes, `S`econds, `m`illiseconds,
+    /// `u`microseconds or `n`anoseconds.
+    pub fn parse_grpc_timeout(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if value.len() < 2 || value.len() > 9 {
+            return None;
+        }
+        let (amount, unit) = value.split_at(value.len() - 1);
+        if !amount.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let amount = amount.parse::<u64>().ok()?;
+        let remaining = match unit {
+            "H" => Duration::from_secs(amount * 60 * 60),
+            "M" => Duration::from_secs(amount * 60),
+            "S" => Duration::from_secs(amount),
+            "m" => Duration::from_millis(amount),
+            "u" => Duration::from_micros(amount),
+            "n" => Duration::from_nanos(amount),
+            _ => return None,
+        };
+        Some(Self::new(remaining))
+    }
+
+    /// Returns the remaining time budget of the request.
+    pub fn remaining(&self) -> Duration {
+        self.remaining
+    }
+
+    /// Returns the given `limits` with their [`Limits::timeout()`] tightened
+    /// to the remaining budget of the request.
+    pub fn limits(&self, limits: Limits) -> Limits {
+        limits.timeout_at_most(self.remaining)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use super::RequestDeadline;
+
+    #[test]
+    fn parses_grpc_timeout() {
+        for (value, expected) in [
+            ("1H", Duration::from_secs(3600)),
+            ("2M", Duration::from_secs(120)),
+            ("3S", Duration::from_secs(3)),
+            ("250m", Duration::from_millis(250)),
+            ("99999999u", Duration::from_micros(99_999_999)),
+            (" 5n ", Duration::from_nanos(5)),
+        ] {
+            assert_eq!(
+                RequestDeadline::parse_grpc_timeout(value).map(|d| d.remaining()),
+                Some(expected),
+                "value: {}",
+                value,
+            );
+        }
+        for value in ["", "m", "10", "10s", "-1S", "+1S", "123456789S"] {
+            assert_eq!(
+                RequestDeadline::parse_grpc_timeout(value),
+                None,
+                "value: {}",
+                value,
+            );
+        }
+    }
+
+    #[test]
+    fn parses_request_deadline() {
+        let in_a_minute = SystemTime::now() + Duration::from_secs(60);
+        let millis = in_a_minute.duration_since(UNIX_EPOCH).unwrap().as_millis();
+
+        let remaining = RequestDeadline::parse_request_deadline(&millis.to_string())
+            .unwrap()
+            .remaining();
+        assert!(remaining > Duration::from_secs(55), "{:?}", remaining);
+        assert!(remaining <= Duration::from_secs(60), "{:?}", remaining);
+
+        assert_eq!(
+            RequestDeadline::parse_request_deadline("0"),
+            Some(RequestDeadline::new(Duration::ZERO)),
+        );
+        assert_eq!(RequestDeadline::parse_request_deadline("soon"), None);
+    }
+
+    #[test]
+    fn picks_tighter_deadline_from_headers() {
+        let far = (SystemTime::now() + Duration::from_secs(3600))
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis()
+            .to_string();
+
+        let deadline = RequestDeadline::from_headers(|name| match name {
+            RequestDeadline::REQUEST_DEADLINE_HEADER => Some(far.as_str()),
+            RequestDeadline::GRPC_TIMEOUT_HEADER => Some("1S"),
+            _ => None,
+        });
+        assert_eq!(deadline, Some(RequestDeadline::new(Duration::from_secs(1))));
+
+        let deadline = RequestDeadline::from_headers(|name| match name {
+            RequestDeadline::GRPC_TIMEOUT_HEADER => Some("bogus"),
+            _ => None,
+        });
+        assert_eq!(deadline, None);
+    }
+}
@@ -0,0 +1,93 @@
+//! Entity tags of GraphQL responses.
+
+use std::{fmt, hash::Hasher as _};
+
+use fnv::FnvHasher;
+
+/// [Entity tag][1] of a serialized GraphQL response, allowing CDNs and
+/// browsers to cache the responses of idempotent `GET` requests.
+///
+/// It's a strong validator computed over the exact bytes of the response body,
+/// so it's stable across processes and server restarts, and is [`Display`]ed
+/// as a quoted string ready to be used as an `ETag` header value.
+///
+/// ```
+/// use juniper::http::ETag;
+///
+/// let etag = ETag::of(br#"{"data":{"hero":{"name":"R2-D2"}}}"#);
+///
+/// assert!(etag.matches(&format!("W/{}", etag)));
+/// assert!(etag.matches("*"));
+/// assert!(!etag.matches(r#""0123456789abcdef""#));
+/// ```
+///
+/// [`Display`]: fmt::Display
+/// [1]: https://httpwg.org/specs/rfc9110.html#field.etag
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ETag(u64);
+
+impl ETag {
+    /// Computes the [`ETag`] of the given serialized response `body`.
+    pub fn of(body: &[u8]) -> Self {
+        let mut hasher = FnvHasher::default();
+        hasher.write(body);
+        Self(hasher.finish())
+    }
+
+    /// Indicates whether this [`ETag`] matches the given value of an
+    /// `If-None-Match` header, so a `304 Not Modified` response should be
+    /// returned instead of the body.
+    ///
+    /// As required for `If-None-Match`, the weak comparison is used, so
+    /// `W/`-prefixed tags match as well.
+    pub fn matches(&self, if_none_match: &str) -> bool {
+        let if_none_match = if_none_match.trim();
+        if if_none_match == "*" {
+            return true;
+        }
+        let this = self.to_string();
+        if_none_match
+            .split(',')
+            .map(|tag| tag.trim())
+            .map(|tag| tag.strip_prefix("W/").unwrap_or(tag))
+            .any(|tag| tag == this)
+    }
+}
+
+impl fmt::Display for ETag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "\"{:016x}\"", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ETag;
+
+    #[test]
+    fn is_stable() {
+        let etag = ETag::of(b"{}");
+
+        assert_eq!(etag, ETag::of(b"{}"));
+        assert_ne!(etag, ETag::of(b"{ }"));
+        assert_eq!(etag.to_string(), "\"08f44b07b5901a25\"");
+    }
+
+    #[test]
+    fn matches_if_none_match() {
+        let etag = ETag::of(b"{}");
+
+        for header in [
+            "*",
+            " * ",
+            "\"08f44b07b5901a25\"",
+            "W/\"08f44b07b5901a25\"",
+            "\"0000000000000000\", W/\"08f44b07b5901a25\"",
+        ] {
+            assert!(etag.matches(header), "not matched: {}", header);
+        }
+        for header in ["", "08f44b07b5901a25", "\"0000000000000000\""] {
+            assert!(!etag.matches(header), "matched: {}", header);
+        }
+    }
+}
@@ -0,0 +1,646 @@
+//! Incremental delivery of GraphQL responses.
+
+use std::pin::Pin;
+
+use futures::{
+    stream::{self, BoxStream},
+    FutureExt as _, StreamExt as _,
+};
+use serde::ser::{Serialize, SerializeMap as _, Serializer};
+
+use crate::{
+    executor::{DeferredPayload, IncrementalPayload, StreamedPayload},
+    value::{DefaultScalarValue, ScalarValue},
+    GraphQLType, GraphQLTypeAsync, RootNode,
+};
+
+use super::{GraphQLRequest, GraphQLResponse};
+
+/// `Content-Type` of the responses delivered incrementally, as the
+/// `multipart/mixed` body made of [`IncrementalPart`]s.
+///
+/// Follows the [`@defer` specification][1] as implemented by the Apollo
+/// clients, so should be sent to the clients accepting it only.
+///
+/// [1]: https://github.com/graphql/graphql-spec/pull/742
+pub const MULTIPART_CONTENT_TYPE: &str = "multipart/mixed; boundary=\"-\"; deferSpec=20220824";
+
+/// Single part of a response to a [`GraphQLRequest`] executed via
+/// [`GraphQLRequest::execute_incremental()`].
+///
+/// Serializes as the JSON object of the initial payload, or of the subsequent
+/// one, having the payload of the deferred fragment or of the streamed list
+/// items in its `incremental` list.
+#[derive(Clone, Debug)]
+pub struct IncrementalPart<S = DefaultScalarValue> {
+    payload: Option<IncrementalPayload<S>>,
+    has_next: bool,
+}
+
+impl<S> IncrementalPart<S> {
+    /// Returns the [`IncrementalPayload`] of this part.
+    ///
+    /// [`None`] for the closing part, sent once no more payloads follow the
+    /// part sent before it, as that's unknown until its operation is executed
+    /// completely.
+    pub fn payload(&self) -> Option<&IncrementalPayload<S>> {
+        self.payload.as_ref()
+    }
+
+    /// Indicates whether more parts follow this one.
+    pub fn has_next(&self) -> bool {
+        self.has_next
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl<S: ScalarValue> IncrementalPart<S> {
+    /// Serializes this part into a chunk of the `multipart/mixed` response
+    /// body, closing the body if it's the last part.
+    ///
+    /// See [`MULTIPART_CONTENT_TYPE`] for the `Content-Type` of the response.
+    pub fn to_multipart_chunk(&self) -> String {
+        let json = serde_json::to_string(self).expect("`IncrementalPart` is serializable");
+        let mut chunk = format!(
+            "\r\n---\r\nContent-Type: application/json; charset=utf-8\r\n\r\n{}",
+            json,
+        );
+        if !self.has_next {
+            chunk.push_str("\r\n-----\r\n");
+        }
+        chunk
+    }
+}
+
+impl<S: ScalarValue> Serialize for IncrementalPart<S> {
+    fn serialize<Ser: Serializer>(&self, ser: Ser) -> Result<Ser::Ok, Ser::Error> {
+        let mut map = ser.serialize_map(None)?;
+
+        match &self.payload {
+            Some(IncrementalPayload::Initial(output)) => {
+                map.serialize_entry("data", &output.data)?;
+                if !output.errors.is_empty() {
+                    map.serialize_entry("errors", &output.errors)?;
+                }
+            }
+            Some(IncrementalPayload::Deferred(payload)) => {
+                map.serialize_entry("incremental", &[Deferred(payload)])?;
+            }
+            Some(IncrementalPayload::Streamed(payload)) => {
+                map.serialize_entry("incremental", &[Streamed(payload)])?;
+            }
+            None => {}
+        }
+        map.serialize_entry("hasNext", &self.has_next)?;
+
+        map.end()
+    }
+}
+
+/// Serializable entry of the `incremental` list of an [`IncrementalPart`] with
+/// a deferred fragment.
+struct Deferred<'p, S>(&'p DeferredPayload<S>);
+
+impl<'p, S: ScalarValue> Serialize for Deferred<'p, S> {
+    fn serialize<Ser: Serializer>(&self, ser: Ser) -> Result<Ser::Ok, Ser::Error> {
+        let mut map = ser.serialize_map(None)?;
+
+        map.serialize_entry("data", &self.0.data)?;
+        map.serialize_entry("path", &self.0.path)?;
+        if let Some(label) = &self.0.label {
+            map.serialize_entry("label", label)?;
+        }
+        if !self.0.errors.is_empty() {
+            map.serialize_entry("errors", &self.0.errors)?;
+        }
+
+        map.end()
+    }
+}
+
+/// Serializable entry of the `incremental` list of an [`IncrementalPart`] with
+/// streamed list items.
+struct Streamed<'p, S>(&'p StreamedPayload<S>);
+
+impl<'p, S: ScalarValue> Serialize for Streamed<'p, S> {
+    fn serialize<Ser: Serializer>(&self, ser: Ser) -> Result<Ser::Ok, Ser::Error> {
+        let mut map = ser.serialize_map(None)?;
+
+        map.serialize_entry("items", &self.0.items)?;
+        map.serialize_entry("path", &self.0.path)?;
+        if let Some(label) = &self.0.label {
+            map.serialize_entry("label", label)?;
+        }
+        if !self.0.errors.is_empty() {
+            map.serialize_entry("errors", &self.0.errors)?;
+        }
+
+        map.end()
+    }
+}
+
+impl<S> GraphQLRequest<S>
+where
+    S: ScalarValue + Send + Sync,
+{
+    /// Executes this request using the specified schema and context,
+    /// delivering the fragments marked with the `@defer` directive and the
+    /// list items marked with the `@stream` directive separately from the rest
+    /// of it.
+    ///
+    /// This is a wrapper around the [`execute_incremental()`] function exposed
+    /// at the top level of this crate, returning the response of the failed
+    /// request as an error, to be sent as a regular JSON response.
+    ///
+    /// [`execute_incremental()`]: crate::execute_incremental
+    pub async fn execute_incremental<'a, QueryT, MutationT, SubscriptionT>(
+        &'a self,
+        root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+        context: &'a QueryT::Context,
+    ) -> Result<BoxStream<'a, IncrementalPart<S>>, GraphQLResponse<'a, S>>
+    where
+        QueryT: GraphQLTypeAsync<S>,
+        QueryT::TypeInfo: Sync,
+        QueryT::Context: Sync,
+        MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+        MutationT::TypeInfo: Sync,
+        SubscriptionT: GraphQLType<S, Context = QueryT::Context> + Sync,
+        SubscriptionT::TypeInfo: Sync,
+    {
+        let op = self.operation_name.as_deref();
+        let vars = self.variables();
+        let hash = self.persisted_hash();
+        let limits = root_node.schema.limits();
+        let payloads = crate::execute_incremental_persisted(
+            &self.query,
+            hash,
+            op,
+            root_node,
+            vars,
+            context,
+            limits,
+        )
+        .await
+        .map_err(|e| GraphQLResponse::from_result(Err(e)))?;
+
+        // Each part is sent as soon as its payload is resolved, so whether any
+        // more payloads follow it is known in place only if the operation is
+        // executed completely already. Otherwise, the closing part follows.
+        let parts = stream::unfold(Some(payloads.peekable()), |payloads| async move {
+            let mut payloads = payloads?;
+            let payload = payloads.next().await;
+            let has_next = payload.is_some()
+                && !matches!(Pin::new(&mut payloads).peek().now_or_never(), Some(None));
+            let part = IncrementalPart { payload, has_next };
+            Some((part, has_next.then_some(payloads)))
+        });
+        Ok(parts.boxed())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use futures::{channel::oneshot, FutureExt as _, StreamExt as _};
+
+    use crate::{
+        graphql_object, http::GraphQLRequest, Context, DefaultScalarValue, EmptyMutation,
+        EmptySubscription, FieldError, FieldResult, RootNode,
+    };
+
+    use super::IncrementalPart;
+
+    struct Hero;
+
+    #[graphql_object]
+    impl Hero {
+        fn name() -> &'static str {
+            "R2-D2"
+        }
+
+        fn friends() -> FieldResult<Option<Vec<String>>> {
+            Err(FieldError::from("Friends are unavailable"))
+        }
+    }
+
+    struct Droid(Option<&'static str>);
+
+    #[graphql_object]
+    impl Droid {
+        fn name(&self) -> FieldResult<Option<&'static str>> {
+            self.0
+                .map(Some)
+                .ok_or_else(|| FieldError::from("Droid is unavailable"))
+        }
+    }
+
+    struct Query;
+
+    #[graphql_object]
+    impl Query {
+        fn hero() -> Hero {
+            Hero
+        }
+
+        fn heroes() -> Vec<Hero> {
+            vec![Hero]
+        }
+
+        fn episodes() -> Vec<i32> {
+            vec![4, 5, 6]
+        }
+
+        fn droids() -> Vec<Droid> {
+            vec![Droid(Some("R2-D2")), Droid(None), Droid(Some("C-3PO"))]
+        }
+    }
+
+    type Schema = RootNode<'static, Query, EmptyMutation, EmptySubscription>;
+
+    /// Gate the resolvers of [`GatedQuery`] wait for to be opened.
+    struct Gate(Mutex<Option<oneshot::Receiver<()>>>);
+
+    impl Context for Gate {}
+
+    impl Gate {
+        async fn pass(&self) {
+            let opened = self.0.lock().unwrap().take().expect("Gate is passed once");
+            opened.await.unwrap();
+        }
+    }
+
+    struct GatedHero;
+
+    #[graphql_object(context = Gate)]
+    impl GatedHero {
+        fn name() -> &'static str {
+            "R2-D2"
+        }
+
+        async fn friends(context: &Gate) -> Vec<&'static str> {
+            context.pass().await;
+            vec!["Luke"]
+        }
+    }
+
+    struct GatedEpisode(i32);
+
+    #[graphql_object(context = Gate)]
+    impl GatedEpisode {
+        async fn number(&self, context: &Gate) -> i32 {
+            if self.0 == 6 {
+                context.pass().await;
+            }
+            self.0
+        }
+    }
+
+    struct GatedQuery;
+
+    #[graphql_object(context = Gate)]
+    impl GatedQuery {
+        fn hero() -> GatedHero {
+            GatedHero
+        }
+
+        fn episodes() -> Vec<GatedEpisode> {
+            vec![GatedEpisode(4), GatedEpisode(5), GatedEpisode(6)]
+        }
+    }
+
+    fn gated_schema() -> RootNode<'static, GatedQuery, EmptyMutation<Gate>, EmptySubscription<Gate>>
+    {
+        RootNode::new(GatedQuery, EmptyMutation::new(), EmptySubscription::new())
+            .enable_defer()
+            .enable_stream()
+    }
+
+    fn schema() -> Schema {
+        Schema::new(Query, EmptyMutation::new(), EmptySubscription::new())
+            .enable_defer()
+            .enable_stream()
+    }
+
+    fn request(query: &str) -> GraphQLRequest {
+        GraphQLRequest::new(query.into(), None, None)
+    }
+
+    async fn parts(
+        query: &str,
+    ) -> Result<Vec<IncrementalPart<DefaultScalarValue>>, serde_json::Value> {
+        let schema = schema();
+        let req = request(query);
+        let parts = match req.execute_incremental(&schema, &()).await {
+            Ok(parts) => Ok(parts.collect().await),
+            Err(res) => Err(serde_json::to_value(&res).unwrap()),
+        };
+        parts
+    }
+
+    fn json(part: &IncrementalPart<DefaultScalarValue>) -> serde_json::Value {
+        serde_json::to_value(part).unwrap()
+    }
+
+    #[tokio::test]
+    async fn delivers_deferred_fragments_separately() {
+        let parts = parts("{ hero { ... @defer(label: \"friends\") { friends } name } }")
+            .await
+            .unwrap();
+
+        assert_eq!(parts.len(), 2);
+        assert_eq!(
+            json(&parts[0]),
+            serde_json::json!({"data": {"hero": {"name": "R2-D2"}}, "hasNext": true}),
+        );
+        assert_eq!(
+            json(&parts[1]),
+            serde_json::json!({
+                "incremental": [{
+                    "data": {"friends": null},
+                    "path": ["hero"],
+                    "label": "friends",
+                    "errors": [{
+                        "message": "Friends are unavailable",
+                        "locations": [{"line": 1, "column": 41}],
+                        "path": ["hero", "friends"],
+                    }],
+                }],
+                "hasNext": false,
+            }),
+        );
+    }
+
+    #[tokio::test]
+    async fn delivers_nested_deferred_fragments_after_outer_ones() {
+        let parts = parts(
+            "{ heroes { ...F @defer(label: \"outer\") } } \
+             fragment F on Hero { name ... @defer(label: \"inner\") { name } }",
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            parts.iter().map(json).collect::<Vec<_>>(),
+            [
+                serde_json::json!({"data": {"heroes": [{}]}, "hasNext": true}),
+                serde_json::json!({
+                    "incremental": [{
+                        "data": {"name": "R2-D2"},
+                        "path": ["heroes", 0],
+                        "label": "outer",
+                    }],
+                    "hasNext": true,
+                }),
+                serde_json::json!({
+                    "incremental": [{
+                        "data": {"name": "R2-D2"},
+                        "path": ["heroes", 0],
+                        "label": "inner",
+                    }],
+                    "hasNext": false,
+                }),
+            ],
+        );
+    }
+
+    #[tokio::test]
+    async fn resolves_fragments_in_place_unless_deferred() {
+        let parts = parts("{ hero { ... @defer(if: false) { name } } }")
+            .await
+            .unwrap();
+
+        assert_eq!(parts.len(), 1);
+        assert_eq!(
+            json(&parts[0]),
+            serde_json::json!({"data": {"hero": {"name": "R2-D2"}}, "hasNext": false}),
+        );
+    }
+
+    #[tokio::test]
+    async fn ignores_defer_outside_incremental_delivery() {
+        let schema = schema();
+        let req = request("{ hero { ...F @defer } } fragment F on Hero { name }");
+        let res = req.execute(&schema, &()).await;
+
+        assert_eq!(
+            serde_json::to_value(&res).unwrap(),
+            serde_json::json!({"data": {"hero": {"name": "R2-D2"}}}),
+        );
+    }
+
+    #[tokio::test]
+    async fn responds_with_request_errors() {
+        let res = parts("{ hero { unknown } }").await.unwrap_err();
+
+        assert!(res.get("data").is_none());
+        assert_eq!(
+            res["errors"][0]["message"],
+            "Unknown field \"unknown\" on type \"Hero\"",
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_defer_unless_enabled() {
+        let schema = Schema::new(Query, EmptyMutation::new(), EmptySubscription::new());
+        let req = request("{ hero { ... @defer { name } } }");
+
+        assert!(req.execute_incremental(&schema, &()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn streams_list_items_after_initial_count() {
+        let parts = parts("{ episodes @stream(initialCount: 1, label: \"episodes\") }")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            parts.iter().map(json).collect::<Vec<_>>(),
+            [
+                serde_json::json!({"data": {"episodes": [4]}, "hasNext": true}),
+                serde_json::json!({
+                    "incremental": [{
+                        "items": [5],
+                        "path": ["episodes", 1],
+                        "label": "episodes",
+                    }],
+                    "hasNext": true,
+                }),
+                serde_json::json!({
+                    "incremental": [{
+                        "items": [6],
+                        "path": ["episodes", 2],
+                        "label": "episodes",
+                    }],
+                    "hasNext": false,
+                }),
+            ],
+        );
+    }
+
+    #[tokio::test]
+    async fn delivers_item_errors_with_streamed_items() {
+        let parts = parts("{ droids @stream(initialCount: 1) { name } }")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            parts.iter().map(json).collect::<Vec<_>>(),
+            [
+                serde_json::json!({"data": {"droids": [{"name": "R2-D2"}]}, "hasNext": true}),
+                serde_json::json!({
+                    "incremental": [{
+                        "items": [{"name": null}],
+                        "path": ["droids", 1],
+                        "errors": [{
+                            "message": "Droid is unavailable",
+                            "locations": [{"line": 1, "column": 37}],
+                            "path": ["droids", 1, "name"],
+                        }],
+                    }],
+                    "hasNext": true,
+                }),
+                serde_json::json!({
+                    "incremental": [{"items": [{"name": "C-3PO"}], "path": ["droids", 2]}],
+                    "hasNext": false,
+                }),
+            ],
+        );
+    }
+
+    #[tokio::test]
+    async fn resolves_lists_in_place_unless_streamed() {
+        let parts =
+            parts("{ episodes @stream(if: false) short: episodes @stream(initialCount: 5) }")
+                .await
+                .unwrap();
+
+        assert_eq!(parts.len(), 1);
+        assert_eq!(
+            json(&parts[0]),
+            serde_json::json!({
+                "data": {"episodes": [4, 5, 6], "short": [4, 5, 6]},
+                "hasNext": false,
+            }),
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_stream_unless_enabled() {
+        let schema =
+            Schema::new(Query, EmptyMutation::new(), EmptySubscription::new()).enable_defer();
+        let req = request("{ episodes @stream }");
+
+        assert!(req.execute_incremental(&schema, &()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn delivers_initial_payload_before_deferred_fragments_resolve() {
+        let (open, opened) = oneshot::channel();
+        let gate = Gate(Mutex::new(Some(opened)));
+        let schema = gated_schema();
+        let req = request("{ hero { name ... @defer { friends } } }");
+        let mut parts = req.execute_incremental(&schema, &gate).await.unwrap();
+
+        assert_eq!(
+            json(&parts.next().await.unwrap()),
+            serde_json::json!({"data": {"hero": {"name": "R2-D2"}}, "hasNext": true}),
+        );
+        assert!(parts.next().now_or_never().is_none());
+
+        open.send(()).unwrap();
+        assert_eq!(
+            json(&parts.next().await.unwrap()),
+            serde_json::json!({
+                "incremental": [{"data": {"friends": ["Luke"]}, "path": ["hero"]}],
+                "hasNext": false,
+            }),
+        );
+        assert!(parts.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn streams_list_items_as_they_resolve() {
+        let (open, opened) = oneshot::channel();
+        let gate = Gate(Mutex::new(Some(opened)));
+        let schema = gated_schema();
+        let req = request("{ episodes @stream(initialCount: 1) { number } }");
+        let mut parts = req.execute_incremental(&schema, &gate).await.unwrap();
+
+        assert_eq!(
+            json(&parts.next().await.unwrap()),
+            serde_json::json!({"data": {"episodes": [{"number": 4}]}, "hasNext": true}),
+        );
+        assert_eq!(
+            json(&parts.next().await.unwrap()),
+            serde_json::json!({
+                "incremental": [{"items": [{"number": 5}], "path": ["episodes", 1]}],
+                "hasNext": true,
+            }),
+        );
+        assert!(parts.next().now_or_never().is_none());
+
+        open.send(()).unwrap();
+        assert_eq!(
+            json(&parts.next().await.unwrap()),
+            serde_json::json!({
+                "incremental": [{"items": [{"number": 6}], "path": ["episodes", 2]}],
+                "hasNext": false,
+            }),
+        );
+        assert!(parts.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn rejects_negative_initial_count() {
+        let parts = parts("{ hero { name } episodes @stream(initialCount: -1) }")
+            .await
+            .unwrap();
+
+        assert_eq!(parts.len(), 1);
+        assert_eq!(
+            json(&parts[0]),
+            serde_json::json!({
+                "data": null,
+                "errors": [{
+                    "message": "`initialCount` argument of `@stream` directive must be \
+                                non-negative, got: -1",
+                    "locations": [{"line": 1, "column": 17}],
+                    "path": ["episodes"],
+                }],
+                "hasNext": false,
+            }),
+        );
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn closes_multipart_body_with_last_part() {
+        let part = |has_next| IncrementalPart {
+            payload: Some(crate::IncrementalPayload::Initial(
+                crate::ExecutionOutput::from_data(crate::Value::<crate::DefaultScalarValue>::null()),
+            )),
+            has_next,
+        };
+        let closing = IncrementalPart::<crate::DefaultScalarValue> {
+            payload: None,
+            has_next: false,
+        };
+
+        assert_eq!(
+            part(true).to_multipart_chunk(),
+            "\r\n---\r\nContent-Type: application/json; charset=utf-8\r\n\r\n\
+             {\"data\":null,\"hasNext\":true}",
+        );
+        assert_eq!(
+            part(false).to_multipart_chunk(),
+            "\r\n---\r\nContent-Type: application/json; charset=utf-8\r\n\r\n\
+             {\"data\":null,\"hasNext\":false}\r\n-----\r\n",
+        );
+        assert_eq!(
+            closing.to_multipart_chunk(),
+            "\r\n---\r\nContent-Type: application/json; charset=utf-8\r\n\r\n\
+             {\"hasNext\":false}\r\n-----\r\n",
+        );
+    }
+}
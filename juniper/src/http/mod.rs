@@ -1,8 +1,19 @@
 //! Utilities for building HTTP endpoints in a library-agnostic manner
 
+#[cfg(feature = "persisted-queries")]
+#[cfg_attr(docsrs, doc(cfg(feature = "persisted-queries")))]
+pub mod apq;
+mod deadline;
+mod etag;
 pub mod graphiql;
+mod incremental;
+#[cfg(feature = "persisted-operations")]
+#[cfg_attr(docsrs, doc(cfg(feature = "persisted-operations")))]
+pub mod persisted;
 pub mod playground;
+mod stats;
 
+use indexmap::IndexMap;
 use serde::{
     de,
     ser::{self, SerializeMap},
@@ -11,10 +22,18 @@ use serde::{
 
 use crate::{
     ast::InputValue,
-    executor::{ExecutionError, ValuesStream},
+    executor::{get_operation, ExecutionError, PiiFields, ValuesStream},
+    parser::parse_document_source,
     value::{DefaultScalarValue, ScalarValue},
-    FieldError, GraphQLError, GraphQLSubscriptionType, GraphQLType, GraphQLTypeAsync, RootNode,
-    Value, Variables,
+    FieldError, GraphQLSubscriptionType, GraphQLType, GraphQLTypeAsync, Limits, RequestError,
+    RootNode, Value, Variables,
+};
+
+pub use self::{
+    deadline::RequestDeadline,
+    etag::ETag,
+    incremental::{IncrementalPart, MULTIPART_CONTENT_TYPE},
+    stats::ResponseStats,
 };
 
 /// The expected structure of the decoded JSON document for either POST or GET requests.
@@ -30,6 +49,12 @@ where
     S: ScalarValue,
 {
     /// GraphQL query representing this request.
+    ///
+    /// May be omitted (deserialized as an empty string) by requests referring
+    /// to a [persisted query][1] by its hash only.
+    ///
+    /// [1]: RequestExtensions::persisted_query
+    #[serde(default)]
     pub query: String,
 
     /// Optional name of the operation associated with this request.
@@ -42,6 +67,51 @@ where
         serialize = "InputValue<S>: Serialize",
     ))]
     pub variables: Option<InputValue<S>>,
+
+    /// Optional extensions of this request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extensions: Option<RequestExtensions>,
+}
+
+/// Extensions of a [`GraphQLRequest`], recognized by `juniper`.
+///
+/// The rest of the extensions sent by clients are ignored.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct RequestExtensions {
+    /// [Automatic persisted query][1] the request refers to.
+    ///
+    /// [1]: https://www.apollographql.com/docs/apollo-server/performance/apq
+    #[serde(
+        rename = "persistedQuery",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub persisted_query: Option<PersistedQuery>,
+}
+
+/// Reference to an [automatic persisted query][1] by the SHA-256 hash of its
+/// text.
+///
+/// [1]: https://www.apollographql.com/docs/apollo-server/performance/apq
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct PersistedQuery {
+    /// Version of the protocol, being `1` at the moment.
+    pub version: u32,
+
+    /// Hex-encoded SHA-256 hash of the query text.
+    #[serde(rename = "sha256Hash")]
+    pub sha256_hash: String,
+}
+
+impl PersistedQuery {
+    /// Creates a new [`PersistedQuery`] of the current protocol version,
+    /// referring to the query with the given hex-encoded SHA-256 hash.
+    pub fn new(sha256_hash: impl Into<String>) -> Self {
+        Self {
+            version: 1,
+            sha256_hash: sha256_hash.into(),
+        }
+    }
 }
 
 impl<S> GraphQLRequest<S>
@@ -79,9 +149,50 @@ where
             query,
             operation_name,
             variables,
+            extensions: None,
         }
     }
 
+    /// Returns the [`PersistedQuery`] this request refers to, if any.
+    pub fn persisted_query(&self) -> Option<&PersistedQuery> {
+        self.extensions.as_ref()?.persisted_query.as_ref()
+    }
+
+    /// Returns the SHA-256 hash of the query text of this request, to look up
+    /// its already parsed document by, if it refers to a [`PersistedQuery`].
+    fn persisted_hash(&self) -> Option<&str> {
+        self.persisted_query()
+            .filter(|q| q.version == 1)
+            .map(|q| q.sha256_hash.as_str())
+    }
+
+    /// Returns the [`PiiFields`] selected by this request in the specified
+    /// schema, so they may be [stripped][1] from its response before logging
+    /// it.
+    ///
+    /// Returns no fields if the request cannot be parsed, or its operation
+    /// cannot be determined.
+    ///
+    /// [1]: PiiFields::strip
+    pub fn pii_fields<QueryT, MutationT, SubscriptionT>(
+        &self,
+        root_node: &RootNode<QueryT, MutationT, SubscriptionT, S>,
+    ) -> PiiFields
+    where
+        QueryT: GraphQLType<S>,
+        MutationT: GraphQLType<S, Context = QueryT::Context>,
+        SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+    {
+        parse_document_source(&self.query, &root_node.schema)
+            .ok()
+            .and_then(|document| {
+                get_operation(&document, self.operation_name.as_deref())
+                    .ok()
+                    .map(|op| PiiFields::collect(&root_node.schema, &document, op))
+            })
+            .unwrap_or_default()
+    }
+
     /// Execute a GraphQL request synchronously using the specified schema and context
     ///
     /// This is a simple wrapper around the `execute_sync` function exposed at the
@@ -97,12 +208,34 @@ where
         MutationT: GraphQLType<S, Context = QueryT::Context>,
         SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
     {
-        GraphQLResponse(crate::execute_sync(
+        self.execute_sync_with_limits(root_node, context, root_node.schema.limits())
+    }
+
+    /// Execute a GraphQL request synchronously using the specified schema and
+    /// context, overriding the default [`Limits`] of the schema.
+    ///
+    /// This is a simple wrapper around the `execute_sync_with_limits` function
+    /// exposed at the top level of this crate.
+    pub fn execute_sync_with_limits<'a, QueryT, MutationT, SubscriptionT>(
+        &'a self,
+        root_node: &'a RootNode<QueryT, MutationT, SubscriptionT, S>,
+        context: &QueryT::Context,
+        limits: &Limits,
+    ) -> GraphQLResponse<'a, S>
+    where
+        S: ScalarValue,
+        QueryT: GraphQLType<S>,
+        MutationT: GraphQLType<S, Context = QueryT::Context>,
+        SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+    {
+        GraphQLResponse::from_result(crate::execute_sync_persisted(
             &self.query,
+            self.persisted_hash(),
             self.operation_name.as_deref(),
             root_node,
             &self.variables(),
             context,
+            limits,
         ))
     }
 
@@ -115,6 +248,31 @@ where
         root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
         context: &'a QueryT::Context,
     ) -> GraphQLResponse<'a, S>
+    where
+        QueryT: GraphQLTypeAsync<S>,
+        QueryT::TypeInfo: Sync,
+        QueryT::Context: Sync,
+        MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+        MutationT::TypeInfo: Sync,
+        SubscriptionT: GraphQLType<S, Context = QueryT::Context> + Sync,
+        SubscriptionT::TypeInfo: Sync,
+        S: ScalarValue + Send + Sync,
+    {
+        self.execute_with_limits(root_node, context, root_node.schema.limits())
+            .await
+    }
+
+    /// Execute a GraphQL request using the specified schema and context,
+    /// overriding the default [`Limits`] of the schema.
+    ///
+    /// This is a simple wrapper around the `execute_with_limits` function
+    /// exposed at the top level of this crate.
+    pub async fn execute_with_limits<'a, QueryT, MutationT, SubscriptionT>(
+        &'a self,
+        root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+        context: &'a QueryT::Context,
+        limits: &Limits,
+    ) -> GraphQLResponse<'a, S>
     where
         QueryT: GraphQLTypeAsync<S>,
         QueryT::TypeInfo: Sync,
@@ -127,8 +285,10 @@ where
     {
         let op = self.operation_name.as_deref();
         let vars = &self.variables();
-        let res = crate::execute(&self.query, op, root_node, vars, context).await;
-        GraphQLResponse(res)
+        let hash = self.persisted_hash();
+        let res =
+            crate::execute_persisted(&self.query, hash, op, root_node, vars, context, limits).await;
+        GraphQLResponse::from_result(res)
     }
 }
 
@@ -140,7 +300,7 @@ pub async fn resolve_into_stream<'req, 'rn, 'ctx, 'a, QueryT, MutationT, Subscri
     req: &'req GraphQLRequest<S>,
     root_node: &'rn RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
     context: &'ctx QueryT::Context,
-) -> Result<(Value<ValuesStream<'a, S>>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+) -> Result<(Value<ValuesStream<'a, S>>, Vec<ExecutionError<S>>), RequestError<'a>>
 where
     'req: 'a,
     'rn: 'a,
@@ -166,22 +326,26 @@ where
 /// to JSON and send it over the wire. Use the `is_ok` method to determine
 /// whether to send a 200 or 400 HTTP status code.
 #[derive(Debug)]
-pub struct GraphQLResponse<'a, S = DefaultScalarValue>(
-    Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>,
-);
+pub struct GraphQLResponse<'a, S = DefaultScalarValue> {
+    result: Result<(Value<S>, Vec<ExecutionError<S>>), RequestError<'a>>,
+    stats_extension: bool,
+}
 
 impl<'a, S> GraphQLResponse<'a, S>
 where
     S: ScalarValue,
 {
     /// Constructs new `GraphQLResponse` using the given result
-    pub fn from_result(r: Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>) -> Self {
-        Self(r)
+    pub fn from_result(r: Result<(Value<S>, Vec<ExecutionError<S>>), RequestError<'a>>) -> Self {
+        Self {
+            result: r,
+            stats_extension: false,
+        }
     }
 
     /// Constructs an error response outside of the normal execution flow
     pub fn error(error: FieldError<S>) -> Self {
-        GraphQLResponse(Ok((Value::null(), vec![ExecutionError::at_origin(error)])))
+        Self::from_result(Ok((Value::null(), vec![ExecutionError::at_origin(error)])))
     }
 
     /// Was the request successful or not?
@@ -189,7 +353,53 @@ where
     /// Note that there still might be errors in the response even though it's
     /// considered OK. This is by design in GraphQL.
     pub fn is_ok(&self) -> bool {
-        self.0.is_ok()
+        self.result.is_ok()
+    }
+
+    /// Indicates whether this response has any errors, either preventing the
+    /// request from being executed or happened during its execution.
+    ///
+    /// Responses with errors shouldn't be cached, as the errors may be
+    /// transient.
+    pub fn has_errors(&self) -> bool {
+        !self.execution_errors().is_empty() || !self.is_ok()
+    }
+
+    /// Returns the [`RequestError`] which prevented the request from being
+    /// executed, if any.
+    pub fn request_error(&self) -> Option<&RequestError<'a>> {
+        self.result.as_ref().err()
+    }
+
+    /// Returns the [`ExecutionError`]s happened during the execution phase of
+    /// the request, being returned along with its (partial) data.
+    ///
+    /// Empty if the request wasn't executed at all (see
+    /// [`GraphQLResponse::request_error()`]).
+    pub fn execution_errors(&self) -> &[ExecutionError<S>] {
+        self.result.as_ref().map_or(&[], |(_, errs)| errs)
+    }
+
+    /// Computes the [`ResponseStats`] of the data of this response, to be
+    /// reported into metrics, for example.
+    ///
+    /// Returns [`None`] if the request wasn't successful.
+    pub fn stats(&self) -> Option<ResponseStats> {
+        self.result
+            .as_ref()
+            .ok()
+            .map(|(data, _)| ResponseStats::of(data))
+    }
+
+    /// Makes this response to include its [`ResponseStats`] in its
+    /// `extensions` under the `stats` key, once serialized.
+    ///
+    /// The statistics are computed on each serialization, so the data is
+    /// serialized twice if the `serde_json` feature is enabled.
+    #[must_use]
+    pub fn with_stats_extension(mut self) -> Self {
+        self.stats_extension = true;
+        self
     }
 }
 
@@ -198,13 +408,13 @@ where
     T: Serialize + ScalarValue,
     Value<T>: Serialize,
     ExecutionError<T>: Serialize,
-    GraphQLError<'a>: Serialize,
+    RequestError<'a>: Serialize,
 {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: ser::Serializer,
     {
-        match self.0 {
+        match self.result {
             Ok((ref res, ref err)) => {
                 let mut map = serializer.serialize_map(None)?;
 
@@ -216,6 +426,13 @@ where
                     map.serialize_value(err)?;
                 }
 
+                if self.stats_extension {
+                    let mut extensions = IndexMap::with_capacity(1);
+                    extensions.insert("stats", ResponseStats::of(res));
+                    map.serialize_key("extensions")?;
+                    map.serialize_value(&extensions)?;
+                }
+
                 map.end()
             }
             Err(ref err) => {
@@ -273,18 +490,38 @@ where
         root_node: &'a RootNode<QueryT, MutationT, SubscriptionT, S>,
         context: &QueryT::Context,
     ) -> GraphQLBatchResponse<'a, S>
+    where
+        QueryT: GraphQLType<S>,
+        MutationT: GraphQLType<S, Context = QueryT::Context>,
+        SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+    {
+        self.execute_sync_with_limits(root_node, context, root_node.schema.limits())
+    }
+
+    /// Execute a GraphQL batch request synchronously using the specified schema
+    /// and context, overriding the default [`Limits`] of the schema for each
+    /// request in the batch.
+    ///
+    /// This is a simple wrapper around the `execute_sync_with_limits` function
+    /// exposed in GraphQLRequest.
+    pub fn execute_sync_with_limits<'a, QueryT, MutationT, SubscriptionT>(
+        &'a self,
+        root_node: &'a RootNode<QueryT, MutationT, SubscriptionT, S>,
+        context: &QueryT::Context,
+        limits: &Limits,
+    ) -> GraphQLBatchResponse<'a, S>
     where
         QueryT: GraphQLType<S>,
         MutationT: GraphQLType<S, Context = QueryT::Context>,
         SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
     {
         match *self {
-            Self::Single(ref req) => {
-                GraphQLBatchResponse::Single(req.execute_sync(root_node, context))
-            }
+            Self::Single(ref req) => GraphQLBatchResponse::Single(
+                req.execute_sync_with_limits(root_node, context, limits),
+            ),
             Self::Batch(ref reqs) => GraphQLBatchResponse::Batch(
                 reqs.iter()
-                    .map(|req| req.execute_sync(root_node, context))
+                    .map(|req| req.execute_sync_with_limits(root_node, context, limits))
                     .collect(),
             ),
         }
@@ -299,6 +536,32 @@ where
         root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
         context: &'a QueryT::Context,
     ) -> GraphQLBatchResponse<'a, S>
+    where
+        QueryT: GraphQLTypeAsync<S>,
+        QueryT::TypeInfo: Sync,
+        QueryT::Context: Sync,
+        MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+        MutationT::TypeInfo: Sync,
+        SubscriptionT: GraphQLSubscriptionType<S, Context = QueryT::Context>,
+        SubscriptionT::TypeInfo: Sync,
+        S: Send + Sync,
+    {
+        self.execute_with_limits(root_node, context, root_node.schema.limits())
+            .await
+    }
+
+    /// Executes a GraphQL request using the specified schema and context,
+    /// overriding the default [`Limits`] of the schema for each request in the
+    /// batch.
+    ///
+    /// This is a simple wrapper around the `execute_with_limits` function
+    /// exposed in GraphQLRequest.
+    pub async fn execute_with_limits<'a, QueryT, MutationT, SubscriptionT>(
+        &'a self,
+        root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+        context: &'a QueryT::Context,
+        limits: &Limits,
+    ) -> GraphQLBatchResponse<'a, S>
     where
         QueryT: GraphQLTypeAsync<S>,
         QueryT::TypeInfo: Sync,
@@ -311,12 +574,13 @@ where
     {
         match self {
             Self::Single(req) => {
-                let resp = req.execute(root_node, context).await;
+                let resp = req.execute_with_limits(root_node, context, limits).await;
                 GraphQLBatchResponse::Single(resp)
             }
             Self::Batch(reqs) => {
                 let resps = futures::future::join_all(
-                    reqs.iter().map(|req| req.execute(root_node, context)),
+                    reqs.iter()
+                        .map(|req| req.execute_with_limits(root_node, context, limits)),
                 )
                 .await;
                 GraphQLBatchResponse::Batch(resps)
@@ -362,6 +626,15 @@ where
             Self::Batch(resps) => resps.iter().all(GraphQLResponse::is_ok),
         }
     }
+
+    /// Indicates whether any [`GraphQLResponse`] in this operation
+    /// [has errors][`GraphQLResponse::has_errors()`].
+    pub fn has_errors(&self) -> bool {
+        match self {
+            Self::Single(resp) => resp.has_errors(),
+            Self::Batch(resps) => resps.iter().any(GraphQLResponse::has_errors),
+        }
+    }
 }
 
 #[cfg(feature = "expose-test-schema")]
@@ -753,7 +1026,10 @@ pub mod tests {
                         "locations":[{
                             "line":1,
                             "column":16
-                        }]
+                        }],
+                        "extensions":{
+                            "rule":"ScalarLeafs"
+                        }
                     }]
                 }"#
                 .to_owned(),
@@ -0,0 +1,507 @@
+//! Allowlist of persisted operations, loaded from a manifest and reloaded
+//! without restarting the server.
+//!
+//! ```
+//! # use std::time::Duration;
+//! # use futures::StreamExt as _;
+//! use juniper::http::persisted::{DocumentStore as _, ManifestFile, PersistedOperations};
+//!
+//! # async fn run() -> Result<(), juniper::http::persisted::ManifestError> {
+//! let operations = PersistedOperations::load(ManifestFile::new("operations.json")).await?;
+//!
+//! // Drive the reloading in the background with any runtime.
+//! let watcher = operations.watch(Duration::from_secs(10)).for_each(|res| async move {
+//!     if let Err(e) = res {
+//!         eprintln!("failed to reload persisted operations: {}", e);
+//!     }
+//! });
+//! # drop(watcher);
+//!
+//! // Only the operations from the manifest are allowed.
+//! let request = operations.request::<juniper::DefaultScalarValue>("a1b2c3", None, None);
+//! # drop(request);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    collections::HashMap,
+    error::Error as StdError,
+    fmt, fs, io,
+    path::PathBuf,
+    sync::{Arc, RwLock},
+    thread,
+    time::Duration,
+};
+
+use futures::{channel::oneshot, future::BoxFuture, stream, Stream};
+use futures_timer::Delay;
+
+use crate::{ast::InputValue, value::ScalarValue};
+
+use super::GraphQLRequest;
+
+/// Store of GraphQL documents, identified by their IDs, which are allowed to be
+/// executed.
+pub trait DocumentStore: Send + Sync {
+    /// Returns the text of the allowed document with the given `id`, if any.
+    fn get(&self, id: &str) -> Option<Arc<str>>;
+
+    /// Builds a [`GraphQLRequest`] of the allowed document with the given
+    /// `id`, if any.
+    fn request<S: ScalarValue>(
+        &self,
+        id: &str,
+        operation_name: Option<String>,
+        variables: Option<InputValue<S>>,
+    ) -> Option<GraphQLRequest<S>>
+    where
+        Self: Sized,
+    {
+        self.get(id)
+            .map(|query| GraphQLRequest::new(query.to_string(), operation_name, variables))
+    }
+}
+
+impl<T: DocumentStore + ?Sized> DocumentStore for Arc<T> {
+    fn get(&self, id: &str) -> Option<Arc<str>> {
+        (**self).get(id)
+    }
+}
+
+/// Source of a persisted operations manifest.
+///
+/// The manifest is a JSON document being either an object mapping operation
+/// IDs to their text:
+/// ```json
+/// {"a1b2c3": "query Hero { hero { name } }"}
+/// ```
+/// or an [Apollo persisted query manifest][1]:
+/// ```json
+/// {
+///   "format": "apollo-persisted-query-manifest",
+///   "version": 1,
+///   "operations": [{"id": "a1b2c3", "name": "Hero", "type": "query", "body": "query Hero { hero { name } }"}]
+/// }
+/// ```
+///
+/// [1]: https://www.apollographql.com/docs/kotlin/advanced/persisted-queries
+pub trait ManifestSource: fmt::Debug + Send + Sync {
+    /// Loads the current text of the manifest.
+    fn load(&self) -> BoxFuture<'_, Result<String, ManifestError>>;
+}
+
+/// [`ManifestSource`] reading a manifest from a file on disk.
+///
+/// The file is read on a dedicated thread, so loading doesn't block the async
+/// runtime.
+#[derive(Clone, Debug)]
+pub struct ManifestFile {
+    path: PathBuf,
+}
+
+impl ManifestFile {
+    /// Creates a new [`ManifestFile`] reading the file at the given `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl ManifestSource for ManifestFile {
+    fn load(&self) -> BoxFuture<'_, Result<String, ManifestError>> {
+        Box::pin(async move {
+            // The file is read on a dedicated thread, so the executor isn't
+            // blocked, whichever async runtime it belongs to.
+            let path = self.path.clone();
+            let (tx, rx) = oneshot::channel();
+            thread::Builder::new()
+                .name("juniper-manifest-file".into())
+                .spawn(move || drop(tx.send(fs::read_to_string(path))))
+                .map_err(ManifestError::Io)?;
+            rx.await
+                .unwrap_or_else(|_| Err(io::Error::other("manifest reading thread panicked")))
+                .map_err(ManifestError::Io)
+        })
+    }
+}
+
+/// Boxed error of fetching a manifest in a [`ManifestUrl`].
+pub type FetchError = Box<dyn StdError + Send + Sync>;
+
+/// Function fetching a manifest by its URL in a [`ManifestUrl`].
+type FetchFn = dyn Fn(&str) -> BoxFuture<'static, Result<String, FetchError>> + Send + Sync;
+
+/// [`ManifestSource`] fetching a manifest from a URL.
+///
+/// As `juniper` doesn't depend on any HTTP client, the actual fetching is
+/// delegated to the provided function:
+/// ```
+/// # use juniper::http::persisted::ManifestUrl;
+/// let source = ManifestUrl::new("https://example.com/operations.json", |url| {
+///     let url = url.to_owned();
+///     Box::pin(async move {
+///         // For example: `Ok(reqwest::get(url).await?.text().await?)`.
+///         # drop(url);
+///         Ok(String::from("{}"))
+///     })
+/// });
+/// # drop(source);
+/// ```
+#[derive(Clone)]
+pub struct ManifestUrl {
+    url: String,
+    fetch: Arc<FetchFn>,
+}
+
+impl fmt::Debug for ManifestUrl {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ManifestUrl")
+            .field("url", &self.url)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ManifestUrl {
+    /// Creates a new [`ManifestUrl`] fetching the given `url` with the
+    /// provided `fetch` function.
+    pub fn new<F>(url: impl Into<String>, fetch: F) -> Self
+    where
+        F: Fn(&str) -> BoxFuture<'static, Result<String, FetchError>> + Send + Sync + 'static,
+    {
+        Self {
+            url: url.into(),
+            fetch: Arc::new(fetch),
+        }
+    }
+
+    /// Returns the URL this [`ManifestUrl`] fetches.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+impl ManifestSource for ManifestUrl {
+    fn load(&self) -> BoxFuture<'_, Result<String, ManifestError>> {
+        let fut = (self.fetch)(&self.url);
+        Box::pin(async move { fut.await.map_err(ManifestError::Source) })
+    }
+}
+
+/// Error of loading a persisted operations manifest.
+#[derive(Debug)]
+pub enum ManifestError {
+    /// Manifest cannot be read from disk.
+    Io(io::Error),
+
+    /// Manifest cannot be fetched from a custom [`ManifestSource`].
+    Source(FetchError),
+
+    /// Manifest is not a valid JSON document.
+    Json(serde_json::Error),
+
+    /// Manifest is a valid JSON document, but of unknown format.
+    Format(String),
+}
+
+impl fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "Failed to read persisted operations manifest: {}", e),
+            Self::Source(e) => write!(f, "Failed to fetch persisted operations manifest: {}", e),
+            Self::Json(e) => write!(f, "Invalid persisted operations manifest: {}", e),
+            Self::Format(e) => write!(f, "Unknown persisted operations manifest format: {}", e),
+        }
+    }
+}
+
+impl StdError for ManifestError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Source(e) => Some(&**e),
+            Self::Json(e) => Some(e),
+            Self::Format(_) => None,
+        }
+    }
+}
+
+/// Allowlist of persisted operations loaded from a [`ManifestSource`].
+///
+/// Reloading the manifest swaps the whole allowlist atomically, so any
+/// concurrent lookup observes either the previous or the new manifest, but
+/// never a mix of them. Cloning is cheap, and all the clones share the same
+/// allowlist.
+#[derive(Clone)]
+pub struct PersistedOperations {
+    source: Arc<dyn ManifestSource>,
+    manifest: Arc<RwLock<Arc<Manifest>>>,
+}
+
+impl fmt::Debug for PersistedOperations {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PersistedOperations")
+            .field("source", &self.source)
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+/// Parsed manifest along with its text it was parsed from.
+#[derive(Debug, Default)]
+struct Manifest {
+    text: String,
+    operations: HashMap<String, Arc<str>>,
+}
+
+impl Manifest {
+    fn parse(text: String) -> Result<Self, ManifestError> {
+        use serde_json::Value as Json;
+
+        let json = serde_json::from_str::<Json>(&text).map_err(ManifestError::Json)?;
+        let invalid = |what: &str| ManifestError::Format(what.into());
+
+        let operations = match json {
+            Json::Object(obj) if obj.contains_key("operations") => obj["operations"]
+                .as_array()
+                .ok_or_else(|| invalid("`operations` is not an array"))?
+                .iter()
+                .map(|op| {
+                    let id = op.get("id").and_then(Json::as_str);
+                    let body = op.get("body").and_then(Json::as_str);
+                    id.zip(body)
+                        .map(|(id, body)| (id.into(), body.into()))
+                        .ok_or_else(|| invalid("operation lacks string `id` or `body`"))
+                })
+                .collect::<Result<_, _>>()?,
+            Json::Object(obj) => obj
+                .into_iter()
+                .map(|(id, body)| match body {
+                    Json::String(body) => Ok((id, body.into())),
+                    _ => Err(invalid("operation text is not a string")),
+                })
+                .collect::<Result<_, _>>()?,
+            _ => return Err(invalid("manifest is not an object")),
+        };
+
+        Ok(Self { text, operations })
+    }
+}
+
+impl PersistedOperations {
+    /// Loads the [`PersistedOperations`] from the given [`ManifestSource`].
+    ///
+    /// # Errors
+    ///
+    /// If the manifest cannot be loaded or parsed.
+    pub async fn load(source: impl ManifestSource + 'static) -> Result<Self, ManifestError> {
+        let text = source.load().await?;
+        Ok(Self {
+            source: Arc::new(source),
+            manifest: Arc::new(RwLock::new(Arc::new(Manifest::parse(text)?))),
+        })
+    }
+
+    /// Reloads the manifest from its [`ManifestSource`], swapping the
+    /// allowlist if the manifest has changed.
+    ///
+    /// Returns whether the allowlist has been swapped.
+    ///
+    /// # Errors
+    ///
+    /// If the manifest cannot be loaded or parsed, in which case the previous
+    /// allowlist remains in use.
+    pub async fn reload(&self) -> Result<bool, ManifestError> {
+        let text = self.source.load().await?;
+        if text == self.current().text {
+            return Ok(false);
+        }
+        let manifest = Arc::new(Manifest::parse(text)?);
+        *self.manifest.write().unwrap() = manifest;
+        Ok(true)
+    }
+
+    /// Returns a [`Stream`] [reloading][1] the manifest every `interval`,
+    /// and yielding the result of each reload.
+    ///
+    /// The [`Stream`] never ends and should be driven in the background for
+    /// as long as the changes of the manifest should be picked up.
+    ///
+    /// [1]: PersistedOperations::reload
+    pub fn watch(
+        &self,
+        interval: Duration,
+    ) -> impl Stream<Item = Result<bool, ManifestError>> + Send + 'static {
+        stream::unfold(self.clone(), move |this| async move {
+            Delay::new(interval).await;
+            let res = this.reload().await;
+            Some((res, this))
+        })
+    }
+
+    /// Returns the number of the currently allowed operations.
+    pub fn len(&self) -> usize {
+        self.current().operations.len()
+    }
+
+    /// Indicates whether no operations are currently allowed.
+    pub fn is_empty(&self) -> bool {
+        self.current().operations.is_empty()
+    }
+
+    /// Returns the current snapshot of the manifest.
+    fn current(&self) -> Arc<Manifest> {
+        Arc::clone(&self.manifest.read().unwrap())
+    }
+}
+
+impl DocumentStore for PersistedOperations {
+    fn get(&self, id: &str) -> Option<Arc<str>> {
+        self.current().operations.get(id).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use futures::{future::BoxFuture, StreamExt as _};
+
+    use crate::DefaultScalarValue;
+
+    use super::{
+        DocumentStore as _, ManifestError, ManifestFile, ManifestSource, ManifestUrl,
+        PersistedOperations,
+    };
+
+    #[derive(Debug, Default)]
+    struct Source(Mutex<String>);
+
+    impl Source {
+        fn set(&self, text: &str) {
+            *self.0.lock().unwrap() = text.into();
+        }
+    }
+
+    impl ManifestSource for Arc<Source> {
+        fn load(&self) -> BoxFuture<'_, Result<String, ManifestError>> {
+            let text = self.0.lock().unwrap().clone();
+            Box::pin(async move { Ok(text) })
+        }
+    }
+
+    #[tokio::test]
+    async fn loads_plain_manifest() {
+        let source = Arc::new(Source::default());
+        source.set(r#"{"a": "{ hero { name } }", "b": "{ human { id } }"}"#);
+
+        let ops = PersistedOperations::load(source).await.unwrap();
+
+        assert_eq!(ops.len(), 2);
+        assert_eq!(ops.get("a").as_deref(), Some("{ hero { name } }"));
+        assert_eq!(ops.get("c"), None);
+
+        let req = ops
+            .request::<DefaultScalarValue>("b", Some("Q".into()), None)
+            .unwrap();
+        assert_eq!(req.query, "{ human { id } }");
+        assert_eq!(req.operation_name.as_deref(), Some("Q"));
+    }
+
+    #[tokio::test]
+    async fn loads_apollo_manifest() {
+        let source = Arc::new(Source::default());
+        source.set(
+            r#"{
+                "format": "apollo-persisted-query-manifest",
+                "version": 1,
+                "operations": [
+                    {"id": "a", "name": "Hero", "type": "query", "body": "query Hero { hero { name } }"}
+                ]
+            }"#,
+        );
+
+        let ops = PersistedOperations::load(source).await.unwrap();
+
+        assert_eq!(
+            ops.get("a").as_deref(),
+            Some("query Hero { hero { name } }")
+        );
+    }
+
+    #[tokio::test]
+    async fn loads_manifest_from_url() {
+        let ops = PersistedOperations::load(ManifestUrl::new("https://example.com/ops", |url| {
+            let res = if url == "https://example.com/ops" {
+                Ok(r#"{"a": "{ hero { name } }"}"#.to_owned())
+            } else {
+                Err("not found".into())
+            };
+            Box::pin(async move { res })
+        }))
+        .await
+        .unwrap();
+        assert_eq!(ops.get("a").as_deref(), Some("{ hero { name } }"));
+
+        let err = PersistedOperations::load(ManifestUrl::new("https://example.com", |_| {
+            Box::pin(async { Err("not found".into()) })
+        }))
+        .await
+        .unwrap_err();
+        assert!(matches!(err, ManifestError::Source(_)), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn loads_manifest_from_file() {
+        let path =
+            std::env::temp_dir().join(format!("juniper-manifest-{}.json", std::process::id()));
+        fs::write(&path, r#"{"a": "{ hero { name } }"}"#).unwrap();
+
+        let res = PersistedOperations::load(ManifestFile::new(&path)).await;
+        fs::remove_file(&path).unwrap();
+        assert_eq!(res.unwrap().get("a").as_deref(), Some("{ hero { name } }"));
+
+        let err = PersistedOperations::load(ManifestFile::new(&path))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ManifestError::Io(_)), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_manifest() {
+        for text in ["", "[]", r#"{"a": 1}"#, r#"{"operations": [{"id": "a"}]}"#] {
+            let source = Arc::new(Source::default());
+            source.set(text);
+
+            assert!(
+                PersistedOperations::load(source).await.is_err(),
+                "accepted: {}",
+                text,
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn swaps_allowlist_on_reload() {
+        let source = Arc::new(Source::default());
+        source.set(r#"{"a": "{ hero { name } }"}"#);
+        let ops = PersistedOperations::load(Arc::clone(&source))
+            .await
+            .unwrap();
+
+        assert!(!ops.reload().await.unwrap(), "unchanged manifest");
+
+        source.set("{");
+        assert!(ops.reload().await.is_err());
+        assert!(ops.get("a").is_some(), "keeps previous allowlist on error");
+
+        source.set(r#"{"b": "{ human { id } }"}"#);
+        let mut watch = Box::pin(ops.watch(Duration::from_millis(1)));
+        assert!(watch.next().await.unwrap().unwrap());
+        assert_eq!(ops.get("a"), None);
+        assert_eq!(ops.get("b").as_deref(), Some("{ human { id } }"));
+    }
+}
@@ -0,0 +1,185 @@
+//! Statistics of GraphQL responses.
+
+use indexmap::IndexMap;
+use serde::{ser::SerializeMap as _, Serialize, Serializer};
+
+use crate::{value::ScalarValue, Value};
+
+/// Statistics of the data of a GraphQL response, helping to identify clients
+/// fetching enormous amounts of it.
+///
+/// Obtained via [`GraphQLResponse::stats()`] (to report them into metrics), or
+/// attached to the response `extensions` under the `stats` key via
+/// [`GraphQLResponse::with_stats_extension()`].
+///
+/// [`GraphQLResponse::stats()`]: super::GraphQLResponse::stats
+/// [`GraphQLResponse::with_stats_extension()`]: super::GraphQLResponse::with_stats_extension
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ResponseStats {
+    field_count: usize,
+    list_items: IndexMap<String, usize>,
+    bytes: Option<usize>,
+}
+
+impl ResponseStats {
+    /// Computes the [`ResponseStats`] of the given response `data`.
+    pub fn of<S: ScalarValue>(data: &Value<S>) -> Self {
+        let mut stats = Self {
+            bytes: json_len(data),
+            ..Self::default()
+        };
+        stats.collect(data, &mut String::new());
+        stats
+    }
+
+    /// Walks the given `value` located at the given `path`, accumulating its
+    /// statistics.
+    fn collect<S>(&mut self, value: &Value<S>, path: &mut String) {
+        match value {
+            Value::Null | Value::Scalar(_) => {}
+            Value::List(items) => {
+                *self.list_items.entry(path.clone()).or_default() += items.len();
+                for item in items {
+                    self.collect(item, path);
+                }
+            }
+            Value::Object(obj) => {
+                for (name, value) in obj.iter() {
+                    self.field_count += 1;
+
+                    let len = path.len();
+                    if !path.is_empty() {
+                        path.push('.');
+                    }
+                    path.push_str(name);
+                    self.collect(value, path);
+                    path.truncate(len);
+                }
+            }
+        }
+    }
+
+    /// Returns the total number of resolved fields in the response data.
+    pub fn field_count(&self) -> usize {
+        self.field_count
+    }
+
+    /// Returns the total number of list items in the response data per path of
+    /// the list field (like `users.friends`), with list indices omitted.
+    pub fn list_items(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.list_items.iter().map(|(path, n)| (path.as_str(), *n))
+    }
+
+    /// Returns the size of the response data serialized to JSON, in bytes.
+    ///
+    /// Always [`None`] without the `serde_json` feature enabled.
+    pub fn bytes(&self) -> Option<usize> {
+        self.bytes
+    }
+}
+
+impl Serialize for ResponseStats {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        let mut map = ser.serialize_map(None)?;
+
+        map.serialize_entry("fieldCount", &self.field_count)?;
+        map.serialize_entry("listItems", &self.list_items)?;
+        if let Some(bytes) = self.bytes {
+            map.serialize_entry("bytes", &bytes)?;
+        }
+
+        map.end()
+    }
+}
+
+/// Calculates the length of the given `data` serialized to JSON.
+#[cfg(feature = "serde_json")]
+fn json_len<S: ScalarValue>(data: &Value<S>) -> Option<usize> {
+    use std::io;
+
+    /// [`io::Write`]r counting the written bytes only.
+    struct Counter(usize);
+
+    impl io::Write for Counter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0 += buf.len();
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut counter = Counter(0);
+    serde_json::to_writer(&mut counter, data).ok()?;
+    Some(counter.0)
+}
+
+/// Calculates the length of the given `data` serialized to JSON.
+#[cfg(not(feature = "serde_json"))]
+fn json_len<S: ScalarValue>(_: &Value<S>) -> Option<usize> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{graphql_value, http::GraphQLResponse, DefaultScalarValue, Value};
+
+    use super::ResponseStats;
+
+    fn data() -> Value<DefaultScalarValue> {
+        graphql_value!({
+            "users": [
+                {"name": "John", "friends": [{"name": "Jane"}, {"name": "Bob"}]},
+                {"name": "Jane", "friends": [{"name": "John"}]},
+            ],
+            "count": 2,
+        })
+    }
+
+    #[test]
+    fn counts_fields() {
+        assert_eq!(ResponseStats::of(&data()).field_count(), 9);
+    }
+
+    #[test]
+    fn counts_list_items_per_path() {
+        assert_eq!(
+            ResponseStats::of(&data()).list_items().collect::<Vec<_>>(),
+            vec![("users", 2), ("users.friends", 3)],
+        );
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn counts_bytes() {
+        let data = data();
+
+        assert_eq!(
+            ResponseStats::of(&data).bytes(),
+            Some(serde_json::to_string(&data).unwrap().len()),
+        );
+    }
+
+    #[test]
+    fn attaches_to_response_extensions() {
+        let res = GraphQLResponse::from_result(Ok((data(), vec![]))).with_stats_extension();
+        let json = serde_json::to_value(&res).unwrap();
+
+        assert_eq!(json["extensions"]["stats"]["fieldCount"], 9);
+        assert_eq!(
+            json["extensions"]["stats"]["listItems"],
+            serde_json::json!({"users": 2, "users.friends": 3}),
+        );
+    }
+
+    #[test]
+    fn omitted_from_response_extensions_by_default() {
+        let res = GraphQLResponse::from_result(Ok((data(), vec![])));
+        let json = serde_json::to_value(&res).unwrap();
+
+        assert!(json.get("extensions").is_none());
+        assert_eq!(res.stats().unwrap().field_count(), 9);
+    }
+}
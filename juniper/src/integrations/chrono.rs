@@ -8,8 +8,10 @@
 //! | [`NaiveTime`]     | `HH:mm[:ss[.SSS]]`    | [`LocalTime`][s2] |
 //! | [`NaiveDateTime`] | `yyyy-MM-dd HH:mm:ss` | `LocalDateTime`   |
 //! | [`DateTime`]      | [RFC 3339] string     | [`DateTime`][s4]  |
+//! | [`FixedOffset`]   | `±hh:mm`              | [`UtcOffset`][s5] |
 //!
 //! [`DateTime`]: chrono::DateTime
+//! [`FixedOffset`]: chrono::FixedOffset
 //! [`NaiveDate`]: chrono::naive::NaiveDate
 //! [`NaiveDateTime`]: chrono::naive::NaiveDateTime
 //! [`NaiveTime`]: chrono::naive::NaiveTime
@@ -17,6 +19,7 @@
 //! [s1]: https://graphql-scalars.dev/docs/scalars/date
 //! [s2]: https://graphql-scalars.dev/docs/scalars/local-time
 //! [s4]: https://graphql-scalars.dev/docs/scalars/date-time
+//! [s5]: https://graphql-scalars.dev/docs/scalars/utc-offset
 
 use std::fmt;
 
@@ -181,6 +184,9 @@ mod local_date_time {
 ///
 /// [`DateTime` scalar][1] compliant.
 ///
+/// Values are normalized to UTC on output. Use [`date_time::to_output_with_offset()`]
+/// in a custom scalar to preserve their offsets instead.
+///
 /// See also [`chrono::DateTime`][2] for details.
 ///
 /// [0]: https://datatracker.ietf.org/doc/html/rfc3339#section-5
@@ -196,12 +202,36 @@ mod local_date_time {
 )]
 pub type DateTime<Tz> = chrono::DateTime<Tz>;
 
-mod date_time {
+/// Resolvers of the [`DateTime`] GraphQL scalar.
+///
+/// May be reused for defining a custom [`DateTime`] GraphQL scalar, preserving
+/// the offsets of its values rather than normalizing them to UTC:
+/// ```rust
+/// # use chrono::FixedOffset;
+/// # use juniper::{integrations::chrono::date_time, GraphQLScalar, ScalarValue, Value};
+/// #
+/// /// Combined date and time (with time zone) in RFC 3339 format, keeping the
+/// /// time zone offset it was specified with.
+/// #[derive(GraphQLScalar)]
+/// #[graphql(to_output_with = Self::to_output, transparent)]
+/// struct ZonedDateTime(chrono::DateTime<FixedOffset>);
+///
+/// impl ZonedDateTime {
+///     fn to_output<S: ScalarValue>(&self) -> Value<S> {
+///         date_time::to_output_with_offset(&self.0)
+///     }
+/// }
+/// ```
+pub mod date_time {
     use chrono::{SecondsFormat, Utc};
 
     use super::*;
 
-    pub(super) fn to_output<S, Tz>(v: &DateTime<Tz>) -> Value<S>
+    /// Formats the given [`DateTime`] in [RFC 3339][0] format, normalizing it
+    /// to UTC.
+    ///
+    /// [0]: https://datatracker.ietf.org/doc/html/rfc3339#section-5
+    pub fn to_output<S, Tz>(v: &DateTime<Tz>) -> Value<S>
     where
         S: ScalarValue,
         Tz: chrono::TimeZone,
@@ -213,7 +243,24 @@ mod date_time {
         )
     }
 
-    pub(super) fn from_input<S, Tz>(v: &InputValue<S>) -> Result<DateTime<Tz>, String>
+    /// Formats the given [`DateTime`] in [RFC 3339][0] format, preserving its
+    /// offset.
+    ///
+    /// [0]: https://datatracker.ietf.org/doc/html/rfc3339#section-5
+    pub fn to_output_with_offset<S, Tz>(v: &DateTime<Tz>) -> Value<S>
+    where
+        S: ScalarValue,
+        Tz: chrono::TimeZone,
+        Tz::Offset: fmt::Display,
+    {
+        Value::scalar(v.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+    }
+
+    /// Parses a [`DateTime`] out of the given [RFC 3339][0] formatted
+    /// [`InputValue`], converting it via [`FromFixedOffset`].
+    ///
+    /// [0]: https://datatracker.ietf.org/doc/html/rfc3339#section-5
+    pub fn from_input<S, Tz>(v: &InputValue<S>) -> Result<DateTime<Tz>, String>
     where
         S: ScalarValue,
         Tz: TimeZone + FromFixedOffset,
@@ -228,6 +275,57 @@ mod date_time {
     }
 }
 
+/// Offset from UTC in `±hh:mm` format. See [list of database time zones][0].
+///
+/// [`UtcOffset` scalar][1] compliant.
+///
+/// See also [`chrono::FixedOffset`][2] for details.
+///
+/// [0]: https://en.wikipedia.org/wiki/List_of_tz_database_time_zones
+/// [1]: https://graphql-scalars.dev/docs/scalars/utc-offset
+/// [2]: https://docs.rs/chrono/latest/chrono/offset/struct.FixedOffset.html
+#[graphql_scalar(
+    with = utc_offset,
+    parse_token(String),
+    specified_by_url = "https://graphql-scalars.dev/docs/scalars/utc-offset",
+)]
+pub type UtcOffset = FixedOffset;
+
+mod utc_offset {
+    use chrono::format::{parse, Parsed, StrftimeItems};
+
+    use super::*;
+
+    /// Format of a [`UtcOffset` scalar][1].
+    ///
+    /// [1]: https://graphql-scalars.dev/docs/scalars/utc-offset
+    const FORMAT: &str = "%:z";
+
+    pub(super) fn to_output<S>(v: &UtcOffset) -> Value<S>
+    where
+        S: ScalarValue,
+    {
+        let secs = v.local_minus_utc();
+        let sign = if secs < 0 { '-' } else { '+' };
+        let mins = secs.abs() / 60;
+        Value::scalar(format!("{}{:02}:{:02}", sign, mins / 60, mins % 60))
+    }
+
+    pub(super) fn from_input<S>(v: &InputValue<S>) -> Result<UtcOffset, String>
+    where
+        S: ScalarValue,
+    {
+        v.as_string_value()
+            .ok_or_else(|| format!("Expected `String`, found: {}", v))
+            .and_then(|s| {
+                let mut parsed = Parsed::new();
+                parse(&mut parsed, s, StrftimeItems::new(FORMAT))
+                    .and_then(|_| parsed.to_fixed_offset())
+                    .map_err(|e| format!("Invalid `UtcOffset`: {}", e))
+            })
+    }
+}
+
 /// Trait allowing to implement a custom [`TimeZone`], which preserves its
 /// [`TimeZone`] information when parsed in a [`DateTime`] GraphQL scalar.
 ///
@@ -582,9 +680,12 @@ mod date_time_test {
         FixedOffset,
     };
 
-    use crate::{graphql_input_value, FromInputValue as _, InputValue, ToInputValue as _};
+    use crate::{
+        graphql_input_value, graphql_value, FromInputValue as _, InputValue, ToInputValue as _,
+        Value,
+    };
 
-    use super::DateTime;
+    use super::{date_time, DateTime};
 
     #[test]
     fn parses_correct_input() {
@@ -706,6 +807,113 @@ mod date_time_test {
             assert_eq!(actual, expected, "on value: {}", val);
         }
     }
+
+    #[test]
+    fn formats_with_offset_correctly() {
+        for (val, expected) in [
+            (
+                DateTime::<FixedOffset>::from_utc(
+                    NaiveDateTime::new(
+                        NaiveDate::from_ymd(1996, 12, 19),
+                        NaiveTime::from_hms(0, 0, 0),
+                    ),
+                    FixedOffset::east(0),
+                ),
+                graphql_value!("1996-12-19T00:00:00Z"),
+            ),
+            (
+                DateTime::<FixedOffset>::from_utc(
+                    NaiveDateTime::new(
+                        NaiveDate::from_ymd(1564, 1, 30),
+                        NaiveTime::from_hms_milli(5, 0, 0, 123),
+                    ),
+                    FixedOffset::east(9 * 3600),
+                ),
+                graphql_value!("1564-01-30T14:00:00.123+09:00"),
+            ),
+        ] {
+            let actual: Value = date_time::to_output_with_offset(&val);
+
+            assert_eq!(actual, expected, "on value: {}", val);
+        }
+    }
+}
+
+#[cfg(test)]
+mod utc_offset_test {
+    use chrono::FixedOffset;
+
+    use crate::{graphql_input_value, FromInputValue as _, InputValue, ToInputValue as _};
+
+    use super::UtcOffset;
+
+    #[test]
+    fn parses_correct_input() {
+        for (raw, expected) in [
+            ("+00:00", FixedOffset::east(0)),
+            ("-00:00", FixedOffset::west(0)),
+            ("+10:00", FixedOffset::east(10 * 3600)),
+            ("-07:30", FixedOffset::west(7 * 3600 + 30 * 60)),
+            ("+14:00", FixedOffset::east(14 * 3600)),
+            ("-12:00", FixedOffset::west(12 * 3600)),
+        ] {
+            let input: InputValue = graphql_input_value!((raw));
+            let parsed = UtcOffset::from_input_value(&input);
+
+            assert!(
+                parsed.is_ok(),
+                "failed to parse `{}`: {:?}",
+                raw,
+                parsed.unwrap_err(),
+            );
+            assert_eq!(parsed.unwrap(), expected, "input: {}", raw);
+        }
+    }
+
+    #[test]
+    fn fails_on_invalid_input() {
+        for input in [
+            graphql_input_value!("12"),
+            graphql_input_value!("12:"),
+            graphql_input_value!("12:00"),
+            graphql_input_value!("+12:"),
+            graphql_input_value!("+12:0"),
+            graphql_input_value!("+12:00:34"),
+            graphql_input_value!("+12"),
+            graphql_input_value!("-12"),
+            graphql_input_value!("-12:"),
+            graphql_input_value!("-12:0"),
+            graphql_input_value!("-12:00:32"),
+            graphql_input_value!("-999:00"),
+            graphql_input_value!("+999:00"),
+            graphql_input_value!("i'm not even an offset"),
+            graphql_input_value!(2.32),
+            graphql_input_value!(1),
+            graphql_input_value!(null),
+            graphql_input_value!(false),
+        ] {
+            let input: InputValue = input;
+            let parsed = UtcOffset::from_input_value(&input);
+
+            assert!(parsed.is_err(), "allows input: {:?}", input);
+        }
+    }
+
+    #[test]
+    fn formats_correctly() {
+        for (val, expected) in [
+            (FixedOffset::east(3600), graphql_input_value!("+01:00")),
+            (FixedOffset::east(0), graphql_input_value!("+00:00")),
+            (
+                FixedOffset::west(2 * 3600 + 30 * 60),
+                graphql_input_value!("-02:30"),
+            ),
+        ] {
+            let actual: InputValue = val.to_input_value();
+
+            assert_eq!(actual, expected, "on value: {}", val);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -716,7 +924,9 @@ mod integration_test {
         types::scalars::{EmptyMutation, EmptySubscription},
     };
 
-    use super::{Date, DateTime, FixedOffset, FromFixedOffset, LocalDateTime, LocalTime, TimeZone};
+    use super::{
+        Date, DateTime, FixedOffset, FromFixedOffset, LocalDateTime, LocalTime, TimeZone, UtcOffset,
+    };
 
     #[tokio::test]
     async fn serializes() {
@@ -792,6 +1002,10 @@ mod integration_test {
             fn transform_date_time(dt: DateTime<CET>) -> DateTime<chrono::Utc> {
                 dt.with_timezone(&chrono::Utc)
             }
+
+            fn utc_offset() -> UtcOffset {
+                UtcOffset::east(11 * 3600 + 30 * 60)
+            }
         }
 
         const DOC: &str = r#"{
@@ -801,6 +1015,7 @@ mod integration_test {
             dateTime,
             passDateTime(dt: "2014-11-28T21:00:09+09:00")
             transformDateTime(dt: "2014-11-28T21:00:09+09:00")
+            utcOffset
         }"#;
 
         let schema = RootNode::new(
@@ -819,6 +1034,7 @@ mod integration_test {
                     "dateTime": "1996-12-20T00:39:57Z",
                     "passDateTime": "2014-11-28T12:00:09Z",
                     "transformDateTime": "2014-11-28T12:00:09Z",
+                    "utcOffset": "+11:30",
                 }),
                 vec![],
             )),
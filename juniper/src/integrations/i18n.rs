@@ -0,0 +1,674 @@
+//! Validating GraphQL scalars for internationalization fields.
+//!
+//! # Supported types
+//!
+//! | Rust type       | Format                       | GraphQL scalar |
+//! |-----------------|------------------------------|----------------|
+//! | [`Locale`]      | [BCP 47] language tag        | `Locale`       |
+//! | [`CountryCode`] | [ISO 3166-1] alpha-2 code    | `CountryCode`  |
+//! | [`Currency`]    | [ISO 4217] alphabetic code   | `CurrencyCode` |
+//!
+//! [BCP 47]: https://www.rfc-editor.org/info/bcp47
+//! [ISO 3166-1]: https://www.iso.org/iso-3166-country-codes.html
+//! [ISO 4217]: https://www.iso.org/iso-4217-currency-codes.html
+
+use std::{fmt, str::FromStr};
+
+use crate::{GraphQLScalar, InputValue, ScalarValue, Value};
+
+/// Active [ISO 4217][0] currencies along with their minor units, sorted by
+/// their codes.
+///
+/// [0]: https://www.iso.org/iso-4217-currency-codes.html
+const CURRENCIES: &[(&str, u8)] = &[
+    ("AED", 2),
+    ("AFN", 2),
+    ("ALL", 2),
+    ("AMD", 2),
+    ("ANG", 2),
+    ("AOA", 2),
+    ("ARS", 2),
+    ("AUD", 2),
+    ("AWG", 2),
+    ("AZN", 2),
+    ("BAM", 2),
+    ("BBD", 2),
+    ("BDT", 2),
+    ("BGN", 2),
+    ("BHD", 3),
+    ("BIF", 0),
+    ("BMD", 2),
+    ("BND", 2),
+    ("BOB", 2),
+    ("BOV", 2),
+    ("BRL", 2),
+    ("BSD", 2),
+    ("BTN", 2),
+    ("BWP", 2),
+    ("BYN", 2),
+    ("BZD", 2),
+    ("CAD", 2),
+    ("CDF", 2),
+    ("CHE", 2),
+    ("CHF", 2),
+    ("CHW", 2),
+    ("CLF", 4),
+    ("CLP", 0),
+    ("CNY", 2),
+    ("COP", 2),
+    ("COU", 2),
+    ("CRC", 2),
+    ("CUP", 2),
+    ("CVE", 2),
+    ("CZK", 2),
+    ("DJF", 0),
+    ("DKK", 2),
+    ("DOP", 2),
+    ("DZD", 2),
+    ("EGP", 2),
+    ("ERN", 2),
+    ("ETB", 2),
+    ("EUR", 2),
+    ("FJD", 2),
+    ("FKP", 2),
+    ("GBP", 2),
+    ("GEL", 2),
+    ("GHS", 2),
+    ("GIP", 2),
+    ("GMD", 2),
+    ("GNF", 0),
+    ("GTQ", 2),
+    ("GYD", 2),
+    ("HKD", 2),
+    ("HNL", 2),
+    ("HTG", 2),
+    ("HUF", 2),
+    ("IDR", 2),
+    ("ILS", 2),
+    ("INR", 2),
+    ("IQD", 3),
+    ("IRR", 2),
+    ("ISK", 0),
+    ("JMD", 2),
+    ("JOD", 3),
+    ("JPY", 0),
+    ("KES", 2),
+    ("KGS", 2),
+    ("KHR", 2),
+    ("KMF", 0),
+    ("KPW", 2),
+    ("KRW", 0),
+    ("KWD", 3),
+    ("KYD", 2),
+    ("KZT", 2),
+    ("LAK", 2),
+    ("LBP", 2),
+    ("LKR", 2),
+    ("LRD", 2),
+    ("LSL", 2),
+    ("LYD", 3),
+    ("MAD", 2),
+    ("MDL", 2),
+    ("MGA", 2),
+    ("MKD", 2),
+    ("MMK", 2),
+    ("MNT", 2),
+    ("MOP", 2),
+    ("MRU", 2),
+    ("MUR", 2),
+    ("MVR", 2),
+    ("MWK", 2),
+    ("MXN", 2),
+    ("MXV", 2),
+    ("MYR", 2),
+    ("MZN", 2),
+    ("NAD", 2),
+    ("NGN", 2),
+    ("NIO", 2),
+    ("NOK", 2),
+    ("NPR", 2),
+    ("NZD", 2),
+    ("OMR", 3),
+    ("PAB", 2),
+    ("PEN", 2),
+    ("PGK", 2),
+    ("PHP", 2),
+    ("PKR", 2),
+    ("PLN", 2),
+    ("PYG", 0),
+    ("QAR", 2),
+    ("RON", 2),
+    ("RSD", 2),
+    ("RUB", 2),
+    ("RWF", 0),
+    ("SAR", 2),
+    ("SBD", 2),
+    ("SCR", 2),
+    ("SDG", 2),
+    ("SEK", 2),
+    ("SGD", 2),
+    ("SHP", 2),
+    ("SLE", 2),
+    ("SOS", 2),
+    ("SRD", 2),
+    ("SSP", 2),
+    ("STN", 2),
+    ("SVC", 2),
+    ("SYP", 2),
+    ("SZL", 2),
+    ("THB", 2),
+    ("TJS", 2),
+    ("TMT", 2),
+    ("TND", 3),
+    ("TOP", 2),
+    ("TRY", 2),
+    ("TTD", 2),
+    ("TWD", 2),
+    ("TZS", 2),
+    ("UAH", 2),
+    ("UGX", 0),
+    ("USD", 2),
+    ("USN", 2),
+    ("UYI", 0),
+    ("UYU", 2),
+    ("UYW", 4),
+    ("UZS", 2),
+    ("VED", 2),
+    ("VES", 2),
+    ("VND", 0),
+    ("VUV", 0),
+    ("WST", 2),
+    ("XAF", 0),
+    ("XCD", 2),
+    ("XCG", 2),
+    ("XOF", 0),
+    ("XPF", 0),
+    ("YER", 2),
+    ("ZAR", 2),
+    ("ZMW", 2),
+    ("ZWG", 2),
+];
+
+/// [ISO 4217][0] currency, represented in GraphQL by its alphabetic code
+/// (`"USD"`, for example).
+///
+/// [0]: https://www.iso.org/iso-4217-currency-codes.html
+#[derive(Clone, Copy, Debug, Eq, GraphQLScalar, Hash, Ord, PartialEq, PartialOrd)]
+#[graphql(name = "CurrencyCode", with = currency_code, parse_token(String))]
+pub struct Currency {
+    code: &'static str,
+    minor_units: u8,
+}
+
+impl Currency {
+    /// Looks up an active [ISO 4217][0] currency by its alphabetic `code`
+    /// (`"JPY"`, for example).
+    ///
+    /// [0]: https://www.iso.org/iso-4217-currency-codes.html
+    pub fn from_code(code: &str) -> Option<Self> {
+        CURRENCIES
+            .binary_search_by(|(c, _)| (*c).cmp(code))
+            .ok()
+            .map(|i| Self {
+                code: CURRENCIES[i].0,
+                minor_units: CURRENCIES[i].1,
+            })
+    }
+
+    /// Returns the alphabetic code of this [`Currency`].
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+
+    /// Returns the number of digits after the decimal separator of the amounts
+    /// in this [`Currency`].
+    pub fn minor_units(&self) -> u8 {
+        self.minor_units
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code)
+    }
+}
+
+impl FromStr for Currency {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_code(s).ok_or_else(|| format!("Unknown ISO 4217 currency code: {}", s))
+    }
+}
+
+mod currency_code {
+    use super::*;
+
+    pub(super) fn to_output<S: ScalarValue>(v: &Currency) -> Value<S> {
+        Value::scalar(v.code().to_owned())
+    }
+
+    pub(super) fn from_input<S: ScalarValue>(v: &InputValue<S>) -> Result<Currency, String> {
+        v.as_string_value()
+            .ok_or_else(|| format!("Expected `String`, found: {}", v))
+            .and_then(str::parse)
+    }
+}
+
+/// Officially assigned [ISO 3166-1 alpha-2][0] country codes, sorted.
+///
+/// [0]: https://www.iso.org/iso-3166-country-codes.html
+const COUNTRIES: &[&str] = &[
+    "AD", "AE", "AF", "AG", "AI", "AL", "AM", "AO", "AQ", "AR", "AS", "AT", "AU", "AW", "AX", "AZ",
+    "BA", "BB", "BD", "BE", "BF", "BG", "BH", "BI", "BJ", "BL", "BM", "BN", "BO", "BQ", "BR", "BS",
+    "BT", "BV", "BW", "BY", "BZ", "CA", "CC", "CD", "CF", "CG", "CH", "CI", "CK", "CL", "CM", "CN",
+    "CO", "CR", "CU", "CV", "CW", "CX", "CY", "CZ", "DE", "DJ", "DK", "DM", "DO", "DZ", "EC", "EE",
+    "EG", "EH", "ER", "ES", "ET", "FI", "FJ", "FK", "FM", "FO", "FR", "GA", "GB", "GD", "GE", "GF",
+    "GG", "GH", "GI", "GL", "GM", "GN", "GP", "GQ", "GR", "GS", "GT", "GU", "GW", "GY", "HK", "HM",
+    "HN", "HR", "HT", "HU", "ID", "IE", "IL", "IM", "IN", "IO", "IQ", "IR", "IS", "IT", "JE", "JM",
+    "JO", "JP", "KE", "KG", "KH", "KI", "KM", "KN", "KP", "KR", "KW", "KY", "KZ", "LA", "LB", "LC",
+    "LI", "LK", "LR", "LS", "LT", "LU", "LV", "LY", "MA", "MC", "MD", "ME", "MF", "MG", "MH", "MK",
+    "ML", "MM", "MN", "MO", "MP", "MQ", "MR", "MS", "MT", "MU", "MV", "MW", "MX", "MY", "MZ", "NA",
+    "NC", "NE", "NF", "NG", "NI", "NL", "NO", "NP", "NR", "NU", "NZ", "OM", "PA", "PE", "PF", "PG",
+    "PH", "PK", "PL", "PM", "PN", "PR", "PS", "PT", "PW", "PY", "QA", "RE", "RO", "RS", "RU", "RW",
+    "SA", "SB", "SC", "SD", "SE", "SG", "SH", "SI", "SJ", "SK", "SL", "SM", "SN", "SO", "SR", "SS",
+    "ST", "SV", "SX", "SY", "SZ", "TC", "TD", "TF", "TG", "TH", "TJ", "TK", "TL", "TM", "TN", "TO",
+    "TR", "TT", "TV", "TW", "TZ", "UA", "UG", "UM", "US", "UY", "UZ", "VA", "VC", "VE", "VG", "VI",
+    "VN", "VU", "WF", "WS", "YE", "YT", "ZA", "ZM", "ZW",
+];
+
+/// Country, represented in GraphQL by its [ISO 3166-1 alpha-2][0] code (`"JP"`,
+/// for example).
+///
+/// [0]: https://www.iso.org/iso-3166-country-codes.html
+#[derive(Clone, Copy, Debug, Eq, GraphQLScalar, Hash, Ord, PartialEq, PartialOrd)]
+#[graphql(with = country_code, parse_token(String))]
+pub struct CountryCode(&'static str);
+
+impl CountryCode {
+    /// Looks up an officially assigned [ISO 3166-1 alpha-2][0] country by its
+    /// `code` (`"JP"`, for example).
+    ///
+    /// [0]: https://www.iso.org/iso-3166-country-codes.html
+    pub fn from_code(code: &str) -> Option<Self> {
+        COUNTRIES
+            .binary_search(&code)
+            .ok()
+            .map(|i| Self(COUNTRIES[i]))
+    }
+
+    /// Returns the alpha-2 code of this [`CountryCode`].
+    pub fn code(&self) -> &'static str {
+        self.0
+    }
+}
+
+impl fmt::Display for CountryCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl FromStr for CountryCode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_code(s).ok_or_else(|| format!("Unknown ISO 3166-1 alpha-2 country code: {}", s))
+    }
+}
+
+mod country_code {
+    use super::*;
+
+    pub(super) fn to_output<S: ScalarValue>(v: &CountryCode) -> Value<S> {
+        Value::scalar(v.code().to_owned())
+    }
+
+    pub(super) fn from_input<S: ScalarValue>(v: &InputValue<S>) -> Result<CountryCode, String> {
+        v.as_string_value()
+            .ok_or_else(|| format!("Expected `String`, found: {}", v))
+            .and_then(str::parse)
+    }
+}
+
+/// Irregular grandfathered [BCP 47][0] tags, not matching the regular syntax.
+///
+/// [0]: https://www.rfc-editor.org/rfc/rfc5646#section-2.1
+const IRREGULAR_LOCALES: &[&str] = &[
+    "en-GB-oed",
+    "i-ami",
+    "i-bnn",
+    "i-default",
+    "i-enochian",
+    "i-hak",
+    "i-klingon",
+    "i-lux",
+    "i-mingo",
+    "i-navajo",
+    "i-pwn",
+    "i-tao",
+    "i-tay",
+    "i-tsu",
+    "sgn-BE-FR",
+    "sgn-BE-NL",
+    "sgn-CH-DE",
+];
+
+/// Well-formed [BCP 47][0] language tag (`"en-US"`, `"zh-Hant-TW"`, for
+/// example).
+///
+/// Only the syntax is validated, so the subtags are not checked against the
+/// [IANA registry][1]. Subtags are normalized to their conventional case
+/// (`"EN-latn-us"` becomes `"en-Latn-US"`).
+///
+/// [0]: https://www.rfc-editor.org/info/bcp47
+/// [1]: https://www.iana.org/assignments/language-subtag-registry
+#[derive(Clone, Debug, Eq, GraphQLScalar, Hash, Ord, PartialEq, PartialOrd)]
+#[graphql(with = locale, parse_token(String))]
+pub struct Locale(String);
+
+impl Locale {
+    /// Returns this [`Locale`] as a string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the primary language subtag of this [`Locale`] (`"en"` for
+    /// `"en-US"`), if it has any.
+    pub fn language(&self) -> Option<&str> {
+        self.0
+            .split('-')
+            .next()
+            .filter(|s| s.len() >= 2 && s.bytes().all(|b| b.is_ascii_alphabetic()))
+            .filter(|_| {
+                !IRREGULAR_LOCALES
+                    .iter()
+                    .any(|t| t.eq_ignore_ascii_case(&self.0))
+            })
+    }
+
+    /// Returns the region subtag of this [`Locale`] (`"US"` for `"en-US"`), if
+    /// it has any.
+    pub fn region(&self) -> Option<&str> {
+        self.language()?;
+        self.0
+            .split('-')
+            .skip(1)
+            .take_while(|s| s.len() > 1)
+            .find(|s| {
+                (s.len() == 2 && s.bytes().all(|b| b.is_ascii_alphabetic()))
+                    || (s.len() == 3 && s.bytes().all(|b| b.is_ascii_digit()))
+            })
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for Locale {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(tag) = IRREGULAR_LOCALES.iter().find(|t| t.eq_ignore_ascii_case(s)) {
+            return Ok(Self((*tag).to_owned()));
+        }
+        normalize_language_tag(s)
+            .map(Self)
+            .ok_or_else(|| format!("Malformed BCP 47 language tag: {}", s))
+    }
+}
+
+/// Validates the given regular [BCP 47][0] language `tag`, returning it with
+/// its subtags in their conventional case.
+///
+/// [0]: https://www.rfc-editor.org/rfc/rfc5646#section-2.1
+fn normalize_language_tag(tag: &str) -> Option<String> {
+    let is_alpha = |s: &str| s.bytes().all(|b| b.is_ascii_alphabetic());
+    let is_digit = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    let is_alnum = |s: &str| s.bytes().all(|b| b.is_ascii_alphanumeric());
+
+    let subtags = tag.split('-').collect::<Vec<_>>();
+    if subtags
+        .iter()
+        .any(|s| s.is_empty() || s.len() > 8 || !is_alnum(s))
+    {
+        return None;
+    }
+
+    let mut out = Vec::with_capacity(subtags.len());
+    let mut rest = &subtags[..];
+
+    // Private use tag (`x-whatever`) consists of the private use part only.
+    if !rest[0].eq_ignore_ascii_case("x") {
+        // language = 2*3ALPHA ["-" extlang] / 4ALPHA / 5*8ALPHA
+        let language = rest[0];
+        if language.len() < 2 || !is_alpha(language) {
+            return None;
+        }
+        out.push(language.to_ascii_lowercase());
+        rest = &rest[1..];
+
+        // extlang = 3ALPHA *2("-" 3ALPHA)
+        if language.len() <= 3 {
+            let mut count = 0;
+            while count < 3 && rest.first().is_some_and(|s| s.len() == 3 && is_alpha(s)) {
+                out.push(rest[0].to_ascii_lowercase());
+                rest = &rest[1..];
+                count += 1;
+            }
+        }
+
+        // script = 4ALPHA
+        if let Some(script) = rest.first().filter(|s| s.len() == 4 && is_alpha(s)) {
+            let (first, tail) = script.split_at(1);
+            out.push(first.to_ascii_uppercase() + &*tail.to_ascii_lowercase());
+            rest = &rest[1..];
+        }
+
+        // region = 2ALPHA / 3DIGIT
+        if let Some(region) = rest
+            .first()
+            .filter(|s| (s.len() == 2 && is_alpha(s)) || (s.len() == 3 && is_digit(s)))
+        {
+            out.push(region.to_ascii_uppercase());
+            rest = &rest[1..];
+        }
+
+        // variant = 5*8alphanum / (DIGIT 3alphanum)
+        while let Some(variant) = rest
+            .first()
+            .filter(|s| s.len() >= 5 || (s.len() == 4 && s.as_bytes()[0].is_ascii_digit()))
+        {
+            out.push(variant.to_ascii_lowercase());
+            rest = &rest[1..];
+        }
+
+        // extension = singleton 1*("-" (2*8alphanum))
+        while let Some(singleton) = rest
+            .first()
+            .filter(|s| s.len() == 1 && !s.eq_ignore_ascii_case("x"))
+        {
+            out.push(singleton.to_ascii_lowercase());
+            rest = &rest[1..];
+            let len = rest.iter().take_while(|s| s.len() >= 2).count();
+            if len == 0 {
+                return None;
+            }
+            out.extend(rest[..len].iter().map(|s| s.to_ascii_lowercase()));
+            rest = &rest[len..];
+        }
+    }
+
+    // privateuse = "x" 1*("-" (1*8alphanum))
+    if let Some((x, private)) = rest.split_first() {
+        if !x.eq_ignore_ascii_case("x") || private.is_empty() {
+            return None;
+        }
+        out.push("x".into());
+        out.extend(private.iter().map(|s| s.to_ascii_lowercase()));
+    }
+
+    Some(out.join("-"))
+}
+
+mod locale {
+    use super::*;
+
+    pub(super) fn to_output<S: ScalarValue>(v: &Locale) -> Value<S> {
+        Value::scalar(v.as_str().to_owned())
+    }
+
+    pub(super) fn from_input<S: ScalarValue>(v: &InputValue<S>) -> Result<Locale, String> {
+        v.as_string_value()
+            .ok_or_else(|| format!("Expected `String`, found: {}", v))
+            .and_then(str::parse)
+    }
+}
+
+#[cfg(test)]
+mod currency_test {
+    use crate::{graphql_input_value, FromInputValue as _, InputValue, ToInputValue as _};
+
+    use super::{Currency, CURRENCIES};
+
+    #[test]
+    fn looks_up_currencies() {
+        assert!(CURRENCIES.windows(2).all(|w| w[0].0 < w[1].0));
+
+        for (code, minor_units) in [("JPY", 0), ("USD", 2), ("EUR", 2), ("KWD", 3), ("CLF", 4)] {
+            let currency = Currency::from_code(code).unwrap();
+
+            assert_eq!(currency.code(), code);
+            assert_eq!(currency.minor_units(), minor_units, "currency: {}", code);
+        }
+        for code in ["usd", "XXX", "", "USDT"] {
+            assert_eq!(Currency::from_code(code), None, "currency: {}", code);
+        }
+    }
+
+    #[test]
+    fn round_trips() {
+        let input: InputValue = graphql_input_value!("JPY");
+        let parsed = Currency::from_input_value(&input).unwrap();
+
+        assert_eq!(parsed.code(), "JPY");
+        assert_eq!(parsed.to_input_value(), input);
+
+        let input: InputValue = graphql_input_value!("jpy");
+        assert!(Currency::from_input_value(&input).is_err());
+    }
+}
+
+#[cfg(test)]
+mod country_code_test {
+    use crate::{graphql_input_value, FromInputValue as _, InputValue, ToInputValue as _};
+
+    use super::{CountryCode, COUNTRIES};
+
+    #[test]
+    fn parses_correct_input() {
+        assert!(COUNTRIES.windows(2).all(|w| w[0] < w[1]));
+
+        for raw in ["JP", "US", "GB", "AX", "ZW"] {
+            let input: InputValue = graphql_input_value!((raw));
+            let parsed = CountryCode::from_input_value(&input);
+
+            assert_eq!(parsed.map(|c| c.code()), Ok(raw), "input: {}", raw);
+        }
+    }
+
+    #[test]
+    fn fails_on_invalid_input() {
+        for input in [
+            graphql_input_value!("jp"),
+            graphql_input_value!("JPN"),
+            graphql_input_value!("UK"),
+            graphql_input_value!("XX"),
+            graphql_input_value!(""),
+            graphql_input_value!(392),
+            graphql_input_value!(null),
+        ] {
+            let input: InputValue = input;
+            let parsed = CountryCode::from_input_value(&input);
+
+            assert!(parsed.is_err(), "allows input: {:?}", input);
+        }
+    }
+
+    #[test]
+    fn formats_correctly() {
+        let actual: InputValue = CountryCode::from_code("JP").unwrap().to_input_value();
+
+        assert_eq!(actual, graphql_input_value!("JP"));
+    }
+}
+
+#[cfg(test)]
+mod locale_test {
+    use crate::{graphql_input_value, FromInputValue as _, InputValue};
+
+    use super::Locale;
+
+    #[test]
+    fn parses_correct_input() {
+        for (raw, expected, language, region) in [
+            ("en", "en", Some("en"), None),
+            ("en-US", "en-US", Some("en"), Some("US")),
+            ("EN-us", "en-US", Some("en"), Some("US")),
+            ("zh-hant-tw", "zh-Hant-TW", Some("zh"), Some("TW")),
+            ("es-419", "es-419", Some("es"), Some("419")),
+            ("zh-yue-HK", "zh-yue-HK", Some("zh"), Some("HK")),
+            ("de-CH-1901", "de-CH-1901", Some("de"), Some("CH")),
+            ("sl-rozaj-biske", "sl-rozaj-biske", Some("sl"), None),
+            (
+                "ja-JP-u-ca-japanese",
+                "ja-JP-u-ca-japanese",
+                Some("ja"),
+                Some("JP"),
+            ),
+            (
+                "en-u-ca-gregory-x-Foo",
+                "en-u-ca-gregory-x-foo",
+                Some("en"),
+                None,
+            ),
+            ("x-whatever", "x-whatever", None, None),
+            ("i-klingon", "i-klingon", None, None),
+            ("EN-gb-OED", "en-GB-oed", None, None),
+        ] {
+            let input: InputValue = graphql_input_value!((raw));
+            let parsed = Locale::from_input_value(&input).unwrap();
+
+            assert_eq!(parsed.as_str(), expected, "input: {}", raw);
+            assert_eq!(parsed.language(), language, "input: {}", raw);
+            assert_eq!(parsed.region(), region, "input: {}", raw);
+        }
+    }
+
+    #[test]
+    fn fails_on_invalid_input() {
+        for input in [
+            graphql_input_value!(""),
+            graphql_input_value!("e"),
+            graphql_input_value!("en_US"),
+            graphql_input_value!("en-"),
+            graphql_input_value!("en--US"),
+            graphql_input_value!("en-US-ab"),
+            graphql_input_value!("123"),
+            graphql_input_value!("toolonglanguage"),
+            graphql_input_value!("en-u"),
+            graphql_input_value!("en-x"),
+            graphql_input_value!("en-US-u-ca-x"),
+            graphql_input_value!(42),
+            graphql_input_value!(null),
+        ] {
+            let input: InputValue = input;
+            let parsed = Locale::from_input_value(&input);
+
+            assert!(parsed.is_err(), "allows input: {:?}", input);
+        }
+    }
+}
@@ -6,10 +6,18 @@ pub mod bson;
 pub mod chrono;
 #[cfg(feature = "chrono-tz")]
 pub mod chrono_tz;
+#[cfg(feature = "i18n")]
+pub mod i18n;
+#[cfg(feature = "money")]
+pub mod money;
 #[doc(hidden)]
 pub mod serde;
+#[cfg(feature = "sql")]
+pub mod sql;
 #[cfg(feature = "time")]
 pub mod time;
+#[cfg(feature = "ulid")]
+pub mod ulid;
 #[cfg(feature = "url")]
 pub mod url;
 #[cfg(feature = "uuid")]
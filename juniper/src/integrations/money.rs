@@ -0,0 +1,446 @@
+//! `Money` GraphQL scalar, validating its amount against the minor units of its
+//! [ISO 4217][0] currency.
+//!
+//! The [`Money`] scalar is always parsed from its string form
+//! (`"12.34 USD"`), as GraphQL doesn't allow object literals for scalars.
+//! Whether it's serialized in the same string form or as an object
+//! (`{"amount": "12.34", "currency": "USD"}`) is configured by its
+//! [`MoneyFormat`] type parameter:
+//!
+//! ```
+//! use juniper::{
+//!     graphql_object, graphql_value, graphql_vars,
+//!     integrations::money::{AsObject, Currency, Money},
+//!     EmptyMutation, EmptySubscription, RootNode,
+//! };
+//!
+//! struct Query;
+//!
+//! #[graphql_object]
+//! impl Query {
+//!     fn price() -> Money {
+//!         Money::new(1234, Currency::from_code("USD").unwrap())
+//!     }
+//!
+//!     fn detailed_price() -> Money<AsObject> {
+//!         Money::new(1234, Currency::from_code("JPY").unwrap())
+//!     }
+//! }
+//!
+//! let schema = RootNode::new(
+//!     Query,
+//!     EmptyMutation::<()>::new(),
+//!     EmptySubscription::<()>::new(),
+//! );
+//!
+//! assert_eq!(
+//!     juniper::execute_sync("{ price detailedPrice }", None, &schema, &graphql_vars! {}, &()),
+//!     Ok((
+//!         graphql_value!({
+//!             "price": "12.34 USD",
+//!             "detailedPrice": {"amount": "1234", "currency": "JPY"},
+//!         }),
+//!         vec![],
+//!     )),
+//! );
+//! ```
+//!
+//! [0]: https://www.iso.org/iso-4217-currency-codes.html
+
+use std::{cmp::Ordering, fmt, hash, marker::PhantomData, str::FromStr};
+
+use crate::{GraphQLScalar, InputValue, ScalarValue, Value};
+
+pub use super::i18n::Currency;
+
+/// Error of parsing a [`Money`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MoneyError {
+    /// Input is not in the `<amount> <currency>` form.
+    InvalidFormat,
+
+    /// Currency is not an active [ISO 4217][0] one.
+    ///
+    /// [0]: https://www.iso.org/iso-4217-currency-codes.html
+    UnknownCurrency(String),
+
+    /// Amount is not a decimal number.
+    InvalidAmount(String),
+
+    /// Amount has more digits after the decimal separator than the minor
+    /// units of its [`Currency`] allow.
+    TooPrecise(Currency),
+
+    /// Amount doesn't fit into an [`i64`] of minor units.
+    Overflow,
+}
+
+impl fmt::Display for MoneyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidFormat => write!(f, "expected `<amount> <currency>`"),
+            Self::UnknownCurrency(c) => write!(f, "unknown ISO 4217 currency `{}`", c),
+            Self::InvalidAmount(a) => write!(f, "invalid amount `{}`", a),
+            Self::TooPrecise(c) => write!(
+                f,
+                "`{}` allows at most {} digit(s) after the decimal separator",
+                c,
+                c.minor_units(),
+            ),
+            Self::Overflow => write!(f, "amount is out of range"),
+        }
+    }
+}
+
+impl std::error::Error for MoneyError {}
+
+/// Serialization format of a [`Money`].
+pub trait MoneyFormat: Sized {
+    /// Serializes the given `money` into a [`Value`].
+    fn to_output<S: ScalarValue>(money: &Money<Self>) -> Value<S>;
+}
+
+/// [`MoneyFormat`] serializing a [`Money`] as a string (`"12.34 USD"`).
+#[derive(Clone, Copy, Debug)]
+pub enum AsString {}
+
+impl MoneyFormat for AsString {
+    fn to_output<S: ScalarValue>(money: &Money<Self>) -> Value<S> {
+        Value::scalar(money.to_string())
+    }
+}
+
+/// [`MoneyFormat`] serializing a [`Money`] as an object
+/// (`{"amount": "12.34", "currency": "USD"}`).
+#[derive(Clone, Copy, Debug)]
+pub enum AsObject {}
+
+impl MoneyFormat for AsObject {
+    fn to_output<S: ScalarValue>(money: &Money<Self>) -> Value<S> {
+        Value::object(
+            vec![
+                ("amount", Value::scalar(money.amount())),
+                ("currency", Value::scalar(money.currency.code().to_owned())),
+            ]
+            .into_iter()
+            .collect(),
+        )
+    }
+}
+
+/// Monetary amount in an [ISO 4217][0] [`Currency`], stored as an integer
+/// number of its minor units, so no precision is lost.
+///
+/// Parsed from `<amount> <currency>` strings (`"12.34 USD"`, `"1234 JPY"`),
+/// rejecting amounts with more digits after the decimal separator than the
+/// [`Currency::minor_units()`] allow. Serialized in a form specified by its
+/// `F`ormat.
+///
+/// [0]: https://www.iso.org/iso-4217-currency-codes.html
+#[derive(GraphQLScalar)]
+#[graphql(with = money_scalar, parse_token(String), where(F: MoneyFormat))]
+pub struct Money<F = AsString> {
+    amount: i64,
+    currency: Currency,
+    _format: PhantomData<fn() -> F>,
+}
+
+impl<F> Money<F> {
+    /// Creates a new [`Money`] out of the given `amount` of minor units of the
+    /// given [`Currency`] (`Money::new(1234, usd)` is `12.34 USD`).
+    pub fn new(amount: i64, currency: Currency) -> Self {
+        Self {
+            amount,
+            currency,
+            _format: PhantomData,
+        }
+    }
+
+    /// Parses a [`Money`] out of the given decimal `amount` in the given
+    /// [`Currency`].
+    ///
+    /// # Errors
+    ///
+    /// If the `amount` is not a decimal number, has more digits after the
+    /// decimal separator than the [`Currency::minor_units()`] allow, or
+    /// doesn't fit into an [`i64`] of minor units.
+    pub fn from_decimal(amount: &str, currency: Currency) -> Result<Self, MoneyError> {
+        let invalid = || MoneyError::InvalidAmount(amount.to_owned());
+
+        let (negative, abs) = match amount.strip_prefix('-') {
+            Some(abs) => (true, abs),
+            None => (false, amount),
+        };
+        let (int, frac) = abs.split_once('.').unwrap_or((abs, ""));
+        let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+        if int.is_empty() || !is_digits(int) || !is_digits(frac) || abs.ends_with('.') {
+            return Err(invalid());
+        }
+        if frac.len() > usize::from(currency.minor_units()) {
+            return Err(MoneyError::TooPrecise(currency));
+        }
+
+        let units = format!(
+            "{}{}{:0<width$}",
+            if negative { "-" } else { "" },
+            int,
+            frac,
+            width = usize::from(currency.minor_units()),
+        );
+        units
+            .parse()
+            .map(|amount| Self::new(amount, currency))
+            .map_err(|_| MoneyError::Overflow)
+    }
+
+    /// Returns the amount of this [`Money`] in minor units of its
+    /// [`Currency`].
+    pub fn minor_amount(&self) -> i64 {
+        self.amount
+    }
+
+    /// Returns the [`Currency`] of this [`Money`].
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// Returns the decimal amount of this [`Money`], having exactly
+    /// [`Currency::minor_units()`] digits after the decimal separator
+    /// (`"12.30"`, for example).
+    pub fn amount(&self) -> String {
+        let scale = 10_u64.pow(self.currency.minor_units().into());
+        let abs = self.amount.unsigned_abs();
+        let sign = if self.amount < 0 { "-" } else { "" };
+        if scale == 1 {
+            format!("{}{}", sign, abs)
+        } else {
+            format!(
+                "{}{}.{:0width$}",
+                sign,
+                abs / scale,
+                abs % scale,
+                width = usize::from(self.currency.minor_units()),
+            )
+        }
+    }
+
+    /// Converts this [`Money`] into the one serialized in another
+    /// [`MoneyFormat`].
+    pub fn into_format<T>(self) -> Money<T> {
+        Money::new(self.amount, self.currency)
+    }
+}
+
+impl<F> FromStr for Money<F> {
+    type Err = MoneyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (amount, code) = s.split_once(' ').ok_or(MoneyError::InvalidFormat)?;
+        let currency = Currency::from_code(code)
+            .ok_or_else(|| MoneyError::UnknownCurrency(code.to_owned()))?;
+        Self::from_decimal(amount, currency)
+    }
+}
+
+impl<F> fmt::Display for Money<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.amount(), self.currency)
+    }
+}
+
+impl<F> fmt::Debug for Money<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Money")
+            .field("amount", &self.amount)
+            .field("currency", &self.currency)
+            .finish()
+    }
+}
+
+// Implemented manually to not require `F` implementing the traits.
+impl<F> Clone for Money<F> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<F> Copy for Money<F> {}
+
+impl<F> PartialEq for Money<F> {
+    fn eq(&self, other: &Self) -> bool {
+        (self.amount, self.currency) == (other.amount, other.currency)
+    }
+}
+
+impl<F> Eq for Money<F> {}
+
+impl<F> PartialOrd for Money<F> {
+    /// Compares the amounts of the same [`Currency`] only.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        (self.currency == other.currency).then(|| self.amount.cmp(&other.amount))
+    }
+}
+
+impl<F> hash::Hash for Money<F> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.amount.hash(state);
+        self.currency.hash(state);
+    }
+}
+
+mod money_scalar {
+    use super::*;
+
+    pub(super) fn to_output<S, F>(v: &Money<F>) -> Value<S>
+    where
+        S: ScalarValue,
+        F: MoneyFormat,
+    {
+        F::to_output(v)
+    }
+
+    pub(super) fn from_input<S, F>(v: &InputValue<S>) -> Result<Money<F>, String>
+    where
+        S: ScalarValue,
+        F: MoneyFormat,
+    {
+        v.as_string_value()
+            .ok_or_else(|| format!("Expected `String`, found: {}", v))
+            .and_then(|s| s.parse().map_err(|e| format!("Invalid `Money`: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        execute_sync, graphql_input_value, graphql_object, graphql_value, graphql_vars,
+        EmptyMutation, EmptySubscription, FromInputValue as _, InputValue, RootNode,
+        ToInputValue as _,
+    };
+
+    use super::{AsObject, AsString, Currency, Money, MoneyError};
+
+    fn currency(code: &str) -> Currency {
+        Currency::from_code(code).unwrap()
+    }
+
+    #[test]
+    fn parses_correct_input() {
+        for (raw, amount, code) in [
+            ("12.34 USD", 1234, "USD"),
+            ("12.3 USD", 1230, "USD"),
+            ("12 USD", 1200, "USD"),
+            ("-0.05 USD", -5, "USD"),
+            ("1234 JPY", 1234, "JPY"),
+            ("1.234 KWD", 1234, "KWD"),
+        ] {
+            let input: InputValue = graphql_input_value!((raw));
+            let parsed = Money::<AsString>::from_input_value(&input);
+
+            assert_eq!(
+                parsed,
+                Ok(Money::new(amount, currency(code))),
+                "input: {}",
+                raw,
+            );
+        }
+    }
+
+    #[test]
+    fn fails_on_invalid_input() {
+        for (raw, err) in [
+            ("12.34", MoneyError::InvalidFormat),
+            ("12.34USD", MoneyError::InvalidFormat),
+            ("12.34 XXX", MoneyError::UnknownCurrency("XXX".into())),
+            ("12.34 usd", MoneyError::UnknownCurrency("usd".into())),
+            ("12.34 USD ", MoneyError::UnknownCurrency("USD ".into())),
+            ("12.5 JPY", MoneyError::TooPrecise(currency("JPY"))),
+            ("12.345 USD", MoneyError::TooPrecise(currency("USD"))),
+            ("12. USD", MoneyError::InvalidAmount("12.".into())),
+            (".5 USD", MoneyError::InvalidAmount(".5".into())),
+            ("+1 USD", MoneyError::InvalidAmount("+1".into())),
+            ("1e3 USD", MoneyError::InvalidAmount("1e3".into())),
+            ("1.2.3 USD", MoneyError::InvalidAmount("1.2.3".into())),
+            ("99999999999999999999 USD", MoneyError::Overflow),
+        ] {
+            assert_eq!(raw.parse::<Money>(), Err(err), "input: {}", raw);
+        }
+
+        for input in [graphql_input_value!(12.34), graphql_input_value!(null)] {
+            let input: InputValue = input;
+            let parsed = Money::<AsString>::from_input_value(&input);
+
+            assert!(parsed.is_err(), "allows input: {:?}", input);
+        }
+    }
+
+    #[test]
+    fn formats_correctly() {
+        for (val, expected) in [
+            (Money::<AsString>::new(1234, currency("USD")), "12.34 USD"),
+            (Money::new(5, currency("USD")), "0.05 USD"),
+            (Money::new(-1230, currency("USD")), "-12.30 USD"),
+            (Money::new(1234, currency("JPY")), "1234 JPY"),
+            (Money::new(-1, currency("KWD")), "-0.001 KWD"),
+        ] {
+            let actual: InputValue = val.to_input_value();
+
+            assert_eq!(
+                actual,
+                graphql_input_value!((expected)),
+                "on value: {}",
+                val
+            );
+        }
+    }
+
+    #[test]
+    fn resolves_in_schema() {
+        struct Query;
+
+        #[graphql_object]
+        impl Query {
+            fn double(money: Money) -> Money {
+                Money::new(money.minor_amount() * 2, money.currency())
+            }
+
+            fn detailed(money: Money) -> Money<AsObject> {
+                money.into_format()
+            }
+        }
+
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+
+        assert_eq!(
+            execute_sync(
+                r#"{
+                    double(money: "6.17 USD")
+                    detailed(money: "500 JPY")
+                }"#,
+                None,
+                &schema,
+                &graphql_vars! {},
+                &(),
+            ),
+            Ok((
+                graphql_value!({
+                    "double": "12.34 USD",
+                    "detailed": {"amount": "500", "currency": "JPY"},
+                }),
+                vec![],
+            )),
+        );
+        assert!(execute_sync(
+            r#"{ double(money: "5.5 JPY") }"#,
+            None,
+            &schema,
+            &graphql_vars! {},
+            &(),
+        )
+        .is_err());
+    }
+}
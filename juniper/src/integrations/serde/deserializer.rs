@@ -0,0 +1,488 @@
+//! Direct [`Deserializer`] implementations for [`InputValue`] and [`Value`].
+
+use std::marker::PhantomData;
+
+use serde::{
+    de::{
+        self,
+        value::{MapDeserializer, SeqDeserializer},
+        Deserializer, IntoDeserializer, Visitor,
+    },
+    forward_to_deserialize_any,
+    ser::{Impossible, Serialize, Serializer},
+};
+
+use crate::{ast::InputValue, value::ScalarValue, Value};
+
+use super::SerdeError;
+
+/// Deserializes a `T` right from the given [`InputValue`], without building
+/// any intermediate representation (like `serde_json::Value`).
+///
+/// Useful for decoding variables of a request into typed Rust values.
+///
+/// # Errors
+///
+/// If the [`InputValue`] doesn't fit `T`, or contains unresolved variables.
+pub fn from_input_value<T, S>(value: InputValue<S>) -> Result<T, SerdeError>
+where
+    T: de::DeserializeOwned,
+    S: ScalarValue,
+{
+    T::deserialize(value)
+}
+
+/// Deserializes a `T` right from the given [`Value`], without building any
+/// intermediate representation (like `serde_json::Value`).
+///
+/// # Errors
+///
+/// If the [`Value`] doesn't fit `T`.
+pub fn from_value<T, S>(value: Value<S>) -> Result<T, SerdeError>
+where
+    T: de::DeserializeOwned,
+    S: ScalarValue,
+{
+    T::deserialize(value)
+}
+
+impl<'de, S: ScalarValue> Deserializer<'de> for InputValue<S> {
+    type Error = SerdeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        match self {
+            Self::Null => visitor.visit_unit(),
+            Self::Scalar(s) => s.serialize(ScalarDeserializer(visitor, PhantomData)),
+            Self::Enum(e) => visitor.visit_string(e),
+            Self::Variable(v) => Err(de::Error::custom(format!("unresolved variable `${}`", v,))),
+            Self::List(l) => visit_seq(l.into_iter().map(|v| v.item), visitor),
+            Self::Object(o) => visit_map(o.into_iter().map(|(k, v)| (k.item, v.item)), visitor),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        match self {
+            Self::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        match self {
+            Self::Enum(variant) => visitor.visit_enum(EnumDeserializer {
+                variant,
+                value: None::<Self>,
+            }),
+            Self::Scalar(ref s) if s.as_str().is_some() => visitor.visit_enum(EnumDeserializer {
+                variant: s.as_string().unwrap(),
+                value: None::<Self>,
+            }),
+            Self::Object(o) if o.len() == 1 => {
+                let (variant, value) = o.into_iter().next().unwrap();
+                visitor.visit_enum(EnumDeserializer {
+                    variant: variant.item,
+                    value: Some(value.item),
+                })
+            }
+            other => Err(de::Error::invalid_type(other.unexpected(), &"enum")),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+impl<'de, S: ScalarValue> IntoDeserializer<'de, SerdeError> for InputValue<S> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<S: ScalarValue> InputValue<S> {
+    /// Describes this [`InputValue`] for [`de::Error::invalid_type()`].
+    fn unexpected(&self) -> de::Unexpected<'_> {
+        match self {
+            Self::Null => de::Unexpected::Unit,
+            Self::Scalar(_) | Self::Enum(_) | Self::Variable(_) => {
+                de::Unexpected::Other("scalar value")
+            }
+            Self::List(_) => de::Unexpected::Seq,
+            Self::Object(_) => de::Unexpected::Map,
+        }
+    }
+}
+
+impl<'de, S: ScalarValue> Deserializer<'de> for Value<S> {
+    type Error = SerdeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        match self {
+            Self::Null => visitor.visit_unit(),
+            Self::Scalar(s) => s.serialize(ScalarDeserializer(visitor, PhantomData)),
+            Self::List(l) => visit_seq(l.into_iter(), visitor),
+            Self::Object(o) => visit_map(o.into_iter(), visitor),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        match self {
+            Self::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        match self {
+            Self::Scalar(ref s) if s.as_str().is_some() => visitor.visit_enum(EnumDeserializer {
+                variant: s.as_string().unwrap(),
+                value: None::<Self>,
+            }),
+            Self::Object(o) if o.field_count() == 1 => {
+                let (variant, value) = o.into_iter().next().unwrap();
+                visitor.visit_enum(EnumDeserializer {
+                    variant,
+                    value: Some(value),
+                })
+            }
+            other => Err(de::Error::invalid_type(other.unexpected(), &"enum")),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+impl<'de, S: ScalarValue> IntoDeserializer<'de, SerdeError> for Value<S> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<S: ScalarValue> Value<S> {
+    /// Describes this [`Value`] for [`de::Error::invalid_type()`].
+    fn unexpected(&self) -> de::Unexpected<'_> {
+        match self {
+            Self::Null => de::Unexpected::Unit,
+            Self::Scalar(_) => de::Unexpected::Other("scalar value"),
+            Self::List(_) => de::Unexpected::Seq,
+            Self::Object(_) => de::Unexpected::Map,
+        }
+    }
+}
+
+/// Visits the given `items` as a sequence, ensuring all of them are consumed.
+fn visit_seq<'de, I, V>(items: I, visitor: V) -> Result<V::Value, SerdeError>
+where
+    I: Iterator,
+    I::Item: IntoDeserializer<'de, SerdeError>,
+    V: Visitor<'de>,
+{
+    let mut seq = SeqDeserializer::new(items);
+    let value = visitor.visit_seq(&mut seq)?;
+    seq.end()?;
+    Ok(value)
+}
+
+/// Visits the given `entries` as a map, ensuring all of them are consumed.
+fn visit_map<'de, I, T, V>(entries: I, visitor: V) -> Result<V::Value, SerdeError>
+where
+    I: Iterator<Item = (String, T)>,
+    T: IntoDeserializer<'de, SerdeError>,
+    V: Visitor<'de>,
+{
+    let mut map = MapDeserializer::new(entries);
+    let value = visitor.visit_map(&mut map)?;
+    map.end()?;
+    Ok(value)
+}
+
+/// [`de::EnumAccess`] of an enum variant represented either by its name only,
+/// or by a single-entry object keyed by its name.
+struct EnumDeserializer<T> {
+    variant: String,
+    value: Option<T>,
+}
+
+impl<'de, T> de::EnumAccess<'de> for EnumDeserializer<T>
+where
+    T: Deserializer<'de, Error = SerdeError>,
+{
+    type Error = SerdeError;
+    type Variant = VariantDeserializer<T>;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), SerdeError>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        let variant = seed.deserialize(self.variant.into_deserializer())?;
+        Ok((variant, VariantDeserializer(self.value)))
+    }
+}
+
+/// [`de::VariantAccess`] of the content of an [`EnumDeserializer`] variant.
+struct VariantDeserializer<T>(Option<T>);
+
+impl<'de, T> de::VariantAccess<'de> for VariantDeserializer<T>
+where
+    T: Deserializer<'de, Error = SerdeError>,
+{
+    type Error = SerdeError;
+
+    fn unit_variant(self) -> Result<(), SerdeError> {
+        match self.0 {
+            Some(value) => de::Deserialize::deserialize(value),
+            None => Ok(()),
+        }
+    }
+
+    fn newtype_variant_seed<V>(self, seed: V) -> Result<V::Value, SerdeError>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        match self.0 {
+            Some(value) => seed.deserialize(value),
+            None => Err(de::Error::invalid_type(
+                de::Unexpected::UnitVariant,
+                &"newtype variant",
+            )),
+        }
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _: usize, visitor: V) -> Result<V::Value, SerdeError> {
+        match self.0 {
+            Some(value) => value.deserialize_seq(visitor),
+            None => Err(de::Error::invalid_type(
+                de::Unexpected::UnitVariant,
+                &"tuple variant",
+            )),
+        }
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        match self.0 {
+            Some(value) => value.deserialize_map(visitor),
+            None => Err(de::Error::invalid_type(
+                de::Unexpected::UnitVariant,
+                &"struct variant",
+            )),
+        }
+    }
+}
+
+/// [`Serializer`] feeding a [`ScalarValue`] into the wrapped [`Visitor`].
+///
+/// As any [`ScalarValue`] is [`Serialize`], this allows to deserialize custom
+/// [`ScalarValue`]s without knowing their variants.
+struct ScalarDeserializer<'de, V>(V, PhantomData<&'de ()>);
+
+impl<'de, V: Visitor<'de>> Serializer for ScalarDeserializer<'de, V> {
+    type Ok = V::Value;
+    type Error = SerdeError;
+    type SerializeSeq = Impossible<V::Value, SerdeError>;
+    type SerializeTuple = Impossible<V::Value, SerdeError>;
+    type SerializeTupleStruct = Impossible<V::Value, SerdeError>;
+    type SerializeTupleVariant = Impossible<V::Value, SerdeError>;
+    type SerializeMap = Impossible<V::Value, SerdeError>;
+    type SerializeStruct = Impossible<V::Value, SerdeError>;
+    type SerializeStructVariant = Impossible<V::Value, SerdeError>;
+
+    fn serialize_bool(self, v: bool) -> Result<V::Value, SerdeError> {
+        self.0.visit_bool(v)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<V::Value, SerdeError> {
+        self.0.visit_i8(v)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<V::Value, SerdeError> {
+        self.0.visit_i16(v)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<V::Value, SerdeError> {
+        self.0.visit_i32(v)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<V::Value, SerdeError> {
+        self.0.visit_i64(v)
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<V::Value, SerdeError> {
+        self.0.visit_i128(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<V::Value, SerdeError> {
+        self.0.visit_u8(v)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<V::Value, SerdeError> {
+        self.0.visit_u16(v)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<V::Value, SerdeError> {
+        self.0.visit_u32(v)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<V::Value, SerdeError> {
+        self.0.visit_u64(v)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<V::Value, SerdeError> {
+        self.0.visit_u128(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<V::Value, SerdeError> {
+        self.0.visit_f32(v)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<V::Value, SerdeError> {
+        self.0.visit_f64(v)
+    }
+
+    fn serialize_char(self, v: char) -> Result<V::Value, SerdeError> {
+        self.0.visit_char(v)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<V::Value, SerdeError> {
+        self.0.visit_str(v)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<V::Value, SerdeError> {
+        self.0.visit_bytes(v)
+    }
+
+    fn serialize_none(self) -> Result<V::Value, SerdeError> {
+        self.0.visit_none()
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<V::Value, SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<V::Value, SerdeError> {
+        self.0.visit_unit()
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<V::Value, SerdeError> {
+        self.0.visit_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<V::Value, SerdeError> {
+        self.0.visit_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<V::Value, SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<V::Value, SerdeError> {
+        Err(non_scalar())
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, SerdeError> {
+        Err(non_scalar())
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, SerdeError> {
+        Err(non_scalar())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, SerdeError> {
+        Err(non_scalar())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, SerdeError> {
+        Err(non_scalar())
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, SerdeError> {
+        Err(non_scalar())
+    }
+
+    fn serialize_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStruct, SerdeError> {
+        Err(non_scalar())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, SerdeError> {
+        Err(non_scalar())
+    }
+}
+
+/// Error of a [`ScalarValue`] being serialized as a non-scalar.
+fn non_scalar() -> SerdeError {
+    de::Error::custom("`ScalarValue` must serialize as a scalar")
+}
@@ -1,5 +1,9 @@
+mod deserializer;
+mod serializer;
+
 use std::{
     convert::{TryFrom as _, TryInto as _},
+    error::Error as StdError,
     fmt,
     marker::PhantomData,
 };
@@ -13,12 +17,44 @@ use serde::{
 
 use crate::{
     ast::InputValue,
-    executor::ExecutionError,
+    executor::{ExecutionError, PathSegment},
     parser::{ParseError, SourcePosition, Spanning},
     validation::RuleError,
-    DefaultScalarValue, GraphQLError, Object, Value,
+    DefaultScalarValue, Object, OperationError, RequestError, Value,
+};
+
+pub use self::{
+    deserializer::{from_input_value, from_value},
+    serializer::{
+        to_input_value, to_value, SerializeList, SerializeObject, SerializeVariant, ValueSerializer,
+    },
 };
 
+/// Error of serializing a Rust value right into an [`InputValue`] or a
+/// [`Value`], or deserializing it right from them.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SerdeError(String);
+
+impl fmt::Display for SerdeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl StdError for SerdeError {}
+
+impl de::Error for SerdeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+impl serde::ser::Error for SerdeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
 impl<T: Serialize> Serialize for ExecutionError<T> {
     fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
         let mut map = ser.serialize_map(Some(4))?;
@@ -42,7 +78,17 @@ impl<T: Serialize> Serialize for ExecutionError<T> {
     }
 }
 
-impl<'a> Serialize for GraphQLError<'a> {
+impl Serialize for PathSegment {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Field(name) => ser.serialize_str(name),
+            Self::Index(idx) => ser.serialize_u64(*idx as u64),
+        }
+    }
+}
+
+#[allow(deprecated)]
+impl<'a> Serialize for crate::GraphQLError<'a> {
     fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
         #[derive(Serialize)]
         struct Helper {
@@ -77,6 +123,33 @@ impl<'a> Serialize for GraphQLError<'a> {
     }
 }
 
+impl<'a> Serialize for RequestError<'a> {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct Helper {
+            message: &'static str,
+        }
+
+        match self {
+            Self::Parse(e) => [e].serialize(ser),
+            Self::Validation(es) | Self::VariableCoercion(es) => es.serialize(ser),
+            Self::Operation(e) => [Helper {
+                message: match e {
+                    OperationError::NoOperationProvided => "Must provide an operation",
+                    OperationError::MultipleOperationsProvided => {
+                        "Must provide operation name \
+                         if query contains multiple operations"
+                    }
+                    OperationError::UnknownOperationName => "Unknown operation",
+                    OperationError::IsSubscription => "Expected query, got subscription",
+                    OperationError::NotSubscription => "Expected subscription, got query",
+                },
+            }]
+            .serialize(ser),
+        }
+    }
+}
+
 impl<'de, S: Deserialize<'de>> Deserialize<'de> for InputValue<S> {
     fn deserialize<D: Deserializer<'de>>(de: D) -> Result<Self, D::Error> {
         struct Visitor<S: ?Sized>(PhantomData<S>);
@@ -219,7 +292,8 @@ impl<T: Serialize> Serialize for InputValue<T> {
 
 impl Serialize for RuleError {
     fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
-        let mut map = ser.serialize_map(Some(2))?;
+        let has_extensions = self.rule().is_some() || !self.params().is_empty();
+        let mut map = ser.serialize_map(Some(if has_extensions { 3 } else { 2 }))?;
 
         map.serialize_key("message")?;
         map.serialize_value(self.message())?;
@@ -227,6 +301,24 @@ impl Serialize for RuleError {
         map.serialize_key("locations")?;
         map.serialize_value(self.locations())?;
 
+        if has_extensions {
+            let mut extensions = Object::with_capacity(2);
+            if let Some(rule) = self.rule() {
+                extensions.add_field("rule", Value::scalar(rule.to_owned()));
+            }
+            if !self.params().is_empty() {
+                let params = self
+                    .params()
+                    .iter()
+                    .map(|(name, value)| (*name, value.clone()))
+                    .collect();
+                extensions.add_field("params", Value::Object(params));
+            }
+
+            map.serialize_key("extensions")?;
+            map.serialize_value(&extensions)?;
+        }
+
         map.end()
     }
 }
@@ -353,16 +445,24 @@ impl<'de> Deserialize<'de> for DefaultScalarValue {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
+    use serde::{Deserialize, Serialize};
     use serde_json::{from_str, to_string};
 
     use crate::{
         ast::InputValue,
-        graphql_input_value,
+        graphql_input_value, graphql_value,
+        parser::SourcePosition,
+        validation::RuleError,
         value::{DefaultScalarValue, Object},
         FieldError, Value,
     };
 
-    use super::{ExecutionError, GraphQLError};
+    use super::{
+        from_input_value, from_value, to_input_value, to_value, ExecutionError, OperationError,
+        PathSegment, RequestError,
+    };
 
     #[test]
     fn int() {
@@ -385,12 +485,20 @@ mod tests {
         );
     }
 
+    #[allow(deprecated)]
     #[test]
     fn errors() {
         assert_eq!(
-            to_string(&GraphQLError::UnknownOperationName).unwrap(),
+            to_string(&crate::GraphQLError::UnknownOperationName).unwrap(),
             r#"[{"message":"Unknown operation"}]"#,
         );
+        assert_eq!(
+            to_string(&RequestError::Operation(
+                OperationError::MultipleOperationsProvided,
+            ))
+            .unwrap(),
+            r#"[{"message":"Must provide operation name if query contains multiple operations"}]"#,
+        );
     }
 
     #[test]
@@ -406,4 +514,160 @@ mod tests {
             r#"{"message":"foo error","locations":[{"line":1,"column":1}],"path":[],"extensions":{"foo":"bar"}}"#,
         );
     }
+
+    #[test]
+    fn rule_error_extensions() {
+        let pos = [SourcePosition::new(0, 0, 0)];
+        assert_eq!(
+            to_string(&RuleError::new("invalid", &pos)).unwrap(),
+            r#"{"message":"invalid","locations":[{"line":1,"column":1}]}"#,
+        );
+        assert_eq!(
+            to_string(
+                &RuleError::new("too many aliases", &pos)
+                    .with_rule("MaxAliases")
+                    .with_param("max", 3)
+                    .with_param("actual", 5),
+            )
+            .unwrap(),
+            r#"{"message":"too many aliases","locations":[{"line":1,"column":1}],"extensions":{"rule":"MaxAliases","params":{"max":3,"actual":5}}}"#,
+        );
+    }
+
+    #[test]
+    fn error_path_with_list_index() {
+        assert_eq!(
+            to_string(&ExecutionError::<DefaultScalarValue>::new(
+                SourcePosition::new(11, 0, 11),
+                &[
+                    PathSegment::from("heroes"),
+                    PathSegment::Index(1),
+                    PathSegment::from("name"),
+                ],
+                FieldError::new("name error", Value::null()),
+            ))
+            .unwrap(),
+            r#"{"message":"name error","locations":[{"line":1,"column":12}],"path":["heroes",1,"name"]}"#,
+        );
+    }
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Order {
+        id: i32,
+        total: f64,
+        paid: bool,
+        note: Option<String>,
+        status: Status,
+        items: Vec<Item>,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    enum Status {
+        Pending,
+        Shipped(String),
+        Returned { reason: String },
+    }
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct Item(String, i32);
+
+    fn order() -> Order {
+        Order {
+            id: 1,
+            total: 9.5,
+            paid: true,
+            note: None,
+            status: Status::Shipped("DHL".into()),
+            items: vec![Item("book".into(), 2)],
+        }
+    }
+
+    #[test]
+    fn serializes_to_value() {
+        assert_eq!(
+            to_value::<DefaultScalarValue, _>(&order()).unwrap(),
+            graphql_value!({
+                "id": 1,
+                "total": 9.5,
+                "paid": true,
+                "note": null,
+                "status": {"Shipped": "DHL"},
+                "items": [["book", 2]],
+            }),
+        );
+        assert_eq!(
+            to_value::<DefaultScalarValue, _>(&Status::Pending).unwrap(),
+            graphql_value!("Pending"),
+        );
+    }
+
+    #[test]
+    fn round_trips_value() {
+        let value: Value = to_value(&order()).unwrap();
+
+        assert_eq!(from_value::<Order, _>(value).unwrap(), order());
+    }
+
+    #[test]
+    fn round_trips_input_value() {
+        let returned = Status::Returned {
+            reason: "broken".into(),
+        };
+        let input: InputValue = to_input_value(&returned).unwrap();
+
+        assert_eq!(
+            input,
+            graphql_input_value!({"Returned": {"reason": "broken"}}),
+        );
+        assert_eq!(from_input_value::<Status, _>(input).unwrap(), returned);
+    }
+
+    #[test]
+    fn deserializes_from_input_value() {
+        let input = graphql_input_value!({
+            "id": 2,
+            "total": 3,
+            "paid": false,
+            "note": "fragile",
+            "status": Pending,
+            "items": [],
+        });
+
+        assert_eq!(
+            from_input_value::<Order, DefaultScalarValue>(input).unwrap(),
+            Order {
+                id: 2,
+                total: 3.0,
+                paid: false,
+                note: Some("fragile".into()),
+                status: Status::Pending,
+                items: vec![],
+            },
+        );
+    }
+
+    #[test]
+    fn errors_on_unresolved_variable() {
+        let input = graphql_input_value!({"id": @id});
+
+        assert_eq!(
+            from_input_value::<HashMap<String, i32>, DefaultScalarValue>(input)
+                .unwrap_err()
+                .to_string(),
+            "unresolved variable `$id`",
+        );
+    }
+
+    #[test]
+    fn errors_on_non_string_map_keys() {
+        let map = HashMap::from([(1, "one")]);
+
+        assert_eq!(
+            to_value::<DefaultScalarValue, _>(&map)
+                .unwrap_err()
+                .to_string(),
+            "map keys must be strings",
+        );
+    }
 }
@@ -0,0 +1,375 @@
+//! Direct [`Serializer`] into [`Value`] (and [`InputValue`]).
+
+use std::marker::PhantomData;
+
+use serde::{
+    de::IntoDeserializer as _,
+    ser::{self, Serialize, Serializer},
+};
+
+use crate::{
+    ast::{InputValue, ToInputValue as _},
+    value::{Object, ScalarValue},
+    Value,
+};
+
+use super::SerdeError;
+
+/// Serializes the given `value` right into a [`Value`], without building any
+/// intermediate representation (like `serde_json::Value`).
+///
+/// Primitives are converted into the [`ScalarValue`] by deserializing it from
+/// them, so custom [`ScalarValue`]s may keep values not fitting into the
+/// [`DefaultScalarValue`] (like [`i64`]) intact.
+///
+/// # Errors
+///
+/// If the `value` fails to serialize, a primitive cannot be represented by the
+/// [`ScalarValue`], or a map has non-string keys.
+///
+/// [`DefaultScalarValue`]: crate::DefaultScalarValue
+pub fn to_value<S, T>(value: &T) -> Result<Value<S>, SerdeError>
+where
+    S: ScalarValue,
+    T: Serialize + ?Sized,
+{
+    value.serialize(ValueSerializer::new())
+}
+
+/// Serializes the given `value` right into an [`InputValue`], without
+/// building any intermediate representation (like `serde_json::Value`).
+///
+/// Useful for building variables of a request out of typed Rust values.
+///
+/// # Errors
+///
+/// Same as [`to_value()`] does.
+pub fn to_input_value<S, T>(value: &T) -> Result<InputValue<S>, SerdeError>
+where
+    S: ScalarValue,
+    T: Serialize + ?Sized,
+{
+    to_value(value).map(|v: Value<S>| v.to_input_value())
+}
+
+/// [`Serializer`] producing a [`Value`] parametrized with the [`ScalarValue`]
+/// `S`.
+#[derive(Debug)]
+pub struct ValueSerializer<S>(PhantomData<S>);
+
+impl<S> ValueSerializer<S> {
+    /// Creates a new [`ValueSerializer`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<S> Default for ValueSerializer<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Clone for ValueSerializer<S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S> Copy for ValueSerializer<S> {}
+
+/// Converts the given primitive into a [`Value::Scalar`] by deserializing the
+/// [`ScalarValue`] from it.
+macro_rules! serialize_primitive {
+    ($($method:ident($ty:ty)),* $(,)?) => {$(
+        fn $method(self, v: $ty) -> Result<Value<S>, SerdeError> {
+            S::deserialize(v.into_deserializer()).map(Value::Scalar)
+        }
+    )*};
+}
+
+impl<S: ScalarValue> Serializer for ValueSerializer<S> {
+    type Ok = Value<S>;
+    type Error = SerdeError;
+    type SerializeSeq = SerializeList<S>;
+    type SerializeTuple = SerializeList<S>;
+    type SerializeTupleStruct = SerializeList<S>;
+    type SerializeTupleVariant = SerializeVariant<SerializeList<S>>;
+    type SerializeMap = SerializeObject<S>;
+    type SerializeStruct = SerializeObject<S>;
+    type SerializeStructVariant = SerializeVariant<SerializeObject<S>>;
+
+    serialize_primitive! {
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_char(char),
+        serialize_i128(i128),
+        serialize_u128(u128),
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Value<S>, SerdeError> {
+        Ok(Value::scalar(v.to_owned()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Value<S>, SerdeError> {
+        v.iter()
+            .map(|b| self.serialize_u8(*b))
+            .collect::<Result<_, _>>()
+            .map(Value::List)
+    }
+
+    fn serialize_none(self) -> Result<Value<S>, SerdeError> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Value<S>, SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Value<S>, SerdeError> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<Value<S>, SerdeError> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<Value<S>, SerdeError> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<Value<S>, SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Value<S>, SerdeError> {
+        Ok(wrap_variant(variant, value.serialize(self)?))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeList<S>, SerdeError> {
+        Ok(SerializeList(Vec::with_capacity(len.unwrap_or(0))))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeList<S>, SerdeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        len: usize,
+    ) -> Result<SerializeList<S>, SerdeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, SerdeError> {
+        Ok(SerializeVariant {
+            variant,
+            inner: self.serialize_seq(Some(len))?,
+        })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<SerializeObject<S>, SerdeError> {
+        Ok(SerializeObject {
+            object: Object::with_capacity(len.unwrap_or(0)),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _: &'static str,
+        len: usize,
+    ) -> Result<SerializeObject<S>, SerdeError> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant, SerdeError> {
+        Ok(SerializeVariant {
+            variant,
+            inner: self.serialize_map(Some(len))?,
+        })
+    }
+}
+
+/// Serializer of sequences and tuples into a [`Value::List`].
+#[derive(Debug)]
+pub struct SerializeList<S>(Vec<Value<S>>);
+
+impl<S: ScalarValue> ser::SerializeSeq for SerializeList<S> {
+    type Ok = Value<S>;
+    type Error = SerdeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        self.0.push(to_value(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value<S>, SerdeError> {
+        Ok(Value::List(self.0))
+    }
+}
+
+impl<S: ScalarValue> ser::SerializeTuple for SerializeList<S> {
+    type Ok = Value<S>;
+    type Error = SerdeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Value<S>, SerdeError> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl<S: ScalarValue> ser::SerializeTupleStruct for SerializeList<S> {
+    type Ok = Value<S>;
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Value<S>, SerdeError> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+/// Serializer of maps and structs into a [`Value::Object`].
+#[derive(Debug)]
+pub struct SerializeObject<S> {
+    object: Object<S>,
+    key: Option<String>,
+}
+
+impl<S: ScalarValue> ser::SerializeMap for SerializeObject<S> {
+    type Ok = Value<S>;
+    type Error = SerdeError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), SerdeError> {
+        match to_value::<S, _>(key)? {
+            Value::Scalar(s) if s.as_str().is_some() => {
+                self.key = s.into_string();
+                Ok(())
+            }
+            _ => Err(ser::Error::custom("map keys must be strings")),
+        }
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| ser::Error::custom("map value serialized before its key"))?;
+        self.object.add_field(key, to_value(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value<S>, SerdeError> {
+        Ok(Value::Object(self.object))
+    }
+}
+
+impl<S: ScalarValue> ser::SerializeStruct for SerializeObject<S> {
+    type Ok = Value<S>;
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SerdeError> {
+        self.object.add_field(key, to_value(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value<S>, SerdeError> {
+        Ok(Value::Object(self.object))
+    }
+}
+
+/// Serializer of tuple and struct enum variants into a single-entry
+/// [`Value::Object`] keyed by the variant name.
+#[derive(Debug)]
+pub struct SerializeVariant<T> {
+    variant: &'static str,
+    inner: T,
+}
+
+/// Wraps the given `value` into a single-entry [`Value::Object`] keyed by the
+/// `variant` name.
+fn wrap_variant<S>(variant: &'static str, value: Value<S>) -> Value<S> {
+    let mut obj = Object::with_capacity(1);
+    obj.add_field(variant, value);
+    Value::Object(obj)
+}
+
+impl<S: ScalarValue> ser::SerializeTupleVariant for SerializeVariant<SerializeList<S>> {
+    type Ok = Value<S>;
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        ser::SerializeSeq::serialize_element(&mut self.inner, value)
+    }
+
+    fn end(self) -> Result<Value<S>, SerdeError> {
+        let value = ser::SerializeSeq::end(self.inner)?;
+        Ok(wrap_variant(self.variant, value))
+    }
+}
+
+impl<S: ScalarValue> ser::SerializeStructVariant for SerializeVariant<SerializeObject<S>> {
+    type Ok = Value<S>;
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SerdeError> {
+        ser::SerializeStruct::serialize_field(&mut self.inner, key, value)
+    }
+
+    fn end(self) -> Result<Value<S>, SerdeError> {
+        let value = ser::SerializeStruct::end(self.inner)?;
+        Ok(wrap_variant(self.variant, value))
+    }
+}
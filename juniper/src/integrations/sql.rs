@@ -0,0 +1,772 @@
+//! Standard filtering, sorting and pagination input types, translated into SQL
+//! fragments with bound parameters.
+//!
+//! The produced [`SqlFragment`]s don't depend on any database library, so can
+//! be fed into [`diesel::sql_query()`] or [`sqlx::query()`] by binding the
+//! [`SqlValue`]s in their order.
+//!
+//! Per-table filter and sort input objects are generated with the
+//! [`sql_filter!`] and [`sql_order_by!`] macros:
+//!
+//! ```
+//! use juniper::{
+//!     integrations::sql::{IntFilter, Pagination, Placeholder, SqlFragment, StringFilter},
+//!     graphql_input_value, sql_filter, sql_order_by, FromInputValue as _, InputValue,
+//! };
+//!
+//! sql_filter! {
+//!     /// Filter of users.
+//!     pub struct UserFilter {
+//!         id: IntFilter => "id",
+//!         name: StringFilter => "name",
+//!     }
+//! }
+//!
+//! sql_order_by! {
+//!     /// Column of users to sort by.
+//!     pub enum UserColumn {
+//!         Id => "id",
+//!         Name => "name",
+//!     }
+//!
+//!     /// Sorting of users.
+//!     pub struct UserOrderBy;
+//! }
+//!
+//! // As received in the arguments of a field:
+//! let input: InputValue = graphql_input_value!({
+//!     "name": {"like": "J%"},
+//!     "or": [{"id": {"lt": 10}}, {"id": {"in": [42, 43]}}],
+//! });
+//! let filter = UserFilter::from_input_value(&input).unwrap();
+//! let order_by = vec![UserOrderBy::desc(UserColumn::Name)];
+//! let pagination = Pagination { first: Some(20), offset: None };
+//!
+//! let mut query = SqlFragment::from("SELECT id, name FROM users");
+//! query
+//!     .push_filter(&filter)
+//!     .push_order_by(&order_by)
+//!     .push_pagination(&pagination);
+//!
+//! let (sql, binds) = query.build(Placeholder::Dollar);
+//! assert_eq!(
+//!     sql,
+//!     "SELECT id, name FROM users \
+//!      WHERE name LIKE $1 AND ((id < $2) OR (id IN ($3, $4))) \
+//!      ORDER BY name DESC \
+//!      LIMIT 20",
+//! );
+//! assert_eq!(binds.len(), 4);
+//! ```
+//!
+//! [`diesel::sql_query()`]: https://docs.rs/diesel/latest/diesel/fn.sql_query.html
+//! [`sql_filter!`]: crate::sql_filter
+//! [`sql_order_by!`]: crate::sql_order_by
+//! [`sqlx::query()`]: https://docs.rs/sqlx/latest/sqlx/fn.query.html
+
+use std::fmt::Write as _;
+
+use crate::{GraphQLEnum, GraphQLInputObject};
+
+/// Style of the placeholders of bound parameters in the SQL built from a
+/// [`SqlFragment`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Placeholder {
+    /// `?` placeholders, as used by SQLite and MySQL.
+    Question,
+
+    /// `$1`, `$2`, ... placeholders, as used by PostgreSQL.
+    Dollar,
+}
+
+/// Value of a bound parameter of a [`SqlFragment`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum SqlValue {
+    /// `Int` value.
+    Int(i32),
+
+    /// `Float` value.
+    Float(f64),
+
+    /// `String` value.
+    String(String),
+
+    /// `Boolean` value.
+    Boolean(bool),
+}
+
+impl From<i32> for SqlValue {
+    fn from(v: i32) -> Self {
+        Self::Int(v)
+    }
+}
+
+impl From<f64> for SqlValue {
+    fn from(v: f64) -> Self {
+        Self::Float(v)
+    }
+}
+
+impl From<String> for SqlValue {
+    fn from(v: String) -> Self {
+        Self::String(v)
+    }
+}
+
+impl From<bool> for SqlValue {
+    fn from(v: bool) -> Self {
+        Self::Boolean(v)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Part {
+    Sql(String),
+    Bind(SqlValue),
+}
+
+/// Piece of SQL with bound parameters, rendered with concrete placeholders only
+/// once [`build`], so fragments may be freely combined.
+///
+/// [`build`]: SqlFragment::build
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SqlFragment {
+    parts: Vec<Part>,
+}
+
+impl From<&str> for SqlFragment {
+    fn from(sql: &str) -> Self {
+        let mut fragment = Self::default();
+        fragment.push_sql(sql);
+        fragment
+    }
+}
+
+impl SqlFragment {
+    /// Creates a new empty [`SqlFragment`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Indicates whether this [`SqlFragment`] is empty.
+    pub fn is_empty(&self) -> bool {
+        self.parts.is_empty()
+    }
+
+    /// Appends the given raw `sql`.
+    pub fn push_sql(&mut self, sql: &str) -> &mut Self {
+        if !sql.is_empty() {
+            match self.parts.last_mut() {
+                Some(Part::Sql(s)) => s.push_str(sql),
+                _ => self.parts.push(Part::Sql(sql.into())),
+            }
+        }
+        self
+    }
+
+    /// Appends a placeholder of the given bound `value`.
+    pub fn push_bind(&mut self, value: impl Into<SqlValue>) -> &mut Self {
+        self.parts.push(Part::Bind(value.into()));
+        self
+    }
+
+    /// Appends the `other` [`SqlFragment`].
+    pub fn push_fragment(&mut self, other: Self) -> &mut Self {
+        for part in other.parts {
+            match part {
+                Part::Sql(s) => self.push_sql(&s),
+                Part::Bind(v) => self.push_bind(v),
+            };
+        }
+        self
+    }
+
+    /// Appends a ` WHERE` clause of the given `filter`, unless it has no
+    /// conditions.
+    pub fn push_filter(&mut self, filter: &impl SqlFilter) -> &mut Self {
+        let conditions = filter.conditions();
+        if !conditions.is_empty() {
+            self.push_sql(" WHERE ");
+            self.push_fragment(and(conditions));
+        }
+        self
+    }
+
+    /// Appends an ` ORDER BY` clause of the given sorting, unless it's empty.
+    pub fn push_order_by(&mut self, order_by: &[impl SqlOrderBy]) -> &mut Self {
+        for (i, o) in order_by.iter().enumerate() {
+            self.push_sql(if i == 0 { " ORDER BY " } else { ", " });
+            self.push_sql(o.column());
+            self.push_sql(match o.direction() {
+                SortDirection::Asc => " ASC",
+                SortDirection::Desc => " DESC",
+            });
+        }
+        self
+    }
+
+    /// Appends ` LIMIT` and ` OFFSET` clauses of the given `pagination`.
+    ///
+    /// Negative values are treated as `0`.
+    pub fn push_pagination(&mut self, pagination: &Pagination) -> &mut Self {
+        // Values are inlined rather than bound, as not every database accepts
+        // placeholders there.
+        let mut sql = String::new();
+        if let Some(first) = pagination.first {
+            let _ = write!(sql, " LIMIT {}", first.max(0));
+        }
+        if let Some(offset) = pagination.offset {
+            let _ = write!(sql, " OFFSET {}", offset.max(0));
+        }
+        self.push_sql(&sql)
+    }
+
+    /// Renders this [`SqlFragment`] into SQL with the given [`Placeholder`]s,
+    /// along with the values to be bound to them, in order.
+    pub fn build(&self, placeholder: Placeholder) -> (String, Vec<SqlValue>) {
+        let mut sql = String::new();
+        let mut binds = Vec::new();
+        for part in &self.parts {
+            match part {
+                Part::Sql(s) => sql.push_str(s),
+                Part::Bind(v) => {
+                    binds.push(v.clone());
+                    match placeholder {
+                        Placeholder::Question => sql.push('?'),
+                        Placeholder::Dollar => {
+                            let _ = write!(sql, "${}", binds.len());
+                        }
+                    }
+                }
+            }
+        }
+        (sql, binds)
+    }
+}
+
+/// Joins the given `conditions` with `AND`, or returns an always true condition
+/// if there are none.
+fn and(conditions: Vec<SqlFragment>) -> SqlFragment {
+    join(conditions, " AND ", "1 = 1")
+}
+
+fn join(conditions: Vec<SqlFragment>, sep: &str, empty: &str) -> SqlFragment {
+    let mut out = SqlFragment::new();
+    if conditions.is_empty() {
+        out.push_sql(empty);
+    }
+    for (i, c) in conditions.into_iter().enumerate() {
+        if i > 0 {
+            out.push_sql(sep);
+        }
+        out.push_fragment(c);
+    }
+    out
+}
+
+/// Filter translatable into SQL conditions, as generated by the
+/// [`sql_filter!`] macro.
+///
+/// [`sql_filter!`]: crate::sql_filter
+pub trait SqlFilter {
+    /// Returns the conditions of this filter, to be joined with `AND`.
+    fn conditions(&self) -> Vec<SqlFragment>;
+}
+
+/// Filter of a single column translatable into SQL conditions.
+pub trait SqlColumnFilter {
+    /// Returns the conditions of this filter for the given `column`, to be
+    /// joined with `AND`.
+    fn conditions(&self, column: &str) -> Vec<SqlFragment>;
+}
+
+impl<T: SqlColumnFilter> SqlColumnFilter for Option<T> {
+    fn conditions(&self, column: &str) -> Vec<SqlFragment> {
+        self.as_ref()
+            .map(|f| f.conditions(column))
+            .unwrap_or_default()
+    }
+}
+
+#[doc(hidden)]
+pub fn __combine<F: SqlFilter>(
+    mut conditions: Vec<SqlFragment>,
+    and_: &Option<Vec<F>>,
+    or_: &Option<Vec<F>>,
+) -> Vec<SqlFragment> {
+    for f in and_.iter().flatten() {
+        conditions.extend(f.conditions());
+    }
+    if let Some(or_) = or_ {
+        let alternatives = or_
+            .iter()
+            .map(|f| {
+                let mut c = SqlFragment::from("(");
+                c.push_fragment(and(f.conditions())).push_sql(")");
+                c
+            })
+            .collect::<Vec<_>>();
+        let mut c = SqlFragment::from("(");
+        c.push_fragment(join(alternatives, " OR ", "1 = 0"))
+            .push_sql(")");
+        conditions.push(c);
+    }
+    conditions
+}
+
+/// Sorting translatable into an SQL `ORDER BY` item, as generated by the
+/// [`sql_order_by!`] macro.
+///
+/// [`sql_order_by!`]: crate::sql_order_by
+pub trait SqlOrderBy {
+    /// Returns the column to sort by.
+    fn column(&self) -> &'static str;
+
+    /// Returns the [`SortDirection`].
+    fn direction(&self) -> SortDirection;
+}
+
+/// Direction of sorting.
+#[derive(Clone, Copy, Debug, Default, Eq, GraphQLEnum, PartialEq)]
+pub enum SortDirection {
+    /// Ascending order.
+    #[default]
+    Asc,
+
+    /// Descending order.
+    Desc,
+}
+
+/// Offset-based pagination.
+#[derive(Clone, Copy, Debug, Default, Eq, GraphQLInputObject, PartialEq)]
+pub struct Pagination {
+    /// Maximum number of items to return.
+    pub first: Option<i32>,
+
+    /// Number of items to skip.
+    pub offset: Option<i32>,
+}
+
+macro_rules! column_filter {
+    (
+        $(#[$attr:meta])*
+        $name:ident($ty:ty) {
+            $($(#[$op_attr:meta])* $op:ident => $sql:literal),* $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        #[derive(Clone, Debug, Default, GraphQLInputObject, PartialEq)]
+        pub struct $name {
+            /// Equal to the value.
+            pub eq: Option<$ty>,
+
+            /// Not equal to the value.
+            pub ne: Option<$ty>,
+
+            $(
+                $(#[$op_attr])*
+                pub $op: Option<$ty>,
+            )*
+
+            /// Equal to any of the values.
+            #[graphql(name = "in")]
+            pub in_: Option<Vec<$ty>>,
+
+            /// Is `null`, or is not, if `false`.
+            pub is_null: Option<bool>,
+        }
+
+        impl SqlColumnFilter for $name {
+            fn conditions(&self, column: &str) -> Vec<SqlFragment> {
+                let mut out = Vec::new();
+                let ops = vec![(&self.eq, " = "), (&self.ne, " <> "), $((&self.$op, $sql)),*];
+                for (v, op) in ops {
+                    if let Some(v) = v {
+                        let mut c = SqlFragment::from(column);
+                        c.push_sql(op).push_bind(v.clone());
+                        out.push(c);
+                    }
+                }
+                if let Some(values) = &self.in_ {
+                    let mut c = SqlFragment::new();
+                    if values.is_empty() {
+                        c.push_sql("1 = 0");
+                    } else {
+                        c.push_sql(column).push_sql(" IN (");
+                        for (i, v) in values.iter().enumerate() {
+                            if i > 0 {
+                                c.push_sql(", ");
+                            }
+                            c.push_bind(v.clone());
+                        }
+                        c.push_sql(")");
+                    }
+                    out.push(c);
+                }
+                if let Some(is_null) = self.is_null {
+                    let mut c = SqlFragment::from(column);
+                    c.push_sql(if is_null { " IS NULL" } else { " IS NOT NULL" });
+                    out.push(c);
+                }
+                out
+            }
+        }
+    };
+}
+
+column_filter! {
+    /// Filter of an `Int` column.
+    IntFilter(i32) {
+        /// Less than the value.
+        lt => " < ",
+
+        /// Less than or equal to the value.
+        lte => " <= ",
+
+        /// Greater than the value.
+        gt => " > ",
+
+        /// Greater than or equal to the value.
+        gte => " >= ",
+    }
+}
+
+column_filter! {
+    /// Filter of a `Float` column.
+    FloatFilter(f64) {
+        /// Less than the value.
+        lt => " < ",
+
+        /// Less than or equal to the value.
+        lte => " <= ",
+
+        /// Greater than the value.
+        gt => " > ",
+
+        /// Greater than or equal to the value.
+        gte => " >= ",
+    }
+}
+
+column_filter! {
+    /// Filter of a `String` column.
+    StringFilter(String) {
+        /// Less than the value.
+        lt => " < ",
+
+        /// Less than or equal to the value.
+        lte => " <= ",
+
+        /// Greater than the value.
+        gt => " > ",
+
+        /// Greater than or equal to the value.
+        gte => " >= ",
+
+        /// Matches the `LIKE` pattern.
+        like => " LIKE ",
+    }
+}
+
+column_filter! {
+    /// Filter of a `Boolean` column.
+    BooleanFilter(bool) {}
+}
+
+/// Generates a filter input object of a table, implementing [`SqlFilter`].
+///
+/// Each field is a column filter (like [`IntFilter`] or [`StringFilter`])
+/// mapped onto a column. The input object also has the `and` and `or` fields,
+/// accepting lists of nested filters. All the given conditions are joined
+/// with `AND`.
+///
+/// See the [module-level docs](crate::integrations::sql) for an example.
+///
+/// [`IntFilter`]: crate::integrations::sql::IntFilter
+/// [`SqlFilter`]: crate::integrations::sql::SqlFilter
+/// [`StringFilter`]: crate::integrations::sql::StringFilter
+#[macro_export]
+macro_rules! sql_filter {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident {
+            $($field:ident: $ty:ty => $column:literal),* $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        #[derive(Clone, Debug, Default, PartialEq, $crate::GraphQLInputObject)]
+        $vis struct $name {
+            $(pub $field: ::std::option::Option<$ty>,)*
+
+            /// All of the filters match.
+            pub and: ::std::option::Option<::std::vec::Vec<$name>>,
+
+            /// Any of the filters matches.
+            pub or: ::std::option::Option<::std::vec::Vec<$name>>,
+        }
+
+        impl $crate::integrations::sql::SqlFilter for $name {
+            fn conditions(&self) -> ::std::vec::Vec<$crate::integrations::sql::SqlFragment> {
+                #[allow(unused_mut)]
+                let mut conditions = ::std::vec::Vec::new();
+                $(conditions.extend(
+                    $crate::integrations::sql::SqlColumnFilter::conditions(&self.$field, $column),
+                );)*
+                $crate::integrations::sql::__combine(conditions, &self.and, &self.or)
+            }
+        }
+    };
+}
+
+/// Generates an enum of the sortable columns of a table, and a sorting input
+/// object of it, implementing [`SqlOrderBy`].
+///
+/// The sorting input object has the `column` and the `direction` (defaulting
+/// to [`SortDirection::Asc`]) fields.
+///
+/// See the [module-level docs](crate::integrations::sql) for an example.
+///
+/// [`SortDirection::Asc`]: crate::integrations::sql::SortDirection::Asc
+/// [`SqlOrderBy`]: crate::integrations::sql::SqlOrderBy
+#[macro_export]
+macro_rules! sql_order_by {
+    (
+        $(#[$enum_attr:meta])*
+        $enum_vis:vis enum $column:ident {
+            $($variant:ident => $sql:literal),* $(,)?
+        }
+
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident;
+    ) => {
+        $(#[$enum_attr])*
+        #[derive(Clone, Copy, Debug, Eq, PartialEq, $crate::GraphQLEnum)]
+        $enum_vis enum $column {
+            $($variant,)*
+        }
+
+        impl $column {
+            /// Returns the name of this column.
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(Self::$variant => $sql,)*
+                }
+            }
+        }
+
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, Eq, PartialEq, $crate::GraphQLInputObject)]
+        $vis struct $name {
+            /// Column to sort by.
+            pub column: $column,
+
+            /// Direction of sorting.
+            #[graphql(default)]
+            pub direction: $crate::integrations::sql::SortDirection,
+        }
+
+        impl $name {
+            /// Sorts by the given `column` in ascending order.
+            pub fn asc(column: $column) -> Self {
+                Self {
+                    column,
+                    direction: $crate::integrations::sql::SortDirection::Asc,
+                }
+            }
+
+            /// Sorts by the given `column` in descending order.
+            pub fn desc(column: $column) -> Self {
+                Self {
+                    column,
+                    direction: $crate::integrations::sql::SortDirection::Desc,
+                }
+            }
+        }
+
+        impl $crate::integrations::sql::SqlOrderBy for $name {
+            fn column(&self) -> &'static str {
+                self.column.name()
+            }
+
+            fn direction(&self) -> $crate::integrations::sql::SortDirection {
+                self.direction
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        execute_sync, graphql_input_value, graphql_object, graphql_value, graphql_vars,
+        EmptyMutation, EmptySubscription, FromInputValue as _, InputValue, RootNode,
+    };
+
+    use super::{
+        BooleanFilter, IntFilter, Pagination, Placeholder, SortDirection, SqlFragment, SqlValue,
+        StringFilter,
+    };
+
+    sql_filter! {
+        struct PostFilter {
+            id: IntFilter => "id",
+            title: StringFilter => "title",
+            published: BooleanFilter => "is_published",
+        }
+    }
+
+    sql_order_by! {
+        enum PostColumn {
+            Id => "id",
+            Title => "title",
+        }
+
+        struct PostOrderBy;
+    }
+
+    fn filter(input: InputValue) -> (String, Vec<SqlValue>) {
+        let filter = PostFilter::from_input_value(&input).unwrap();
+        let mut sql = SqlFragment::from("SELECT * FROM posts");
+        sql.push_filter(&filter);
+        sql.build(Placeholder::Question)
+    }
+
+    #[test]
+    fn skips_empty_filter() {
+        assert_eq!(
+            filter(graphql_input_value!({})),
+            ("SELECT * FROM posts".into(), vec![]),
+        );
+    }
+
+    #[test]
+    fn joins_conditions_with_and() {
+        assert_eq!(
+            filter(graphql_input_value!({
+                "id": {"gte": 1, "ne": 3},
+                "title": {"isNull": false},
+                "published": {"eq": true},
+            })),
+            (
+                "SELECT * FROM posts \
+                 WHERE id <> ? AND id >= ? AND title IS NOT NULL AND is_published = ?"
+                    .into(),
+                vec![SqlValue::Int(3), SqlValue::Int(1), SqlValue::Boolean(true)],
+            ),
+        );
+    }
+
+    #[test]
+    fn nests_and_or() {
+        assert_eq!(
+            filter(graphql_input_value!({
+                "and": [{"id": {"gt": 1}}, {"id": {"lt": 9}}],
+                "or": [{"title": {"like": "a%"}}, {}],
+            })),
+            (
+                "SELECT * FROM posts WHERE id > ? AND id < ? AND ((title LIKE ?) OR (1 = 1))"
+                    .into(),
+                vec![
+                    SqlValue::Int(1),
+                    SqlValue::Int(9),
+                    SqlValue::String("a%".into()),
+                ],
+            ),
+        );
+        assert_eq!(
+            filter(graphql_input_value!({"or": []})).0,
+            "SELECT * FROM posts WHERE (1 = 0)",
+        );
+    }
+
+    #[test]
+    fn expands_in() {
+        assert_eq!(
+            filter(graphql_input_value!({"id": {"in": [1, 2]}, "title": {"in": []}})),
+            (
+                "SELECT * FROM posts WHERE id IN (?, ?) AND 1 = 0".into(),
+                vec![SqlValue::Int(1), SqlValue::Int(2)],
+            ),
+        );
+    }
+
+    #[test]
+    fn numbers_dollar_placeholders() {
+        let mut sql = SqlFragment::from("a = ");
+        sql.push_bind(1)
+            .push_sql(" AND b = ")
+            .push_bind("x".to_owned());
+
+        assert_eq!(
+            sql.build(Placeholder::Dollar),
+            (
+                "a = $1 AND b = $2".into(),
+                vec![SqlValue::Int(1), SqlValue::String("x".into())],
+            ),
+        );
+    }
+
+    #[test]
+    fn orders_and_paginates() {
+        let input: InputValue = graphql_input_value!({"column": TITLE});
+        let order_by = PostOrderBy::from_input_value(&input).unwrap();
+        assert_eq!(order_by.direction, SortDirection::Asc);
+
+        let mut sql = SqlFragment::from("SELECT * FROM posts");
+        sql.push_order_by(&[order_by, PostOrderBy::desc(PostColumn::Id)])
+            .push_pagination(&Pagination {
+                first: Some(10),
+                offset: Some(-5),
+            });
+
+        assert_eq!(
+            sql.build(Placeholder::Question).0,
+            "SELECT * FROM posts ORDER BY title ASC, id DESC LIMIT 10 OFFSET 0",
+        );
+    }
+
+    #[test]
+    fn resolves_from_arguments() {
+        struct Query;
+
+        #[graphql_object]
+        impl Query {
+            fn posts(
+                filter: Option<PostFilter>,
+                order_by: Option<Vec<PostOrderBy>>,
+                pagination: Option<Pagination>,
+            ) -> String {
+                let mut sql = SqlFragment::from("SELECT * FROM posts");
+                sql.push_filter(&filter.unwrap_or_default())
+                    .push_order_by(&order_by.unwrap_or_default())
+                    .push_pagination(&pagination.unwrap_or_default());
+                sql.build(Placeholder::Dollar).0
+            }
+        }
+
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+        let query = r#"{
+            posts(
+                filter: {or: [{id: {eq: 1}}, {and: [{title: {like: "%x"}}]}]},
+                orderBy: [{column: ID, direction: DESC}],
+                pagination: {first: 5},
+            )
+        }"#;
+
+        assert_eq!(
+            execute_sync(query, None, &schema, &graphql_vars! {}, &()),
+            Ok((
+                graphql_value!({
+                    "posts": "SELECT * FROM posts \
+                              WHERE ((id = $1) OR (title LIKE $2)) \
+                              ORDER BY id DESC LIMIT 5",
+                }),
+                vec![],
+            )),
+        );
+    }
+}
@@ -0,0 +1,182 @@
+//! GraphQL support for [ulid](https://github.com/dylanhart/ulid-rs) types.
+//!
+//! [`Ulid`] is represented in GraphQL as an `ID`, in its canonical
+//! [Crockford's Base32][1] form, so it may replace an [`ID`] in a schema
+//! without any changes on the client side.
+//!
+//! As the `ID` type is shared with the [`ID`] (and all other `ID`-compatible
+//! types) of a schema, an `ID` not being a valid [`Ulid`] passes the
+//! validation, and is rejected only while resolving the field accepting it,
+//! with a field error.
+//!
+//! [1]: https://www.crockford.com/base32.html
+//! [`ID`]: crate::ID
+
+use ulid::Ulid;
+
+use crate::{
+    ast::{FromInputValue, InputValue, Selection, ToInputValue},
+    executor::{ExecutionResult, Executor, Registry},
+    macros::reflect,
+    schema::meta::MetaType,
+    types::{
+        async_await::GraphQLValueAsync,
+        base::{GraphQLType, GraphQLValue},
+        marker::{IsInputType, IsOutputType},
+        scalars::ID,
+    },
+    value::{ScalarValue, Value},
+    BoxFuture,
+};
+
+impl<S: ScalarValue> GraphQLType<S> for Ulid {
+    fn name(_: &()) -> Option<&'static str> {
+        Some("ID")
+    }
+
+    fn meta<'r>(info: &(), registry: &mut Registry<'r, S>) -> MetaType<'r, S>
+    where
+        S: 'r,
+    {
+        <ID as GraphQLType<S>>::meta(info, registry)
+    }
+}
+
+impl<S: ScalarValue> GraphQLValue<S> for Ulid {
+    type Context = ();
+    type TypeInfo = ();
+
+    fn type_name<'i>(&self, info: &'i ()) -> Option<&'i str> {
+        <Self as GraphQLType<S>>::name(info)
+    }
+
+    fn resolve(
+        &self,
+        _: &(),
+        _: Option<&[Selection<S>]>,
+        _: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        Ok(Value::scalar(self.to_string()))
+    }
+}
+
+impl<S: ScalarValue + Send + Sync> GraphQLValueAsync<S> for Ulid {
+    fn resolve_async<'a>(
+        &'a self,
+        info: &'a (),
+        selection_set: Option<&'a [Selection<S>]>,
+        executor: &'a Executor<Self::Context, S>,
+    ) -> BoxFuture<'a, ExecutionResult<S>> {
+        let v = self.resolve(info, selection_set, executor);
+        Box::pin(futures::future::ready(v))
+    }
+}
+
+impl<S: ScalarValue> FromInputValue<S> for Ulid {
+    type Error = String;
+
+    fn from_input_value(v: &InputValue<S>) -> Result<Self, Self::Error> {
+        v.as_string_value()
+            .ok_or_else(|| format!("Expected `String`, found: {}", v))
+            .and_then(|s| {
+                Ulid::from_string(s).map_err(|e| format!("Failed to parse `Ulid`: {}", e))
+            })
+    }
+}
+
+impl<S: ScalarValue> ToInputValue<S> for Ulid {
+    fn to_input_value(&self) -> InputValue<S> {
+        InputValue::scalar(self.to_string())
+    }
+}
+
+impl<S: ScalarValue> IsInputType<S> for Ulid {}
+
+impl<S: ScalarValue> IsOutputType<S> for Ulid {}
+
+impl<S: ScalarValue> reflect::BaseType<S> for Ulid {
+    const NAME: reflect::Type = "ID";
+}
+
+impl<S: ScalarValue> reflect::BaseSubTypes<S> for Ulid {
+    const NAMES: reflect::Types = &[<Self as reflect::BaseType<S>>::NAME];
+}
+
+impl<S: ScalarValue> reflect::WrappedType<S> for Ulid {
+    const VALUE: reflect::WrappedValue = 1;
+}
+
+#[cfg(test)]
+mod test {
+    use ulid::Ulid;
+
+    use crate::{
+        execute_sync, graphql_input_value, graphql_object, graphql_value, graphql_vars,
+        EmptyMutation, EmptySubscription, FromInputValue, InputValue, RootNode, ID,
+    };
+
+    #[test]
+    fn ulid_from_input() {
+        let raw = "01ARZ3NDEKTSV4RRFFQ69G5FAV";
+        let input: InputValue = graphql_input_value!((raw));
+
+        let parsed: Ulid = FromInputValue::from_input_value(&input).unwrap();
+        let id = Ulid::from_string(raw).unwrap();
+
+        assert_eq!(parsed, id);
+    }
+
+    #[test]
+    fn ulid_from_invalid_input() {
+        for input in [graphql_input_value!("not-a-ulid"), graphql_input_value!(42)] {
+            let input: InputValue = input;
+            let parsed = <Ulid as FromInputValue>::from_input_value(&input);
+
+            assert!(parsed.is_err(), "allows input: {:?}", input);
+        }
+    }
+
+    #[test]
+    fn is_id_in_schema() {
+        struct Query;
+
+        #[graphql_object]
+        impl Query {
+            fn ulid(id: Ulid) -> Ulid {
+                id
+            }
+
+            fn id(id: ID) -> ID {
+                id
+            }
+        }
+
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+
+        assert_eq!(
+            execute_sync(
+                r#"{
+                    ulid(id: "01ARZ3NDEKTSV4RRFFQ69G5FAV")
+                    id(id: "1")
+                    __type(name: "ID") { kind }
+                }"#,
+                None,
+                &schema,
+                &graphql_vars! {},
+                &(),
+            ),
+            Ok((
+                graphql_value!({
+                    "ulid": "01ARZ3NDEKTSV4RRFFQ69G5FAV",
+                    "id": "1",
+                    "__type": {"kind": "SCALAR"},
+                }),
+                vec![],
+            )),
+        );
+    }
+}
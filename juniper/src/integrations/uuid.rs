@@ -21,6 +21,30 @@ mod uuid_scalar {
     }
 }
 
+/// Generates a new [UUIDv7][1] out of the current Unix timestamp, so the
+/// generated [`Uuid`]s are sortable by their creation time.
+///
+/// [1]: https://www.rfc-editor.org/rfc/rfc9562#name-uuid-version-7
+#[cfg(feature = "uuid-v7")]
+pub fn now_v7() -> Uuid {
+    Uuid::now_v7()
+}
+
+/// Returns the Unix timestamp (in milliseconds) embedded into the given
+/// [UUIDv7][1], or [`None`] if the given [`Uuid`] is not a UUIDv7.
+///
+/// [1]: https://www.rfc-editor.org/rfc/rfc9562#name-uuid-version-7
+#[cfg(feature = "uuid-v7")]
+pub fn v7_timestamp_millis(id: &Uuid) -> Option<u64> {
+    (id.get_version_num() == 7)
+        .then(|| id.get_timestamp())
+        .flatten()
+        .map(|ts| {
+            let (secs, nanos) = ts.to_unix();
+            secs * 1_000 + u64::from(nanos) / 1_000_000
+        })
+}
+
 #[cfg(test)]
 mod test {
     use uuid::Uuid;
@@ -37,4 +61,28 @@ mod test {
 
         assert_eq!(parsed, id);
     }
+
+    #[cfg(feature = "uuid-v7")]
+    #[test]
+    fn v7_is_time_ordered() {
+        use super::{now_v7, v7_timestamp_millis};
+
+        let (first, second) = (now_v7(), now_v7());
+
+        assert_eq!(first.get_version_num(), 7);
+        assert!(first < second);
+        assert!(v7_timestamp_millis(&first).unwrap() <= v7_timestamp_millis(&second).unwrap());
+    }
+
+    #[cfg(feature = "uuid-v7")]
+    #[test]
+    fn v7_timestamp_of_other_versions() {
+        use super::v7_timestamp_millis;
+
+        let v7 = Uuid::parse_str("017f22e2-79b0-7cc3-98c4-dc0c0c07398f").unwrap();
+        let v4 = Uuid::parse_str("123e4567-e89b-42d3-a456-426655440000").unwrap();
+
+        assert_eq!(v7_timestamp_millis(&v7), Some(1_645_557_742_000));
+        assert_eq!(v7_timestamp_millis(&v4), None);
+    }
 }
@@ -0,0 +1,744 @@
+//! Building a [`SchemaType`] out of a result of the canonical introspection
+//! query.
+
+use std::{borrow::Cow, fmt};
+
+use fnv::FnvHashMap;
+use serde::Deserialize;
+
+use crate::{
+    ast::{FromInputValue, InputValue, Type},
+    executor::Registry,
+    parser::{parse_value_literal, Lexer, ParseError, Parser, ScalarToken, Spanning, Token},
+    schema::{
+        meta::{
+            Argument, DeprecationStatus, EnumMeta, EnumValue, Field, InputObjectMeta,
+            InterfaceMeta, MetaType, ObjectMeta, ScalarMeta, UnionMeta,
+        },
+        model::{DirectiveLocation, DirectiveType, SchemaType},
+    },
+    types::{name::Name, scalars::ID},
+    value::{ParseScalarValue, ScalarValue},
+    FieldError,
+};
+
+/// Error of building a [`SchemaType`] out of an introspection result.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IntrospectionImportError {
+    /// Introspection result has unexpected shape and cannot be deserialized.
+    Malformed(String),
+
+    /// Type has an invalid GraphQL name.
+    InvalidName(String),
+
+    /// Type has an unknown `__TypeKind`.
+    UnknownKind(String),
+
+    /// Type is referenced, but isn't defined in the introspection result.
+    UnknownType(String),
+
+    /// Root operation type is not a GraphQL object.
+    InvalidRootType(String),
+
+    /// Default value of an argument or an input field cannot be parsed.
+    InvalidDefaultValue {
+        /// Name of the argument or the input field.
+        name: String,
+        /// Literal of the default value.
+        value: String,
+    },
+}
+
+impl fmt::Display for IntrospectionImportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Malformed(e) => write!(f, "Malformed introspection result: {}", e),
+            Self::InvalidName(n) => write!(f, "Invalid GraphQL name `{}`", n),
+            Self::UnknownKind(k) => write!(f, "Unknown type kind `{}`", k),
+            Self::UnknownType(n) => write!(f, "Unknown type `{}`", n),
+            Self::InvalidRootType(n) => write!(f, "Root type `{}` is not an object", n),
+            Self::InvalidDefaultValue { name, value } => {
+                write!(f, "Invalid default value `{}` of `{}`", value, name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for IntrospectionImportError {}
+
+/// `__Schema` introspection object.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct IntrospectedSchema {
+    description: Option<String>,
+    query_type: NamedTypeRef,
+    mutation_type: Option<NamedTypeRef>,
+    subscription_type: Option<NamedTypeRef>,
+    types: Vec<IntrospectedType>,
+    #[serde(default)]
+    directives: Vec<IntrospectedDirective>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NamedTypeRef {
+    name: String,
+}
+
+/// `__Type` introspection object being a named type.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IntrospectedType {
+    kind: String,
+    name: String,
+    description: Option<String>,
+    #[serde(alias = "specifiedByURL")]
+    specified_by_url: Option<String>,
+    fields: Option<Vec<IntrospectedField>>,
+    input_fields: Option<Vec<IntrospectedInputValue>>,
+    interfaces: Option<Vec<TypeRef>>,
+    enum_values: Option<Vec<IntrospectedEnumValue>>,
+    possible_types: Option<Vec<TypeRef>>,
+}
+
+/// `__Type` introspection object being a reference to another type.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TypeRef {
+    kind: String,
+    name: Option<String>,
+    of_type: Option<Box<TypeRef>>,
+}
+
+/// `__Field` introspection object.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IntrospectedField {
+    name: String,
+    description: Option<String>,
+    #[serde(default)]
+    args: Vec<IntrospectedInputValue>,
+    #[serde(rename = "type")]
+    ty: TypeRef,
+    #[serde(default)]
+    is_deprecated: bool,
+    deprecation_reason: Option<String>,
+}
+
+/// `__InputValue` introspection object.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IntrospectedInputValue {
+    name: String,
+    description: Option<String>,
+    #[serde(rename = "type")]
+    ty: TypeRef,
+    default_value: Option<String>,
+    #[serde(default)]
+    is_deprecated: bool,
+    deprecation_reason: Option<String>,
+}
+
+/// `__EnumValue` introspection object.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IntrospectedEnumValue {
+    name: String,
+    description: Option<String>,
+    #[serde(default)]
+    is_deprecated: bool,
+    deprecation_reason: Option<String>,
+}
+
+/// `__Directive` introspection object.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IntrospectedDirective {
+    name: String,
+    description: Option<String>,
+    #[serde(default)]
+    locations: Vec<String>,
+    #[serde(default)]
+    args: Vec<IntrospectedInputValue>,
+    #[serde(default)]
+    is_repeatable: bool,
+}
+
+impl TypeRef {
+    fn to_type(&self) -> Result<Type<'static>, IntrospectionImportError> {
+        let of_type = || {
+            self.of_type
+                .as_ref()
+                .ok_or_else(|| {
+                    IntrospectionImportError::Malformed(format!(
+                        "`{}` type reference without `ofType`",
+                        self.kind,
+                    ))
+                })?
+                .to_type()
+        };
+        Ok(match self.kind.as_str() {
+            "NON_NULL" => match of_type()? {
+                Type::Named(n) => Type::NonNullNamed(n),
+                Type::List(t, size) => Type::NonNullList(t, size),
+                t => t,
+            },
+            "LIST" => Type::List(Box::new(of_type()?), None),
+            _ => Type::Named(Cow::Owned(self.name.clone().ok_or_else(|| {
+                IntrospectionImportError::Malformed(format!(
+                    "`{}` type reference without `name`",
+                    self.kind,
+                ))
+            })?)),
+        })
+    }
+}
+
+impl IntrospectedField {
+    fn to_meta<S>(&self) -> Result<Field<'static, S>, IntrospectionImportError> {
+        Ok(Field {
+            name: self.name.as_str().into(),
+            description: self.description.clone(),
+            arguments: if self.args.is_empty() {
+                None
+            } else {
+                Some(
+                    self.args
+                        .iter()
+                        .map(IntrospectedInputValue::to_meta)
+                        .collect::<Result<_, _>>()?,
+                )
+            },
+            field_type: self.ty.to_type()?,
+            deprecation_status: deprecation_status(
+                self.is_deprecated,
+                self.deprecation_reason.as_ref(),
+            ),
+            pii: false,
+            hidden: false,
+            complexity: None,
+        })
+    }
+}
+
+impl IntrospectedInputValue {
+    /// Converts this [`IntrospectedInputValue`] into an [`Argument`].
+    ///
+    /// Default value is omitted, as it can be parsed only once all the schema
+    /// types are known.
+    fn to_meta<S>(&self) -> Result<Argument<'static, S>, IntrospectionImportError> {
+        let mut arg = Argument::new(&self.name, self.ty.to_type()?);
+        arg.description = self.description.clone();
+        arg.deprecation_status =
+            deprecation_status(self.is_deprecated, self.deprecation_reason.as_ref());
+        Ok(arg)
+    }
+}
+
+impl IntrospectedType {
+    fn to_meta<S>(&self) -> Result<MetaType<'static, S>, IntrospectionImportError>
+    where
+        S: ScalarValue,
+    {
+        let name = Name::intern(&self.name);
+        let description = self.description.clone();
+        let fields = || {
+            let mut fields = self
+                .fields
+                .iter()
+                .flatten()
+                .map(IntrospectedField::to_meta)
+                .collect::<Result<Vec<_>, _>>()?;
+            fields.push(Field {
+                name: "__typename".into(),
+                description: None,
+                arguments: None,
+                field_type: Type::NonNullNamed(Cow::Borrowed("String")),
+                deprecation_status: DeprecationStatus::Current,
+                pii: false,
+                hidden: false,
+                complexity: None,
+            });
+            Ok(fields)
+        };
+        let type_names = |types: &Option<Vec<TypeRef>>| {
+            types
+                .iter()
+                .flatten()
+                .map(|t| t.to_type().map(|t| Name::intern(t.innermost_name())))
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        Ok(match self.kind.as_str() {
+            "SCALAR" => MetaType::Scalar(ScalarMeta {
+                name,
+                description,
+                specified_by_url: self.specified_by_url.clone().map(Cow::Owned),
+                try_parse_fn: try_parse_any_scalar,
+                parse_fn: parse_any_scalar_token,
+            }),
+            "OBJECT" => MetaType::Object(ObjectMeta {
+                name,
+                description,
+                fields: fields()?,
+                interface_names: type_names(&self.interfaces)?,
+            }),
+            "INTERFACE" => MetaType::Interface(InterfaceMeta {
+                name,
+                description,
+                fields: fields()?,
+            }),
+            "UNION" => MetaType::Union(UnionMeta {
+                name,
+                description,
+                of_type_names: type_names(&self.possible_types)?,
+            }),
+            "ENUM" => MetaType::Enum(EnumMeta {
+                name,
+                description,
+                values: self
+                    .enum_values
+                    .iter()
+                    .flatten()
+                    .map(|v| EnumValue {
+                        name: v.name.clone(),
+                        description: v.description.clone(),
+                        deprecation_status: deprecation_status(
+                            v.is_deprecated,
+                            v.deprecation_reason.as_ref(),
+                        ),
+                    })
+                    .collect(),
+                try_parse_fn: try_parse_any_enum,
+            }),
+            "INPUT_OBJECT" => MetaType::InputObject(InputObjectMeta {
+                name,
+                description,
+                input_fields: self
+                    .input_fields
+                    .iter()
+                    .flatten()
+                    .map(IntrospectedInputValue::to_meta)
+                    .collect::<Result<_, _>>()?,
+                try_parse_fn: try_parse_any_input_object,
+            }),
+            kind => return Err(IntrospectionImportError::UnknownKind(kind.to_owned())),
+        })
+    }
+}
+
+impl IntrospectedSchema {
+    /// Builds a new [`SchemaType`] out of this [`IntrospectedSchema`].
+    pub(crate) fn into_schema<'a, S>(self) -> Result<SchemaType<'a, S>, IntrospectionImportError>
+    where
+        S: ScalarValue + 'a,
+    {
+        let mut registry = Registry::new(FnvHashMap::default());
+
+        for t in &self.types {
+            if t.name.starts_with("__") || registry.types.contains_key(t.name.as_str()) {
+                continue;
+            }
+            // Built-in scalars are backed by their native implementations.
+            match t.name.as_str() {
+                "Boolean" => drop(registry.get_type::<bool>(&())),
+                "Int" => drop(registry.get_type::<i32>(&())),
+                "Float" => drop(registry.get_type::<f64>(&())),
+                "String" => drop(registry.get_type::<String>(&())),
+                "ID" => drop(registry.get_type::<ID>(&())),
+                _ => {}
+            }
+            if registry.types.contains_key(t.name.as_str()) {
+                continue;
+            }
+            let name = t
+                .name
+                .parse::<Name>()
+                .map_err(|_| IntrospectionImportError::InvalidName(t.name.clone()))?;
+            registry.types.insert(name, t.to_meta()?);
+        }
+
+        check_type_refs(&registry.types)?;
+        let roots = std::iter::once(&self.query_type)
+            .chain(&self.mutation_type)
+            .chain(&self.subscription_type);
+        for root in roots {
+            match registry.types.get(root.name.as_str()) {
+                Some(MetaType::Object(_)) => {}
+                Some(_) => {
+                    return Err(IntrospectionImportError::InvalidRootType(root.name.clone()))
+                }
+                None => return Err(IntrospectionImportError::UnknownType(root.name.clone())),
+            }
+        }
+
+        let mut schema = SchemaType::from_registry(
+            registry,
+            self.query_type.name.into(),
+            self.mutation_type.map(|t| t.name.into()),
+            self.subscription_type.map(|t| t.name.into()),
+        );
+        schema.description = self.description.map(Cow::Owned);
+
+        for d in &self.directives {
+            if schema.directive_by_name(&d.name).is_some() {
+                continue;
+            }
+            let locations = d
+                .locations
+                .iter()
+                .filter_map(|l| {
+                    DirectiveLocation::from_input_value(&InputValue::<S>::enum_value(l)).ok()
+                })
+                .collect::<Vec<_>>();
+            let args = d
+                .args
+                .iter()
+                .map(IntrospectedInputValue::to_meta)
+                .collect::<Result<Vec<_>, _>>()?;
+            let mut directive = DirectiveType::new(&d.name, &locations, &args, d.is_repeatable);
+            directive.description = d.description.clone();
+            schema.add_directive(directive);
+        }
+
+        // Default values are parsed against the already built schema, so the
+        // custom scalars and input objects are resolved properly.
+        let mut defaults = vec![];
+        for t in &self.types {
+            let args = t.input_fields.iter().flatten().map(|f| (None, f)).chain(
+                t.fields.iter().flatten().flat_map(|field| {
+                    field
+                        .args
+                        .iter()
+                        .map(move |a| (Some(field.name.as_str()), a))
+                }),
+            );
+            for (field, arg) in args {
+                if let Some(literal) = &arg.default_value {
+                    let value = parse_default_value(&schema, &arg.ty.to_type()?, literal)
+                        .ok_or_else(|| IntrospectionImportError::InvalidDefaultValue {
+                            name: arg.name.clone(),
+                            value: literal.clone(),
+                        })?;
+                    defaults.push((t.name.as_str(), field, arg.name.as_str(), value));
+                }
+            }
+        }
+        for (type_name, field_name, arg_name, value) in defaults {
+            let arg = match (schema.types.get_mut(type_name), field_name) {
+                (Some(MetaType::InputObject(io)), None) => {
+                    io.input_fields.iter_mut().find(|f| f.name == arg_name)
+                }
+                (Some(MetaType::Object(ObjectMeta { fields, .. })), Some(field_name))
+                | (Some(MetaType::Interface(InterfaceMeta { fields, .. })), Some(field_name)) => {
+                    fields
+                        .iter_mut()
+                        .find(|f| f.name == field_name)
+                        .and_then(|f| f.arguments.as_mut())
+                        .and_then(|args| args.iter_mut().find(|a| a.name == arg_name))
+                }
+                _ => None,
+            };
+            if let Some(arg) = arg {
+                arg.default_value = Some(value);
+            }
+        }
+
+        Ok(schema)
+    }
+}
+
+/// Checks that all the types referenced by the given `types` are defined.
+fn check_type_refs<S>(
+    types: &FnvHashMap<Name, MetaType<'_, S>>,
+) -> Result<(), IntrospectionImportError> {
+    let check = |name: &str| {
+        if types.contains_key(name) {
+            Ok(())
+        } else {
+            Err(IntrospectionImportError::UnknownType(name.to_owned()))
+        }
+    };
+    let check_args = |args: &[Argument<'_, S>]| {
+        args.iter()
+            .try_for_each(|a| check(a.arg_type.innermost_name()))
+    };
+    let check_fields = |fields: &[Field<'_, S>]| {
+        fields.iter().try_for_each(|f| {
+            check(f.field_type.innermost_name())?;
+            check_args(f.arguments.as_deref().unwrap_or_default())
+        })
+    };
+
+    types.values().try_for_each(|t| match t {
+        MetaType::Object(o) => {
+            check_fields(&o.fields)?;
+            o.interface_names.iter().try_for_each(|n| check(n))
+        }
+        MetaType::Interface(i) => check_fields(&i.fields),
+        MetaType::Union(u) => u.of_type_names.iter().try_for_each(|n| check(n)),
+        MetaType::InputObject(io) => check_args(&io.input_fields),
+        _ => Ok(()),
+    })
+}
+
+/// Parses the given default value `literal` of the provided type.
+fn parse_default_value<S>(
+    schema: &SchemaType<'_, S>,
+    ty: &Type<'_>,
+    literal: &str,
+) -> Option<InputValue<S>>
+where
+    S: ScalarValue,
+{
+    let mut lexer = Lexer::new(literal);
+    let mut parser = Parser::new(&mut lexer).ok()?;
+    let value = parse_value_literal(&mut parser, true, schema, schema.lookup_type(ty)).ok()?;
+    if parser.peek().item == Token::EndOfFile {
+        Some(value.item)
+    } else {
+        None
+    }
+}
+
+fn deprecation_status(is_deprecated: bool, reason: Option<&String>) -> DeprecationStatus {
+    if is_deprecated {
+        DeprecationStatus::Deprecated(reason.cloned())
+    } else {
+        DeprecationStatus::Current
+    }
+}
+
+/// Accepts any scalar value of an imported custom scalar, as its parsing rules
+/// are unknown.
+fn try_parse_any_scalar<S: ScalarValue>(v: &InputValue<S>) -> Result<(), FieldError<S>> {
+    match v {
+        InputValue::Scalar(_) | InputValue::Enum(_) | InputValue::Null => Ok(()),
+        _ => Err(FieldError::from(format!("Expected scalar, found: {}", v))),
+    }
+}
+
+fn parse_any_scalar_token<S: ScalarValue>(
+    token: Spanning<ScalarToken<'_>>,
+) -> Result<S, Spanning<ParseError<'_>>> {
+    let (start, end) = (token.start, token.end);
+    let token = token.item;
+    match token {
+        ScalarToken::String(_) | ScalarToken::BlockString(_) => {
+            <String as ParseScalarValue<S>>::from_str(token)
+        }
+        ScalarToken::Int(_) => <i32 as ParseScalarValue<S>>::from_str(token)
+            .or_else(|_| <f64 as ParseScalarValue<S>>::from_str(token)),
+        ScalarToken::Float(_) => <f64 as ParseScalarValue<S>>::from_str(token),
+    }
+    .map_err(|e| Spanning::start_end(&start, &end, e))
+}
+
+/// Accepts any enum value, as the values themselves are checked against the
+/// [`EnumMeta`] separately.
+fn try_parse_any_enum<S: ScalarValue>(v: &InputValue<S>) -> Result<(), FieldError<S>> {
+    match v {
+        InputValue::Enum(_) | InputValue::Null => Ok(()),
+        InputValue::Scalar(s) if s.as_str().is_some() => Ok(()),
+        _ => Err(FieldError::from(format!(
+            "Expected enum value, found: {}",
+            v
+        ))),
+    }
+}
+
+/// Accepts any input object, as its fields are checked against the
+/// [`InputObjectMeta`] separately.
+fn try_parse_any_input_object<S: ScalarValue>(v: &InputValue<S>) -> Result<(), FieldError<S>> {
+    match v {
+        InputValue::Object(_) | InputValue::Null => Ok(()),
+        _ => Err(FieldError::from(format!(
+            "Expected input object, found: {}",
+            v
+        ))),
+    }
+}
+
+/// Builds a new [`SchemaType`] out of the given `json` result of the canonical
+/// introspection query, either wrapped into a `data` field or not.
+pub(crate) fn schema_from_json<'a, S>(
+    json: &str,
+) -> Result<SchemaType<'a, S>, IntrospectionImportError>
+where
+    S: ScalarValue + 'a,
+{
+    let malformed = |e: serde_json::Error| IntrospectionImportError::Malformed(e.to_string());
+
+    let json: serde_json::Value = serde_json::from_str(json).map_err(malformed)?;
+    let schema = json
+        .get("data")
+        .unwrap_or(&json)
+        .get("__schema")
+        .ok_or_else(|| IntrospectionImportError::Malformed("missing `__schema` field".into()))?;
+    IntrospectedSchema::deserialize(schema)
+        .map_err(malformed)?
+        .into_schema()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        graphql_object,
+        introspection::IntrospectionFormat,
+        parser::{parse_document_source, SourcePosition},
+        schema::{
+            meta::{DeprecationStatus, MetaType},
+            model::SchemaType,
+        },
+        tests::fixtures::starwars::schema::{Database, Query},
+        validation::{visit_all_rules, RuleError, ValidatorContext},
+        DefaultScalarValue, EmptyMutation, EmptySubscription, GraphQLEnum, GraphQLInputObject,
+        InputValue, RootNode,
+    };
+
+    use super::IntrospectionImportError;
+
+    fn validate(schema: &SchemaType<DefaultScalarValue>, query: &str) -> Vec<RuleError> {
+        let document = parse_document_source(query, schema).unwrap();
+        let mut ctx = ValidatorContext::new(schema, &document);
+        visit_all_rules(&mut ctx, &document);
+        ctx.into_errors()
+    }
+
+    #[test]
+    fn round_trips_introspection() {
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        );
+        let mut exported = vec![];
+        schema
+            .write_introspection_json(&mut exported, IntrospectionFormat::All)
+            .unwrap();
+
+        let imported = SchemaType::<DefaultScalarValue>::from_introspection_json(
+            std::str::from_utf8(&exported).unwrap(),
+        )
+        .unwrap();
+        let imported = RootNode {
+            schema: imported,
+            ..RootNode::new(
+                Query,
+                EmptyMutation::<Database>::new(),
+                EmptySubscription::<Database>::new(),
+            )
+        };
+        let mut reexported = vec![];
+        imported
+            .write_introspection_json(&mut reexported, IntrospectionFormat::All)
+            .unwrap();
+
+        assert_eq!(
+            std::str::from_utf8(&exported).unwrap(),
+            std::str::from_utf8(&reexported).unwrap(),
+        );
+    }
+
+    #[test]
+    fn validates_against_imported_schema() {
+        #[derive(GraphQLEnum)]
+        enum Color {
+            Red,
+            Green,
+        }
+
+        #[derive(GraphQLInputObject)]
+        struct Filter {
+            color: Color,
+            #[graphql(default = "10")]
+            limit: i32,
+        }
+
+        struct Root;
+
+        #[graphql_object]
+        impl Root {
+            fn count(
+                filter: Filter,
+                #[graphql(default = true)] strict: bool,
+                #[graphql(deprecated = "Ignored.")] offset: Option<i32>,
+            ) -> i32 {
+                let _ = (filter.color, strict, offset);
+                filter.limit
+            }
+        }
+
+        let schema = RootNode::new(
+            Root,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+        let json = schema.introspect(IntrospectionFormat::All).to_string();
+        let wrapped = format!(r#"{{"data": {}}}"#, json);
+
+        let imported = SchemaType::from_introspection_json(&wrapped).unwrap();
+
+        assert_eq!(
+            validate(&imported, "{ count(filter: {color: RED}) }"),
+            vec![]
+        );
+        assert_eq!(
+            validate(&imported, "{ count(filter: {color: BLUE}) }"),
+            vec![RuleError::new(
+                r#"Invalid value for argument "filter", expected type "Filter!""#,
+                &[SourcePosition::new(24, 0, 24)],
+            )],
+        );
+        assert_eq!(
+            validate(&imported, "{ unknown }"),
+            vec![RuleError::new(
+                r#"Unknown field "unknown" on type "Root""#,
+                &[SourcePosition::new(2, 0, 2)],
+            )],
+        );
+
+        match imported.concrete_type_by_name("Filter") {
+            Some(MetaType::InputObject(io)) => assert_eq!(
+                io.input_fields[1].default_value,
+                Some(InputValue::scalar(10)),
+            ),
+            t => panic!("unexpected `Filter` type: {:?}", t),
+        }
+        let strict = imported
+            .concrete_type_by_name("Root")
+            .and_then(|t| t.field_by_name("count"))
+            .and_then(|f| f.arguments.as_ref())
+            .map(|args| args[1].default_value.clone());
+        assert_eq!(strict, Some(Some(InputValue::scalar(true))));
+        let offset = imported
+            .concrete_type_by_name("Root")
+            .and_then(|t| t.field_by_name("count"))
+            .and_then(|f| f.arguments.as_ref())
+            .map(|args| args[2].deprecation_status.clone());
+        assert_eq!(
+            offset,
+            Some(DeprecationStatus::Deprecated(Some("Ignored.".into()))),
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_types() {
+        let json = r#"{"__schema": {
+            "queryType": {"name": "Query"},
+            "types": [{
+                "kind": "OBJECT",
+                "name": "Query",
+                "fields": [{
+                    "name": "foo",
+                    "args": [],
+                    "type": {"kind": "OBJECT", "name": "Foo", "ofType": null}
+                }]
+            }]
+        }}"#;
+
+        assert_eq!(
+            SchemaType::<DefaultScalarValue>::from_introspection_json(json).unwrap_err(),
+            IntrospectionImportError::UnknownType("Foo".into()),
+        );
+    }
+}
@@ -1,3 +1,11 @@
+#[cfg(feature = "serde_json")]
+mod import;
+
+#[cfg(feature = "serde_json")]
+pub(crate) use self::import::schema_from_json;
+#[cfg(feature = "serde_json")]
+pub use self::import::IntrospectionImportError;
+
 /// From <https://github.com/graphql/graphql-js/blob/90bd6ff72625173dd39a1f82cfad9336cfad8f65/src/utilities/getIntrospectionQuery.ts#L62>
 pub(crate) const INTROSPECTION_QUERY: &str = include_str!("./query.graphql");
 pub(crate) const INTROSPECTION_QUERY_WITHOUT_DESCRIPTIONS: &str =
@@ -8,7 +8,9 @@
 // absolute `::juniper` path correctly, without errors.
 extern crate self as juniper;
 
-use std::fmt;
+use std::{fmt, sync::Arc, task::Poll, time::Instant};
+
+use futures::{future, FutureExt as _};
 
 // These are required by the code generated via the `juniper_codegen` macros.
 #[doc(hidden)]
@@ -22,14 +24,18 @@ pub use futures::future::{BoxFuture, LocalBoxFuture};
 // functionality automatically.
 pub use juniper_codegen::{
     graphql_interface, graphql_object, graphql_scalar, graphql_subscription, graphql_union,
-    GraphQLEnum, GraphQLInputObject, GraphQLInterface, GraphQLObject, GraphQLScalar, GraphQLUnion,
+    GraphQLCrud, GraphQLDirective, GraphQLEnum, GraphQLInputObject, GraphQLInterface,
+    GraphQLObject, GraphQLScalar, GraphQLUnion,
 };
 
 #[doc(hidden)]
 #[macro_use]
 pub mod macros;
 mod ast;
+mod builder;
+pub mod dataloader;
 pub mod executor;
+pub mod fuzz;
 mod introspection;
 pub mod parser;
 pub(crate) mod schema;
@@ -50,25 +56,42 @@ pub mod tests;
 #[cfg(test)]
 mod executor_tests;
 
+#[cfg(feature = "bench")]
+pub mod bench;
+
 // Needs to be public because macros use it.
 pub use crate::util::to_camel_case;
 
 use crate::{
-    executor::{execute_validated_query, get_operation},
+    executor::{execute_validated_query, get_operation, incremental_stream, SubsequentPayloads},
     introspection::{INTROSPECTION_QUERY, INTROSPECTION_QUERY_WITHOUT_DESCRIPTIONS},
     parser::parse_document_source,
-    validation::{validate_input_values, visit_all_rules, ValidatorContext},
+    validation::{
+        validate_input_values, visit_all_rules, visit_disabled_introspection, visit_fragment_rules,
+        visit_limit_rules, ValidatorContext,
+    },
 };
 
 pub use crate::{
     ast::{
-        Definition, Document, FromInputValue, InputValue, Operation, OperationType, Selection,
-        ToInputValue, Type,
+        Definition, Directive, Document, Field, Fragment, FragmentSpread, FromInputValue,
+        InlineFragment, InputPathSegment, InputValue, InputValueVisitor, Operation, OperationType,
+        Selection, ToInputValue, Type, VariableDefinition,
     },
+    builder::{OperationBuilder, SelectionSetBuilder},
     executor::{
-        Applies, Context, ExecutionError, ExecutionResult, Executor, FieldError, FieldResult,
-        FromContext, IntoFieldError, IntoResolvable, LookAheadArgument, LookAheadMethods,
-        LookAheadSelection, LookAheadValue, OwnedExecutor, Registry, ValuesStream, Variables,
+        Applies, CachedDocument, Context, DeferredPayload, DocumentCache, EntityResolver,
+        ExecutionError, ExecutionResult, ExecutionStrategy, Executor, ExecutorOptions, FieldError,
+        FieldResult, FromContext, IncrementalPayload, IncrementalStream, IntoFieldError,
+        IntoResolvable, Limits, LookAheadArgument, LookAheadDirective, LookAheadMethods,
+        LookAheadSelection, LookAheadValue, LruDocumentCache, MetricsSink, NPlusOneHook,
+        OperationMeasure, OwnedExecutor, Parallel, ParentValue, PathSegment, PiiField, PiiFields,
+        Projection, ProjectionMap, QueryLogEntry, QueryLogger, Registry, RepeatedField,
+        RequestState, ResponseField, ResponseTransform, Serial, SlowQuery, SlowQueryHook,
+        StreamedPayload, ValuesStream, Variables,
+    },
+    integrations::serde::{
+        from_input_value, from_value, to_input_value, to_value, SerdeError, ValueSerializer,
     },
     introspection::IntrospectionFormat,
     macros::helper::{
@@ -78,24 +101,176 @@ pub use crate::{
     parser::{ParseError, ScalarToken, Spanning},
     schema::{
         meta,
-        model::{RootNode, SchemaType},
+        model::{DirectiveLocation, DirectiveType, RootNode, SchemaError, SchemaType},
     },
     types::{
         async_await::{DynGraphQLValueAsync, GraphQLTypeAsync, GraphQLValueAsync},
         base::{Arguments, DynGraphQLValue, GraphQLType, GraphQLValue, TypeKind},
+        crud::{Crud, CrudStorage},
+        directives::GraphQLDirective,
         marker::{self, GraphQLInterface, GraphQLObject, GraphQLUnion},
+        merged::MergedObject,
         nullable::Nullable,
         scalars::{EmptyMutation, EmptySubscription, ID},
+        sensitive::Sensitive,
         subscriptions::{
             ExecutionOutput, GraphQLSubscriptionType, GraphQLSubscriptionValue,
             SubscriptionConnection, SubscriptionCoordinator,
         },
+        typed_id::{IdTag, TypedId},
+    },
+    validation::{
+        ConfigurableRule, LintIssue, LintIssueKind, LintReport, RuleError, RuleSeverity,
+        ValidationPreset,
+    },
+    value::{
+        DefaultScalarValue, Object, ParseScalarResult, ParseScalarValue, ScalarValue,
+        SpannedParseScalarResult, Value,
     },
-    validation::RuleError,
-    value::{DefaultScalarValue, Object, ParseScalarResult, ParseScalarValue, ScalarValue, Value},
 };
 
-/// An error that prevented query execution
+#[cfg(feature = "serde_json")]
+pub use crate::introspection::IntrospectionImportError;
+
+#[cfg(feature = "rayon")]
+pub use crate::executor::Rayon;
+
+/// An error that prevented a request from being executed, structured by the
+/// phase of the request pipeline it happened in.
+///
+/// Errors of the execution phase (happening while resolving fields) are not
+/// represented here: as required by the [GraphQL spec][1], they don't fail the
+/// request, but are returned along with a (partial) result as
+/// [`ExecutionError`]s. Having them here would make the response data to be
+/// lost (or be carried along with an error), and would tie this type to a
+/// [`ScalarValue`] of the executed schema. Use
+/// [`http::GraphQLResponse::execution_errors()`] to access them along with
+/// this [`RequestError`] (via [`http::GraphQLResponse::request_error()`]).
+///
+/// [1]: https://spec.graphql.org/October2021#sec-Errors
+#[derive(Debug, PartialEq)]
+pub enum RequestError<'a> {
+    /// The document couldn't be parsed.
+    Parse(Spanning<ParseError<'a>>),
+
+    /// The document failed the validation against the schema.
+    Validation(Vec<RuleError>),
+
+    /// The provided variables couldn't be coerced into the types declared by
+    /// the executed operation.
+    VariableCoercion(Vec<RuleError>),
+
+    /// The operation to execute couldn't be selected, or it's not of the
+    /// expected type.
+    Operation(OperationError),
+}
+
+/// An error of selecting an operation to execute out of a document.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OperationError {
+    /// The document doesn't contain any operations.
+    NoOperationProvided,
+
+    /// The document contains multiple operations, but no operation name is
+    /// provided.
+    MultipleOperationsProvided,
+
+    /// The document doesn't contain an operation with the provided name.
+    UnknownOperationName,
+
+    /// The operation is a subscription, but a query or a mutation is expected.
+    IsSubscription,
+
+    /// The operation is a query or a mutation, but a subscription is expected.
+    NotSubscription,
+}
+
+impl<'a> RequestError<'a> {
+    /// Returns the [`SourcePosition`]s in the document this [`RequestError`]
+    /// relates to, if any.
+    ///
+    /// [`SourcePosition`]: parser::SourcePosition
+    pub fn locations(&self) -> Vec<parser::SourcePosition> {
+        match self {
+            Self::Parse(e) => vec![e.start],
+            Self::Validation(errs) | Self::VariableCoercion(errs) => errs
+                .iter()
+                .flat_map(|e| e.locations().iter().copied())
+                .collect(),
+            Self::Operation(_) => vec![],
+        }
+    }
+}
+
+impl<'a> fmt::Display for RequestError<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Parse(e) => write!(f, "{}", e),
+            Self::Validation(errs) | Self::VariableCoercion(errs) => {
+                for e in errs {
+                    writeln!(f, "{}", e)?;
+                }
+                Ok(())
+            }
+            Self::Operation(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl<'a> std::error::Error for RequestError<'a> {}
+
+impl fmt::Display for OperationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::NoOperationProvided => "No operation provided",
+            Self::MultipleOperationsProvided => "Multiple operations provided",
+            Self::UnknownOperationName => "Unknown operation name",
+            Self::IsSubscription => "Operation is a subscription",
+            Self::NotSubscription => "Operation is not a subscription",
+        })
+    }
+}
+
+impl std::error::Error for OperationError {}
+
+impl<'a> From<Spanning<ParseError<'a>>> for RequestError<'a> {
+    fn from(e: Spanning<ParseError<'a>>) -> Self {
+        Self::Parse(e)
+    }
+}
+
+impl<'a> From<OperationError> for RequestError<'a> {
+    fn from(e: OperationError) -> Self {
+        Self::Operation(e)
+    }
+}
+
+#[allow(deprecated)]
+impl<'a> From<GraphQLError<'a>> for RequestError<'a> {
+    fn from(e: GraphQLError<'a>) -> Self {
+        match e {
+            GraphQLError::ParseError(e) => Self::Parse(e),
+            GraphQLError::ValidationError(errs) => Self::Validation(errs),
+            GraphQLError::NoOperationProvided => OperationError::NoOperationProvided.into(),
+            GraphQLError::MultipleOperationsProvided => {
+                OperationError::MultipleOperationsProvided.into()
+            }
+            GraphQLError::UnknownOperationName => OperationError::UnknownOperationName.into(),
+            GraphQLError::IsSubscription => OperationError::IsSubscription.into(),
+            GraphQLError::NotSubscription => OperationError::NotSubscription.into(),
+        }
+    }
+}
+
+/// An error that prevented query execution.
+///
+/// Superseded by [`RequestError`], which is returned by the request pipeline
+/// now. Kept for backward compatibility, and is convertible from/to
+/// [`RequestError`].
+#[deprecated(
+    since = "0.16.0",
+    note = "Use `RequestError` instead, converting via `From` where needed."
+)]
 #[derive(Debug, PartialEq)]
 #[allow(missing_docs)]
 pub enum GraphQLError<'a> {
@@ -108,6 +283,7 @@ pub enum GraphQLError<'a> {
     NotSubscription,
 }
 
+#[allow(deprecated)]
 impl<'a> fmt::Display for GraphQLError<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -127,6 +303,7 @@ impl<'a> fmt::Display for GraphQLError<'a> {
     }
 }
 
+#[allow(deprecated)]
 impl<'a> std::error::Error for GraphQLError<'a> {}
 
 /// Execute a query synchronously in a provided schema
@@ -136,36 +313,197 @@ pub fn execute_sync<'a, S, QueryT, MutationT, SubscriptionT>(
     root_node: &'a RootNode<QueryT, MutationT, SubscriptionT, S>,
     variables: &Variables<S>,
     context: &QueryT::Context,
-) -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+) -> Result<(Value<S>, Vec<ExecutionError<S>>), RequestError<'a>>
 where
     S: ScalarValue,
     QueryT: GraphQLType<S>,
     MutationT: GraphQLType<S, Context = QueryT::Context>,
     SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
 {
-    let document = parse_document_source(document_source, &root_node.schema)?;
+    execute_sync_with_limits(
+        document_source,
+        operation_name,
+        root_node,
+        variables,
+        context,
+        root_node.schema.limits(),
+    )
+}
 
-    {
-        let mut ctx = ValidatorContext::new(&root_node.schema, &document);
-        visit_all_rules(&mut ctx, &document);
+/// Execute a query synchronously in a provided schema, overriding the default
+/// [`Limits`] of the schema for this single request.
+pub fn execute_sync_with_limits<'a, S, QueryT, MutationT, SubscriptionT>(
+    document_source: &'a str,
+    operation_name: Option<&str>,
+    root_node: &'a RootNode<QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+    limits: &Limits,
+) -> Result<(Value<S>, Vec<ExecutionError<S>>), RequestError<'a>>
+where
+    S: ScalarValue,
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S, Context = QueryT::Context>,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+{
+    execute_sync_persisted(
+        document_source,
+        None,
+        operation_name,
+        root_node,
+        variables,
+        context,
+        limits,
+    )
+}
 
-        let errors = ctx.into_errors();
-        if !errors.is_empty() {
-            return Err(GraphQLError::ValidationError(errors));
+/// Executes a query synchronously, looking its document up in the
+/// [`DocumentCache`] of the schema by the given SHA-256 `persisted_hash` of its
+/// text, if any.
+pub(crate) fn execute_sync_persisted<'a, S, QueryT, MutationT, SubscriptionT>(
+    document_source: &'a str,
+    persisted_hash: Option<&str>,
+    operation_name: Option<&str>,
+    root_node: &'a RootNode<QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+    limits: &Limits,
+) -> Result<(Value<S>, Vec<ExecutionError<S>>), RequestError<'a>>
+where
+    S: ScalarValue,
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S, Context = QueryT::Context>,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+{
+    let started = Instant::now();
+    let result = execute_sync_unlogged(
+        document_source,
+        persisted_hash,
+        operation_name,
+        root_node,
+        variables,
+        context,
+        limits,
+    );
+    root_node.log_query(
+        document_source,
+        operation_name,
+        variables,
+        context,
+        started,
+        &result,
+    );
+    result
+}
+
+/// Executes a query synchronously, without logging it into the
+/// [`QueryLogger`] of the schema.
+fn execute_sync_unlogged<'a, S, QueryT, MutationT, SubscriptionT>(
+    document_source: &'a str,
+    persisted_hash: Option<&str>,
+    operation_name: Option<&str>,
+    root_node: &'a RootNode<QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+    limits: &Limits,
+) -> Result<(Value<S>, Vec<ExecutionError<S>>), RequestError<'a>>
+where
+    S: ScalarValue,
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S, Context = QueryT::Context>,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+{
+    if let Some(cached) = root_node.cached_document(document_source, persisted_hash) {
+        if !root_node.allows_introspection(context) && !cached.introspection_errors().is_empty() {
+            return Err(RequestError::Validation(
+                cached.introspection_errors().to_vec(),
+            ));
         }
+
+        let document = cached.document();
+        validate_cached_limits(document, &root_node.schema, limits)?;
+        let operation = prepare_operation(
+            document,
+            operation_name,
+            &root_node.schema,
+            variables,
+            limits,
+        )?;
+        return execute_validated_query(document, operation, root_node, variables, context, limits);
     }
 
-    let operation = get_operation(&document, operation_name)?;
+    let document = parse_document_source(document_source, &root_node.schema)?;
 
-    {
-        let errors = validate_input_values(variables, operation, &root_node.schema);
+    validate_document(
+        &document,
+        &root_node.schema,
+        root_node.allows_introspection(context),
+        limits,
+    )?;
 
-        if !errors.is_empty() {
-            return Err(GraphQLError::ValidationError(errors));
-        }
-    }
+    let operation = prepare_operation(
+        &document,
+        operation_name,
+        &root_node.schema,
+        variables,
+        limits,
+    )?;
 
-    execute_validated_query(&document, operation, root_node, variables, context)
+    execute_validated_query(&document, operation, root_node, variables, context, limits)
+}
+
+/// Execute a query in a provided schema synchronously, resolving the sibling
+/// fields of its selection sets in parallel on the current [`rayon`] thread
+/// pool.
+///
+/// Mutations are never resolved on the pool, but with their
+/// [`ExecutorOptions::mutation_strategy()`] on the current thread, as their
+/// root fields may be required to be resolved serially.
+///
+/// The types are required to implement [`GraphQLTypeAsync`] (as the ones
+/// defined via the `juniper` macros do), as the fields are resolved via their
+/// asynchronous resolvers, calling the synchronous ones where there are no
+/// `async` resolvers. This way the resolvers may await each other (like the
+/// keys batched by a [`DataLoader`]) without blocking the threads of the pool,
+/// which resolve the other fields meanwhile. Only the current thread is
+/// blocked until the whole query is resolved, so this function should never be
+/// called on the threads of an asynchronous runtime.
+///
+/// See also the [`Rayon`] execution strategy.
+///
+/// [`DataLoader`]: crate::dataloader::DataLoader
+#[cfg(feature = "rayon")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+pub fn execute_sync_parallel<'a, S, QueryT, MutationT, SubscriptionT>(
+    document_source: &'a str,
+    operation_name: Option<&str>,
+    root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+) -> Result<(Value<S>, Vec<ExecutionError<S>>), RequestError<'a>>
+where
+    QueryT: GraphQLTypeAsync<S>,
+    QueryT::TypeInfo: Sync,
+    QueryT::Context: Sync,
+    MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+    MutationT::TypeInfo: Sync,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context> + Sync,
+    SubscriptionT::TypeInfo: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    let state = RequestState::new();
+    state.get_or_init(|| executor::SyncParallel);
+
+    executor::block_on(execute_logged(
+        document_source,
+        None,
+        operation_name,
+        root_node,
+        variables,
+        context,
+        root_node.schema.limits(),
+        Arc::new(state),
+    ))
 }
 
 /// Execute a query in a provided schema
@@ -175,7 +513,7 @@ pub async fn execute<'a, S, QueryT, MutationT, SubscriptionT>(
     root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
     variables: &Variables<S>,
     context: &QueryT::Context,
-) -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+) -> Result<(Value<S>, Vec<ExecutionError<S>>), RequestError<'a>>
 where
     QueryT: GraphQLTypeAsync<S>,
     QueryT::TypeInfo: Sync,
@@ -186,30 +524,313 @@ where
     SubscriptionT::TypeInfo: Sync,
     S: ScalarValue + Send + Sync,
 {
-    let document = parse_document_source(document_source, &root_node.schema)?;
+    execute_with_limits(
+        document_source,
+        operation_name,
+        root_node,
+        variables,
+        context,
+        root_node.schema.limits(),
+    )
+    .await
+}
 
-    {
-        let mut ctx = ValidatorContext::new(&root_node.schema, &document);
-        visit_all_rules(&mut ctx, &document);
+/// Execute a query in a provided schema, overriding the default [`Limits`] of
+/// the schema for this single request.
+pub async fn execute_with_limits<'a, S, QueryT, MutationT, SubscriptionT>(
+    document_source: &'a str,
+    operation_name: Option<&str>,
+    root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+    limits: &Limits,
+) -> Result<(Value<S>, Vec<ExecutionError<S>>), RequestError<'a>>
+where
+    QueryT: GraphQLTypeAsync<S>,
+    QueryT::TypeInfo: Sync,
+    QueryT::Context: Sync,
+    MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+    MutationT::TypeInfo: Sync,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context> + Sync,
+    SubscriptionT::TypeInfo: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    execute_persisted(
+        document_source,
+        None,
+        operation_name,
+        root_node,
+        variables,
+        context,
+        limits,
+    )
+    .await
+}
 
-        let errors = ctx.into_errors();
-        if !errors.is_empty() {
-            return Err(GraphQLError::ValidationError(errors));
+/// Executes a query, looking its document up in the [`DocumentCache`] of the
+/// schema by the given SHA-256 `persisted_hash` of its text, if any.
+pub(crate) async fn execute_persisted<'a, S, QueryT, MutationT, SubscriptionT>(
+    document_source: &'a str,
+    persisted_hash: Option<&str>,
+    operation_name: Option<&str>,
+    root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+    limits: &Limits,
+) -> Result<(Value<S>, Vec<ExecutionError<S>>), RequestError<'a>>
+where
+    QueryT: GraphQLTypeAsync<S>,
+    QueryT::TypeInfo: Sync,
+    QueryT::Context: Sync,
+    MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+    MutationT::TypeInfo: Sync,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context> + Sync,
+    SubscriptionT::TypeInfo: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    execute_logged(
+        document_source,
+        persisted_hash,
+        operation_name,
+        root_node,
+        variables,
+        context,
+        limits,
+        Arc::new(RequestState::new()),
+    )
+    .await
+}
+
+/// Execute a query in a provided schema, delivering the fragments marked with
+/// the `@defer` directive and the list items marked with the `@stream`
+/// directive separately from the rest of it.
+///
+/// The returned [`IncrementalStream`] starts with the
+/// [`IncrementalPayload::Initial`] payload, having the deferred fragments and
+/// the streamed list items omitted from its data, followed by an
+/// [`IncrementalPayload::Deferred`] payload for each deferred fragment and an
+/// [`IncrementalPayload::Streamed`] payload for each streamed list item. The
+/// payloads nested inside other deferred fragments or streamed items always
+/// follow the payloads of the latter.
+///
+/// The `@defer` and `@stream` directives should be enabled via
+/// [`RootNode::enable_defer()`] and [`RootNode::enable_stream()`]. They're
+/// honored by the asynchronous resolvers only, so the fragments and the lists
+/// of the types resolved synchronously are resolved in place. The operation is
+/// executed while the returned [`IncrementalStream`] is polled: the initial
+/// payload is yielded as soon as its data is resolved, and each subsequent one
+/// as soon as its deferred fragment or streamed item is.
+pub async fn execute_incremental<'a, S, QueryT, MutationT, SubscriptionT>(
+    document_source: &'a str,
+    operation_name: Option<&'a str>,
+    root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &'a QueryT::Context,
+) -> Result<IncrementalStream<'a, S>, RequestError<'a>>
+where
+    QueryT: GraphQLTypeAsync<S>,
+    QueryT::TypeInfo: Sync,
+    QueryT::Context: Sync,
+    MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+    MutationT::TypeInfo: Sync,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context> + Sync,
+    SubscriptionT::TypeInfo: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    execute_incremental_persisted(
+        document_source,
+        None,
+        operation_name,
+        root_node,
+        variables.clone(),
+        context,
+        root_node.schema.limits(),
+    )
+    .await
+}
+
+/// Executes a query incrementally, looking its document up in the
+/// [`DocumentCache`] of the schema by the given SHA-256 `persisted_hash` of
+/// its text, if any.
+///
+/// Returns once the initial payload is resolved, so the request errors are
+/// returned as such.
+pub(crate) async fn execute_incremental_persisted<'a, S, QueryT, MutationT, SubscriptionT>(
+    document_source: &'a str,
+    persisted_hash: Option<&'a str>,
+    operation_name: Option<&'a str>,
+    root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+    variables: Variables<S>,
+    context: &'a QueryT::Context,
+    limits: &'a Limits,
+) -> Result<IncrementalStream<'a, S>, RequestError<'a>>
+where
+    QueryT: GraphQLTypeAsync<S>,
+    QueryT::TypeInfo: Sync,
+    QueryT::Context: Sync,
+    MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+    MutationT::TypeInfo: Sync,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context> + Sync,
+    SubscriptionT::TypeInfo: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    let state = RequestState::new();
+    state.get_or_default::<SubsequentPayloads<S>>();
+    let state = Arc::new(state);
+
+    let mut execution = {
+        let state = Arc::clone(&state);
+        async move {
+            let (data, errors) = execute_logged(
+                document_source,
+                persisted_hash,
+                operation_name,
+                root_node,
+                &variables,
+                context,
+                limits,
+                Arc::clone(&state),
+            )
+            .await?;
+            if let Some(payloads) = state.get::<SubsequentPayloads<S>>() {
+                payloads.start(ExecutionOutput { data, errors });
+            }
+            Ok::<_, RequestError<'a>>(())
         }
-    }
+        .boxed()
+    };
 
-    let operation = get_operation(&document, operation_name)?;
+    let is_started = || {
+        state
+            .get::<SubsequentPayloads<S>>()
+            .is_some_and(SubsequentPayloads::is_started)
+    };
+    let finished = future::poll_fn(|cx| match execution.poll_unpin(cx) {
+        Poll::Ready(res) => Poll::Ready(Some(res)),
+        Poll::Pending if is_started() => Poll::Ready(None),
+        Poll::Pending => Poll::Pending,
+    })
+    .await;
 
-    {
-        let errors = validate_input_values(variables, operation, &root_node.schema);
+    let execution = match finished {
+        Some(res) => res.map(|()| None)?,
+        None => Some(execution.map(drop).boxed()),
+    };
+    Ok(incremental_stream(execution, state))
+}
 
-        if !errors.is_empty() {
-            return Err(GraphQLError::ValidationError(errors));
+/// Executes a query with the given [`RequestState`], logging it into the
+/// [`QueryLogger`] of the schema.
+#[allow(clippy::too_many_arguments)]
+async fn execute_logged<'a, S, QueryT, MutationT, SubscriptionT>(
+    document_source: &'a str,
+    persisted_hash: Option<&str>,
+    operation_name: Option<&str>,
+    root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+    limits: &Limits,
+    state: Arc<RequestState>,
+) -> Result<(Value<S>, Vec<ExecutionError<S>>), RequestError<'a>>
+where
+    QueryT: GraphQLTypeAsync<S>,
+    QueryT::TypeInfo: Sync,
+    QueryT::Context: Sync,
+    MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+    MutationT::TypeInfo: Sync,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context> + Sync,
+    SubscriptionT::TypeInfo: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    let started = Instant::now();
+    let result = execute_unlogged(
+        document_source,
+        persisted_hash,
+        operation_name,
+        root_node,
+        variables,
+        context,
+        limits,
+        state,
+    )
+    .await;
+    root_node.log_query(
+        document_source,
+        operation_name,
+        variables,
+        context,
+        started,
+        &result,
+    );
+    result
+}
+
+/// Executes a query with the given [`RequestState`], without logging it into
+/// the [`QueryLogger`] of the schema.
+#[allow(clippy::too_many_arguments)]
+async fn execute_unlogged<'a, S, QueryT, MutationT, SubscriptionT>(
+    document_source: &'a str,
+    persisted_hash: Option<&str>,
+    operation_name: Option<&str>,
+    root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+    limits: &Limits,
+    state: Arc<RequestState>,
+) -> Result<(Value<S>, Vec<ExecutionError<S>>), RequestError<'a>>
+where
+    QueryT: GraphQLTypeAsync<S>,
+    QueryT::TypeInfo: Sync,
+    QueryT::Context: Sync,
+    MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+    MutationT::TypeInfo: Sync,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context> + Sync,
+    SubscriptionT::TypeInfo: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    if let Some(cached) = root_node.cached_document(document_source, persisted_hash) {
+        if !root_node.allows_introspection(context) && !cached.introspection_errors().is_empty() {
+            return Err(RequestError::Validation(
+                cached.introspection_errors().to_vec(),
+            ));
         }
+
+        let document = cached.document();
+        validate_cached_limits(document, &root_node.schema, limits)?;
+        let operation = prepare_operation(
+            document,
+            operation_name,
+            &root_node.schema,
+            variables,
+            limits,
+        )?;
+        return executor::execute_validated_query_async_with_state(
+            document, operation, root_node, variables, context, limits, state,
+        )
+        .await;
     }
 
-    executor::execute_validated_query_async(&document, operation, root_node, variables, context)
-        .await
+    let document = parse_document_source(document_source, &root_node.schema)?;
+
+    validate_document(
+        &document,
+        &root_node.schema,
+        root_node.allows_introspection(context),
+        limits,
+    )?;
+
+    let operation = prepare_operation(
+        &document,
+        operation_name,
+        &root_node.schema,
+        variables,
+        limits,
+    )?;
+
+    executor::execute_validated_query_async_with_state(
+        &document, operation, root_node, variables, context, limits, state,
+    )
+    .await
 }
 
 /// Resolve subscription into `ValuesStream`
@@ -219,7 +840,38 @@ pub async fn resolve_into_stream<'a, S, QueryT, MutationT, SubscriptionT>(
     root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
     variables: &Variables<S>,
     context: &'a QueryT::Context,
-) -> Result<(Value<ValuesStream<'a, S>>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+) -> Result<(Value<ValuesStream<'a, S>>, Vec<ExecutionError<S>>), RequestError<'a>>
+where
+    QueryT: GraphQLTypeAsync<S>,
+    QueryT::TypeInfo: Sync,
+    QueryT::Context: Sync,
+    MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+    MutationT::TypeInfo: Sync,
+    SubscriptionT: GraphQLSubscriptionType<S, Context = QueryT::Context>,
+    SubscriptionT::TypeInfo: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    resolve_into_stream_with_limits(
+        document_source,
+        operation_name,
+        root_node,
+        variables,
+        context,
+        root_node.schema.limits(),
+    )
+    .await
+}
+
+/// Resolve subscription into `ValuesStream`, overriding the default [`Limits`]
+/// of the schema for this single request.
+pub async fn resolve_into_stream_with_limits<'a, S, QueryT, MutationT, SubscriptionT>(
+    document_source: &'a str,
+    operation_name: Option<&str>,
+    root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &'a QueryT::Context,
+    limits: &Limits,
+) -> Result<(Value<ValuesStream<'a, S>>, Vec<ExecutionError<S>>), RequestError<'a>>
 where
     QueryT: GraphQLTypeAsync<S>,
     QueryT::TypeInfo: Sync,
@@ -233,27 +885,373 @@ where
     let document: crate::ast::OwnedDocument<'a, S> =
         parse_document_source(document_source, &root_node.schema)?;
 
+    validate_document(
+        &document,
+        &root_node.schema,
+        root_node.allows_introspection(context),
+        limits,
+    )?;
+
+    let operation = prepare_operation(
+        &document,
+        operation_name,
+        &root_node.schema,
+        variables,
+        limits,
+    )?;
+
+    executor::resolve_validated_subscription(&document, operation, root_node, variables, context)
+        .await
+}
+
+/// Checks the cached `document` against the `limits` of a single request, as
+/// it's validated regardless of any once cached.
+fn validate_cached_limits<S: ScalarValue>(
+    document: &Document<S>,
+    schema: &SchemaType<S>,
+    limits: &Limits,
+) -> Result<(), RequestError<'static>> {
+    let mut ctx = ValidatorContext::new(schema, document);
+    ctx.set_limits(limits);
+    visit_limit_rules(&mut ctx, document);
+
+    let errors = ctx.into_errors();
+    if !errors.is_empty() {
+        return Err(RequestError::Validation(errors));
+    }
+    Ok(())
+}
+
+/// Validates the freshly parsed `document` against the `schema` and the
+/// `limits` of a single request, rejecting the introspection queries unless
+/// `introspection` is allowed.
+pub(crate) fn validate_document<S: ScalarValue>(
+    document: &Document<S>,
+    schema: &SchemaType<S>,
+    introspection: bool,
+    limits: &Limits,
+) -> Result<(), RequestError<'static>> {
+    let mut ctx = ValidatorContext::new(schema, document);
+    ctx.set_limits(limits);
+    visit_all_rules(&mut ctx, document);
+    if !introspection {
+        visit_disabled_introspection(&mut ctx, document);
+    }
+    ctx.record_metrics();
+
+    let errors = ctx.into_errors();
+    if !errors.is_empty() {
+        return Err(RequestError::Validation(errors));
+    }
+    Ok(())
+}
+
+/// Selects the operation to execute out of the already validated `document`,
+/// and checks it against the `limits` along with the provided `variables`.
+pub(crate) fn prepare_operation<'b, 'd, S: ScalarValue>(
+    document: &'b Document<'d, S>,
+    operation_name: Option<&str>,
+    schema: &SchemaType<S>,
+    variables: &Variables<S>,
+    limits: &Limits,
+) -> Result<&'b Spanning<Operation<'d, S>>, RequestError<'static>> {
+    let operation = get_operation(document, operation_name)?;
+
+    let errors = limits.check(
+        schema,
+        document,
+        operation,
+        variables,
+        schema.executor_options().sink(),
+    );
+    if !errors.is_empty() {
+        return Err(RequestError::Validation(errors));
+    }
+
+    let errors = validate_input_values(variables, operation, schema);
+    if !errors.is_empty() {
+        return Err(RequestError::VariableCoercion(errors));
+    }
+
+    PiiFields::record(
+        schema,
+        document,
+        operation,
+        schema.executor_options().sink(),
+    );
+
+    Ok(operation)
+}
+
+/// Execute the selection set of a fragment synchronously against the given
+/// `value` of some type in a provided schema, rather than against its query
+/// root.
+///
+/// The `document_source` should contain the fragment to execute (selected by
+/// its `fragment_name`, if there are multiple ones) along with the fragments
+/// it spreads, and its type condition should apply to the type of the `value`.
+/// [`OperationError`]s are returned if the fragment cannot be selected.
+///
+/// Allows rendering a saved fragment against a cached entity, for example.
+/// Neither the [`ResponseTransform`] nor the hooks of the schema are applied,
+/// as there is no operation being executed.
+///
+/// ```rust
+/// # use juniper::{
+/// #     execute_fragment_sync, graphql_object, graphql_value, EmptyMutation, EmptySubscription,
+/// #     RootNode, Variables,
+/// # };
+/// #
+/// struct User {
+///     name: String,
+/// }
+///
+/// #[graphql_object]
+/// impl User {
+///     fn name(&self) -> &str {
+///         &self.name
+///     }
+/// }
+///
+/// struct Query;
+///
+/// #[graphql_object]
+/// impl Query {
+///     fn user() -> User {
+///         User { name: "Alice".into() }
+///     }
+/// }
+///
+/// let schema = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::<()>::new());
+/// let cached = User { name: "Bob".into() };
+///
+/// let (res, errors) = execute_fragment_sync(
+///     "fragment UserCard on User { name }",
+///     None,
+///     &schema,
+///     &cached,
+///     &(),
+///     &Variables::new(),
+///     &(),
+/// )
+/// .unwrap();
+/// assert_eq!(res, graphql_value!({"name": "Bob"}));
+/// assert!(errors.is_empty());
+/// ```
+pub fn execute_fragment_sync<'a, S, T, QueryT, MutationT, SubscriptionT>(
+    document_source: &'a str,
+    fragment_name: Option<&str>,
+    root_node: &'a RootNode<QueryT, MutationT, SubscriptionT, S>,
+    value: &T,
+    info: &T::TypeInfo,
+    variables: &Variables<S>,
+    context: &T::Context,
+) -> Result<(Value<S>, Vec<ExecutionError<S>>), RequestError<'a>>
+where
+    S: ScalarValue,
+    T: GraphQLType<S>,
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S, Context = QueryT::Context>,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+{
+    let document = parse_document_source(document_source, &root_node.schema)?;
+
     {
         let mut ctx = ValidatorContext::new(&root_node.schema, &document);
-        visit_all_rules(&mut ctx, &document);
+        visit_fragment_rules(&mut ctx, &document);
+        ctx.record_metrics();
 
         let errors = ctx.into_errors();
         if !errors.is_empty() {
-            return Err(GraphQLError::ValidationError(errors));
+            return Err(RequestError::Validation(errors));
         }
     }
 
-    let operation = get_operation(&document, operation_name)?;
+    let fragment = executor::get_fragment(&document, fragment_name)?;
+
+    executor::execute_validated_fragment(
+        &document,
+        fragment,
+        &root_node.schema,
+        value,
+        info,
+        variables,
+        context,
+    )
+}
+
+/// Execute the selection set of a fragment against the given `value` of some
+/// type in a provided schema, rather than against its query root.
+///
+/// See [`execute_fragment_sync()`] for details.
+pub async fn execute_fragment<'a, S, T, QueryT, MutationT, SubscriptionT>(
+    document_source: &'a str,
+    fragment_name: Option<&str>,
+    root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+    value: &T,
+    info: &T::TypeInfo,
+    variables: &Variables<S>,
+    context: &T::Context,
+) -> Result<(Value<S>, Vec<ExecutionError<S>>), RequestError<'a>>
+where
+    S: ScalarValue + Send + Sync,
+    T: GraphQLTypeAsync<S>,
+    T::TypeInfo: Sync,
+    T::Context: Sync,
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S, Context = QueryT::Context>,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+{
+    let document = parse_document_source(document_source, &root_node.schema)?;
 
     {
-        let errors = validate_input_values(variables, operation, &root_node.schema);
+        let mut ctx = ValidatorContext::new(&root_node.schema, &document);
+        visit_fragment_rules(&mut ctx, &document);
+        ctx.record_metrics();
 
+        let errors = ctx.into_errors();
         if !errors.is_empty() {
-            return Err(GraphQLError::ValidationError(errors));
+            return Err(RequestError::Validation(errors));
         }
     }
 
-    executor::resolve_validated_subscription(&document, operation, root_node, variables, context)
+    let fragment = executor::get_fragment(&document, fragment_name)?;
+
+    executor::execute_validated_fragment_async(
+        &document,
+        fragment,
+        &root_node.schema,
+        value,
+        info,
+        variables,
+        context,
+    )
+    .await
+}
+
+/// Execute the selection set of a fragment against the entity of the GraphQL
+/// object type `typename` with the given `representation`, resolved by the
+/// [`EntityResolver`] registered in a provided schema.
+///
+/// Allows internal gateways to resolve individual entities by their keys. The
+/// `document_source` and the `fragment_name` are treated the same way as
+/// [`execute_fragment_sync()`] does. Resolves into `null` if there is no such
+/// entity, and into `null` with an [`ExecutionError`] if resolving the entity
+/// fails.
+///
+/// ```rust
+/// # use juniper::{
+/// #     execute_entity, graphql_input_value, graphql_object, graphql_value, graphql_vars,
+/// #     futures::future::{self, BoxFuture, FutureExt as _},
+/// #     EmptyMutation, EmptySubscription, EntityResolver, FieldResult, InputValue, RootNode,
+/// # };
+/// #
+/// struct User {
+///     id: i32,
+/// }
+///
+/// #[graphql_object]
+/// impl User {
+///     fn id(&self) -> i32 {
+///         self.id
+///     }
+///
+///     fn name(&self) -> String {
+///         format!("User {}", self.id)
+///     }
+/// }
+///
+/// struct UserById;
+///
+/// impl EntityResolver for UserById {
+///     type Entity = User;
+///
+///     fn resolve_entity<'a>(
+///         &'a self,
+///         representation: &'a InputValue,
+///         _: &'a (),
+///     ) -> BoxFuture<'a, FieldResult<Option<User>>> {
+///         let id = representation.get_path("id").and_then(InputValue::as_int_value);
+///         future::ready(Ok(id.map(|id| User { id }))).boxed()
+///     }
+/// }
+///
+/// struct Query;
+///
+/// #[graphql_object]
+/// impl Query {
+///     fn me() -> User {
+///         User { id: 1 }
+///     }
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let schema = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::<()>::new())
+///     .with_entity_resolver(UserById);
+///
+/// let (res, _) = execute_entity(
+///     "User",
+///     &graphql_input_value!({"id": 42}),
+///     "fragment F on User { name }",
+///     None,
+///     &schema,
+///     &graphql_vars! {},
+///     &(),
+/// )
+/// .await
+/// .unwrap();
+/// assert_eq!(res, graphql_value!({"name": "User 42"}));
+/// # }
+/// ```
+pub async fn execute_entity<'a, S, QueryT, MutationT, SubscriptionT>(
+    typename: &str,
+    representation: &InputValue<S>,
+    document_source: &'a str,
+    fragment_name: Option<&str>,
+    root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+) -> Result<(Value<S>, Vec<ExecutionError<S>>), RequestError<'a>>
+where
+    S: ScalarValue + Send + Sync,
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S, Context = QueryT::Context>,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+{
+    let document = parse_document_source(document_source, &root_node.schema)?;
+
+    {
+        let mut ctx = ValidatorContext::new(&root_node.schema, &document);
+        visit_fragment_rules(&mut ctx, &document);
+        ctx.record_metrics();
+
+        let errors = ctx.into_errors();
+        if !errors.is_empty() {
+            return Err(RequestError::Validation(errors));
+        }
+    }
+
+    let fragment = executor::get_fragment(&document, fragment_name)?;
+
+    let resolver = root_node.entity_resolvers.get(typename).ok_or_else(|| {
+        RequestError::Validation(vec![RuleError::new(
+            &format!("No entity resolver is registered for type \"{}\"", typename),
+            &[fragment.start],
+        )
+        .with_rule("KnownEntityResolver")
+        .with_param("type", typename)])
+    })?;
+
+    resolver
+        .execute(
+            &document,
+            fragment,
+            &root_node.schema,
+            representation,
+            variables,
+            context,
+        )
         .await
 }
 
@@ -262,7 +1260,7 @@ pub fn introspect<'a, S, QueryT, MutationT, SubscriptionT>(
     root_node: &'a RootNode<QueryT, MutationT, SubscriptionT, S>,
     context: &QueryT::Context,
     format: IntrospectionFormat,
-) -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+) -> Result<(Value<S>, Vec<ExecutionError<S>>), RequestError<'a>>
 where
     S: ScalarValue,
     QueryT: GraphQLType<S>,
@@ -281,8 +1279,28 @@ where
     )
 }
 
+#[allow(deprecated)]
 impl<'a> From<Spanning<ParseError<'a>>> for GraphQLError<'a> {
     fn from(f: Spanning<ParseError<'a>>) -> GraphQLError<'a> {
         GraphQLError::ParseError(f)
     }
 }
+
+#[allow(deprecated)]
+impl<'a> From<RequestError<'a>> for GraphQLError<'a> {
+    fn from(e: RequestError<'a>) -> Self {
+        match e {
+            RequestError::Parse(e) => Self::ParseError(e),
+            RequestError::Validation(errs) | RequestError::VariableCoercion(errs) => {
+                Self::ValidationError(errs)
+            }
+            RequestError::Operation(e) => match e {
+                OperationError::NoOperationProvided => Self::NoOperationProvided,
+                OperationError::MultipleOperationsProvided => Self::MultipleOperationsProvided,
+                OperationError::UnknownOperationName => Self::UnknownOperationName,
+                OperationError::IsSubscription => Self::IsSubscription,
+                OperationError::NotSubscription => Self::NotSubscription,
+            },
+        }
+    }
+}
@@ -0,0 +1,187 @@
+//! [`graphql_forward!`] macro implementation.
+//!
+//! [`graphql_forward!`]: graphql_forward
+
+/// Declares deprecated [GraphQL object][1] fields forwarding to their
+/// replacements, cutting the boilerplate of keeping the old fields available
+/// after a rename or a signature change.
+///
+/// Expands into a [`graphql_object`] `impl` block of the given `part` of the
+/// object, so the part should be listed in the `parts` attribute's argument
+/// of the object. Each field resolves into the replacement method called with
+/// the given arguments (or into a reference to the replacement struct field,
+/// if no arguments are given), and is deprecated with the
+/// ``"Use `replacement` instead."`` reason, naming the replacement as any
+/// other field.
+///
+/// Doc comments and `#[graphql(...)]` attributes placed on the fields are
+/// passed to the [`graphql_object`] macro as they are, and so does a
+/// `#[graphql(...)]` attribute placed on the `impl` (to specify its `context`
+/// or `scalar`, for example).
+///
+/// ```
+/// # use juniper::{
+/// #     execute_sync, graphql_forward, graphql_object, graphql_value, graphql_vars,
+/// #     EmptyMutation, EmptySubscription, GraphQLObject, RootNode,
+/// # };
+/// #
+/// #[derive(GraphQLObject)]
+/// #[graphql(parts = HumanLegacy)]
+/// struct Human {
+///     planet: String,
+/// }
+///
+/// #[graphql_object(part = HumanFriends)]
+/// impl Human {
+///     fn friend_names(&self, first: i32) -> Vec<String> {
+///         vec!["Han".into(); first as usize]
+///     }
+/// }
+///
+/// graphql_forward! {
+///     impl HumanLegacy for Human {
+///         fn home_planet(&self) -> &str => planet;
+///         fn friends(&self, count: i32) -> Vec<String> => friend_names(count);
+///     }
+/// }
+/// #
+/// # struct Query;
+/// #
+/// # #[graphql_object]
+/// # impl Query {
+/// #     fn human() -> Human {
+/// #         Human { planet: "Tatooine".into() }
+/// #     }
+/// # }
+///
+/// let schema = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::<()>::new());
+///
+/// assert_eq!(
+///     execute_sync(
+///         "{ human { homePlanet friends(count: 1) } }",
+///         None,
+///         &schema,
+///         &graphql_vars! {},
+///         &(),
+///     ),
+///     Ok((
+///         graphql_value!({"human": {"homePlanet": "Tatooine", "friends": ["Han"]}}),
+///         vec![],
+///     )),
+/// );
+///
+/// let human = schema.schema.concrete_type_by_name("Human").unwrap();
+/// assert_eq!(
+///     human.field_by_name("homePlanet").unwrap().deprecation_status.reason(),
+///     Some("Use `planet` instead."),
+/// );
+/// ```
+///
+/// [`graphql_object`]: macro@crate::graphql_object
+/// [1]: https://spec.graphql.org/June2018/#sec-Objects
+#[macro_export]
+macro_rules! graphql_forward {
+    (
+        $(#[graphql($($attr:tt)*)])?
+        impl $part:ident for $ty:ident {
+            $(
+                $(#[$field_attr:meta])*
+                fn $field:ident(&self $(, $arg:ident : $arg_ty:ty)* $(,)?) -> $ret:ty
+                    => $replacement:ident $(($($fwd:expr),* $(,)?))?;
+            )*
+        }
+    ) => {
+        #[$crate::graphql_object(part = $part $(, $($attr)*)?)]
+        impl $ty {
+            $(
+                $(#[$field_attr])*
+                #[graphql(replaced_by = $replacement)]
+                fn $field(&self $(, $arg: $arg_ty)*) -> $ret {
+                    $crate::graphql_forward!(@forward self.$replacement $(($($fwd),*))?)
+                }
+            )*
+        }
+    };
+
+    (@forward $self:ident . $replacement:ident) => {
+        &$self.$replacement
+    };
+
+    (@forward $self:ident . $replacement:ident ($($fwd:expr),*)) => {
+        $self.$replacement($($fwd),*)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        execute_sync, graphql_object, graphql_value, graphql_vars, EmptyMutation,
+        EmptySubscription, RootNode,
+    };
+
+    struct Context(i32);
+
+    impl crate::Context for Context {}
+
+    struct Query;
+
+    #[graphql_object(context = Context, parts = QueryLegacy)]
+    impl Query {
+        fn balance(&self, ctx: &Context, #[graphql(default = 1)] multiplier: i32) -> i32 {
+            ctx.0 * multiplier
+        }
+    }
+
+    graphql_forward! {
+        #[graphql(context = Context)]
+        impl QueryLegacy for Query {
+            /// Balance of the account.
+            fn amount(&self, ctx: &Context) -> i32 => balance(ctx, 1);
+            #[graphql(name = "scaledAmount")]
+            fn scaled(&self, ctx: &Context, by: i32) -> i32 => balance(ctx, by);
+        }
+    }
+
+    fn schema() -> RootNode<'static, Query, EmptyMutation<Context>, EmptySubscription<Context>> {
+        RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+    }
+
+    #[test]
+    fn forwards_to_replacements() {
+        assert_eq!(
+            execute_sync(
+                "{ balance amount scaledAmount(by: 3) }",
+                None,
+                &schema(),
+                &graphql_vars! {},
+                &Context(7),
+            ),
+            Ok((
+                graphql_value!({"balance": 7, "amount": 7, "scaledAmount": 21}),
+                vec![],
+            )),
+        );
+    }
+
+    #[test]
+    fn deprecates_forwarded_fields() {
+        let schema = schema();
+        let query = schema.schema.concrete_query_type();
+
+        let amount = query.field_by_name("amount").unwrap();
+        assert_eq!(
+            amount.description.as_deref(),
+            Some("Balance of the account.")
+        );
+        assert_eq!(
+            amount.deprecation_status.reason(),
+            Some("Use `balance` instead."),
+        );
+
+        let scaled = query.field_by_name("scaledAmount").unwrap();
+        assert_eq!(
+            scaled.deprecation_status.reason(),
+            Some("Use `balance` instead."),
+        );
+    }
+}
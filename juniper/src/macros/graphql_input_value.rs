@@ -49,6 +49,33 @@
 /// });
 /// ```
 ///
+/// - Any other expression is converted via
+///   [`ToInputValue`], so Rust enum values and custom scalars may be
+///   interpolated directly.
+/// ```rust
+/// # use juniper::{graphql_input_value, GraphQLEnum, InputValue, ID};
+/// #
+/// #[derive(GraphQLEnum)]
+/// enum Episode {
+///     NewHope,
+///     Empire,
+/// }
+///
+/// let id = ID::new("1");
+///
+/// let value: InputValue = graphql_input_value!({
+///     "id": (id),
+///     "episodes": [Episode::NewHope, (Episode::Empire)],
+/// });
+/// assert_eq!(
+///     value,
+///     graphql_input_value!({
+///         "id": "1",
+///         "episodes": [NEW_HOPE, EMPIRE],
+///     }),
+/// );
+/// ```
+///
 /// > __NOTE:__ [`InputValue::List`]s and [`InputValue::Object`]s will be
 /// >           created in a [`Spanning::unlocated`].
 ///
@@ -85,6 +112,7 @@
 /// [`InputValue::Scalar`]: crate::InputValue::Scalar
 /// [`InputValue::Variable`]: crate::InputValue::Variable
 /// [`Spanning::unlocated`]: crate::Spanning::unlocated
+/// [`ToInputValue`]: crate::ToInputValue
 #[macro_export]
 macro_rules! graphql_input_value {
     ///////////
@@ -376,17 +404,25 @@ macro_rules! graphql_input_value {
 
     ($enum:ident$(,)?) => ($crate::InputValue::enum_value(stringify!($enum)));
 
-    (($e:expr)$(,)?) => ($crate::InputValue::from($e));
+    (($e:expr)$(,)?) => ($crate::ToInputValue::to_input_value(&$e));
 
-    ($e:expr$(,)?) => ($crate::InputValue::from($e));
+    ($e:expr$(,)?) => ($crate::ToInputValue::to_input_value(&$e));
 }
 
 #[cfg(test)]
 mod tests {
     use indexmap::{indexmap, IndexMap};
 
+    use crate::{GraphQLEnum, ID};
+
     type V = crate::InputValue;
 
+    #[derive(Clone, Copy, GraphQLEnum)]
+    enum Color {
+        Red,
+        DarkBlue,
+    }
+
     #[test]
     fn null() {
         assert_eq!(graphql_input_value!(null), V::Null);
@@ -409,6 +445,36 @@ mod tests {
         assert_eq!(graphql_input_value!(lowercase), V::enum_value("lowercase"));
     }
 
+    #[test]
+    fn to_input_value() {
+        let color = Color::DarkBlue;
+        let id = ID::new("id");
+
+        assert_eq!(graphql_input_value!(Color::Red), V::enum_value("RED"));
+        assert_eq!(graphql_input_value!((color)), V::enum_value("DARK_BLUE"));
+        assert_eq!(graphql_input_value!((id)), V::scalar("id"));
+        assert_eq!(
+            graphql_input_value!([Color::Red, (color), None]),
+            V::list(vec![
+                V::enum_value("RED"),
+                V::enum_value("DARK_BLUE"),
+                V::Null,
+            ]),
+        );
+        assert_eq!(
+            graphql_input_value!({
+                "color": Color::Red,
+                "colors": [Some(color)],
+                "id": (id),
+            }),
+            V::object(indexmap! {
+                "color" => V::enum_value("RED"),
+                "colors" => V::list(vec![V::enum_value("DARK_BLUE")]),
+                "id" => V::scalar("id"),
+            }),
+        );
+    }
+
     #[test]
     fn variable() {
         assert_eq!(graphql_input_value!(@var), V::variable("var"));
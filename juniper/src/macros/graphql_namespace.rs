@@ -0,0 +1,185 @@
+//! [`graphql_namespace!`] macro implementation.
+//!
+//! [`graphql_namespace!`]: graphql_namespace
+
+/// Declares a [GraphQL object][1] grouping fields into namespaces, like
+/// `query { payments { ... } orders { ... } }`.
+///
+/// Declares the `struct` itself along with an empty `struct` for each of its
+/// namespaces, and implements the [GraphQL object][1] for the former,
+/// resolving each field into its namespace. Fields of the namespaces are
+/// defined by implementing [GraphQL objects][1] for them as usual, with the
+/// same [`Context`].
+///
+/// Doc comments and `#[graphql(...)]` attributes placed on the fields are
+/// passed to the [`graphql_object`] macro as they are, and so does a
+/// `#[graphql(...)]` attribute placed on the `struct` after its doc comments
+/// (to specify its `context` or `scalar`, for example).
+///
+/// Usually used along with [`MergedObject`] to keep a big root type organized.
+///
+/// ```
+/// # use juniper::{
+/// #     execute_sync, graphql_namespace, graphql_object, graphql_value, graphql_vars,
+/// #     EmptyMutation, EmptySubscription, RootNode,
+/// # };
+/// #
+/// struct Database {
+///     balance: i32,
+/// }
+///
+/// impl juniper::Context for Database {}
+///
+/// graphql_namespace! {
+///     /// Root of the whole API.
+///     #[graphql(context = Database)]
+///     struct Query {
+///         /// Payments API.
+///         payments: PaymentsQuery,
+///         #[graphql(name = "ordersApi")]
+///         orders: OrdersQuery,
+///     }
+/// }
+///
+/// #[graphql_object(context = Database)]
+/// impl PaymentsQuery {
+///     fn balance(context: &Database) -> i32 {
+///         context.balance
+///     }
+/// }
+///
+/// #[graphql_object(context = Database)]
+/// impl OrdersQuery {
+///     fn count() -> i32 {
+///         0
+///     }
+/// }
+///
+/// let schema = RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new());
+///
+/// assert_eq!(
+///     execute_sync(
+///         "{ payments { balance } ordersApi { count } }",
+///         None,
+///         &schema,
+///         &graphql_vars! {},
+///         &Database { balance: 42 },
+///     ),
+///     Ok((
+///         graphql_value!({"payments": {"balance": 42}, "ordersApi": {"count": 0}}),
+///         vec![],
+///     )),
+/// );
+/// ```
+///
+/// [`Context`]: crate::Context
+/// [`graphql_object`]: macro@crate::graphql_object
+/// [`MergedObject`]: crate::MergedObject
+/// [1]: https://spec.graphql.org/June2018/#sec-Objects
+#[macro_export]
+macro_rules! graphql_namespace {
+    (
+        $(#[doc = $doc:expr])*
+        $(#[graphql($($attr:tt)*)])?
+        $vis:vis struct $name:ident {
+            $(
+                $(#[$field_attr:meta])*
+                $field:ident : $namespace:ident
+            ),* $(,)?
+        }
+    ) => {
+        $(#[doc = $doc])*
+        #[derive(Clone, Copy, Debug, Default)]
+        $vis struct $name;
+
+        $(
+            #[doc = concat!(
+                "Namespace of the `", stringify!($field), "` field of [`", stringify!($name), "`].",
+            )]
+            #[derive(Clone, Copy, Debug, Default)]
+            $vis struct $namespace;
+        )*
+
+        #[$crate::graphql_object($($($attr)*)?)]
+        impl $name {
+            $(
+                $(#[$field_attr])*
+                fn $field() -> $namespace {
+                    $namespace
+                }
+            )*
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        execute_sync, graphql_object, graphql_value, graphql_vars, EmptyMutation,
+        EmptySubscription, RootNode,
+    };
+
+    struct Context(i32);
+
+    impl crate::Context for Context {}
+
+    graphql_namespace! {
+        #[graphql(context = Context)]
+        struct Query {
+            /// Payments API.
+            payments: PaymentsQuery,
+            #[graphql(deprecated = "Use `payments` instead.")]
+            legacy_payments: LegacyPaymentsQuery,
+        }
+    }
+
+    #[graphql_object(context = Context)]
+    impl PaymentsQuery {
+        fn amount(ctx: &Context) -> i32 {
+            ctx.0
+        }
+    }
+
+    #[graphql_object(context = Context)]
+    impl LegacyPaymentsQuery {
+        fn total() -> i32 {
+            0
+        }
+    }
+
+    fn schema() -> RootNode<'static, Query, EmptyMutation<Context>, EmptySubscription<Context>> {
+        RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+    }
+
+    #[test]
+    fn resolves_namespaces() {
+        assert_eq!(
+            execute_sync(
+                "{ payments { amount } legacyPayments { total } }",
+                None,
+                &schema(),
+                &graphql_vars! {},
+                &Context(7),
+            ),
+            Ok((
+                graphql_value!({"payments": {"amount": 7}, "legacyPayments": {"total": 0}}),
+                vec![],
+            )),
+        );
+    }
+
+    #[test]
+    fn passes_field_attributes() {
+        let schema = schema();
+        let query = schema.schema.concrete_query_type();
+
+        let payments = query.field_by_name("payments").unwrap();
+        assert_eq!(payments.description.as_deref(), Some("Payments API."));
+
+        let legacy = query.field_by_name("legacyPayments").unwrap();
+        assert_eq!(
+            legacy.deprecation_status.reason(),
+            Some("Use `payments` instead."),
+        );
+    }
+}
@@ -0,0 +1,197 @@
+//! [`graphql_tuple!`] macro implementation.
+//!
+//! [`graphql_tuple!`]: graphql_tuple
+
+/// Declares a [GraphQL object][1] wrapping a tuple of up to 12 values, with
+/// positional field names `_0`, `_1`, `_2` and so on.
+///
+/// Declares the tuple `struct` itself with public fields, implements
+/// [`From`] the plain tuple for it, and implements the [GraphQL object][1]
+/// resolving each of its fields. Useful for small aggregate return values not
+/// worth a dedicated object type.
+///
+/// Doc comments placed on the `struct` become the description of the
+/// [GraphQL object][1], and a `#[graphql(...)]` attribute placed after them is
+/// passed to the [`graphql_object`] macro as it is (to specify its `name`,
+/// `context` or `scalar`, for example).
+///
+/// ```
+/// # use juniper::{
+/// #     execute_sync, graphql_object, graphql_tuple, graphql_value, graphql_vars,
+/// #     EmptyMutation, EmptySubscription, RootNode,
+/// # };
+/// #
+/// graphql_tuple! {
+///     /// Minimum and maximum of a series.
+///     pub struct MinMax(i32, i32);
+/// }
+///
+/// struct Query;
+///
+/// #[graphql_object]
+/// impl Query {
+///     fn bounds(values: Vec<i32>) -> Option<MinMax> {
+///         let min = values.iter().min()?;
+///         let max = values.iter().max()?;
+///         Some((*min, *max).into())
+///     }
+/// }
+///
+/// let schema = RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new());
+///
+/// assert_eq!(
+///     execute_sync(
+///         "{ bounds(values: [3, 1, 2]) { _0 _1 } }",
+///         None,
+///         &schema,
+///         &graphql_vars! {},
+///         &(),
+///     ),
+///     Ok((graphql_value!({"bounds": {"_0": 1, "_1": 3}}), vec![])),
+/// );
+/// ```
+///
+/// [`graphql_object`]: macro@crate::graphql_object
+/// [1]: https://spec.graphql.org/June2018/#sec-Objects
+#[macro_export]
+macro_rules! graphql_tuple {
+    (
+        $(#[doc = $doc:expr])*
+        $(#[graphql($($attr:tt)*)])?
+        $vis:vis struct $name:ident ( $($ty:ty),+ $(,)? );
+    ) => {
+        $crate::graphql_tuple! {
+            @fields
+            [$(#[doc = $doc])*]
+            [$($($attr)*)?]
+            $vis $name
+            []
+            [$($ty),+]
+            [
+                (_0 0 "_0") (_1 1 "_1") (_2 2 "_2") (_3 3 "_3")
+                (_4 4 "_4") (_5 5 "_5") (_6 6 "_6") (_7 7 "_7")
+                (_8 8 "_8") (_9 9 "_9") (_10 10 "_10") (_11 11 "_11")
+            ]
+        }
+    };
+
+    // Assigns the next positional field to the next type.
+    (
+        @fields [$($doc:tt)*] [$($attr:tt)*] $vis:vis $name:ident
+        [$($done:tt)*]
+        [$ty:ty $(, $rest:ty)*]
+        [($field:ident $idx:tt $field_name:literal) $($positions:tt)*]
+    ) => {
+        $crate::graphql_tuple! {
+            @fields [$($doc)*] [$($attr)*] $vis $name
+            [$($done)* ($field $idx $field_name $ty)]
+            [$($rest),*]
+            [$($positions)*]
+        }
+    };
+
+    // All the types have their positional fields assigned.
+    (
+        @fields [$($doc:tt)*] [$($attr:tt)*] $vis:vis $name:ident
+        [$(($field:ident $idx:tt $field_name:literal $ty:ty))+]
+        []
+        [$($positions:tt)*]
+    ) => {
+        $($doc)*
+        $vis struct $name($(pub $ty),+);
+
+        impl ::std::convert::From<($($ty,)+)> for $name {
+            fn from(tuple: ($($ty,)+)) -> Self {
+                Self($(tuple.$idx),+)
+            }
+        }
+
+        $($doc)*
+        #[$crate::graphql_object($($attr)*)]
+        impl $name {
+            $(
+                #[graphql(name = $field_name)]
+                fn $field(&self) -> &$ty {
+                    &self.$idx
+                }
+            )+
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        execute_sync, graphql_object, graphql_value, graphql_vars, EmptyMutation,
+        EmptySubscription, RootNode,
+    };
+
+    struct Context(i32);
+
+    impl crate::Context for Context {}
+
+    graphql_tuple! {
+        /// Position on a map.
+        #[graphql(name = "Coordinates", context = Context)]
+        struct Point(f64, f64, Option<String>);
+    }
+
+    graphql_tuple! {
+        #[graphql(context = Context)]
+        struct Scored(String, Vec<i32>, Point);
+    }
+
+    struct Query;
+
+    #[graphql_object(context = Context)]
+    impl Query {
+        fn point() -> Point {
+            (1.5, -2.0, None).into()
+        }
+
+        fn scored(ctx: &Context) -> Scored {
+            Scored(
+                "best".into(),
+                vec![ctx.0],
+                Point(0.0, 0.0, Some("origin".into())),
+            )
+        }
+    }
+
+    fn schema() -> RootNode<'static, Query, EmptyMutation<Context>, EmptySubscription<Context>> {
+        RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+    }
+
+    #[test]
+    fn resolves_positional_fields() {
+        assert_eq!(
+            execute_sync(
+                "{ point { _0 _1 _2 } scored { _0 _1 _2 { _2 } } }",
+                None,
+                &schema(),
+                &graphql_vars! {},
+                &Context(7),
+            ),
+            Ok((
+                graphql_value!({
+                    "point": {"_0": 1.5, "_1": -2.0, "_2": null},
+                    "scored": {"_0": "best", "_1": [7], "_2": {"_2": "origin"}},
+                }),
+                vec![],
+            )),
+        );
+    }
+
+    #[test]
+    fn passes_attributes() {
+        let schema = schema();
+        let point = schema.schema.concrete_type_by_name("Coordinates").unwrap();
+
+        assert_eq!(point.description(), Some("Position on a map."));
+        assert_eq!(
+            point.field_by_name("_2").map(|f| f.field_type.to_string()),
+            Some("String".into()),
+        );
+        assert!(schema.schema.concrete_type_by_name("Point").is_none());
+    }
+}
@@ -42,6 +42,26 @@
 /// graphql_value!({"key": "value", "foo": 1234});
 /// ```
 ///
+/// # Interpolating scalars
+///
+/// Interpolated expressions are converted via [`Value`]'s [`From`]
+/// implementations, so only built-in scalars are accepted as they are. Values
+/// of custom scalar types (anything the [`ScalarValue`] can be constructed
+/// [`From`]) should be interpolated with `@scalar(expr)`, and bytes (anything
+/// implementing [`AsRef`]`<[`[`u8`]`]>`) with `@bytes(expr)`, in case the
+/// [`ScalarValue`] implements [`From`]`<`[`Vec`]`<`[`u8`]`>>`.
+/// ```rust
+/// # use juniper::{graphql_value, DefaultScalarValue, Value};
+/// #
+/// # type V = Value<DefaultScalarValue>;
+/// #
+/// let code = 200;
+///
+/// # let _: V =
+/// graphql_value!({"code": @scalar(code), "codes": [@scalar(code), 404]});
+/// ```
+///
+/// [`ScalarValue`]: crate::ScalarValue
 /// [`Value`]: crate::Value
 /// [`Value::Object`]: crate::Value::Object
 #[macro_export]
@@ -78,6 +98,13 @@ macro_rules! graphql_value {
         )
     };
 
+    // Next element is an interpolated scalar.
+    (@array [$($elems:expr,)*] @$kind:ident($($e:tt)*) $($rest:tt)*) => {
+        $crate::graphql_value!(
+            @array [$($elems,)* $crate::graphql_value!(@$kind($($e)*))] $($rest)*
+        )
+    };
+
     // Next element is an array.
     (@array [$($elems:expr,)*] [$($array:tt)*] $($rest:tt)*) => {
         $crate::graphql_value!(
@@ -157,6 +184,15 @@ macro_rules! graphql_value {
         );
     };
 
+    // Next value is an interpolated scalar.
+    (@object $object:ident ($($key:tt)+) (: @$kind:ident($($e:tt)*) $($rest:tt)*) $copy:tt) => {
+        $crate::graphql_value!(
+            @object $object
+            [$($key)+]
+            ($crate::graphql_value!(@$kind($($e)*))) $($rest)*
+        );
+    };
+
     // Next value is an array.
     (@object $object:ident ($($key:tt)+) (: [$($array:tt)*] $($rest:tt)*) $copy:tt) => {
         $crate::graphql_value!(
@@ -268,13 +304,57 @@ macro_rules! graphql_value {
 
     (None$(,)?) => ($crate::Value::null());
 
+    (@scalar($e:expr)$(,)?) => ($crate::Value::scalar($e));
+
+    (@bytes($e:expr)$(,)?) => (
+        $crate::Value::scalar(<[u8]>::to_vec(::std::convert::AsRef::<[u8]>::as_ref(&$e)))
+    );
+
     ($e:expr$(,)?) => ($crate::Value::from($e));
 }
 
 #[cfg(test)]
 mod tests {
+    use std::fmt;
+
+    use serde::{Deserialize, Serialize};
+
+    use crate::ScalarValue;
+
     type V = crate::Value;
 
+    #[derive(Clone, Debug, Deserialize, PartialEq, ScalarValue, Serialize)]
+    #[serde(untagged)]
+    enum CustomScalarValue {
+        #[value(as_float, as_int)]
+        Int(i32),
+        Long(i64),
+        #[value(as_float)]
+        Float(f64),
+        #[value(as_str, as_string, into_string)]
+        String(String),
+        #[value(as_bool)]
+        Boolean(bool),
+        Bytes(Bytes),
+    }
+
+    #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+    struct Bytes(Vec<u8>);
+
+    impl fmt::Display for Bytes {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{:?}", self.0)
+        }
+    }
+
+    impl From<Vec<u8>> for CustomScalarValue {
+        fn from(bytes: Vec<u8>) -> Self {
+            Self::Bytes(Bytes(bytes))
+        }
+    }
+
+    type CV = crate::Value<CustomScalarValue>;
+
     #[test]
     fn null() {
         assert_eq!(graphql_value!(null), V::Null);
@@ -384,4 +464,46 @@ mod tests {
         assert_eq!(graphql_value!(Some(42)), V::scalar(42));
         assert_eq!(graphql_value!(val), V::scalar(42));
     }
+
+    #[test]
+    fn interpolated_scalar() {
+        let val = i64::MAX;
+
+        assert_eq!(graphql_value!(@scalar(42)), V::scalar(42));
+        assert_eq!(graphql_value!(@scalar(val)), CV::scalar(val));
+        assert_eq!(
+            graphql_value!([@scalar(val), 1, @scalar(val)]),
+            CV::list(vec![CV::scalar(val), CV::scalar(1), CV::scalar(val)]),
+        );
+        assert_eq!(
+            graphql_value!({ "key": @scalar(val), "other": [@scalar(1 + 2)] }),
+            CV::object(
+                vec![
+                    ("key", CV::scalar(val)),
+                    ("other", CV::list(vec![CV::scalar(3)])),
+                ]
+                .into_iter()
+                .collect(),
+            ),
+        );
+    }
+
+    #[test]
+    fn interpolated_bytes() {
+        let val = vec![1, 2, 3];
+
+        assert_eq!(graphql_value!(@bytes(val)), CV::scalar(vec![1_u8, 2, 3]));
+        assert_eq!(graphql_value!(@bytes(b"raw")), CV::scalar(b"raw".to_vec()));
+        assert_eq!(
+            graphql_value!([@bytes(val), @bytes("str")]),
+            CV::list(vec![
+                CV::scalar(vec![1_u8, 2, 3]),
+                CV::scalar(b"str".to_vec()),
+            ]),
+        );
+        assert_eq!(
+            graphql_value!({ "key": @bytes(&val[..1]) }),
+            CV::object(vec![("key", CV::scalar(vec![1_u8]))].into_iter().collect(),),
+        );
+    }
 }
@@ -1,12 +1,18 @@
 //! Helper traits and definitions for macros.
 
 pub mod subscription;
+pub mod validate;
 
-use std::fmt;
+use std::{cell::Cell, fmt, time::Duration};
 
-use futures::future::{self, BoxFuture};
+use futures::future::{self, BoxFuture, Either};
+use futures_timer::Delay;
 
-use crate::{DefaultScalarValue, DynGraphQLValue, DynGraphQLValueAsync, FieldError, ScalarValue};
+use crate::{
+    graphql_value, macros::reflect, meta, Arguments, DefaultScalarValue, DynGraphQLValue,
+    DynGraphQLValueAsync, ExecutionResult, Executor, FieldError, GraphQLValue, GraphQLValueAsync,
+    Object, Registry, ScalarValue, Value,
+};
 
 /// Conversion of a [`GraphQLValue`] to its [trait object][1].
 ///
@@ -37,6 +43,66 @@ pub trait AsDynGraphQLValue<S: ScalarValue = DefaultScalarValue> {
 
 crate::sa::assert_obj_safe!(AsDynGraphQLValue<Context = (), TypeInfo = ()>);
 
+/// Part of a [GraphQL object][1]'s fields defined in a separate `impl` block
+/// with a `#[graphql_object(part = P)]` attribute, where `P` is a marker type
+/// distinguishing the part.
+///
+/// Used by [`graphql_object`] and [`GraphQLObject`] macros to merge the parts
+/// listed in a `parts` attribute argument into a single [GraphQL object][1].
+///
+/// [`graphql_object`]: macro@crate::graphql_object
+/// [`GraphQLObject`]: derive@crate::GraphQLObject
+/// [1]: https://spec.graphql.org/June2018/#sec-Objects
+pub trait ObjectPart<S: ScalarValue, P>: GraphQLValue<S> {
+    /// [`reflect::Names`] of the fields defined by this part.
+    const NAMES: reflect::Names;
+
+    /// Performs static checks of the fields defined by this part.
+    ///
+    /// See [`IsOutputType::mark()`] for more info.
+    ///
+    /// [`IsOutputType::mark()`]: crate::marker::IsOutputType::mark
+    fn mark() {}
+
+    /// Returns [`meta::Field`]s of the fields defined by this part.
+    fn meta_fields<'r>(
+        info: &Self::TypeInfo,
+        registry: &mut Registry<'r, S>,
+    ) -> Vec<meta::Field<'r, S>>
+    where
+        S: 'r;
+
+    /// Resolves the value of the `field` defined by this part.
+    ///
+    /// See [`GraphQLValue::resolve_field()`] for more info.
+    fn resolve_field(
+        &self,
+        info: &Self::TypeInfo,
+        field: &str,
+        args: &Arguments<S>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S>;
+}
+
+/// Asynchronous resolution of an [`ObjectPart`].
+pub trait AsyncObjectPart<S: ScalarValue, P>: ObjectPart<S, P> + GraphQLValueAsync<S>
+where
+    Self::TypeInfo: Sync,
+    Self::Context: Sync,
+    S: Send + Sync,
+{
+    /// Resolves the value of the `field` defined by this part asynchronously.
+    ///
+    /// See [`GraphQLValueAsync::resolve_field_async()`] for more info.
+    fn resolve_field_async<'b>(
+        &'b self,
+        info: &'b Self::TypeInfo,
+        field: &'b str,
+        args: &'b Arguments<S>,
+        executor: &'b Executor<Self::Context, S>,
+    ) -> BoxFuture<'b, ExecutionResult<S>>;
+}
+
 /// This trait is used by [`graphql_scalar!`] macro to retrieve [`Error`] type
 /// from a [`Result`].
 ///
@@ -75,6 +141,82 @@ pub fn err_unnamed_type<S>(name: &str) -> FieldError<S> {
     ))
 }
 
+/// Checks that the given `fields` of an input object contain only the `known`
+/// ones, erroring with the first unknown one named otherwise.
+///
+/// Used by [`GraphQLInputObject`] derive macro to implement
+/// `deny_unknown_fields` attribute argument.
+///
+/// [`GraphQLInputObject`]: derive@crate::GraphQLInputObject
+pub fn deny_unknown_fields<'f, S: ScalarValue>(
+    type_name: &str,
+    known: &[&str],
+    mut fields: impl Iterator<Item = &'f str>,
+) -> Result<(), FieldError<S>> {
+    match fields.find(|f| !known.contains(f)) {
+        Some(field) => Err(FieldError::new(
+            format!("Unknown field `{}` of `{}` input object", field, type_name),
+            graphql_value!({ "unknownField": field }),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Default maximum nesting depth of input objects being converted via
+/// [`FromInputValue`], unless overridden with `max_depth` attribute argument of
+/// [`GraphQLInputObject`] derive macro.
+///
+/// [`FromInputValue`]: crate::FromInputValue
+/// [`GraphQLInputObject`]: derive@crate::GraphQLInputObject
+pub const DEFAULT_MAX_INPUT_DEPTH: usize = 64;
+
+thread_local! {
+    /// Nesting depth of input objects being currently converted on this thread.
+    static INPUT_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Guard tracking the nesting depth of input objects being converted via
+/// [`FromInputValue`], so recursive input objects can't be nested arbitrarily
+/// deep by clients.
+///
+/// Used by [`GraphQLInputObject`] derive macro in the generated
+/// [`FromInputValue`] implementations.
+///
+/// [`FromInputValue`]: crate::FromInputValue
+/// [`GraphQLInputObject`]: derive@crate::GraphQLInputObject
+#[must_use]
+pub struct InputDepthGuard(());
+
+impl InputDepthGuard {
+    /// Enters the conversion of an input object of the given `type_name`,
+    /// erroring if it's nested deeper than the given `max_depth`.
+    ///
+    /// The depth is left once the returned [`InputDepthGuard`] is dropped.
+    pub fn enter<S: ScalarValue>(type_name: &str, max_depth: usize) -> Result<Self, FieldError<S>> {
+        let depth = INPUT_DEPTH.with(|d| {
+            d.set(d.get() + 1);
+            d.get()
+        });
+        let guard = Self(());
+        if depth > max_depth {
+            return Err(FieldError::new(
+                format!(
+                    "`{}` input object is nested too deeply, maximum depth is {}",
+                    type_name, max_depth,
+                ),
+                graphql_value!({ "maxDepth": (max_depth as i32) }),
+            ));
+        }
+        Ok(guard)
+    }
+}
+
+impl Drop for InputDepthGuard {
+    fn drop(&mut self) {
+        INPUT_DEPTH.with(|d| d.set(d.get() - 1));
+    }
+}
+
 /// Returns a [`future::err`] wrapping the [`err_unnamed_type`].
 pub fn err_unnamed_type_fut<'ok, Ok, S>(name: &str) -> BoxFuture<'ok, Result<Ok, FieldError<S>>>
 where
@@ -83,3 +225,76 @@ where
 {
     Box::pin(future::err(err_unnamed_type(name)))
 }
+
+/// Wraps the given `fut`ure resolving a [GraphQL field][1], so it resolves into
+/// a [`FieldError`] with a `TIMEOUT` code once the given `timeout` elapses.
+///
+/// Used by [`graphql_object`] macro to implement `timeout` attribute argument
+/// of fields.
+///
+/// [`graphql_object`]: macro@crate::graphql_object
+/// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
+pub fn with_timeout<'f, S>(
+    fut: BoxFuture<'f, ExecutionResult<S>>,
+    timeout: Duration,
+) -> BoxFuture<'f, ExecutionResult<S>>
+where
+    S: ScalarValue + Send + 'f,
+{
+    Box::pin(async move {
+        match future::select(fut, Delay::new(timeout)).await {
+            Either::Left((res, _)) => res,
+            Either::Right(_) => Err(FieldError::new(
+                format!("Field resolution timed out after {:?}", timeout),
+                graphql_value!({ "code": "TIMEOUT" }),
+            )),
+        }
+    })
+}
+
+/// Resolves a [GraphQL field][1] with the futures produced by the given
+/// `attempt` function, retrying it up to the given number of `times` once it
+/// fails, and waiting for the given `backoff` before each retry.
+///
+/// The [`FieldError`] of the last attempt is extended with an `attempts`
+/// extension, containing the total number of attempts made.
+///
+/// Used by [`graphql_object`] macro to implement `retry` attribute argument of
+/// fields.
+///
+/// [`graphql_object`]: macro@crate::graphql_object
+/// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
+pub fn with_retry<'f, S, F>(
+    attempt: F,
+    times: u32,
+    backoff: Option<Duration>,
+) -> BoxFuture<'f, ExecutionResult<S>>
+where
+    F: Fn() -> BoxFuture<'f, ExecutionResult<S>> + Send + 'f,
+    S: ScalarValue + Send + 'f,
+{
+    Box::pin(async move {
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            match attempt().await {
+                Err(e) if attempts > times => {
+                    let mut extensions = match e.extensions() {
+                        Value::Null => Value::Object(Object::with_capacity(1)),
+                        ext => ext.clone(),
+                    };
+                    if let Some(obj) = extensions.as_mut_object_value() {
+                        obj.add_field("attempts", Value::scalar(attempts as i32));
+                    }
+                    return Err(FieldError::new(e.message(), extensions));
+                }
+                Err(_) => {
+                    if let Some(backoff) = backoff {
+                        Delay::new(backoff).await;
+                    }
+                }
+                res => return res,
+            }
+        }
+    })
+}
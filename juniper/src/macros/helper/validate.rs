@@ -0,0 +1,209 @@
+//! Helper traits and functions for checking values against constraints of
+//! `#[graphql(validate(...))]` attribute argument.
+
+use crate::{graphql_value, FieldError, Nullable, ScalarValue, ID};
+
+/// Value, which can be checked against a `range(min = ..., max = ...)`
+/// constraint.
+pub trait ValidateRange {
+    /// Checks that this value is in the inclusive range between `min` and
+    /// `max`, if they're specified.
+    ///
+    /// # Errors
+    ///
+    /// With a human-readable message if the check fails.
+    fn validate_range(&self, min: Option<f64>, max: Option<f64>) -> Result<(), String>;
+}
+
+/// Value, which can be checked against a `length(min = ..., max = ...)`
+/// constraint.
+pub trait ValidateLength {
+    /// Checks that the length of this value is in the inclusive range between
+    /// `min` and `max`, if they're specified.
+    ///
+    /// Strings are measured in [`char`]s, while lists in their elements.
+    ///
+    /// # Errors
+    ///
+    /// With a human-readable message if the check fails.
+    fn validate_length(&self, min: Option<usize>, max: Option<usize>) -> Result<(), String>;
+}
+
+/// Value, which can be checked against a `regex = "..."` constraint.
+#[cfg(feature = "regex")]
+pub trait ValidateRegex {
+    /// Checks that this value matches the given regular expression `pattern`.
+    ///
+    /// # Errors
+    ///
+    /// With a human-readable message if the check fails.
+    ///
+    /// # Panics
+    ///
+    /// If the `pattern` is not a valid regular expression. The patterns of
+    /// `validate(regex = "...")` attribute arguments are checked during macro
+    /// expansion, so this may happen only if this method is called manually.
+    fn validate_regex(&self, pattern: &'static str) -> Result<(), String>;
+}
+
+/// Creates a [`FieldError`] describing the failed `constraint` of the input
+/// value with the given `name`.
+pub fn err_invalid<S: ScalarValue>(name: &str, constraint: &str, msg: String) -> FieldError<S> {
+    FieldError::new(
+        format!("Invalid value for `{}`: {}", name, msg),
+        graphql_value!({
+            "input": name,
+            "constraint": constraint,
+        }),
+    )
+}
+
+fn check_bounds<T>(val: T, min: Option<T>, max: Option<T>, what: &str) -> Result<(), String>
+where
+    T: PartialOrd + std::fmt::Display,
+{
+    if let Some(min) = min.filter(|min| val < *min) {
+        return Err(format!("{} must be at least {}, found: {}", what, min, val));
+    }
+    if let Some(max) = max.filter(|max| val > *max) {
+        return Err(format!("{} must be at most {}, found: {}", what, max, val));
+    }
+    Ok(())
+}
+
+impl ValidateRange for i32 {
+    fn validate_range(&self, min: Option<f64>, max: Option<f64>) -> Result<(), String> {
+        check_bounds(f64::from(*self), min, max, "value")
+    }
+}
+
+impl ValidateRange for f64 {
+    fn validate_range(&self, min: Option<f64>, max: Option<f64>) -> Result<(), String> {
+        check_bounds(*self, min, max, "value")
+    }
+}
+
+impl ValidateLength for str {
+    fn validate_length(&self, min: Option<usize>, max: Option<usize>) -> Result<(), String> {
+        check_bounds(self.chars().count(), min, max, "length")
+    }
+}
+
+impl ValidateLength for String {
+    fn validate_length(&self, min: Option<usize>, max: Option<usize>) -> Result<(), String> {
+        self.as_str().validate_length(min, max)
+    }
+}
+
+impl ValidateLength for ID {
+    fn validate_length(&self, min: Option<usize>, max: Option<usize>) -> Result<(), String> {
+        (**self).validate_length(min, max)
+    }
+}
+
+impl<T> ValidateLength for Vec<T> {
+    fn validate_length(&self, min: Option<usize>, max: Option<usize>) -> Result<(), String> {
+        check_bounds(self.len(), min, max, "length")
+    }
+}
+
+#[cfg(feature = "regex")]
+impl ValidateRegex for str {
+    fn validate_regex(&self, pattern: &'static str) -> Result<(), String> {
+        use std::{cell::RefCell, collections::HashMap};
+
+        thread_local! {
+            static CACHE: RefCell<HashMap<&'static str, regex::Regex>> = RefCell::default();
+        }
+
+        let is_match = CACHE.with(|cache| {
+            cache
+                .borrow_mut()
+                .entry(pattern)
+                .or_insert_with(|| {
+                    regex::Regex::new(pattern).unwrap_or_else(|e| {
+                        panic!("Invalid `validate(regex = {:?})` pattern: {}", pattern, e)
+                    })
+                })
+                .is_match(self)
+        });
+        if is_match {
+            Ok(())
+        } else {
+            Err(format!("value must match `{}` pattern", pattern))
+        }
+    }
+}
+
+#[cfg(feature = "regex")]
+impl ValidateRegex for String {
+    fn validate_regex(&self, pattern: &'static str) -> Result<(), String> {
+        self.as_str().validate_regex(pattern)
+    }
+}
+
+#[cfg(feature = "regex")]
+impl ValidateRegex for ID {
+    fn validate_regex(&self, pattern: &'static str) -> Result<(), String> {
+        (**self).validate_regex(pattern)
+    }
+}
+
+/// Implements the given validation trait for the nullable wrappers, so `null`
+/// values are always considered valid.
+macro_rules! impl_for_nullable {
+    ($trait:ident::$method:ident($($arg:ident: $arg_ty:ty),*)) => {
+        impl<T: $trait> $trait for Option<T> {
+            fn $method(&self, $($arg: $arg_ty),*) -> Result<(), String> {
+                self.as_ref().map_or(Ok(()), |v| v.$method($($arg),*))
+            }
+        }
+
+        impl<T: $trait> $trait for Nullable<T> {
+            fn $method(&self, $($arg: $arg_ty),*) -> Result<(), String> {
+                match self {
+                    Self::Some(v) => v.$method($($arg),*),
+                    Self::ImplicitNull | Self::ExplicitNull => Ok(()),
+                }
+            }
+        }
+    };
+}
+
+impl_for_nullable!(ValidateRange::validate_range(min: Option<f64>, max: Option<f64>));
+impl_for_nullable!(ValidateLength::validate_length(min: Option<usize>, max: Option<usize>));
+#[cfg(feature = "regex")]
+impl_for_nullable!(ValidateRegex::validate_regex(pattern: &'static str));
+
+#[cfg(test)]
+mod tests {
+    use super::{ValidateLength as _, ValidateRange as _};
+
+    #[test]
+    fn range() {
+        assert_eq!(5.validate_range(Some(1.0), Some(10.0)), Ok(()));
+        assert_eq!(
+            0.validate_range(Some(1.0), None),
+            Err("value must be at least 1, found: 0".into()),
+        );
+        assert_eq!(
+            10.5.validate_range(None, Some(10.0)),
+            Err("value must be at most 10, found: 10.5".into()),
+        );
+        assert_eq!(None::<i32>.validate_range(Some(1.0), None), Ok(()));
+    }
+
+    #[test]
+    fn length() {
+        assert_eq!("ab".validate_length(Some(2), Some(2)), Ok(()));
+        assert_eq!("äö".validate_length(None, Some(2)), Ok(()));
+        assert_eq!(
+            String::new().validate_length(Some(1), None),
+            Err("length must be at least 1, found: 0".into()),
+        );
+        assert_eq!(
+            vec![1, 2, 3].validate_length(None, Some(2)),
+            Err("length must be at most 2, found: 3".into()),
+        );
+    }
+}
@@ -6,9 +6,15 @@ pub mod helper;
 #[macro_use]
 pub mod reflect;
 
+#[macro_use]
+mod graphql_forward;
 #[macro_use]
 mod graphql_input_value;
 #[macro_use]
+mod graphql_namespace;
+#[macro_use]
+mod graphql_tuple;
+#[macro_use]
 mod graphql_value;
 #[macro_use]
 mod graphql_vars;
@@ -296,6 +296,18 @@ pub trait Fields<S> {
     const NAMES: Names;
 }
 
+/// Fields of a [GraphQL input object][1] as the [`Arguments`] of the
+/// [GraphQL fields][2] it's flattened into via `#[graphql(args)]`.
+///
+/// [1]: https://spec.graphql.org/October2021#sec-Input-Objects
+/// [2]: https://spec.graphql.org/October2021#sec-Language.Fields
+pub trait InputFields<S> {
+    /// [`Arguments`] made of the fields of this [GraphQL input object][1].
+    ///
+    /// [1]: https://spec.graphql.org/October2021#sec-Input-Objects
+    const FIELDS: Arguments;
+}
+
 /// [`Types`] of the [GraphQL interfaces][1] implemented by this type.
 ///
 /// [1]: https://spec.graphql.org/October2021#sec-Interfaces
@@ -470,6 +482,90 @@ pub const fn str_exists_in_arr(val: &str, arr: &[&str]) -> bool {
     false
 }
 
+/// Returns the total number of [`Name`]s in the given `names`.
+#[must_use]
+pub const fn names_len(names: &[Names]) -> usize {
+    let (mut i, mut len) = (0, 0);
+    while i < names.len() {
+        len += names[i].len();
+        i += 1;
+    }
+    len
+}
+
+/// Concatenates the given `names` into a single array in a `const` context.
+///
+/// # Panics
+///
+/// If `N` differs from the [`names_len()`] of the given `names`.
+#[must_use]
+pub const fn concat_names<const N: usize>(names: &[Names]) -> [Name; N] {
+    let mut out = [""; N];
+    let (mut i, mut n) = (0, 0);
+    while i < names.len() {
+        let mut j = 0;
+        while j < names[i].len() {
+            out[n] = names[i][j];
+            n += 1;
+            j += 1;
+        }
+        i += 1;
+    }
+    assert!(n == N, "`N` should be equal to the number of `names`");
+    out
+}
+
+/// Returns the total number of [`Argument`]s in the given `args`.
+#[must_use]
+pub const fn arguments_len(args: &[Arguments]) -> usize {
+    let (mut i, mut len) = (0, 0);
+    while i < args.len() {
+        len += args[i].len();
+        i += 1;
+    }
+    len
+}
+
+/// Concatenates the given `args` into a single array in a `const` context.
+///
+/// # Panics
+///
+/// If `N` differs from the [`arguments_len()`] of the given `args`.
+#[must_use]
+pub const fn concat_arguments<const N: usize>(args: &[Arguments]) -> [Argument; N] {
+    let mut out = [("", "", 0); N];
+    let (mut i, mut n) = (0, 0);
+    while i < args.len() {
+        let mut j = 0;
+        while j < args[i].len() {
+            out[n] = args[i][j];
+            n += 1;
+            j += 1;
+        }
+        i += 1;
+    }
+    assert!(n == N, "`N` should be equal to the number of `args`");
+    out
+}
+
+/// Returns the first [`Name`] occurring more than once in the given `names`,
+/// if any.
+#[must_use]
+pub const fn find_duplicate(names: Names) -> Option<Name> {
+    let mut i = 0;
+    while i < names.len() {
+        let mut j = i + 1;
+        while j < names.len() {
+            if str_eq(names[i], names[j]) {
+                return Some(names[i]);
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+    None
+}
+
 /// Compares strings in a `const` context.
 ///
 /// As there is no `const impl Trait` and `l == r` calls [`Eq`], we have to
@@ -523,6 +619,34 @@ macro_rules! assert_implemented_for {
     };
 }
 
+/// Asserts that the given `$ty` defines each of its [`Fields`] only once,
+/// which may be violated if they're split across multiple `impl` blocks.
+#[macro_export]
+macro_rules! assert_unique_fields {
+    ($scalar: ty, $ty: ty $(,)?) => {
+        const _: () = {
+            const DUPLICATE: Option<$crate::macros::reflect::Name> =
+                $crate::macros::reflect::find_duplicate(
+                    <$ty as $crate::macros::reflect::Fields<$scalar>>::NAMES,
+                );
+            if let Some(_) = DUPLICATE {
+                const FIELD: $crate::macros::reflect::Name = match DUPLICATE {
+                    Some(name) => name,
+                    None => "",
+                };
+                const MSG: &str = $crate::const_concat!(
+                    "Field `",
+                    FIELD,
+                    "` is defined more than once on `",
+                    <$ty as $crate::macros::reflect::BaseType<$scalar>>::NAME,
+                    "`.",
+                );
+                ::std::panic!("{}", MSG);
+            }
+        };
+    };
+}
+
 /// Asserts that `impl = ...` attribute argument has all the types referencing
 /// this GraphQL type in `#[graphql_interface(for = ...)]`.
 ///
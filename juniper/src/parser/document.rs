@@ -27,7 +27,9 @@ where
     S: ScalarValue,
 {
     let mut lexer = Lexer::new(s);
-    let mut parser = Parser::new(&mut lexer).map_err(|s| s.map(ParseError::LexerError))?;
+    let mut parser = Parser::new(&mut lexer)
+        .map_err(|s| s.map(ParseError::LexerError))?
+        .with_recursion_limit(schema.parser_recursion_limit());
     parse_document(&mut parser, schema)
 }
 
@@ -193,11 +195,13 @@ fn parse_selection_set<'a, 'b, S>(
 where
     S: ScalarValue,
 {
-    parser.unlocated_delimited_nonempty_list(
-        &Token::CurlyOpen,
-        |p| parse_selection(p, schema, fields),
-        &Token::CurlyClose,
-    )
+    parser.nested(|parser| {
+        parser.unlocated_delimited_nonempty_list(
+            &Token::CurlyOpen,
+            |p| parse_selection(p, schema, fields),
+            &Token::CurlyClose,
+        )
+    })
 }
 
 fn parse_selection<'a, 'b, S>(
@@ -521,7 +525,7 @@ pub fn parse_type<'a>(parser: &mut Parser<'a>) -> ParseResult<'a, Type<'a>> {
         start: start_pos, ..
     }) = parser.skip(&Token::BracketOpen)?
     {
-        let inner_type = parse_type(parser)?;
+        let inner_type = parser.nested(parse_type)?;
         let Spanning { end: end_pos, .. } = parser.expect(&Token::BracketClose)?;
         Spanning::start_end(
             &start_pos,
@@ -24,6 +24,9 @@ pub struct Lexer<'a> {
 #[allow(missing_docs)]
 pub enum ScalarToken<'a> {
     String(&'a str),
+    /// Raw contents of a `"""`-delimited block string, before its
+    /// indentation is stripped with [`block_string_value()`].
+    BlockString(&'a str),
     Float(&'a str),
     Int(&'a str),
 }
@@ -94,6 +97,12 @@ pub enum LexerError {
     InvalidNumber,
 }
 
+/// Delimiter of a block string literal.
+const BLOCK_STRING_QUOTES: &str = r#"""""#;
+
+/// Escaped delimiter inside a block string literal.
+const ESCAPED_BLOCK_STRING_QUOTES: &str = r#"\""""#;
+
 pub type LexerResult<'a> = Result<Spanning<Token<'a>>, Spanning<LexerError>>;
 
 impl<'a> Lexer<'a> {
@@ -132,6 +141,12 @@ impl<'a> Lexer<'a> {
         next
     }
 
+    /// Returns the not yet consumed part of the input source.
+    fn remaining(&mut self) -> &'a str {
+        let source = self.source;
+        self.iterator.peek().map_or("", |&(idx, _)| &source[idx..])
+    }
+
     fn emit_single_char(&mut self, t: Token<'a>) -> Spanning<Token<'a>> {
         assert!(self.position.index() <= self.length);
 
@@ -273,6 +288,48 @@ impl<'a> Lexer<'a> {
         ))
     }
 
+    fn scan_block_string(&mut self) -> LexerResult<'a> {
+        let start_pos = self.position;
+        let (start_idx, _) = self
+            .peek_char()
+            .ok_or_else(|| Spanning::zero_width(&self.position, LexerError::UnexpectedEndOfFile))?;
+        for _ in 0..BLOCK_STRING_QUOTES.len() {
+            self.next_char();
+        }
+        let content_idx = start_idx + BLOCK_STRING_QUOTES.len();
+
+        while let Some((idx, ch)) = self.peek_char() {
+            if self.remaining().starts_with(BLOCK_STRING_QUOTES) {
+                for _ in 0..BLOCK_STRING_QUOTES.len() {
+                    self.next_char();
+                }
+                return Ok(Spanning::start_end(
+                    &start_pos,
+                    &self.position,
+                    Token::Scalar(ScalarToken::BlockString(&self.source[content_idx..idx])),
+                ));
+            }
+            if self.remaining().starts_with(ESCAPED_BLOCK_STRING_QUOTES) {
+                for _ in 0..ESCAPED_BLOCK_STRING_QUOTES.len() {
+                    self.next_char();
+                }
+                continue;
+            }
+            if !is_source_char(ch) {
+                return Err(Spanning::zero_width(
+                    &self.position,
+                    LexerError::UnknownCharacterInString(ch),
+                ));
+            }
+            self.next_char();
+        }
+
+        Err(Spanning::zero_width(
+            &self.position,
+            LexerError::UnterminatedString,
+        ))
+    }
+
     fn scan_escaped_unicode(
         &mut self,
         start_pos: &SourcePosition,
@@ -316,6 +373,27 @@ impl<'a> Lexer<'a> {
             )
         })?;
 
+        if is_leading_surrogate(code_point) {
+            // A leading surrogate is only valid as the first half of a
+            // `\uXXXX\uXXXX` surrogate pair.
+            let trailing = self
+                .remaining()
+                .get(..6)
+                .and_then(|s| s.strip_prefix("\\u"))
+                .and_then(|s| u32::from_str_radix(s, 16).ok())
+                .filter(|&c| is_trailing_surrogate(c));
+            if trailing.is_none() {
+                return Err(Spanning::zero_width(
+                    start_pos,
+                    LexerError::UnknownEscapeSequence("\\u".to_owned() + escape),
+                ));
+            }
+            for _ in 0..6 {
+                self.next_char();
+            }
+            return Ok(());
+        }
+
         char::from_u32(code_point)
             .ok_or_else(|| {
                 Spanning::zero_width(
@@ -459,6 +537,9 @@ impl<'a> Iterator for Lexer<'a> {
             Some('@') => Ok(self.emit_single_char(Token::At)),
             Some('|') => Ok(self.emit_single_char(Token::Pipe)),
             Some('.') => self.scan_ellipsis(),
+            Some('"') if self.remaining().starts_with(BLOCK_STRING_QUOTES) => {
+                self.scan_block_string()
+            }
             Some('"') => self.scan_string(),
             Some(ch) => {
                 if is_number_start(ch) {
@@ -490,6 +571,9 @@ impl<'a> fmt::Display for Token<'a> {
             Token::Scalar(ScalarToken::String(s)) => {
                 write!(f, "\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
             }
+            Token::Scalar(ScalarToken::BlockString(s)) => {
+                write!(f, "{}{}{}", BLOCK_STRING_QUOTES, s, BLOCK_STRING_QUOTES)
+            }
             Token::ExclamationMark => write!(f, "!"),
             Token::Dollar => write!(f, "$"),
             Token::ParenOpen => write!(f, "("),
@@ -508,6 +592,69 @@ impl<'a> fmt::Display for Token<'a> {
     }
 }
 
+/// Checks whether the given code point is the leading (high) half of a UTF-16
+/// surrogate pair.
+pub(crate) fn is_leading_surrogate(c: u32) -> bool {
+    (0xD800..=0xDBFF).contains(&c)
+}
+
+/// Checks whether the given code point is the trailing (low) half of a UTF-16
+/// surrogate pair.
+pub(crate) fn is_trailing_surrogate(c: u32) -> bool {
+    (0xDC00..=0xDFFF).contains(&c)
+}
+
+/// Combines the halves of a UTF-16 surrogate pair into a [`char`].
+pub(crate) fn decode_surrogate_pair(leading: u32, trailing: u32) -> Option<char> {
+    char::from_u32(0x10000 + ((leading - 0xD800) << 10) + (trailing - 0xDC00))
+}
+
+/// Produces the value of a block string literal out of its raw contents, as
+/// defined by the [`BlockStringValue()`][0] algorithm of the GraphQL
+/// specification.
+///
+/// Escaped `\"""` delimiters are unescaped, the common indentation of all the
+/// lines but the first one is removed, and leading and trailing blank lines
+/// are dropped. Line terminators are normalized to `\n`.
+///
+/// [0]: https://spec.graphql.org/October2021#BlockStringValue()
+pub fn block_string_value(raw: &str) -> String {
+    let raw = raw.replace(ESCAPED_BLOCK_STRING_QUOTES, BLOCK_STRING_QUOTES);
+    let lines = raw
+        .split("\r\n")
+        .flat_map(|l| l.split(['\n', '\r']))
+        .collect::<Vec<_>>();
+
+    let indent_len = |l: &str| l.len() - l.trim_start_matches([' ', '\t']).len();
+    let common_indent = lines
+        .iter()
+        .skip(1)
+        .filter(|l| indent_len(l) < l.len())
+        .map(|l| indent_len(l))
+        .min();
+
+    let lines = lines
+        .iter()
+        .enumerate()
+        .map(|(i, l)| match common_indent {
+            Some(indent) if i > 0 => l.get(indent..).unwrap_or(""),
+            _ => l,
+        })
+        .collect::<Vec<_>>();
+
+    let is_blank = |l: &&str| l.chars().all(|c| c == ' ' || c == '\t');
+    let first = lines
+        .iter()
+        .position(|l| !is_blank(l))
+        .unwrap_or(lines.len());
+    let last = lines
+        .iter()
+        .rposition(|l| !is_blank(l))
+        .map_or(first, |i| i + 1);
+
+    lines[first..last].join("\n")
+}
+
 fn is_source_char(c: char) -> bool {
     c == '\t' || c == '\n' || c == '\r' || c >= ' '
 }
@@ -12,8 +12,19 @@ mod tests;
 
 pub use self::document::parse_document_source;
 
+pub(crate) use self::{
+    document::parse_type,
+    lexer::{decode_surrogate_pair, is_leading_surrogate, is_trailing_surrogate},
+};
+
+#[cfg(feature = "serde_json")]
+pub(crate) use self::value::parse_value_literal;
+
 pub use self::{
-    lexer::{Lexer, LexerError, ScalarToken, Token},
-    parser::{OptionParseResult, ParseError, ParseResult, Parser, UnlocatedParseResult},
+    lexer::{block_string_value, Lexer, LexerError, ScalarToken, Token},
+    parser::{
+        OptionParseResult, ParseError, ParseResult, Parser, UnlocatedParseResult,
+        DEFAULT_RECURSION_LIMIT,
+    },
     utils::{SourcePosition, Spanning},
 };
@@ -16,8 +16,28 @@ pub enum ParseError<'a> {
 
     /// A scalar of unexpected type occurred in the source
     ExpectedScalarError(&'static str),
+
+    /// A scalar literal is not a valid value of its type, as reported by the
+    /// [`ParseScalarValue`] implementation of this type
+    ///
+    /// Unlike other errors of parsing a scalar literal, this one is not
+    /// recovered from by parsing the literal as a value of its inferred type,
+    /// but is reported at the position provided by
+    /// [`ParseScalarValue::from_spanned_str()`].
+    ///
+    /// [`ParseScalarValue`]: crate::ParseScalarValue
+    /// [`ParseScalarValue::from_spanned_str()`]: crate::ParseScalarValue::from_spanned_str
+    InvalidScalar(String),
+
+    /// Selection sets, list/object literals or list types are nested deeper
+    /// than the recursion limit of the [`Parser`] allows
+    RecursionLimitExceeded,
 }
 
+/// Default maximum nesting depth of selection sets, list/object literals and
+/// list types allowed by the [`Parser`].
+pub const DEFAULT_RECURSION_LIMIT: usize = 128;
+
 #[doc(hidden)]
 pub type ParseResult<'a, T> = Result<Spanning<T>, Spanning<ParseError<'a>>>;
 
@@ -31,6 +51,8 @@ pub type OptionParseResult<'a, T> = Result<Option<Spanning<T>>, Spanning<ParseEr
 #[derive(Debug)]
 pub struct Parser<'a> {
     tokens: Vec<Spanning<Token<'a>>>,
+    depth: usize,
+    recursion_limit: usize,
 }
 
 impl<'a> Parser<'a> {
@@ -45,7 +67,40 @@ impl<'a> Parser<'a> {
             }
         }
 
-        Ok(Parser { tokens })
+        Ok(Parser {
+            tokens,
+            depth: 0,
+            recursion_limit: DEFAULT_RECURSION_LIMIT,
+        })
+    }
+
+    #[doc(hidden)]
+    pub fn with_recursion_limit(mut self, limit: usize) -> Self {
+        self.recursion_limit = limit;
+        self
+    }
+
+    /// Runs the given `parser` one nesting level deeper, failing with
+    /// [`ParseError::RecursionLimitExceeded`] instead of risking a stack
+    /// overflow once the recursion limit is reached.
+    #[doc(hidden)]
+    pub fn nested<T, F>(&mut self, parser: F) -> Result<T, Spanning<ParseError<'a>>>
+    where
+        F: FnOnce(&mut Parser<'a>) -> Result<T, Spanning<ParseError<'a>>>,
+    {
+        if self.depth >= self.recursion_limit {
+            return Err(Spanning::start_end(
+                &self.peek().start,
+                &self.peek().end,
+                ParseError::RecursionLimitExceeded,
+            ));
+        }
+
+        self.depth += 1;
+        let res = parser(self);
+        self.depth -= 1;
+
+        res
     }
 
     #[doc(hidden)]
@@ -200,6 +255,8 @@ impl<'a> fmt::Display for ParseError<'a> {
             ParseError::UnexpectedEndOfFile => write!(f, "Unexpected end of input"),
             ParseError::LexerError(ref err) => err.fmt(f),
             ParseError::ExpectedScalarError(err) => err.fmt(f),
+            ParseError::InvalidScalar(ref err) => err.fmt(f),
+            ParseError::RecursionLimitExceeded => write!(f, "Exceeded maximum nesting depth"),
         }
     }
 }
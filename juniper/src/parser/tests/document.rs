@@ -1,7 +1,10 @@
 use crate::{
     ast::{Arguments, Definition, Field, Operation, OperationType, OwnedDocument, Selection},
     graphql_input_value,
-    parser::{document::parse_document_source, ParseError, SourcePosition, Spanning, Token},
+    parser::{
+        document::parse_document_source, ParseError, SourcePosition, Spanning, Token,
+        DEFAULT_RECURSION_LIMIT,
+    },
     schema::model::SchemaType,
     types::scalars::{EmptyMutation, EmptySubscription},
     validation::test_harness::{MutationRoot, QueryRoot, SubscriptionRoot},
@@ -173,3 +176,49 @@ fn issue_427_panic_is_not_expected() {
         ParseError::ExpectedScalarError("There needs to be a Float type")
     );
 }
+
+#[test]
+fn recursion_limit() {
+    let nested_selection = |depth: usize| format!("{}{}", "{ f ".repeat(depth), "}".repeat(depth));
+
+    parse_document::<DefaultScalarValue>(&nested_selection(DEFAULT_RECURSION_LIMIT));
+    assert_eq!(
+        parse_document_error::<DefaultScalarValue>(&nested_selection(DEFAULT_RECURSION_LIMIT + 1))
+            .item,
+        ParseError::RecursionLimitExceeded,
+    );
+    assert_eq!(
+        parse_document_error::<DefaultScalarValue>(&nested_selection(100_000)).item,
+        ParseError::RecursionLimitExceeded,
+    );
+
+    let mut schema = SchemaType::<DefaultScalarValue>::new::<
+        QueryRoot,
+        MutationRoot,
+        SubscriptionRoot,
+    >(&(), &(), &());
+    schema.set_parser_recursion_limit(3);
+
+    for (source, ok) in [
+        ("{ f { f { f } } }", true),
+        ("{ f { f { f { f } } } }", false),
+        ("{ f(a: [[1]]) }", true),
+        ("{ f(a: [[[1]]]) }", false),
+        ("{ f(a: {b: {c: 1}}) }", true),
+        ("{ f(a: {b: [{c: 1}]}) }", false),
+        ("query($v: [[[Int]]]) { f }", true),
+        ("query($v: [[[[Int]]]]) { f }", false),
+    ] {
+        let res = parse_document_source(source, &schema);
+        if ok {
+            assert!(res.is_ok(), "Parse error on input {:#?}", source);
+        } else {
+            assert_eq!(
+                res.unwrap_err().item,
+                ParseError::RecursionLimitExceeded,
+                "on input {:#?}",
+                source,
+            );
+        }
+    }
+}
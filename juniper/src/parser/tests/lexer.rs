@@ -1,4 +1,6 @@
-use crate::parser::{Lexer, LexerError, ScalarToken, SourcePosition, Spanning, Token};
+use crate::parser::{
+    block_string_value, Lexer, LexerError, ScalarToken, SourcePosition, Spanning, Token,
+};
 
 fn tokenize_to_vec<'a>(s: &'a str) -> Vec<Spanning<Token<'a>>> {
     let mut tokens = Vec::new();
@@ -199,6 +201,109 @@ fn strings() {
             Token::Scalar(ScalarToken::String(r#"unicode \u1234\u5678\u90AB\uCDEF"#))
         )
     );
+
+    assert_eq!(
+        tokenize_single(r#""surrogate \uD83D\uDE00 pair""#),
+        Spanning::start_end(
+            &SourcePosition::new(0, 0, 0),
+            &SourcePosition::new(29, 0, 29),
+            Token::Scalar(ScalarToken::String(r#"surrogate \uD83D\uDE00 pair"#))
+        )
+    );
+}
+
+#[test]
+fn block_strings() {
+    assert_eq!(
+        tokenize_single(r#""""simple""""#),
+        Spanning::start_end(
+            &SourcePosition::new(0, 0, 0),
+            &SourcePosition::new(12, 0, 12),
+            Token::Scalar(ScalarToken::BlockString("simple"))
+        )
+    );
+
+    assert_eq!(
+        tokenize_single(r#""""contains " and \n and \u1234""""#),
+        Spanning::start_end(
+            &SourcePosition::new(0, 0, 0),
+            &SourcePosition::new(34, 0, 34),
+            Token::Scalar(ScalarToken::BlockString(r#"contains " and \n and \u1234"#))
+        )
+    );
+
+    assert_eq!(
+        tokenize_single(r#""""escaped \""" quotes""""#),
+        Spanning::start_end(
+            &SourcePosition::new(0, 0, 0),
+            &SourcePosition::new(25, 0, 25),
+            Token::Scalar(ScalarToken::BlockString(r#"escaped \""" quotes"#))
+        )
+    );
+
+    assert_eq!(
+        tokenize_single("\"\"\"\n  multi\n  line\n\"\"\""),
+        Spanning::start_end(
+            &SourcePosition::new(0, 0, 0),
+            &SourcePosition::new(22, 3, 3),
+            Token::Scalar(ScalarToken::BlockString("\n  multi\n  line\n"))
+        )
+    );
+
+    assert_eq!(
+        tokenize_single(r#""""""""#),
+        Spanning::start_end(
+            &SourcePosition::new(0, 0, 0),
+            &SourcePosition::new(6, 0, 6),
+            Token::Scalar(ScalarToken::BlockString(""))
+        )
+    );
+}
+
+#[test]
+fn block_string_errors() {
+    assert_eq!(
+        tokenize_error(r#""""no end quotes""#),
+        Spanning::zero_width(
+            &SourcePosition::new(17, 0, 17),
+            LexerError::UnterminatedString
+        )
+    );
+
+    assert_eq!(
+        tokenize_error("\"\"\"contains \u{0007} control char\"\"\""),
+        Spanning::zero_width(
+            &SourcePosition::new(12, 0, 12),
+            LexerError::UnknownCharacterInString('\u{0007}')
+        )
+    );
+}
+
+#[test]
+fn block_string_values() {
+    assert_eq!(block_string_value("simple"), "simple");
+    assert_eq!(block_string_value("  leading space"), "  leading space");
+    assert_eq!(
+        block_string_value("\n    Hello,\n      World!\n\n    Yours,\n      GraphQL.\n  "),
+        "Hello,\n  World!\n\nYours,\n  GraphQL.",
+    );
+    assert_eq!(
+        block_string_value("first line\n    indented\n    lines"),
+        "first line\nindented\nlines",
+    );
+    assert_eq!(
+        block_string_value("\r\n\t\ttabs\r\n\t\t\tand\r\t\tcarriage returns\r\n"),
+        "tabs\n\tand\ncarriage returns",
+    );
+    assert_eq!(
+        block_string_value("  \n\n  only\n  \n  blank lines  \n \n"),
+        "only\n\nblank lines  ",
+    );
+    assert_eq!(
+        block_string_value(r#"escaped \""" quotes"#),
+        r#"escaped """ quotes"#
+    );
+    assert_eq!(block_string_value("  \n \t \n"), "");
 }
 
 #[test]
@@ -307,6 +412,30 @@ fn string_errors() {
         )
     );
 
+    assert_eq!(
+        tokenize_error(r#""lone \uD83D surrogate""#),
+        Spanning::zero_width(
+            &SourcePosition::new(7, 0, 7),
+            LexerError::UnknownEscapeSequence("\\uD83D".to_owned())
+        )
+    );
+
+    assert_eq!(
+        tokenize_error(r#""unpaired \uD83D\u0041 surrogate""#),
+        Spanning::zero_width(
+            &SourcePosition::new(11, 0, 11),
+            LexerError::UnknownEscapeSequence("\\uD83D".to_owned())
+        )
+    );
+
+    assert_eq!(
+        tokenize_error(r#""trailing \uDE00 surrogate""#),
+        Spanning::zero_width(
+            &SourcePosition::new(11, 0, 11),
+            LexerError::UnknownEscapeSequence("\\uDE00".to_owned())
+        )
+    );
+
     assert_eq!(
         tokenize_error(r#""unterminated in string \""#),
         Spanning::zero_width(
@@ -684,6 +813,14 @@ fn display() {
         "\"string with \\\\ escape and \\\" quote\""
     );
 
+    assert_eq!(
+        format!(
+            "{}",
+            Token::Scalar(ScalarToken::BlockString("block \" string"))
+        ),
+        "\"\"\"block \" string\"\"\"",
+    );
+
     assert_eq!(format!("{}", Token::ExclamationMark), "!");
     assert_eq!(format!("{}", Token::Dollar), "$");
     assert_eq!(format!("{}", Token::ParenOpen), "(");
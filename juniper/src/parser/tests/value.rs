@@ -1,13 +1,19 @@
 use crate::{
     ast::{FromInputValue, InputValue, Type},
     graphql_input_value,
-    parser::{value::parse_value_literal, Lexer, Parser, SourcePosition, Spanning},
+    parser::{
+        value::parse_value_literal, Lexer, ParseError, Parser, ScalarToken, SourcePosition,
+        Spanning,
+    },
     schema::{
         meta::{Argument, EnumMeta, EnumValue, InputObjectMeta, MetaType, ScalarMeta},
         model::SchemaType,
     },
     types::scalars::{EmptyMutation, EmptySubscription},
-    value::{DefaultScalarValue, ParseScalarValue, ScalarValue},
+    value::{
+        DefaultScalarValue, ParseScalarResult, ParseScalarValue, ScalarValue,
+        SpannedParseScalarResult,
+    },
     GraphQLEnum, GraphQLInputObject, IntoFieldError,
 };
 
@@ -52,12 +58,74 @@ impl Query {
     }
 }
 
+/// `#rrggbb` color, pointing at the first invalid digit of its literal.
+struct Rgb;
+
+impl FromInputValue for Rgb {
+    type Error = String;
+
+    fn from_input_value(_: &InputValue) -> Result<Self, Self::Error> {
+        Ok(Self)
+    }
+}
+
+impl ParseScalarValue for Rgb {
+    fn from_str(value: ScalarToken<'_>) -> ParseScalarResult<'_> {
+        <String as ParseScalarValue>::from_str(value)
+    }
+
+    fn from_spanned_str(token: Spanning<ScalarToken<'_>>) -> SpannedParseScalarResult<'_> {
+        if let ScalarToken::String(s) = token.item {
+            if let Some((i, _)) = s
+                .char_indices()
+                .skip(1)
+                .find(|(_, c)| !c.is_ascii_hexdigit())
+            {
+                // Skip the opening quote of the literal.
+                let offset = i + 1;
+                let pos = SourcePosition::new(
+                    token.start.index() + offset,
+                    token.start.line(),
+                    token.start.column() + offset,
+                );
+                return Err(Spanning::single_width(
+                    &pos,
+                    ParseError::InvalidScalar(format!("Invalid hex digit in `{}`", s)),
+                ));
+            }
+        }
+        Self::from_str(token.item).map_err(|e| Spanning::start_end(&token.start, &token.end, e))
+    }
+}
+
+/// Even integer, rejecting the odd ones without a precise position.
+struct Even;
+
+impl FromInputValue for Even {
+    type Error = String;
+
+    fn from_input_value(_: &InputValue) -> Result<Self, Self::Error> {
+        Ok(Self)
+    }
+}
+
+impl ParseScalarValue for Even {
+    fn from_str(value: ScalarToken<'_>) -> ParseScalarResult<'_> {
+        match value {
+            ScalarToken::Int(v) if v.ends_with(['1', '3', '5', '7', '9']) => {
+                Err(ParseError::InvalidScalar(format!("`{}` is odd", v)))
+            }
+            _ => <i32 as ParseScalarValue>::from_str(value),
+        }
+    }
+}
+
 fn scalar_meta<T>(name: &'static str) -> MetaType
 where
     T: FromInputValue<DefaultScalarValue> + ParseScalarValue<DefaultScalarValue>,
     T::Error: IntoFieldError,
 {
-    MetaType::Scalar(ScalarMeta::new::<T>(name.into()))
+    MetaType::Scalar(ScalarMeta::new::<T>(name))
 }
 
 fn parse_value<S>(s: &str, meta: &MetaType<S>) -> Spanning<InputValue<S>>
@@ -72,6 +140,15 @@ where
         .expect(&format!("Parse error on input {:#?}", s))
 }
 
+fn parse_value_error<'a>(s: &'a str, meta: &MetaType) -> Spanning<ParseError<'a>> {
+    let mut lexer = Lexer::new(s);
+    let mut parser = Parser::new(&mut lexer).expect(&format!("Lexer error on input {:#?}", s));
+    let schema = SchemaType::new::<Query, EmptyMutation<()>, EmptySubscription<()>>(&(), &(), &());
+
+    parse_value_literal(&mut parser, false, &schema, Some(meta))
+        .expect_err(&format!("No parse error on input {:#?}", s))
+}
+
 #[test]
 fn input_value_literals() {
     assert_eq!(
@@ -114,8 +191,30 @@ fn input_value_literals() {
             graphql_input_value!("test"),
         ),
     );
+    assert_eq!(
+        parse_value::<DefaultScalarValue>(
+            r#""surrogate \uD83D\uDE00 pair""#,
+            &scalar_meta::<String>("String"),
+        ),
+        Spanning::start_end(
+            &SourcePosition::new(0, 0, 0),
+            &SourcePosition::new(29, 0, 29),
+            graphql_input_value!("surrogate \u{1F600} pair"),
+        ),
+    );
+    assert_eq!(
+        parse_value::<DefaultScalarValue>(
+            "\"\"\"\n    block\n      \\\"\"\" string\n  \"\"\"",
+            &scalar_meta::<String>("String"),
+        ),
+        Spanning::start_end(
+            &SourcePosition::new(0, 0, 0),
+            &SourcePosition::new(37, 3, 5),
+            graphql_input_value!("block\n  \"\"\" string"),
+        ),
+    );
     let values = &[EnumValue::new("enum_value")];
-    let e: EnumMeta<DefaultScalarValue> = EnumMeta::new::<Enum>("TestEnum".into(), values);
+    let e: EnumMeta<DefaultScalarValue> = EnumMeta::new::<Enum>("TestEnum", values);
 
     assert_eq!(
         parse_value::<DefaultScalarValue>("enum_value", &MetaType::Enum(e)),
@@ -175,7 +274,7 @@ fn input_value_literals() {
         Argument::new("key", Type::NonNullNamed("Int".into())),
         Argument::new("other", Type::NonNullNamed("Bar".into())),
     ];
-    let meta = &MetaType::InputObject(InputObjectMeta::new::<Foo>("foo".into(), &fields));
+    let meta = &MetaType::InputObject(InputObjectMeta::new::<Foo>("foo", &fields));
     assert_eq!(
         parse_value::<DefaultScalarValue>("{}", meta),
         Spanning::start_end(
@@ -230,3 +329,34 @@ fn input_value_literals() {
         ),
     );
 }
+
+#[test]
+fn invalid_scalar_literals() {
+    assert_eq!(
+        parse_value_error(r##"  "#12x456""##, &scalar_meta::<Rgb>("Rgb")),
+        Spanning::start_end(
+            &SourcePosition::new(6, 0, 6),
+            &SourcePosition::new(7, 0, 7),
+            ParseError::InvalidScalar("Invalid hex digit in `#12x456`".into()),
+        ),
+    );
+    assert_eq!(
+        parse_value_error("[2, 13]", &scalar_meta::<Even>("Even")),
+        Spanning::start_end(
+            &SourcePosition::new(4, 0, 4),
+            &SourcePosition::new(6, 0, 6),
+            ParseError::InvalidScalar("`13` is odd".into()),
+        ),
+    );
+
+    // Other errors fall back to parsing the literal as its inferred type,
+    // leaving the error to the validation.
+    assert_eq!(
+        parse_value::<DefaultScalarValue>(r#""24""#, &scalar_meta::<Even>("Even")),
+        Spanning::start_end(
+            &SourcePosition::new(0, 0, 0),
+            &SourcePosition::new(4, 0, 4),
+            graphql_input_value!("24"),
+        ),
+    );
+}
@@ -60,9 +60,16 @@ where
                 end,
             } = parser.next_token()?
             {
-                (s.parse_fn)(scalar)
-                    .map(|s| Spanning::start_end(&start, &end, InputValue::Scalar(s)))
-                    .or_else(|_| parse_scalar_literal_by_infered_type(scalar, &start, &end, schema))
+                match (s.parse_fn)(Spanning::start_end(&start, &end, scalar)) {
+                    Ok(s) => Ok(Spanning::start_end(&start, &end, InputValue::Scalar(s))),
+                    Err(
+                        e @ Spanning {
+                            item: ParseError::InvalidScalar(_),
+                            ..
+                        },
+                    ) => Err(e),
+                    Err(_) => parse_scalar_literal_by_infered_type(scalar, &start, &end, schema),
+                }
             } else {
                 unreachable!()
             }
@@ -127,11 +134,13 @@ where
     S: ScalarValue,
 {
     Ok(parser
-        .delimited_list(
-            &Token::BracketOpen,
-            |p| parse_value_literal(p, is_const, schema, tpe),
-            &Token::BracketClose,
-        )?
+        .nested(|parser| {
+            parser.delimited_list(
+                &Token::BracketOpen,
+                |p| parse_value_literal(p, is_const, schema, tpe),
+                &Token::BracketClose,
+            )
+        })?
         .map(InputValue::parsed_list))
 }
 
@@ -145,11 +154,13 @@ where
     S: ScalarValue,
 {
     Ok(parser
-        .delimited_list(
-            &Token::CurlyOpen,
-            |p| parse_object_field(p, is_const, schema, object_tpe),
-            &Token::CurlyClose,
-        )?
+        .nested(|parser| {
+            parser.delimited_list(
+                &Token::CurlyOpen,
+                |p| parse_object_field(p, is_const, schema, object_tpe),
+                &Token::CurlyClose,
+            )
+        })?
         .map(|items| InputValue::parsed_object(items.into_iter().map(|s| s.item).collect())))
 }
 
@@ -208,36 +219,41 @@ fn parse_scalar_literal_by_infered_type<'a, 'b, S>(
 where
     S: ScalarValue,
 {
+    let spanned = Spanning::start_end(start, end, token);
     let result = match token {
-        ScalarToken::String(_) => {
+        ScalarToken::String(_) | ScalarToken::BlockString(_) => {
             if let Some(&MetaType::Scalar(ref s)) = schema.concrete_type_by_name("String") {
-                (s.parse_fn)(token).map(InputValue::Scalar)
+                (s.parse_fn)(spanned).map(InputValue::Scalar)
             } else {
-                Err(ParseError::ExpectedScalarError(
-                    "There needs to be a String type",
+                Err(Spanning::start_end(
+                    start,
+                    end,
+                    ParseError::ExpectedScalarError("There needs to be a String type"),
                 ))
             }
         }
         ScalarToken::Int(_) => {
             if let Some(&MetaType::Scalar(ref s)) = schema.concrete_type_by_name("Int") {
-                (s.parse_fn)(token).map(InputValue::Scalar)
+                (s.parse_fn)(spanned).map(InputValue::Scalar)
             } else {
-                Err(ParseError::ExpectedScalarError(
-                    "There needs to be an Int type",
+                Err(Spanning::start_end(
+                    start,
+                    end,
+                    ParseError::ExpectedScalarError("There needs to be an Int type"),
                 ))
             }
         }
         ScalarToken::Float(_) => {
             if let Some(&MetaType::Scalar(ref s)) = schema.concrete_type_by_name("Float") {
-                (s.parse_fn)(token).map(InputValue::Scalar)
+                (s.parse_fn)(spanned).map(InputValue::Scalar)
             } else {
-                Err(ParseError::ExpectedScalarError(
-                    "There needs to be a Float type",
+                Err(Spanning::start_end(
+                    start,
+                    end,
+                    ParseError::ExpectedScalarError("There needs to be a Float type"),
                 ))
             }
         }
     };
-    result
-        .map(|s| Spanning::start_end(start, end, s))
-        .map_err(|e| Spanning::start_end(start, end, e))
+    result.map(|s| Spanning::start_end(start, end, s))
 }
@@ -4,13 +4,16 @@ use juniper::IntoFieldError;
 use std::{
     borrow::{Cow, ToOwned},
     fmt,
+    sync::Arc,
 };
 
+pub use crate::types::name::Name;
+
 use crate::{
     ast::{FromInputValue, InputValue, Type},
-    parser::{ParseError, ScalarToken},
+    parser::{ParseError, ScalarToken, Spanning},
     schema::model::SchemaType,
-    types::base::TypeKind,
+    types::base::{Arguments, TypeKind},
     value::{DefaultScalarValue, ParseScalarValue},
     FieldError,
 };
@@ -45,7 +48,7 @@ impl DeprecationStatus {
 /// Scalar type metadata
 pub struct ScalarMeta<'a, S> {
     #[doc(hidden)]
-    pub name: Cow<'a, str>,
+    pub name: Name,
     #[doc(hidden)]
     pub description: Option<String>,
     #[doc(hidden)]
@@ -57,8 +60,12 @@ pub struct ScalarMeta<'a, S> {
 /// Shortcut for an [`InputValue`] parsing function.
 pub type InputValueParseFn<S> = for<'b> fn(&'b InputValue<S>) -> Result<(), FieldError<S>>;
 
-/// Shortcut for a [`ScalarToken`] parsing function.
-pub type ScalarTokenParseFn<S> = for<'b> fn(ScalarToken<'b>) -> Result<S, ParseError<'b>>;
+/// Shortcut for a function parsing a [`ScalarToken`] positioned in the source.
+pub type ScalarTokenParseFn<S> =
+    for<'b> fn(Spanning<ScalarToken<'b>>) -> Result<S, Spanning<ParseError<'b>>>;
+
+/// Shortcut for a function computing a [`Complexity::Dynamic`] cost of a field.
+pub type ComplexityFn<S> = dyn Fn(&Arguments<S>, usize) -> usize + Send + Sync;
 
 /// List type metadata
 #[derive(Debug)]
@@ -81,19 +88,19 @@ pub struct NullableMeta<'a> {
 #[derive(Debug)]
 pub struct ObjectMeta<'a, S> {
     #[doc(hidden)]
-    pub name: Cow<'a, str>,
+    pub name: Name,
     #[doc(hidden)]
     pub description: Option<String>,
     #[doc(hidden)]
     pub fields: Vec<Field<'a, S>>,
     #[doc(hidden)]
-    pub interface_names: Vec<String>,
+    pub interface_names: Vec<Name>,
 }
 
 /// Enum type metadata
-pub struct EnumMeta<'a, S> {
+pub struct EnumMeta<S> {
     #[doc(hidden)]
-    pub name: Cow<'a, str>,
+    pub name: Name,
     #[doc(hidden)]
     pub description: Option<String>,
     #[doc(hidden)]
@@ -105,7 +112,7 @@ pub struct EnumMeta<'a, S> {
 #[derive(Debug)]
 pub struct InterfaceMeta<'a, S> {
     #[doc(hidden)]
-    pub name: Cow<'a, str>,
+    pub name: Name,
     #[doc(hidden)]
     pub description: Option<String>,
     #[doc(hidden)]
@@ -114,19 +121,19 @@ pub struct InterfaceMeta<'a, S> {
 
 /// Union type metadata
 #[derive(Debug)]
-pub struct UnionMeta<'a> {
+pub struct UnionMeta {
     #[doc(hidden)]
-    pub name: Cow<'a, str>,
+    pub name: Name,
     #[doc(hidden)]
     pub description: Option<String>,
     #[doc(hidden)]
-    pub of_type_names: Vec<String>,
+    pub of_type_names: Vec<Name>,
 }
 
 /// Input object metadata
 pub struct InputObjectMeta<'a, S> {
     #[doc(hidden)]
-    pub name: Cow<'a, str>,
+    pub name: Name,
     #[doc(hidden)]
     pub description: Option<String>,
     #[doc(hidden)]
@@ -156,11 +163,11 @@ pub enum MetaType<'a, S = DefaultScalarValue> {
     #[doc(hidden)]
     Object(ObjectMeta<'a, S>),
     #[doc(hidden)]
-    Enum(EnumMeta<'a, S>),
+    Enum(EnumMeta<S>),
     #[doc(hidden)]
     Interface(InterfaceMeta<'a, S>),
     #[doc(hidden)]
-    Union(UnionMeta<'a>),
+    Union(UnionMeta),
     #[doc(hidden)]
     InputObject(InputObjectMeta<'a, S>),
     #[doc(hidden)]
@@ -171,7 +178,7 @@ pub enum MetaType<'a, S = DefaultScalarValue> {
 #[derive(Debug, Clone)]
 pub struct Field<'a, S> {
     #[doc(hidden)]
-    pub name: smartstring::alias::String,
+    pub name: Name,
     #[doc(hidden)]
     pub description: Option<String>,
     #[doc(hidden)]
@@ -180,6 +187,57 @@ pub struct Field<'a, S> {
     pub field_type: Type<'a>,
     #[doc(hidden)]
     pub deprecation_status: DeprecationStatus,
+    #[doc(hidden)]
+    pub pii: bool,
+    #[doc(hidden)]
+    pub hidden: bool,
+    #[doc(hidden)]
+    pub complexity: Option<Complexity<S>>,
+}
+
+/// Declared cost of resolving a [`Field`], used by the query cost analysis to
+/// reject too expensive operations before executing them.
+///
+/// A [`Field`] without a declared [`Complexity`] costs `1` plus the cost of its
+/// selection set.
+pub enum Complexity<S> {
+    /// Fixed cost of the [`Field`] itself, added to the cost of its selection
+    /// set.
+    Static(usize),
+
+    /// Function computing the total cost of the [`Field`] from its
+    /// [`Arguments`] and the cost of its selection set (like multiplying the
+    /// latter by the requested page size).
+    Dynamic(Arc<ComplexityFn<S>>),
+}
+
+impl<S> Complexity<S> {
+    /// Computes the total cost of a [`Field`] with this [`Complexity`], given
+    /// its `args` and the `child_cost` of its selection set.
+    pub fn cost(&self, args: &Arguments<S>, child_cost: usize) -> usize {
+        match self {
+            Self::Static(cost) => cost.saturating_add(child_cost),
+            Self::Dynamic(f) => f(args, child_cost),
+        }
+    }
+}
+
+impl<S> Clone for Complexity<S> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Static(cost) => Self::Static(*cost),
+            Self::Dynamic(f) => Self::Dynamic(Arc::clone(f)),
+        }
+    }
+}
+
+impl<S> fmt::Debug for Complexity<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Static(cost) => f.debug_tuple("Static").field(cost).finish(),
+            Self::Dynamic(_) => f.debug_tuple("Dynamic").finish(),
+        }
+    }
 }
 
 impl<'a, S> Field<'a, S> {
@@ -188,19 +246,32 @@ impl<'a, S> Field<'a, S> {
         // "used exclusively by GraphQL’s introspection system"
         self.name.starts_with("__")
     }
+
+    /// Returns true if this [`Field`] is tagged as containing personally
+    /// identifiable information.
+    pub fn is_pii(&self) -> bool {
+        self.pii
+    }
+
+    /// Returns true if this [`Field`] is hidden from introspection.
+    pub fn is_hidden(&self) -> bool {
+        self.hidden
+    }
 }
 
 /// Metadata for an argument to a field
 #[derive(Debug, Clone)]
 pub struct Argument<'a, S> {
     #[doc(hidden)]
-    pub name: String,
+    pub name: Name,
     #[doc(hidden)]
     pub description: Option<String>,
     #[doc(hidden)]
     pub arg_type: Type<'a>,
     #[doc(hidden)]
     pub default_value: Option<InputValue<S>>,
+    #[doc(hidden)]
+    pub deprecation_status: DeprecationStatus,
 }
 
 impl<'a, S> Argument<'a, S> {
@@ -272,6 +343,37 @@ impl<'a, S> MetaType<'a, S> {
         }
     }
 
+    /// Checks whether this [`MetaType`] describes the same GraphQL type as the
+    /// `other` one, by comparing their kinds, descriptions and the names of
+    /// their members.
+    pub(crate) fn has_same_shape(&self, other: &Self) -> bool {
+        fn names<'n, T: 'n>(items: &'n [T], f: impl Fn(&'n T) -> &'n str) -> Vec<&'n str> {
+            items.iter().map(f).collect()
+        }
+
+        if self.name() != other.name() || self.description() != other.description() {
+            return false;
+        }
+        match (self, other) {
+            (Self::Scalar(_), Self::Scalar(_)) => true,
+            (Self::Object(a), Self::Object(b)) => {
+                names(&a.fields, |f| &f.name) == names(&b.fields, |f| &f.name)
+                    && a.interface_names == b.interface_names
+            }
+            (Self::Interface(a), Self::Interface(b)) => {
+                names(&a.fields, |f| &f.name) == names(&b.fields, |f| &f.name)
+            }
+            (Self::Union(a), Self::Union(b)) => a.of_type_names == b.of_type_names,
+            (Self::Enum(a), Self::Enum(b)) => {
+                names(&a.values, |v| &v.name) == names(&b.values, |v| &v.name)
+            }
+            (Self::InputObject(a), Self::InputObject(b)) => {
+                names(&a.input_fields, |f| &f.name) == names(&b.input_fields, |f| &f.name)
+            }
+            _ => false,
+        }
+    }
+
     /// Construct a `TypeKind` for a given type
     ///
     /// # Panics
@@ -325,7 +427,7 @@ impl<'a, S> MetaType<'a, S> {
             | MetaType::Interface(InterfaceMeta { ref name, .. })
             | MetaType::Union(UnionMeta { ref name, .. })
             | MetaType::InputObject(InputObjectMeta { ref name, .. }) => {
-                Type::NonNullNamed(name.clone())
+                Type::NonNullNamed(Cow::Owned(name.to_string()))
             }
             MetaType::List(ListMeta {
                 ref of_type,
@@ -414,37 +516,40 @@ impl<'a, S> MetaType<'a, S> {
     }
 
     pub(crate) fn fields<'b>(&self, schema: &'b SchemaType<S>) -> Option<Vec<&'b Field<'b, S>>> {
-        schema
-            .lookup_type(&self.as_type())
-            .and_then(|tpe| match *tpe {
-                MetaType::Interface(ref i) => Some(i.fields.iter().collect()),
-                MetaType::Object(ref o) => Some(o.fields.iter().collect()),
-                MetaType::Union(ref u) => Some(
-                    u.of_type_names
-                        .iter()
-                        .filter_map(|n| schema.concrete_type_by_name(n))
-                        .filter_map(|t| t.fields(schema))
-                        .flatten()
-                        .collect(),
-                ),
-                _ => None,
-            })
+        // Named types are looked up directly, without allocating their `Type`.
+        let tpe = match self.name() {
+            Some(name) => schema.concrete_type_by_name(name),
+            None => schema.lookup_type(&self.as_type()),
+        };
+        tpe.and_then(|tpe| match *tpe {
+            MetaType::Interface(ref i) => Some(i.fields.iter().collect()),
+            MetaType::Object(ref o) => Some(o.fields.iter().collect()),
+            MetaType::Union(ref u) => Some(
+                u.of_type_names
+                    .iter()
+                    .filter_map(|n| schema.concrete_type_by_name(n))
+                    .filter_map(|t| t.fields(schema))
+                    .flatten()
+                    .collect(),
+            ),
+            _ => None,
+        })
     }
 }
 
 impl<'a, S> ScalarMeta<'a, S> {
     /// Builds a new [`ScalarMeta`] type with the specified `name`.
-    pub fn new<T>(name: Cow<'a, str>) -> Self
+    pub fn new<T>(name: impl Into<Name>) -> Self
     where
         T: FromInputValue<S> + ParseScalarValue<S>,
         T::Error: IntoFieldError<S>,
     {
         Self {
-            name,
+            name: name.into(),
             description: None,
             specified_by_url: None,
             try_parse_fn: try_parse_fn::<S, T>,
-            parse_fn: <T as ParseScalarValue<S>>::from_str,
+            parse_fn: <T as ParseScalarValue<S>>::from_spanned_str,
         }
     }
 
@@ -506,12 +611,12 @@ impl<'a> NullableMeta<'a> {
 
 impl<'a, S> ObjectMeta<'a, S> {
     /// Build a new [`ObjectMeta`] type with the specified `name` and `fields`.
-    pub fn new(name: Cow<'a, str>, fields: &[Field<'a, S>]) -> Self
+    pub fn new(name: impl Into<Name>, fields: &[Field<'a, S>]) -> Self
     where
         S: Clone,
     {
         Self {
-            name,
+            name: name.into(),
             description: None,
             fields: fields.to_vec(),
             interface_names: vec![],
@@ -534,7 +639,7 @@ impl<'a, S> ObjectMeta<'a, S> {
     pub fn interfaces(mut self, interfaces: &[Type<'a>]) -> Self {
         self.interface_names = interfaces
             .iter()
-            .map(|t| t.innermost_name().to_owned())
+            .map(|t| Name::intern(t.innermost_name()))
             .collect();
         self
     }
@@ -545,16 +650,16 @@ impl<'a, S> ObjectMeta<'a, S> {
     }
 }
 
-impl<'a, S> EnumMeta<'a, S> {
+impl<S> EnumMeta<S> {
     /// Build a new [`EnumMeta`] type with the specified `name` and possible
     /// `values`.
-    pub fn new<T>(name: Cow<'a, str>, values: &[EnumValue]) -> Self
+    pub fn new<T>(name: impl Into<Name>, values: &[EnumValue]) -> Self
     where
         T: FromInputValue<S>,
         T::Error: IntoFieldError<S>,
     {
         Self {
-            name,
+            name: name.into(),
             description: None,
             values: values.to_owned(),
             try_parse_fn: try_parse_fn::<S, T>,
@@ -571,7 +676,7 @@ impl<'a, S> EnumMeta<'a, S> {
     }
 
     /// Wraps this [`EnumMeta`] type into a generic [`MetaType`].
-    pub fn into_meta(self) -> MetaType<'a, S> {
+    pub fn into_meta<'a>(self) -> MetaType<'a, S> {
         MetaType::Enum(self)
     }
 }
@@ -579,12 +684,12 @@ impl<'a, S> EnumMeta<'a, S> {
 impl<'a, S> InterfaceMeta<'a, S> {
     /// Builds a new [`InterfaceMeta`] type with the specified `name` and
     /// `fields`.
-    pub fn new(name: Cow<'a, str>, fields: &[Field<'a, S>]) -> Self
+    pub fn new(name: impl Into<Name>, fields: &[Field<'a, S>]) -> Self
     where
         S: Clone,
     {
         Self {
-            name,
+            name: name.into(),
             description: None,
             fields: fields.to_vec(),
         }
@@ -605,16 +710,16 @@ impl<'a, S> InterfaceMeta<'a, S> {
     }
 }
 
-impl<'a> UnionMeta<'a> {
+impl UnionMeta {
     /// Build a new [`UnionMeta`] type with the specified `name` and possible
     /// [`Type`]s.
-    pub fn new(name: Cow<'a, str>, of_types: &[Type]) -> Self {
+    pub fn new(name: impl Into<Name>, of_types: &[Type]) -> Self {
         Self {
-            name,
+            name: name.into(),
             description: None,
             of_type_names: of_types
                 .iter()
-                .map(|t| t.innermost_name().to_owned())
+                .map(|t| Name::intern(t.innermost_name()))
                 .collect(),
         }
     }
@@ -629,7 +734,7 @@ impl<'a> UnionMeta<'a> {
     }
 
     /// Wraps this [`UnionMeta`] type into a generic [`MetaType`].
-    pub fn into_meta<S>(self) -> MetaType<'a, S> {
+    pub fn into_meta<'a, S>(self) -> MetaType<'a, S> {
         MetaType::Union(self)
     }
 }
@@ -637,14 +742,14 @@ impl<'a> UnionMeta<'a> {
 impl<'a, S> InputObjectMeta<'a, S> {
     /// Builds a new [`InputObjectMeta`] type with the specified `name` and
     /// `input_fields`.
-    pub fn new<T>(name: Cow<'a, str>, input_fields: &[Argument<'a, S>]) -> Self
+    pub fn new<T>(name: impl Into<Name>, input_fields: &[Argument<'a, S>]) -> Self
     where
         T: FromInputValue<S>,
         T::Error: IntoFieldError<S>,
         S: Clone,
     {
         Self {
-            name,
+            name: name.into(),
             description: None,
             input_fields: input_fields.to_vec(),
             try_parse_fn: try_parse_fn::<S, T>,
@@ -692,6 +797,17 @@ impl<'a, S> Field<'a, S> {
         self
     }
 
+    /// Adds all the provided `arguments` to this [`Field`].
+    ///
+    /// Arguments are unordered and can't contain duplicates by name.
+    #[must_use]
+    pub fn arguments(mut self, arguments: impl IntoIterator<Item = Argument<'a, S>>) -> Self {
+        self.arguments
+            .get_or_insert_with(Vec::new)
+            .extend(arguments);
+        self
+    }
+
     /// Sets this [`Field`] as deprecated with an optional `reason`.
     ///
     /// Overwrites any previously set deprecation reason.
@@ -700,16 +816,65 @@ impl<'a, S> Field<'a, S> {
         self.deprecation_status = DeprecationStatus::Deprecated(reason.map(ToOwned::to_owned));
         self
     }
+
+    /// Tags this [`Field`] as containing personally identifiable information
+    /// (PII).
+    ///
+    /// Doesn't affect the execution, but is reported to the [`MetricsSink`],
+    /// and allows stripping the field from logged responses via
+    /// [`PiiFields::strip()`].
+    ///
+    /// [`MetricsSink`]: crate::MetricsSink
+    /// [`PiiFields::strip()`]: crate::PiiFields::strip
+    #[must_use]
+    pub fn pii(mut self) -> Self {
+        self.pii = true;
+        self
+    }
+
+    /// Hides this [`Field`] from introspection and the schema language, while
+    /// still allowing to query it (like a legacy alias of another field, which
+    /// no new clients should discover).
+    #[must_use]
+    pub fn hidden(mut self) -> Self {
+        self.hidden = true;
+        self
+    }
+
+    /// Declares the fixed `cost` of resolving this [`Field`], added to the
+    /// cost of its selection set by the query cost analysis.
+    ///
+    /// Overwrites any previously declared [`Complexity`].
+    #[must_use]
+    pub fn complexity(mut self, cost: usize) -> Self {
+        self.complexity = Some(Complexity::Static(cost));
+        self
+    }
+
+    /// Declares the cost of resolving this [`Field`] as a function of its
+    /// [`Arguments`] and the cost of its selection set, returning the total
+    /// cost of the [`Field`].
+    ///
+    /// Overwrites any previously declared [`Complexity`].
+    #[must_use]
+    pub fn complexity_fn<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&Arguments<S>, usize) -> usize + Send + Sync + 'static,
+    {
+        self.complexity = Some(Complexity::Dynamic(Arc::new(f)));
+        self
+    }
 }
 
 impl<'a, S> Argument<'a, S> {
     /// Builds a new [`Argument`] of the given [`Type`] with the given `name`.
     pub fn new(name: &str, arg_type: Type<'a>) -> Self {
         Self {
-            name: name.to_owned(),
+            name: Name::intern(name),
             description: None,
             arg_type,
             default_value: None,
+            deprecation_status: DeprecationStatus::Current,
         }
     }
 
@@ -730,6 +895,15 @@ impl<'a, S> Argument<'a, S> {
         self.default_value = Some(val);
         self
     }
+
+    /// Sets this [`Argument`] as deprecated with an optional `reason`.
+    ///
+    /// Overwrites any previously set deprecation reason.
+    #[must_use]
+    pub fn deprecated(mut self, reason: Option<&str>) -> Self {
+        self.deprecation_status = DeprecationStatus::Deprecated(reason.map(ToOwned::to_owned));
+        self
+    }
 }
 
 impl EnumValue {
@@ -770,7 +944,7 @@ impl<'a, S: fmt::Debug> fmt::Debug for ScalarMeta<'a, S> {
     }
 }
 
-impl<'a, S: fmt::Debug> fmt::Debug for EnumMeta<'a, S> {
+impl<S: fmt::Debug> fmt::Debug for EnumMeta<S> {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         fmt.debug_struct("EnumMeta")
             .field("name", &self.name)
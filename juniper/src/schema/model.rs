@@ -1,4 +1,13 @@
-use std::{borrow::Cow, fmt};
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fmt,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Instant,
+};
 
 use fnv::FnvHashMap;
 #[cfg(feature = "graphql-parser")]
@@ -6,11 +15,32 @@ use graphql_parser::schema::Document;
 
 use crate::{
     ast::Type,
-    executor::{Context, Registry},
-    schema::meta::{Argument, InterfaceMeta, MetaType, ObjectMeta, PlaceholderMeta, UnionMeta},
-    types::{base::GraphQLType, name::Name},
-    value::{DefaultScalarValue, ScalarValue},
-    GraphQLEnum,
+    executor::{
+        execute_validated_introspection, get_operation, CachedDocument, Context, DocumentCache,
+        EntityResolver, EntityResolvers, ExecutionError, ExecutorOptions, FieldResult, Limits,
+        QueryLogEntry, QueryLogger, Registry, ResponseTransform, Variables,
+    },
+    introspection::{
+        IntrospectionFormat, INTROSPECTION_QUERY, INTROSPECTION_QUERY_WITHOUT_DESCRIPTIONS,
+    },
+    parser::{parse_document_source, Spanning, DEFAULT_RECURSION_LIMIT},
+    schema::meta::{
+        Argument, Field, InputObjectMeta, InterfaceMeta, MetaType, ObjectMeta, PlaceholderMeta,
+        UnionMeta,
+    },
+    types::{
+        async_await::GraphQLTypeAsync,
+        base::{Arguments, GraphQLType},
+        directives::GraphQLDirective,
+        name::Name,
+        utilities::invalid_literal_value_position,
+    },
+    validation::{
+        lint_document, validate_input_values, visit_all_rules, ConfigurableRule, CustomRule,
+        LintReport, RuleSeverity, ValidationPreset, ValidatorContext, Visitor,
+    },
+    value::{DefaultScalarValue, ScalarValue, Value},
+    GraphQLEnum, RequestError,
 };
 
 #[cfg(feature = "graphql-parser")]
@@ -44,17 +74,197 @@ pub struct RootNode<
     pub subscription_info: SubscriptionT::TypeInfo,
     #[doc(hidden)]
     pub schema: SchemaType<'a, S>,
+    pub(crate) query_logger: Option<Arc<dyn QueryLogger<QueryT::Context>>>,
+    pub(crate) entity_resolvers: EntityResolvers<QueryT::Context, S>,
+    pub(crate) introspection_allowlist: Option<IntrospectionAllowlist<QueryT::Context>>,
+    pub(crate) document_cache: Option<Arc<dyn DocumentCache<S>>>,
+}
+
+/// Predicate of the request contexts trusted to introspect a schema with
+/// disabled introspection.
+pub(crate) struct IntrospectionAllowlist<CtxT>(Arc<dyn Fn(&CtxT) -> bool + Send + Sync>);
+
+impl<CtxT> fmt::Debug for IntrospectionAllowlist<CtxT> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IntrospectionAllowlist")
+            .finish_non_exhaustive()
+    }
+}
+
+/// Error of building a schema out of Rust types.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SchemaError {
+    /// Different Rust types describing different GraphQL types are mapped to
+    /// the same GraphQL type name.
+    NameCollision {
+        /// Colliding GraphQL type name.
+        name: String,
+        /// Path of the Rust type registered under the `name` first.
+        first: String,
+        /// Path of the Rust type colliding with the `first` one.
+        second: String,
+    },
+
+    /// GraphQL interface is not implemented by any GraphQL object of the
+    /// schema.
+    OrphanInterface(String),
+
+    /// Default value of an argument or an input field is not valid for its
+    /// type.
+    InvalidDefaultValue {
+        /// Qualified name of the argument or the input field, like
+        /// `Query.users(first)`, `UserFilter.limit` or `@include(if)`.
+        name: String,
+        /// Literal of the default value.
+        value: String,
+    },
+
+    /// GraphQL object declares to implement a type, which is not a GraphQL
+    /// interface registered in the schema.
+    InvalidInterface {
+        /// Name of the implementing GraphQL object.
+        object: String,
+        /// Name of the declared GraphQL interface.
+        interface: String,
+    },
+
+    /// GraphQL object lacks a field of the GraphQL interface it implements.
+    MissingInterfaceField {
+        /// Name of the implementing GraphQL object.
+        object: String,
+        /// Name of the implemented GraphQL interface.
+        interface: String,
+        /// Name of the missing field.
+        field: String,
+    },
+
+    /// GraphQL union has no members.
+    EmptyUnion(String),
+
+    /// GraphQL union member is not a GraphQL object registered in the schema.
+    InvalidUnionMember {
+        /// Name of the GraphQL union.
+        union: String,
+        /// Name of the invalid member.
+        member: String,
+    },
+
+    /// GraphQL query root type is removed from the schema, either directly or
+    /// as being left without fields.
+    QueryTypeRemoved(String),
+
+    /// Part of a [`MergedObject`] is not a GraphQL object.
+    ///
+    /// [`MergedObject`]: crate::MergedObject
+    InvalidMergedPart {
+        /// Name of the merged GraphQL object.
+        object: String,
+        /// Name of the invalid part.
+        part: String,
+    },
+
+    /// Field is defined by more than one part of a [`MergedObject`].
+    ///
+    /// [`MergedObject`]: crate::MergedObject
+    DuplicateMergedField {
+        /// Name of the merged GraphQL object.
+        object: String,
+        /// Name of the duplicated field.
+        field: String,
+    },
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NameCollision {
+                name,
+                first,
+                second,
+            } => write!(
+                f,
+                "GraphQL type name `{}` is used by both `{}` and `{}` Rust types, \
+                 consider renaming one of them via `#[graphql(name = \"...\")]` \
+                 attribute argument",
+                name, first, second,
+            ),
+            Self::OrphanInterface(name) => write!(
+                f,
+                "GraphQL interface `{}` is not implemented by any GraphQL object",
+                name,
+            ),
+            Self::InvalidDefaultValue { name, value } => write!(
+                f,
+                "Default value `{}` of `{}` is not valid for its type",
+                value, name,
+            ),
+            Self::InvalidInterface { object, interface } => write!(
+                f,
+                "GraphQL object `{}` implements `{}`, which is not a GraphQL \
+                 interface registered in the schema",
+                object, interface,
+            ),
+            Self::MissingInterfaceField {
+                object,
+                interface,
+                field,
+            } => write!(
+                f,
+                "GraphQL object `{}` implements GraphQL interface `{}`, but has \
+                 no `{}` field",
+                object, interface, field,
+            ),
+            Self::EmptyUnion(name) => write!(f, "GraphQL union `{}` has no members", name),
+            Self::InvalidUnionMember { union, member } => write!(
+                f,
+                "GraphQL union `{}` member `{}` is not a GraphQL object registered \
+                 in the schema",
+                union, member,
+            ),
+            Self::QueryTypeRemoved(name) => write!(
+                f,
+                "GraphQL query root type `{}` cannot be removed from the schema",
+                name,
+            ),
+            Self::InvalidMergedPart { object, part } => write!(
+                f,
+                "GraphQL object `{}` merges `{}`, which is not a GraphQL object",
+                object, part,
+            ),
+            Self::DuplicateMergedField { object, field } => write!(
+                f,
+                "Field `{}` of merged GraphQL object `{}` is defined by more than \
+                 one of its parts",
+                field, object,
+            ),
+        }
+    }
 }
 
+impl std::error::Error for SchemaError {}
+
+/// Counter of the [`SchemaType`]s created, providing their unique IDs.
+static SCHEMA_IDS: AtomicUsize = AtomicUsize::new(0);
+
 /// Metadata for a schema
 #[derive(Debug)]
 pub struct SchemaType<'a, S> {
+    /// Unique ID of this schema, distinguishing the documents validated
+    /// against it.
+    pub(crate) id: usize,
     pub(crate) description: Option<Cow<'a, str>>,
     pub(crate) types: FnvHashMap<Name, MetaType<'a, S>>,
-    pub(crate) query_type_name: String,
-    pub(crate) mutation_type_name: Option<String>,
-    pub(crate) subscription_type_name: Option<String>,
-    directives: FnvHashMap<String, DirectiveType<'a, S>>,
+    pub(crate) query_type_name: Name,
+    pub(crate) mutation_type_name: Option<Name>,
+    pub(crate) subscription_type_name: Option<Name>,
+    pub(crate) directives: FnvHashMap<String, DirectiveType<'a, S>>,
+    pub(crate) parser_recursion_limit: usize,
+    pub(crate) executor_options: ExecutorOptions,
+    pub(crate) limits: Limits,
+    pub(crate) validation_preset: ValidationPreset,
+    pub(crate) rule_severities: HashMap<ConfigurableRule, RuleSeverity>,
+    pub(crate) validation_rules: Vec<CustomRule<S>>,
+    pub(crate) response_transform: Option<Arc<dyn ResponseTransform<S>>>,
 }
 
 impl<'a, S> Context for SchemaType<'a, S> {}
@@ -66,15 +276,27 @@ pub enum TypeType<'a, S: 'a> {
     List(Box<TypeType<'a, S>>, Option<usize>),
 }
 
+/// Metadata for a directive
 #[derive(Debug)]
 pub struct DirectiveType<'a, S> {
+    /// Name of the directive (without the `@`).
     pub name: String,
+    /// Description of the directive.
     pub description: Option<String>,
+    /// Locations the directive may be placed at.
     pub locations: Vec<DirectiveLocation>,
+    /// Arguments of the directive.
     pub arguments: Vec<Argument<'a, S>>,
+    /// Whether the directive may be placed multiple times at a single location.
     pub is_repeatable: bool,
+    pub(crate) resolve_fn: Option<DirectiveResolveFn<S>>,
 }
 
+/// Function applying a custom executable directive, called with its
+/// arguments, to a resolved field value.
+pub type DirectiveResolveFn<S> = fn(&Arguments<S>, Value<S>) -> FieldResult<Value<S>, S>;
+
+#[allow(missing_docs)]
 #[derive(Clone, PartialEq, Eq, Debug, GraphQLEnum)]
 #[graphql(name = "__DirectiveLocation", internal)]
 pub enum DirectiveLocation {
@@ -95,6 +317,10 @@ pub enum DirectiveLocation {
     InlineFragment,
     #[graphql(name = "ENUM_VALUE")]
     EnumValue,
+    #[graphql(name = "ARGUMENT_DEFINITION")]
+    ArgumentDefinition,
+    #[graphql(name = "INPUT_FIELD_DEFINITION")]
+    InputFieldDefinition,
 }
 
 impl<'a, QueryT, MutationT, SubscriptionT>
@@ -106,8 +332,76 @@ where
 {
     /// Constructs a new [`RootNode`] from `query`, `mutation` and `subscription` nodes,
     /// parametrizing it with a [`DefaultScalarValue`].
+    ///
+    /// # Panics
+    ///
+    /// If the schema cannot be built, see [`RootNode::new_with_info()`] for
+    /// details.
     pub fn new(query: QueryT, mutation: MutationT, subscription: SubscriptionT) -> Self {
         Self::new_with_info(query, mutation, subscription, (), (), ())
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Constructs a new [`RootNode`] from `query`, `mutation` and `subscription` nodes,
+    /// parametrizing it with a [`DefaultScalarValue`].
+    ///
+    /// Unlike [`RootNode::new()`], returns a [`SchemaError`] rather than
+    /// panicking on a misconfigured schema, and eagerly checks all the
+    /// registered types, so it's reported before serving any request.
+    ///
+    /// # Errors
+    ///
+    /// - With a [`SchemaError::NameCollision`] if different Rust types are
+    ///   mapped to the same GraphQL type name.
+    /// - With a [`SchemaError::OrphanInterface`] if a GraphQL interface isn't
+    ///   implemented by any GraphQL object.
+    /// - With a [`SchemaError::InvalidInterface`] or a
+    ///   [`SchemaError::MissingInterfaceField`] if a GraphQL object doesn't
+    ///   match a GraphQL interface it declares to implement.
+    /// - With a [`SchemaError::EmptyUnion`] or a
+    ///   [`SchemaError::InvalidUnionMember`] if a GraphQL union has no members
+    ///   or a member which isn't a GraphQL object.
+    /// - With a [`SchemaError::InvalidDefaultValue`] if a default value of an
+    ///   argument or an input field isn't valid for its type.
+    /// - With a [`SchemaError::DuplicateMergedField`] or a
+    ///   [`SchemaError::InvalidMergedPart`] if the parts of a [`MergedObject`]
+    ///   define the same field or aren't GraphQL objects.
+    ///
+    /// # Panics
+    ///
+    /// Only on the invalid manual implementations of the [`GraphQLType`]
+    /// trait, like a GraphQL object without a [`GraphQLType::name()`], never
+    /// produced by the `juniper` macros.
+    ///
+    /// ```
+    /// # use juniper::{graphql_object, EmptyMutation, EmptySubscription, RootNode};
+    /// #
+    /// struct Query;
+    ///
+    /// #[graphql_object]
+    /// impl Query {
+    ///     fn users(#[graphql(default = 10)] first: i32) -> Vec<String> {
+    ///         vec!["John".into(); first as usize]
+    ///     }
+    /// }
+    ///
+    /// let schema = RootNode::try_new(
+    ///     Query,
+    ///     EmptyMutation::<()>::new(),
+    ///     EmptySubscription::<()>::new(),
+    /// );
+    /// assert!(schema.is_ok());
+    /// ```
+    ///
+    /// [`MergedObject`]: crate::MergedObject
+    pub fn try_new(
+        query: QueryT,
+        mutation: MutationT,
+        subscription: SubscriptionT,
+    ) -> Result<Self, SchemaError> {
+        let root = Self::new_with_info(query, mutation, subscription, (), (), ())?;
+        root.schema.validate()?;
+        Ok(root)
     }
 }
 
@@ -120,12 +414,18 @@ where
 {
     /// Constructs a new [`RootNode`] from `query`, `mutation` and `subscription` nodes,
     /// parametrizing it with the provided [`ScalarValue`].
+    ///
+    /// # Panics
+    ///
+    /// If the schema cannot be built, see [`RootNode::new_with_info()`] for
+    /// details.
     pub fn new_with_scalar_value(
         query: QueryT,
         mutation: MutationT,
         subscription: SubscriptionT,
     ) -> Self {
         RootNode::new_with_info(query, mutation, subscription, (), (), ())
+            .unwrap_or_else(|e| panic!("{}", e))
     }
 }
 
@@ -139,6 +439,15 @@ where
     /// Construct a new root node from query and mutation nodes,
     /// while also providing type info objects for the query and
     /// mutation types.
+    ///
+    /// # Errors
+    ///
+    /// - With a [`SchemaError::NameCollision`] if different Rust types are
+    ///   mapped to the same GraphQL type name, naming both of them.
+    /// - With a [`SchemaError::DuplicateMergedField`] or a
+    ///   [`SchemaError::InvalidMergedPart`] if a [`MergedObject`] is invalid.
+    ///
+    /// [`MergedObject`]: crate::MergedObject
     pub fn new_with_info(
         query_obj: QueryT,
         mutation_obj: MutationT,
@@ -146,20 +455,523 @@ where
         query_info: QueryT::TypeInfo,
         mutation_info: MutationT::TypeInfo,
         subscription_info: SubscriptionT::TypeInfo,
-    ) -> Self {
-        RootNode {
+    ) -> Result<Self, SchemaError> {
+        Ok(RootNode {
             query_type: query_obj,
             mutation_type: mutation_obj,
             subscription_type: subscription_obj,
-            schema: SchemaType::new::<QueryT, MutationT, SubscriptionT>(
+            schema: SchemaType::try_new::<QueryT, MutationT, SubscriptionT>(
                 &query_info,
                 &mutation_info,
                 &subscription_info,
-            ),
+            )?,
             query_info,
             mutation_info,
             subscription_info,
+            query_logger: None,
+            entity_resolvers: EntityResolvers::default(),
+            introspection_allowlist: None,
+            document_cache: None,
+        })
+    }
+
+    /// Registers the custom executable [`GraphQLDirective`] `D` in the schema
+    /// of this [`RootNode`].
+    ///
+    /// See [`SchemaType::register_directive()`] for details.
+    pub fn with_directive<D>(mut self) -> Self
+    where
+        D: GraphQLDirective<S>,
+    {
+        self.schema.register_directive::<D>();
+        self
+    }
+
+    /// Enables the `@defer` directive in the schema of this [`RootNode`].
+    ///
+    /// See [`SchemaType::enable_defer()`] for details.
+    pub fn enable_defer(mut self) -> Self {
+        self.schema.enable_defer();
+        self
+    }
+
+    /// Enables the `@stream` directive in the schema of this [`RootNode`].
+    ///
+    /// See [`SchemaType::enable_stream()`] for details.
+    pub fn enable_stream(mut self) -> Self {
+        self.schema.enable_stream();
+        self
+    }
+
+    /// Sets the maximum nesting depth allowed when parsing operations against
+    /// this [`RootNode`].
+    ///
+    /// See [`SchemaType::set_parser_recursion_limit()`] for details.
+    pub fn with_parser_recursion_limit(mut self, limit: usize) -> Self {
+        self.schema.set_parser_recursion_limit(limit);
+        self
+    }
+
+    /// Sets the [`ExecutorOptions`] to execute operations against this
+    /// [`RootNode`] with.
+    pub fn with_executor_options(mut self, options: ExecutorOptions) -> Self {
+        self.schema.set_executor_options(options);
+        self
+    }
+
+    /// Sets the default [`Limits`] of operations executed against this
+    /// [`RootNode`].
+    ///
+    /// See [`SchemaType::set_limits()`] for details.
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.schema.set_limits(limits);
+        self
+    }
+
+    /// Sets the maximum number of aliased fields in an operation executed
+    /// against this [`RootNode`], keeping its other default [`Limits`] (like
+    /// the ones bundled with its [`ValidationPreset`]) intact.
+    ///
+    /// See [`Limits::max_aliases()`] for details, and
+    /// [`max_aliases::factory_with_limit()`] for registering the rule checking
+    /// it with a limit not overridable per request.
+    ///
+    /// [`max_aliases::factory_with_limit()`]: crate::validation::max_aliases::factory_with_limit
+    pub fn with_max_aliases(mut self, aliases: usize) -> Self {
+        let limits = self.schema.limits().max_aliases(aliases);
+        self.schema.set_limits(limits);
+        self
+    }
+
+    /// Sets the maximum nesting depth of fields in an operation executed
+    /// against this [`RootNode`], keeping its other default [`Limits`] (like
+    /// the ones bundled with its [`ValidationPreset`]) intact.
+    ///
+    /// See [`Limits::max_depth()`] for details.
+    pub fn with_max_depth(mut self, depth: usize) -> Self {
+        let limits = self.schema.limits().max_depth(depth);
+        self.schema.set_limits(limits);
+        self
+    }
+
+    /// Sets the [`ValidationPreset`] to validate operations executed against
+    /// this [`RootNode`] with, along with its default [`Limits`].
+    ///
+    /// See [`SchemaType::set_validation_preset()`] for details.
+    pub fn with_validation_preset(mut self, preset: ValidationPreset) -> Self {
+        self.schema.set_validation_preset(preset);
+        self
+    }
+
+    /// Sets the [`RuleSeverity`] of the given built-in `rule` for operations
+    /// executed against this [`RootNode`].
+    ///
+    /// See [`SchemaType::set_rule_severity()`] for details.
+    pub fn with_rule_severity(mut self, rule: ConfigurableRule, severity: RuleSeverity) -> Self {
+        self.schema.set_rule_severity(rule, severity);
+        self
+    }
+
+    /// Registers a custom validation rule for operations executed against this
+    /// [`RootNode`], the [`Visitor`] of which is created by the given
+    /// `factory` for every validated document.
+    ///
+    /// See [`SchemaType::add_validation_rule()`] for details.
+    pub fn with_validation_rule<F, V>(mut self, factory: F) -> Self
+    where
+        F: Fn() -> V + Send + Sync + 'static,
+        V: for<'v> Visitor<'v, S> + 'static,
+    {
+        self.schema.add_validation_rule(factory);
+        self
+    }
+
+    /// Sets the [`ResponseTransform`] to apply to the response of every query
+    /// and mutation executed against this [`RootNode`].
+    ///
+    /// See [`SchemaType::set_response_transform()`] for details.
+    pub fn with_response_transform(
+        mut self,
+        transform: impl ResponseTransform<S> + 'static,
+    ) -> Self {
+        self.schema.set_response_transform(transform);
+        self
+    }
+
+    /// Sets the [`QueryLogger`] to log every query and mutation executed
+    /// against this [`RootNode`] into.
+    pub fn with_query_logger(
+        mut self,
+        logger: impl QueryLogger<QueryT::Context> + 'static,
+    ) -> Self {
+        self.query_logger = Some(Arc::new(logger));
+        self
+    }
+
+    /// Sets the [`DocumentCache`] to look up the parsed and validated documents
+    /// of the queries and mutations executed against this [`RootNode`] in,
+    /// skipping the parsing and validation of the cached ones.
+    ///
+    /// The documents are validated against this [`RootNode`] only, so the ones
+    /// cached by other schemas sharing the same `cache` are treated as misses
+    /// (and replaced). None by default.
+    pub fn with_document_cache(mut self, cache: impl DocumentCache<S> + 'static) -> Self {
+        self.document_cache = Some(Arc::new(cache));
+        self
+    }
+
+    /// Returns the document with the given `source` text, parsed and
+    /// validated against this [`RootNode`], from its [`DocumentCache`], if
+    /// any.
+    ///
+    /// Looks the document up by the given SHA-256 `persisted_hash` of its
+    /// `source` text instead, if provided. The `source` may be empty then, so
+    /// only the already cached document is returned. Otherwise, the `source`
+    /// should match the cached one, so the requests providing a hash not
+    /// matching their text are executed as if there were no hash.
+    ///
+    /// Caches the document on a miss, if it's valid. Returns [`None`] if there
+    /// is no [`DocumentCache`] or the document is not valid.
+    pub(crate) fn cached_document(
+        &self,
+        source: &str,
+        persisted_hash: Option<&str>,
+    ) -> Option<Arc<CachedDocument<S>>> {
+        let cache = self.document_cache.as_ref()?;
+
+        let hash = persisted_hash.map(str::to_ascii_lowercase);
+        let mut hit = hash
+            .as_deref()
+            .and_then(|hash| cache.get(hash))
+            .filter(|doc| source.is_empty() || doc.source() == source)
+            .filter(|doc| doc.is_validated_against(&self.schema));
+        if hit.is_none() && !source.is_empty() {
+            hit = cache
+                .get(source)
+                .filter(|doc| doc.is_validated_against(&self.schema));
+        }
+        if let Some(sink) = self.schema.executor_options().sink() {
+            sink.record_document_cache(hit.is_some());
+        }
+        if hit.is_some() || source.is_empty() {
+            return hit;
+        }
+
+        let document = Arc::new(CachedDocument::compile(source, &self.schema)?);
+        #[cfg(feature = "persisted-queries")]
+        if let Some(hash) = hash {
+            if crate::http::apq::sha256_hex(source) == hash {
+                cache.insert(hash.into(), Arc::clone(&document));
+                return Some(document);
+            }
+        }
+        cache.insert(document.shared_source(), Arc::clone(&document));
+        Some(document)
+    }
+
+    /// Disables the introspection of this [`RootNode`], rejecting every
+    /// operation selecting the `__schema` or `__type` fields.
+    ///
+    /// The `__typename` field is still allowed. Operations executed via
+    /// [`execute_fragment()`] and [`RootNode::introspect()`] are not affected.
+    ///
+    /// [`execute_fragment()`]: crate::execute_fragment
+    pub fn disable_introspection(self) -> Self {
+        self.with_introspection_allowlist(|_| false)
+    }
+
+    /// Disables the introspection of this [`RootNode`], the same way
+    /// [`RootNode::disable_introspection()`] does, except for the requests
+    /// the `trusted` predicate returns `true` for, given their context.
+    ///
+    /// ```
+    /// # use juniper::{graphql_object, graphql_vars, EmptyMutation, EmptySubscription, RootNode};
+    /// #
+    /// struct Context {
+    ///     is_admin: bool,
+    /// }
+    ///
+    /// impl juniper::Context for Context {}
+    ///
+    /// struct Query;
+    ///
+    /// #[graphql_object(context = Context)]
+    /// impl Query {
+    ///     fn hello() -> &'static str {
+    ///         "world"
+    ///     }
+    /// }
+    ///
+    /// let schema = RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+    ///     .with_introspection_allowlist(|ctx: &Context| ctx.is_admin);
+    ///
+    /// let query = "{ __schema { queryType { name } } }";
+    /// let admin = Context { is_admin: true };
+    /// let user = Context { is_admin: false };
+    /// assert!(juniper::execute_sync(query, None, &schema, &graphql_vars! {}, &admin).is_ok());
+    /// assert!(juniper::execute_sync(query, None, &schema, &graphql_vars! {}, &user).is_err());
+    /// ```
+    pub fn with_introspection_allowlist(
+        mut self,
+        trusted: impl Fn(&QueryT::Context) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.introspection_allowlist = Some(IntrospectionAllowlist(Arc::new(trusted)));
+        self
+    }
+
+    /// Indicates whether a request with the given `context` is allowed to
+    /// introspect this [`RootNode`].
+    pub fn allows_introspection(&self, context: &QueryT::Context) -> bool {
+        match &self.introspection_allowlist {
+            Some(IntrospectionAllowlist(trusted)) => trusted(context),
+            None => true,
+        }
+    }
+
+    /// Registers the [`EntityResolver`] of the GraphQL object type `R::Entity`
+    /// in this [`RootNode`], allowing to execute selections on its entities
+    /// with [`execute_entity()`].
+    ///
+    /// Replaces the previously registered resolver of the same type, if any.
+    ///
+    /// [`execute_entity()`]: crate::execute_entity
+    pub fn with_entity_resolver<R>(mut self, resolver: R) -> Self
+    where
+        R: EntityResolver<S> + 'static,
+        R::Entity: GraphQLTypeAsync<S, Context = QueryT::Context>,
+        QueryT::Context: Sync,
+        S: Send + Sync,
+    {
+        self.entity_resolvers.register(resolver);
+        self
+    }
+
+    /// Logs the request executing the given `query` into the given `result`
+    /// with the [`QueryLogger`] of this [`RootNode`], if any.
+    pub(crate) fn log_query(
+        &self,
+        query: &str,
+        operation_name: Option<&str>,
+        variables: &Variables<S>,
+        context: &QueryT::Context,
+        started: Instant,
+        result: &Result<(Value<S>, Vec<ExecutionError<S>>), RequestError<'_>>,
+    ) {
+        if let Some(logger) = &self.query_logger {
+            logger.log(&QueryLogEntry::new(
+                query,
+                operation_name,
+                variables,
+                logger.client_identity(context),
+                started.elapsed(),
+                result,
+            ));
+        }
+    }
+
+    /// Applies the given composing function `f` to this [`RootNode`] only if
+    /// the `flag` is set, allowing to compose a schema depending on runtime
+    /// feature flags.
+    ///
+    /// ```
+    /// # use juniper::{graphql_object, EmptyMutation, EmptySubscription, RootNode, SchemaError};
+    /// #
+    /// struct Query;
+    ///
+    /// #[graphql_object]
+    /// impl Query {
+    ///     fn stable() -> bool {
+    ///         true
+    ///     }
+    ///
+    ///     fn canary() -> bool {
+    ///         true
+    ///     }
+    /// }
+    ///
+    /// let canary_enabled = false;
+    ///
+    /// let schema = RootNode::try_new(
+    ///     Query,
+    ///     EmptyMutation::<()>::new(),
+    ///     EmptySubscription::<()>::new(),
+    /// )?
+    /// .when(!canary_enabled, |root| root.without_field("Query", "canary"))?;
+    ///
+    /// assert!(schema.schema.concrete_query_type().field_by_name("canary").is_none());
+    /// # Ok::<_, SchemaError>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// With the [`SchemaError`] returned by the composing function `f`.
+    pub fn when(
+        self,
+        flag: bool,
+        f: impl FnOnce(Self) -> Result<Self, SchemaError>,
+    ) -> Result<Self, SchemaError> {
+        if flag {
+            f(self)
+        } else {
+            Ok(self)
+        }
+    }
+
+    /// Removes the field `field_name` of the object or interface type
+    /// `type_name` from the schema of this [`RootNode`], along with everything
+    /// left invalid by that.
+    ///
+    /// See [`SchemaType::remove_field()`] for details.
+    ///
+    /// # Errors
+    ///
+    /// If the pruned schema is not valid, see [`SchemaType::remove_field()`]
+    /// for details.
+    pub fn without_field(mut self, type_name: &str, field_name: &str) -> Result<Self, SchemaError> {
+        self.schema.remove_field(type_name, field_name)?;
+        Ok(self)
+    }
+
+    /// Removes the type `name` from the schema of this [`RootNode`], along
+    /// with everything referring to it or left invalid by that.
+    ///
+    /// See [`SchemaType::remove_type()`] for details.
+    ///
+    /// # Errors
+    ///
+    /// If the pruned schema is not valid, see [`SchemaType::remove_type()`]
+    /// for details.
+    pub fn without_type(mut self, name: &str) -> Result<Self, SchemaError> {
+        self.schema.remove_type(name)?;
+        Ok(self)
+    }
+
+    /// Checks whether the given operation would be executed against this
+    /// [`RootNode`] without running any resolvers (a dry run), returning the
+    /// [`RequestError`] its execution would fail with, if any.
+    ///
+    /// Performs the same checks as [`execute()`] does before executing the
+    /// operation: parsing, validation, selecting the operation by its
+    /// `operation_name`, checking the default [`Limits`] of this [`RootNode`],
+    /// and coercing the `variables`. Neither a context nor the root values are
+    /// touched, so this is useful for pre-flight endpoints and checking
+    /// client operations in CI.
+    ///
+    /// Operations of any type are checked, including subscriptions.
+    ///
+    /// ```
+    /// # use juniper::{
+    /// #     graphql_object, graphql_vars, EmptyMutation, EmptySubscription, RequestError,
+    /// #     RootNode,
+    /// # };
+    /// #
+    /// struct Query;
+    ///
+    /// #[graphql_object]
+    /// impl Query {
+    ///     fn hello(name: String) -> String {
+    ///         format!("Hello, {}!", name)
+    ///     }
+    /// }
+    ///
+    /// let schema = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::<()>::new());
+    /// let doc = "query Greet($name: String!) { hello(name: $name) }";
+    ///
+    /// assert_eq!(schema.check(doc, &graphql_vars! {"name": "World"}, None), Ok(()));
+    /// assert!(matches!(
+    ///     schema.check(doc, &graphql_vars! {}, None),
+    ///     Err(RequestError::VariableCoercion(_)),
+    /// ));
+    /// assert!(matches!(
+    ///     schema.check("{ bye }", &graphql_vars! {}, None),
+    ///     Err(RequestError::Validation(_)),
+    /// ));
+    /// ```
+    ///
+    /// [`execute()`]: crate::execute
+    pub fn check<'d>(
+        &self,
+        document_source: &'d str,
+        variables: &Variables<S>,
+        operation_name: Option<&str>,
+    ) -> Result<(), RequestError<'d>> {
+        let document = parse_document_source(document_source, &self.schema)?;
+
+        let mut ctx = ValidatorContext::new(&self.schema, &document);
+        visit_all_rules(&mut ctx, &document);
+        let errors = ctx.into_errors();
+        if !errors.is_empty() {
+            return Err(RequestError::Validation(errors));
+        }
+
+        let operation = get_operation(&document, operation_name)?;
+
+        let errors =
+            self.schema
+                .limits()
+                .check(&self.schema, &document, operation, variables, None);
+        if !errors.is_empty() {
+            return Err(RequestError::Validation(errors));
         }
+
+        let errors = validate_input_values(variables, operation, &self.schema);
+        if !errors.is_empty() {
+            return Err(RequestError::VariableCoercion(errors));
+        }
+
+        Ok(())
+    }
+
+    /// Lints the given client `documents` (pairs of a name and a source, like
+    /// the files of a directory with stored operations) against this schema,
+    /// returning a [`LintReport`] for each of them, in the same order.
+    ///
+    /// Along with any validation failures, reports usages of deprecated
+    /// fields, arguments and enum values, and distinguishes unknown fields and
+    /// undefined variables by their [`LintIssueKind`]. Unlike [`check()`], no
+    /// operation is selected and no variables are coerced.
+    ///
+    /// ```
+    /// # use juniper::{graphql_object, EmptyMutation, EmptySubscription, LintIssueKind, RootNode};
+    /// #
+    /// struct Query;
+    ///
+    /// #[graphql_object]
+    /// impl Query {
+    ///     #[graphql(deprecated = "Use `hello` instead.")]
+    ///     fn hi() -> &'static str {
+    ///         "Hi!"
+    ///     }
+    ///
+    ///     fn hello() -> &'static str {
+    ///         "Hello!"
+    ///     }
+    /// }
+    ///
+    /// let schema = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::<()>::new());
+    ///
+    /// let reports = schema.lint([
+    ///     ("hello.graphql", "{ hello }"),
+    ///     ("hi.graphql", "{ hi }"),
+    ///     ("bye.graphql", "{ bye }"),
+    /// ]);
+    ///
+    /// assert!(reports[0].issues().is_empty());
+    /// assert!(reports[1].is_valid());
+    /// assert_eq!(reports[1].issues()[0].kind(), LintIssueKind::Deprecated);
+    /// assert!(!reports[2].is_valid());
+    /// assert_eq!(reports[2].issues()[0].kind(), LintIssueKind::UnknownField);
+    /// ```
+    ///
+    /// [`check()`]: RootNode::check
+    pub fn lint<'d, I>(&self, documents: I) -> Vec<LintReport<'d>>
+    where
+        I: IntoIterator<Item = (&'d str, &'d str)>,
+    {
+        documents
+            .into_iter()
+            .map(|(name, source)| lint_document(&self.schema, name, source))
+            .collect()
     }
 
     #[cfg(feature = "schema-language")]
@@ -177,10 +989,54 @@ where
     pub fn as_parser_document(&'a self) -> Document<'a, &'a str> {
         GraphQLParserTranslator::translate_schema(&self.schema)
     }
+
+    /// Returns the result of the canonical introspection query of this schema
+    /// in the given `format` as a [`serde_json::Value`].
+    ///
+    /// The introspection is resolved right from the schema metadata, so
+    /// neither a context nor the root values are touched.
+    #[cfg(feature = "serde_json")]
+    pub fn introspect(&self, format: IntrospectionFormat) -> serde_json::Value {
+        serde_json::to_value(self.schema.introspect(format))
+            .expect("introspection result is always representable as JSON")
+    }
+
+    /// Writes the result of the canonical introspection query of this schema
+    /// in the given `format` into the provided `writer` as a pretty-printed
+    /// JSON (a `schema.json` file, as expected by client codegen tools).
+    ///
+    /// Types and directives are sorted by their names, so the output is
+    /// stable across builds of the same schema.
+    ///
+    /// # Errors
+    ///
+    /// If writing into the `writer` fails.
+    #[cfg(feature = "serde_json")]
+    pub fn write_introspection_json<W: std::io::Write>(
+        &self,
+        mut writer: W,
+        format: IntrospectionFormat,
+    ) -> serde_json::Result<()> {
+        let mut json = self.introspect(format);
+        if let Some(schema) = json.get_mut("__schema") {
+            for key in ["types", "directives"] {
+                if let Some(serde_json::Value::Array(items)) = schema.get_mut(key) {
+                    items.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+                }
+            }
+        }
+        serde_json::to_writer_pretty(&mut writer, &json)?;
+        writer.write_all(b"\n").map_err(serde_json::Error::io)
+    }
 }
 
 impl<'a, S> SchemaType<'a, S> {
     /// Create a new schema.
+    ///
+    /// # Panics
+    ///
+    /// If the schema cannot be built, see [`SchemaType::try_new()`] for
+    /// details.
     pub fn new<QueryT, MutationT, SubscriptionT>(
         query_info: &QueryT::TypeInfo,
         mutation_info: &MutationT::TypeInfo,
@@ -192,21 +1048,87 @@ impl<'a, S> SchemaType<'a, S> {
         MutationT: GraphQLType<S>,
         SubscriptionT: GraphQLType<S>,
     {
-        let mut directives = FnvHashMap::default();
+        Self::try_new::<QueryT, MutationT, SubscriptionT>(
+            query_info,
+            mutation_info,
+            subscription_info,
+        )
+        .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Creates a new schema, erroring if it cannot be built.
+    ///
+    /// # Errors
+    ///
+    /// - With a [`SchemaError::NameCollision`] if different Rust types are
+    ///   mapped to the same GraphQL type name, naming both of them.
+    /// - With a [`SchemaError::DuplicateMergedField`] or a
+    ///   [`SchemaError::InvalidMergedPart`] if a [`MergedObject`] is invalid.
+    ///
+    /// [`MergedObject`]: crate::MergedObject
+    pub fn try_new<QueryT, MutationT, SubscriptionT>(
+        query_info: &QueryT::TypeInfo,
+        mutation_info: &MutationT::TypeInfo,
+        subscription_info: &SubscriptionT::TypeInfo,
+    ) -> Result<Self, SchemaError>
+    where
+        S: ScalarValue + 'a,
+        QueryT: GraphQLType<S>,
+        MutationT: GraphQLType<S>,
+        SubscriptionT: GraphQLType<S>,
+    {
         let mut registry = Registry::new(FnvHashMap::default());
 
-        let query_type_name = registry
+        let query_type_name: Name = registry
             .get_type::<QueryT>(query_info)
             .innermost_name()
-            .to_owned();
-        let mutation_type_name = registry
+            .into();
+        let mutation_type_name: Name = registry
             .get_type::<MutationT>(mutation_info)
             .innermost_name()
-            .to_owned();
-        let subscription_type_name = registry
+            .into();
+        let subscription_type_name: Name = registry
             .get_type::<SubscriptionT>(subscription_info)
             .innermost_name()
-            .to_owned();
+            .into();
+
+        if let Some(e) = registry.take_errors().into_iter().next() {
+            return Err(e);
+        }
+
+        Ok(Self::from_registry(
+            registry,
+            query_type_name,
+            if mutation_type_name != "_EmptyMutation" {
+                Some(mutation_type_name)
+            } else {
+                None
+            },
+            if subscription_type_name != "_EmptySubscription" {
+                Some(subscription_type_name)
+            } else {
+                None
+            },
+        ))
+    }
+
+    /// Creates a new schema out of the types collected in the `registry`,
+    /// registering the introspection types and the built-in directives.
+    ///
+    /// # Panics
+    ///
+    /// If the query root type is not an object registered in the `registry`,
+    /// or if any of the registered types is still a placeholder.
+    pub(crate) fn from_registry(
+        mut registry: Registry<'a, S>,
+        query_type_name: Name,
+        mutation_type_name: Option<Name>,
+        subscription_type_name: Option<Name>,
+    ) -> Self
+    where
+        S: ScalarValue + 'a,
+    {
+        let mut directives = FnvHashMap::default();
 
         registry.get_type::<SchemaType<S>>(&());
 
@@ -231,7 +1153,7 @@ impl<'a, S> SchemaType<'a, S> {
                 .argument(registry.arg::<String>("name", &())),
         ];
 
-        if let Some(root_type) = registry.types.get_mut(&query_type_name) {
+        if let Some(root_type) = registry.types.get_mut(&*query_type_name) {
             if let MetaType::Object(ObjectMeta { ref mut fields, .. }) = *root_type {
                 fields.append(&mut meta_fields);
             } else {
@@ -247,21 +1169,173 @@ impl<'a, S> SchemaType<'a, S> {
             }
         }
         SchemaType {
+            id: SCHEMA_IDS.fetch_add(1, Ordering::Relaxed),
             description: None,
             types: registry.types,
             query_type_name,
-            mutation_type_name: if &mutation_type_name != "_EmptyMutation" {
-                Some(mutation_type_name)
-            } else {
-                None
-            },
-            subscription_type_name: if &subscription_type_name != "_EmptySubscription" {
-                Some(subscription_type_name)
-            } else {
-                None
-            },
+            mutation_type_name,
+            subscription_type_name,
             directives,
+            parser_recursion_limit: DEFAULT_RECURSION_LIMIT,
+            executor_options: ExecutorOptions::default(),
+            limits: Limits::default(),
+            validation_preset: ValidationPreset::default(),
+            rule_severities: HashMap::new(),
+            validation_rules: Vec::new(),
+            response_transform: None,
+        }
+    }
+
+    /// Checks the registered types of this schema, which are not checked while
+    /// registering them.
+    ///
+    /// # Errors
+    ///
+    /// With the first found [`SchemaError::OrphanInterface`],
+    /// [`SchemaError::InvalidInterface`], [`SchemaError::MissingInterfaceField`],
+    /// [`SchemaError::EmptyUnion`], [`SchemaError::InvalidUnionMember`] or
+    /// [`SchemaError::InvalidDefaultValue`], in the order of type names.
+    pub(crate) fn validate(&self) -> Result<(), SchemaError>
+    where
+        S: ScalarValue,
+    {
+        let check_default = |name: String, arg: &Argument<S>| match &arg.default_value {
+            Some(value) => {
+                let ty = self.make_type(&arg.arg_type);
+                match invalid_literal_value_position(self, &ty, &Spanning::unlocated(value.clone()))
+                {
+                    Some(_) => Err(SchemaError::InvalidDefaultValue {
+                        name,
+                        value: value.to_string(),
+                    }),
+                    None => Ok(()),
+                }
+            }
+            None => Ok(()),
+        };
+        let check_fields = |type_name: &str, fields: &[Field<S>]| {
+            fields.iter().try_for_each(|f| {
+                f.arguments.iter().flatten().try_for_each(|a| {
+                    check_default(format!("{}.{}({})", type_name, f.name, a.name), a)
+                })
+            })
+        };
+
+        let mut types = self.types.values().collect::<Vec<_>>();
+        types.sort_by(|a, b| a.name().cmp(&b.name()));
+        for meta_type in types {
+            match meta_type {
+                MetaType::Object(ObjectMeta {
+                    name,
+                    fields,
+                    interface_names,
+                    ..
+                }) => {
+                    for iface in interface_names {
+                        let iface_fields = match self.types.get(iface.as_str()) {
+                            Some(MetaType::Interface(InterfaceMeta { fields, .. })) => fields,
+                            _ => {
+                                return Err(SchemaError::InvalidInterface {
+                                    object: name.to_string(),
+                                    interface: iface.to_string(),
+                                })
+                            }
+                        };
+                        if let Some(f) = iface_fields
+                            .iter()
+                            .find(|f| fields.iter().all(|of| of.name != f.name))
+                        {
+                            return Err(SchemaError::MissingInterfaceField {
+                                object: name.to_string(),
+                                interface: iface.to_string(),
+                                field: f.name.to_string(),
+                            });
+                        }
+                    }
+                    check_fields(name, fields)?;
+                }
+                MetaType::Interface(InterfaceMeta { name, fields, .. }) => {
+                    if self.possible_types(meta_type).is_empty() {
+                        return Err(SchemaError::OrphanInterface(name.to_string()));
+                    }
+                    check_fields(name, fields)?;
+                }
+                MetaType::Union(UnionMeta {
+                    name,
+                    of_type_names,
+                    ..
+                }) => {
+                    if of_type_names.is_empty() {
+                        return Err(SchemaError::EmptyUnion(name.to_string()));
+                    }
+                    if let Some(member) = of_type_names
+                        .iter()
+                        .find(|n| !matches!(self.types.get(n.as_str()), Some(MetaType::Object(_))))
+                    {
+                        return Err(SchemaError::InvalidUnionMember {
+                            union: name.to_string(),
+                            member: member.to_string(),
+                        });
+                    }
+                }
+                MetaType::InputObject(InputObjectMeta {
+                    name, input_fields, ..
+                }) => input_fields
+                    .iter()
+                    .try_for_each(|f| check_default(format!("{}.{}", name, f.name), f))?,
+                _ => {}
+            }
         }
+
+        let mut directives = self.directives.values().collect::<Vec<_>>();
+        directives.sort_by(|a, b| a.name.cmp(&b.name));
+        directives.into_iter().try_for_each(|d| {
+            d.arguments
+                .iter()
+                .try_for_each(|a| check_default(format!("@{}({})", d.name, a.name), a))
+        })
+    }
+
+    /// Returns the result of the canonical introspection query of this schema
+    /// in the given `format`.
+    ///
+    /// The introspection is resolved right from the schema metadata, so
+    /// neither a context nor the root values are required.
+    pub fn introspect(&self, format: IntrospectionFormat) -> Value<S>
+    where
+        S: ScalarValue,
+    {
+        let query = match format {
+            IntrospectionFormat::All => INTROSPECTION_QUERY,
+            IntrospectionFormat::WithoutDescriptions => INTROSPECTION_QUERY_WITHOUT_DESCRIPTIONS,
+        };
+        let document =
+            parse_document_source(query, self).expect("introspection query is always valid");
+        let operation = get_operation(&document, None).expect("introspection query has operation");
+
+        execute_validated_introspection(&document, operation, self, &HashMap::new()).0
+    }
+
+    /// Builds a new schema out of the given `json` result of the canonical
+    /// introspection query (as produced by [`RootNode::write_introspection_json()`]
+    /// or fetched from a remote service).
+    ///
+    /// The built schema can be used for validating operations against it.
+    /// Custom scalars are imported as accepting any scalar value, as their
+    /// parsing rules are unknown.
+    ///
+    /// # Errors
+    ///
+    /// If the `json` is not a valid introspection result, or describes an
+    /// inconsistent schema.
+    #[cfg(feature = "serde_json")]
+    pub fn from_introspection_json(
+        json: &str,
+    ) -> Result<Self, crate::introspection::IntrospectionImportError>
+    where
+        S: ScalarValue,
+    {
+        crate::introspection::schema_from_json(json)
     }
 
     /// Add a description.
@@ -269,60 +1343,470 @@ impl<'a, S> SchemaType<'a, S> {
         self.description = Some(description.into());
     }
 
-    /// Add a directive like `skip` or `include`.
-    pub fn add_directive(&mut self, directive: DirectiveType<'a, S>) {
-        self.directives.insert(directive.name.clone(), directive);
+    /// Sets the maximum nesting depth of selection sets, list/object literals
+    /// and list types allowed when parsing operations against this schema.
+    ///
+    /// Operations nested deeper are rejected with a
+    /// [`ParseError::RecursionLimitExceeded`] instead of risking a stack
+    /// overflow. Defaults to [`DEFAULT_RECURSION_LIMIT`].
+    ///
+    /// [`DEFAULT_RECURSION_LIMIT`]: crate::parser::DEFAULT_RECURSION_LIMIT
+    /// [`ParseError::RecursionLimitExceeded`]: crate::parser::ParseError::RecursionLimitExceeded
+    pub fn set_parser_recursion_limit(&mut self, limit: usize) {
+        self.parser_recursion_limit = limit;
     }
 
-    /// Get a type by name.
-    pub fn type_by_name(&self, name: &str) -> Option<TypeType<S>> {
-        self.types.get(name).map(|t| TypeType::Concrete(t))
+    /// Returns the maximum nesting depth allowed when parsing operations
+    /// against this schema.
+    pub fn parser_recursion_limit(&self) -> usize {
+        self.parser_recursion_limit
     }
 
-    /// Get a concrete type by name.
-    pub fn concrete_type_by_name(&self, name: &str) -> Option<&MetaType<S>> {
-        self.types.get(name)
+    /// Sets the [`ExecutorOptions`] to execute operations against this schema
+    /// with.
+    pub fn set_executor_options(&mut self, options: ExecutorOptions) {
+        self.executor_options = options;
     }
 
-    pub(crate) fn lookup_type(&self, tpe: &Type) -> Option<&MetaType<S>> {
-        match *tpe {
-            Type::NonNullNamed(ref name) | Type::Named(ref name) => {
-                self.concrete_type_by_name(name)
-            }
-            Type::List(ref inner, _) | Type::NonNullList(ref inner, _) => self.lookup_type(inner),
-        }
+    /// Returns the [`ExecutorOptions`] operations are executed against this
+    /// schema with.
+    pub fn executor_options(&self) -> &ExecutorOptions {
+        &self.executor_options
     }
 
-    /// Get the query type from the schema.
-    pub fn query_type(&self) -> TypeType<S> {
-        TypeType::Concrete(
-            self.types
-                .get(&self.query_type_name)
-                .expect("Query type does not exist in schema"),
-        )
+    /// Sets the default [`Limits`] of operations executed against this schema.
+    ///
+    /// They may be overridden for a single request by executing it with
+    /// [`execute_with_limits()`], [`execute_sync_with_limits()`] or
+    /// [`resolve_into_stream_with_limits()`].
+    ///
+    /// [`execute_with_limits()`]: crate::execute_with_limits
+    /// [`execute_sync_with_limits()`]: crate::execute_sync_with_limits
+    /// [`resolve_into_stream_with_limits()`]: crate::resolve_into_stream_with_limits
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.limits = limits;
     }
 
-    /// Get the concrete query type from the schema.
-    pub fn concrete_query_type(&self) -> &MetaType<S> {
-        self.types
-            .get(&self.query_type_name)
-            .expect("Query type does not exist in schema")
+    /// Returns the default [`Limits`] of operations executed against this
+    /// schema.
+    pub fn limits(&self) -> &Limits {
+        &self.limits
     }
 
-    /// Get the mutation type from the schema.
-    pub fn mutation_type(&self) -> Option<TypeType<S>> {
-        self.mutation_type_name.as_ref().map(|name| {
-            self.type_by_name(name)
-                .expect("Mutation type does not exist in schema")
-        })
+    /// Sets the [`ValidationPreset`] to validate operations executed against
+    /// this schema with, replacing its default [`Limits`] with the ones
+    /// bundled with the `preset`.
+    ///
+    /// The bundled [`Limits`] may be adjusted afterwards with
+    /// [`SchemaType::set_limits()`].
+    pub fn set_validation_preset(&mut self, preset: ValidationPreset) {
+        self.validation_preset = preset;
+        self.limits = preset.default_limits();
     }
 
-    /// Get the concrete mutation type from the schema.
-    pub fn concrete_mutation_type(&self) -> Option<&MetaType<S>> {
-        self.mutation_type_name.as_ref().map(|name| {
-            self.concrete_type_by_name(name)
-                .expect("Mutation type does not exist in schema")
-        })
+    /// Returns the [`ValidationPreset`] operations executed against this
+    /// schema are validated with.
+    pub fn validation_preset(&self) -> ValidationPreset {
+        self.validation_preset
+    }
+
+    /// Sets the [`RuleSeverity`] of the given built-in `rule` for operations
+    /// executed against this schema, overriding the default one of its
+    /// [`ValidationPreset`].
+    ///
+    /// Violations of the rules downgraded to [`RuleSeverity::Warning`] are
+    /// recorded into the [`MetricsSink`] of this schema, if any.
+    ///
+    /// [`MetricsSink`]: crate::MetricsSink
+    pub fn set_rule_severity(&mut self, rule: ConfigurableRule, severity: RuleSeverity) {
+        self.rule_severities.insert(rule, severity);
+    }
+
+    /// Returns the [`RuleSeverity`] of the given built-in `rule` for
+    /// operations executed against this schema.
+    pub fn rule_severity(&self, rule: ConfigurableRule) -> RuleSeverity {
+        self.rule_severities
+            .get(&rule)
+            .copied()
+            .unwrap_or_else(|| self.validation_preset.default_severity(rule))
+    }
+
+    /// Registers a custom validation rule for operations executed against this
+    /// schema, the [`Visitor`] of which is created by the given `factory` for
+    /// every validated document.
+    ///
+    /// Custom rules are checked after all the built-in ones, only if the
+    /// document satisfies them, and report their violations as errors via
+    /// [`ValidatorContext::report_error()`]. Documents cached in a
+    /// [`DocumentCache`] are checked once, before being cached, so the rules
+    /// should depend on the document and the schema only.
+    pub fn add_validation_rule<F, V>(&mut self, factory: F)
+    where
+        S: ScalarValue,
+        F: Fn() -> V + Send + Sync + 'static,
+        V: for<'v> Visitor<'v, S> + 'static,
+    {
+        self.validation_rules.push(CustomRule::new(factory));
+    }
+
+    /// Returns the custom validation rules registered in this schema.
+    pub(crate) fn validation_rules(&self) -> &[CustomRule<S>] {
+        &self.validation_rules
+    }
+
+    /// Sets the [`ResponseTransform`] to apply to the response of every query
+    /// and mutation executed against this schema, before it's returned.
+    ///
+    /// Not applied to subscriptions and introspection performed with
+    /// [`RootNode::introspect()`].
+    pub fn set_response_transform(&mut self, transform: impl ResponseTransform<S> + 'static) {
+        self.response_transform = Some(Arc::new(transform));
+    }
+
+    /// Returns the [`ResponseTransform`] to apply to the response of every
+    /// query and mutation executed against this schema, if any.
+    pub(crate) fn response_transform(&self) -> Option<&dyn ResponseTransform<S>> {
+        self.response_transform.as_deref()
+    }
+
+    /// Add a directive like `skip` or `include`.
+    pub fn add_directive(&mut self, directive: DirectiveType<'a, S>) {
+        self.directives.insert(directive.name.clone(), directive);
+    }
+
+    /// Registers the custom executable [`GraphQLDirective`] `D`, so it's
+    /// applied to the resolved values of the fields it's placed on.
+    ///
+    /// Types of the directive arguments are registered in this schema too.
+    pub fn register_directive<D>(&mut self)
+    where
+        D: GraphQLDirective<S>,
+        S: ScalarValue + 'a,
+    {
+        let mut registry = Registry::new(std::mem::take(&mut self.types));
+        let mut directive = D::meta(&mut registry);
+        directive.resolve_fn = Some(resolve_directive::<S, D>);
+        self.types = registry.types;
+        self.add_directive(directive);
+    }
+
+    /// Adds the `@defer` directive, so the fragments marked with it are
+    /// delivered separately from the rest of their operation, when it's
+    /// executed via [`execute_incremental()`].
+    ///
+    /// Otherwise, the directive is ignored and the fragments are resolved in
+    /// place, as usual.
+    ///
+    /// [`execute_incremental()`]: crate::execute_incremental
+    pub fn enable_defer(&mut self)
+    where
+        S: ScalarValue + 'a,
+    {
+        let mut registry = Registry::new(std::mem::take(&mut self.types));
+        let directive = DirectiveType::new_defer(&mut registry);
+        self.types = registry.types;
+        self.add_directive(directive);
+    }
+
+    /// Adds the `@stream` directive, so the items of the list fields marked
+    /// with it, following the first `initialCount` ones, are delivered
+    /// separately from the rest of their operation, when it's executed via
+    /// [`execute_incremental()`].
+    ///
+    /// Otherwise, the directive is ignored and the lists are resolved in
+    /// place, as usual.
+    ///
+    /// [`execute_incremental()`]: crate::execute_incremental
+    pub fn enable_stream(&mut self)
+    where
+        S: ScalarValue + 'a,
+    {
+        let mut registry = Registry::new(std::mem::take(&mut self.types));
+        let directive = DirectiveType::new_stream(&mut registry);
+        self.types = registry.types;
+        self.add_directive(directive);
+    }
+
+    /// Removes the field `field_name` of the object or interface type
+    /// `type_name` from this schema, so it's hidden from the introspection
+    /// and operations selecting it are rejected by the validation.
+    ///
+    /// Everything left invalid by the removal is removed too, the same way as
+    /// [`SchemaType::remove_type()`] does. Objects lacking the removed field of
+    /// an interface they implement don't implement it anymore.
+    ///
+    /// Does nothing if there is no such field.
+    ///
+    /// # Errors
+    ///
+    /// With a [`SchemaError::QueryTypeRemoved`] if the query root type is left
+    /// without fields, or with the [`SchemaError`] the pruned schema fails to
+    /// be checked with, as [`RootNode::try_new()`] does.
+    pub fn remove_field(&mut self, type_name: &str, field_name: &str) -> Result<(), SchemaError>
+    where
+        S: ScalarValue,
+    {
+        if self
+            .types
+            .get(type_name)
+            .and_then(|t| t.field_by_name(field_name))
+            .is_none()
+        {
+            return Ok(());
+        }
+        self.prune(|schema| {
+            if let Some(MetaType::Object(ObjectMeta { fields, .. }))
+            | Some(MetaType::Interface(InterfaceMeta { fields, .. })) =
+                schema.types.get_mut(type_name)
+            {
+                fields.retain(|f| f.name != field_name);
+            }
+            Vec::new()
+        })?;
+        self.validate()
+    }
+
+    /// Removes the type `name` from this schema along with everything
+    /// referring to it: the fields of this type or having arguments of this
+    /// type, the input object fields of this type, the directives having
+    /// arguments of this type, and the mentions of this type as a union member
+    /// or an implemented interface.
+    ///
+    /// The types left invalid by the removal are removed as well, along with
+    /// everything referring to them: objects, interfaces and input objects
+    /// without fields, unions without members, interfaces not implemented by
+    /// any object, and input objects missing a required field. Objects
+    /// lacking a field of an interface they implement don't implement it
+    /// anymore.
+    ///
+    /// Removing a mutation or subscription root type removes the whole
+    /// operation type from this schema.
+    ///
+    /// Does nothing if there is no such type.
+    ///
+    /// # Errors
+    ///
+    /// With a [`SchemaError::QueryTypeRemoved`] if the query root type is
+    /// removed, either directly or as being left without fields, or with the
+    /// [`SchemaError`] the pruned schema fails to be checked with, as
+    /// [`RootNode::try_new()`] does.
+    pub fn remove_type(&mut self, name: &str) -> Result<(), SchemaError>
+    where
+        S: ScalarValue,
+    {
+        if !self.types.contains_key(name) {
+            return Ok(());
+        }
+        self.prune(|_| vec![name.into()])?;
+        self.validate()
+    }
+
+    /// Prunes this schema with the `remove` function, returning the names of
+    /// the types to remove, and then removes these types along with everything
+    /// referring to them, and the types left invalid by that, until there are
+    /// none.
+    ///
+    /// The types and the interface implementations being invalid before the
+    /// pruning are left as they are, to be reported by
+    /// [`SchemaType::validate()`].
+    fn prune(&mut self, remove: impl FnOnce(&mut Self) -> Vec<String>) -> Result<(), SchemaError> {
+        let invalid_types = self.invalid_types();
+        let invalid_impls = self.invalid_implementations();
+
+        let mut queued = remove(self);
+        loop {
+            while let Some(name) = queued.pop() {
+                self.remove_type_references(&name, &mut queued)?;
+            }
+            for (object, iface) in self.invalid_implementations() {
+                if invalid_impls.contains(&(object.clone(), iface.clone())) {
+                    continue;
+                }
+                if let Some(MetaType::Object(ObjectMeta {
+                    interface_names, ..
+                })) = self.types.get_mut(object.as_str())
+                {
+                    interface_names.retain(|n| n != iface.as_str());
+                }
+            }
+            queued = self
+                .invalid_types()
+                .into_iter()
+                .filter(|n| !invalid_types.contains(n))
+                .collect();
+            if queued.is_empty() {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Removes the type `name` along with everything referring to it, queueing
+    /// the input objects left without a required field for removal.
+    fn remove_type_references(
+        &mut self,
+        name: &str,
+        queued: &mut Vec<String>,
+    ) -> Result<(), SchemaError> {
+        if name == self.query_type_name {
+            return Err(SchemaError::QueryTypeRemoved(name.into()));
+        }
+        if self.types.remove(name).is_none() {
+            return Ok(());
+        }
+        if self.mutation_type_name.as_deref() == Some(name) {
+            self.mutation_type_name = None;
+        }
+        if self.subscription_type_name.as_deref() == Some(name) {
+            self.subscription_type_name = None;
+        }
+
+        let refers = |arg: &Argument<S>| arg.arg_type.innermost_name() == name;
+        for meta_type in self.types.values_mut() {
+            match meta_type {
+                MetaType::Object(ObjectMeta {
+                    fields,
+                    interface_names,
+                    ..
+                }) => {
+                    fields.retain(|f| {
+                        f.field_type.innermost_name() != name
+                            && !f.arguments.iter().flatten().any(refers)
+                    });
+                    interface_names.retain(|n| n != name);
+                }
+                MetaType::Interface(InterfaceMeta { fields, .. }) => {
+                    fields.retain(|f| {
+                        f.field_type.innermost_name() != name
+                            && !f.arguments.iter().flatten().any(refers)
+                    });
+                }
+                MetaType::Union(UnionMeta { of_type_names, .. }) => {
+                    of_type_names.retain(|n| n != name);
+                }
+                MetaType::InputObject(InputObjectMeta {
+                    name: input_name,
+                    input_fields,
+                    ..
+                }) => {
+                    if input_fields
+                        .iter()
+                        .any(|f| refers(f) && f.arg_type.is_non_null() && f.default_value.is_none())
+                    {
+                        queued.push(input_name.to_string());
+                    }
+                    input_fields.retain(|f| !refers(f));
+                }
+                _ => {}
+            }
+        }
+        self.directives
+            .retain(|_, d| !d.arguments.iter().any(refers));
+        Ok(())
+    }
+
+    /// Returns the names of the types of this schema, which cannot be used:
+    /// objects, interfaces and input objects without fields, unions without
+    /// members, and interfaces not implemented by any object.
+    fn invalid_types(&self) -> Vec<String> {
+        // Introspection fields, like `__typename`, are present in any object.
+        let no_fields = |fields: &[Field<S>]| fields.iter().all(|f| f.name.starts_with("__"));
+        self.types
+            .values()
+            .filter(|t| match t {
+                MetaType::Object(ObjectMeta { fields, .. }) => no_fields(fields),
+                MetaType::Interface(InterfaceMeta { fields, .. }) => {
+                    no_fields(fields) || self.possible_types(t).is_empty()
+                }
+                MetaType::Union(UnionMeta { of_type_names, .. }) => of_type_names.is_empty(),
+                MetaType::InputObject(InputObjectMeta { input_fields, .. }) => {
+                    input_fields.is_empty()
+                }
+                _ => false,
+            })
+            .filter_map(|t| t.name().map(Into::into))
+            .collect()
+    }
+
+    /// Returns the names of the objects of this schema along with the names of
+    /// the interfaces they implement, lacking some fields of them.
+    fn invalid_implementations(&self) -> Vec<(String, String)> {
+        self.types
+            .values()
+            .filter_map(|t| match t {
+                MetaType::Object(ObjectMeta {
+                    name,
+                    fields,
+                    interface_names,
+                    ..
+                }) => Some(interface_names.iter().filter_map(move |iface| {
+                    match self.types.get(iface.as_str()) {
+                        Some(MetaType::Interface(InterfaceMeta {
+                            fields: iface_fields,
+                            ..
+                        })) if iface_fields
+                            .iter()
+                            .any(|f| fields.iter().all(|of| of.name != f.name)) =>
+                        {
+                            Some((name.to_string(), iface.to_string()))
+                        }
+                        _ => None,
+                    }
+                })),
+                _ => None,
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// Get a type by name.
+    pub fn type_by_name(&self, name: &str) -> Option<TypeType<S>> {
+        self.types.get(name).map(|t| TypeType::Concrete(t))
+    }
+
+    /// Get a concrete type by name.
+    pub fn concrete_type_by_name(&self, name: &str) -> Option<&MetaType<S>> {
+        self.types.get(name)
+    }
+
+    pub(crate) fn lookup_type(&self, tpe: &Type) -> Option<&MetaType<S>> {
+        match *tpe {
+            Type::NonNullNamed(ref name) | Type::Named(ref name) => {
+                self.concrete_type_by_name(name)
+            }
+            Type::List(ref inner, _) | Type::NonNullList(ref inner, _) => self.lookup_type(inner),
+        }
+    }
+
+    /// Get the query type from the schema.
+    pub fn query_type(&self) -> TypeType<S> {
+        TypeType::Concrete(
+            self.types
+                .get(&*self.query_type_name)
+                .expect("Query type does not exist in schema"),
+        )
+    }
+
+    /// Get the concrete query type from the schema.
+    pub fn concrete_query_type(&self) -> &MetaType<S> {
+        self.types
+            .get(&*self.query_type_name)
+            .expect("Query type does not exist in schema")
+    }
+
+    /// Get the mutation type from the schema.
+    pub fn mutation_type(&self) -> Option<TypeType<S>> {
+        self.mutation_type_name.as_ref().map(|name| {
+            self.type_by_name(name)
+                .expect("Mutation type does not exist in schema")
+        })
+    }
+
+    /// Get the concrete mutation type from the schema.
+    pub fn concrete_mutation_type(&self) -> Option<&MetaType<S>> {
+        self.mutation_type_name.as_ref().map(|name| {
+            self.concrete_type_by_name(name)
+                .expect("Mutation type does not exist in schema")
+        })
     }
 
     /// Get the subscription type.
@@ -503,6 +1987,8 @@ impl<'a, S> DirectiveType<'a, S>
 where
     S: ScalarValue + 'a,
 {
+    /// Creates a new directive with the given `name`, `locations` and
+    /// `arguments`.
     pub fn new(
         name: &str,
         locations: &[DirectiveLocation],
@@ -515,6 +2001,7 @@ where
             locations: locations.to_vec(),
             arguments: arguments.to_vec(),
             is_repeatable,
+            resolve_fn: None,
         }
     }
 
@@ -550,6 +2037,40 @@ where
         )
     }
 
+    fn new_defer(registry: &mut Registry<'a, S>) -> DirectiveType<'a, S>
+    where
+        S: ScalarValue,
+    {
+        Self::new(
+            "defer",
+            &[
+                DirectiveLocation::FragmentSpread,
+                DirectiveLocation::InlineFragment,
+            ],
+            &[
+                registry.arg_with_default::<bool>("if", &true, &()),
+                registry.arg::<Option<String>>("label", &()),
+            ],
+            false,
+        )
+    }
+
+    fn new_stream(registry: &mut Registry<'a, S>) -> DirectiveType<'a, S>
+    where
+        S: ScalarValue,
+    {
+        Self::new(
+            "stream",
+            &[DirectiveLocation::Field],
+            &[
+                registry.arg_with_default::<bool>("if", &true, &()),
+                registry.arg::<Option<String>>("label", &()),
+                registry.arg_with_default::<i32>("initialCount", &0, &()),
+            ],
+            false,
+        )
+    }
+
     fn new_deprecated(registry: &mut Registry<'a, S>) -> DirectiveType<'a, S>
     where
         S: ScalarValue,
@@ -558,6 +2079,8 @@ where
             "deprecated",
             &[
                 DirectiveLocation::FieldDefinition,
+                DirectiveLocation::ArgumentDefinition,
+                DirectiveLocation::InputFieldDefinition,
                 DirectiveLocation::EnumValue,
             ],
             &[registry.arg::<String>("reason", &())],
@@ -577,10 +2100,25 @@ where
         )
     }
 
+    /// Sets the `description` of this directive.
     pub fn description(mut self, description: &str) -> DirectiveType<'a, S> {
         self.description = Some(description.to_owned());
         self
     }
+
+    /// Adds an `argument` to this directive.
+    pub fn argument(mut self, argument: Argument<'a, S>) -> DirectiveType<'a, S> {
+        self.arguments.push(argument);
+        self
+    }
+}
+
+fn resolve_directive<S, D>(args: &Arguments<S>, value: Value<S>) -> FieldResult<Value<S>, S>
+where
+    S: ScalarValue,
+    D: GraphQLDirective<S>,
+{
+    D::from_arguments(args)?.resolve(value)
 }
 
 impl fmt::Display for DirectiveLocation {
@@ -597,6 +2135,8 @@ impl fmt::Display for DirectiveLocation {
             Self::VariableDefinition => "variable definition",
             Self::Scalar => "scalar",
             Self::EnumValue => "enum value",
+            Self::ArgumentDefinition => "argument definition",
+            Self::InputFieldDefinition => "input field definition",
         })
     }
 }
@@ -682,6 +2222,8 @@ mod test {
             struct Coordinate {
                 latitude: f64,
                 longitude: f64,
+                #[graphql(deprecated)]
+                altitude: Option<f64>,
             }
             struct Query;
             #[graphql_object]
@@ -693,7 +2235,10 @@ mod test {
                 fn whatever() -> String {
                     "foo".to_string()
                 }
-                fn arr(stuff: Vec<Coordinate>) -> Option<&'static str> {
+                fn arr(
+                    stuff: Vec<Coordinate>,
+                    #[graphql(deprecated = "Not used anymore.")] _limit: Option<i32>,
+                ) -> Option<&'static str> {
                     (!stuff.is_empty()).then(|| "stuff")
                 }
                 fn fruit() -> Fruit {
@@ -731,22 +2276,23 @@ mod test {
                 type Cake {
                     fresh: Boolean!
                 }
-                type IceCream {
-                    cold: Boolean!
-                }
                 type Query {
                   blah: Boolean!
                   "This is whatever's description."
                   whatever: String!
-                  arr(stuff: [Coordinate!]!): String
+                  arr(stuff: [Coordinate!]!, limit: Int @deprecated(reason: "Not used anymore.")): String
                   fruit: Fruit!
                   glutenFree(flavor: String!): GlutenFree!
                   old: Int! @deprecated
                   reallyOld: Float! @deprecated(reason: "This field is deprecated, use another.")
                 }
+                type IceCream {
+                    cold: Boolean!
+                }
                 input Coordinate {
                     latitude: Float!
                     longitude: Float!
+                    altitude: Float @deprecated
                 }
                 schema {
                   query: Query
@@ -757,4 +2303,1527 @@ mod test {
             assert_eq!(format!("{}", ast), schema.as_schema_language());
         }
     }
+
+    mod composition {
+        use crate::{
+            execute_sync, graphql_interface, graphql_object, graphql_value, graphql_vars,
+            EmptyMutation, EmptySubscription, GraphQLInputObject, GraphQLObject, GraphQLUnion,
+            RequestError, RootNode, SchemaError,
+        };
+
+        #[derive(GraphQLObject)]
+        struct Canary {
+            chirp: bool,
+        }
+
+        #[derive(GraphQLObject)]
+        struct Stable {
+            id: i32,
+        }
+
+        #[derive(GraphQLUnion)]
+        enum Bird {
+            Canary(Canary),
+            Stable(Stable),
+        }
+
+        #[derive(GraphQLObject)]
+        struct Nest {
+            canary: Canary,
+        }
+
+        #[derive(GraphQLUnion)]
+        enum Canaries {
+            Canary(Canary),
+        }
+
+        #[graphql_interface(for = [Robin, Sparrow])]
+        trait Songbird {
+            fn song(&self) -> &str;
+        }
+
+        #[derive(GraphQLObject)]
+        #[graphql(impl = SongbirdValue)]
+        struct Robin {
+            song: String,
+            red: bool,
+        }
+
+        #[derive(GraphQLObject)]
+        #[graphql(impl = SongbirdValue)]
+        struct Sparrow {
+            song: String,
+        }
+
+        #[derive(GraphQLInputObject)]
+        struct CanaryFilter {
+            loud: bool,
+        }
+
+        #[derive(GraphQLInputObject)]
+        struct NestFilter {
+            canary: CanaryFilter,
+        }
+
+        #[derive(GraphQLInputObject)]
+        struct Filter {
+            id: Option<i32>,
+            canary: Option<CanaryFilter>,
+        }
+
+        struct Query;
+
+        #[graphql_object]
+        impl Query {
+            fn stable() -> Stable {
+                Stable { id: 1 }
+            }
+
+            fn canary() -> Canary {
+                Canary { chirp: true }
+            }
+
+            fn birds(filter: Option<Filter>) -> Vec<Bird> {
+                let _ = filter;
+                vec![Bird::Stable(Stable { id: 1 })]
+            }
+
+            fn loud_canaries(filter: CanaryFilter) -> i32 {
+                i32::from(filter.loud)
+            }
+
+            fn nest() -> Nest {
+                Nest {
+                    canary: Canary { chirp: true },
+                }
+            }
+
+            fn canaries() -> Vec<Canaries> {
+                vec![Canaries::Canary(Canary { chirp: true })]
+            }
+
+            fn nests(filter: Option<NestFilter>) -> i32 {
+                i32::from(filter.is_some())
+            }
+
+            fn songbirds() -> Vec<SongbirdValue> {
+                vec![Robin {
+                    song: "cheerily".into(),
+                    red: true,
+                }
+                .into()]
+            }
+        }
+
+        fn schema(canary: bool) -> RootNode<'static, Query, EmptyMutation, EmptySubscription> {
+            RootNode::try_new(Query, EmptyMutation::new(), EmptySubscription::new())
+                .and_then(|root| root.when(!canary, |root| root.without_type("Canary")))
+                .unwrap()
+        }
+
+        #[test]
+        fn keeps_schema_when_flag_is_not_set() {
+            let schema = schema(true);
+
+            assert!(schema.schema.concrete_type_by_name("Canary").is_some());
+            assert_eq!(
+                execute_sync(
+                    "{ canary { chirp } }",
+                    None,
+                    &schema,
+                    &graphql_vars! {},
+                    &()
+                ),
+                Ok((graphql_value!({"canary": {"chirp": true}}), vec![])),
+            );
+        }
+
+        #[test]
+        fn removes_field() {
+            let schema = schema(true).without_field("Query", "canary").unwrap();
+
+            assert!(schema
+                .schema
+                .concrete_query_type()
+                .field_by_name("canary")
+                .is_none());
+            assert!(matches!(
+                execute_sync(
+                    "{ canary { chirp } }",
+                    None,
+                    &schema,
+                    &graphql_vars! {},
+                    &()
+                ),
+                Err(RequestError::Validation(_)),
+            ));
+            assert_eq!(
+                execute_sync("{ stable { id } }", None, &schema, &graphql_vars! {}, &()),
+                Ok((graphql_value!({"stable": {"id": 1}}), vec![])),
+            );
+        }
+
+        #[test]
+        fn removes_type_with_references() {
+            let schema = schema(false).without_type("CanaryFilter").unwrap();
+            let schema = &schema.schema;
+
+            assert!(schema.concrete_type_by_name("Canary").is_none());
+            assert!(schema.concrete_type_by_name("CanaryFilter").is_none());
+
+            let query = schema.concrete_query_type();
+            assert!(query.field_by_name("canary").is_none());
+            assert!(query.field_by_name("loudCanaries").is_none());
+            assert!(query.field_by_name("birds").is_some());
+
+            let bird = schema.concrete_type_by_name("Bird").unwrap();
+            assert_eq!(
+                schema
+                    .possible_types(bird)
+                    .into_iter()
+                    .filter_map(|t| t.name())
+                    .collect::<Vec<_>>(),
+                ["Stable"],
+            );
+
+            let filter = schema.concrete_type_by_name("Filter").unwrap();
+            assert!(filter.input_field_by_name("id").is_some());
+            assert!(filter.input_field_by_name("canary").is_none());
+        }
+
+        #[test]
+        fn hides_removed_type_from_introspection() {
+            let schema = schema(false);
+
+            let (res, errs) = execute_sync(
+                "{ __schema { types { name } } }",
+                None,
+                &schema,
+                &graphql_vars! {},
+                &(),
+            )
+            .unwrap();
+
+            assert_eq!(errs, []);
+            let types = res
+                .as_object_value()
+                .and_then(|o| o.get_field_value("__schema"))
+                .and_then(|s| s.as_object_value())
+                .and_then(|s| s.get_field_value("types"))
+                .and_then(|t| t.as_list_value())
+                .unwrap();
+            assert!(types.contains(&graphql_value!({"name": "Stable"})));
+            assert!(!types.contains(&graphql_value!({"name": "Canary"})));
+        }
+
+        #[test]
+        fn removes_types_left_invalid() {
+            let schema = schema(false).without_type("CanaryFilter").unwrap();
+            let schema = &schema.schema;
+
+            assert!(schema.concrete_type_by_name("Nest").is_none());
+            assert!(schema.concrete_type_by_name("Canaries").is_none());
+            assert!(schema.concrete_type_by_name("NestFilter").is_none());
+
+            let query = schema.concrete_query_type();
+            assert!(query.field_by_name("nest").is_none());
+            assert!(query.field_by_name("canaries").is_none());
+            assert!(query.field_by_name("nests").is_none());
+            assert!(query.field_by_name("stable").is_some());
+        }
+
+        #[test]
+        fn drops_implementations_lacking_removed_field() {
+            let schema = schema(true).without_field("Robin", "song").unwrap();
+            let schema = &schema.schema;
+
+            let songbird = schema.concrete_type_by_name("Songbird").unwrap();
+            assert_eq!(
+                schema
+                    .possible_types(songbird)
+                    .into_iter()
+                    .filter_map(|t| t.name())
+                    .collect::<Vec<_>>(),
+                ["Sparrow"],
+            );
+            assert!(schema.concrete_type_by_name("Robin").is_some());
+        }
+
+        #[test]
+        fn removes_interfaces_left_unimplemented() {
+            let schema = schema(true)
+                .without_field("Robin", "song")
+                .and_then(|root| root.without_type("Sparrow"))
+                .unwrap();
+            let schema = &schema.schema;
+
+            assert!(schema.concrete_type_by_name("Songbird").is_none());
+            assert!(schema
+                .concrete_query_type()
+                .field_by_name("songbirds")
+                .is_none());
+        }
+
+        #[test]
+        fn errors_on_removing_query_type() {
+            assert_eq!(
+                schema(true).without_type("Query").err(),
+                Some(SchemaError::QueryTypeRemoved("Query".into())),
+            );
+        }
+
+        #[test]
+        fn errors_on_leaving_query_type_without_fields() {
+            let fields = [
+                "stable",
+                "canary",
+                "birds",
+                "loudCanaries",
+                "nest",
+                "canaries",
+                "nests",
+            ];
+            let schema = fields
+                .iter()
+                .try_fold(schema(true), |root, f| root.without_field("Query", f))
+                .unwrap();
+
+            assert_eq!(
+                schema.without_field("Query", "songbirds").err(),
+                Some(SchemaError::QueryTypeRemoved("Query".into())),
+            );
+        }
+    }
+
+    mod check {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        use crate::{
+            graphql_object, graphql_vars, EmptyMutation, EmptySubscription, Limits, OperationError,
+            RequestError, RootNode,
+        };
+
+        static RESOLVED: AtomicBool = AtomicBool::new(false);
+
+        struct Query;
+
+        #[graphql_object]
+        impl Query {
+            fn hello(name: String) -> String {
+                RESOLVED.store(true, Ordering::SeqCst);
+                name
+            }
+        }
+
+        fn schema() -> RootNode<'static, Query, EmptyMutation, EmptySubscription> {
+            RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+        }
+
+        #[test]
+        fn passes_valid_operation_without_resolving() {
+            let schema = schema();
+
+            assert_eq!(
+                schema.check(
+                    "query Q($n: String!) { hello(name: $n) }",
+                    &graphql_vars! {"n": "World"},
+                    Some("Q"),
+                ),
+                Ok(()),
+            );
+            assert!(!RESOLVED.load(Ordering::SeqCst));
+        }
+
+        #[test]
+        fn reports_parse_errors() {
+            assert!(matches!(
+                schema().check("{ hello(", &graphql_vars! {}, None),
+                Err(RequestError::Parse(_)),
+            ));
+        }
+
+        #[test]
+        fn reports_validation_errors() {
+            assert!(matches!(
+                schema().check("{ unknown }", &graphql_vars! {}, None),
+                Err(RequestError::Validation(_)),
+            ));
+        }
+
+        #[test]
+        fn reports_operation_errors() {
+            assert_eq!(
+                schema().check(
+                    r#"query A { hello(name: "a") } query B { hello(name: "b") }"#,
+                    &graphql_vars! {},
+                    Some("C"),
+                ),
+                Err(OperationError::UnknownOperationName.into()),
+            );
+        }
+
+        #[test]
+        fn reports_exceeded_limits() {
+            let schema = schema().with_limits(Limits::new().max_aliases(1));
+
+            assert!(matches!(
+                schema.check(
+                    r#"{ a: hello(name: "a") b: hello(name: "b") }"#,
+                    &graphql_vars! {},
+                    None,
+                ),
+                Err(RequestError::Validation(_)),
+            ));
+        }
+
+        #[test]
+        fn reports_variable_coercion_errors() {
+            assert!(matches!(
+                schema().check(
+                    "query Q($n: String!) { hello(name: $n) }",
+                    &graphql_vars! {"n": 1},
+                    None,
+                ),
+                Err(RequestError::VariableCoercion(_)),
+            ));
+        }
+    }
+
+    mod validation_preset {
+        use crate::{
+            graphql_object, graphql_value, graphql_vars, parser::SourcePosition, EmptyMutation,
+            EmptySubscription, Limits, RequestError, RootNode, RuleError, ValidationPreset,
+        };
+
+        struct Query;
+
+        #[graphql_object]
+        impl Query {
+            fn hello() -> &'static str {
+                "world"
+            }
+
+            fn greet(name: String) -> String {
+                format!("Hello, {name}")
+            }
+        }
+
+        fn schema<'a>() -> RootNode<'a, Query, EmptyMutation, EmptySubscription> {
+            RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+        }
+
+        const UNUSED: &str = "query Q($unused: String) { hello } fragment F on Query { hello }";
+
+        #[test]
+        fn spec_by_default() {
+            let schema = schema();
+
+            assert_eq!(schema.schema.validation_preset(), ValidationPreset::Spec);
+            assert_eq!(schema.schema.limits(), &Limits::new());
+            assert!(matches!(
+                schema.check(UNUSED, &graphql_vars! {}, None),
+                Err(RequestError::Validation(errs)) if errs.len() == 2,
+            ));
+        }
+
+        #[test]
+        fn relaxed_allows_unused_fragments_and_variables() {
+            let schema = schema().with_validation_preset(ValidationPreset::Relaxed);
+
+            assert_eq!(schema.check(UNUSED, &graphql_vars! {}, None), Ok(()));
+            assert!(matches!(
+                schema.check("{ unknown }", &graphql_vars! {}, None),
+                Err(RequestError::Validation(_)),
+            ));
+        }
+
+        #[test]
+        fn spec_plus_security_bundles_limits() {
+            let schema = schema().with_validation_preset(ValidationPreset::SpecPlusSecurity);
+
+            assert_eq!(
+                schema.schema.limits(),
+                &ValidationPreset::SpecPlusSecurity.default_limits(),
+            );
+            let aliases = (0..=ValidationPreset::SECURITY_MAX_ALIASES)
+                .map(|i| format!("a{}: hello", i))
+                .collect::<Vec<_>>()
+                .join(" ");
+            assert!(matches!(
+                schema.check(&format!("{{ {} }}", aliases), &graphql_vars! {}, None),
+                Err(RequestError::Validation(_)),
+            ));
+            assert!(matches!(
+                schema.check(UNUSED, &graphql_vars! {}, None),
+                Err(RequestError::Validation(_)),
+            ));
+        }
+
+        #[test]
+        fn bundled_limits_may_be_overridden() {
+            let schema = schema()
+                .with_validation_preset(ValidationPreset::SpecPlusSecurity)
+                .with_limits(Limits::new());
+
+            assert_eq!(schema.schema.limits(), &Limits::new());
+        }
+
+        #[test]
+        fn max_aliases_may_be_tuned() {
+            let schema = schema()
+                .with_validation_preset(ValidationPreset::SpecPlusSecurity)
+                .with_max_aliases(2);
+
+            assert_eq!(
+                schema.schema.limits(),
+                &ValidationPreset::SpecPlusSecurity
+                    .default_limits()
+                    .max_aliases(2),
+            );
+            assert_eq!(
+                schema.check("{ a: hello b: hello }", &graphql_vars! {}, None),
+                Ok(()),
+            );
+            assert_eq!(
+                schema.check("{ a: hello b: hello c: hello }", &graphql_vars! {}, None),
+                Err(RequestError::Validation(vec![RuleError::new(
+                    "Operation has 3 aliases, exceeding the maximum allowed number of 2",
+                    &[SourcePosition::new(20, 0, 20)],
+                )
+                .with_rule("MaxAliases")
+                .with_param("max", graphql_value!(2))
+                .with_param("actual", graphql_value!(3))])),
+            );
+        }
+
+        #[test]
+        fn max_depth_may_be_tuned() {
+            let schema = schema()
+                .with_validation_preset(ValidationPreset::SpecPlusSecurity)
+                .with_max_depth(2);
+
+            assert_eq!(
+                schema.schema.limits(),
+                &ValidationPreset::SpecPlusSecurity
+                    .default_limits()
+                    .max_depth(2),
+            );
+            assert_eq!(
+                schema.check(
+                    "{ __schema { queryType { name } } }",
+                    &graphql_vars! {},
+                    None
+                ),
+                Err(RequestError::Validation(vec![RuleError::new(
+                    "Operation has depth of 3, exceeding the maximum allowed depth of 2",
+                    &[SourcePosition::new(25, 0, 25)],
+                )
+                .with_rule("MaxDepth")
+                .with_param("max", graphql_value!(2))
+                .with_param("actual", graphql_value!(3))])),
+            );
+            assert!(matches!(
+                schema.check(
+                    "{ ...F } fragment F on Query { __schema { queryType { name } } }",
+                    &graphql_vars! {},
+                    None,
+                ),
+                Err(RequestError::Validation(errs))
+                    if errs.len() == 1 && errs[0].rule() == Some("MaxDepth"),
+            ));
+            assert_eq!(
+                schema.check("{ __schema { description } }", &graphql_vars! {}, None),
+                Ok(()),
+            );
+        }
+    }
+
+    mod rule_severity {
+        use std::sync::{Arc, Mutex};
+
+        use crate::{
+            graphql_object, graphql_vars, ConfigurableRule, EmptyMutation, EmptySubscription,
+            ExecutorOptions, MetricsSink, OperationMeasure, OperationType, RequestError, RootNode,
+            RuleError, RuleSeverity, ValidationPreset,
+        };
+
+        #[derive(Debug, Default)]
+        struct Warnings(Mutex<Vec<String>>);
+
+        impl MetricsSink for Warnings {
+            fn record_measure(&self, _: Option<&str>, _: OperationType, _: &OperationMeasure) {}
+
+            fn record_validation_warnings(&self, warnings: &[RuleError]) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .extend(warnings.iter().map(|w| w.message().to_owned()));
+            }
+        }
+
+        struct Query;
+
+        #[graphql_object]
+        impl Query {
+            fn hello() -> &'static str {
+                "world"
+            }
+
+            fn greet(name: String) -> String {
+                format!("Hello, {name}")
+            }
+        }
+
+        fn schema<'a>() -> RootNode<'a, Query, EmptyMutation, EmptySubscription> {
+            RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+        }
+
+        const UNUSED: &str = "query Q($unused: String) { hello } fragment F on Query { hello }";
+
+        #[test]
+        fn defaults_to_preset() {
+            let schema = schema();
+            assert_eq!(
+                schema
+                    .schema
+                    .rule_severity(ConfigurableRule::NoUnusedFragments),
+                RuleSeverity::Error,
+            );
+
+            let schema = schema.with_validation_preset(ValidationPreset::Relaxed);
+            assert_eq!(
+                schema
+                    .schema
+                    .rule_severity(ConfigurableRule::NoUnusedVariables),
+                RuleSeverity::Off,
+            );
+        }
+
+        #[test]
+        fn disables_rules() {
+            let schema =
+                schema().with_rule_severity(ConfigurableRule::NoUnusedFragments, RuleSeverity::Off);
+
+            assert!(matches!(
+                schema.check(UNUSED, &graphql_vars! {}, None),
+                Err(RequestError::Validation(errs))
+                    if errs.len() == 1 && errs[0].message().contains("$unused"),
+            ));
+        }
+
+        #[test]
+        fn records_warnings() {
+            let sink = Arc::new(Warnings::default());
+            let schema = schema()
+                .with_executor_options(ExecutorOptions::new().metrics_sink(sink.clone()))
+                .with_rule_severity(ConfigurableRule::NoUnusedFragments, RuleSeverity::Warning)
+                .with_rule_severity(ConfigurableRule::NoUnusedVariables, RuleSeverity::Warning);
+
+            assert_eq!(
+                crate::execute_sync(UNUSED, None, &schema, &graphql_vars! {}, &()),
+                Ok((crate::graphql_value!({"hello": "world"}), vec![])),
+            );
+            assert_eq!(
+                *sink.0.lock().unwrap(),
+                [
+                    r#"Variable "$unused" is not used by operation "Q""#,
+                    r#"Fragment "F" is never used"#,
+                ],
+            );
+        }
+
+        #[test]
+        fn disables_builtin_rules() {
+            const QUERY: &str = r#"{ greet(name: "Ann", unknown: "value") }"#;
+
+            assert!(matches!(
+                schema().check(QUERY, &graphql_vars! {}, None),
+                Err(RequestError::Validation(errs)) if errs.len() == 1,
+            ));
+
+            let schema = schema()
+                .with_rule_severity(ConfigurableRule::KnownArgumentNames, RuleSeverity::Off);
+            assert_eq!(
+                crate::execute_sync(QUERY, None, &schema, &graphql_vars! {}, &()),
+                Ok((crate::graphql_value!({"greet": "Hello, Ann"}), vec![])),
+            );
+        }
+    }
+
+    mod disable_introspection {
+        use crate::{
+            execute_sync, graphql_object, graphql_value, graphql_vars, EmptyMutation,
+            EmptySubscription, RequestError, RootNode,
+        };
+
+        struct Context {
+            trusted: bool,
+        }
+
+        impl crate::Context for Context {}
+
+        struct Query;
+
+        #[graphql_object(context = Context)]
+        impl Query {
+            fn hello() -> &'static str {
+                "world"
+            }
+        }
+
+        fn schema<'a>() -> RootNode<'a, Query, EmptyMutation<Context>, EmptySubscription<Context>> {
+            RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+        }
+
+        const INTROSPECTION: &str =
+            r#"{ __schema { queryType { name } } __type(name: "Query") { name } }"#;
+
+        #[test]
+        fn allows_introspection_by_default() {
+            let ctx = Context { trusted: false };
+
+            assert!(schema().allows_introspection(&ctx));
+            assert!(execute_sync(INTROSPECTION, None, &schema(), &graphql_vars! {}, &ctx).is_ok());
+        }
+
+        #[test]
+        fn rejects_introspection() {
+            let schema = schema().disable_introspection();
+            let ctx = Context { trusted: true };
+
+            assert!(matches!(
+                execute_sync(INTROSPECTION, None, &schema, &graphql_vars! {}, &ctx),
+                Err(RequestError::Validation(errs))
+                    if errs.len() == 2
+                        && errs.iter().all(|e| e.rule() == Some("DisableIntrospection")),
+            ));
+            assert_eq!(
+                execute_sync(
+                    "{ __typename hello }",
+                    None,
+                    &schema,
+                    &graphql_vars! {},
+                    &ctx
+                ),
+                Ok((
+                    graphql_value!({"__typename": "Query", "hello": "world"}),
+                    vec![]
+                )),
+            );
+        }
+
+        #[test]
+        fn allows_trusted_requests() {
+            let schema = schema().with_introspection_allowlist(|ctx: &Context| ctx.trusted);
+
+            let trusted = Context { trusted: true };
+            assert!(
+                execute_sync(INTROSPECTION, None, &schema, &graphql_vars! {}, &trusted).is_ok()
+            );
+
+            let untrusted = Context { trusted: false };
+            assert!(matches!(
+                execute_sync(INTROSPECTION, None, &schema, &graphql_vars! {}, &untrusted),
+                Err(RequestError::Validation(_)),
+            ));
+        }
+    }
+
+    mod validation_rules {
+        use std::{
+            sync::{Arc, Mutex},
+            time::Duration,
+        };
+
+        use crate::{
+            graphql_object, graphql_vars,
+            parser::{SourcePosition, Spanning},
+            validation::{max_aliases, ValidatorContext, Visitor},
+            EmptyMutation, EmptySubscription, ExecutorOptions, Field, MetricsSink,
+            OperationMeasure, OperationType, RequestError, RootNode, ScalarValue,
+        };
+
+        /// Rejects documents with more aliased fields than the given maximum.
+        struct MaxAliases {
+            max: usize,
+            aliases: usize,
+        }
+
+        impl<'a, S: ScalarValue> Visitor<'a, S> for MaxAliases {
+            fn enter_field(
+                &mut self,
+                ctx: &mut ValidatorContext<'a, S>,
+                field: &'a Spanning<Field<S>>,
+            ) {
+                if field.item.alias.is_some() {
+                    self.aliases += 1;
+                    if self.aliases == self.max + 1 {
+                        ctx.report_rule_error(
+                            "CustomMaxAliases",
+                            "Too many aliases",
+                            &[field.start],
+                        );
+                    }
+                }
+            }
+        }
+
+        struct Query;
+
+        #[graphql_object]
+        impl Query {
+            fn hello() -> &'static str {
+                "world"
+            }
+        }
+
+        fn schema<'a>() -> RootNode<'a, Query, EmptyMutation, EmptySubscription> {
+            RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+                .with_validation_rule(|| MaxAliases { max: 2, aliases: 0 })
+        }
+
+        #[test]
+        fn reports_custom_rule_errors() {
+            let schema = schema();
+
+            assert!(schema
+                .check("{ a: hello b: hello }", &graphql_vars! {}, None)
+                .is_ok());
+            assert!(matches!(
+                schema.check("{ a: hello b: hello c: hello d: hello }", &graphql_vars! {}, None),
+                Err(RequestError::Validation(errs))
+                    if errs.len() == 1 && errs[0].rule() == Some("CustomMaxAliases"),
+            ));
+        }
+
+        #[test]
+        fn registers_alias_rule_with_custom_limit() {
+            let schema = RootNode::new(
+                Query,
+                EmptyMutation::<()>::new(),
+                EmptySubscription::<()>::new(),
+            )
+            .with_validation_rule(|| max_aliases::factory_with_limit(2));
+
+            assert!(schema
+                .check("{ a: hello b: hello }", &graphql_vars! {}, None)
+                .is_ok());
+            assert!(matches!(
+                schema.check("{ a: hello b: hello c: hello }", &graphql_vars! {}, None),
+                Err(RequestError::Validation(errs))
+                    if errs.len() == 1
+                        && errs[0].rule() == Some("MaxAliases")
+                        && errs[0].locations() == [SourcePosition::new(20, 0, 20)],
+            ));
+        }
+
+        #[test]
+        fn creates_fresh_visitor_per_document() {
+            let schema = schema();
+
+            for _ in 0..3 {
+                assert!(schema
+                    .check("{ a: hello b: hello }", &graphql_vars! {}, None)
+                    .is_ok());
+            }
+        }
+
+        #[test]
+        fn runs_after_builtin_rules() {
+            assert!(matches!(
+                schema().check("{ a: unknown b: unknown c: unknown }", &graphql_vars! {}, None),
+                Err(RequestError::Validation(errs))
+                    if errs.iter().all(|e| e.rule() != Some("CustomMaxAliases")),
+            ));
+        }
+
+        #[derive(Debug, Default)]
+        struct Timings(Mutex<Vec<&'static str>>);
+
+        impl MetricsSink for Timings {
+            fn record_measure(&self, _: Option<&str>, _: OperationType, _: &OperationMeasure) {}
+
+            fn record_validation_timings(&self, timings: &[(&'static str, Duration)]) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .extend(timings.iter().map(|(name, _)| *name));
+            }
+        }
+
+        #[test]
+        fn records_rule_timings_only_if_enabled() {
+            let sink = Arc::new(Timings::default());
+            let options = ExecutorOptions::new().metrics_sink(sink.clone());
+
+            let untimed = schema().with_executor_options(options.clone());
+            crate::execute_sync("{ hello }", None, &untimed, &graphql_vars! {}, &()).unwrap();
+            assert!(sink.0.lock().unwrap().is_empty());
+
+            let timed = schema().with_executor_options(options.time_validation_rules());
+            crate::execute_sync("{ hello }", None, &timed, &graphql_vars! {}, &()).unwrap();
+            let timings = sink.0.lock().unwrap();
+            for rule in [
+                "FieldsOnCorrectType",
+                "NoFragmentCycles",
+                "UniqueOperationNames",
+                "OverlappingFieldsCanBeMerged",
+            ] {
+                assert!(timings.contains(&rule), "no timing of `{}`", rule);
+            }
+            assert!(timings.iter().any(|name| name.ends_with("MaxAliases")));
+        }
+    }
+
+    mod lint {
+        use crate::{
+            graphql_object, parser::SourcePosition, EmptyMutation, EmptySubscription, GraphQLEnum,
+            LintIssueKind, RootNode,
+        };
+
+        #[derive(GraphQLEnum)]
+        enum Color {
+            Red,
+            #[graphql(deprecated = "Use `Red` instead.")]
+            Crimson,
+        }
+
+        struct Query;
+
+        #[graphql_object]
+        impl Query {
+            fn color(
+                #[graphql(deprecated = "Not used anymore.")] _shade: Option<i32>,
+                color: Option<Color>,
+            ) -> Option<Color> {
+                color
+            }
+
+            #[graphql(deprecated = "Use `color` instead.")]
+            fn colour() -> Option<Color> {
+                None
+            }
+        }
+
+        fn schema() -> RootNode<'static, Query, EmptyMutation, EmptySubscription> {
+            RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+        }
+
+        fn kinds(
+            schema: &RootNode<Query, EmptyMutation, EmptySubscription>,
+            doc: &str,
+        ) -> Vec<LintIssueKind> {
+            schema.lint([("doc", doc)])[0]
+                .issues()
+                .iter()
+                .map(|i| i.kind())
+                .collect()
+        }
+
+        #[test]
+        fn reports_each_document_by_name() {
+            let reports = schema().lint([("a.graphql", "{ color }"), ("b.graphql", "{ colour }")]);
+
+            assert_eq!(reports.len(), 2);
+            assert_eq!(reports[0].name(), "a.graphql");
+            assert!(reports[0].issues().is_empty());
+            assert_eq!(reports[1].name(), "b.graphql");
+        }
+
+        #[test]
+        fn reports_syntax_errors() {
+            assert_eq!(kinds(&schema(), "{ color("), vec![LintIssueKind::Syntax]);
+        }
+
+        #[test]
+        fn reports_unknown_fields() {
+            let schema = schema();
+            let reports = schema.lint([("doc", "{ color\n  size }")]);
+
+            let issue = &reports[0].issues()[0];
+            assert_eq!(issue.kind(), LintIssueKind::UnknownField);
+            assert_eq!(issue.message(), r#"Unknown field "size" on type "Query""#,);
+            assert_eq!(issue.locations(), &[SourcePosition::new(10, 1, 2)]);
+            assert!(!reports[0].is_valid());
+        }
+
+        #[test]
+        fn reports_undefined_variables() {
+            assert_eq!(
+                kinds(&schema(), "query Q { color(color: $c) }"),
+                vec![LintIssueKind::UndefinedVariable],
+            );
+        }
+
+        #[test]
+        fn reports_other_validation_errors() {
+            assert_eq!(
+                kinds(&schema(), "query Q($c: Color) { color }"),
+                vec![LintIssueKind::Invalid],
+            );
+        }
+
+        #[test]
+        fn reports_deprecated_usage() {
+            let schema = schema();
+            let reports = schema.lint([("doc", "{ colour color(shade: 1, color: CRIMSON) }")]);
+
+            let report = &reports[0];
+            assert!(report.is_valid());
+            assert_eq!(
+                report
+                    .issues_of(LintIssueKind::Deprecated)
+                    .map(|i| i.message())
+                    .collect::<Vec<_>>(),
+                vec![
+                    r#"Field "colour" on type "Query" is deprecated: Use `color` instead."#,
+                    r#"Argument "shade" of field "color" is deprecated: Not used anymore."#,
+                    r#"Enum value "CRIMSON" of type "Color" is deprecated: Use `Red` instead."#,
+                ],
+            );
+        }
+    }
+
+    mod name_collision {
+        use crate::{
+            graphql_object, EmptyMutation, EmptySubscription, GraphQLObject, RootNode, SchemaError,
+        };
+
+        mod a {
+            #[derive(crate::GraphQLObject)]
+            #[graphql(name = "User")]
+            pub struct User {
+                pub id: i32,
+            }
+        }
+
+        mod b {
+            #[derive(crate::GraphQLObject)]
+            #[graphql(name = "User")]
+            pub struct Account {
+                pub login: String,
+            }
+        }
+
+        #[derive(GraphQLObject)]
+        struct Item {
+            name: String,
+        }
+
+        struct Colliding;
+
+        #[graphql_object]
+        impl Colliding {
+            fn user() -> a::User {
+                a::User { id: 1 }
+            }
+
+            fn account() -> b::Account {
+                b::Account {
+                    login: "root".into(),
+                }
+            }
+        }
+
+        struct Aliased;
+
+        #[graphql_object]
+        impl Aliased {
+            fn item() -> Item {
+                Item { name: "a".into() }
+            }
+
+            fn boxed() -> Box<Item> {
+                Box::new(Item { name: "b".into() })
+            }
+
+            fn label() -> &'static str {
+                "c"
+            }
+
+            fn owned_label() -> String {
+                "d".into()
+            }
+        }
+
+        #[test]
+        fn reports_both_rust_types() {
+            let err = RootNode::<_, _, _>::new_with_info(
+                Colliding,
+                EmptyMutation::<()>::new(),
+                EmptySubscription::<()>::new(),
+                (),
+                (),
+                (),
+            )
+            .err()
+            .unwrap();
+
+            let (name, first, second) = match &err {
+                SchemaError::NameCollision {
+                    name,
+                    first,
+                    second,
+                } => (name, first, second),
+                e => panic!("unexpected error: {}", e),
+            };
+            assert_eq!(name, "User");
+            assert!(first.ends_with("a::User"), "first: {}", first);
+            assert!(second.ends_with("b::Account"), "second: {}", second);
+            assert!(err
+                .to_string()
+                .contains(r#"consider renaming one of them via `#[graphql(name = "...")]`"#));
+        }
+
+        #[test]
+        #[should_panic(expected = "GraphQL type name `User` is used by both")]
+        fn panics_in_infallible_constructor() {
+            RootNode::new(
+                Colliding,
+                EmptyMutation::<()>::new(),
+                EmptySubscription::<()>::new(),
+            );
+        }
+
+        #[test]
+        fn allows_rust_types_describing_same_type() {
+            assert!(RootNode::<_, _, _>::new_with_info(
+                Aliased,
+                EmptyMutation::<()>::new(),
+                EmptySubscription::<()>::new(),
+                (),
+                (),
+                (),
+            )
+            .is_ok());
+        }
+    }
+
+    mod try_new {
+        use crate::{
+            graphql_interface, graphql_object, meta::MetaType, EmptyMutation, EmptySubscription,
+            GraphQLObject, GraphQLType, InputValue, Registry, RootNode, ScalarValue, SchemaError,
+        };
+
+        #[graphql_interface(for = Human)]
+        trait Character {
+            fn id(&self) -> i32;
+        }
+
+        #[derive(GraphQLObject)]
+        #[graphql(impl = CharacterValue)]
+        struct Human {
+            id: i32,
+        }
+
+        #[graphql_interface]
+        trait Orphan {
+            fn id(&self) -> i32;
+        }
+
+        /// Implements [`GraphQLType`] for the given type with a hand-written
+        /// meta, as the derived ones are checked at compile time.
+        macro_rules! hand_written {
+            ($ty:ident, |$registry:ident| $meta:expr) => {
+                struct $ty;
+
+                impl<S: ScalarValue> GraphQLType<S> for $ty {
+                    fn name(_: &()) -> Option<&'static str> {
+                        Some(stringify!($ty))
+                    }
+
+                    fn meta<'r>(_: &(), $registry: &mut Registry<'r, S>) -> MetaType<'r, S>
+                    where
+                        S: 'r,
+                    {
+                        $meta
+                    }
+                }
+
+                impl<S: ScalarValue> crate::GraphQLValue<S> for $ty {
+                    type Context = ();
+                    type TypeInfo = ();
+
+                    fn type_name<'i>(&self, info: &'i ()) -> Option<&'i str> {
+                        <Self as GraphQLType<S>>::name(info)
+                    }
+                }
+            };
+        }
+
+        // Query with an argument, which default value is invalid for its type.
+        hand_written!(BadDefault, |registry| {
+            let fields = [registry.field::<i32>("count", &()).argument(
+                registry
+                    .arg::<Option<i32>>("limit", &())
+                    .default_value(InputValue::scalar("ten".to_owned())),
+            )];
+            registry.build_object_type::<Self>(&(), &fields).into_meta()
+        });
+
+        // Object declaring to implement `Character` without its `id` field.
+        hand_written!(Rogue, |registry| {
+            let fields = [registry.field::<String>("name", &())];
+            let iface = registry.get_type::<CharacterValue>(&());
+            registry
+                .build_object_type::<Self>(&(), &fields)
+                .interfaces(&[iface])
+                .into_meta()
+        });
+
+        // Object declaring to implement the `Human` object.
+        hand_written!(Impostor, |registry| {
+            let fields = [registry.field::<i32>("id", &())];
+            let iface = registry.get_type::<Human>(&());
+            registry
+                .build_object_type::<Self>(&(), &fields)
+                .interfaces(&[iface])
+                .into_meta()
+        });
+
+        hand_written!(Nothing, |registry| {
+            registry.build_union_type::<Self>(&(), &[]).into_meta()
+        });
+
+        // Union having the `Character` interface as its member.
+        hand_written!(Anything, |registry| {
+            let members = [
+                registry.get_type::<Human>(&()),
+                registry.get_type::<CharacterValue>(&()),
+            ];
+            registry.build_union_type::<Self>(&(), &members).into_meta()
+        });
+
+        hand_written!(NothingQuery, |registry| {
+            let fields = [registry.field::<Option<Nothing>>("nothing", &())];
+            registry.build_object_type::<Self>(&(), &fields).into_meta()
+        });
+
+        hand_written!(AnythingQuery, |registry| {
+            let fields = [registry.field::<Option<Anything>>("anything", &())];
+            registry.build_object_type::<Self>(&(), &fields).into_meta()
+        });
+
+        struct Query;
+
+        #[graphql_object]
+        impl Query {
+            fn hero() -> CharacterValue {
+                Human { id: 1 }.into()
+            }
+        }
+
+        struct OrphanQuery;
+
+        #[graphql_object]
+        impl OrphanQuery {
+            fn orphan() -> Option<OrphanValue> {
+                None
+            }
+        }
+
+        #[test]
+        fn builds_valid_schema() {
+            assert!(RootNode::try_new(
+                Query,
+                EmptyMutation::<()>::new(),
+                EmptySubscription::<()>::new()
+            )
+            .is_ok());
+        }
+
+        #[test]
+        fn reports_orphan_interface() {
+            assert_eq!(
+                RootNode::try_new(
+                    OrphanQuery,
+                    EmptyMutation::<()>::new(),
+                    EmptySubscription::<()>::new()
+                )
+                .err(),
+                Some(SchemaError::OrphanInterface("Orphan".into())),
+            );
+        }
+
+        #[test]
+        fn reports_invalid_default_value() {
+            let err = RootNode::try_new(
+                BadDefault,
+                EmptyMutation::<()>::new(),
+                EmptySubscription::<()>::new(),
+            )
+            .err()
+            .unwrap();
+
+            assert_eq!(
+                err,
+                SchemaError::InvalidDefaultValue {
+                    name: "BadDefault.count(limit)".into(),
+                    value: r#""ten""#.into(),
+                },
+            );
+            assert_eq!(
+                err.to_string(),
+                r#"Default value `"ten"` of `BadDefault.count(limit)` is not valid for its type"#,
+            );
+        }
+
+        #[test]
+        fn reports_missing_interface_field() {
+            let err = RootNode::try_new(
+                Rogue,
+                EmptyMutation::<()>::new(),
+                EmptySubscription::<()>::new(),
+            )
+            .err()
+            .unwrap();
+
+            assert_eq!(
+                err,
+                SchemaError::MissingInterfaceField {
+                    object: "Rogue".into(),
+                    interface: "Character".into(),
+                    field: "id".into(),
+                },
+            );
+            assert_eq!(
+                err.to_string(),
+                "GraphQL object `Rogue` implements GraphQL interface `Character`, \
+                 but has no `id` field",
+            );
+        }
+
+        #[test]
+        fn reports_invalid_interface() {
+            assert_eq!(
+                RootNode::try_new(
+                    Impostor,
+                    EmptyMutation::<()>::new(),
+                    EmptySubscription::<()>::new()
+                )
+                .err(),
+                Some(SchemaError::InvalidInterface {
+                    object: "Impostor".into(),
+                    interface: "Human".into(),
+                }),
+            );
+        }
+
+        #[test]
+        fn reports_empty_union() {
+            assert_eq!(
+                RootNode::try_new(
+                    NothingQuery,
+                    EmptyMutation::<()>::new(),
+                    EmptySubscription::<()>::new()
+                )
+                .err(),
+                Some(SchemaError::EmptyUnion("Nothing".into())),
+            );
+        }
+
+        #[test]
+        fn reports_invalid_union_member() {
+            assert_eq!(
+                RootNode::try_new(
+                    AnythingQuery,
+                    EmptyMutation::<()>::new(),
+                    EmptySubscription::<()>::new()
+                )
+                .err(),
+                Some(SchemaError::InvalidUnionMember {
+                    union: "Anything".into(),
+                    member: "Character".into(),
+                }),
+            );
+        }
+
+        #[test]
+        fn keeps_panicking_constructor_lenient() {
+            let _ = RootNode::new(
+                OrphanQuery,
+                EmptyMutation::<()>::new(),
+                EmptySubscription::<()>::new(),
+            );
+        }
+    }
+
+    mod document_cache {
+        use std::sync::{Arc, Mutex};
+
+        use crate::{
+            execute, execute_sync, execute_sync_with_limits, graphql_object, graphql_vars,
+            DocumentCache as _, EmptyMutation, EmptySubscription, ExecutorOptions, Limits,
+            LruDocumentCache, MetricsSink, OperationMeasure, OperationType, RequestError, RootNode,
+        };
+
+        struct Query;
+
+        #[graphql_object]
+        impl Query {
+            fn greet(name: String) -> String {
+                format!("Hello, {}", name)
+            }
+        }
+
+        type Schema = RootNode<'static, Query, EmptyMutation, EmptySubscription>;
+
+        fn schema(cache: &Arc<LruDocumentCache<crate::DefaultScalarValue>>) -> Schema {
+            RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+                .with_document_cache(Arc::clone(cache))
+        }
+
+        const QUERY: &str = "query($name: String!) { greet(name: $name) }";
+
+        #[test]
+        fn executes_cached_documents() {
+            let cache = Arc::new(LruDocumentCache::new(10));
+            let schema = schema(&cache);
+
+            assert_eq!(
+                execute_sync(QUERY, None, &schema, &graphql_vars! {"name": "Ann"}, &()),
+                Ok((crate::graphql_value!({"greet": "Hello, Ann"}), vec![])),
+            );
+            assert_eq!(
+                execute_sync(QUERY, None, &schema, &graphql_vars! {"name": "Bob"}, &()),
+                Ok((crate::graphql_value!({"greet": "Hello, Bob"}), vec![])),
+            );
+            assert_eq!(cache.len(), 1);
+            assert!(cache.get(QUERY).is_some());
+        }
+
+        #[tokio::test]
+        async fn executes_cached_documents_async() {
+            let cache = Arc::new(LruDocumentCache::new(10));
+            let schema = schema(&cache);
+
+            for _ in 0..2 {
+                assert_eq!(
+                    execute(QUERY, None, &schema, &graphql_vars! {"name": "Ann"}, &()).await,
+                    Ok((crate::graphql_value!({"greet": "Hello, Ann"}), vec![])),
+                );
+            }
+            assert_eq!(cache.len(), 1);
+        }
+
+        #[test]
+        fn checks_variables_of_cached_documents() {
+            let cache = Arc::new(LruDocumentCache::new(10));
+            let schema = schema(&cache);
+
+            execute_sync(QUERY, None, &schema, &graphql_vars! {"name": "Ann"}, &()).unwrap();
+            assert!(matches!(
+                execute_sync(QUERY, None, &schema, &graphql_vars! {}, &()),
+                Err(RequestError::VariableCoercion(_)),
+            ));
+        }
+
+        #[test]
+        fn checks_limits_of_cached_documents_per_request() {
+            let cache = Arc::new(LruDocumentCache::new(10));
+            let schema = schema(&cache);
+            let doc = r#"{ a: greet(name: "Ann") b: greet(name: "Bob") }"#;
+
+            execute_sync(doc, None, &schema, &graphql_vars! {}, &()).unwrap();
+            assert_eq!(cache.len(), 1);
+            assert!(matches!(
+                execute_sync_with_limits(
+                    doc,
+                    None,
+                    &schema,
+                    &graphql_vars! {},
+                    &(),
+                    &Limits::new().max_aliases(1),
+                ),
+                Err(RequestError::Validation(errs))
+                    if errs.len() == 1 && errs[0].rule() == Some("MaxAliases"),
+            ));
+        }
+
+        #[test]
+        fn reports_invalid_documents_uncached() {
+            let cache = Arc::new(LruDocumentCache::new(10));
+            let schema = schema(&cache);
+
+            assert!(matches!(
+                execute_sync("{ greet", None, &schema, &graphql_vars! {}, &()),
+                Err(RequestError::Parse(_)),
+            ));
+            assert!(matches!(
+                execute_sync("{ unknown }", None, &schema, &graphql_vars! {}, &()),
+                Err(RequestError::Validation(_)),
+            ));
+            assert!(cache.is_empty());
+        }
+
+        #[derive(Debug, Default)]
+        struct Lookups(Mutex<Vec<bool>>);
+
+        impl MetricsSink for Lookups {
+            fn record_measure(&self, _: Option<&str>, _: OperationType, _: &OperationMeasure) {}
+
+            fn record_document_cache(&self, hit: bool) {
+                self.0.lock().unwrap().push(hit);
+            }
+        }
+
+        #[test]
+        fn records_hits_and_misses() {
+            let cache = Arc::new(LruDocumentCache::new(10));
+            let sink = Arc::new(Lookups::default());
+            let schema = schema(&cache)
+                .with_executor_options(ExecutorOptions::new().metrics_sink(sink.clone()));
+
+            for _ in 0..3 {
+                execute_sync(QUERY, None, &schema, &graphql_vars! {"name": "Ann"}, &()).unwrap();
+            }
+            assert_eq!(*sink.0.lock().unwrap(), [false, true, true]);
+        }
+
+        #[test]
+        fn rejects_cached_introspection_if_disabled() {
+            let cache = Arc::new(LruDocumentCache::new(10));
+            let schema = schema(&cache).disable_introspection();
+            let introspection = "{ __schema { queryType { name } } }";
+
+            for _ in 0..2 {
+                assert!(matches!(
+                    execute_sync(introspection, None, &schema, &graphql_vars! {}, &()),
+                    Err(RequestError::Validation(errs)) if errs.len() == 1,
+                ));
+            }
+            assert!(cache.get(introspection).is_some());
+        }
+
+        struct OtherQuery;
+
+        #[graphql_object]
+        impl OtherQuery {
+            fn greet(id: i32) -> String {
+                format!("Hello, #{}", id)
+            }
+        }
+
+        #[test]
+        fn validates_documents_per_schema_sharing_cache() {
+            let cache = Arc::new(LruDocumentCache::new(10));
+            let schema = schema(&cache);
+            let other = RootNode::new(OtherQuery, EmptyMutation::new(), EmptySubscription::new())
+                .with_document_cache(Arc::clone(&cache));
+
+            execute_sync(QUERY, None, &schema, &graphql_vars! {"name": "Ann"}, &()).unwrap();
+            assert!(matches!(
+                execute_sync(QUERY, None, &other, &graphql_vars! {"name": "Ann"}, &()),
+                Err(RequestError::Validation(_)),
+            ));
+            assert_eq!(
+                execute_sync("{ greet(id: 1) }", None, &other, &graphql_vars! {}, &()),
+                Ok((crate::graphql_value!({"greet": "Hello, #1"}), vec![])),
+            );
+            assert_eq!(
+                execute_sync(QUERY, None, &schema, &graphql_vars! {"name": "Bob"}, &()),
+                Ok((crate::graphql_value!({"greet": "Hello, Bob"}), vec![])),
+            );
+        }
+    }
 }
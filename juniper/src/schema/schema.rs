@@ -1,3 +1,5 @@
+use std::marker::PhantomData;
+
 use crate::{
     ast::Selection,
     executor::{ExecutionResult, Executor, Registry},
@@ -83,12 +85,13 @@ where
         selection_set: Option<&[Selection<S>]>,
         executor: &Executor<Self::Context, S>,
     ) -> ExecutionResult<S> {
-        use crate::{types::base::resolve_selection_set_into, value::Object};
+        use crate::types::base::resolve_selection_set_into;
         if let Some(selection_set) = selection_set {
-            let mut result = Object::with_capacity(selection_set.len());
+            let mut result = executor.object_pool().object(selection_set.len());
             if resolve_selection_set_into(self, info, selection_set, executor, &mut result) {
                 Ok(Value::Object(result))
             } else {
+                executor.object_pool().recycle(result);
                 Ok(Value::null())
             }
         } else {
@@ -130,6 +133,51 @@ where
     }
 }
 
+/// Root value of an introspection query resolved right against a [`SchemaType`],
+/// without touching any user-defined root values.
+///
+/// Only the `__schema`, `__type` and `__typename` fields can be resolved on it.
+pub(crate) struct IntrospectionRoot<'a, S>(PhantomData<&'a SchemaType<'a, S>>);
+
+impl<'a, S> IntrospectionRoot<'a, S> {
+    pub(crate) fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<'a, S> GraphQLValue<S> for IntrospectionRoot<'a, S>
+where
+    S: ScalarValue + 'a,
+{
+    type Context = SchemaType<'a, S>;
+    type TypeInfo = SchemaType<'a, S>;
+
+    fn type_name<'i>(&self, info: &'i Self::TypeInfo) -> Option<&'i str> {
+        Some(&info.query_type_name)
+    }
+
+    fn concrete_type_name(&self, _: &Self::Context, info: &Self::TypeInfo) -> String {
+        info.query_type_name.to_string()
+    }
+
+    fn resolve_field(
+        &self,
+        info: &Self::TypeInfo,
+        field: &str,
+        args: &Arguments<S>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        match field {
+            "__schema" => executor.resolve(&(), info),
+            "__type" => {
+                let type_name: String = args.get("name")?.unwrap();
+                executor.resolve(&(), &info.type_by_name(&type_name))
+            }
+            _ => Err(format!("Field `{}` is not an introspection field", field).into()),
+        }
+    }
+}
+
 #[graphql_object(
     name = "__Schema"
     context = SchemaType<'a, S>,
@@ -218,7 +266,7 @@ impl<'a, S: ScalarValue + 'a> TypeType<'a, S> {
                 fields
                     .iter()
                     .filter(|f| include_deprecated || !f.deprecation_status.is_deprecated())
-                    .filter(|f| !f.name.starts_with("__"))
+                    .filter(|f| !f.name.starts_with("__") && !f.is_hidden())
                     .collect(),
             ),
             _ => None,
@@ -232,12 +280,20 @@ impl<'a, S: ScalarValue + 'a> TypeType<'a, S> {
         }
     }
 
-    fn input_fields(&self) -> Option<&[Argument<S>]> {
+    fn input_fields(
+        &self,
+        #[graphql(default)] include_deprecated: bool,
+    ) -> Option<Vec<&Argument<S>>> {
         match self {
             TypeType::Concrete(&MetaType::InputObject(InputObjectMeta {
                 ref input_fields,
                 ..
-            })) => Some(input_fields.as_slice()),
+            })) => Some(
+                input_fields
+                    .iter()
+                    .filter(|f| include_deprecated || !f.deprecation_status.is_deprecated())
+                    .collect(),
+            ),
             _ => None,
         }
     }
@@ -281,7 +337,7 @@ impl<'a, S: ScalarValue + 'a> TypeType<'a, S> {
                             ..
                         }) = *ct
                         {
-                            if interface_names.contains(&iface_name.to_string()) {
+                            if interface_names.iter().any(|n| n == iface_name) {
                                 context.type_by_name(name)
                             } else {
                                 None
@@ -325,10 +381,12 @@ impl<'a, S: ScalarValue + 'a> Field<'a, S> {
         self.description.as_deref()
     }
 
-    fn args(&self) -> Vec<&Argument<S>> {
-        self.arguments
-            .as_ref()
-            .map_or_else(Vec::new, |v| v.iter().collect())
+    fn args(&self, #[graphql(default)] include_deprecated: bool) -> Vec<&Argument<S>> {
+        self.arguments.as_ref().map_or_else(Vec::new, |v| {
+            v.iter()
+                .filter(|a| include_deprecated || !a.deprecation_status.is_deprecated())
+                .collect()
+        })
     }
 
     #[graphql(name = "type")]
@@ -370,6 +428,14 @@ impl<'a, S: ScalarValue + 'a> Argument<'a, S> {
     fn default_value_(&self) -> Option<String> {
         self.default_value.as_ref().map(ToString::to_string)
     }
+
+    fn is_deprecated(&self) -> bool {
+        self.deprecation_status.is_deprecated()
+    }
+
+    fn deprecation_reason(&self) -> Option<&str> {
+        self.deprecation_status.reason()
+    }
 }
 
 #[graphql_object(name = "__EnumValue", internal)]
@@ -416,8 +482,11 @@ impl<'a, S: ScalarValue + 'a> DirectiveType<'a, S> {
         self.is_repeatable
     }
 
-    fn args(&self) -> &[Argument<S>] {
-        &self.arguments
+    fn args(&self, #[graphql(default)] include_deprecated: bool) -> Vec<&Argument<S>> {
+        self.arguments
+            .iter()
+            .filter(|a| include_deprecated || !a.deprecation_status.is_deprecated())
+            .collect()
     }
 
     // Included for compatibility with the introspection query in GraphQL.js
@@ -90,7 +90,7 @@ impl GraphQLParserTranslator {
                 .default_value
                 .as_ref()
                 .map(|x| GraphQLParserTranslator::translate_value(x)),
-            directives: vec![],
+            directives: generate_directives(&input.deprecation_status),
         }
     }
 
@@ -196,7 +196,7 @@ impl GraphQLParserTranslator {
                 fields: x
                     .fields
                     .iter()
-                    .filter(|x| !x.is_builtin())
+                    .filter(|x| !x.is_builtin() && !x.is_hidden())
                     .map(GraphQLParserTranslator::translate_field)
                     .collect(),
             }),
@@ -222,7 +222,7 @@ impl GraphQLParserTranslator {
                 fields: x
                     .fields
                     .iter()
-                    .filter(|x| !x.is_builtin())
+                    .filter(|x| !x.is_builtin() && !x.is_hidden())
                     .map(GraphQLParserTranslator::translate_field)
                     .collect(),
                 implements_interfaces: x
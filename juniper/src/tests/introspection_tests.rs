@@ -212,6 +212,8 @@ async fn test_introspection_directives() {
                     "name": "deprecated",
                     "locations": [
                         "FIELD_DEFINITION",
+                        "ARGUMENT_DEFINITION",
+                        "INPUT_FIELD_DEFINITION",
                         "ENUM_VALUE",
                     ],
                 },
@@ -308,3 +310,52 @@ async fn test_builtin_introspection_query_without_descriptions() {
 
     assert_eq!(result, (expected, vec![]));
 }
+
+#[test]
+fn test_schema_introspect_without_context() {
+    let schema = RootNode::new(
+        Query,
+        EmptyMutation::<Database>::new(),
+        EmptySubscription::<Database>::new(),
+    );
+
+    let mut result = schema.schema.introspect(IntrospectionFormat::default());
+    sort_schema_value(&mut result);
+
+    assert_eq!(result, schema_introspection_result());
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn test_introspection_json_is_stable() {
+    let schema = RootNode::new(
+        Query,
+        EmptyMutation::<Database>::new(),
+        EmptySubscription::<Database>::new(),
+    );
+
+    let mut first = Vec::new();
+    schema
+        .write_introspection_json(&mut first, IntrospectionFormat::default())
+        .unwrap();
+    let mut second = Vec::new();
+    schema
+        .write_introspection_json(&mut second, IntrospectionFormat::default())
+        .unwrap();
+    assert_eq!(first, second);
+
+    let json: serde_json::Value = serde_json::from_slice(&first).unwrap();
+    let type_names = json["__schema"]["types"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["name"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    let mut sorted = type_names.clone();
+    sorted.sort_unstable();
+    assert_eq!(type_names, sorted);
+    assert_eq!(
+        json["__schema"]["queryType"],
+        serde_json::json!({"name": "Query"}),
+    );
+}
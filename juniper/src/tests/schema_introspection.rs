@@ -221,6 +221,34 @@ pub(crate) fn schema_introspection_result() -> Value {
                   },
                   "isDeprecated": false,
                   "deprecationReason": null
+                },
+                {
+                  "name": "isDeprecated",
+                  "description": null,
+                  "args": [],
+                  "type": {
+                    "kind": "NON_NULL",
+                    "name": null,
+                    "ofType": {
+                      "kind": "SCALAR",
+                      "name": "Boolean",
+                      "ofType": null
+                    }
+                  },
+                  "isDeprecated": false,
+                  "deprecationReason": null
+                },
+                {
+                  "name": "deprecationReason",
+                  "description": null,
+                  "args": [],
+                  "type": {
+                    "kind": "SCALAR",
+                    "name": "String",
+                    "ofType": null
+                  },
+                  "isDeprecated": false,
+                  "deprecationReason": null
                 }
               ],
               "inputFields": null,
@@ -276,7 +304,20 @@ pub(crate) fn schema_introspection_result() -> Value {
                 {
                   "name": "args",
                   "description": null,
-                  "args": [],
+                  "args": [
+                    {
+                      "name": "includeDeprecated",
+                      "description": null,
+                      "type": {
+                        "kind": "SCALAR",
+                        "name": "Boolean",
+                        "ofType": null
+                      },
+                      "defaultValue": "false",
+                      "isDeprecated": false,
+                      "deprecationReason": null
+                    }
+                  ],
                   "type": {
                     "kind": "NON_NULL",
                     "name": null,
@@ -465,7 +506,9 @@ pub(crate) fn schema_introspection_result() -> Value {
                         "name": "Boolean",
                         "ofType": null
                       },
-                      "defaultValue": "false"
+                      "defaultValue": "false",
+                      "isDeprecated": false,
+                      "deprecationReason": null
                     }
                   ],
                   "type": {
@@ -499,7 +542,20 @@ pub(crate) fn schema_introspection_result() -> Value {
                 {
                   "name": "inputFields",
                   "description": null,
-                  "args": [],
+                  "args": [
+                    {
+                      "name": "includeDeprecated",
+                      "description": null,
+                      "type": {
+                        "kind": "SCALAR",
+                        "name": "Boolean",
+                        "ofType": null
+                      },
+                      "defaultValue": "false",
+                      "isDeprecated": false,
+                      "deprecationReason": null
+                    }
+                  ],
                   "type": {
                     "kind": "LIST",
                     "name": null,
@@ -580,7 +636,9 @@ pub(crate) fn schema_introspection_result() -> Value {
                         "name": "Boolean",
                         "ofType": null
                       },
-                      "defaultValue": "false"
+                      "defaultValue": "false",
+                      "isDeprecated": false,
+                      "deprecationReason": null
                     }
                   ],
                   "type": {
@@ -845,7 +903,9 @@ pub(crate) fn schema_introspection_result() -> Value {
                           "ofType": null
                         }
                       },
-                      "defaultValue": null
+                      "defaultValue": null,
+                      "isDeprecated": false,
+                      "deprecationReason": null
                     }
                   ],
                   "type": {
@@ -873,7 +933,9 @@ pub(crate) fn schema_introspection_result() -> Value {
                           "ofType": null
                         }
                       },
-                      "defaultValue": null
+                      "defaultValue": null,
+                      "isDeprecated": false,
+                      "deprecationReason": null
                     }
                   ],
                   "type": {
@@ -896,7 +958,9 @@ pub(crate) fn schema_introspection_result() -> Value {
                         "name": "Episode",
                         "ofType": null
                       },
-                      "defaultValue": null
+                      "defaultValue": null,
+                      "isDeprecated": false,
+                      "deprecationReason": null
                     }
                   ],
                   "type": {
@@ -1085,6 +1149,18 @@ pub(crate) fn schema_introspection_result() -> Value {
                   "description": null,
                   "isDeprecated": false,
                   "deprecationReason": null
+                },
+                {
+                  "name": "ARGUMENT_DEFINITION",
+                  "description": null,
+                  "isDeprecated": false,
+                  "deprecationReason": null
+                },
+                {
+                  "name": "INPUT_FIELD_DEFINITION",
+                  "description": null,
+                  "isDeprecated": false,
+                  "deprecationReason": null
                 }
               ],
               "possibleTypes": null
@@ -1265,7 +1341,20 @@ pub(crate) fn schema_introspection_result() -> Value {
                 {
                   "name": "args",
                   "description": null,
-                  "args": [],
+                  "args": [
+                    {
+                      "name": "includeDeprecated",
+                      "description": null,
+                      "type": {
+                        "kind": "SCALAR",
+                        "name": "Boolean",
+                        "ofType": null
+                      },
+                      "defaultValue": "false",
+                      "isDeprecated": false,
+                      "deprecationReason": null
+                    }
+                  ],
                   "type": {
                     "kind": "NON_NULL",
                     "name": null,
@@ -1364,7 +1453,9 @@ pub(crate) fn schema_introspection_result() -> Value {
                       "ofType": null
                     }
                   },
-                  "defaultValue": null
+                  "defaultValue": null,
+                  "isDeprecated": false,
+                  "deprecationReason": null
                 }
               ]
             },
@@ -1390,7 +1481,9 @@ pub(crate) fn schema_introspection_result() -> Value {
                       "ofType": null
                     }
                   },
-                  "defaultValue": null
+                  "defaultValue": null,
+                  "isDeprecated": false,
+                  "deprecationReason": null
                 }
               ]
             },
@@ -1400,6 +1493,8 @@ pub(crate) fn schema_introspection_result() -> Value {
               "isRepeatable": false,
               "locations": [
                 "FIELD_DEFINITION",
+                "ARGUMENT_DEFINITION",
+                "INPUT_FIELD_DEFINITION",
                 "ENUM_VALUE"
               ],
               "args": [
@@ -1415,7 +1510,9 @@ pub(crate) fn schema_introspection_result() -> Value {
                       "ofType": null
                     }
                   },
-                  "defaultValue": null
+                  "defaultValue": null,
+                  "isDeprecated": false,
+                  "deprecationReason": null
                 }
               ]
             },
@@ -1439,7 +1536,9 @@ pub(crate) fn schema_introspection_result() -> Value {
                       "ofType": null
                     }
                   },
-                  "defaultValue": null
+                  "defaultValue": null,
+                  "isDeprecated": false,
+                  "deprecationReason": null
                 }
               ]
             }
@@ -1625,6 +1724,32 @@ pub(crate) fn schema_introspection_result_without_descriptions() -> Value {
                   },
                   "isDeprecated": false,
                   "deprecationReason": null
+                },
+                {
+                  "name": "isDeprecated",
+                  "args": [],
+                  "type": {
+                    "kind": "NON_NULL",
+                    "name": null,
+                    "ofType": {
+                      "kind": "SCALAR",
+                      "name": "Boolean",
+                      "ofType": null
+                    }
+                  },
+                  "isDeprecated": false,
+                  "deprecationReason": null
+                },
+                {
+                  "name": "deprecationReason",
+                  "args": [],
+                  "type": {
+                    "kind": "SCALAR",
+                    "name": "String",
+                    "ofType": null
+                  },
+                  "isDeprecated": false,
+                  "deprecationReason": null
                 }
               ],
               "inputFields": null,
@@ -1675,7 +1800,19 @@ pub(crate) fn schema_introspection_result_without_descriptions() -> Value {
                 },
                 {
                   "name": "args",
-                  "args": [],
+                  "args": [
+                    {
+                      "name": "includeDeprecated",
+                      "type": {
+                        "kind": "SCALAR",
+                        "name": "Boolean",
+                        "ofType": null
+                      },
+                      "defaultValue": "false",
+                      "isDeprecated": false,
+                      "deprecationReason": null
+                    }
+                  ],
                   "type": {
                     "kind": "NON_NULL",
                     "name": null,
@@ -1846,7 +1983,9 @@ pub(crate) fn schema_introspection_result_without_descriptions() -> Value {
                         "name": "Boolean",
                         "ofType": null
                       },
-                      "defaultValue": "false"
+                      "defaultValue": "false",
+                      "isDeprecated": false,
+                      "deprecationReason": null
                     }
                   ],
                   "type": {
@@ -1878,7 +2017,19 @@ pub(crate) fn schema_introspection_result_without_descriptions() -> Value {
                 },
                 {
                   "name": "inputFields",
-                  "args": [],
+                  "args": [
+                    {
+                      "name": "includeDeprecated",
+                      "type": {
+                        "kind": "SCALAR",
+                        "name": "Boolean",
+                        "ofType": null
+                      },
+                      "defaultValue": "false",
+                      "isDeprecated": false,
+                      "deprecationReason": null
+                    }
+                  ],
                   "type": {
                     "kind": "LIST",
                     "name": null,
@@ -1954,7 +2105,9 @@ pub(crate) fn schema_introspection_result_without_descriptions() -> Value {
                         "name": "Boolean",
                         "ofType": null
                       },
-                      "defaultValue": "false"
+                      "defaultValue": "false",
+                      "isDeprecated": false,
+                      "deprecationReason": null
                     }
                   ],
                   "type": {
@@ -2203,7 +2356,9 @@ pub(crate) fn schema_introspection_result_without_descriptions() -> Value {
                           "ofType": null
                         }
                       },
-                      "defaultValue": null
+                      "defaultValue": null,
+                      "isDeprecated": false,
+                      "deprecationReason": null
                     }
                   ],
                   "type": {
@@ -2228,7 +2383,9 @@ pub(crate) fn schema_introspection_result_without_descriptions() -> Value {
                           "ofType": null
                         }
                       },
-                      "defaultValue": null
+                      "defaultValue": null,
+                      "isDeprecated": false,
+                      "deprecationReason": null
                     }
                   ],
                   "type": {
@@ -2249,7 +2406,9 @@ pub(crate) fn schema_introspection_result_without_descriptions() -> Value {
                         "name": "Episode",
                         "ofType": null
                       },
-                      "defaultValue": null
+                      "defaultValue": null,
+                      "isDeprecated": false,
+                      "deprecationReason": null
                     }
                   ],
                   "type": {
@@ -2417,6 +2576,16 @@ pub(crate) fn schema_introspection_result_without_descriptions() -> Value {
                   "name": "ENUM_VALUE",
                   "isDeprecated": false,
                   "deprecationReason": null
+                },
+                {
+                  "name": "ARGUMENT_DEFINITION",
+                  "isDeprecated": false,
+                  "deprecationReason": null
+                },
+                {
+                  "name": "INPUT_FIELD_DEFINITION",
+                  "isDeprecated": false,
+                  "deprecationReason": null
                 }
               ],
               "possibleTypes": null
@@ -2586,7 +2755,19 @@ pub(crate) fn schema_introspection_result_without_descriptions() -> Value {
                 },
                 {
                   "name": "args",
-                  "args": [],
+                  "args": [
+                    {
+                      "name": "includeDeprecated",
+                      "type": {
+                        "kind": "SCALAR",
+                        "name": "Boolean",
+                        "ofType": null
+                      },
+                      "defaultValue": "false",
+                      "isDeprecated": false,
+                      "deprecationReason": null
+                    }
+                  ],
                   "type": {
                     "kind": "NON_NULL",
                     "name": null,
@@ -2680,7 +2861,9 @@ pub(crate) fn schema_introspection_result_without_descriptions() -> Value {
                       "ofType": null
                     }
                   },
-                  "defaultValue": null
+                  "defaultValue": null,
+                  "isDeprecated": false,
+                  "deprecationReason": null
                 }
               ]
             },
@@ -2704,7 +2887,9 @@ pub(crate) fn schema_introspection_result_without_descriptions() -> Value {
                       "ofType": null
                     }
                   },
-                  "defaultValue": null
+                  "defaultValue": null,
+                  "isDeprecated": false,
+                  "deprecationReason": null
                 }
               ]
             },
@@ -2713,6 +2898,8 @@ pub(crate) fn schema_introspection_result_without_descriptions() -> Value {
               "isRepeatable": false,
               "locations": [
                 "FIELD_DEFINITION",
+                "ARGUMENT_DEFINITION",
+                "INPUT_FIELD_DEFINITION",
                 "ENUM_VALUE"
               ],
               "args": [
@@ -2727,7 +2914,9 @@ pub(crate) fn schema_introspection_result_without_descriptions() -> Value {
                       "ofType": null
                     }
                   },
-                  "defaultValue": null
+                  "defaultValue": null,
+                  "isDeprecated": false,
+                  "deprecationReason": null
                 }
               ]
             },
@@ -2749,7 +2938,9 @@ pub(crate) fn schema_introspection_result_without_descriptions() -> Value {
                       "ofType": null
                     }
                   },
-                  "defaultValue": null
+                  "defaultValue": null,
+                  "isDeprecated": false,
+                  "deprecationReason": null
                 }
               ]
             }
@@ -86,7 +86,7 @@ impl MySubscription {
 
 /// Create all variables, execute subscription
 /// and collect returned iterators.
-/// Panics if query is invalid (GraphQLError is returned)
+/// Panics if query is invalid (`RequestError` is returned)
 fn create_and_execute(
     query: String,
 ) -> Result<
@@ -91,7 +91,8 @@ fn test_node() {
         node_info,
         (),
         (),
-    );
+    )
+    .unwrap();
 
     assert_eq!(
         crate::execute_sync(doc, None, &schema, &graphql_vars! {}, &()),
@@ -1,13 +1,24 @@
+use std::{iter, mem, sync::RwLock};
+
+use futures::future::{self, FusedFuture as _, FutureExt as _};
+
 use crate::{
-    ast::Selection,
-    executor::{ExecutionResult, Executor},
-    parser::Spanning,
+    ast::{Directive, Selection},
+    executor::{
+        resolve_known, resolve_until, DeferredPayload, EarlyValue, ExecutionError, ExecutionResult,
+        Executor, FieldError, IncrementalPayload, Known, KnownValues, ParentValue, PathSegment,
+        Reservation, StreamArgs, StreamedPayload, SubsequentPayloads,
+    },
+    parser::{SourcePosition, Spanning},
     value::{DefaultScalarValue, Object, ScalarValue, Value},
 };
 
 use crate::BoxFuture;
 
-use super::base::{is_excluded, merge_key_into, Arguments, GraphQLType, GraphQLValue};
+use super::base::{
+    apply_directives, deferred_label, is_excluded, merge_key_into, stream_args, Arguments,
+    GraphQLType, GraphQLValue,
+};
 
 /// Extension of [`GraphQLValue`] trait with asynchronous queries/mutations resolvers.
 ///
@@ -155,12 +166,175 @@ where
     S: ScalarValue + Send + Sync,
     'e: 'a,
 {
-    Box::pin(resolve_selection_set_into_async_recursive(
-        instance,
-        info,
-        selection_set,
-        executor,
-    ))
+    executor
+        .schema()
+        .executor_options()
+        .maybe_grow_future(Box::pin(resolve_selection_set_into_async_recursive(
+            instance,
+            info,
+            selection_set,
+            executor,
+        )))
+}
+
+/// Fragment deferred with the `@defer` directive, being resolved into its own
+/// [`DeferredPayload`] instead of the object of its parent.
+struct Deferral<'d, S> {
+    reservation: Reservation<'d, S>,
+    label: Option<String>,
+    errors: RwLock<Vec<ExecutionError<S>>>,
+}
+
+impl<'d, S> Deferral<'d, S>
+where
+    S: ScalarValue + Send + Sync,
+{
+    /// Starts deferring the fragment with the given `directives`, if it's
+    /// marked with the `@defer` directive and its operation is delivered
+    /// incrementally.
+    fn start<CtxT>(
+        directives: &Option<Vec<Spanning<Directive<S>>>>,
+        executor: &'d Executor<'_, '_, CtxT, S>,
+    ) -> Option<Self> {
+        let payloads = executor.subsequent_payloads()?;
+        let label = deferred_label(directives, executor.variables())?;
+        Some(Self {
+            reservation: payloads.reserve(executor.subsequent_payload()),
+            label,
+            errors: RwLock::new(Vec::new()),
+        })
+    }
+
+    /// Makes the given `executor` resolve the deferred fragment, publishing its
+    /// data into the `early` slot as soon as it's known.
+    fn executor<'r, 'a, CtxT>(
+        &'r self,
+        executor: Executor<'r, 'a, CtxT, S>,
+        early: &'r EarlyValue<S>,
+    ) -> Executor<'r, 'a, CtxT, S> {
+        executor
+            .with_errors(&self.errors)
+            .with_early_value(early)
+            .with_subsequent_payload(self.reservation.id())
+    }
+
+    /// Finishes the deferred fragment with its `known` data, delivering it as
+    /// a [`DeferredPayload`].
+    fn finish<CtxT>(
+        &self,
+        known: Known<ExecutionResult<S>, S>,
+        executor: &Executor<'_, '_, CtxT, S>,
+        pos: SourcePosition,
+    ) {
+        let data = match known {
+            Known::Finished(result) => result.unwrap_or_else(|e| {
+                executor.push_error_at(e, pos);
+                Value::null()
+            }),
+            Known::Early(data) => data,
+        };
+        let mut errors = mem::take(&mut *self.errors.write().unwrap());
+        errors.sort();
+        self.reservation
+            .deliver(iter::once(IncrementalPayload::Deferred(DeferredPayload {
+                data,
+                path: executor.current_path(),
+                label: self.label.clone(),
+                errors,
+            })));
+    }
+}
+
+/// List field streamed with the `@stream` directive, having the items following
+/// its first `initialCount` ones resolved into their own [`StreamedPayload`]s
+/// instead of the list.
+struct Streaming<'s, S> {
+    payloads: &'s SubsequentPayloads<S>,
+    args: StreamArgs,
+    errors: RwLock<Vec<ExecutionError<S>>>,
+}
+
+impl<'s, S> Streaming<'s, S>
+where
+    S: ScalarValue + Send + Sync,
+{
+    /// Starts streaming the field with the given `directives`, if it's marked
+    /// with the `@stream` directive and its operation is delivered
+    /// incrementally.
+    ///
+    /// Errors if the arguments of the `@stream` directive are invalid.
+    fn start<CtxT>(
+        directives: &Option<Vec<Spanning<Directive<S>>>>,
+        executor: &'s Executor<'_, '_, CtxT, S>,
+    ) -> Option<Result<Self, FieldError<S>>> {
+        let payloads = executor.subsequent_payloads()?;
+        let args = stream_args(directives, executor.variables())?;
+        Some(args.map(|args| Self {
+            payloads,
+            args,
+            errors: RwLock::new(Vec::new()),
+        }))
+    }
+
+    /// Finishes the streamed field with its known `value`, reporting the
+    /// errors of its first `initialCount` items via the `parent` executor.
+    ///
+    /// The rest of the items are streamed by the list resolution already,
+    /// unless the field is resolved into a list by other means, so they're
+    /// split off it into [`StreamedPayload`]s along with their errors here.
+    /// Nothing is streamed if the `value` is not a list, as it's been nulled.
+    fn finish<CtxT>(
+        &self,
+        value: &mut Option<Value<S>>,
+        executor: &Executor<'_, '_, CtxT, S>,
+        parent: &Executor<'_, '_, CtxT, S>,
+    ) {
+        let initial_count = self.args.initial_count;
+        let errors = mem::take(&mut *self.errors.write().unwrap());
+        let items = match value {
+            Some(Value::List(list)) if list.len() > initial_count => list.split_off(initial_count),
+            _ => return parent.push_errors(errors),
+        };
+
+        let path = executor.current_path();
+        let mut item_errors = iter::repeat_with(Vec::new)
+            .take(items.len())
+            .collect::<Vec<_>>();
+        let mut rest = Vec::new();
+        for e in errors {
+            let item = match e.path().strip_prefix(&path[..]) {
+                Some([PathSegment::Index(i), ..]) if *i >= initial_count => {
+                    Some(*i - initial_count)
+                }
+                _ => None,
+            };
+            match item {
+                Some(i) => item_errors[i].push(e),
+                None => rest.push(e),
+            }
+        }
+        parent.push_errors(rest);
+
+        let payloads =
+            items
+                .into_iter()
+                .zip(item_errors)
+                .enumerate()
+                .map(|(i, (item, mut errors))| {
+                    errors.sort();
+                    let mut path = path.clone();
+                    path.push(PathSegment::Index(initial_count + i));
+                    IncrementalPayload::Streamed(StreamedPayload {
+                        items: vec![item],
+                        path,
+                        label: self.args.label.clone(),
+                        errors,
+                    })
+                });
+        self.payloads
+            .reserve(executor.subsequent_payload())
+            .deliver(payloads);
+    }
 }
 
 struct AsyncField<S> {
@@ -170,6 +344,7 @@ struct AsyncField<S> {
 
 enum AsyncValue<S> {
     Field(AsyncField<S>),
+    Fragment(Object<S>),
     Nested(Value<S>),
 }
 
@@ -185,8 +360,6 @@ where
     T::Context: Sync,
     S: ScalarValue + Send + Sync,
 {
-    use futures::stream::{FuturesOrdered, StreamExt as _};
-
     #[derive(futures_enum::Future)]
     enum AsyncValueFuture<A, B, C, D> {
         Field(A),
@@ -195,9 +368,7 @@ where
         InlineFragment2(D),
     }
 
-    let mut object = Object::with_capacity(selection_set.len());
-
-    let mut async_values = FuturesOrdered::<AsyncValueFuture<_, _, _, _>>::new();
+    let mut object = executor.object_pool().object(selection_set.len());
 
     let meta_type = executor
         .schema()
@@ -208,6 +379,19 @@ where
                 .as_ref(),
         )
         .expect("Type not found in schema");
+    let parent = ParentValue::new(
+        meta_type.name().unwrap_or_default(),
+        instance.as_any(),
+        executor.parent_value(),
+    );
+
+    // In the operations delivered incrementally, the values of the selections
+    // are known as soon as they're published, while the deferred fragments and
+    // the streamed list items nested inside them are still being resolved.
+    let incremental = executor.subsequent_payloads().is_some();
+    let known = KnownValues::default();
+    let mut async_values = Vec::<AsyncValueFuture<_, _, _, _>>::new();
+    let mut deferred = Vec::<BoxFuture<'_, ()>>::new();
 
     for selection in selection_set {
         match *selection {
@@ -240,12 +424,14 @@ where
 
                 let exec_vars = executor.variables();
 
-                let sub_exec = executor.field_sub_executor(
-                    response_name,
-                    f.name.item,
-                    *start_pos,
-                    f.selection_set.as_ref().map(|v| &v[..]),
-                );
+                let sub_exec = executor
+                    .field_sub_executor(
+                        response_name,
+                        f.name.item,
+                        *start_pos,
+                        f.selection_set.as_ref().map(|v| &v[..]),
+                    )
+                    .with_parent(&parent);
                 let args = Arguments::new(
                     f.arguments.as_ref().map(|m| {
                         m.item
@@ -253,37 +439,82 @@ where
                             .map(|&(ref k, ref v)| (k.item, v.item.clone().into_const(exec_vars)))
                             .collect()
                     }),
-                    &meta_field.arguments,
+                    meta_field.arguments.as_deref(),
                 );
 
                 let pos = *start_pos;
                 let is_non_null = meta_field.field_type.is_non_null();
 
                 let response_name = response_name.to_string();
+                let streaming = Streaming::start(&f.directives, executor);
+                let (i, known) = (known.reserve(), &known);
                 async_values.push(AsyncValueFuture::Field(async move {
+                    let (streaming, invalid_stream) = match streaming {
+                        Some(Ok(s)) => (Some(s), None),
+                        Some(Err(e)) => (None, Some(e)),
+                        None => (None, None),
+                    };
+                    let sub_exec = match &streaming {
+                        Some(s) => sub_exec.with_errors(&s.errors),
+                        None => sub_exec,
+                    };
+                    let early = incremental
+                        .then(|| EarlyValue::new(streaming.as_ref().map(|s| s.args.clone())));
+                    let sub_exec = match &early {
+                        Some(early) => sub_exec.with_early_value(early),
+                        None => sub_exec,
+                    };
+
                     // TODO: implement custom future type instead of
                     //       two-level boxing.
-                    let res = instance
-                        .resolve_field_async(info, f.name.item, &args, &sub_exec)
-                        .await;
-
-                    let value = match res {
-                        Ok(Value::Null) if is_non_null => None,
-                        Ok(v) => Some(v),
-                        Err(e) => {
-                            sub_exec.push_error_at(e, pos);
-
-                            if is_non_null {
-                                None
-                            } else {
-                                Some(Value::null())
-                            }
+                    sub_exec.count_field_resolution();
+                    let started = sub_exec.start_field_timer();
+                    let resolution = async {
+                        if sub_exec.is_timed_out() {
+                            Err(FieldError::from("Operation timed out"))
+                        } else if let Err(e) = sub_exec.spend_work(1) {
+                            Err(e)
+                        } else if let Some(e) = invalid_stream {
+                            Err(e)
+                        } else {
+                            instance
+                                .resolve_field_async(info, f.name.item, &args, &sub_exec)
+                                .await
                         }
                     };
-                    AsyncValue::Field(AsyncField {
-                        name: response_name,
-                        value,
+                    resolve_known(early.as_ref(), resolution, |res| {
+                        let res = match res {
+                            Known::Finished(res) => res,
+                            Known::Early(v) => Ok(v),
+                        }
+                        .and_then(|v| apply_directives(&f.directives, v, &sub_exec));
+                        sub_exec.record_field_timer(started);
+
+                        let mut value = match res {
+                            Ok(Value::Null) if is_non_null => None,
+                            Ok(v) => Some(v),
+                            Err(e) => {
+                                sub_exec.push_error_at(e, pos);
+
+                                if is_non_null {
+                                    None
+                                } else {
+                                    Some(Value::null())
+                                }
+                            }
+                        };
+                        if let Some(s) = &streaming {
+                            s.finish(&mut value, &sub_exec, executor);
+                        }
+                        known.set(
+                            i,
+                            AsyncValue::Field(AsyncField {
+                                name: response_name,
+                                value,
+                            }),
+                        );
                     })
+                    .await;
                 }));
             }
 
@@ -296,7 +527,7 @@ where
                     continue;
                 }
 
-                let fragment = &executor
+                let fragment = executor
                     .fragment_by_name(spread.name.item)
                     .expect("Fragment could not be found");
 
@@ -312,27 +543,43 @@ where
                     .is_named_subtype(&concrete_type_name, fragment.type_condition.item)
                     || Some(fragment.type_condition.item) == type_name
                 {
-                    let sub_result = instance
-                        .resolve_into_type_async(
+                    let pos = *start_pos;
+                    if let Some(d) = Deferral::start(&spread.directives, executor) {
+                        deferred.push(Box::pin(async move {
+                            let early = EarlyValue::new(None);
+                            let sub_exec = d.executor(sub_exec, &early);
+                            let resolution = instance.resolve_into_type_async(
+                                info,
+                                &concrete_type_name,
+                                Some(&fragment.selection_set[..]),
+                                &sub_exec,
+                            );
+                            resolve_known(Some(&early), resolution, |res| {
+                                d.finish(res, &sub_exec, pos)
+                            })
+                            .await;
+                        }));
+                        continue;
+                    }
+
+                    let (i, known) = (known.reserve(), &known);
+                    async_values.push(AsyncValueFuture::FragmentSpread(async move {
+                        let early = incremental.then(|| EarlyValue::new(None));
+                        let sub_exec = match &early {
+                            Some(early) => sub_exec.with_early_value(early),
+                            None => sub_exec,
+                        };
+                        let resolution = instance.resolve_into_type_async(
                             info,
                             &concrete_type_name,
                             Some(&fragment.selection_set[..]),
                             &sub_exec,
-                        )
+                        );
+                        resolve_known(early.as_ref(), resolution, |res| {
+                            known.set(i, fragment_fields(res, &sub_exec, pos))
+                        })
                         .await;
-
-                    if let Ok(Value::Object(obj)) = sub_result {
-                        for (k, v) in obj {
-                            async_values.push(AsyncValueFuture::FragmentSpread(async move {
-                                AsyncValue::Field(AsyncField {
-                                    name: k,
-                                    value: Some(v),
-                                })
-                            }));
-                        }
-                    } else if let Err(e) = sub_result {
-                        sub_exec.push_error_at(e, *start_pos);
-                    }
+                    }));
                 }
             }
 
@@ -349,6 +596,7 @@ where
                     fragment.type_condition.as_ref().map(|c| c.item),
                     Some(&fragment.selection_set[..]),
                 );
+                let pos = *start_pos;
 
                 if let Some(ref type_condition) = fragment.type_condition {
                     // Check whether the type matches the type condition.
@@ -357,66 +605,163 @@ where
                         .schema()
                         .is_named_subtype(&concrete_type_name, type_condition.item)
                     {
-                        let sub_result = instance
-                            .resolve_into_type_async(
+                        if let Some(d) = Deferral::start(&fragment.directives, executor) {
+                            deferred.push(Box::pin(async move {
+                                let early = EarlyValue::new(None);
+                                let sub_exec = d.executor(sub_exec, &early);
+                                let resolution = instance.resolve_into_type_async(
+                                    info,
+                                    &concrete_type_name,
+                                    Some(&fragment.selection_set[..]),
+                                    &sub_exec,
+                                );
+                                resolve_known(Some(&early), resolution, |res| {
+                                    d.finish(res, &sub_exec, pos)
+                                })
+                                .await;
+                            }));
+                            continue;
+                        }
+
+                        let (i, known) = (known.reserve(), &known);
+                        async_values.push(AsyncValueFuture::InlineFragment1(async move {
+                            let early = incremental.then(|| EarlyValue::new(None));
+                            let sub_exec = match &early {
+                                Some(early) => sub_exec.with_early_value(early),
+                                None => sub_exec,
+                            };
+                            let resolution = instance.resolve_into_type_async(
                                 info,
                                 &concrete_type_name,
                                 Some(&fragment.selection_set[..]),
                                 &sub_exec,
-                            )
+                            );
+                            resolve_known(early.as_ref(), resolution, |res| {
+                                known.set(i, fragment_fields(res, &sub_exec, pos))
+                            })
                             .await;
-
-                        if let Ok(Value::Object(obj)) = sub_result {
-                            for (k, v) in obj {
-                                async_values.push(AsyncValueFuture::InlineFragment1(async move {
-                                    AsyncValue::Field(AsyncField {
-                                        name: k,
-                                        value: Some(v),
-                                    })
-                                }));
-                            }
-                        } else if let Err(e) = sub_result {
-                            sub_exec.push_error_at(e, *start_pos);
-                        }
+                        }));
                     }
+                } else if let Some(d) = Deferral::start(&fragment.directives, executor) {
+                    deferred.push(Box::pin(async move {
+                        let early = EarlyValue::new(None);
+                        let sub_exec = d.executor(sub_exec, &early);
+                        let resolution = resolve_selection_set_into_async(
+                            instance,
+                            info,
+                            &fragment.selection_set[..],
+                            &sub_exec,
+                        );
+                        resolve_known(Some(&early), resolution, |value| {
+                            let value = match value {
+                                Known::Finished(v) | Known::Early(v) => v,
+                            };
+                            d.finish(Known::Finished(Ok(value)), &sub_exec, pos)
+                        })
+                        .await;
+                    }));
                 } else {
+                    let (i, known) = (known.reserve(), &known);
                     async_values.push(AsyncValueFuture::InlineFragment2(async move {
-                        let value = resolve_selection_set_into_async(
+                        let early = incremental.then(|| EarlyValue::new(None));
+                        let sub_exec = match &early {
+                            Some(early) => sub_exec.with_early_value(early),
+                            None => sub_exec,
+                        };
+                        let resolution = resolve_selection_set_into_async(
                             instance,
                             info,
                             &fragment.selection_set[..],
                             &sub_exec,
-                        )
+                        );
+                        resolve_known(early.as_ref(), resolution, |value| {
+                            let value = match value {
+                                Known::Finished(v) | Known::Early(v) => v,
+                            };
+                            known.set(i, AsyncValue::Nested(value))
+                        })
                         .await;
-                        AsyncValue::Nested(value)
                     }));
                 }
             }
         }
     }
 
-    while let Some(item) = async_values.next().await {
+    let fields = async_values
+        .into_iter()
+        .map(|fut| -> BoxFuture<'_, ()> { Box::pin(fut) })
+        .collect();
+    let mut resolution = executor.strategy().resolve_fields(fields).fuse();
+    let values = resolve_until(&mut resolution, || known.take_all()).await;
+
+    for item in values {
         match item {
             AsyncValue::Field(AsyncField { name, value }) => {
                 if let Some(value) = value {
-                    merge_key_into(&mut object, &name, value);
+                    merge_key_into(&mut object, name, value);
                 } else {
+                    executor.object_pool().recycle(object);
                     return Value::null();
                 }
             }
+            AsyncValue::Fragment(mut obj) => {
+                for (k, v) in obj.drain() {
+                    merge_key_into(&mut object, k, v);
+                }
+                executor.object_pool().recycle(obj);
+            }
             AsyncValue::Nested(obj) => match obj {
                 v @ Value::Null => {
+                    executor.object_pool().recycle(object);
                     return v;
                 }
-                Value::Object(obj) => {
-                    for (k, v) in obj {
-                        merge_key_into(&mut object, &k, v);
+                Value::Object(mut obj) => {
+                    for (k, v) in obj.drain() {
+                        merge_key_into(&mut object, k, v);
                     }
+                    executor.object_pool().recycle(obj);
                 }
                 _ => unreachable!(),
             },
         }
     }
+    let value = Value::Object(object);
 
-    Value::Object(object)
+    // The deferred fragments are resolved once the rest of the object is
+    // known, along with the rest of its selections.
+    if !resolution.is_terminated() || !deferred.is_empty() {
+        if let Some(early) = executor.early_value() {
+            early.publish(value.clone());
+        }
+        let rest = async move {
+            if !resolution.is_terminated() {
+                resolution.await;
+            }
+        };
+        future::join(rest, future::join_all(deferred)).await;
+    }
+
+    value
+}
+
+/// Collects the fields of a fragment from its `known` result, reporting the
+/// error it's failed with, if any.
+fn fragment_fields<CtxT, S>(
+    known: Known<ExecutionResult<S>, S>,
+    executor: &Executor<'_, '_, CtxT, S>,
+    pos: SourcePosition,
+) -> AsyncValue<S>
+where
+    S: ScalarValue,
+{
+    match known {
+        Known::Finished(Ok(Value::Object(obj))) | Known::Early(Value::Object(obj)) => {
+            AsyncValue::Fragment(obj)
+        }
+        Known::Finished(Err(e)) => {
+            executor.push_error_at(e, pos);
+            AsyncValue::Fragment(Object::with_capacity(0))
+        }
+        Known::Finished(Ok(_)) | Known::Early(_) => AsyncValue::Fragment(Object::with_capacity(0)),
+    }
 }
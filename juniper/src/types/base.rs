@@ -1,12 +1,17 @@
+use std::{any::Any, convert::TryFrom as _, mem};
+
 use indexmap::IndexMap;
 
 use crate::{
-    ast::{Directive, FromInputValue, InputValue, Selection},
-    executor::{ExecutionResult, Executor, Registry, Variables},
+    ast::{self, Directive, FromInputValue, InputValue, Selection},
+    executor::{ExecutionResult, Executor, ParentValue, Registry, StreamArgs, Variables},
     parser::Spanning,
-    schema::meta::{Argument, MetaType},
+    schema::{
+        meta::{Argument, MetaType},
+        model::DirectiveType,
+    },
     value::{DefaultScalarValue, Object, ScalarValue, Value},
-    FieldResult, GraphQLEnum, IntoFieldError,
+    FieldError, FieldResult, GraphQLEnum, IntoFieldError,
 };
 
 /// GraphQL type kind
@@ -77,7 +82,7 @@ impl<'a, S> Arguments<'a, S> {
     #[doc(hidden)]
     pub fn new(
         mut args: Option<IndexMap<&'a str, InputValue<S>>>,
-        meta_args: &'a Option<Vec<Argument<S>>>,
+        meta_args: Option<&'a [Argument<S>]>,
     ) -> Self
     where
         S: Clone,
@@ -89,7 +94,7 @@ impl<'a, S> Arguments<'a, S> {
         if let (Some(args), Some(meta_args)) = (&mut args, meta_args) {
             for arg in meta_args {
                 let arg_name = arg.name.as_str();
-                if args.get(arg_name).map_or(true, InputValue::is_null) {
+                if args.get(arg_name).is_none_or(InputValue::is_null) {
                     if let Some(val) = arg.default_value.as_ref() {
                         args.insert(arg_name, val.clone());
                     }
@@ -100,6 +105,31 @@ impl<'a, S> Arguments<'a, S> {
         Self { args }
     }
 
+    /// Creates [`Arguments`] out of the provided `args`, the same way as
+    /// [`Arguments::new()`] does, but reusing the given emptied `buffer`.
+    pub(crate) fn new_in(
+        mut buffer: IndexMap<&'a str, InputValue<S>>,
+        args: Option<&'a ast::Arguments<'a, S>>,
+        meta_args: Option<&'a [Argument<S>]>,
+        vars: &Variables<S>,
+    ) -> Self
+    where
+        S: Clone,
+    {
+        for (k, v) in args.into_iter().flat_map(ast::Arguments::iter) {
+            buffer.insert(k.item, v.item.clone().into_const(vars));
+        }
+        Self::new(Some(buffer), meta_args)
+    }
+
+    /// Empties these [`Arguments`] to reuse their buffer for the arguments of
+    /// another field.
+    pub(crate) fn into_buffer(self) -> IndexMap<&'a str, InputValue<S>> {
+        let mut args = self.args.unwrap_or_default();
+        args.clear();
+        args
+    }
+
     /// Gets an argument by the given `name` and converts it into the desired
     /// type.
     ///
@@ -123,6 +153,23 @@ impl<'a, S> Arguments<'a, S> {
             .transpose()
             .map_err(IntoFieldError::into_field_error)
     }
+
+    /// Converts all the arguments into the desired type `T`, as if they were
+    /// fields of a single [`InputValue::Object`].
+    ///
+    /// # Errors
+    ///
+    /// If the [`FromInputValue`] conversion fails.
+    pub fn get_all<T>(&self) -> FieldResult<T, S>
+    where
+        T: FromInputValue<S>,
+        T::Error: IntoFieldError<S>,
+        S: Clone,
+    {
+        InputValue::object(self.args.clone().unwrap_or_default())
+            .convert()
+            .map_err(IntoFieldError::into_field_error)
+    }
 }
 
 /// Primary trait used to resolve GraphQL values.
@@ -254,6 +301,17 @@ where
         );
     }
 
+    /// Returns this [`GraphQLValue`] (being an [object][3]) as [`Any`], so it may
+    /// be accessed by the resolvers of its descendant fields via
+    /// [`Executor::parent()`].
+    ///
+    /// The default implementation returns [`None`].
+    ///
+    /// [3]: https://spec.graphql.org/June2018/#sec-Objects
+    fn as_any(&self) -> Option<&(dyn Any + Sync)> {
+        None
+    }
+
     /// Resolves the provided `selection_set` against this [`GraphQLValue`].
     ///
     /// For non-[object][3] types, the `selection_set` will be [`None`] and the value should simply
@@ -280,11 +338,12 @@ where
         executor: &Executor<Self::Context, S>,
     ) -> ExecutionResult<S> {
         if let Some(sel) = selection_set {
-            let mut res = Object::with_capacity(sel.len());
+            let mut res = executor.object_pool().object(sel.len());
             Ok(
                 if resolve_selection_set_into(self, info, sel, executor, &mut res) {
                     Value::Object(res)
                 } else {
+                    executor.object_pool().recycle(res);
                     Value::null()
                 },
             )
@@ -423,6 +482,24 @@ pub(crate) fn resolve_selection_set_into<T, S>(
     executor: &Executor<T::Context, S>,
     result: &mut Object<S>,
 ) -> bool
+where
+    T: GraphQLValue<S> + ?Sized,
+    S: ScalarValue,
+{
+    executor.schema().executor_options().maybe_grow(|| {
+        resolve_selection_set_into_recursive(instance, info, selection_set, executor, result)
+    })
+}
+
+// Resolves the selection set, being wrapped by `resolve_selection_set_into()`
+// growing the stack if required.
+fn resolve_selection_set_into_recursive<T, S>(
+    instance: &T,
+    info: &T::TypeInfo,
+    selection_set: &[Selection<S>],
+    executor: &Executor<T::Context, S>,
+    result: &mut Object<S>,
+) -> bool
 where
     T: GraphQLValue<S> + ?Sized,
     S: ScalarValue,
@@ -436,6 +513,14 @@ where
                 .as_ref(),
         )
         .expect("Type not found in schema");
+    let parent = ParentValue::new(
+        meta_type.name().unwrap_or_default(),
+        instance.as_any(),
+        executor.parent_value(),
+    );
+    // Arguments of the resolved fields are dropped once the field is resolved,
+    // so the same buffer is reused for all of them.
+    let mut args_buffer = IndexMap::new();
 
     for selection in selection_set {
         match *selection {
@@ -468,29 +553,34 @@ where
 
                 let exec_vars = executor.variables();
 
-                let sub_exec = executor.field_sub_executor(
-                    response_name,
-                    f.name.item,
-                    *start_pos,
-                    f.selection_set.as_ref().map(|v| &v[..]),
-                );
-
-                let field_result = instance.resolve_field(
-                    info,
-                    f.name.item,
-                    &Arguments::new(
-                        f.arguments.as_ref().map(|m| {
-                            m.item
-                                .iter()
-                                .map(|&(ref k, ref v)| {
-                                    (k.item, v.item.clone().into_const(exec_vars))
-                                })
-                                .collect()
-                        }),
-                        &meta_field.arguments,
-                    ),
-                    &sub_exec,
-                );
+                let sub_exec = executor
+                    .field_sub_executor(
+                        response_name,
+                        f.name.item,
+                        *start_pos,
+                        f.selection_set.as_ref().map(|v| &v[..]),
+                    )
+                    .with_parent(&parent);
+
+                sub_exec.count_field_resolution();
+                let started = sub_exec.start_field_timer();
+                let field_result = if sub_exec.is_timed_out() {
+                    Err(FieldError::from("Operation timed out"))
+                } else if let Err(e) = sub_exec.spend_work(1) {
+                    Err(e)
+                } else {
+                    let args = Arguments::new_in(
+                        mem::take(&mut args_buffer),
+                        f.arguments.as_ref().map(|m| &m.item),
+                        meta_field.arguments.as_deref(),
+                        exec_vars,
+                    );
+                    let res = instance.resolve_field(info, f.name.item, &args, &sub_exec);
+                    args_buffer = args.into_buffer();
+                    res
+                }
+                .and_then(|v| apply_directives(&f.directives, v, &sub_exec));
+                sub_exec.record_field_timer(started);
 
                 match field_result {
                     Ok(Value::Null) if meta_field.field_type.is_non_null() => return false,
@@ -538,10 +628,11 @@ where
                         &sub_exec,
                     );
 
-                    if let Ok(Value::Object(object)) = sub_result {
-                        for (k, v) in object {
-                            merge_key_into(result, &k, v);
+                    if let Ok(Value::Object(mut object)) = sub_result {
+                        for (k, v) in object.drain() {
+                            merge_key_into(result, k, v);
                         }
+                        executor.object_pool().recycle(object);
                     } else if let Err(e) = sub_result {
                         sub_exec.push_error_at(e, *start_pos);
                     }
@@ -575,10 +666,11 @@ where
                             &sub_exec,
                         );
 
-                        if let Ok(Value::Object(object)) = sub_result {
-                            for (k, v) in object {
-                                merge_key_into(result, &k, v);
+                        if let Ok(Value::Object(mut object)) = sub_result {
+                            for (k, v) in object.drain() {
+                                merge_key_into(result, k, v);
                             }
+                            executor.object_pool().recycle(object);
                         } else if let Err(e) = sub_result {
                             sub_exec.push_error_at(e, *start_pos);
                         }
@@ -612,6 +704,12 @@ where
             ..
         } in directives
         {
+            let skip_if = match directive.name.item {
+                "skip" => true,
+                "include" => false,
+                _ => continue,
+            };
+
             let condition: bool = directive
                 .arguments
                 .iter()
@@ -620,9 +718,7 @@ where
                 .next()
                 .unwrap();
 
-            if (directive.name.item == "skip" && condition)
-                || (directive.name.item == "include" && !condition)
-            {
+            if condition == skip_if {
                 return true;
             }
         }
@@ -630,9 +726,130 @@ where
     false
 }
 
+/// Returns the `label` of the `@defer` directive among the given `directives`,
+/// if the fragment they're placed on is deferred.
+///
+/// The outer [`Option`] is [`None`] if the fragment is not deferred, and the
+/// inner one is [`None`] if the directive has no `label`.
+pub(super) fn deferred_label<S>(
+    directives: &Option<Vec<Spanning<Directive<S>>>>,
+    vars: &Variables<S>,
+) -> Option<Option<String>>
+where
+    S: ScalarValue,
+{
+    let directive = &directives
+        .as_ref()?
+        .iter()
+        .find(|d| d.item.name.item == "defer")?
+        .item;
+    let argument = |name| {
+        directive
+            .arguments
+            .iter()
+            .flat_map(|m| m.item.get(name))
+            .map(|v| v.item.clone().into_const(vars))
+            .next()
+    };
+
+    let condition = argument("if")
+        .and_then(|v| v.convert::<bool>().ok())
+        .unwrap_or(true);
+    condition.then(|| argument("label").and_then(|v| v.convert().ok()))
+}
+
+/// Returns the [`StreamArgs`] of the `@stream` directive among the given
+/// `directives`, if the list field they're placed on is streamed.
+///
+/// Errors if the `initialCount` argument is negative, as it may be provided by
+/// a variable, so isn't checked during validation.
+pub(super) fn stream_args<S>(
+    directives: &Option<Vec<Spanning<Directive<S>>>>,
+    vars: &Variables<S>,
+) -> Option<Result<StreamArgs, FieldError<S>>>
+where
+    S: ScalarValue,
+{
+    let directive = &directives
+        .as_ref()?
+        .iter()
+        .find(|d| d.item.name.item == "stream")?
+        .item;
+    let argument = |name| {
+        directive
+            .arguments
+            .iter()
+            .flat_map(|m| m.item.get(name))
+            .map(|v| v.item.clone().into_const(vars))
+            .next()
+    };
+
+    let condition = argument("if")
+        .and_then(|v| v.convert::<bool>().ok())
+        .unwrap_or(true);
+    let initial_count = argument("initialCount")
+        .and_then(|v| v.convert::<i32>().ok())
+        .unwrap_or(0);
+    condition.then(|| {
+        let initial_count = usize::try_from(initial_count).map_err(|_| {
+            FieldError::from(format!(
+                "`initialCount` argument of `@stream` directive must be non-negative, got: {}",
+                initial_count,
+            ))
+        })?;
+        Ok(StreamArgs {
+            label: argument("label").and_then(|v| v.convert().ok()),
+            initial_count,
+        })
+    })
+}
+
+/// Applies custom executable directives, registered in the schema via
+/// [`SchemaType::register_directive()`], to the resolved `value` of a field.
+///
+/// [`SchemaType::register_directive()`]: crate::SchemaType::register_directive
+pub(super) fn apply_directives<CtxT, S>(
+    directives: &Option<Vec<Spanning<Directive<S>>>>,
+    value: Value<S>,
+    executor: &Executor<CtxT, S>,
+) -> FieldResult<Value<S>, S>
+where
+    S: ScalarValue,
+{
+    let directives = match directives {
+        Some(directives) => directives,
+        None => return Ok(value),
+    };
+    directives.iter().try_fold(value, |value, directive| {
+        let directive = &directive.item;
+        match executor.schema().directive_by_name(directive.name.item) {
+            Some(&DirectiveType {
+                resolve_fn: Some(resolve_fn),
+                ref arguments,
+                ..
+            }) => {
+                let args = Arguments::new(
+                    directive.arguments.as_ref().map(|m| {
+                        m.item
+                            .iter()
+                            .map(|(k, v)| (k.item, v.item.clone().into_const(executor.variables())))
+                            .collect()
+                    }),
+                    Some(arguments),
+                );
+                resolve_fn(&args, value)
+            }
+            _ => Ok(value),
+        }
+    })
+}
+
 /// Merges `response_name`/`value` pair into `result`
-pub(crate) fn merge_key_into<S>(result: &mut Object<S>, response_name: &str, value: Value<S>) {
-    if let Some(v) = result.get_mut_field_value(response_name) {
+pub(crate) fn merge_key_into<K, S>(result: &mut Object<S>, response_name: K, value: Value<S>)
+where
+    K: AsRef<str> + Into<String>,
+{
+    if let Some(v) = result.get_mut_field_value(response_name.as_ref()) {
         match v {
             Value::Object(dest_obj) => {
                 if let Value::Object(src_obj) = value {
@@ -664,7 +881,7 @@ pub(crate) fn merge_key_into<S>(result: &mut Object<S>, response_name: &str, val
 fn merge_maps<S>(dest: &mut Object<S>, src: Object<S>) {
     for (key, value) in src {
         if dest.contains_field(&key) {
-            merge_key_into(dest, &key, value);
+            merge_key_into(dest, key, value);
         } else {
             dest.add_field(key, value);
         }
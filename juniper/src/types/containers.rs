@@ -1,11 +1,19 @@
 use std::{
+    iter,
     mem::{self, MaybeUninit},
     ptr,
+    sync::RwLock,
 };
 
+use futures::future::{self, FusedFuture as _, FutureExt as _};
+
 use crate::{
     ast::{FromInputValue, InputValue, Selection, ToInputValue},
-    executor::{ExecutionResult, Executor, FieldError, IntoFieldError, Registry},
+    executor::{
+        resolve_known, resolve_until, EarlyValue, ExecutionResult, Executor, FieldError,
+        IncrementalPayload, IntoFieldError, Known, KnownValues, PathSegment, Registry,
+        StreamedPayload, SubsequentPayloads,
+    },
     schema::meta::MetaType,
     types::{
         async_await::GraphQLValueAsync,
@@ -548,10 +556,11 @@ where
         .list_contents()
         .expect("Current type is not a list type")
         .is_non_null();
+    executor.spend_work(iter.len())?;
     let mut result = Vec::with_capacity(iter.len());
 
-    for o in iter {
-        let val = executor.resolve(info, o)?;
+    for (i, o) in iter.enumerate() {
+        let val = executor.index_sub_executor(i).resolve(info, o)?;
         if stop_on_null && val.is_null() {
             return Ok(val);
         } else {
@@ -582,8 +591,23 @@ where
         .expect("Current type is not a list type")
         .is_non_null();
 
+    executor.spend_work(items.len())?;
+
+    if let (Some(early), Some(payloads)) = (executor.early_value(), executor.subsequent_payloads())
+    {
+        let list =
+            resolve_into_list_incrementally(executor, early, payloads, info, items, stop_on_null);
+        return Ok(list.await);
+    }
+
     let mut futures = items
-        .map(|it| async move { executor.resolve_into_value_async(info, it).await })
+        .enumerate()
+        .map(|(i, it)| async move {
+            executor
+                .index_sub_executor(i)
+                .resolve_into_value_async(info, it)
+                .await
+        })
         .collect::<FuturesOrdered<_>>();
 
     let mut values = Vec::with_capacity(futures.len());
@@ -597,6 +621,104 @@ where
     Ok(Value::list(values))
 }
 
+/// Resolves the `items` of a list being a part of an operation delivered
+/// incrementally, publishing the list into the `early` slot as soon as its
+/// items are known, while the deferred fragments nested inside them are still
+/// being resolved.
+///
+/// If the list field is marked with the `@stream` directive, only its first
+/// `initialCount` items are published, and each one of the rest is delivered
+/// as a [`StreamedPayload`] as soon as it's known, in their order.
+async fn resolve_into_list_incrementally<'a, 't, S, T, I>(
+    executor: &'a Executor<'a, 'a, T::Context, S>,
+    early: &'a EarlyValue<S>,
+    payloads: &'a SubsequentPayloads<S>,
+    info: &'a T::TypeInfo,
+    items: I,
+    stop_on_null: bool,
+) -> Value<S>
+where
+    I: Iterator<Item = &'t T> + ExactSizeIterator,
+    T: GraphQLValueAsync<S> + ?Sized + 't,
+    T::TypeInfo: Sync,
+    T::Context: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    let stream = early.stream();
+    let initial_count = stream.map_or(items.len(), |s| s.initial_count.min(items.len()));
+    let streamed = iter::repeat_with(|| {
+        let reservation = payloads.reserve(executor.subsequent_payload());
+        (reservation, RwLock::new(Vec::new()))
+    })
+    .take(items.len() - initial_count)
+    .collect::<Vec<_>>();
+
+    let known = KnownValues::default();
+    let resolutions = items
+        .map(|it| {
+            let (i, known) = (known.reserve(), &known);
+            let streamed = i.checked_sub(initial_count).map(|i| &streamed[i]);
+            async move {
+                let early = EarlyValue::new(None);
+                let sub_exec = executor.index_sub_executor(i).with_early_value(&early);
+                let sub_exec = match streamed {
+                    Some((reservation, errors)) => sub_exec
+                        .with_errors(errors)
+                        .with_subsequent_payload(reservation.id()),
+                    None => sub_exec,
+                };
+                let resolution = sub_exec.resolve_into_value_async(info, it);
+                resolve_known(Some(&early), resolution, |value| match value {
+                    Known::Finished(v) | Known::Early(v) => known.set(i, v),
+                })
+                .await;
+            }
+        })
+        .collect::<Vec<_>>();
+    let mut resolution = future::join_all(resolutions).map(drop).fuse();
+
+    let mut values = Vec::with_capacity(initial_count);
+    for i in 0..initial_count {
+        let value = resolve_until(&mut resolution, || known.take(i)).await;
+        if stop_on_null && value.is_null() {
+            return value;
+        }
+        values.push(value);
+    }
+    let list = Value::list(values);
+    if !resolution.is_terminated() {
+        early.publish(list.clone());
+    }
+
+    if let Some(stream) = stream {
+        let path = executor.current_path();
+        for (i, (reservation, errors)) in (initial_count..).zip(&streamed) {
+            let value = resolve_until(&mut resolution, || known.take(i)).await;
+            let mut errors = mem::take(&mut *errors.write().unwrap());
+            errors.sort();
+            let mut path = path.clone();
+            path.push(PathSegment::Index(i));
+            // The list can't be nulled anymore, so the item is omitted instead.
+            let items = if stop_on_null && value.is_null() {
+                vec![]
+            } else {
+                vec![value]
+            };
+            reservation.deliver(iter::once(IncrementalPayload::Streamed(StreamedPayload {
+                items,
+                path,
+                label: stream.label.clone(),
+                errors,
+            })));
+        }
+    }
+
+    if !resolution.is_terminated() {
+        resolution.await;
+    }
+    list
+}
+
 #[cfg(test)]
 mod coercion {
     use crate::{graphql_input_value, FromInputValue as _, InputValue, IntoFieldError as _};
@@ -0,0 +1,47 @@
+//! Storage abstraction behind the CRUD fields generated by
+//! `#[derive(GraphQLCrud)]` macro.
+
+use crate::{executor::FieldResult, value::DefaultScalarValue};
+
+/// Entity having CRUD fields generated by `#[derive(GraphQLCrud)]` macro.
+///
+/// Implemented by the macro, so shouldn't be implemented manually.
+pub trait Crud: Sized {
+    /// Type of the ID identifying this entity.
+    type Id;
+
+    /// [GraphQL input object][1] creating this entity, having all its fields
+    /// but the ID.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Input-Objects
+    type CreateInput;
+
+    /// [GraphQL input object][1] updating this entity, having all its fields
+    /// but the ID optional, so only the specified ones are updated.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Input-Objects
+    type UpdateInput;
+}
+
+/// Storage of [`Crud`] entities of type `T`, being implemented by the
+/// [`Context`] of the fields generated by `#[derive(GraphQLCrud)]` macro.
+///
+/// [`Context`]: crate::Context
+pub trait CrudStorage<T: Crud, S = DefaultScalarValue> {
+    /// Returns the entity with the given `id`, if any.
+    fn get(&self, id: T::Id) -> FieldResult<Option<T>, S>;
+
+    /// Returns the entities, skipping the `offset` first ones, if specified,
+    /// and returning no more than `first` ones, if specified.
+    fn list(&self, first: Option<i32>, offset: Option<i32>) -> FieldResult<Vec<T>, S>;
+
+    /// Creates a new entity out of the given `input`, returning it.
+    fn create(&self, input: T::CreateInput) -> FieldResult<T, S>;
+
+    /// Updates the entity with the given `id` with the given `input`, returning
+    /// the updated entity, or [`None`] if there is no such entity.
+    fn update(&self, id: T::Id, input: T::UpdateInput) -> FieldResult<Option<T>, S>;
+
+    /// Deletes the entity with the given `id`, returning whether it existed.
+    fn delete(&self, id: T::Id) -> FieldResult<bool, S>;
+}
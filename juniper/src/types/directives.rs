@@ -0,0 +1,55 @@
+//! Custom [executable directives][1].
+//!
+//! [1]: https://spec.graphql.org/October2021#ExecutableDirectiveLocation
+
+use crate::{
+    executor::{FieldResult, Registry},
+    schema::model::DirectiveType,
+    types::base::Arguments,
+    value::{DefaultScalarValue, ScalarValue, Value},
+};
+
+/// Custom [executable directive][1] transforming resolved values of the
+/// [GraphQL fields][2] it's placed on in a query (like `@uppercase`).
+///
+/// Usually derived with `#[derive(GraphQLDirective)]` macro, and should be
+/// registered in a schema with [`RootNode::with_directive()`] to be
+/// recognized by the validation and applied by the executor.
+///
+/// Directives placed on the same field are applied in the order they appear in
+/// a query, each one receiving the value produced by the previous one.
+///
+/// [`RootNode::with_directive()`]: crate::RootNode::with_directive
+/// [1]: https://spec.graphql.org/October2021#ExecutableDirectiveLocation
+/// [2]: https://spec.graphql.org/October2021#sec-Language.Fields
+pub trait GraphQLDirective<S = DefaultScalarValue>: Sized
+where
+    S: ScalarValue,
+{
+    /// Returns name of this directive in GraphQL schema (without the `@`).
+    fn name() -> &'static str;
+
+    /// Returns [`DirectiveType`] describing this directive and its arguments.
+    fn meta<'r>(registry: &mut Registry<'r, S>) -> DirectiveType<'r, S>
+    where
+        S: 'r;
+
+    /// Builds this directive out of the [`Arguments`] it's used with in a
+    /// query.
+    ///
+    /// Default values of the arguments are already populated.
+    ///
+    /// # Errors
+    ///
+    /// If the [`Arguments`] cannot be converted into this directive.
+    fn from_arguments(args: &Arguments<S>) -> FieldResult<Self, S>;
+
+    /// Transforms the resolved `value` of the field this directive is placed
+    /// on.
+    ///
+    /// # Errors
+    ///
+    /// If the `value` cannot be transformed. The error is reported as a field
+    /// error, and the field is resolved to `null`.
+    fn resolve(self, value: Value<S>) -> FieldResult<Value<S>, S>;
+}
@@ -0,0 +1,231 @@
+//! Merging of [GraphQL objects][1] contributed by independent modules into a
+//! single one.
+//!
+//! [1]: https://spec.graphql.org/June2018/#sec-Objects
+
+use crate::{
+    executor::{ExecutionResult, Executor, Registry},
+    macros::reflect,
+    schema::{meta::MetaType, model::SchemaError},
+    types::{
+        async_await::GraphQLValueAsync,
+        base::{Arguments, GraphQLType, GraphQLValue},
+        subscriptions::GraphQLSubscriptionValue,
+    },
+    value::{ScalarValue, Value},
+    BoxFuture, FieldError, ValuesStream,
+};
+
+/// [GraphQL object][1] merging the fields of two [GraphQL objects][1], usually
+/// contributed by independent modules or crates, so they may be used as a
+/// single query, mutation or subscription root of a [`RootNode`].
+///
+/// The merged object is named after its first part `A`, and the second part
+/// `B` may be a [`MergedObject`] itself, allowing to merge any number of
+/// objects: `MergedObject<A, MergedObject<B, C>>`. All the parts should share
+/// the same [`Context`] and type info.
+///
+/// The parts are not registered in a schema on their own, so they shouldn't
+/// be used anywhere else in it. Building a schema fails with a
+/// [`SchemaError::DuplicateMergedField`] if the parts define fields with the
+/// same name, or with a [`SchemaError::InvalidMergedPart`] if any of them
+/// isn't a GraphQL object.
+///
+/// ```
+/// # use juniper::{graphql_object, EmptyMutation, EmptySubscription, MergedObject, RootNode};
+/// #
+/// struct Query;
+///
+/// #[graphql_object]
+/// impl Query {
+///     fn users() -> Vec<String> {
+///         vec!["John".into()]
+///     }
+/// }
+///
+/// struct OrdersQuery;
+///
+/// #[graphql_object]
+/// impl OrdersQuery {
+///     fn orders() -> Vec<i32> {
+///         vec![1, 2]
+///     }
+/// }
+///
+/// let schema = RootNode::new(
+///     MergedObject(Query, OrdersQuery),
+///     EmptyMutation::<()>::new(),
+///     EmptySubscription::<()>::new(),
+/// );
+///
+/// let query = schema.schema.concrete_query_type();
+/// assert_eq!(query.name(), Some("Query"));
+/// assert!(query.field_by_name("users").is_some());
+/// assert!(query.field_by_name("orders").is_some());
+/// ```
+///
+/// [`Context`]: crate::Context
+/// [`RootNode`]: crate::RootNode
+/// [`SchemaError::DuplicateMergedField`]: crate::SchemaError::DuplicateMergedField
+/// [`SchemaError::InvalidMergedPart`]: crate::SchemaError::InvalidMergedPart
+/// [1]: https://spec.graphql.org/June2018/#sec-Objects
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MergedObject<A, B>(pub A, pub B);
+
+impl<A, B> MergedObject<A, B> {
+    /// Checks whether the given `field` is resolved by the first part of this
+    /// [`MergedObject`].
+    fn is_first<S>(field: &str) -> bool
+    where
+        A: reflect::Fields<S>,
+    {
+        A::NAMES.contains(&field)
+    }
+}
+
+impl<A, B, S> GraphQLType<S> for MergedObject<A, B>
+where
+    A: GraphQLType<S> + reflect::Fields<S>,
+    B: GraphQLType<S, Context = A::Context, TypeInfo = A::TypeInfo>,
+    S: ScalarValue,
+{
+    fn name(info: &Self::TypeInfo) -> Option<&str> {
+        A::name(info)
+    }
+
+    fn meta<'r>(info: &Self::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S>
+    where
+        S: 'r,
+    {
+        let (mut meta, other) = match (A::meta(info, registry), B::meta(info, registry)) {
+            (MetaType::Object(meta), MetaType::Object(other)) => (meta, other),
+            (meta, other) => {
+                let object = meta.name().unwrap_or_default().to_owned();
+                let part = match &meta {
+                    MetaType::Object(_) => other.name(),
+                    _ => meta.name(),
+                };
+                registry.push_error(SchemaError::InvalidMergedPart {
+                    part: part.unwrap_or_default().to_owned(),
+                    object,
+                });
+                return meta;
+            }
+        };
+
+        for field in other.fields {
+            if field.name == "__typename" {
+                continue;
+            }
+            if meta.fields.iter().any(|f| f.name == field.name) {
+                registry.push_error(SchemaError::DuplicateMergedField {
+                    object: meta.name.to_string(),
+                    field: field.name.to_string(),
+                });
+                continue;
+            }
+            meta.fields.push(field);
+        }
+        for name in other.interface_names {
+            if !meta.interface_names.contains(&name) {
+                meta.interface_names.push(name);
+            }
+        }
+        if meta.description.is_none() {
+            meta.description = other.description;
+        }
+
+        meta.into_meta()
+    }
+}
+
+impl<A, B, S> GraphQLValue<S> for MergedObject<A, B>
+where
+    A: GraphQLType<S> + reflect::Fields<S>,
+    B: GraphQLType<S, Context = A::Context, TypeInfo = A::TypeInfo>,
+    S: ScalarValue,
+{
+    type Context = A::Context;
+    type TypeInfo = A::TypeInfo;
+
+    fn type_name<'i>(&self, info: &'i Self::TypeInfo) -> Option<&'i str> {
+        A::name(info)
+    }
+
+    fn resolve_field(
+        &self,
+        info: &Self::TypeInfo,
+        field: &str,
+        args: &Arguments<S>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        if Self::is_first(field) {
+            self.0.resolve_field(info, field, args, executor)
+        } else {
+            self.1.resolve_field(info, field, args, executor)
+        }
+    }
+
+    fn concrete_type_name(&self, context: &Self::Context, info: &Self::TypeInfo) -> String {
+        self.0.concrete_type_name(context, info)
+    }
+}
+
+impl<A, B, S> GraphQLValueAsync<S> for MergedObject<A, B>
+where
+    A: GraphQLType<S> + GraphQLValueAsync<S> + reflect::Fields<S>,
+    A::TypeInfo: Sync,
+    A::Context: Sync,
+    B: GraphQLType<S, Context = A::Context, TypeInfo = A::TypeInfo> + GraphQLValueAsync<S>,
+    S: ScalarValue + Send + Sync,
+{
+    fn resolve_field_async<'a>(
+        &'a self,
+        info: &'a Self::TypeInfo,
+        field_name: &'a str,
+        arguments: &'a Arguments<S>,
+        executor: &'a Executor<Self::Context, S>,
+    ) -> BoxFuture<'a, ExecutionResult<S>> {
+        if Self::is_first(field_name) {
+            self.0
+                .resolve_field_async(info, field_name, arguments, executor)
+        } else {
+            self.1
+                .resolve_field_async(info, field_name, arguments, executor)
+        }
+    }
+}
+
+impl<A, B, S> GraphQLSubscriptionValue<S> for MergedObject<A, B>
+where
+    A: GraphQLType<S> + GraphQLSubscriptionValue<S> + reflect::Fields<S>,
+    A::TypeInfo: Sync,
+    A::Context: Sync,
+    B: GraphQLType<S, Context = A::Context, TypeInfo = A::TypeInfo> + GraphQLSubscriptionValue<S>,
+    S: ScalarValue + Send + Sync,
+{
+    fn resolve_field_into_stream<'s, 'i, 'ft, 'args, 'e, 'ref_e, 'res, 'f>(
+        &'s self,
+        info: &'i Self::TypeInfo,
+        field_name: &'ft str,
+        arguments: Arguments<'args, S>,
+        executor: &'ref_e Executor<'ref_e, 'e, Self::Context, S>,
+    ) -> BoxFuture<'f, Result<Value<ValuesStream<'res, S>>, FieldError<S>>>
+    where
+        's: 'f,
+        'i: 'res,
+        'ft: 'f,
+        'args: 'f,
+        'ref_e: 'f,
+        'res: 'f,
+        'e: 'res,
+    {
+        if Self::is_first(field_name) {
+            self.0
+                .resolve_field_into_stream(info, field_name, arguments, executor)
+        } else {
+            self.1
+                .resolve_field_into_stream(info, field_name, arguments, executor)
+        }
+    }
+}
@@ -1,10 +1,16 @@
 pub mod async_await;
 pub mod base;
 pub mod containers;
+pub mod crud;
+pub mod directives;
 pub mod marker;
+pub mod merged;
 pub mod name;
 pub mod nullable;
 pub mod pointers;
 pub mod scalars;
+pub mod sensitive;
+pub mod std_scalars;
 pub mod subscriptions;
+pub mod typed_id;
 pub mod utilities;
@@ -1,10 +1,17 @@
 use std::{
-    borrow::Borrow,
+    borrow::{Borrow, Cow},
+    cmp::Ordering,
+    collections::HashSet,
     error::Error,
-    fmt::{Display, Formatter, Result as FmtResult},
+    fmt::{Debug, Display, Formatter, Result as FmtResult},
+    hash::{Hash, Hasher},
+    ops::Deref,
     str::FromStr,
+    sync::{Arc, PoisonError, RwLock},
 };
 
+use once_cell::sync::Lazy;
+
 // Helper functions until the corresponding AsciiExt methods
 // stabilise (https://github.com/rust-lang/rust/issues/39658).
 
@@ -16,10 +23,74 @@ fn is_ascii_digit(c: char) -> bool {
     ('0'..='9').contains(&c)
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Name(String);
+/// Interned name of a GraphQL schema entity (like a type, a field or an
+/// argument).
+///
+/// Every distinct name is stored only once per process while it's in use, so
+/// cloning a [`Name`] is cheap and comparing two [`Name`]s is a single pointer
+/// comparison, rather than comparing their strings.
+///
+/// Names no longer in use are freed by subsequent interning, so the names of
+/// schemas built at runtime (like the ones imported from an introspection
+/// result) don't accumulate.
+#[derive(Clone)]
+pub struct Name(Arc<str>);
+
+/// Minimum number of the names in the [`INTERNER`] to free the unused ones.
+const MIN_PRUNED_LEN: usize = 1024;
+
+/// Set of all the interned [`Name`]s.
+///
+/// Names interned already are looked up under a read lock only, so interning
+/// them concurrently doesn't contend.
+static INTERNER: Lazy<RwLock<Interner>> = Lazy::new(RwLock::default);
+
+struct Interner {
+    names: HashSet<Arc<str>>,
+
+    /// Number of the names to free the unused ones at, being doubled after
+    /// each time, so they're freed in amortized constant time.
+    prune_at: usize,
+}
+
+impl Default for Interner {
+    fn default() -> Self {
+        Self {
+            names: HashSet::new(),
+            prune_at: MIN_PRUNED_LEN,
+        }
+    }
+}
+
+impl Interner {
+    /// Returns the given `name`, if it's interned already.
+    fn get(&self, name: &str) -> Option<Name> {
+        self.names.get(name).cloned().map(Name)
+    }
+
+    /// Interns the given `name`, freeing the unused names once there are
+    /// enough of them.
+    fn intern(&mut self, name: &str) -> Name {
+        if let Some(interned) = self.get(name) {
+            return interned;
+        }
+
+        if self.names.len() >= self.prune_at {
+            // A name referenced by the set only can't be cloned by anyone else
+            // while the write lock is held.
+            self.names.retain(|n| Arc::strong_count(n) > 1);
+            self.prune_at = (self.names.len() * 2).max(MIN_PRUNED_LEN);
+        }
+
+        let interned = Arc::<str>::from(name);
+        self.names.insert(interned.clone());
+        Name(interned)
+    }
+}
 
 impl Name {
+    /// Checks whether the given `input` is a valid GraphQL name, matching the
+    /// `/^[_a-zA-Z][_a-zA-Z0-9]*$/` regex.
     pub fn is_valid(input: &str) -> bool {
         for (i, c) in input.chars().enumerate() {
             let is_valid = is_ascii_alphabetic(c) || c == '_' || (i > 0 && is_ascii_digit(c));
@@ -29,6 +100,149 @@ impl Name {
         }
         !input.is_empty()
     }
+
+    /// Interns the given `name`, without checking it to be a valid GraphQL
+    /// name.
+    ///
+    /// Use [`str::parse()`] for checking it.
+    pub fn intern(name: &str) -> Self {
+        let interned = INTERNER
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(name);
+        interned.unwrap_or_else(|| {
+            INTERNER
+                .write()
+                .unwrap_or_else(PoisonError::into_inner)
+                .intern(name)
+        })
+    }
+
+    /// Returns the string of this [`Name`].
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq for Name {
+    fn eq(&self, other: &Self) -> bool {
+        // Interned strings are equal only if they're the same string.
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for Name {}
+
+impl PartialEq<str> for Name {
+    fn eq(&self, other: &str) -> bool {
+        &*self.0 == other
+    }
+}
+
+impl PartialEq<&str> for Name {
+    fn eq(&self, other: &&str) -> bool {
+        &*self.0 == *other
+    }
+}
+
+impl PartialEq<String> for Name {
+    fn eq(&self, other: &String) -> bool {
+        &*self.0 == other
+    }
+}
+
+impl PartialEq<Name> for str {
+    fn eq(&self, other: &Name) -> bool {
+        self == &*other.0
+    }
+}
+
+impl PartialEq<Name> for &str {
+    fn eq(&self, other: &Name) -> bool {
+        *self == &*other.0
+    }
+}
+
+impl PartialEq<Name> for String {
+    fn eq(&self, other: &Name) -> bool {
+        self == &*other.0
+    }
+}
+
+impl PartialOrd for Name {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Name {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl Hash for Name {
+    // Hashed as its string, so a map keyed by `Name`s may be looked up by
+    // `&str`s as well.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl Deref for Name {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Name {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for Name {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Debug for Name {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+impl Display for Name {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl From<&str> for Name {
+    fn from(name: &str) -> Self {
+        Self::intern(name)
+    }
+}
+
+impl From<String> for Name {
+    fn from(name: String) -> Self {
+        Self::intern(&name)
+    }
+}
+
+impl From<Cow<'_, str>> for Name {
+    fn from(name: Cow<'_, str>) -> Self {
+        Self::intern(&name)
+    }
+}
+
+impl From<Name> for String {
+    fn from(name: Name) -> Self {
+        (*name.0).into()
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -36,7 +250,7 @@ pub struct NameParseError(String);
 
 impl Display for NameParseError {
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
-        self.0.fmt(f)
+        Display::fmt(&self.0, f)
     }
 }
 
@@ -50,7 +264,7 @@ impl FromStr for Name {
     type Err = NameParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if Name::is_valid(s) {
-            Ok(Name(s.to_string()))
+            Ok(Name::intern(s))
         } else {
             Err(NameParseError(format!(
                 "Names must match /^[_a-zA-Z][_a-zA-Z0-9]*$/ but \"{}\" does not",
@@ -60,16 +274,42 @@ impl FromStr for Name {
     }
 }
 
-impl Borrow<String> for Name {
-    fn borrow(&self) -> &String {
-        &self.0
-    }
+#[test]
+fn test_name_interning() {
+    let foo = Name::intern("Foo");
+    assert_eq!(foo, "Foo".parse::<Name>().unwrap());
+    assert!(std::ptr::eq(
+        foo.as_str(),
+        Name::intern(&String::from("Foo")).as_str()
+    ));
+    assert_ne!(foo, Name::intern("Bar"));
+    assert_eq!(foo, "Foo");
+    assert!(Name::intern("Bar") < foo);
 }
 
-impl Borrow<str> for Name {
-    fn borrow(&self) -> &str {
-        &self.0
+#[test]
+fn test_name_freeing() {
+    let mut interner = Interner::default();
+    let used = interner.intern("Used");
+    for i in 0..MIN_PRUNED_LEN * 2 {
+        drop(interner.intern(&format!("Unused{}", i)));
     }
+
+    assert!(interner.names.contains("Used"));
+    assert!(interner.names.len() < MIN_PRUNED_LEN);
+    assert_eq!(interner.intern("Used"), used);
+}
+
+#[test]
+fn test_name_not_kept_by_type() {
+    use crate::{ast::Type, meta::ObjectMeta, DefaultScalarValue};
+
+    let name = Name::intern("NotKeptByType");
+    let meta = ObjectMeta::<DefaultScalarValue>::new(name.clone(), &[]).into_meta();
+    let count = Arc::strong_count(&name.0);
+
+    assert_eq!(meta.as_type(), Type::NonNullNamed("NotKeptByType".into()));
+    assert_eq!(Arc::strong_count(&name.0), count);
 }
 
 #[test]
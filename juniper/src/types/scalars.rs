@@ -1,5 +1,6 @@
 use std::{
-    char, convert::From, fmt, marker::PhantomData, ops::Deref, rc::Rc, thread::JoinHandle, u32,
+    borrow::Cow, char, convert::From, fmt, marker::PhantomData, ops::Deref, rc::Rc,
+    thread::JoinHandle, u32,
 };
 
 use serde::{Deserialize, Serialize};
@@ -9,7 +10,10 @@ use crate::{
     executor::{ExecutionResult, Executor, Registry},
     graphql_scalar,
     macros::reflect,
-    parser::{LexerError, ParseError, ScalarToken, Token},
+    parser::{
+        block_string_value, decode_surrogate_pair, is_leading_surrogate, is_trailing_surrogate,
+        LexerError, ParseError, ScalarToken, Token,
+    },
     schema::meta::MetaType,
     types::{
         async_await::GraphQLValueAsync,
@@ -131,6 +135,8 @@ mod impl_string_scalar {
                 }
             }
             Ok(ret.into())
+        } else if let ScalarToken::BlockString(value) = value {
+            Ok(block_string_value(value).into())
         } else {
             Err(ParseError::UnexpectedToken(Token::Scalar(value)))
         }
@@ -141,59 +147,55 @@ fn parse_unicode_codepoint<'a, I>(char_iter: &mut I) -> Result<char, ParseError<
 where
     I: Iterator<Item = char>,
 {
-    let escaped_code_point = char_iter
-        .next()
-        .ok_or_else(|| {
-            ParseError::LexerError(LexerError::UnknownEscapeSequence(String::from("\\u")))
-        })
-        .and_then(|c1| {
-            char_iter
-                .next()
-                .map(|c2| format!("{}{}", c1, c2))
-                .ok_or_else(|| {
-                    ParseError::LexerError(LexerError::UnknownEscapeSequence(format!("\\u{}", c1)))
-                })
-        })
-        .and_then(|mut s| {
-            char_iter
-                .next()
-                .ok_or_else(|| {
-                    ParseError::LexerError(LexerError::UnknownEscapeSequence(format!(
-                        "\\u{}",
-                        s.clone()
-                    )))
-                })
-                .map(|c2| {
-                    s.push(c2);
-                    s
-                })
-        })
-        .and_then(|mut s| {
-            char_iter
-                .next()
-                .ok_or_else(|| {
-                    ParseError::LexerError(LexerError::UnknownEscapeSequence(format!(
-                        "\\u{}",
-                        s.clone()
-                    )))
-                })
-                .map(|c2| {
-                    s.push(c2);
-                    s
-                })
-        })?;
-    let code_point = u32::from_str_radix(&escaped_code_point, 16).map_err(|_| {
+    let (escaped_code_point, code_point) = parse_unicode_code_unit(char_iter)?;
+    if !is_leading_surrogate(code_point) {
+        return char::from_u32(code_point).ok_or_else(|| {
+            ParseError::LexerError(LexerError::UnknownEscapeSequence(format!(
+                "\\u{}",
+                escaped_code_point,
+            )))
+        });
+    }
+
+    // A leading surrogate must be followed by an escaped trailing one.
+    let unpaired = || {
         ParseError::LexerError(LexerError::UnknownEscapeSequence(format!(
             "\\u{}",
-            escaped_code_point
+            escaped_code_point,
         )))
-    })?;
-    char::from_u32(code_point).ok_or_else(|| {
+    };
+    if char_iter.next() != Some('\\') || char_iter.next() != Some('u') {
+        return Err(unpaired());
+    }
+    let (_, trailing) = parse_unicode_code_unit(char_iter)?;
+    if !is_trailing_surrogate(trailing) {
+        return Err(unpaired());
+    }
+    decode_surrogate_pair(code_point, trailing).ok_or_else(unpaired)
+}
+
+/// Parses the four hexadecimal digits of a `\\uXXXX` escape sequence.
+fn parse_unicode_code_unit<'a, I>(char_iter: &mut I) -> Result<(String, u32), ParseError<'a>>
+where
+    I: Iterator<Item = char>,
+{
+    let mut escaped_code_point = String::with_capacity(4);
+    for _ in 0..4 {
+        let c = char_iter.next().ok_or_else(|| {
+            ParseError::LexerError(LexerError::UnknownEscapeSequence(format!(
+                "\\u{}",
+                escaped_code_point,
+            )))
+        })?;
+        escaped_code_point.push(c);
+    }
+    let code_point = u32::from_str_radix(&escaped_code_point, 16).map_err(|_| {
         ParseError::LexerError(LexerError::UnknownEscapeSequence(format!(
             "\\u{}",
             escaped_code_point
         )))
-    })
+    })?;
+    Ok((escaped_code_point, code_point))
 }
 
 impl<S> reflect::WrappedType<S> for str {
@@ -269,6 +271,15 @@ where
     }
 }
 
+impl<'a, S> ToInputValue<S> for Cow<'a, str>
+where
+    S: ScalarValue,
+{
+    fn to_input_value(&self) -> InputValue<S> {
+        InputValue::scalar(self.clone().into_owned())
+    }
+}
+
 #[graphql_scalar(with = impl_boolean_scalar)]
 type Boolean = bool;
 
@@ -328,7 +339,8 @@ mod impl_float_scalar {
     }
 
     pub(super) fn from_input<S: ScalarValue>(v: &InputValue<S>) -> Result<Float, String> {
-        v.as_float_value()
+        v.as_scalar()
+            .and_then(ScalarValue::coerce_float)
             .ok_or_else(|| format!("Expected `Float`, found: {}", v))
     }
 
@@ -336,13 +348,17 @@ mod impl_float_scalar {
         match value {
             ScalarToken::Int(v) => v
                 .parse()
-                .map_err(|_| ParseError::UnexpectedToken(Token::Scalar(value)))
-                .map(|s: i32| f64::from(s).into()),
+                .ok()
+                .and_then(|s: i32| S::from(s).coerce_float())
+                .map(S::from)
+                .ok_or(ParseError::UnexpectedToken(Token::Scalar(value))),
             ScalarToken::Float(v) => v
                 .parse()
                 .map_err(|_| ParseError::UnexpectedToken(Token::Scalar(value)))
                 .map(|s: f64| s.into()),
-            ScalarToken::String(_) => Err(ParseError::UnexpectedToken(Token::Scalar(value))),
+            ScalarToken::String(_) | ScalarToken::BlockString(_) => {
+                Err(ParseError::UnexpectedToken(Token::Scalar(value)))
+            }
         }
     }
 }
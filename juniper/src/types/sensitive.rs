@@ -0,0 +1,318 @@
+//! Wrapper for sensitive values, redacting them everywhere but in GraphQL
+//! responses.
+
+use std::fmt;
+
+use crate::{
+    ast::{FromInputValue, InputValue, Selection, ToInputValue},
+    executor::{ExecutionResult, Executor, Registry},
+    macros::reflect,
+    schema::meta::MetaType,
+    types::{
+        async_await::GraphQLValueAsync,
+        base::{Arguments, GraphQLType, GraphQLValue},
+        marker::{IsInputType, IsOutputType},
+    },
+    value::ScalarValue,
+    BoxFuture,
+};
+
+/// Placeholder printed instead of a [`Sensitive`] value.
+const REDACTED: &str = "[REDACTED]";
+
+/// Sensitive value (a card number fragment, an email or a token, for example),
+/// behaving in GraphQL exactly as the wrapped `T`, but redacted when being
+/// formatted via [`Debug`] or [`Display`], and in the errors of its parsing.
+///
+/// So logging the arguments of a resolver, or a [`FieldError`] of a failed
+/// parsing doesn't leak the value. The wrapped value is accessible via the
+/// [`Sensitive::expose()`] method only, making its usage explicit.
+///
+/// Note, that an input value is validated against the GraphQL type of `T`
+/// before being parsed into a [`Sensitive`], so validation errors (of passing
+/// an `Int` into a `String` argument, for example) are reported the same way
+/// as for `T`.
+///
+/// ```
+/// # use juniper::{graphql_object, Sensitive};
+/// #
+/// struct Query;
+///
+/// #[graphql_object]
+/// impl Query {
+///     fn card_suffix(pan: Sensitive<String>) -> Sensitive<String> {
+///         // Prints `[REDACTED]` instead of the card number.
+///         println!("card_suffix({:?})", pan);
+///
+///         let pan = pan.expose();
+///         Sensitive::new(pan[pan.len().saturating_sub(4)..].to_owned())
+///     }
+/// }
+///
+/// assert_eq!(format!("{}", Sensitive::new("4242")), "[REDACTED]");
+/// ```
+///
+/// [`Debug`]: std::fmt::Debug
+/// [`Display`]: std::fmt::Display
+/// [`FieldError`]: crate::FieldError
+#[derive(Clone, Copy, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Sensitive<T>(T);
+
+impl<T> Sensitive<T> {
+    /// Wraps the given `value` as a [`Sensitive`] one.
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Returns a reference to the wrapped sensitive value.
+    pub fn expose(&self) -> &T {
+        &self.0
+    }
+
+    /// Unwraps the sensitive value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Sensitive<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> fmt::Debug for Sensitive<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl<T> fmt::Display for Sensitive<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl<S, T> GraphQLType<S> for Sensitive<T>
+where
+    T: GraphQLType<S>,
+    S: ScalarValue,
+{
+    fn name(info: &Self::TypeInfo) -> Option<&str> {
+        T::name(info)
+    }
+
+    fn meta<'r>(info: &Self::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S>
+    where
+        S: 'r,
+    {
+        T::meta(info, registry)
+    }
+}
+
+impl<S, T> GraphQLValue<S> for Sensitive<T>
+where
+    T: GraphQLValue<S>,
+    S: ScalarValue,
+{
+    type Context = T::Context;
+    type TypeInfo = T::TypeInfo;
+
+    fn type_name<'i>(&self, info: &'i Self::TypeInfo) -> Option<&'i str> {
+        self.0.type_name(info)
+    }
+
+    fn resolve_into_type(
+        &self,
+        info: &Self::TypeInfo,
+        name: &str,
+        selection_set: Option<&[Selection<S>]>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        self.0
+            .resolve_into_type(info, name, selection_set, executor)
+    }
+
+    fn resolve_field(
+        &self,
+        info: &Self::TypeInfo,
+        field: &str,
+        args: &Arguments<S>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        self.0.resolve_field(info, field, args, executor)
+    }
+
+    fn resolve(
+        &self,
+        info: &Self::TypeInfo,
+        selection_set: Option<&[Selection<S>]>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        self.0.resolve(info, selection_set, executor)
+    }
+}
+
+impl<S, T> GraphQLValueAsync<S> for Sensitive<T>
+where
+    T: GraphQLValueAsync<S>,
+    T::TypeInfo: Sync,
+    T::Context: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    fn resolve_async<'a>(
+        &'a self,
+        info: &'a Self::TypeInfo,
+        selection_set: Option<&'a [Selection<S>]>,
+        executor: &'a Executor<Self::Context, S>,
+    ) -> BoxFuture<'a, ExecutionResult<S>> {
+        self.0.resolve_async(info, selection_set, executor)
+    }
+}
+
+impl<S, T> FromInputValue<S> for Sensitive<T>
+where
+    T: FromInputValue<S> + GraphQLType<S, TypeInfo = ()>,
+    S: ScalarValue,
+{
+    type Error = String;
+
+    fn from_input_value(v: &InputValue<S>) -> Result<Self, Self::Error> {
+        T::from_input_value(v)
+            .map(Self)
+            .map_err(redacted_error::<S, T>)
+    }
+
+    fn from_implicit_null() -> Result<Self, Self::Error> {
+        T::from_implicit_null()
+            .map(Self)
+            .map_err(redacted_error::<S, T>)
+    }
+}
+
+/// Returns an error of parsing a [`Sensitive`]`<T>`, omitting the original
+/// one, as it may contain the value itself.
+fn redacted_error<S, T>(_: T::Error) -> String
+where
+    T: FromInputValue<S> + GraphQLType<S, TypeInfo = ()>,
+    S: ScalarValue,
+{
+    format!(
+        "Invalid `{}` value: {}",
+        T::name(&()).unwrap_or("<unnamed>"),
+        REDACTED,
+    )
+}
+
+impl<S, T> ToInputValue<S> for Sensitive<T>
+where
+    T: ToInputValue<S>,
+{
+    fn to_input_value(&self) -> InputValue<S> {
+        self.0.to_input_value()
+    }
+}
+
+impl<S: ScalarValue, T: IsInputType<S>> IsInputType<S> for Sensitive<T> {}
+
+impl<S: ScalarValue, T: IsOutputType<S>> IsOutputType<S> for Sensitive<T> {}
+
+impl<S, T: reflect::BaseType<S>> reflect::BaseType<S> for Sensitive<T> {
+    const NAME: reflect::Type = T::NAME;
+}
+
+impl<S, T: reflect::BaseSubTypes<S>> reflect::BaseSubTypes<S> for Sensitive<T> {
+    const NAMES: reflect::Types = T::NAMES;
+}
+
+impl<S, T: reflect::WrappedType<S>> reflect::WrappedType<S> for Sensitive<T> {
+    const VALUE: reflect::WrappedValue = T::VALUE;
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        execute_sync, graphql_input_value, graphql_object, graphql_value, graphql_vars,
+        EmptyMutation, EmptySubscription, FromInputValue as _, InputValue, RootNode,
+    };
+
+    use super::Sensitive;
+
+    #[test]
+    fn redacts_formatting() {
+        let token = Sensitive::new("secret-token");
+
+        assert_eq!(format!("{:?}", token), "[REDACTED]");
+        assert_eq!(format!("{}", token), "[REDACTED]");
+        assert_eq!(format!("{:?}", Some(token)), "Some([REDACTED])");
+        assert_eq!(*token.expose(), "secret-token");
+    }
+
+    #[test]
+    fn redacts_parsing_errors() {
+        let input: InputValue = graphql_input_value!("4242424242424242");
+        let err = Sensitive::<i32>::from_input_value(&input).unwrap_err();
+
+        assert_eq!(err, "Invalid `Int` value: [REDACTED]");
+        assert!(!err.contains("4242"));
+
+        let parsed = Sensitive::<String>::from_input_value(&input).unwrap();
+        assert_eq!(parsed.into_inner(), "4242424242424242");
+    }
+
+    #[test]
+    fn behaves_as_wrapped_type() {
+        struct Query;
+
+        #[graphql_object]
+        impl Query {
+            fn echo(
+                email: Sensitive<String>,
+                suffix: Option<Sensitive<String>>,
+            ) -> Sensitive<String> {
+                let mut email = email.into_inner();
+                if let Some(suffix) = suffix {
+                    email.push_str(suffix.expose());
+                }
+                email.into()
+            }
+        }
+
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+
+        assert_eq!(
+            execute_sync(
+                r#"{
+                    echo(email: "john@example.com")
+                    __type(name: "Query") {
+                        fields { args { name type { name kind ofType { name } } } }
+                    }
+                }"#,
+                None,
+                &schema,
+                &graphql_vars! {},
+                &(),
+            ),
+            Ok((
+                graphql_value!({
+                    "echo": "john@example.com",
+                    "__type": {"fields": [{"args": [
+                        {
+                            "name": "email",
+                            "type": {"name": null, "kind": "NON_NULL", "ofType": {"name": "String"}},
+                        },
+                        {
+                            "name": "suffix",
+                            "type": {"name": "String", "kind": "SCALAR", "ofType": null},
+                        },
+                    ]}]},
+                }),
+                vec![],
+            )),
+        );
+    }
+}
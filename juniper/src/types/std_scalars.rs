@@ -0,0 +1,362 @@
+//! GraphQL support for common [`std`] types.
+//!
+//! # Supported types
+//!
+//! | Rust type      | Format                               | GraphQL scalar |
+//! |----------------|--------------------------------------|----------------|
+//! | [`NonZeroU32`] | positive `Int` or its `String` form  | `NonZeroU32`   |
+//! | [`NonZeroI64`] | non-zero `Int` or its `String` form  | `NonZeroI64`   |
+//! | [`IpAddr`]     | IPv4 or IPv6 address `String`        | `IpAddr`       |
+//! | [`Ipv4Addr`]   | `a.b.c.d`                            | `Ipv4Addr`     |
+//! | [`Ipv6Addr`]   | [RFC 5952] `String`                  | `Ipv6Addr`     |
+//! | [`SocketAddr`] | `a.b.c.d:port` or `[ipv6]:port`      | `SocketAddr`   |
+//! | [`PathBuf`]    | `String`                             | `PathBuf`      |
+//!
+//! As a GraphQL `Int` is a signed 32-bit integer, the [`NonZeroU32`] and
+//! [`NonZeroI64`] values not fitting into it are represented as decimal
+//! `String`s instead. Both forms are accepted as input.
+//!
+//! [`IpAddr`]: std::net::IpAddr
+//! [`Ipv4Addr`]: std::net::Ipv4Addr
+//! [`Ipv6Addr`]: std::net::Ipv6Addr
+//! [`NonZeroI64`]: std::num::NonZeroI64
+//! [`NonZeroU32`]: std::num::NonZeroU32
+//! [`PathBuf`]: std::path::PathBuf
+//! [`SocketAddr`]: std::net::SocketAddr
+//! [RFC 5952]: https://datatracker.ietf.org/doc/html/rfc5952
+
+use std::{convert::TryInto, fmt, str::FromStr};
+
+use crate::{graphql_scalar, InputValue, ScalarValue, Value};
+
+/// Non-zero unsigned 32-bit integer.
+///
+/// See also [`std::num::NonZeroU32`] for details.
+#[graphql_scalar(with = non_zero_u32, parse_token(i32, String))]
+type NonZeroU32 = std::num::NonZeroU32;
+
+mod non_zero_u32 {
+    use super::*;
+
+    pub(super) fn to_output<S: ScalarValue>(v: &NonZeroU32) -> Value<S> {
+        int_output(v.get())
+    }
+
+    pub(super) fn from_input<S: ScalarValue>(v: &InputValue<S>) -> Result<NonZeroU32, String> {
+        int_input(v, "NonZeroU32")
+    }
+}
+
+/// Non-zero signed 64-bit integer.
+///
+/// See also [`std::num::NonZeroI64`] for details.
+#[graphql_scalar(with = non_zero_i64, parse_token(i32, String))]
+type NonZeroI64 = std::num::NonZeroI64;
+
+mod non_zero_i64 {
+    use super::*;
+
+    pub(super) fn to_output<S: ScalarValue>(v: &NonZeroI64) -> Value<S> {
+        int_output(v.get())
+    }
+
+    pub(super) fn from_input<S: ScalarValue>(v: &InputValue<S>) -> Result<NonZeroI64, String> {
+        int_input(v, "NonZeroI64")
+    }
+}
+
+/// Represents the given integer as an `Int` if it fits into it, or as a
+/// decimal `String` otherwise.
+fn int_output<T, S>(v: T) -> Value<S>
+where
+    T: Copy + TryInto<i32> + ToString,
+    S: ScalarValue,
+{
+    match v.try_into() {
+        Ok(i) => Value::scalar(i),
+        Err(_) => Value::scalar(v.to_string()),
+    }
+}
+
+/// Parses an integer of the `name`d type out of either an `Int` or a decimal
+/// `String`.
+fn int_input<T, S>(v: &InputValue<S>, name: &str) -> Result<T, String>
+where
+    T: FromStr,
+    S: ScalarValue,
+{
+    if let Some(i) = v.as_int_value() {
+        i.to_string()
+            .parse()
+            .map_err(|_| format!("Invalid `{}`: {}", name, i))
+    } else if let Some(s) = v.as_string_value() {
+        s.parse()
+            .map_err(|_| format!("Invalid `{}`: \"{}\"", name, s))
+    } else {
+        Err(format!("Expected `Int` or `String`, found: {}", v))
+    }
+}
+
+/// IPv4 or IPv6 address.
+///
+/// See also [`std::net::IpAddr`] for details.
+#[graphql_scalar(with = ip_addr, parse_token(String))]
+type IpAddr = std::net::IpAddr;
+
+mod ip_addr {
+    use super::*;
+
+    pub(super) fn to_output<S: ScalarValue>(v: &IpAddr) -> Value<S> {
+        Value::scalar(v.to_string())
+    }
+
+    pub(super) fn from_input<S: ScalarValue>(v: &InputValue<S>) -> Result<IpAddr, String> {
+        from_str_input(v)
+    }
+}
+
+/// IPv4 address in `a.b.c.d` format.
+///
+/// See also [`std::net::Ipv4Addr`] for details.
+#[graphql_scalar(with = ipv4_addr, parse_token(String))]
+type Ipv4Addr = std::net::Ipv4Addr;
+
+mod ipv4_addr {
+    use super::*;
+
+    pub(super) fn to_output<S: ScalarValue>(v: &Ipv4Addr) -> Value<S> {
+        Value::scalar(v.to_string())
+    }
+
+    pub(super) fn from_input<S: ScalarValue>(v: &InputValue<S>) -> Result<Ipv4Addr, String> {
+        from_str_input(v)
+    }
+}
+
+/// IPv6 address in [RFC 5952] format.
+///
+/// See also [`std::net::Ipv6Addr`] for details.
+///
+/// [RFC 5952]: https://datatracker.ietf.org/doc/html/rfc5952
+#[graphql_scalar(with = ipv6_addr, parse_token(String))]
+type Ipv6Addr = std::net::Ipv6Addr;
+
+mod ipv6_addr {
+    use super::*;
+
+    pub(super) fn to_output<S: ScalarValue>(v: &Ipv6Addr) -> Value<S> {
+        Value::scalar(v.to_string())
+    }
+
+    pub(super) fn from_input<S: ScalarValue>(v: &InputValue<S>) -> Result<Ipv6Addr, String> {
+        from_str_input(v)
+    }
+}
+
+/// IPv4 or IPv6 socket address, in `a.b.c.d:port` or `[ipv6]:port` format.
+///
+/// See also [`std::net::SocketAddr`] for details.
+#[graphql_scalar(with = socket_addr, parse_token(String))]
+type SocketAddr = std::net::SocketAddr;
+
+mod socket_addr {
+    use super::*;
+
+    pub(super) fn to_output<S: ScalarValue>(v: &SocketAddr) -> Value<S> {
+        Value::scalar(v.to_string())
+    }
+
+    pub(super) fn from_input<S: ScalarValue>(v: &InputValue<S>) -> Result<SocketAddr, String> {
+        from_str_input(v)
+    }
+}
+
+/// Parses a value out of a `String` with its [`FromStr`] implementation.
+fn from_str_input<T, S>(v: &InputValue<S>) -> Result<T, String>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+    S: ScalarValue,
+{
+    v.as_string_value()
+        .ok_or_else(|| format!("Expected `String`, found: {}", v))
+        .and_then(|s| s.parse().map_err(|e| format!("Invalid `{}`: {}", s, e)))
+}
+
+/// Filesystem path.
+///
+/// Paths not being valid UTF-8 are represented lossily.
+///
+/// See also [`std::path::PathBuf`] for details.
+#[graphql_scalar(with = path_buf, parse_token(String))]
+type PathBuf = std::path::PathBuf;
+
+mod path_buf {
+    use super::*;
+
+    pub(super) fn to_output<S: ScalarValue>(v: &std::path::Path) -> Value<S> {
+        Value::scalar(v.to_string_lossy().into_owned())
+    }
+
+    pub(super) fn from_input<S: ScalarValue>(v: &InputValue<S>) -> Result<PathBuf, String> {
+        v.as_string_value()
+            .map(PathBuf::from)
+            .ok_or_else(|| format!("Expected `String`, found: {}", v))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+        num::{NonZeroI64, NonZeroU32},
+        path::PathBuf,
+    };
+
+    use crate::{
+        execute_sync, graphql_input_value, graphql_object, graphql_value, graphql_vars,
+        EmptyMutation, EmptySubscription, FromInputValue, InputValue, RootNode, ToInputValue,
+    };
+
+    fn from_input<T: FromInputValue>(input: InputValue) -> Result<T, T::Error> {
+        T::from_input_value(&input)
+    }
+
+    fn to_output<T: ToInputValue>(v: T) -> InputValue {
+        v.to_input_value()
+    }
+
+    #[test]
+    fn parses_non_zero_integers() {
+        for (input, expected) in [
+            (graphql_input_value!(1), Some(1)),
+            (graphql_input_value!("4294967295"), Some(u32::MAX)),
+            (graphql_input_value!(0), None),
+            (graphql_input_value!(-1), None),
+            (graphql_input_value!("0"), None),
+            (graphql_input_value!("4294967296"), None),
+            (graphql_input_value!(1.5), None),
+        ] {
+            let parsed = from_input::<NonZeroU32>(input.clone()).ok();
+            assert_eq!(parsed.map(NonZeroU32::get), expected, "input: {}", input);
+        }
+
+        for (input, expected) in [
+            (graphql_input_value!(-1), Some(-1)),
+            (graphql_input_value!("-9223372036854775808"), Some(i64::MIN)),
+            (graphql_input_value!(0), None),
+            (graphql_input_value!("zero"), None),
+            (graphql_input_value!(true), None),
+        ] {
+            let parsed = from_input::<NonZeroI64>(input.clone()).ok();
+            assert_eq!(parsed.map(NonZeroI64::get), expected, "input: {}", input);
+        }
+    }
+
+    #[test]
+    fn formats_non_zero_integers() {
+        let small = NonZeroU32::new(42).unwrap();
+        let large = NonZeroI64::new(i64::MAX).unwrap();
+
+        assert_eq!(to_output(small), graphql_input_value!(42));
+        assert_eq!(
+            to_output(large),
+            graphql_input_value!("9223372036854775807")
+        );
+    }
+
+    #[test]
+    fn parses_addresses() {
+        assert_eq!(
+            from_input::<IpAddr>(graphql_input_value!("::1")),
+            Ok(IpAddr::V6(Ipv6Addr::LOCALHOST)),
+        );
+        assert_eq!(
+            from_input::<Ipv4Addr>(graphql_input_value!("127.0.0.1")),
+            Ok(Ipv4Addr::LOCALHOST),
+        );
+        assert_eq!(
+            from_input::<SocketAddr>(graphql_input_value!("[::1]:8080")),
+            Ok(SocketAddr::new(Ipv6Addr::LOCALHOST.into(), 8080)),
+        );
+
+        assert!(from_input::<Ipv4Addr>(graphql_input_value!("::1")).is_err());
+        assert!(from_input::<Ipv6Addr>(graphql_input_value!("256.0.0.1")).is_err());
+        assert!(from_input::<SocketAddr>(graphql_input_value!("127.0.0.1")).is_err());
+        assert!(from_input::<IpAddr>(graphql_input_value!(127)).is_err());
+    }
+
+    #[test]
+    fn formats_addresses() {
+        let addr = SocketAddr::new(Ipv4Addr::new(10, 0, 0, 1).into(), 443);
+
+        assert_eq!(to_output(addr), graphql_input_value!("10.0.0.1:443"));
+        assert_eq!(to_output(Ipv6Addr::LOCALHOST), graphql_input_value!("::1"),);
+    }
+
+    #[test]
+    fn parses_and_formats_paths() {
+        let path = from_input::<PathBuf>(graphql_input_value!("/tmp/file.txt")).unwrap();
+
+        assert_eq!(path, PathBuf::from("/tmp/file.txt"));
+        assert_eq!(to_output(path), graphql_input_value!("/tmp/file.txt"));
+        assert!(from_input::<PathBuf>(graphql_input_value!(null)).is_err());
+    }
+
+    #[test]
+    fn resolves_in_schema() {
+        struct Query;
+
+        #[graphql_object]
+        impl Query {
+            fn next(id: NonZeroU32) -> Option<NonZeroU32> {
+                id.checked_add(1)
+            }
+
+            fn port(addr: SocketAddr) -> i32 {
+                addr.port().into()
+            }
+
+            fn loopback(v6: bool) -> IpAddr {
+                if v6 {
+                    Ipv6Addr::LOCALHOST.into()
+                } else {
+                    Ipv4Addr::LOCALHOST.into()
+                }
+            }
+        }
+
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+
+        assert_eq!(
+            execute_sync(
+                r#"{
+                    small: next(id: 41)
+                    large: next(id: "4294967294")
+                    port(addr: "127.0.0.1:8080")
+                    loopback(v6: true)
+                }"#,
+                None,
+                &schema,
+                &graphql_vars! {},
+                &(),
+            ),
+            Ok((
+                graphql_value!({
+                    "small": 42,
+                    "large": "4294967295",
+                    "port": 8080,
+                    "loopback": "::1",
+                }),
+                vec![],
+            )),
+        );
+        assert!(
+            execute_sync(r#"{ next(id: 0) }"#, None, &schema, &graphql_vars! {}, &(),).is_err()
+        );
+    }
+}
@@ -5,12 +5,12 @@ use crate::{
     parser::Spanning,
     types::base::{is_excluded, merge_key_into, GraphQLType, GraphQLValue},
     Arguments, BoxFuture, DefaultScalarValue, ExecutionError, Executor, FieldError, Object,
-    ScalarValue, Selection, Value, ValuesStream,
+    ParentValue, ScalarValue, Selection, Value, ValuesStream,
 };
 
 /// Represents the result of executing a GraphQL operation (after parsing and validating has been
 /// done).
-#[derive(Debug, Serialize)]
+#[derive(Clone, Debug, Serialize)]
 pub struct ExecutionOutput<S> {
     /// The output data.
     pub data: Value<S>,
@@ -278,6 +278,11 @@ where
                 .as_ref(),
         )
         .expect("Type not found in schema");
+    let parent = ParentValue::new(
+        meta_type.name().unwrap_or_default(),
+        instance.as_any(),
+        executor.parent_value(),
+    );
 
     for selection in selection_set {
         match selection {
@@ -305,12 +310,14 @@ where
 
                 let exec_vars = executor.variables();
 
-                let sub_exec = executor.field_sub_executor(
-                    response_name,
-                    f.name.item,
-                    *start_pos,
-                    f.selection_set.as_ref().map(|x| &x[..]),
-                );
+                let sub_exec = executor
+                    .field_sub_executor(
+                        response_name,
+                        f.name.item,
+                        *start_pos,
+                        f.selection_set.as_ref().map(|x| &x[..]),
+                    )
+                    .with_parent(&parent);
 
                 let args = Arguments::new(
                     f.arguments.as_ref().map(|m| {
@@ -319,7 +326,7 @@ where
                             .map(|&(ref k, ref v)| (k.item, v.item.clone().into_const(exec_vars)))
                             .collect()
                     }),
-                    &meta_field.arguments,
+                    meta_field.arguments.as_deref(),
                 );
 
                 let is_non_null = meta_field.field_type.is_non_null();
@@ -372,7 +379,7 @@ where
                         match val {
                             Value::Object(o) => {
                                 for (k, v) in o {
-                                    merge_key_into(&mut object, &k, v);
+                                    merge_key_into(&mut object, k, v);
                                 }
                             }
                             // since this was a wrapper of current function,
@@ -405,7 +412,7 @@ where
 
                     if let Ok(Value::Object(obj)) = sub_result {
                         for (k, v) in obj {
-                            merge_key_into(&mut object, &k, v);
+                            merge_key_into(&mut object, k, v);
                         }
                     } else if let Err(e) = sub_result {
                         sub_exec.push_error_at(e, *start_pos);
@@ -417,7 +424,7 @@ where
 
                     if let Ok(Value::Object(obj)) = sub_result {
                         for (k, v) in obj {
-                            merge_key_into(&mut object, &k, v);
+                            merge_key_into(&mut object, k, v);
                         }
                     } else if let Err(e) = sub_result {
                         sub_exec.push_error_at(e, *start_pos);
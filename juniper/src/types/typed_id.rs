@@ -0,0 +1,349 @@
+//! Type-safe [`ID`]s, tagged with the type they identify.
+//!
+//! [`ID`]: crate::ID
+
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+};
+
+use crate::{
+    ast::{FromInputValue, InputValue, Selection, ToInputValue},
+    executor::{ExecutionResult, Executor, Registry},
+    macros::reflect,
+    schema::meta::MetaType,
+    types::{
+        async_await::GraphQLValueAsync,
+        base::{GraphQLType, GraphQLValue},
+        marker::{IsInputType, IsOutputType},
+        scalars::ID,
+    },
+    value::{ScalarValue, Value},
+    BoxFuture,
+};
+
+/// Tag of the type identified by a [`TypedId`], embedded into its GraphQL
+/// representation.
+pub trait IdTag {
+    /// Tag embedded into the [`TypedId`]s of this type.
+    ///
+    /// Should be unique across the schema, and shouldn't contain `:`.
+    const TAG: &'static str;
+}
+
+/// [`ID`] of an object of type `T`, represented in GraphQL as an `ID` with
+/// the [`IdTag`] of `T` embedded (`"User:42"`, for example).
+///
+/// Parses back only from an `ID` with the same tag, so a client passing an ID
+/// of a `User` where an ID of an `Order` is expected gets an error, while the
+/// GraphQL type stays the plain `ID`.
+///
+/// As the `ID` type is shared by all the [`TypedId`]s (and the [`ID`]) of a
+/// schema, an `ID` with a wrong tag passes the validation, and is rejected
+/// only while resolving the field accepting it, with a field error.
+///
+/// ```
+/// # use juniper::{graphql_object, IdTag, TypedId};
+/// #
+/// struct User {
+///     id: i32,
+/// }
+///
+/// impl IdTag for User {
+///     const TAG: &'static str = "User";
+/// }
+///
+/// type UserId = TypedId<User>;
+///
+/// #[graphql_object]
+/// impl User {
+///     fn id(&self) -> UserId {
+///         UserId::new(self.id.to_string())
+///     }
+/// }
+///
+/// let id = UserId::new("42");
+/// assert_eq!(id.value(), "42");
+/// assert_eq!(id.to_string(), "User:42");
+/// assert_eq!("User:42".parse::<UserId>(), Ok(id));
+/// assert!("Order:42".parse::<UserId>().is_err());
+/// ```
+///
+/// [`ID`]: crate::ID
+pub struct TypedId<T> {
+    value: String,
+    _tag: PhantomData<fn() -> T>,
+}
+
+impl<T> TypedId<T> {
+    /// Creates a new [`TypedId`] out of the given untagged `value`.
+    pub fn new(value: impl Into<String>) -> Self {
+        Self {
+            value: value.into(),
+            _tag: PhantomData,
+        }
+    }
+
+    /// Returns the untagged value of this [`TypedId`].
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Converts this [`TypedId`] into its untagged value.
+    pub fn into_value(self) -> String {
+        self.value
+    }
+}
+
+impl<T: IdTag> std::str::FromStr for TypedId<T> {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some((tag, value)) if tag == T::TAG => Ok(Self::new(value)),
+            Some((tag, _)) => Err(format!(
+                "Expected `ID` of `{}`, found `ID` of `{}`",
+                T::TAG,
+                tag,
+            )),
+            None => Err(format!("Expected `ID` of `{}`, found: {}", T::TAG, s)),
+        }
+    }
+}
+
+impl<T: IdTag> fmt::Display for TypedId<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", T::TAG, self.value)
+    }
+}
+
+impl<T> fmt::Debug for TypedId<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("TypedId").field(&self.value).finish()
+    }
+}
+
+// Implemented manually to not require `T` implementing the traits.
+impl<T> Clone for TypedId<T> {
+    fn clone(&self) -> Self {
+        Self::new(self.value.clone())
+    }
+}
+
+impl<T> PartialEq for TypedId<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T> Eq for TypedId<T> {}
+
+impl<T> PartialOrd for TypedId<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for TypedId<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+impl<T> Hash for TypedId<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state)
+    }
+}
+
+// The `ID` meta is reused, so the `ID` type stays the same across all the
+// `TypedId`s of a schema, whatever their tags are.
+impl<T: IdTag, S: ScalarValue> GraphQLType<S> for TypedId<T> {
+    fn name(_: &()) -> Option<&'static str> {
+        Some("ID")
+    }
+
+    fn meta<'r>(info: &(), registry: &mut Registry<'r, S>) -> MetaType<'r, S>
+    where
+        S: 'r,
+    {
+        <ID as GraphQLType<S>>::meta(info, registry)
+    }
+}
+
+impl<T: IdTag, S: ScalarValue> GraphQLValue<S> for TypedId<T> {
+    type Context = ();
+    type TypeInfo = ();
+
+    fn type_name<'i>(&self, info: &'i ()) -> Option<&'i str> {
+        <Self as GraphQLType<S>>::name(info)
+    }
+
+    fn resolve(
+        &self,
+        _: &(),
+        _: Option<&[Selection<S>]>,
+        _: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        Ok(Value::scalar(self.to_string()))
+    }
+}
+
+impl<T: IdTag, S: ScalarValue + Send + Sync> GraphQLValueAsync<S> for TypedId<T> {
+    fn resolve_async<'a>(
+        &'a self,
+        info: &'a (),
+        selection_set: Option<&'a [Selection<S>]>,
+        executor: &'a Executor<Self::Context, S>,
+    ) -> BoxFuture<'a, ExecutionResult<S>> {
+        let v = self.resolve(info, selection_set, executor);
+        Box::pin(futures::future::ready(v))
+    }
+}
+
+impl<T: IdTag, S: ScalarValue> FromInputValue<S> for TypedId<T> {
+    type Error = String;
+
+    fn from_input_value(v: &InputValue<S>) -> Result<Self, Self::Error> {
+        v.as_string_value()
+            .ok_or_else(|| format!("Expected `String`, found: {}", v))
+            .and_then(str::parse)
+    }
+}
+
+impl<T: IdTag, S: ScalarValue> ToInputValue<S> for TypedId<T> {
+    fn to_input_value(&self) -> InputValue<S> {
+        InputValue::scalar(self.to_string())
+    }
+}
+
+impl<T: IdTag, S: ScalarValue> IsInputType<S> for TypedId<T> {}
+
+impl<T: IdTag, S: ScalarValue> IsOutputType<S> for TypedId<T> {}
+
+impl<T: IdTag, S: ScalarValue> reflect::BaseType<S> for TypedId<T> {
+    const NAME: reflect::Type = "ID";
+}
+
+impl<T: IdTag, S: ScalarValue> reflect::BaseSubTypes<S> for TypedId<T> {
+    const NAMES: reflect::Types = &[<Self as reflect::BaseType<S>>::NAME];
+}
+
+impl<T: IdTag, S: ScalarValue> reflect::WrappedType<S> for TypedId<T> {
+    const VALUE: reflect::WrappedValue = 1;
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        execute_sync, graphql_object, graphql_value, graphql_vars, DefaultScalarValue,
+        EmptyMutation, EmptySubscription, FromInputValue as _, InputValue, RootNode,
+        ToInputValue as _,
+    };
+
+    use super::{IdTag, TypedId};
+
+    struct User;
+
+    impl IdTag for User {
+        const TAG: &'static str = "User";
+    }
+
+    struct Order;
+
+    impl IdTag for Order {
+        const TAG: &'static str = "Order";
+    }
+
+    #[test]
+    fn round_trips() {
+        let id = TypedId::<User>::new("42");
+        let input: InputValue = id.to_input_value();
+
+        assert_eq!(input, InputValue::scalar("User:42"));
+        assert_eq!(TypedId::<User>::from_input_value(&input), Ok(id));
+    }
+
+    #[test]
+    fn rejects_other_tags() {
+        let input: InputValue = TypedId::<Order>::new("42").to_input_value();
+        assert_eq!(
+            TypedId::<User>::from_input_value(&input).unwrap_err(),
+            "Expected `ID` of `User`, found `ID` of `Order`",
+        );
+        for input in [
+            InputValue::<DefaultScalarValue>::scalar("42"),
+            InputValue::scalar(42),
+        ] {
+            assert!(TypedId::<User>::from_input_value(&input).is_err());
+        }
+    }
+
+    #[test]
+    fn keeps_value_with_colons() {
+        let id = "User:a:b".parse::<TypedId<User>>().unwrap();
+
+        assert_eq!(id.value(), "a:b");
+        assert_eq!(id.to_string(), "User:a:b");
+    }
+
+    #[test]
+    fn is_id_in_schema() {
+        struct Query;
+
+        #[graphql_object]
+        impl Query {
+            fn user(id: TypedId<User>) -> TypedId<User> {
+                id
+            }
+
+            fn order(id: TypedId<Order>) -> String {
+                id.into_value()
+            }
+        }
+
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+
+        assert_eq!(
+            execute_sync(
+                r#"{
+                    user(id: "User:1")
+                    order(id: "Order:2")
+                    __type(name: "ID") { kind }
+                }"#,
+                None,
+                &schema,
+                &graphql_vars! {},
+                &(),
+            ),
+            Ok((
+                graphql_value!({
+                    "user": "User:1",
+                    "order": "2",
+                    "__type": {"kind": "SCALAR"},
+                }),
+                vec![],
+            )),
+        );
+
+        let (res, errs) = execute_sync(
+            r#"{ order(id: "User:1") }"#,
+            None,
+            &schema,
+            &graphql_vars! {},
+            &(),
+        )
+        .unwrap();
+        assert_eq!(res, graphql_value!(null));
+        assert_eq!(
+            errs[0].error().message(),
+            "Expected `ID` of `Order`, found `ID` of `User`",
+        );
+    }
+}
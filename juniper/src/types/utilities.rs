@@ -1,5 +1,6 @@
 use crate::{
     ast::InputValue,
+    parser::{SourcePosition, Spanning},
     schema::{
         meta::{EnumMeta, InputObjectMeta, MetaType},
         model::{SchemaType, TypeType},
@@ -8,40 +9,61 @@ use crate::{
 };
 use std::collections::HashSet;
 
-pub fn is_valid_literal_value<S>(
+/// Returns the position of the innermost part of the literal `arg_value`,
+/// which is not valid for the `arg_type`, if any.
+pub fn invalid_literal_value_position<S>(
+    schema: &SchemaType<S>,
+    arg_type: &TypeType<S>,
+    arg_value: &Spanning<InputValue<S>>,
+) -> Option<SourcePosition>
+where
+    S: ScalarValue,
+{
+    check_literal_value(schema, arg_type, &arg_value.item)
+        .err()
+        .map(|pos| pos.unwrap_or(arg_value.start))
+}
+
+/// Checks whether the literal `arg_value` is valid for the `arg_type`.
+///
+/// Errors with [`None`] if the `arg_value` itself is invalid, or with the
+/// position of its invalid nested value.
+fn check_literal_value<S>(
     schema: &SchemaType<S>,
     arg_type: &TypeType<S>,
     arg_value: &InputValue<S>,
-) -> bool
+) -> Result<(), Option<SourcePosition>>
 where
     S: ScalarValue,
 {
+    let nested = |value: &Spanning<InputValue<S>>, ty: &TypeType<S>| {
+        check_literal_value(schema, ty, &value.item).map_err(|pos| Some(pos.unwrap_or(value.start)))
+    };
+
     match *arg_type {
         TypeType::NonNull(ref inner) => {
             if arg_value.is_null() {
-                false
+                Err(None)
             } else {
-                is_valid_literal_value(schema, inner, arg_value)
+                check_literal_value(schema, inner, arg_value)
             }
         }
         TypeType::List(ref inner, expected_size) => match *arg_value {
             InputValue::List(ref items) => {
                 if let Some(expected) = expected_size {
                     if items.len() != expected {
-                        return false;
+                        return Err(None);
                     }
                 }
-                items
-                    .iter()
-                    .all(|i| is_valid_literal_value(schema, inner, &i.item))
+                items.iter().try_for_each(|i| nested(i, inner))
             }
             ref v => {
                 if let Some(expected) = expected_size {
                     if expected != 1 {
-                        return false;
+                        return Err(None);
                     }
                 }
-                is_valid_literal_value(schema, inner, v)
+                check_literal_value(schema, inner, v)
             }
         },
         TypeType::Concrete(t) => {
@@ -50,19 +72,25 @@ where
             if let (&InputValue::Scalar(_), Some(&MetaType::Enum(EnumMeta { .. }))) =
                 (arg_value, arg_type.to_concrete())
             {
-                return false;
+                return Err(None);
             }
 
             match *arg_value {
-                InputValue::Null | InputValue::Variable(_) => true,
+                InputValue::Null | InputValue::Variable(_) => Ok(()),
                 ref v @ InputValue::Scalar(_) | ref v @ InputValue::Enum(_) => {
-                    if let Some(parse_fn) = t.input_value_parse_fn() {
-                        parse_fn(v).is_ok()
-                    } else {
-                        false
+                    if let (InputValue::Enum(name), MetaType::Enum(EnumMeta { values, .. })) =
+                        (v, t)
+                    {
+                        if !values.iter().any(|ev| &ev.name == name) {
+                            return Err(None);
+                        }
+                    }
+                    match t.input_value_parse_fn() {
+                        Some(parse_fn) if parse_fn(v).is_ok() => Ok(()),
+                        _ => Err(None),
                     }
                 }
-                InputValue::List(_) => false,
+                InputValue::List(_) => Err(None),
                 InputValue::Object(ref obj) => {
                     if let MetaType::InputObject(InputObjectMeta {
                         ref input_fields, ..
@@ -72,30 +100,34 @@ where
                             .iter()
                             .filter_map(|f| {
                                 if f.arg_type.is_non_null() {
-                                    Some(&f.name)
+                                    Some(f.name.as_str())
                                 } else {
                                     None
                                 }
                             })
                             .collect::<HashSet<_>>();
 
-                        let all_types_ok = obj.iter().all(|&(ref key, ref value)| {
-                            remaining_required_fields.remove(&key.item);
+                        obj.iter().try_for_each(|&(ref key, ref value)| {
+                            remaining_required_fields.remove(key.item.as_str());
                             if let Some(ref arg_type) = input_fields
                                 .iter()
                                 .filter(|f| f.name == key.item)
                                 .map(|f| schema.make_type(&f.arg_type))
                                 .next()
                             {
-                                is_valid_literal_value(schema, arg_type, &value.item)
+                                nested(value, arg_type)
                             } else {
-                                false
+                                Err(Some(key.start))
                             }
-                        });
+                        })?;
 
-                        all_types_ok && remaining_required_fields.is_empty()
+                        if remaining_required_fields.is_empty() {
+                            Ok(())
+                        } else {
+                            Err(None)
+                        }
                     } else {
-                        false
+                        Err(None)
                     }
                 }
             }
@@ -1,4 +1,8 @@
-use std::borrow::Cow;
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
 
 /// Convert string to camel case.
 ///
@@ -51,3 +55,95 @@ fn test_to_camel_case() {
     assert_eq!(&to_camel_case("a")[..], "a");
     assert_eq!(&to_camel_case("")[..], "");
 }
+
+/// Map of a bounded capacity keyed by strings, evicting its least recently
+/// used entries once the capacity is reached.
+#[derive(Debug)]
+pub(crate) struct LruMap<V> {
+    capacity: usize,
+    tick: u64,
+    entries: HashMap<Arc<str>, (V, u64)>,
+    recency: BTreeMap<u64, Arc<str>>,
+}
+
+impl<V: Clone> LruMap<V> {
+    /// Creates a new empty [`LruMap`] holding at most `capacity` entries.
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            tick: 0,
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the number of entries in this [`LruMap`].
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns the value of the given `key`, if any, marking it as the most
+    /// recently used one.
+    pub(crate) fn get(&mut self, key: &str) -> Option<V> {
+        let (key, tick) = self
+            .entries
+            .get_key_value(key)
+            .map(|(k, (_, tick))| (Arc::clone(k), *tick))?;
+        self.recency.remove(&tick);
+        let tick = self.next_tick();
+        self.recency.insert(tick, Arc::clone(&key));
+
+        let entry = self.entries.get_mut(&key).unwrap();
+        entry.1 = tick;
+        Some(entry.0.clone())
+    }
+
+    /// Inserts the given `value` of the given `key`, as the most recently used
+    /// one, evicting the least recently used entry if the capacity is reached.
+    pub(crate) fn insert(&mut self, key: Arc<str>, value: V) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if let Some((_, tick)) = self.entries.remove(&key) {
+            self.recency.remove(&tick);
+        } else if self.entries.len() >= self.capacity {
+            if let Some(oldest) = self.recency.keys().next().copied() {
+                let evicted = self.recency.remove(&oldest).unwrap();
+                self.entries.remove(&evicted);
+            }
+        }
+
+        let tick = self.next_tick();
+        self.recency.insert(tick, Arc::clone(&key));
+        self.entries.insert(key, (value, tick));
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+}
+
+#[test]
+fn lru_map_evicts_least_recently_used() {
+    let mut map = LruMap::new(2);
+    map.insert("a".into(), 1);
+    map.insert("b".into(), 2);
+    map.insert("a".into(), 3);
+    map.insert("c".into(), 4);
+
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get("b"), None);
+    assert_eq!(map.get("a"), Some(3));
+    assert_eq!(map.get("c"), Some(4));
+}
+
+#[test]
+fn lru_map_of_zero_capacity_is_empty() {
+    let mut map = LruMap::new(0);
+    map.insert("a".into(), 1);
+
+    assert_eq!(map.len(), 0);
+    assert_eq!(map.get("a"), None);
+}
@@ -0,0 +1,272 @@
+//! Built-in and custom validation rules wrapped with their configuration.
+
+use std::time::{Duration, Instant};
+
+use crate::{
+    ast::{
+        Directive, Document, Field, Fragment, FragmentSpread, InlineFragment, InputValue,
+        Operation, Selection, VariableDefinition,
+    },
+    parser::Spanning,
+    validation::{RuleSeverity, ValidatorContext, Visitor},
+    value::ScalarValue,
+};
+
+/// Validation rule visited along with the other ones in a single pass over a
+/// document, according to its [`RuleSeverity`].
+///
+/// Violations of a [`RuleSeverity::Warning`] rule are downgraded to warnings
+/// right after being reported, and a [`RuleSeverity::Off`] rule is not
+/// visited at all, so relaxing a rule doesn't require an extra pass.
+///
+/// Measures the time spent in the rule (unless it's off), if the
+/// [`ValidatorContext`] records per-rule timings.
+pub(crate) struct ConfiguredRule<V> {
+    name: &'static str,
+    severity: RuleSeverity,
+    elapsed: Option<Duration>,
+    rule: V,
+}
+
+impl<V> ConfiguredRule<V> {
+    /// Wraps the given `rule` identified by the given `name`, to be visited
+    /// with the given `severity` in the provided [`ValidatorContext`].
+    pub(crate) fn new<S: ScalarValue>(
+        ctx: &ValidatorContext<'_, S>,
+        name: &'static str,
+        severity: RuleSeverity,
+        rule: V,
+    ) -> Self {
+        Self {
+            name,
+            severity,
+            elapsed: (ctx.times_rules() && severity != RuleSeverity::Off).then(Duration::default),
+            rule,
+        }
+    }
+
+    /// Runs the given callback `f` of the wrapped rule, according to its
+    /// [`RuleSeverity`].
+    fn run<'a, S: ScalarValue>(
+        &mut self,
+        ctx: &mut ValidatorContext<'a, S>,
+        f: impl FnOnce(&mut V, &mut ValidatorContext<'a, S>),
+    ) {
+        let errors = match self.severity {
+            RuleSeverity::Error => None,
+            RuleSeverity::Warning => Some(ctx.errors_count()),
+            RuleSeverity::Off => return,
+        };
+
+        match &mut self.elapsed {
+            Some(elapsed) => {
+                let start = Instant::now();
+                f(&mut self.rule, ctx);
+                *elapsed += start.elapsed();
+            }
+            None => f(&mut self.rule, ctx),
+        }
+
+        if let Some(errors) = errors {
+            ctx.downgrade_errors_since(errors);
+        }
+    }
+}
+
+impl<'a, S, V> Visitor<'a, S> for ConfiguredRule<V>
+where
+    S: ScalarValue,
+    V: Visitor<'a, S>,
+{
+    fn enter_document(&mut self, ctx: &mut ValidatorContext<'a, S>, doc: &'a Document<S>) {
+        self.run(ctx, |rule, ctx| rule.enter_document(ctx, doc));
+    }
+    fn exit_document(&mut self, ctx: &mut ValidatorContext<'a, S>, doc: &'a Document<S>) {
+        self.run(ctx, |rule, ctx| rule.exit_document(ctx, doc));
+        if let Some(elapsed) = self.elapsed {
+            ctx.push_rule_timing(self.name, elapsed);
+        }
+    }
+
+    fn enter_operation_definition(
+        &mut self,
+        ctx: &mut ValidatorContext<'a, S>,
+        op: &'a Spanning<Operation<S>>,
+    ) {
+        self.run(ctx, |rule, ctx| rule.enter_operation_definition(ctx, op));
+    }
+    fn exit_operation_definition(
+        &mut self,
+        ctx: &mut ValidatorContext<'a, S>,
+        op: &'a Spanning<Operation<S>>,
+    ) {
+        self.run(ctx, |rule, ctx| rule.exit_operation_definition(ctx, op));
+    }
+
+    fn enter_fragment_definition(
+        &mut self,
+        ctx: &mut ValidatorContext<'a, S>,
+        f: &'a Spanning<Fragment<S>>,
+    ) {
+        self.run(ctx, |rule, ctx| rule.enter_fragment_definition(ctx, f));
+    }
+    fn exit_fragment_definition(
+        &mut self,
+        ctx: &mut ValidatorContext<'a, S>,
+        f: &'a Spanning<Fragment<S>>,
+    ) {
+        self.run(ctx, |rule, ctx| rule.exit_fragment_definition(ctx, f));
+    }
+
+    fn enter_variable_definition(
+        &mut self,
+        ctx: &mut ValidatorContext<'a, S>,
+        def: &'a (Spanning<&'a str>, VariableDefinition<S>),
+    ) {
+        self.run(ctx, |rule, ctx| rule.enter_variable_definition(ctx, def));
+    }
+    fn exit_variable_definition(
+        &mut self,
+        ctx: &mut ValidatorContext<'a, S>,
+        def: &'a (Spanning<&'a str>, VariableDefinition<S>),
+    ) {
+        self.run(ctx, |rule, ctx| rule.exit_variable_definition(ctx, def));
+    }
+
+    fn enter_directive(
+        &mut self,
+        ctx: &mut ValidatorContext<'a, S>,
+        d: &'a Spanning<Directive<S>>,
+    ) {
+        self.run(ctx, |rule, ctx| rule.enter_directive(ctx, d));
+    }
+    fn exit_directive(&mut self, ctx: &mut ValidatorContext<'a, S>, d: &'a Spanning<Directive<S>>) {
+        self.run(ctx, |rule, ctx| rule.exit_directive(ctx, d));
+    }
+
+    fn enter_argument(
+        &mut self,
+        ctx: &mut ValidatorContext<'a, S>,
+        arg: &'a (Spanning<&'a str>, Spanning<InputValue<S>>),
+    ) {
+        self.run(ctx, |rule, ctx| rule.enter_argument(ctx, arg));
+    }
+    fn exit_argument(
+        &mut self,
+        ctx: &mut ValidatorContext<'a, S>,
+        arg: &'a (Spanning<&'a str>, Spanning<InputValue<S>>),
+    ) {
+        self.run(ctx, |rule, ctx| rule.exit_argument(ctx, arg));
+    }
+
+    fn enter_selection_set(&mut self, ctx: &mut ValidatorContext<'a, S>, s: &'a [Selection<S>]) {
+        self.run(ctx, |rule, ctx| rule.enter_selection_set(ctx, s));
+    }
+    fn exit_selection_set(&mut self, ctx: &mut ValidatorContext<'a, S>, s: &'a [Selection<S>]) {
+        self.run(ctx, |rule, ctx| rule.exit_selection_set(ctx, s));
+    }
+
+    fn enter_field(&mut self, ctx: &mut ValidatorContext<'a, S>, f: &'a Spanning<Field<S>>) {
+        self.run(ctx, |rule, ctx| rule.enter_field(ctx, f));
+    }
+    fn exit_field(&mut self, ctx: &mut ValidatorContext<'a, S>, f: &'a Spanning<Field<S>>) {
+        self.run(ctx, |rule, ctx| rule.exit_field(ctx, f));
+    }
+
+    fn enter_fragment_spread(
+        &mut self,
+        ctx: &mut ValidatorContext<'a, S>,
+        s: &'a Spanning<FragmentSpread<S>>,
+    ) {
+        self.run(ctx, |rule, ctx| rule.enter_fragment_spread(ctx, s));
+    }
+    fn exit_fragment_spread(
+        &mut self,
+        ctx: &mut ValidatorContext<'a, S>,
+        s: &'a Spanning<FragmentSpread<S>>,
+    ) {
+        self.run(ctx, |rule, ctx| rule.exit_fragment_spread(ctx, s));
+    }
+
+    fn enter_inline_fragment(
+        &mut self,
+        ctx: &mut ValidatorContext<'a, S>,
+        f: &'a Spanning<InlineFragment<S>>,
+    ) {
+        self.run(ctx, |rule, ctx| rule.enter_inline_fragment(ctx, f));
+    }
+    fn exit_inline_fragment(
+        &mut self,
+        ctx: &mut ValidatorContext<'a, S>,
+        f: &'a Spanning<InlineFragment<S>>,
+    ) {
+        self.run(ctx, |rule, ctx| rule.exit_inline_fragment(ctx, f));
+    }
+
+    fn enter_null_value(&mut self, ctx: &mut ValidatorContext<'a, S>, n: Spanning<()>) {
+        self.run(ctx, |rule, ctx| rule.enter_null_value(ctx, n));
+    }
+    fn exit_null_value(&mut self, ctx: &mut ValidatorContext<'a, S>, n: Spanning<()>) {
+        self.run(ctx, |rule, ctx| rule.exit_null_value(ctx, n));
+    }
+
+    fn enter_scalar_value(&mut self, ctx: &mut ValidatorContext<'a, S>, n: Spanning<&'a S>) {
+        self.run(ctx, |rule, ctx| rule.enter_scalar_value(ctx, n));
+    }
+    fn exit_scalar_value(&mut self, ctx: &mut ValidatorContext<'a, S>, n: Spanning<&'a S>) {
+        self.run(ctx, |rule, ctx| rule.exit_scalar_value(ctx, n));
+    }
+
+    fn enter_enum_value(&mut self, ctx: &mut ValidatorContext<'a, S>, s: Spanning<&'a String>) {
+        self.run(ctx, |rule, ctx| rule.enter_enum_value(ctx, s));
+    }
+    fn exit_enum_value(&mut self, ctx: &mut ValidatorContext<'a, S>, s: Spanning<&'a String>) {
+        self.run(ctx, |rule, ctx| rule.exit_enum_value(ctx, s));
+    }
+
+    fn enter_variable_value(&mut self, ctx: &mut ValidatorContext<'a, S>, s: Spanning<&'a String>) {
+        self.run(ctx, |rule, ctx| rule.enter_variable_value(ctx, s));
+    }
+    fn exit_variable_value(&mut self, ctx: &mut ValidatorContext<'a, S>, s: Spanning<&'a String>) {
+        self.run(ctx, |rule, ctx| rule.exit_variable_value(ctx, s));
+    }
+
+    fn enter_list_value(
+        &mut self,
+        ctx: &mut ValidatorContext<'a, S>,
+        l: Spanning<&'a Vec<Spanning<InputValue<S>>>>,
+    ) {
+        self.run(ctx, |rule, ctx| rule.enter_list_value(ctx, l));
+    }
+    fn exit_list_value(
+        &mut self,
+        ctx: &mut ValidatorContext<'a, S>,
+        l: Spanning<&'a Vec<Spanning<InputValue<S>>>>,
+    ) {
+        self.run(ctx, |rule, ctx| rule.exit_list_value(ctx, l));
+    }
+
+    fn enter_object_value(&mut self, ctx: &mut ValidatorContext<'a, S>, o: SpannedObject<'a, S>) {
+        self.run(ctx, |rule, ctx| rule.enter_object_value(ctx, o));
+    }
+    fn exit_object_value(&mut self, ctx: &mut ValidatorContext<'a, S>, o: SpannedObject<'a, S>) {
+        self.run(ctx, |rule, ctx| rule.exit_object_value(ctx, o));
+    }
+
+    fn enter_object_field(
+        &mut self,
+        ctx: &mut ValidatorContext<'a, S>,
+        f: &'a (Spanning<String>, Spanning<InputValue<S>>),
+    ) {
+        self.run(ctx, |rule, ctx| rule.enter_object_field(ctx, f));
+    }
+    fn exit_object_field(
+        &mut self,
+        ctx: &mut ValidatorContext<'a, S>,
+        f: &'a (Spanning<String>, Spanning<InputValue<S>>),
+    ) {
+        self.run(ctx, |rule, ctx| rule.exit_object_field(ctx, f));
+    }
+}
+
+type SpannedObject<'a, S> = Spanning<&'a Vec<(Spanning<String>, Spanning<InputValue<S>>)>>;
@@ -1,31 +1,54 @@
 use std::{
+    cmp::Ordering,
     collections::HashSet,
     fmt::{self, Debug},
+    time::Duration,
 };
 
 use crate::ast::{Definition, Document, Type};
 
-use crate::schema::{meta::MetaType, model::SchemaType};
+use crate::schema::{
+    meta::MetaType,
+    model::{DirectiveType, SchemaType},
+};
 
-use crate::parser::SourcePosition;
+use crate::{executor::Limits, parser::SourcePosition, value::Value};
 
 /// Query validation error
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+///
+/// Along with the human-readable message, may carry the stable identifier of
+/// the rule it's reported by (like `NoUnusedFragments` or `MaxAliases`) and
+/// the structured parameters of the failure (like `{max: 3, actual: 5}`),
+/// both serialized into the `extensions` of the error, so clients may react
+/// to specific rule failures without parsing the message.
+///
+/// Errors are compared by their locations and messages only.
+#[derive(Clone, Debug)]
 pub struct RuleError {
     locations: Vec<SourcePosition>,
     message: String,
+    rule: Option<&'static str>,
+    params: Vec<(&'static str, Value)>,
 }
 
-#[doc(hidden)]
-pub struct ValidatorContext<'a, S: Debug + 'a> {
+/// Context of a GraphQL document being validated, tracking the types of the
+/// visited selections and collecting the errors reported by [`Visitor`]s.
+///
+/// [`Visitor`]: crate::validation::Visitor
+pub struct ValidatorContext<'a, S: 'a> {
+    /// Schema the document is validated against.
     pub schema: &'a SchemaType<'a, S>,
     errors: Vec<RuleError>,
+    warnings: Vec<RuleError>,
     type_stack: Vec<Option<&'a MetaType<'a, S>>>,
     type_literal_stack: Vec<Option<Type<'a>>>,
     input_type_stack: Vec<Option<&'a MetaType<'a, S>>>,
     input_type_literal_stack: Vec<Option<Type<'a>>>,
     parent_type_stack: Vec<Option<&'a MetaType<'a, S>>>,
+    directive_stack: Vec<Option<&'a DirectiveType<'a, S>>>,
     fragment_names: HashSet<&'a str>,
+    limits: Limits,
+    rule_timings: Option<Vec<(&'static str, Duration)>>,
 }
 
 impl RuleError {
@@ -34,9 +57,26 @@ impl RuleError {
         RuleError {
             message: message.to_owned(),
             locations: locations.to_vec(),
+            rule: None,
+            params: Vec::new(),
         }
     }
 
+    /// Sets the stable identifier of the `rule` this error is reported by.
+    #[must_use]
+    pub fn with_rule(mut self, rule: &'static str) -> Self {
+        self.rule = Some(rule);
+        self
+    }
+
+    /// Adds the structured parameter with the given `name` and `value` to
+    /// this error.
+    #[must_use]
+    pub fn with_param(mut self, name: &'static str, value: impl Into<Value>) -> Self {
+        self.params.push((name, value.into()));
+        self
+    }
+
     /// Access the message for a validation error
     pub fn message(&self) -> &str {
         &self.message
@@ -49,6 +89,44 @@ impl RuleError {
     pub fn locations(&self) -> &[SourcePosition] {
         &self.locations
     }
+
+    /// Returns the stable identifier of the rule this error is reported by,
+    /// if any.
+    pub fn rule(&self) -> Option<&'static str> {
+        self.rule
+    }
+
+    /// Returns the structured parameters of this error, in the order they
+    /// were added.
+    pub fn params(&self) -> &[(&'static str, Value)] {
+        &self.params
+    }
+
+    /// Returns the value of the structured parameter with the given `name`,
+    /// if any.
+    pub fn param(&self, name: &str) -> Option<&Value> {
+        self.params.iter().find(|(n, _)| *n == name).map(|(_, v)| v)
+    }
+}
+
+impl PartialEq for RuleError {
+    fn eq(&self, other: &Self) -> bool {
+        self.locations == other.locations && self.message == other.message
+    }
+}
+
+impl Eq for RuleError {}
+
+impl PartialOrd for RuleError {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for RuleError {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (&self.locations, &self.message).cmp(&(&other.locations, &other.message))
+    }
 }
 
 impl fmt::Display for RuleError {
@@ -71,12 +149,14 @@ impl<'a, S: Debug> ValidatorContext<'a, S> {
     pub fn new(schema: &'a SchemaType<S>, document: &Document<'a, S>) -> ValidatorContext<'a, S> {
         ValidatorContext {
             errors: Vec::new(),
+            warnings: Vec::new(),
             schema,
             type_stack: Vec::new(),
             type_literal_stack: Vec::new(),
             parent_type_stack: Vec::new(),
             input_type_stack: Vec::new(),
             input_type_literal_stack: Vec::new(),
+            directive_stack: Vec::new(),
             fragment_names: document
                 .iter()
                 .filter_map(|def| match *def {
@@ -84,6 +164,11 @@ impl<'a, S: Debug> ValidatorContext<'a, S> {
                     _ => None,
                 })
                 .collect(),
+            limits: *schema.limits(),
+            rule_timings: schema
+                .executor_options()
+                .times_validation_rules()
+                .then(Vec::new),
         }
     }
 
@@ -92,15 +177,102 @@ impl<'a, S: Debug> ValidatorContext<'a, S> {
         self.errors.append(&mut errors);
     }
 
-    #[doc(hidden)]
+    /// Reports a validation error with the given `message` at the given
+    /// `locations`, making the document invalid.
     pub fn report_error(&mut self, message: &str, locations: &[SourcePosition]) {
         self.errors.push(RuleError::new(message, locations))
     }
 
+    /// Reports a validation error of the given `rule` with the given `message`
+    /// at the given `locations`, making the document invalid.
+    ///
+    /// The `rule` identifier is serialized into the `extensions` of the error.
+    pub fn report_rule_error(
+        &mut self,
+        rule: &'static str,
+        message: &str,
+        locations: &[SourcePosition],
+    ) {
+        self.errors
+            .push(RuleError::new(message, locations).with_rule(rule))
+    }
+
+    /// Returns the warnings found by the rules downgraded to the
+    /// [`RuleSeverity::Warning`], which don't make the document invalid.
+    ///
+    /// [`RuleSeverity::Warning`]: crate::RuleSeverity::Warning
+    pub fn warnings(&self) -> &[RuleError] {
+        &self.warnings
+    }
+
+    /// Returns the [`Limits`] the document is validated against, being the
+    /// ones of the schema, unless overridden with [`set_limits()`].
+    ///
+    /// [`set_limits()`]: ValidatorContext::set_limits
+    pub(crate) fn limits(&self) -> &Limits {
+        &self.limits
+    }
+
+    /// Overrides the [`Limits`] of the schema the document is validated
+    /// against (for example, with the ones of a single request).
+    pub(crate) fn set_limits(&mut self, limits: &Limits) {
+        self.limits = *limits;
+    }
+
+    /// Indicates whether the time spent in every validation rule is measured.
+    pub(crate) fn times_rules(&self) -> bool {
+        self.rule_timings.is_some()
+    }
+
+    /// Records the given time `elapsed` in the validation rule with the given
+    /// `name`, if the timings are measured.
+    pub(crate) fn push_rule_timing(&mut self, name: &'static str, elapsed: Duration) {
+        if let Some(timings) = &mut self.rule_timings {
+            timings.push((name, elapsed));
+        }
+    }
+
+    /// Returns the time spent in every validation rule visited so far, along
+    /// with its name, if enabled with [`ExecutorOptions::time_validation_rules()`].
+    ///
+    /// Empty otherwise.
+    ///
+    /// [`ExecutorOptions::time_validation_rules()`]: crate::ExecutorOptions::time_validation_rules
+    pub fn rule_timings(&self) -> &[(&'static str, Duration)] {
+        self.rule_timings.as_deref().unwrap_or_default()
+    }
+
+    /// Records the found warnings and the measured per-rule timings into the
+    /// [`MetricsSink`] of the schema, if there are any.
+    ///
+    /// [`MetricsSink`]: crate::MetricsSink
+    pub(crate) fn record_metrics(&self) {
+        if let Some(sink) = self.schema.executor_options().sink() {
+            if !self.warnings.is_empty() {
+                sink.record_validation_warnings(&self.warnings);
+            }
+            if !self.rule_timings().is_empty() {
+                sink.record_validation_timings(self.rule_timings());
+            }
+        }
+    }
+
     pub(crate) fn has_errors(&self) -> bool {
         !self.errors.is_empty()
     }
 
+    /// Returns the number of errors reported so far.
+    pub(crate) fn errors_count(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Turns the errors reported after the first `count` ones into warnings.
+    pub(crate) fn downgrade_errors_since(&mut self, count: usize) {
+        let mut downgraded = self.errors.split_off(count);
+        self.warnings.append(&mut downgraded);
+        self.warnings.sort();
+    }
+
     #[doc(hidden)]
     pub fn into_errors(mut self) -> Vec<RuleError> {
         self.errors.sort();
@@ -165,6 +337,20 @@ impl<'a, S: Debug> ValidatorContext<'a, S> {
     }
 
     #[doc(hidden)]
+    pub fn with_pushed_directive<F, R>(&mut self, name: &str, f: F) -> R
+    where
+        F: FnOnce(&mut ValidatorContext<'a, S>) -> R,
+    {
+        self.directive_stack
+            .push(self.schema.directive_by_name(name));
+        let res = f(self);
+        self.directive_stack.pop();
+
+        res
+    }
+
+    /// Returns the schema type of the selection being currently visited, if
+    /// it's known to the schema.
     pub fn current_type(&self) -> Option<&'a MetaType<'a, S>> {
         *self.type_stack.last().unwrap_or(&None)
     }
@@ -177,7 +363,8 @@ impl<'a, S: Debug> ValidatorContext<'a, S> {
         }
     }
 
-    #[doc(hidden)]
+    /// Returns the schema type the field being currently visited is selected
+    /// on, if it's known to the schema.
     pub fn parent_type(&self) -> Option<&'a MetaType<'a, S>> {
         *self.parent_type_stack.last().unwrap_or(&None)
     }
@@ -190,6 +377,12 @@ impl<'a, S: Debug> ValidatorContext<'a, S> {
         }
     }
 
+    /// Returns the schema declaration of the directive being currently
+    /// visited, if it's known to the schema.
+    pub fn current_directive(&self) -> Option<&'a DirectiveType<'a, S>> {
+        *self.directive_stack.last().unwrap_or(&None)
+    }
+
     #[doc(hidden)]
     pub fn is_known_fragment(&self, name: &str) -> bool {
         self.fragment_names.contains(name)
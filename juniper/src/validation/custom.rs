@@ -0,0 +1,56 @@
+//! Custom validation rules registered per schema.
+
+use std::{any::type_name, fmt, sync::Arc};
+
+use crate::{
+    ast::Document,
+    validation::{visit, ConfiguredRule, MultiVisitorNil, RuleSeverity, ValidatorContext, Visitor},
+    value::ScalarValue,
+};
+
+/// Type-erased visiting of a document with a fresh instance of a custom rule.
+type VisitFn<S> = dyn for<'a> Fn(&mut ValidatorContext<'a, S>, &'a Document<'a, S>) + Send + Sync;
+
+/// Custom validation rule, registered for a whole schema with
+/// [`RootNode::with_validation_rule()`].
+///
+/// [`RootNode::with_validation_rule()`]: crate::RootNode::with_validation_rule
+pub(crate) struct CustomRule<S> {
+    visit: Arc<VisitFn<S>>,
+}
+
+impl<S> CustomRule<S>
+where
+    S: ScalarValue,
+{
+    /// Creates a new [`CustomRule`] out of the given `factory` of its
+    /// [`Visitor`]s, creating a fresh one for every validated document.
+    ///
+    /// The type name of the [`Visitor`] identifies the rule in the per-rule
+    /// timings.
+    pub(crate) fn new<F, V>(factory: F) -> Self
+    where
+        F: Fn() -> V + Send + Sync + 'static,
+        V: for<'a> Visitor<'a, S> + 'static,
+    {
+        Self {
+            visit: Arc::new(move |ctx, doc| {
+                let rule =
+                    ConfiguredRule::new(ctx, type_name::<V>(), RuleSeverity::Error, factory());
+                visit(&mut MultiVisitorNil.with(rule), ctx, doc)
+            }),
+        }
+    }
+
+    /// Visits the given document with this [`CustomRule`], reporting its
+    /// violations into the provided [`ValidatorContext`].
+    pub(crate) fn visit<'a>(&self, ctx: &mut ValidatorContext<'a, S>, doc: &'a Document<'a, S>) {
+        (self.visit)(ctx, doc)
+    }
+}
+
+impl<S> fmt::Debug for CustomRule<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CustomRule").finish_non_exhaustive()
+    }
+}
@@ -53,13 +53,17 @@ fn validate_var_defs<S>(
                 let ct = schema.make_type(&def.var_type.item);
 
                 if def.var_type.item.is_non_null() && is_absent_or_null(values.get(name.item)) {
-                    errors.push(RuleError::new(
-                        &format!(
-                            r#"Variable "${}" of required type "{}" was not provided."#,
-                            name.item, def.var_type.item,
-                        ),
-                        &[name.start],
-                    ));
+                    errors.push(
+                        RuleError::new(
+                            &format!(
+                                r#"Variable "${}" of required type "{}" was not provided."#,
+                                name.item, def.var_type.item,
+                            ),
+                            &[name.start],
+                        )
+                        .with_rule("VariableValuesOfCorrectType")
+                        .with_param("variable", name.item),
+                    );
                 } else if let Some(v) = values.get(name.item) {
                     errors.append(&mut unify_value(
                         name.item,
@@ -346,7 +350,7 @@ fn is_absent_or_null<S>(v: Option<&InputValue<S>>) -> bool
 where
     S: ScalarValue,
 {
-    v.map_or(true, InputValue::is_null)
+    v.is_none_or(InputValue::is_null)
 }
 
 fn unification_error<'a>(
@@ -362,6 +366,8 @@ fn unification_error<'a>(
         ),
         &[*var_pos],
     )
+    .with_rule("VariableValuesOfCorrectType")
+    .with_param("variable", var_name)
 }
 
 impl<'a> fmt::Display for Path<'a> {
@@ -0,0 +1,184 @@
+//! Linting of stored client operations against a schema.
+
+use std::fmt;
+
+use crate::{
+    ast::Document,
+    parser::{parse_document_source, SourcePosition},
+    schema::model::SchemaType,
+    validation::{
+        rules::{visit_deprecated_usage, visit_undefined_variables, visit_unknown_fields},
+        visit_all_rules, RuleError, ValidatorContext,
+    },
+    value::ScalarValue,
+};
+
+/// Kind of a [`LintIssue`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum LintIssueKind {
+    /// Document cannot be parsed.
+    Syntax,
+
+    /// Field is not defined on the type it's selected on.
+    UnknownField,
+
+    /// Variable is used, but not defined by its operation.
+    UndefinedVariable,
+
+    /// Deprecated field, argument or enum value is used.
+    ///
+    /// Doesn't make the document invalid.
+    Deprecated,
+
+    /// Document fails any other validation rule.
+    Invalid,
+}
+
+/// Single issue found by linting a client document against a schema.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LintIssue {
+    kind: LintIssueKind,
+    message: String,
+    locations: Vec<SourcePosition>,
+}
+
+impl LintIssue {
+    fn new(kind: LintIssueKind, message: String, locations: Vec<SourcePosition>) -> Self {
+        Self {
+            kind,
+            message,
+            locations,
+        }
+    }
+
+    fn from_rule_error(kind: LintIssueKind, err: &RuleError) -> Self {
+        Self::new(kind, err.message().into(), err.locations().into())
+    }
+
+    /// Returns the [`LintIssueKind`] of this [`LintIssue`].
+    pub fn kind(&self) -> LintIssueKind {
+        self.kind
+    }
+
+    /// Returns the human-readable message of this [`LintIssue`].
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the positions in the document this [`LintIssue`] relates to.
+    pub fn locations(&self) -> &[SourcePosition] {
+        &self.locations
+    }
+}
+
+impl fmt::Display for LintIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let locations = self
+            .locations
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(f, "{}. At {}", self.message, locations)
+    }
+}
+
+/// Result of linting a single named client document against a schema.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LintReport<'d> {
+    name: &'d str,
+    issues: Vec<LintIssue>,
+}
+
+impl<'d> LintReport<'d> {
+    /// Returns the name of the linted document (usually, its file name).
+    pub fn name(&self) -> &'d str {
+        self.name
+    }
+
+    /// Returns all the [`LintIssue`]s found in the linted document, in the
+    /// order of their kinds.
+    pub fn issues(&self) -> &[LintIssue] {
+        &self.issues
+    }
+
+    /// Returns the [`LintIssue`]s of the given `kind` found in the linted
+    /// document.
+    pub fn issues_of(&self, kind: LintIssueKind) -> impl Iterator<Item = &LintIssue> {
+        self.issues.iter().filter(move |i| i.kind == kind)
+    }
+
+    /// Indicates whether the linted document is valid against the schema.
+    ///
+    /// [`LintIssueKind::Deprecated`] issues don't make it invalid.
+    pub fn is_valid(&self) -> bool {
+        self.issues
+            .iter()
+            .all(|i| i.kind == LintIssueKind::Deprecated)
+    }
+}
+
+/// Lints the given client document `source` against the `schema`, reporting
+/// its issues under the given `name`.
+pub(crate) fn lint_document<'d, S>(
+    schema: &SchemaType<S>,
+    name: &'d str,
+    source: &str,
+) -> LintReport<'d>
+where
+    S: ScalarValue,
+{
+    let document = match parse_document_source(source, schema) {
+        Ok(doc) => doc,
+        Err(e) => {
+            let issue = LintIssue::new(LintIssueKind::Syntax, e.item.to_string(), vec![e.start]);
+            return LintReport {
+                name,
+                issues: vec![issue],
+            };
+        }
+    };
+
+    let unknown_fields = errors_of(schema, &document, visit_unknown_fields);
+    let undefined_variables = errors_of(schema, &document, visit_undefined_variables);
+    let all = errors_of(schema, &document, visit_all_rules);
+
+    let mut issues = Vec::new();
+    issues.extend(
+        unknown_fields
+            .iter()
+            .map(|e| LintIssue::from_rule_error(LintIssueKind::UnknownField, e)),
+    );
+    issues.extend(
+        undefined_variables
+            .iter()
+            .map(|e| LintIssue::from_rule_error(LintIssueKind::UndefinedVariable, e)),
+    );
+    issues.extend(
+        all.iter()
+            .filter(|e| !unknown_fields.contains(e) && !undefined_variables.contains(e))
+            .map(|e| LintIssue::from_rule_error(LintIssueKind::Invalid, e)),
+    );
+    issues.extend(
+        errors_of(schema, &document, visit_deprecated_usage)
+            .iter()
+            .map(|e| LintIssue::from_rule_error(LintIssueKind::Deprecated, e)),
+    );
+
+    LintReport { name, issues }
+}
+
+/// Collects the [`RuleError`]s reported by the provided `visit`or of the given
+/// `document`.
+fn errors_of<'a, S>(
+    schema: &'a SchemaType<S>,
+    document: &'a Document<'a, S>,
+    visit: fn(&mut ValidatorContext<'a, S>, &'a Document<'a, S>),
+) -> Vec<RuleError>
+where
+    S: ScalarValue,
+{
+    let mut ctx = ValidatorContext::new(schema, document);
+    visit(&mut ctx, document);
+    ctx.into_errors()
+}
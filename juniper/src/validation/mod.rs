@@ -1,20 +1,38 @@
 //! Query validation related methods and data structures
 
+mod configured;
 mod context;
+mod custom;
 mod input_value;
+mod lint;
 mod multi_visitor;
+mod preset;
 mod rules;
+mod severity;
 mod traits;
 mod visitor;
 
 #[cfg(test)]
 pub(crate) mod test_harness;
 
+pub(crate) use self::{
+    configured::ConfiguredRule,
+    custom::CustomRule,
+    lint::lint_document,
+    rules::{
+        query_cost::operation_cost, visit_disabled_introspection, visit_fragment_rules,
+        visit_limit_rules,
+    },
+};
+
 pub use self::{
     context::{RuleError, ValidatorContext},
     input_value::validate_input_values,
-    multi_visitor::MultiVisitorNil,
-    rules::visit_all_rules,
+    lint::{LintIssue, LintIssueKind, LintReport},
+    multi_visitor::{MultiVisitorCons, MultiVisitorNil},
+    preset::ValidationPreset,
+    rules::{max_aliases, max_depth, visit_all_rules},
+    severity::{ConfigurableRule, RuleSeverity},
     traits::Visitor,
     visitor::visit,
 };
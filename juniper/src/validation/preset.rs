@@ -0,0 +1,72 @@
+//! Named presets of validation rules and default limits.
+
+use crate::{
+    executor::Limits,
+    validation::{ConfigurableRule, RuleSeverity},
+};
+
+/// Named preset of the validation rules a document is validated with, along
+/// with the default [`Limits`] of the operations executed against a schema.
+///
+/// Selected for a whole schema with [`RootNode::with_validation_preset()`].
+///
+/// [`RootNode::with_validation_preset()`]: crate::RootNode::with_validation_preset
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum ValidationPreset {
+    /// All the validation rules of the GraphQL specification, without any
+    /// limits.
+    ///
+    /// This is the default preset.
+    #[default]
+    Spec,
+
+    /// All the validation rules of the GraphQL specification, along with the
+    /// depth, aliases and cost limits protecting a public API from overly
+    /// expensive operations.
+    ///
+    /// See [`ValidationPreset::default_limits()`] for the exact limits.
+    SpecPlusSecurity,
+
+    /// The validation rules of the GraphQL specification, except the ones
+    /// requiring the fragments and variables of a document to be used, so the
+    /// documents generated by some clients are accepted as well.
+    Relaxed,
+}
+
+impl ValidationPreset {
+    /// Maximum nesting depth of fields in an operation allowed by the
+    /// [`ValidationPreset::SpecPlusSecurity`] preset.
+    pub const SECURITY_MAX_DEPTH: usize = 15;
+
+    /// Maximum number of aliased fields in an operation allowed by the
+    /// [`ValidationPreset::SpecPlusSecurity`] preset.
+    pub const SECURITY_MAX_ALIASES: usize = 30;
+
+    /// Maximum cost of an operation allowed by the
+    /// [`ValidationPreset::SpecPlusSecurity`] preset.
+    pub const SECURITY_MAX_COST: usize = 1000;
+
+    /// Returns the default [`Limits`] bundled with this preset.
+    pub fn default_limits(self) -> Limits {
+        match self {
+            Self::Spec | Self::Relaxed => Limits::new(),
+            Self::SpecPlusSecurity => Limits::new()
+                .max_depth(Self::SECURITY_MAX_DEPTH)
+                .max_aliases(Self::SECURITY_MAX_ALIASES)
+                .max_cost(Self::SECURITY_MAX_COST),
+        }
+    }
+
+    /// Returns the default [`RuleSeverity`] of the given `rule` in this
+    /// preset.
+    pub fn default_severity(self, rule: ConfigurableRule) -> RuleSeverity {
+        match (self, rule) {
+            (Self::Spec | Self::SpecPlusSecurity, _) => RuleSeverity::Error,
+            (
+                Self::Relaxed,
+                ConfigurableRule::NoUnusedFragments | ConfigurableRule::NoUnusedVariables,
+            ) => RuleSeverity::Off,
+            (Self::Relaxed, _) => RuleSeverity::Error,
+        }
+    }
+}
@@ -2,7 +2,7 @@ use crate::{
     ast::{Directive, Field, InputValue},
     parser::Spanning,
     schema::meta::Argument,
-    types::utilities::is_valid_literal_value,
+    types::utilities::invalid_literal_value_position,
     validation::{ValidatorContext, Visitor},
     value::ScalarValue,
 };
@@ -23,12 +23,9 @@ where
     fn enter_directive(
         &mut self,
         ctx: &mut ValidatorContext<'a, S>,
-        directive: &'a Spanning<Directive<S>>,
+        _: &'a Spanning<Directive<S>>,
     ) {
-        self.current_args = ctx
-            .schema
-            .directive_by_name(directive.item.name.item)
-            .map(|d| &d.arguments);
+        self.current_args = ctx.current_directive().map(|d| &d.arguments);
     }
 
     fn exit_directive(&mut self, _: &mut ValidatorContext<'a, S>, _: &'a Spanning<Directive<S>>) {
@@ -57,10 +54,11 @@ where
         {
             let meta_type = ctx.schema.make_type(&argument_meta.arg_type);
 
-            if !is_valid_literal_value(ctx.schema, &meta_type, &arg_value.item) {
-                ctx.report_error(
+            if let Some(pos) = invalid_literal_value_position(ctx.schema, &meta_type, arg_value) {
+                ctx.report_rule_error(
+                    "ArgumentsOfCorrectType",
                     &error_message(arg_name.item, &format!("{}", argument_meta.arg_type)),
-                    &[arg_value.start],
+                    &[pos],
                 );
             }
         }
@@ -158,6 +156,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn good_block_string_value() {
+        expect_passes_rule::<_, _, DefaultScalarValue>(
+            factory,
+            r#"
+            {
+              complicatedArgs {
+                stringArgField(stringArg: """
+                  multi-line "block" string
+                """)
+              }
+            }
+        "#,
+        );
+    }
+
     #[test]
     fn good_float_value() {
         expect_passes_rule::<_, _, DefaultScalarValue>(
@@ -715,7 +729,7 @@ mod tests {
         "#,
             &[RuleError::new(
                 &error_message("stringListArg", "[String]"),
-                &[SourcePosition::new(97, 3, 50)],
+                &[SourcePosition::new(105, 3, 58)],
             )],
         );
     }
@@ -1050,7 +1064,30 @@ mod tests {
         "#,
             &[RuleError::new(
                 &error_message("complexArg", "ComplexInput"),
-                &[SourcePosition::new(91, 3, 44)],
+                &[SourcePosition::new(136, 4, 43)],
+            )],
+        );
+    }
+
+    #[test]
+    fn partial_object_invalid_block_string_field() {
+        expect_fails_rule::<_, _, DefaultScalarValue>(
+            factory,
+            r#"
+            {
+              complicatedArgs {
+                complexArgField(complexArg: {
+                  requiredField: true,
+                  intField: """
+                    not an int
+                  """,
+                })
+              }
+            }
+        "#,
+            &[RuleError::new(
+                &error_message("complexArg", "ComplexInput"),
+                &[SourcePosition::new(160, 5, 28)],
             )],
         );
     }
@@ -1071,7 +1108,7 @@ mod tests {
         "#,
             &[RuleError::new(
                 &error_message("complexArg", "ComplexInput"),
-                &[SourcePosition::new(91, 3, 44)],
+                &[SourcePosition::new(150, 5, 18)],
             )],
         );
     }
@@ -1,7 +1,7 @@
 use crate::{
     ast::VariableDefinition,
     parser::Spanning,
-    types::utilities::is_valid_literal_value,
+    types::utilities::invalid_literal_value_position,
     validation::{ValidatorContext, Visitor},
     value::ScalarValue,
 };
@@ -21,24 +21,22 @@ where
         ctx: &mut ValidatorContext<'a, S>,
         &(ref var_name, ref var_def): &'a (Spanning<&'a str>, VariableDefinition<S>),
     ) {
-        if let Some(Spanning {
-            item: ref var_value,
-            ref start,
-            ..
-        }) = var_def.default_value
-        {
+        if let Some(ref var_value) = var_def.default_value {
             if var_def.var_type.item.is_non_null() {
-                ctx.report_error(
+                ctx.report_rule_error(
+                    "DefaultValuesOfCorrectType",
                     &non_null_error_message(var_name.item, &format!("{}", var_def.var_type.item)),
-                    &[*start],
+                    &[var_value.start],
                 )
             } else {
                 let meta_type = ctx.schema.make_type(&var_def.var_type.item);
 
-                if !is_valid_literal_value(ctx.schema, &meta_type, var_value) {
-                    ctx.report_error(
+                if let Some(pos) = invalid_literal_value_position(ctx.schema, &meta_type, var_value)
+                {
+                    ctx.report_rule_error(
+                        "DefaultValuesOfCorrectType",
                         &type_error_message(var_name.item, &format!("{}", var_def.var_type.item)),
-                        &[*start],
+                        &[pos],
                     );
                 }
             }
@@ -189,7 +187,7 @@ mod tests {
         "#,
             &[RuleError::new(
                 &type_error_message("a", "[String]"),
-                &[SourcePosition::new(44, 1, 43)],
+                &[SourcePosition::new(52, 1, 51)],
             )],
         );
     }
@@ -0,0 +1,97 @@
+use crate::{
+    ast::Field,
+    parser::Spanning,
+    validation::{RuleError, ValidatorContext, Visitor},
+    value::ScalarValue,
+};
+
+pub struct DisableIntrospection;
+
+pub fn factory() -> DisableIntrospection {
+    DisableIntrospection
+}
+
+impl<'a, S> Visitor<'a, S> for DisableIntrospection
+where
+    S: ScalarValue,
+{
+    fn enter_field(&mut self, ctx: &mut ValidatorContext<'a, S>, field: &'a Spanning<Field<S>>) {
+        let field_name = field.item.name.item;
+        if matches!(field_name, "__schema" | "__type") {
+            ctx.append_errors(vec![RuleError::new(
+                &error_message(field_name),
+                &[field.start],
+            )
+            .with_rule("DisableIntrospection")
+            .with_param("field", field_name)]);
+        }
+    }
+}
+
+fn error_message(field_name: &str) -> String {
+    format!(
+        "GraphQL introspection is not allowed, but the operation contained \"{}\"",
+        field_name,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{error_message, factory};
+
+    use crate::{
+        parser::SourcePosition,
+        validation::{expect_fails_rule, expect_passes_rule, RuleError},
+        value::DefaultScalarValue,
+    };
+
+    #[test]
+    fn allows_typename() {
+        expect_passes_rule::<_, _, DefaultScalarValue>(
+            factory,
+            r#"
+          {
+            __typename
+            dog { __typename name }
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn rejects_schema_and_type() {
+        expect_fails_rule::<_, _, DefaultScalarValue>(
+            factory,
+            r#"
+          {
+            __schema { queryType { name } }
+            __type(name: "Dog") { name }
+          }
+        "#,
+            &[
+                RuleError::new(
+                    &error_message("__schema"),
+                    &[SourcePosition::new(25, 2, 12)],
+                ),
+                RuleError::new(&error_message("__type"), &[SourcePosition::new(69, 3, 12)]),
+            ],
+        );
+    }
+
+    #[test]
+    fn rejects_introspection_in_fragments() {
+        expect_fails_rule::<_, _, DefaultScalarValue>(
+            factory,
+            r#"
+          { ...schemaFragment }
+          fragment schemaFragment on QueryRoot {
+            __schema { types { name } }
+          }
+        "#,
+            &[RuleError::new(
+                &error_message("__schema"),
+                &[SourcePosition::new(94, 3, 12)],
+            )],
+        );
+    }
+}
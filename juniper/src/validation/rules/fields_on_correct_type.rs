@@ -27,7 +27,8 @@ where
             for selection in &operation.item.selection_set {
                 if let Selection::Field(field) = selection {
                     if field.item.name.item == "__typename" {
-                        context.report_error(
+                        context.report_rule_error(
+                            "FieldsOnCorrectType",
                             "`__typename` may not be included as a root \
                              field in a subscription operation",
                             &[field.item.name.start],
@@ -58,7 +59,8 @@ where
                         }
                     }
 
-                    context.report_error(
+                    context.report_rule_error(
+                        "FieldsOnCorrectType",
                         &error_message(field_name.item, type_name),
                         &[field_name.start],
                     );
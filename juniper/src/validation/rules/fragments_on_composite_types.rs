@@ -26,7 +26,8 @@ where
                     let type_name = current_type.name().unwrap_or("<unknown>");
                     let type_cond = &f.item.type_condition;
 
-                    context.report_error(
+                    context.report_rule_error(
+                        "FragmentsOnCompositeTypes",
                         &error_message(Some(f.item.name.item), type_name),
                         &[type_cond.start],
                     );
@@ -50,7 +51,11 @@ where
                     .next();
 
                 if let Some(name) = invalid_type_name {
-                    context.report_error(&error_message(None, name), &[type_cond.start]);
+                    context.report_rule_error(
+                        "FragmentsOnCompositeTypes",
+                        &error_message(None, name),
+                        &[type_cond.start],
+                    );
                 }
             }
         }
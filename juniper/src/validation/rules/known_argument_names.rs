@@ -30,15 +30,12 @@ where
         ctx: &mut ValidatorContext<'a, S>,
         directive: &'a Spanning<Directive<S>>,
     ) {
-        self.current_args = ctx
-            .schema
-            .directive_by_name(directive.item.name.item)
-            .map(|d| {
-                (
-                    ArgumentPosition::Directive(directive.item.name.item),
-                    &d.arguments,
-                )
-            });
+        self.current_args = ctx.current_directive().map(|d| {
+            (
+                ArgumentPosition::Directive(directive.item.name.item),
+                &d.arguments,
+            )
+        });
     }
 
     fn exit_directive(&mut self, _: &mut ValidatorContext<'a, S>, _: &'a Spanning<Directive<S>>) {
@@ -84,7 +81,7 @@ where
                     }
                 };
 
-                ctx.report_error(&message, &[arg_name.start]);
+                ctx.report_rule_error("KnownArgumentNames", &message, &[arg_name.start]);
             }
         }
     }
@@ -134,21 +134,26 @@ where
     ) {
         let directive_name = &directive.item.name.item;
 
-        if let Some(directive_type) = ctx.schema.directive_by_name(directive_name) {
+        if let Some(directive_type) = ctx.current_directive() {
             if let Some(current_location) = self.location_stack.last() {
                 if !directive_type
                     .locations
                     .iter()
                     .any(|l| l == current_location)
                 {
-                    ctx.report_error(
+                    ctx.report_rule_error(
+                        "KnownDirectives",
                         &misplaced_error_message(directive_name, current_location),
                         &[directive.start],
                     );
                 }
             }
         } else {
-            ctx.report_error(&unknown_error_message(directive_name), &[directive.start]);
+            ctx.report_rule_error(
+                "KnownDirectives",
+                &unknown_error_message(directive_name),
+                &[directive.start],
+            );
         }
     }
 }
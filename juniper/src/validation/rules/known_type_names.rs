@@ -51,7 +51,7 @@ fn validate_type<'a, S: Debug>(
     location: &SourcePosition,
 ) {
     if ctx.schema.type_by_name(type_name).is_none() {
-        ctx.report_error(&error_message(type_name), &[*location]);
+        ctx.report_rule_error("KnownTypeNames", &error_message(type_name), &[*location]);
     }
 }
 
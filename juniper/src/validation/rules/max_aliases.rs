@@ -0,0 +1,284 @@
+//! `MaxAliases` validation rule, limiting the number of aliased fields in an
+//! operation.
+
+use crate::{
+    ast::{Definition, Document, Field, Selection},
+    executor::{usize_value, Measurer},
+    parser::Spanning,
+    validation::{RuleError, ValidatorContext, Visitor},
+    value::ScalarValue,
+};
+
+/// Rule rejecting the operations selecting more aliased fields than allowed,
+/// with the aliases of fragments counted once per their spread.
+///
+/// Reports the first aliased field exceeding the limit.
+pub struct MaxAliases {
+    max: usize,
+}
+
+/// Creates a new [`MaxAliases`] rule, allowing at most `max` aliased fields in
+/// an operation.
+///
+/// The rule is visited by the [`visit_all_rules()`] already, configured by the
+/// [`Limits::max_aliases()`] (overridable per request). Registering it as a
+/// custom rule enforces its `max` on every request instead:
+/// ```
+/// # use juniper::{
+/// #     graphql_object, validation::max_aliases, EmptyMutation, EmptySubscription, RootNode,
+/// # };
+/// #
+/// # struct Query;
+/// #
+/// # #[graphql_object]
+/// # impl Query {
+/// #     fn hello() -> &'static str {
+/// #         "world"
+/// #     }
+/// # }
+/// #
+/// let schema = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::<()>::new())
+///     .with_validation_rule(|| max_aliases::factory_with_limit(2));
+/// ```
+///
+/// [`Limits::max_aliases()`]: crate::Limits::max_aliases
+/// [`visit_all_rules()`]: crate::validation::visit_all_rules
+pub fn factory_with_limit(max: usize) -> MaxAliases {
+    MaxAliases { max }
+}
+
+impl<'a, S> Visitor<'a, S> for MaxAliases
+where
+    S: ScalarValue,
+{
+    fn exit_document(&mut self, ctx: &mut ValidatorContext<'a, S>, doc: &'a Document<S>) {
+        let mut measurer = Measurer::new(doc);
+
+        for def in doc {
+            if let Definition::Operation(op) = def {
+                let aliases = measurer.measure(&op.item.selection_set).aliases();
+                if aliases <= self.max {
+                    continue;
+                }
+
+                let pos = find_alias(
+                    &mut measurer,
+                    &op.item.selection_set,
+                    self.max + 1,
+                    &mut Vec::new(),
+                )
+                .map_or(op.start, |f| f.start);
+                ctx.append_errors(vec![RuleError::new(
+                    &error_message(aliases, self.max),
+                    &[pos],
+                )
+                .with_rule("MaxAliases")
+                .with_param("max", usize_value(self.max))
+                .with_param("actual", usize_value(aliases))]);
+            }
+        }
+    }
+}
+
+/// Finds the `n`th (counting from `1`) aliased field of the given
+/// `selection_set`, with the aliases of fragments counted once per their
+/// spread.
+///
+/// The fragments spread within the `fragments` being searched already (so
+/// forming a cycle) are skipped.
+fn find_alias<'d, 'a, S>(
+    measurer: &mut Measurer<'d, 'a, S>,
+    selection_set: &'d [Selection<'a, S>],
+    mut n: usize,
+    fragments: &mut Vec<&'a str>,
+) -> Option<&'d Spanning<Field<'a, S>>> {
+    for selection in selection_set {
+        let aliases = measurer.measure_selection(selection).aliases();
+        if aliases < n {
+            n -= aliases;
+            continue;
+        }
+
+        match selection {
+            Selection::Field(f) => {
+                if f.item.alias.is_some() {
+                    if n == 1 {
+                        return Some(f);
+                    }
+                    n -= 1;
+                }
+                return find_alias(measurer, f.item.selection_set.as_deref()?, n, fragments);
+            }
+            Selection::InlineFragment(f) => {
+                return find_alias(measurer, &f.item.selection_set, n, fragments);
+            }
+            Selection::FragmentSpread(s) => {
+                let name = s.item.name.item;
+                if fragments.contains(&name) {
+                    continue;
+                }
+                let fragment = measurer.fragment(name)?;
+
+                fragments.push(name);
+                let field = find_alias(measurer, &fragment.selection_set, n, fragments);
+                fragments.pop();
+                return field;
+            }
+        }
+    }
+    None
+}
+
+fn error_message(aliases: usize, max: usize) -> String {
+    format!(
+        "Operation has {} aliases, exceeding the maximum allowed number of {}",
+        aliases, max,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{error_message, factory_with_limit};
+
+    use crate::{
+        parser::SourcePosition,
+        validation::{expect_fails_rule, expect_passes_rule, RuleError},
+        value::DefaultScalarValue,
+    };
+
+    #[test]
+    fn allows_aliases_within_limit() {
+        expect_passes_rule::<_, _, DefaultScalarValue>(
+            || factory_with_limit(2),
+            r#"
+          {
+            a: dog { name }
+            b: dog { name }
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn reports_first_exceeding_alias() {
+        expect_fails_rule::<_, _, DefaultScalarValue>(
+            || factory_with_limit(2),
+            r#"
+          {
+            a: dog { n: name }
+            b: dog { name }
+          }
+        "#,
+            &[RuleError::new(
+                &error_message(3, 2),
+                &[SourcePosition::new(56, 3, 12)],
+            )],
+        );
+    }
+
+    #[test]
+    fn counts_fragment_aliases_per_spread() {
+        expect_fails_rule::<_, _, DefaultScalarValue>(
+            || factory_with_limit(3),
+            r#"
+          {
+            dog { ...F }
+            d: dog { ...F }
+          }
+          fragment F on Dog {
+            a: name
+            b: name
+          }
+        "#,
+            &[RuleError::new(
+                &error_message(5, 3),
+                &[SourcePosition::new(120, 6, 12)],
+            )],
+        );
+    }
+
+    #[test]
+    fn counts_nested_fragment_aliases() {
+        expect_fails_rule::<_, _, DefaultScalarValue>(
+            || factory_with_limit(4),
+            r#"
+          fragment G on Dog {
+            b: name
+            c: name
+          }
+          {
+            dog { ...F }
+            a: dog { ...F }
+          }
+          fragment F on Dog {
+            ...G
+          }
+        "#,
+            &[RuleError::new(
+                &error_message(5, 4),
+                &[SourcePosition::new(63, 3, 12)],
+            )],
+        );
+    }
+
+    #[test]
+    fn counts_inline_fragment_aliases() {
+        expect_fails_rule::<_, _, DefaultScalarValue>(
+            || factory_with_limit(1),
+            r#"
+          {
+            dog {
+              ... on Dog { a: name }
+              ... { b: name }
+            }
+          }
+        "#,
+            &[RuleError::new(
+                &error_message(2, 1),
+                &[SourcePosition::new(88, 4, 20)],
+            )],
+        );
+    }
+
+    #[test]
+    fn checks_every_operation() {
+        expect_fails_rule::<_, _, DefaultScalarValue>(
+            || factory_with_limit(1),
+            r#"
+          query A { a: dog { name } }
+          query B { b: dog { name } c: dog { name } }
+        "#,
+            &[RuleError::new(
+                &error_message(2, 1),
+                &[SourcePosition::new(75, 2, 36)],
+            )],
+        );
+    }
+
+    #[test]
+    fn handles_fragment_cycles() {
+        expect_fails_rule::<_, _, DefaultScalarValue>(
+            || factory_with_limit(1),
+            r#"
+          { dog { ...F } }
+          fragment F on Dog { a: name ...G }
+          fragment G on Dog { b: name ...F }
+        "#,
+            &[RuleError::new(
+                &error_message(2, 1),
+                &[SourcePosition::new(103, 3, 30)],
+            )],
+        );
+        expect_fails_rule::<_, _, DefaultScalarValue>(
+            || factory_with_limit(0),
+            r#"
+          { dog { ...F } }
+          fragment F on Dog { ...F a: name }
+        "#,
+            &[RuleError::new(
+                &error_message(1, 0),
+                &[SourcePosition::new(63, 2, 35)],
+            )],
+        );
+    }
+}
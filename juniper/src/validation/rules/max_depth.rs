@@ -0,0 +1,246 @@
+//! `MaxDepth` validation rule, limiting the nesting depth of fields in an
+//! operation.
+
+use crate::{
+    ast::{Definition, Document, Field, Selection},
+    executor::{usize_value, Measurer},
+    parser::Spanning,
+    validation::{RuleError, ValidatorContext, Visitor},
+    value::ScalarValue,
+};
+
+/// Rule rejecting the operations nesting fields deeper than allowed, where
+/// the root fields have the depth of `1`, following the fragment spreads.
+///
+/// Reports the first field nested deeper than the limit.
+pub struct MaxDepth {
+    max: usize,
+}
+
+/// Creates a new [`MaxDepth`] rule, allowing the fields of an operation to be
+/// nested at most `max` levels deep.
+///
+/// The rule is visited by the [`visit_all_rules()`] already, configured by the
+/// [`Limits::max_depth()`] (overridable per request). Registering it as a
+/// custom rule enforces its `max` on every request instead:
+/// ```
+/// # use juniper::{
+/// #     graphql_object, validation::max_depth, EmptyMutation, EmptySubscription, RootNode,
+/// # };
+/// #
+/// # struct Query;
+/// #
+/// # #[graphql_object]
+/// # impl Query {
+/// #     fn hello() -> &'static str {
+/// #         "world"
+/// #     }
+/// # }
+/// #
+/// let schema = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::<()>::new())
+///     .with_validation_rule(|| max_depth::factory_with_limit(10));
+/// ```
+///
+/// [`Limits::max_depth()`]: crate::Limits::max_depth
+/// [`visit_all_rules()`]: crate::validation::visit_all_rules
+pub fn factory_with_limit(max: usize) -> MaxDepth {
+    MaxDepth { max }
+}
+
+impl<'a, S> Visitor<'a, S> for MaxDepth
+where
+    S: ScalarValue,
+{
+    fn exit_document(&mut self, ctx: &mut ValidatorContext<'a, S>, doc: &'a Document<S>) {
+        let mut measurer = Measurer::new(doc);
+
+        for def in doc {
+            if let Definition::Operation(op) = def {
+                let depth = measurer.measure(&op.item.selection_set).depth();
+                if depth <= self.max {
+                    continue;
+                }
+
+                let pos = find_too_deep(
+                    &mut measurer,
+                    &op.item.selection_set,
+                    self.max,
+                    &mut Vec::new(),
+                )
+                .map_or(op.start, |f| f.start);
+                ctx.append_errors(vec![RuleError::new(
+                    &error_message(depth, self.max),
+                    &[pos],
+                )
+                .with_rule("MaxDepth")
+                .with_param("max", usize_value(self.max))
+                .with_param("actual", usize_value(depth))]);
+            }
+        }
+    }
+}
+
+/// Finds the first field of the given `selection_set` nested deeper than the
+/// `allowed` depth, following the fragment spreads.
+///
+/// The fragments spread within the `fragments` being searched already (so
+/// forming a cycle) are skipped.
+fn find_too_deep<'d, 'a, S>(
+    measurer: &mut Measurer<'d, 'a, S>,
+    selection_set: &'d [Selection<'a, S>],
+    allowed: usize,
+    fragments: &mut Vec<&'a str>,
+) -> Option<&'d Spanning<Field<'a, S>>> {
+    for selection in selection_set {
+        if measurer.measure_selection(selection).depth() <= allowed {
+            continue;
+        }
+
+        match selection {
+            Selection::Field(f) => {
+                if allowed == 0 {
+                    return Some(f);
+                }
+                return find_too_deep(
+                    measurer,
+                    f.item.selection_set.as_deref()?,
+                    allowed - 1,
+                    fragments,
+                );
+            }
+            Selection::InlineFragment(f) => {
+                return find_too_deep(measurer, &f.item.selection_set, allowed, fragments);
+            }
+            Selection::FragmentSpread(s) => {
+                let name = s.item.name.item;
+                if fragments.contains(&name) {
+                    continue;
+                }
+                let fragment = measurer.fragment(name)?;
+
+                fragments.push(name);
+                let field = find_too_deep(measurer, &fragment.selection_set, allowed, fragments);
+                fragments.pop();
+                return field;
+            }
+        }
+    }
+    None
+}
+
+fn error_message(depth: usize, max: usize) -> String {
+    format!(
+        "Operation has depth of {}, exceeding the maximum allowed depth of {}",
+        depth, max,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{error_message, factory_with_limit};
+
+    use crate::{
+        parser::SourcePosition,
+        validation::{expect_fails_rule, expect_passes_rule, RuleError},
+        value::DefaultScalarValue,
+    };
+
+    #[test]
+    fn allows_fields_within_limit() {
+        expect_passes_rule::<_, _, DefaultScalarValue>(
+            || factory_with_limit(2),
+            r#"
+          {
+            dog { name }
+            human { name }
+          }
+        "#,
+        );
+    }
+
+    #[test]
+    fn reports_first_too_deep_field() {
+        expect_fails_rule::<_, _, DefaultScalarValue>(
+            || factory_with_limit(2),
+            r#"
+          {
+            dog { name }
+            human { pets { name } }
+          }
+        "#,
+            &[RuleError::new(
+                &error_message(3, 2),
+                &[SourcePosition::new(65, 3, 27)],
+            )],
+        );
+    }
+
+    #[test]
+    fn follows_fragment_spreads() {
+        expect_fails_rule::<_, _, DefaultScalarValue>(
+            || factory_with_limit(2),
+            r#"
+          {
+            human { ...F }
+          }
+          fragment F on Human {
+            name
+            pets { name }
+          }
+        "#,
+            &[RuleError::new(
+                &error_message(3, 2),
+                &[SourcePosition::new(120, 6, 19)],
+            )],
+        );
+    }
+
+    #[test]
+    fn follows_inline_fragments() {
+        expect_fails_rule::<_, _, DefaultScalarValue>(
+            || factory_with_limit(1),
+            r#"
+          {
+            human {
+              ... on Human { pets { name } }
+            }
+          }
+        "#,
+            &[RuleError::new(
+                &error_message(3, 1),
+                &[SourcePosition::new(62, 3, 29)],
+            )],
+        );
+    }
+
+    #[test]
+    fn checks_every_operation() {
+        expect_fails_rule::<_, _, DefaultScalarValue>(
+            || factory_with_limit(1),
+            r#"
+          query A { dog }
+          query B { human { name } }
+        "#,
+            &[RuleError::new(
+                &error_message(2, 1),
+                &[SourcePosition::new(55, 2, 28)],
+            )],
+        );
+    }
+
+    #[test]
+    fn handles_fragment_cycles() {
+        expect_fails_rule::<_, _, DefaultScalarValue>(
+            || factory_with_limit(2),
+            r#"
+          { human { ...F } }
+          fragment F on Human { pets { ...G } }
+          fragment G on Pet { ...F name }
+        "#,
+            &[RuleError::new(
+                &error_message(3, 2),
+                &[SourcePosition::new(113, 3, 35)],
+            )],
+        );
+    }
+}
@@ -1,5 +1,6 @@
 mod arguments_of_correct_type;
 mod default_values_of_correct_type;
+mod disable_introspection;
 mod fields_on_correct_type;
 mod fragments_on_composite_types;
 mod known_argument_names;
@@ -7,6 +8,9 @@ mod known_directives;
 mod known_fragment_names;
 mod known_type_names;
 mod lone_anonymous_operation;
+pub mod max_aliases;
+pub mod max_depth;
+mod no_deprecated_usage;
 mod no_fragment_cycles;
 mod no_undefined_variables;
 mod no_unused_fragments;
@@ -14,6 +18,7 @@ mod no_unused_variables;
 mod overlapping_fields_can_be_merged;
 mod possible_fragment_spreads;
 mod provided_non_null_arguments;
+pub(crate) mod query_cost;
 mod scalar_leafs;
 mod unique_argument_names;
 mod unique_fragment_names;
@@ -25,7 +30,9 @@ mod variables_in_allowed_position;
 
 use crate::{
     ast::Document,
-    validation::{visit, MultiVisitorNil, ValidatorContext},
+    validation::{
+        visit, ConfigurableRule, ConfiguredRule, MultiVisitorNil, RuleSeverity, ValidatorContext,
+    },
     value::ScalarValue,
 };
 use std::fmt::Debug;
@@ -42,44 +49,346 @@ where
     // next stage only once the previous succeeds. This is better than making
     // every single validator being aware of fragments cycles and/or other
     // assumptions.
-    let mut stage1 = MultiVisitorNil
-        .with(self::arguments_of_correct_type::factory())
-        .with(self::default_values_of_correct_type::factory())
-        .with(self::fields_on_correct_type::factory())
-        .with(self::fragments_on_composite_types::factory())
-        .with(self::known_argument_names::factory())
-        .with(self::known_directives::factory())
-        .with(self::known_fragment_names::factory())
-        .with(self::known_type_names::factory())
-        .with(self::lone_anonymous_operation::factory())
-        .with(self::no_fragment_cycles::factory())
-        .with(self::no_undefined_variables::factory())
-        .with(self::no_unused_fragments::factory())
-        .with(self::no_unused_variables::factory())
-        .with(self::possible_fragment_spreads::factory())
-        .with(self::provided_non_null_arguments::factory())
-        .with(self::scalar_leafs::factory())
-        .with(self::unique_argument_names::factory())
-        .with(self::unique_fragment_names::factory())
-        .with(self::unique_input_field_names::factory())
-        .with(self::unique_operation_names::factory())
-        .with(self::unique_variable_names::factory())
-        .with(self::variables_are_input_types::factory())
-        .with(self::variables_in_allowed_position::factory());
-    visit(&mut stage1, ctx, doc);
+    visit_stage1(ctx, doc, true);
+    if ctx.has_errors() {
+        return;
+    }
+
+    visit_stage2(ctx, doc);
+}
+
+/// Validates the given document with all the rules, except requiring its
+/// fragments to be used by its operations, as they're executed on their own.
+pub(crate) fn visit_fragment_rules<'a, S>(ctx: &mut ValidatorContext<'a, S>, doc: &'a Document<S>)
+where
+    S: ScalarValue,
+{
+    visit_stage1(ctx, doc, false);
     if ctx.has_errors() {
         return;
     }
 
-    let mut stage2 = MultiVisitorNil.with(self::overlapping_fields_can_be_merged::factory());
+    visit_stage2(ctx, doc);
+}
+
+/// Visits the given document with the rules of the first validation stage,
+/// including the `no_unused_fragments` one if `unused_fragments` is `true`.
+///
+/// All the rules are visited in a single pass over the document, even if some
+/// of them are relaxed in the schema.
+fn visit_stage1<'a, S>(
+    ctx: &mut ValidatorContext<'a, S>,
+    doc: &'a Document<S>,
+    unused_fragments: bool,
+) where
+    S: ScalarValue,
+{
+    let unused_fragments_severity = if unused_fragments {
+        ctx.schema
+            .rule_severity(ConfigurableRule::NoUnusedFragments)
+    } else {
+        RuleSeverity::Off
+    };
+
+    let mut stage1 = MultiVisitorNil
+        .with(required(
+            ctx,
+            "ArgumentsOfCorrectType",
+            self::arguments_of_correct_type::factory(),
+        ))
+        .with(required(
+            ctx,
+            "DefaultValuesOfCorrectType",
+            self::default_values_of_correct_type::factory(),
+        ))
+        .with(required(
+            ctx,
+            "FieldsOnCorrectType",
+            self::fields_on_correct_type::factory(),
+        ))
+        .with(required(
+            ctx,
+            "FragmentsOnCompositeTypes",
+            self::fragments_on_composite_types::factory(),
+        ))
+        .with(required(
+            ctx,
+            "KnownFragmentNames",
+            self::known_fragment_names::factory(),
+        ))
+        .with(required(
+            ctx,
+            "KnownTypeNames",
+            self::known_type_names::factory(),
+        ))
+        .with(required(
+            ctx,
+            "NoFragmentCycles",
+            self::no_fragment_cycles::factory(),
+        ))
+        .with(required(
+            ctx,
+            "NoUndefinedVariables",
+            self::no_undefined_variables::factory(),
+        ))
+        .with(required(
+            ctx,
+            "PossibleFragmentSpreads",
+            self::possible_fragment_spreads::factory(),
+        ))
+        .with(required(
+            ctx,
+            "ProvidedNonNullArguments",
+            self::provided_non_null_arguments::factory(),
+        ))
+        .with(required(ctx, "ScalarLeafs", self::scalar_leafs::factory()))
+        .with(required(
+            ctx,
+            "VariablesAreInputTypes",
+            self::variables_are_input_types::factory(),
+        ))
+        .with(required(
+            ctx,
+            "VariablesInAllowedPosition",
+            self::variables_in_allowed_position::factory(),
+        ))
+        .with(configurable(
+            ctx,
+            ConfigurableRule::KnownArgumentNames,
+            self::known_argument_names::factory(),
+        ))
+        .with(configurable(
+            ctx,
+            ConfigurableRule::KnownDirectives,
+            self::known_directives::factory(),
+        ))
+        .with(configurable(
+            ctx,
+            ConfigurableRule::LoneAnonymousOperation,
+            self::lone_anonymous_operation::factory(),
+        ))
+        .with(configurable(
+            ctx,
+            ConfigurableRule::NoUnusedVariables,
+            self::no_unused_variables::factory(),
+        ))
+        .with(configurable(
+            ctx,
+            ConfigurableRule::UniqueArgumentNames,
+            self::unique_argument_names::factory(),
+        ))
+        .with(configurable(
+            ctx,
+            ConfigurableRule::UniqueFragmentNames,
+            self::unique_fragment_names::factory(),
+        ))
+        .with(configurable(
+            ctx,
+            ConfigurableRule::UniqueInputFieldNames,
+            self::unique_input_field_names::factory(),
+        ))
+        .with(configurable(
+            ctx,
+            ConfigurableRule::UniqueOperationNames,
+            self::unique_operation_names::factory(),
+        ))
+        .with(configurable(
+            ctx,
+            ConfigurableRule::UniqueVariableNames,
+            self::unique_variable_names::factory(),
+        ))
+        .with(ConfiguredRule::new(
+            ctx,
+            ConfigurableRule::NoUnusedFragments.name(),
+            unused_fragments_severity,
+            self::no_unused_fragments::factory(),
+        ));
+    visit(&mut stage1, ctx, doc);
+}
+
+/// Visits the given document with the rules of the second validation stage,
+/// and the custom rules registered in the schema.
+fn visit_stage2<'a, S>(ctx: &mut ValidatorContext<'a, S>, doc: &'a Document<S>)
+where
+    S: ScalarValue,
+{
+    let mut stage2 = MultiVisitorNil
+        .with(configurable(
+            ctx,
+            ConfigurableRule::OverlappingFieldsCanBeMerged,
+            self::overlapping_fields_can_be_merged::factory(),
+        ))
+        .with(limited(
+            ctx,
+            "MaxAliases",
+            ctx.limits().alias_limit(),
+            self::max_aliases::factory_with_limit,
+        ))
+        .with(limited(
+            ctx,
+            "MaxDepth",
+            ctx.limits().depth_limit(),
+            self::max_depth::factory_with_limit,
+        ));
     visit(&mut stage2, ctx, doc);
+
+    // Custom rules are visited last, so may rely on the document being valid
+    // according to the built-in ones.
+    let schema = ctx.schema;
+    for rule in schema.validation_rules() {
+        rule.visit(ctx, doc);
+    }
+}
+
+/// Wraps the given built-in `rule`, which can't be relaxed, as the execution
+/// relies on it.
+fn required<S, V>(ctx: &ValidatorContext<'_, S>, name: &'static str, rule: V) -> ConfiguredRule<V>
+where
+    S: ScalarValue,
+{
+    ConfiguredRule::new(ctx, name, RuleSeverity::Error, rule)
+}
+
+/// Wraps the given `visitor` of the [`ConfigurableRule`], according to its
+/// [`RuleSeverity`] configured in the schema.
+fn configurable<S, V>(
+    ctx: &ValidatorContext<'_, S>,
+    rule: ConfigurableRule,
+    visitor: V,
+) -> ConfiguredRule<V>
+where
+    S: ScalarValue,
+{
+    ConfiguredRule::new(ctx, rule.name(), ctx.schema.rule_severity(rule), visitor)
+}
+
+/// Wraps the built-in rule checking the given `limit` of the [`Limits`] the
+/// document is validated against, being off if there is no such limit.
+///
+/// [`Limits`]: crate::Limits
+fn limited<S, V>(
+    ctx: &ValidatorContext<'_, S>,
+    name: &'static str,
+    limit: Option<usize>,
+    factory: fn(usize) -> V,
+) -> ConfiguredRule<V>
+where
+    S: ScalarValue,
+{
+    let severity = if limit.is_some() {
+        RuleSeverity::Error
+    } else {
+        RuleSeverity::Off
+    };
+    ConfiguredRule::new(ctx, name, severity, factory(limit.unwrap_or_default()))
+}
+
+/// Checks the given already validated document against the [`Limits`] of the
+/// [`ValidatorContext`] only (like the per-request ones of a cached document,
+/// validated regardless of any).
+///
+/// [`Limits`]: crate::Limits
+pub(crate) fn visit_limit_rules<'a, S>(ctx: &mut ValidatorContext<'a, S>, doc: &'a Document<S>)
+where
+    S: ScalarValue,
+{
+    let mut rules = MultiVisitorNil
+        .with(limited(
+            ctx,
+            "MaxAliases",
+            ctx.limits().alias_limit(),
+            self::max_aliases::factory_with_limit,
+        ))
+        .with(limited(
+            ctx,
+            "MaxDepth",
+            ctx.limits().depth_limit(),
+            self::max_depth::factory_with_limit,
+        ));
+    visit(&mut rules, ctx, doc);
+}
+
+/// Reports usages of deprecated fields, arguments and enum values in the given
+/// document, which are valid, but worth linting client operations for.
+pub(crate) fn visit_deprecated_usage<'a, S>(ctx: &mut ValidatorContext<'a, S>, doc: &'a Document<S>)
+where
+    S: ScalarValue,
+{
+    visit(
+        &mut MultiVisitorNil.with(self::no_deprecated_usage::factory()),
+        ctx,
+        doc,
+    );
+}
+
+/// Reports the introspection fields (`__schema` and `__type`) selected in the
+/// given document, which is not allowed for untrusted requests when the
+/// introspection is disabled.
+pub(crate) fn visit_disabled_introspection<'a, S>(
+    ctx: &mut ValidatorContext<'a, S>,
+    doc: &'a Document<S>,
+) where
+    S: ScalarValue,
+{
+    visit(
+        &mut MultiVisitorNil.with(self::disable_introspection::factory()),
+        ctx,
+        doc,
+    );
+}
+
+/// Reports only the fields unknown to the schema in the given document.
+pub(crate) fn visit_unknown_fields<'a, S>(ctx: &mut ValidatorContext<'a, S>, doc: &'a Document<S>)
+where
+    S: ScalarValue,
+{
+    visit(
+        &mut MultiVisitorNil.with(self::fields_on_correct_type::factory()),
+        ctx,
+        doc,
+    );
+}
+
+/// Reports only the variables not defined by their operations in the given
+/// document.
+pub(crate) fn visit_undefined_variables<'a, S>(
+    ctx: &mut ValidatorContext<'a, S>,
+    doc: &'a Document<S>,
+) where
+    S: ScalarValue,
+{
+    visit(
+        &mut MultiVisitorNil.with(self::no_undefined_variables::factory()),
+        ctx,
+        doc,
+    );
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{parser::SourcePosition, DefaultScalarValue};
 
-    use crate::validation::{expect_fails_fn, RuleError};
+    use crate::validation::{
+        expect_fails_fn,
+        test_harness::{validate, MutationRoot, QueryRoot, SubscriptionRoot},
+        RuleError,
+    };
+
+    #[test]
+    fn identifies_rules() {
+        let errors = validate::<_, _, _, _, DefaultScalarValue>(
+            QueryRoot,
+            MutationRoot,
+            SubscriptionRoot,
+            "fragment f on QueryRoot { ...f }",
+            super::visit_all_rules,
+        );
+
+        assert_eq!(
+            errors.iter().map(RuleError::rule).collect::<Vec<_>>(),
+            [Some("NoUnusedFragments"), Some("NoFragmentCycles")],
+        );
+    }
 
     #[test]
     fn handles_recursive_fragments() {
@@ -0,0 +1,120 @@
+use crate::{
+    ast::{Field, InputValue},
+    parser::Spanning,
+    schema::meta::{Argument, DeprecationStatus, MetaType},
+    validation::{ValidatorContext, Visitor},
+    value::ScalarValue,
+};
+use std::fmt::Debug;
+
+/// Reports usages of deprecated fields, arguments and enum values.
+///
+/// Not a part of the validation, as using deprecated schema members is valid,
+/// but is worth linting client operations for.
+pub struct NoDeprecatedUsage<'a, S: Debug + 'a> {
+    current_args: Option<(&'a str, &'a Vec<Argument<'a, S>>)>,
+}
+
+pub fn factory<'a, S: Debug>() -> NoDeprecatedUsage<'a, S> {
+    NoDeprecatedUsage { current_args: None }
+}
+
+impl<'a, S> Visitor<'a, S> for NoDeprecatedUsage<'a, S>
+where
+    S: ScalarValue,
+{
+    fn enter_field(&mut self, ctx: &mut ValidatorContext<'a, S>, field: &'a Spanning<Field<S>>) {
+        let field_name = field.item.name.item;
+        let parent_type = ctx.parent_type();
+        let meta_field = parent_type.and_then(|t| t.field_by_name(field_name));
+
+        if let Some(meta_field) = meta_field {
+            if let DeprecationStatus::Deprecated(ref reason) = meta_field.deprecation_status {
+                let type_name = parent_type.and_then(|t| t.name()).unwrap_or("<unknown>");
+                ctx.report_rule_error(
+                    "NoDeprecatedUsage",
+                    &field_error_message(field_name, type_name, reason.as_deref()),
+                    &[field.item.name.start],
+                );
+            }
+        }
+
+        self.current_args = meta_field
+            .and_then(|f| f.arguments.as_ref())
+            .map(|args| (field_name, args));
+    }
+
+    fn exit_field(&mut self, _: &mut ValidatorContext<'a, S>, _: &'a Spanning<Field<S>>) {
+        self.current_args = None;
+    }
+
+    fn enter_argument(
+        &mut self,
+        ctx: &mut ValidatorContext<'a, S>,
+        (arg_name, _): &'a (Spanning<&'a str>, Spanning<InputValue<S>>),
+    ) {
+        if let Some((field_name, args)) = self.current_args {
+            let deprecation = args
+                .iter()
+                .find(|a| a.name == arg_name.item)
+                .map(|a| &a.deprecation_status);
+            if let Some(DeprecationStatus::Deprecated(reason)) = deprecation {
+                ctx.report_rule_error(
+                    "NoDeprecatedUsage",
+                    &argument_error_message(arg_name.item, field_name, reason.as_deref()),
+                    &[arg_name.start],
+                );
+            }
+        }
+    }
+
+    fn enter_enum_value(&mut self, ctx: &mut ValidatorContext<'a, S>, value: Spanning<&'a String>) {
+        let enum_type = ctx
+            .current_input_type_literal()
+            .and_then(|t| ctx.schema.concrete_type_by_name(t.innermost_name()));
+
+        if let Some(MetaType::Enum(enum_type)) = enum_type {
+            let deprecation = enum_type
+                .values
+                .iter()
+                .find(|v| &v.name == value.item)
+                .map(|v| &v.deprecation_status);
+            if let Some(DeprecationStatus::Deprecated(reason)) = deprecation {
+                let message =
+                    enum_value_error_message(value.item, &enum_type.name, reason.as_deref());
+                ctx.report_rule_error("NoDeprecatedUsage", &message, &[value.start]);
+            }
+        }
+    }
+}
+
+fn with_reason(message: String, reason: Option<&str>) -> String {
+    match reason {
+        Some(reason) => format!("{}: {}", message, reason),
+        None => message,
+    }
+}
+
+fn field_error_message(field: &str, type_name: &str, reason: Option<&str>) -> String {
+    with_reason(
+        format!(r#"Field "{}" on type "{}" is deprecated"#, field, type_name),
+        reason,
+    )
+}
+
+fn argument_error_message(arg: &str, field: &str, reason: Option<&str>) -> String {
+    with_reason(
+        format!(r#"Argument "{}" of field "{}" is deprecated"#, arg, field),
+        reason,
+    )
+}
+
+fn enum_value_error_message(value: &str, type_name: &str, reason: Option<&str>) -> String {
+    with_reason(
+        format!(
+            r#"Enum value "{}" of type "{}" is deprecated"#,
+            value, type_name
+        ),
+        reason,
+    )
+}
@@ -112,8 +112,10 @@ impl<'a> CycleDetector<'a> {
                     node
                 };
 
-                self.errors
-                    .push(RuleError::new(&error_message(name), &[err_pos.start]));
+                self.errors.push(
+                    RuleError::new(&error_message(name), &[err_pos.start])
+                        .with_rule("NoFragmentCycles"),
+                );
             } else if !self.visited.contains(name) {
                 path.push(node);
                 self.detect_from(name, path);
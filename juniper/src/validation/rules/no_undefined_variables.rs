@@ -78,6 +78,7 @@ where
                     .into_iter()
                     .map(|var| {
                         RuleError::new(&error_message(var.item, *op_name), &[var.start, *pos])
+                            .with_rule("NoUndefinedVariables")
                     })
                     .collect(),
             );
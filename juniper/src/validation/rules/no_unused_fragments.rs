@@ -65,7 +65,11 @@ where
 
         for fragment in &self.defined_fragments {
             if !reachable.contains(&fragment.item) {
-                ctx.report_error(&error_message(fragment.item), &[fragment.start]);
+                ctx.report_rule_error(
+                    "NoUnusedFragments",
+                    &error_message(fragment.item),
+                    &[fragment.start],
+                );
             }
         }
     }
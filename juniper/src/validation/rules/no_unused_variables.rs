@@ -77,7 +77,10 @@ where
                 def_vars
                     .iter()
                     .filter(|var| !used.contains(var.item))
-                    .map(|var| RuleError::new(&error_message(var.item, *op_name), &[var.start]))
+                    .map(|var| {
+                        RuleError::new(&error_message(var.item, *op_name), &[var.start])
+                            .with_rule("NoUnusedVariables")
+                    })
                     .collect(),
             );
         }
@@ -714,7 +714,11 @@ where
             self.find_conflicts_within_selection_set(ctx.parent_type(), selection_set, ctx)
         {
             p1.append(&mut p2);
-            ctx.report_error(&error_message(&reason_name, &reason_msg), &p1);
+            ctx.report_rule_error(
+                "OverlappingFieldsCanBeMerged",
+                &error_message(&reason_name, &reason_msg),
+                &p1,
+            );
         }
     }
 }
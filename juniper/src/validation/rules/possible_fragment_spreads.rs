@@ -46,7 +46,8 @@ where
                 .and_then(|s| ctx.schema.concrete_type_by_name(s.item)),
         ) {
             if !ctx.schema.type_overlap(parent_type, frag_type) {
-                ctx.report_error(
+                ctx.report_rule_error(
+                    "PossibleFragmentSpreads",
                     &error_message(
                         None,
                         parent_type.name().unwrap_or("<unknown>"),
@@ -68,7 +69,8 @@ where
             self.fragment_types.get(spread.item.name.item),
         ) {
             if !ctx.schema.type_overlap(parent_type, frag_type) {
-                ctx.report_error(
+                ctx.report_rule_error(
+                    "PossibleFragmentSpreads",
                     &error_message(
                         Some(spread.item.name.item),
                         parent_type.name().unwrap_or("<unknown>"),
@@ -33,7 +33,8 @@ where
                         .and_then(|args| args.item.get(&meta_arg.name))
                         .is_none()
                 {
-                    ctx.report_error(
+                    ctx.report_rule_error(
+                        "ProvidedNonNullArguments",
                         &field_error_message(
                             field_name,
                             &meta_arg.name,
@@ -56,7 +57,7 @@ where
         if let Some(&DirectiveType {
             arguments: ref meta_args,
             ..
-        }) = ctx.schema.directive_by_name(directive_name)
+        }) = ctx.current_directive()
         {
             for meta_arg in meta_args {
                 if meta_arg.arg_type.is_non_null()
@@ -67,7 +68,8 @@ where
                         .and_then(|args| args.item.get(&meta_arg.name))
                         .is_none()
                 {
-                    ctx.report_error(
+                    ctx.report_rule_error(
+                        "ProvidedNonNullArguments",
                         &directive_error_message(
                             directive_name,
                             &meta_arg.name,
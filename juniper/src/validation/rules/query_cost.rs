@@ -0,0 +1,264 @@
+use std::collections::HashMap;
+
+use crate::{
+    ast::{Definition, Document, Field, Fragment, Operation, OperationType, Selection},
+    parser::Spanning,
+    schema::{meta::MetaType, model::SchemaType},
+    types::base::Arguments,
+    value::ScalarValue,
+    Variables,
+};
+
+/// Computes the cost of the given `operation` of the `document`, according to
+/// the [`Complexity`] declared by the fields of the `schema`.
+///
+/// Each field costs its declared [`Complexity`], given its arguments (with the
+/// `variables` substituted) and the cost of its selection set, or `1` plus the
+/// cost of its selection set if it declares none. Fields of fragments are
+/// counted once per their spread, and fields excluded by `@skip`/`@include`
+/// directives are counted too, so the result is an upper bound.
+///
+/// [`Complexity`]: crate::meta::Complexity
+pub(crate) fn operation_cost<S>(
+    schema: &SchemaType<S>,
+    document: &Document<S>,
+    operation: &Spanning<Operation<S>>,
+    variables: &Variables<S>,
+) -> usize
+where
+    S: ScalarValue,
+{
+    let root = match operation.item.operation_type {
+        OperationType::Query => Some(schema.concrete_query_type()),
+        OperationType::Mutation => schema.concrete_mutation_type(),
+        OperationType::Subscription => schema.concrete_subscription_type(),
+    };
+    let mut analyzer = QueryCost {
+        schema,
+        variables,
+        fragments: document
+            .iter()
+            .filter_map(|def| match def {
+                Definition::Fragment(f) => Some((f.item.name.item, &f.item)),
+                Definition::Operation(_) => None,
+            })
+            .collect(),
+        costs: HashMap::new(),
+    };
+    analyzer.selection_set_cost(root, &operation.item.selection_set)
+}
+
+/// Analyzer of selection sets costs, computing the cost of each fragment once,
+/// so spreading the same fragment many times doesn't blow up the analysis
+/// itself.
+struct QueryCost<'s, 'd, 'a, S> {
+    schema: &'s SchemaType<'s, S>,
+    variables: &'s Variables<S>,
+    fragments: HashMap<&'a str, &'d Fragment<'a, S>>,
+    costs: HashMap<&'a str, Option<usize>>,
+}
+
+impl<'s, 'd, 'a, S> QueryCost<'s, 'd, 'a, S>
+where
+    S: ScalarValue,
+{
+    fn selection_set_cost(
+        &mut self,
+        parent: Option<&'s MetaType<'s, S>>,
+        selection_set: &'d [Selection<'a, S>],
+    ) -> usize {
+        selection_set.iter().fold(0, |acc, selection| {
+            let cost = match selection {
+                Selection::Field(f) => self.field_cost(parent, &f.item),
+                Selection::InlineFragment(f) => {
+                    let parent = match &f.item.type_condition {
+                        Some(cond) => self.schema.concrete_type_by_name(cond.item),
+                        None => parent,
+                    };
+                    self.selection_set_cost(parent, &f.item.selection_set)
+                }
+                Selection::FragmentSpread(s) => self.fragment_cost(s.item.name.item),
+            };
+            acc.saturating_add(cost)
+        })
+    }
+
+    fn field_cost(
+        &mut self,
+        parent: Option<&'s MetaType<'s, S>>,
+        field: &'d Field<'a, S>,
+    ) -> usize {
+        let meta_field = parent.and_then(|t| t.field_by_name(field.name.item));
+        let child_cost = field
+            .selection_set
+            .as_deref()
+            .map(|s| {
+                let ty = meta_field.and_then(|f| {
+                    self.schema
+                        .concrete_type_by_name(f.field_type.innermost_name())
+                });
+                self.selection_set_cost(ty, s)
+            })
+            .unwrap_or_default();
+
+        match meta_field.and_then(|f| f.complexity.as_ref().map(|c| (f, c))) {
+            Some((meta_field, complexity)) => {
+                let args = Arguments::new(
+                    field.arguments.as_ref().map(|m| {
+                        m.item
+                            .iter()
+                            .map(|(k, v)| (k.item, v.item.clone().into_const(self.variables)))
+                            .collect()
+                    }),
+                    meta_field.arguments.as_deref(),
+                );
+                complexity.cost(&args, child_cost)
+            }
+            None => child_cost.saturating_add(1),
+        }
+    }
+
+    fn fragment_cost(&mut self, name: &'a str) -> usize {
+        match self.costs.get(name) {
+            Some(cost) => {
+                // `None` means a fragment cycle, which is rejected by the
+                // validation anyway.
+                return cost.unwrap_or_default();
+            }
+            None => {
+                self.costs.insert(name, None);
+            }
+        }
+        let cost = self
+            .fragments
+            .get(name)
+            .copied()
+            .map(|f| {
+                let parent = self.schema.concrete_type_by_name(f.type_condition.item);
+                self.selection_set_cost(parent, &f.selection_set)
+            })
+            .unwrap_or_default();
+        self.costs.insert(name, Some(cost));
+        cost
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::operation_cost;
+
+    use crate::{
+        ast::{Definition, OwnedDocument},
+        graphql_object, graphql_vars,
+        parser::parse_document_source,
+        schema::{meta::Complexity, model::RootNode},
+        types::{base::Arguments, scalars::EmptySubscription},
+        value::DefaultScalarValue,
+        EmptyMutation, Registry, Variables,
+    };
+
+    struct Item;
+
+    #[graphql_object]
+    impl Item {
+        fn id() -> i32 {
+            1
+        }
+
+        #[graphql(complexity = 5)]
+        fn price() -> i32 {
+            10
+        }
+    }
+
+    struct Query;
+
+    #[graphql_object]
+    impl Query {
+        fn item() -> Item {
+            Item
+        }
+
+        #[graphql(complexity = |args, child| {
+            let first = args.get::<i32>("first").ok().flatten().unwrap_or(0);
+            first.max(0) as usize * child
+        })]
+        fn items(#[graphql(default = 10)] first: i32) -> Vec<Item> {
+            (0..first).map(|_| Item).collect()
+        }
+    }
+
+    fn cost(query: &str, vars: &Variables) -> usize {
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+        let document: OwnedDocument<DefaultScalarValue> =
+            parse_document_source(query, &schema.schema).unwrap();
+        let operation = document
+            .iter()
+            .find_map(|def| match def {
+                Definition::Operation(op) => Some(op),
+                Definition::Fragment(_) => None,
+            })
+            .unwrap();
+        operation_cost(&schema.schema, &document, operation, vars)
+    }
+
+    #[test]
+    fn counts_fields_by_default() {
+        assert_eq!(cost("{ item { id __typename } }", &graphql_vars! {}), 3);
+    }
+
+    #[test]
+    fn adds_static_complexity() {
+        assert_eq!(cost("{ item { id price } }", &graphql_vars! {}), 7);
+    }
+
+    #[test]
+    fn computes_dynamic_complexity() {
+        assert_eq!(
+            cost("{ items(first: 3) { id price } }", &graphql_vars! {}),
+            18
+        );
+        assert_eq!(cost("{ items { id } }", &graphql_vars! {}), 10);
+        assert_eq!(
+            cost(
+                "query($n: Int!) { items(first: $n) { id } }",
+                &graphql_vars! {"n": 7},
+            ),
+            7,
+        );
+    }
+
+    #[test]
+    fn follows_fragments() {
+        assert_eq!(
+            cost(
+                r#"
+                  { items(first: 2) { ...itemFields } item { ... on Item { ...itemFields } } }
+                  fragment itemFields on Item { id price }
+                "#,
+                &graphql_vars! {},
+            ),
+            19,
+        );
+    }
+
+    #[test]
+    fn overrides_complexity_in_registry() {
+        let mut registry = Registry::new(Default::default());
+        registry.get_type::<Query>(&());
+
+        assert!(registry.set_field_complexity("Item", "id", Complexity::Static(3)));
+        assert!(!registry.set_field_complexity("Item", "name", Complexity::Static(3)));
+        assert!(!registry.set_field_complexity("Shop", "id", Complexity::Static(3)));
+
+        let complexity = registry.types["Item"]
+            .field_by_name("id")
+            .and_then(|f| f.complexity.as_ref())
+            .map(|c| c.cost(&Arguments::<DefaultScalarValue>::new(None, None), 0));
+        assert_eq!(complexity, Some(3));
+    }
+}
@@ -37,7 +37,7 @@ where
         };
 
         if let Some(error) = error {
-            ctx.append_errors(vec![error]);
+            ctx.append_errors(vec![error.with_rule("ScalarLeafs")]);
         }
     }
 }
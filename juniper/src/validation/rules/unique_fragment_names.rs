@@ -28,7 +28,8 @@ where
     ) {
         match self.names.entry(f.item.name.item) {
             Entry::Occupied(e) => {
-                context.report_error(
+                context.report_rule_error(
+                    "UniqueFragmentNames",
                     &duplicate_message(f.item.name.item),
                     &[*e.get(), f.item.name.start],
                 );
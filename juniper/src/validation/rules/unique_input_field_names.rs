@@ -37,7 +37,8 @@ where
         if let Some(ref mut known_names) = self.known_name_stack.last_mut() {
             match known_names.entry(&field_name.item) {
                 Entry::Occupied(e) => {
-                    ctx.report_error(
+                    ctx.report_rule_error(
+                        "UniqueInputFieldNames",
                         &error_message(&field_name.item),
                         &[*e.get(), field_name.start],
                     );
@@ -29,7 +29,11 @@ where
         if let Some(ref op_name) = op.item.name {
             match self.names.entry(op_name.item) {
                 Entry::Occupied(e) => {
-                    ctx.report_error(&error_message(op_name.item), &[*e.get(), op.start]);
+                    ctx.report_rule_error(
+                        "UniqueOperationNames",
+                        &error_message(op_name.item),
+                        &[*e.get(), op.start],
+                    );
                 }
                 Entry::Vacant(e) => {
                     e.insert(op.start);
@@ -36,7 +36,11 @@ where
     ) {
         match self.names.entry(var_name.item) {
             Entry::Occupied(e) => {
-                ctx.report_error(&error_message(var_name.item), &[*e.get(), var_name.start]);
+                ctx.report_rule_error(
+                    "UniqueVariableNames",
+                    &error_message(var_name.item),
+                    &[*e.get(), var_name.start],
+                );
             }
             Entry::Vacant(e) => {
                 e.insert(var_name.start);
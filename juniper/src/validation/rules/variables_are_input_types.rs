@@ -25,7 +25,8 @@ where
             .concrete_type_by_name(var_def.var_type.item.innermost_name())
         {
             if !var_type.is_input() {
-                ctx.report_error(
+                ctx.report_rule_error(
+                    "VariablesAreInputTypes",
                     &error_message(var_name.item, &format!("{}", var_def.var_type.item)),
                     &[var_def.var_type.start],
                 );
@@ -65,7 +65,8 @@ impl<'a, S: Debug> VariableInAllowedPosition<'a, S> {
                     };
 
                     if !ctx.schema.is_subtype(&expected_type, var_type) {
-                        ctx.report_error(
+                        ctx.report_rule_error(
+                            "VariablesInAllowedPosition",
                             &error_message(
                                 var_name.item,
                                 &format!("{}", expected_type),
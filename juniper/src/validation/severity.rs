@@ -0,0 +1,86 @@
+//! Configurable severities of built-in validation rules.
+
+/// Built-in validation rule, the [`RuleSeverity`] of which may be configured
+/// per schema with [`RootNode::with_rule_severity()`].
+///
+/// The rest of the built-in rules can't be relaxed, as the execution relies on
+/// the documents satisfying them (e.g. on every queried field being known to
+/// the schema).
+///
+/// [`RootNode::with_rule_severity()`]: crate::RootNode::with_rule_severity
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ConfigurableRule {
+    /// Requires all the arguments of fields and directives to be defined by
+    /// them.
+    KnownArgumentNames,
+
+    /// Requires all the directives to be known to the schema and to be used in
+    /// the locations they're allowed in.
+    KnownDirectives,
+
+    /// Requires an anonymous operation to be the only operation in a document.
+    LoneAnonymousOperation,
+
+    /// Requires all the fragments defined in a document to be used by its
+    /// operations.
+    NoUnusedFragments,
+
+    /// Requires all the variables defined by an operation to be used by it.
+    NoUnusedVariables,
+
+    /// Requires the fields selected under the same response name to be
+    /// mergeable into a single one.
+    OverlappingFieldsCanBeMerged,
+
+    /// Requires the arguments of a field or directive to be named uniquely.
+    UniqueArgumentNames,
+
+    /// Requires the fragments of a document to be named uniquely.
+    UniqueFragmentNames,
+
+    /// Requires the fields of input object literals to be named uniquely.
+    UniqueInputFieldNames,
+
+    /// Requires the operations of a document to be named uniquely.
+    UniqueOperationNames,
+
+    /// Requires the variables of an operation to be named uniquely.
+    UniqueVariableNames,
+}
+
+impl ConfigurableRule {
+    /// Returns the stable identifier of this [`ConfigurableRule`], as reported
+    /// in its errors and timings.
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Self::KnownArgumentNames => "KnownArgumentNames",
+            Self::KnownDirectives => "KnownDirectives",
+            Self::LoneAnonymousOperation => "LoneAnonymousOperation",
+            Self::NoUnusedFragments => "NoUnusedFragments",
+            Self::NoUnusedVariables => "NoUnusedVariables",
+            Self::OverlappingFieldsCanBeMerged => "OverlappingFieldsCanBeMerged",
+            Self::UniqueArgumentNames => "UniqueArgumentNames",
+            Self::UniqueFragmentNames => "UniqueFragmentNames",
+            Self::UniqueInputFieldNames => "UniqueInputFieldNames",
+            Self::UniqueOperationNames => "UniqueOperationNames",
+            Self::UniqueVariableNames => "UniqueVariableNames",
+        }
+    }
+}
+
+/// Severity of a [`ConfigurableRule`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum RuleSeverity {
+    /// Violations of the rule make a document invalid, as the GraphQL
+    /// specification requires.
+    Error,
+
+    /// Violations of the rule don't make a document invalid, but are recorded
+    /// into the [`MetricsSink`] of the schema, if any.
+    ///
+    /// [`MetricsSink`]: crate::MetricsSink
+    Warning,
+
+    /// The rule is not checked at all.
+    Off,
+}
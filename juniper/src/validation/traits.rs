@@ -8,20 +8,31 @@ use crate::{
     value::ScalarValue,
 };
 
-#[doc(hidden)]
+/// Visitor of a GraphQL document being validated, implementing a validation
+/// rule.
+///
+/// Custom rules may be registered for a schema with
+/// [`RootNode::with_validation_rule()`], and report their violations into the
+/// provided [`ValidatorContext`].
+///
+/// [`RootNode::with_validation_rule()`]: crate::RootNode::with_validation_rule
 pub trait Visitor<'a, S>
 where
     S: ScalarValue,
 {
+    /// Called on entering the document.
     fn enter_document(&mut self, _: &mut ValidatorContext<'a, S>, _: &'a Document<S>) {}
+    /// Called on exiting the document.
     fn exit_document(&mut self, _: &mut ValidatorContext<'a, S>, _: &'a Document<S>) {}
 
+    /// Called on entering an operation definition.
     fn enter_operation_definition(
         &mut self,
         _: &mut ValidatorContext<'a, S>,
         _: &'a Spanning<Operation<S>>,
     ) {
     }
+    /// Called on exiting an operation definition.
     fn exit_operation_definition(
         &mut self,
         _: &mut ValidatorContext<'a, S>,
@@ -29,12 +40,14 @@ where
     ) {
     }
 
+    /// Called on entering a fragment definition.
     fn enter_fragment_definition(
         &mut self,
         _: &mut ValidatorContext<'a, S>,
         _: &'a Spanning<Fragment<S>>,
     ) {
     }
+    /// Called on exiting a fragment definition.
     fn exit_fragment_definition(
         &mut self,
         _: &mut ValidatorContext<'a, S>,
@@ -42,12 +55,14 @@ where
     ) {
     }
 
+    /// Called on entering a variable definition.
     fn enter_variable_definition(
         &mut self,
         _: &mut ValidatorContext<'a, S>,
         _: &'a (Spanning<&'a str>, VariableDefinition<S>),
     ) {
     }
+    /// Called on exiting a variable definition.
     fn exit_variable_definition(
         &mut self,
         _: &mut ValidatorContext<'a, S>,
@@ -55,15 +70,19 @@ where
     ) {
     }
 
+    /// Called on entering a directive.
     fn enter_directive(&mut self, _: &mut ValidatorContext<'a, S>, _: &'a Spanning<Directive<S>>) {}
+    /// Called on exiting a directive.
     fn exit_directive(&mut self, _: &mut ValidatorContext<'a, S>, _: &'a Spanning<Directive<S>>) {}
 
+    /// Called on entering an argument.
     fn enter_argument(
         &mut self,
         _: &mut ValidatorContext<'a, S>,
         _: &'a (Spanning<&'a str>, Spanning<InputValue<S>>),
     ) {
     }
+    /// Called on exiting an argument.
     fn exit_argument(
         &mut self,
         _: &mut ValidatorContext<'a, S>,
@@ -71,18 +90,24 @@ where
     ) {
     }
 
+    /// Called on entering a selection set.
     fn enter_selection_set(&mut self, _: &mut ValidatorContext<'a, S>, _: &'a [Selection<S>]) {}
+    /// Called on exiting a selection set.
     fn exit_selection_set(&mut self, _: &mut ValidatorContext<'a, S>, _: &'a [Selection<S>]) {}
 
+    /// Called on entering a field.
     fn enter_field(&mut self, _: &mut ValidatorContext<'a, S>, _: &'a Spanning<Field<S>>) {}
+    /// Called on exiting a field.
     fn exit_field(&mut self, _: &mut ValidatorContext<'a, S>, _: &'a Spanning<Field<S>>) {}
 
+    /// Called on entering a fragment spread.
     fn enter_fragment_spread(
         &mut self,
         _: &mut ValidatorContext<'a, S>,
         _: &'a Spanning<FragmentSpread<S>>,
     ) {
     }
+    /// Called on exiting a fragment spread.
     fn exit_fragment_spread(
         &mut self,
         _: &mut ValidatorContext<'a, S>,
@@ -90,12 +115,14 @@ where
     ) {
     }
 
+    /// Called on entering an inline fragment.
     fn enter_inline_fragment(
         &mut self,
         _: &mut ValidatorContext<'a, S>,
         _: &'a Spanning<InlineFragment<S>>,
     ) {
     }
+    /// Called on exiting an inline fragment.
     fn exit_inline_fragment(
         &mut self,
         _: &mut ValidatorContext<'a, S>,
@@ -103,24 +130,34 @@ where
     ) {
     }
 
+    /// Called on entering a `null` literal.
     fn enter_null_value(&mut self, _: &mut ValidatorContext<'a, S>, _: Spanning<()>) {}
+    /// Called on exiting a `null` literal.
     fn exit_null_value(&mut self, _: &mut ValidatorContext<'a, S>, _: Spanning<()>) {}
 
+    /// Called on entering a scalar literal.
     fn enter_scalar_value(&mut self, _: &mut ValidatorContext<'a, S>, _: Spanning<&'a S>) {}
+    /// Called on exiting a scalar literal.
     fn exit_scalar_value(&mut self, _: &mut ValidatorContext<'a, S>, _: Spanning<&'a S>) {}
 
+    /// Called on entering an enum literal.
     fn enter_enum_value(&mut self, _: &mut ValidatorContext<'a, S>, _: Spanning<&'a String>) {}
+    /// Called on exiting an enum literal.
     fn exit_enum_value(&mut self, _: &mut ValidatorContext<'a, S>, _: Spanning<&'a String>) {}
 
+    /// Called on entering a variable usage.
     fn enter_variable_value(&mut self, _: &mut ValidatorContext<'a, S>, _: Spanning<&'a String>) {}
+    /// Called on exiting a variable usage.
     fn exit_variable_value(&mut self, _: &mut ValidatorContext<'a, S>, _: Spanning<&'a String>) {}
 
+    /// Called on entering a list literal.
     fn enter_list_value(
         &mut self,
         _: &mut ValidatorContext<'a, S>,
         _: Spanning<&'a Vec<Spanning<InputValue<S>>>>,
     ) {
     }
+    /// Called on exiting a list literal.
     fn exit_list_value(
         &mut self,
         _: &mut ValidatorContext<'a, S>,
@@ -128,15 +165,19 @@ where
     ) {
     }
 
+    /// Called on entering an input object literal.
     fn enter_object_value(&mut self, _: &mut ValidatorContext<'a, S>, _: SpannedObject<'a, S>) {}
+    /// Called on exiting an input object literal.
     fn exit_object_value(&mut self, _: &mut ValidatorContext<'a, S>, _: SpannedObject<'a, S>) {}
 
+    /// Called on entering a field of an input object literal.
     fn enter_object_field(
         &mut self,
         _: &mut ValidatorContext<'a, S>,
         _: &'a (Spanning<String>, Spanning<InputValue<S>>),
     ) {
     }
+    /// Called on exiting a field of an input object literal.
     fn exit_object_field(
         &mut self,
         _: &mut ValidatorContext<'a, S>,
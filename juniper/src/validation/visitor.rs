@@ -141,18 +141,7 @@ fn visit_variable_definitions<'a, S, V>(
                     visit_input_value(v, ctx, default_value);
                 }
 
-                if let Some(dirs) = &def.1.directives {
-                    for directive in dirs {
-                        let directive_arguments = ctx
-                            .schema
-                            .directive_by_name(directive.item.name.item)
-                            .map(|d| &d.arguments);
-
-                        v.enter_directive(ctx, directive);
-                        visit_arguments(v, ctx, directive_arguments, &directive.item.arguments);
-                        v.exit_directive(ctx, directive);
-                    }
-                }
+                visit_directives(v, ctx, &def.1.directives);
 
                 v.exit_variable_definition(ctx, def);
             })
@@ -170,14 +159,13 @@ fn visit_directives<'a, S, V>(
 {
     if let Some(ref directives) = *directives {
         for directive in directives {
-            let directive_arguments = ctx
-                .schema
-                .directive_by_name(directive.item.name.item)
-                .map(|d| &d.arguments);
+            ctx.with_pushed_directive(directive.item.name.item, |ctx| {
+                let directive_arguments = ctx.current_directive().map(|d| &d.arguments);
 
-            v.enter_directive(ctx, directive);
-            visit_arguments(v, ctx, directive_arguments, &directive.item.arguments);
-            v.exit_directive(ctx, directive);
+                v.enter_directive(ctx, directive);
+                visit_arguments(v, ctx, directive_arguments, &directive.item.arguments);
+                v.exit_directive(ctx, directive);
+            })
         }
     }
 }
@@ -1,5 +1,6 @@
 mod object;
 mod scalar;
+mod walk;
 
 use std::{any::TypeId, borrow::Cow, fmt, mem};
 
@@ -10,7 +11,10 @@ use crate::{
 
 pub use self::{
     object::Object,
-    scalar::{DefaultScalarValue, ParseScalarResult, ParseScalarValue, ScalarValue},
+    scalar::{
+        DefaultScalarValue, ParseScalarResult, ParseScalarValue, ScalarValue,
+        SpannedParseScalarResult,
+    },
 };
 
 /// Serializable value returned from query and field execution.
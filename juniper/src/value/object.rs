@@ -1,7 +1,7 @@
 use std::{iter::FromIterator, mem};
 
 use super::Value;
-use indexmap::map::{IndexMap, IntoIter};
+use indexmap::map::{Drain, IndexMap, IntoIter};
 
 /// An Object value
 #[derive(Debug, Clone, PartialEq)]
@@ -65,6 +65,33 @@ impl<S> Object<S> {
     pub fn get_mut_field_value<K: AsRef<str>>(&mut self, key: K) -> Option<&mut Value<S>> {
         self.key_value_list.get_mut(key.as_ref())
     }
+
+    /// Remove all the field-value pairs, keeping the allocated slots for the
+    /// new ones
+    pub(crate) fn drain(&mut self) -> Drain<'_, String, Value<S>> {
+        self.key_value_list.drain(..)
+    }
+
+    /// Get the number of the field-value pairs this object can hold without
+    /// reallocating
+    pub(crate) fn capacity(&self) -> usize {
+        self.key_value_list.capacity()
+    }
+
+    /// Preallocate slots for at least the given number of additional
+    /// field-value pairs
+    pub(crate) fn reserve(&mut self, additional: usize) {
+        self.key_value_list.reserve(additional)
+    }
+
+    /// Retain only the fields for which the given predicate returns `true`,
+    /// preserving the order of the remaining ones
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&str, &mut Value<S>) -> bool,
+    {
+        self.key_value_list.retain(|k, v| f(k, v))
+    }
 }
 
 impl<S> IntoIterator for Object<S> {
@@ -2,17 +2,36 @@ use std::{borrow::Cow, fmt};
 
 use serde::{de::DeserializeOwned, Serialize};
 
-use crate::parser::{ParseError, ScalarToken};
+use crate::parser::{ParseError, ScalarToken, Spanning};
 
 pub use juniper_codegen::ScalarValue;
 
 /// The result of converting a string into a scalar value
 pub type ParseScalarResult<'a, S = DefaultScalarValue> = Result<S, ParseError<'a>>;
 
+/// The result of converting a string positioned in the source into a scalar
+/// value, with the failure located in the source.
+pub type SpannedParseScalarResult<'a, S = DefaultScalarValue> = Result<S, Spanning<ParseError<'a>>>;
+
 /// A trait used to convert a `ScalarToken` into a certain scalar value type
 pub trait ParseScalarValue<S = DefaultScalarValue> {
     /// See the trait documentation
     fn from_str(value: ScalarToken<'_>) -> ParseScalarResult<'_, S>;
+
+    /// Converts the given [`ScalarToken`] positioned in the source into a
+    /// scalar value.
+    ///
+    /// By default, delegates to [`ParseScalarValue::from_str()`] and locates
+    /// its error at the whole token. Override this method to point the error
+    /// at the exact part of the token that is invalid (like the day of an
+    /// invalid date).
+    ///
+    /// Returning a [`ParseError::InvalidScalar`] reports it to the client as
+    /// is, while any other error makes the token to be parsed as a value of
+    /// its inferred type, leaving the error to the validation.
+    fn from_spanned_str(token: Spanning<ScalarToken<'_>>) -> SpannedParseScalarResult<'_, S> {
+        Self::from_str(token.item).map_err(|e| Spanning::start_end(&token.start, &token.end, e))
+    }
 }
 
 /// A trait marking a type that could be used as internal representation of
@@ -200,6 +219,36 @@ pub trait ScalarValue:
     #[must_use]
     fn as_float(&self) -> Option<f64>;
 
+    /// Coerces this [`ScalarValue`] into a float value, when provided as an
+    /// input for a [`Float`] type.
+    ///
+    /// This function is used for implementing [`FromInputValue`] for [`f64`]
+    /// and parsing [`Float`] literals for all possible [`ScalarValue`]s, so
+    /// both literals and variables are coerced the same way. By default, it
+    /// accepts integer values along with float ones, as required by
+    /// [input coercion rules][0] of the GraphQL specification.
+    ///
+    /// Implementations may override it to customize the coercion, like
+    /// rejecting integer values for a strict mode. [`ScalarValue`] derive
+    /// macro does so when the `coerce_float` attribute argument is placed on
+    /// enum variants, considering only them as float values.
+    ///
+    /// ```
+    /// # use juniper::{ScalarValue, DefaultScalarValue};
+    /// #
+    /// assert_eq!(DefaultScalarValue::Int(1).coerce_float(), Some(1.0));
+    /// assert_eq!(DefaultScalarValue::Float(0.5).coerce_float(), Some(0.5));
+    /// assert_eq!(DefaultScalarValue::Boolean(true).coerce_float(), None);
+    /// ```
+    ///
+    /// [`Float`]: https://spec.graphql.org/June2018/#sec-Float
+    /// [`FromInputValue`]: crate::FromInputValue
+    /// [0]: https://spec.graphql.org/June2018/#sec-Float.Input-Coercion
+    #[must_use]
+    fn coerce_float(&self) -> Option<f64> {
+        self.as_float().or_else(|| self.as_int().map(f64::from))
+    }
+
     /// Represents this [`ScalarValue`] as a boolean value
     ///
     /// This function is used for implementing [`GraphQLValue`] for [`bool`] for
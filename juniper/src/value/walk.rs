@@ -0,0 +1,271 @@
+//! Traversal and transformation of [`Value`] trees.
+
+use crate::executor::PathSegment;
+
+use super::Value;
+
+impl<S> Value<S> {
+    /// Walks this [`Value`] recursively in depth-first order, calling the
+    /// provided function on every nested value (parents before their children),
+    /// along with its path from this [`Value`].
+    ///
+    /// ```rust
+    /// # use juniper::{graphql_value, PathSegment, Value};
+    /// #
+    /// let value: Value = graphql_value!({"user": {"emails": ["a@b.c", null]}});
+    ///
+    /// let mut nulls = vec![];
+    /// value.walk(|path, v| {
+    ///     if v.is_null() {
+    ///         nulls.push(path.to_vec());
+    ///     }
+    /// });
+    /// assert_eq!(
+    ///     nulls,
+    ///     [["user".into(), "emails".into(), PathSegment::Index(1)]],
+    /// );
+    /// ```
+    pub fn walk<F>(&self, mut f: F)
+    where
+        F: FnMut(&[PathSegment], &Self),
+    {
+        self.walk_at(&mut Vec::new(), &mut f)
+    }
+
+    fn walk_at<F>(&self, path: &mut Vec<PathSegment>, f: &mut F)
+    where
+        F: FnMut(&[PathSegment], &Self),
+    {
+        f(path, self);
+        match self {
+            Self::Null | Self::Scalar(_) => {}
+            Self::List(l) => {
+                for (i, v) in l.iter().enumerate() {
+                    path.push(PathSegment::Index(i));
+                    v.walk_at(path, f);
+                    path.pop();
+                }
+            }
+            Self::Object(o) => {
+                for (k, v) in o.iter() {
+                    path.push(PathSegment::Field(k.clone()));
+                    v.walk_at(path, f);
+                    path.pop();
+                }
+            }
+        }
+    }
+
+    /// Transforms this [`Value`] recursively, replacing every nested value
+    /// with the one returned by the provided function, which is given its path
+    /// from this [`Value`].
+    ///
+    /// Children are transformed before their parents, so the function receives
+    /// already transformed lists and objects.
+    ///
+    /// ```rust
+    /// # use juniper::{graphql_value, PathSegment, Value};
+    /// #
+    /// let value: Value = graphql_value!({"card": {"number": "4242424242424242"}});
+    ///
+    /// let redacted = value.map(|path, v| match path.last() {
+    ///     Some(PathSegment::Field(f)) if f == "number" => graphql_value!("****"),
+    ///     _ => v,
+    /// });
+    /// assert_eq!(redacted, graphql_value!({"card": {"number": "****"}}));
+    /// ```
+    #[must_use]
+    pub fn map<F>(self, mut f: F) -> Self
+    where
+        F: FnMut(&[PathSegment], Self) -> Self,
+    {
+        self.map_at(&mut Vec::new(), &mut f)
+    }
+
+    fn map_at<F>(self, path: &mut Vec<PathSegment>, f: &mut F) -> Self
+    where
+        F: FnMut(&[PathSegment], Self) -> Self,
+    {
+        let mapped = match self {
+            Self::Null | Self::Scalar(_) => self,
+            Self::List(l) => Self::List(
+                l.into_iter()
+                    .enumerate()
+                    .map(|(i, v)| {
+                        path.push(PathSegment::Index(i));
+                        let v = v.map_at(path, f);
+                        path.pop();
+                        v
+                    })
+                    .collect(),
+            ),
+            Self::Object(o) => Self::Object(
+                o.into_iter()
+                    .map(|(k, v)| {
+                        path.push(PathSegment::Field(k));
+                        let v = v.map_at(path, f);
+                        match path.pop() {
+                            Some(PathSegment::Field(k)) => (k, v),
+                            _ => unreachable!("pushed `PathSegment::Field` is popped"),
+                        }
+                    })
+                    .collect(),
+            ),
+        };
+        f(path, mapped)
+    }
+
+    /// Prunes this [`Value`] recursively, removing every nested object field
+    /// and list element for which the provided predicate returns `false`. The
+    /// predicate is given the path of the value from this [`Value`].
+    ///
+    /// Parents are checked before their children, so the predicate isn't
+    /// called for the values inside the removed ones. This [`Value`] itself is
+    /// never removed.
+    ///
+    /// ```rust
+    /// # use juniper::{graphql_value, PathSegment, Value};
+    /// #
+    /// let mut value: Value = graphql_value!({"user": {"name": "Alice", "beta": true}});
+    ///
+    /// // Strips the fields unknown to old clients.
+    /// value.retain(|path, _| path.last() != Some(&"beta".into()));
+    /// assert_eq!(value, graphql_value!({"user": {"name": "Alice"}}));
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&[PathSegment], &Self) -> bool,
+    {
+        self.retain_at(&mut Vec::new(), &mut f)
+    }
+
+    fn retain_at<F>(&mut self, path: &mut Vec<PathSegment>, f: &mut F)
+    where
+        F: FnMut(&[PathSegment], &Self) -> bool,
+    {
+        match self {
+            Self::Null | Self::Scalar(_) => {}
+            Self::List(l) => {
+                let mut i = 0;
+                l.retain_mut(|v| {
+                    path.push(PathSegment::Index(i));
+                    i += 1;
+                    let keep = f(path, v);
+                    if keep {
+                        v.retain_at(path, f);
+                    }
+                    path.pop();
+                    keep
+                });
+            }
+            Self::Object(o) => o.retain(|k, v| {
+                path.push(PathSegment::Field(k.into()));
+                let keep = f(path, v);
+                if keep {
+                    v.retain_at(path, f);
+                }
+                path.pop();
+                keep
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{executor::PathSegment, graphql_value};
+
+    use super::Value;
+
+    fn value() -> Value {
+        graphql_value!({
+            "a": [1, {"b": null}],
+            "c": {"d": "str"},
+        })
+    }
+
+    #[test]
+    fn walk() {
+        let mut visited = vec![];
+        value().walk(|path, v| visited.push((path.to_vec(), v.clone())));
+
+        assert_eq!(
+            visited,
+            [
+                (vec![], value()),
+                (vec!["a".into()], graphql_value!([1, {"b": null}])),
+                (vec!["a".into(), 0.into()], graphql_value!(1)),
+                (vec!["a".into(), 1.into()], graphql_value!({"b": null})),
+                (vec!["a".into(), 1.into(), "b".into()], graphql_value!(null),),
+                (vec!["c".into()], graphql_value!({"d": "str"})),
+                (vec!["c".into(), "d".into()], graphql_value!("str")),
+            ],
+        );
+    }
+
+    #[test]
+    fn map() {
+        let mut order = vec![];
+        let mapped = value().map(|path, v| {
+            order.push(path.to_vec());
+            match v {
+                Value::Null => graphql_value!("was null"),
+                Value::Object(o) if o.contains_field("d") => graphql_value!([]),
+                v => v,
+            }
+        });
+
+        assert_eq!(
+            mapped,
+            graphql_value!({
+                "a": [1, {"b": "was null"}],
+                "c": [],
+            }),
+        );
+        assert_eq!(
+            order,
+            [
+                vec!["a".into(), 0.into()],
+                vec!["a".into(), 1.into(), "b".into()],
+                vec!["a".into(), 1.into()],
+                vec!["a".into()],
+                vec!["c".into(), "d".into()],
+                vec!["c".into()],
+                vec![],
+            ],
+        );
+    }
+
+    #[test]
+    fn retain() {
+        let mut visited = vec![];
+        let mut value = value();
+        value.retain(|path, v| {
+            visited.push(path.to_vec());
+            !matches!(path.last(), Some(PathSegment::Index(1))) && !v.is_null()
+        });
+
+        assert_eq!(value, graphql_value!({"a": [1], "c": {"d": "str"}}));
+        assert_eq!(
+            visited,
+            [
+                vec!["a".into()],
+                vec!["a".into(), 0.into()],
+                vec!["a".into(), 1.into()],
+                vec!["c".into()],
+                vec!["c".into(), "d".into()],
+            ],
+        );
+    }
+
+    #[test]
+    fn retain_keeps_root() {
+        let mut value: Value = graphql_value!(null);
+        value.retain(|_, _| false);
+        assert_eq!(value, graphql_value!(null));
+
+        let mut value: Value = graphql_value!([1, 2]);
+        value.retain(|_, _| false);
+        assert_eq!(value, graphql_value!([]));
+    }
+}
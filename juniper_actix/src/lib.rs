@@ -4,15 +4,16 @@
 #![deny(warnings)]
 
 use actix_web::{
-    error::JsonPayloadError, http::Method, web, Error, FromRequest, HttpMessage, HttpRequest,
-    HttpResponse,
+    error::JsonPayloadError,
+    http::{header, Method},
+    web, Error, FromRequest, HttpMessage, HttpRequest, HttpResponse,
 };
 use juniper::{
     http::{
-        graphiql::graphiql_source, playground::playground_source, GraphQLBatchRequest,
-        GraphQLRequest,
+        graphiql::graphiql_source, playground::playground_source, ETag, GraphQLBatchRequest,
+        GraphQLRequest, RequestDeadline,
     },
-    ScalarValue,
+    Limits, ScalarValue,
 };
 use serde::Deserialize;
 
@@ -80,15 +81,39 @@ where
     S: ScalarValue + Send + Sync,
 {
     let get_req = web::Query::<GetGraphQLRequest>::from_query(req.query_string())?;
-    let req = GraphQLRequest::from(get_req.into_inner());
-    let gql_response = req.execute(schema, context).await;
+    let if_none_match = req
+        .headers()
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok());
+    let limits = request_limits(schema.schema.limits(), &req);
+    let gql_req = GraphQLRequest::from(get_req.into_inner());
+    let gql_response = gql_req.execute_with_limits(schema, context, &limits).await;
     let body_response = serde_json::to_string(&gql_response)?;
-    let mut response = match gql_response.is_ok() {
-        true => HttpResponse::Ok(),
-        false => HttpResponse::BadRequest(),
-    };
-    Ok(response
+    if !gql_response.is_ok() {
+        return Ok(HttpResponse::BadRequest()
+            .content_type("application/json")
+            .body(body_response));
+    }
+
+    // Responses having errors are not cacheable, as the errors may be
+    // transient.
+    if gql_response.has_errors() {
+        return Ok(HttpResponse::Ok()
+            .content_type("application/json")
+            .body(body_response));
+    }
+
+    // Successful responses to `GET` requests are cacheable, so are tagged to
+    // be served as `304 Not Modified` once the client has them cached.
+    let etag = ETag::of(body_response.as_bytes());
+    if matches!(if_none_match, Some(v) if etag.matches(v)) {
+        return Ok(HttpResponse::NotModified()
+            .insert_header((header::ETAG, etag.to_string()))
+            .finish());
+    }
+    Ok(HttpResponse::Ok()
         .content_type("application/json")
+        .insert_header((header::ETAG, etag.to_string()))
         .body(body_response))
 }
 
@@ -109,6 +134,7 @@ where
     CtxT: Sync,
     S: ScalarValue + Send + Sync,
 {
+    let limits = request_limits(schema.schema.limits(), &req);
     let req = match req.content_type() {
         "application/json" => {
             let body = String::from_request(&req, &mut payload.into_inner()).await?;
@@ -123,7 +149,7 @@ where
         }
         _ => Err(JsonPayloadError::ContentType),
     }?;
-    let gql_batch_response = req.execute(schema, context).await;
+    let gql_batch_response = req.execute_with_limits(schema, context, &limits).await;
     let gql_response = serde_json::to_string(&gql_batch_response)?;
     let mut response = match gql_batch_response.is_ok() {
         true => HttpResponse::Ok(),
@@ -132,6 +158,13 @@ where
     Ok(response.content_type("application/json").body(gql_response))
 }
 
+/// Returns the given schema `limits` with the deadline propagated in the
+/// headers of the given request applied.
+fn request_limits(limits: &Limits, req: &HttpRequest) -> Limits {
+    RequestDeadline::from_headers(|name| req.headers().get(name).and_then(|v| v.to_str().ok()))
+        .map_or(*limits, |deadline| deadline.limits(*limits))
+}
+
 /// Create a handler that replies with an HTML page containing GraphiQL. This does not handle routing, so you can mount it on any endpoint
 ///
 /// For example:
@@ -189,7 +222,10 @@ pub mod subscriptions {
         },
         GraphQLSubscriptionType, GraphQLTypeAsync, RootNode, ScalarValue,
     };
-    use juniper_graphql_ws::{ArcSchema, ClientMessage, Connection, Init, ServerMessage};
+    use juniper_graphql_ws::{
+        ArcSchema, ClientMessage, Connection, Init, ServerMessage, ShutdownCoordinator,
+        TraceContext,
+    };
     use tokio::sync::Mutex;
 
     /// Serves the graphql-ws protocol over a WebSocket connection.
@@ -199,6 +235,9 @@ pub mod subscriptions {
     /// configuration are already known, or it can be a closure that gets executed asynchronously
     /// when the client sends the ConnectionInit message. Using a closure allows you to perform
     /// authentication based on the parameters provided by the client.
+    ///
+    /// The W3C `traceparent` and `tracestate` headers of the upgrade request are propagated into
+    /// the spans of the connection's operations (see `juniper_graphql_ws::TraceContext`).
     pub async fn subscriptions_handler<Query, Mutation, Subscription, CtxT, S, I>(
         req: HttpRequest,
         stream: web::Payload,
@@ -216,7 +255,76 @@ pub mod subscriptions {
         S: ScalarValue + Send + Sync + 'static,
         I: Init<S, CtxT> + Send,
     {
-        let (s_tx, s_rx) = Connection::new(ArcSchema(root_node), init).split::<Message>();
+        let trace_context =
+            TraceContext::from_headers(|name| req.headers().get(name)?.to_str().ok());
+        let conn = Connection::new(ArcSchema(root_node), init).with_trace_context(trace_context);
+        start_actor(req, stream, conn)
+    }
+
+    /// Serves the graphql-ws protocol over a WebSocket connection, gracefully shutting it down
+    /// along with the given `juniper_graphql_ws::ShutdownCoordinator`.
+    ///
+    /// Once a shutdown is initiated, new operations are rejected, and after the drain period the
+    /// active ones are completed and the WebSocket is closed. Initiating it before stopping the
+    /// server prevents deploys from dropping clients in the middle of a message:
+    ///
+    /// ```ignore
+    /// let shutdown = juniper_graphql_ws::ShutdownCoordinator::new();
+    /// let server = HttpServer::new(/* ... */).disable_signals().bind(addr)?.run();
+    /// let handle = server.handle();
+    /// actix_web::rt::spawn(async move {
+    ///     actix_web::rt::signal::ctrl_c().await.unwrap();
+    ///     shutdown.shutdown(Duration::from_secs(10)).await;
+    ///     handle.stop(true).await;
+    /// });
+    /// server.await
+    /// ```
+    ///
+    /// See `subscriptions_handler()` for the description of the `init` argument.
+    pub async fn subscriptions_handler_with_shutdown<Query, Mutation, Subscription, CtxT, S, I>(
+        req: HttpRequest,
+        stream: web::Payload,
+        root_node: Arc<RootNode<'static, Query, Mutation, Subscription, S>>,
+        init: I,
+        shutdown: &ShutdownCoordinator,
+    ) -> Result<HttpResponse, actix_web::Error>
+    where
+        Query: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+        Query::TypeInfo: Send + Sync,
+        Mutation: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+        Mutation::TypeInfo: Send + Sync,
+        Subscription: GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+        Subscription::TypeInfo: Send + Sync,
+        CtxT: Unpin + Send + Sync + 'static,
+        S: ScalarValue + Send + Sync + 'static,
+        I: Init<S, CtxT> + Send,
+    {
+        let trace_context =
+            TraceContext::from_headers(|name| req.headers().get(name)?.to_str().ok());
+        let conn = Connection::new(ArcSchema(root_node), init)
+            .with_trace_context(trace_context)
+            .with_shutdown(shutdown);
+        start_actor(req, stream, conn)
+    }
+
+    /// Starts the `SubscriptionActor` serving the given graphql-ws `Connection`.
+    fn start_actor<Query, Mutation, Subscription, CtxT, S, I>(
+        req: HttpRequest,
+        stream: web::Payload,
+        conn: Connection<ArcSchema<Query, Mutation, Subscription, CtxT, S>, I>,
+    ) -> Result<HttpResponse, actix_web::Error>
+    where
+        Query: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+        Query::TypeInfo: Send + Sync,
+        Mutation: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+        Mutation::TypeInfo: Send + Sync,
+        Subscription: GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+        Subscription::TypeInfo: Send + Sync,
+        CtxT: Unpin + Send + Sync + 'static,
+        S: ScalarValue + Send + Sync + 'static,
+        I: Init<S, CtxT> + Send,
+    {
+        let (s_tx, s_rx) = conn.split::<Message>();
 
         let mut resp = ws::start(
             SubscriptionActor {
@@ -329,6 +437,9 @@ pub mod subscriptions {
                     // sending the message to self so that it can be forwarded back to the client
                     addr.do_send(ServerMessageWrapper { message });
                 }
+                // the connection has ended (terminated by the client or shut down), so close the
+                // websocket once all the messages sent to self before have been forwarded
+                addr.do_send(CloseConnection);
             }
             .into_actor(self);
 
@@ -377,6 +488,32 @@ pub mod subscriptions {
             };
         }
     }
+    /// actor -> websocket close
+    impl<Query, Mutation, Subscription, CtxT, S, I> actix::prelude::Handler<CloseConnection>
+        for SubscriptionActor<Query, Mutation, Subscription, CtxT, S, I>
+    where
+        Query: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+        Query::TypeInfo: Send + Sync,
+        Mutation: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+        Mutation::TypeInfo: Send + Sync,
+        Subscription: GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+        Subscription::TypeInfo: Send + Sync,
+        CtxT: Unpin + Send + Sync + 'static,
+        S: ScalarValue + Send + Sync + 'static,
+        I: Init<S, CtxT> + Send,
+    {
+        type Result = ();
+
+        fn handle(&mut self, _: CloseConnection, ctx: &mut Self::Context) -> Self::Result {
+            ctx.close(Some(ws::CloseCode::Normal.into()));
+            ctx.stop();
+        }
+    }
+
+    #[derive(Message)]
+    #[rtype(result = "()")]
+    struct CloseConnection;
+
     #[derive(Message)]
     #[rtype(result = "()")]
     struct ServerMessageWrapper<S>
@@ -627,6 +764,104 @@ mod tests {
         );
     }
 
+    #[actix_web::rt::test]
+    async fn graphql_get_honors_if_none_match() {
+        let schema: Schema = RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        );
+
+        let mut app = test::init_service(
+            App::new()
+                .app_data(Data::new(schema))
+                .route("/", web::get().to(index)),
+        )
+        .await;
+        let uri = "/?query=%7B%20hero%20%7B%20name%20%7D%20%7D";
+
+        let req = TestRequest::get().uri(uri).to_request();
+        let resp = test::call_service(&mut app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        let etag = resp.headers().get("etag").unwrap().clone();
+
+        let req = TestRequest::get()
+            .append_header(("if-none-match", etag.clone()))
+            .uri(uri)
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::NOT_MODIFIED);
+        assert_eq!(resp.headers().get("etag"), Some(&etag));
+
+        let req = TestRequest::get()
+            .append_header(("if-none-match", "\"0000000000000000\""))
+            .uri(uri)
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        assert_eq!(
+            take_response_body_string(resp).await,
+            r#"{"data":{"hero":{"name":"R2-D2"}}}"#
+        );
+    }
+
+    #[actix_web::rt::test]
+    async fn graphql_get_doesnt_tag_responses_with_errors() {
+        let schema: Schema = RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        );
+
+        let mut app = test::init_service(
+            App::new()
+                .app_data(Data::new(schema))
+                .route("/", web::get().to(index)),
+        )
+        .await;
+
+        let req = TestRequest::get()
+            .append_header(("grpc-timeout", "0n"))
+            .uri("/?query=%7B%20hero%20%7B%20name%20%7D%20%7D")
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        assert!(resp.headers().get("etag").is_none());
+    }
+
+    #[actix_web::rt::test]
+    async fn graphql_propagates_deadline_from_headers() {
+        let schema: Schema = RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        );
+
+        let mut app = test::init_service(
+            App::new()
+                .app_data(Data::new(schema))
+                .route("/", web::get().to(index))
+                .route("/", web::post().to(index)),
+        )
+        .await;
+
+        let req = TestRequest::get()
+            .append_header(("grpc-timeout", "0n"))
+            .uri("/?query=%7B%20hero%20%7B%20name%20%7D%20%7D")
+            .to_request();
+        let body = take_response_body_string(test::call_service(&mut app, req).await).await;
+        assert!(body.contains("Operation timed out"), "body: {}", body);
+
+        let req = TestRequest::post()
+            .append_header(("content-type", "application/json"))
+            .append_header(("grpc-timeout", "0n"))
+            .set_payload(r#"{ "query": "{ hero { name } }" }"#)
+            .uri("/")
+            .to_request();
+        let body = take_response_body_string(test::call_service(&mut app, req).await).await;
+        assert!(body.contains("Operation timed out"), "body: {}", body);
+    }
+
     #[actix_web::rt::test]
     async fn batch_request_works() {
         use juniper::{
@@ -21,9 +21,12 @@ use crate::{
             ParseBufferExt as _, TypeExt as _,
         },
         scalar,
+        validate::Constraints,
     },
     result::GraphQLScope,
-    util::{filter_attrs, path_eq_single, span_container::SpanContainer, RenameRule},
+    util::{
+        filter_attrs, get_doc_comment, path_eq_single, span_container::SpanContainer, RenameRule,
+    },
 };
 
 /// Available metadata (arguments) behind `#[graphql]` attribute placed on a
@@ -42,10 +45,21 @@ pub(crate) struct Attr {
 
     /// Explicitly specified [description][2] of this [GraphQL argument][1].
     ///
+    /// If [`None`], then Rust doc comment is used as [description][2], if any.
+    ///
     /// [1]: https://spec.graphql.org/June2018/#sec-Language.Arguments
     /// [2]: https://spec.graphql.org/June2018/#sec-Descriptions
     pub(crate) description: Option<SpanContainer<syn::LitStr>>,
 
+    /// Explicitly specified [deprecation][2] of this [GraphQL argument][1].
+    ///
+    /// If inner [`Option`] is [`None`], then deprecation has no message
+    /// attached.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Arguments
+    /// [2]: https://spec.graphql.org/June2018/#sec-Deprecation
+    pub(crate) deprecated: Option<SpanContainer<Option<syn::LitStr>>>,
+
     /// Explicitly specified [default value][2] of this [GraphQL argument][1].
     ///
     /// If the exact default expression is not specified, then the [`Default`]
@@ -58,6 +72,21 @@ pub(crate) struct Attr {
     /// [2]: https://spec.graphql.org/June2018/#sec-Required-Arguments
     pub(crate) default: Option<SpanContainer<Option<syn::Expr>>>,
 
+    /// Explicitly specified function computing a default value of this
+    /// [GraphQL argument][1] out of the [`Context`], whenever the argument is
+    /// omitted or `null`.
+    ///
+    /// [`Context`]: juniper::Context
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Arguments
+    pub(crate) default_with_context: Option<SpanContainer<syn::ExprPath>>,
+
+    /// Explicitly specified [`Constraints`] to check a value of this
+    /// [GraphQL argument][1] against, before passing it into a resolving
+    /// method.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Arguments
+    pub(crate) validate: Option<SpanContainer<Constraints>>,
+
     /// Explicitly specified marker indicating that this method argument doesn't
     /// represent a [GraphQL argument][1], but is a [`Context`] being injected
     /// into a [GraphQL field][2] resolving function.
@@ -81,6 +110,16 @@ pub(crate) struct Attr {
     /// [1]: https://spec.graphql.org/June2018/#sec-Language.Arguments
     /// [2]: https://spec.graphql.org/June2018/#sec-Language.Fields
     pub(crate) executor: Option<SpanContainer<syn::Ident>>,
+
+    /// Explicitly specified marker indicating that this method argument doesn't
+    /// represent a single [GraphQL argument][1], but is a [GraphQL input
+    /// object][2] whose fields are [GraphQL arguments][1] of a
+    /// [GraphQL field][3].
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Arguments
+    /// [2]: https://spec.graphql.org/June2018/#sec-Input-Objects
+    /// [3]: https://spec.graphql.org/June2018/#sec-Language.Fields
+    pub(crate) args: Option<SpanContainer<syn::Ident>>,
 }
 
 impl Parse for Attr {
@@ -103,6 +142,20 @@ impl Parse for Attr {
                         .replace(SpanContainer::new(ident.span(), Some(desc.span()), desc))
                         .none_or_else(|_| err::dup_arg(&ident))?
                 }
+                "deprecated" => {
+                    let mut reason = None;
+                    if input.is_next::<token::Eq>() {
+                        input.parse::<token::Eq>()?;
+                        reason = Some(input.parse::<syn::LitStr>()?);
+                    }
+                    out.deprecated
+                        .replace(SpanContainer::new(
+                            ident.span(),
+                            reason.as_ref().map(|r| r.span()),
+                            reason,
+                        ))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
                 "default" => {
                     let mut expr = None;
                     if input.is_next::<token::Eq>() {
@@ -121,6 +174,22 @@ impl Parse for Attr {
                         ))
                         .none_or_else(|_| err::dup_arg(&ident))?
                 }
+                "default_with_context" => {
+                    input.parse::<token::Eq>()?;
+                    let lit = input.parse::<syn::LitStr>()?;
+                    let path = lit.parse::<syn::ExprPath>()?;
+                    out.default_with_context
+                        .replace(SpanContainer::new(ident.span(), Some(lit.span()), path))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
+                "validate" => {
+                    let inner;
+                    let _ = syn::parenthesized!(inner in input);
+                    let constraints = inner.parse::<Constraints>()?;
+                    out.validate
+                        .replace(SpanContainer::new(ident.span(), None, constraints))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
                 "ctx" | "context" | "Context" => {
                     let span = ident.span();
                     out.context
@@ -133,6 +202,12 @@ impl Parse for Attr {
                         .replace(SpanContainer::new(span, Some(span), ident))
                         .none_or_else(|_| err::dup_arg(span))?
                 }
+                "args" => {
+                    let span = ident.span();
+                    out.args
+                        .replace(SpanContainer::new(span, Some(span), ident))
+                        .none_or_else(|_| err::dup_arg(span))?
+                }
                 name => {
                     return Err(err::unknown_arg(&ident, name));
                 }
@@ -150,9 +225,13 @@ impl Attr {
         Ok(Self {
             name: try_merge_opt!(name: self, another),
             description: try_merge_opt!(description: self, another),
+            deprecated: try_merge_opt!(deprecated: self, another),
             default: try_merge_opt!(default: self, another),
+            default_with_context: try_merge_opt!(default_with_context: self, another),
+            validate: try_merge_opt!(validate: self, another),
             context: try_merge_opt!(context: self, another),
             executor: try_merge_opt!(executor: self, another),
+            args: try_merge_opt!(args: self, another),
         })
     }
 
@@ -166,8 +245,12 @@ impl Attr {
         if let Some(context) = &attr.context {
             if attr.name.is_some()
                 || attr.description.is_some()
+                || attr.deprecated.is_some()
                 || attr.default.is_some()
+                || attr.default_with_context.is_some()
+                || attr.validate.is_some()
                 || attr.executor.is_some()
+                || attr.args.is_some()
             {
                 return Err(syn::Error::new(
                     context.span(),
@@ -179,8 +262,12 @@ impl Attr {
         if let Some(executor) = &attr.executor {
             if attr.name.is_some()
                 || attr.description.is_some()
+                || attr.deprecated.is_some()
                 || attr.default.is_some()
+                || attr.default_with_context.is_some()
+                || attr.validate.is_some()
                 || attr.context.is_some()
+                || attr.args.is_some()
             {
                 return Err(syn::Error::new(
                     executor.span(),
@@ -189,6 +276,31 @@ impl Attr {
             }
         }
 
+        if let Some(args) = &attr.args {
+            if attr.name.is_some()
+                || attr.description.is_some()
+                || attr.deprecated.is_some()
+                || attr.default.is_some()
+                || attr.default_with_context.is_some()
+                || attr.validate.is_some()
+                || attr.context.is_some()
+                || attr.executor.is_some()
+            {
+                return Err(syn::Error::new(
+                    args.span(),
+                    "`args` attribute argument is not composable with any other arguments",
+                ));
+            }
+        }
+
+        if let (Some(default), Some(_)) = (&attr.default, &attr.default_with_context) {
+            return Err(syn::Error::new(
+                default.span_ident(),
+                "`default` attribute argument is not composable with \
+                 `default_with_context` attribute argument",
+            ));
+        }
+
         Ok(attr)
     }
 
@@ -201,9 +313,18 @@ impl Attr {
         if let Some(span) = &self.description {
             return Err(Self::err_disallowed(&span, "description"));
         }
+        if let Some(span) = &self.deprecated {
+            return Err(Self::err_disallowed(&span.span_ident(), "deprecated"));
+        }
         if let Some(span) = &self.default {
             return Err(Self::err_disallowed(&span, "default"));
         }
+        if let Some(span) = &self.default_with_context {
+            return Err(Self::err_disallowed(&span, "default_with_context"));
+        }
+        if let Some(span) = &self.validate {
+            return Err(Self::err_disallowed(&span.span_ident(), "validate"));
+        }
         Ok(())
     }
 
@@ -243,6 +364,16 @@ pub(crate) struct OnField {
     /// [2]: https://spec.graphql.org/June2018/#sec-Descriptions
     pub(crate) description: Option<String>,
 
+    /// [Deprecation][2] of this [GraphQL field argument][1] to put into
+    /// GraphQL schema.
+    ///
+    /// If inner [`Option`] is [`None`], then deprecation has no message
+    /// attached.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Arguments
+    /// [2]: https://spec.graphql.org/June2018/#sec-Deprecation
+    pub(crate) deprecated: Option<Option<String>>,
+
     /// Default value of this [GraphQL field argument][1] in GraphQL schema.
     ///
     /// If outer [`Option`] is [`None`], then this [argument][1] is a
@@ -253,6 +384,22 @@ pub(crate) struct OnField {
     /// [1]: https://spec.graphql.org/June2018/#sec-Language.Arguments
     /// [2]: https://spec.graphql.org/June2018/#sec-Required-Arguments
     pub(crate) default: Option<Option<syn::Expr>>,
+
+    /// Function computing a default value of this [GraphQL field argument][1]
+    /// out of the [`Context`], in case it's omitted or `null`.
+    ///
+    /// If [`Some`], then this [argument][1] is registered as a nullable one
+    /// without a default value in GraphQL schema.
+    ///
+    /// [`Context`]: juniper::Context
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Arguments
+    pub(crate) default_with_context: Option<syn::ExprPath>,
+
+    /// [`Constraints`] to check a value of this [GraphQL field argument][1]
+    /// against.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Arguments
+    pub(crate) validate: Option<Constraints>,
 }
 
 /// Possible kinds of Rust method arguments for code generation.
@@ -274,6 +421,14 @@ pub(crate) enum OnMethod {
     /// [`Executor`]: juniper::Executor
     /// [2]: https://spec.graphql.org/June2018/#sec-Language.Fields
     Executor,
+
+    /// [GraphQL input object][1] whose fields are the [GraphQL arguments][2]
+    /// of a [GraphQL field][3].
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Input-Objects
+    /// [2]: https://spec.graphql.org/June2018/#sec-Language.Arguments
+    /// [3]: https://spec.graphql.org/June2018/#sec-Language.Fields
+    Args(Box<syn::Type>),
 }
 
 impl OnMethod {
@@ -300,12 +455,16 @@ impl OnMethod {
 
     /// Returns generated code for the [`marker::IsOutputType::mark`] method,
     /// which performs static checks for this argument, if it represents an
-    /// [`OnField`] one.
+    /// [`OnField`] or an [`OnMethod::Args`] one.
     ///
     /// [`marker::IsOutputType::mark`]: juniper::marker::IsOutputType::mark
     #[must_use]
     pub(crate) fn method_mark_tokens(&self, scalar: &scalar::Type) -> Option<TokenStream> {
-        let ty = &self.as_regular()?.ty;
+        let ty = match self {
+            Self::Regular(arg) => &arg.ty,
+            Self::Args(ty) => ty,
+            Self::Context(_) | Self::Executor => return None,
+        };
         Some(quote_spanned! { ty.span() =>
             <#ty as ::juniper::marker::IsInputType<#scalar>>::mark();
         })
@@ -313,12 +472,16 @@ impl OnMethod {
 
     /// Returns generated code for the [`GraphQLType::meta`] method, which
     /// registers this argument in [`Registry`], if it represents an [`OnField`]
-    /// argument.
+    /// or an [`OnMethod::Args`] argument.
     ///
     /// [`GraphQLType::meta`]: juniper::GraphQLType::meta
     /// [`Registry`]: juniper::Registry
     #[must_use]
     pub(crate) fn method_meta_tokens(&self) -> Option<TokenStream> {
+        if let Self::Args(ty) = self {
+            return Some(quote! { .arguments(registry.args::<#ty>(info)) });
+        }
+
         let arg = self.as_regular()?;
 
         let (name, ty) = (&arg.name, &arg.ty);
@@ -328,6 +491,14 @@ impl OnMethod {
             .as_ref()
             .map(|desc| quote! { .description(#desc) });
 
+        let deprecated = arg.deprecated.as_ref().map(|reason| {
+            let reason = reason
+                .as_ref()
+                .map(|rsn| quote! { Some(#rsn) })
+                .unwrap_or_else(|| quote! { None });
+            quote! { .deprecated(#reason) }
+        });
+
         let method = if let Some(val) = &arg.default {
             let val = val
                 .as_ref()
@@ -336,11 +507,13 @@ impl OnMethod {
             quote_spanned! { val.span() =>
                 .arg_with_default::<#ty>(#name, &#val, info)
             }
+        } else if arg.default_with_context.is_some() {
+            quote! { .arg::<::std::option::Option<#ty>>(#name, info) }
         } else {
             quote! { .arg::<#ty>(#name, info) }
         };
 
-        Some(quote! { .argument(registry#method#description) })
+        Some(quote! { .argument(registry#method#description#deprecated) })
     }
 
     /// Returns generated code for the [`GraphQLValue::resolve_field`] method,
@@ -359,15 +532,46 @@ impl OnMethod {
                 let (name, ty) = (&arg.name, &arg.ty);
                 let err_text = format!("Missing argument `{}`: {{}}", &name);
 
-                let arg = quote! {
-                    args.get::<#ty>(#name).and_then(|opt| opt.map_or_else(|| {
-                        <#ty as ::juniper::FromInputValue<#scalar>>::from_implicit_null()
-                            .map_err(|e| {
-                                ::juniper::IntoFieldError::<#scalar>::into_field_error(e)
-                                    .map_message(|m| format!(#err_text, m))
-                            })
-                    }, Ok))
+                let mut val = if let Some(func) = &arg.default_with_context {
+                    quote! {
+                        args.get::<::std::option::Option<#ty>>(#name).map(|opt| {
+                            opt.flatten().unwrap_or_else(|| #func(executor.context()))
+                        })
+                    }
+                } else {
+                    quote! {
+                        args.get::<#ty>(#name).and_then(|opt| opt.map_or_else(|| {
+                            <#ty as ::juniper::FromInputValue<#scalar>>::from_implicit_null()
+                                .map_err(|e| {
+                                    ::juniper::IntoFieldError::<#scalar>::into_field_error(e)
+                                        .map_message(|m| format!(#err_text, m))
+                                })
+                        }, Ok))
+                    }
                 };
+                if let Some(constraints) = &arg.validate {
+                    let checks = constraints.checks_tokens(&quote! { &v }, name, scalar);
+                    val = quote! {
+                        #val.and_then(|v| {
+                            #checks
+                            Ok(v)
+                        })
+                    };
+                }
+                if for_async {
+                    quote! {
+                        match #val {
+                            Ok(v) => v,
+                            Err(e) => return Box::pin(async { Err(e) }),
+                        }
+                    }
+                } else {
+                    quote! { #val? }
+                }
+            }
+
+            Self::Args(ty) => {
+                let arg = quote! { args.get_all::<#ty>() };
                 if for_async {
                     quote! {
                         match #arg {
@@ -401,9 +605,11 @@ impl OnMethod {
         let orig_attrs = argument.attrs.clone();
 
         // Remove repeated attributes from the method, to omit incorrect expansion.
+        // Doc comments are removed too, as Rust doesn't allow them on function
+        // parameters.
         argument.attrs = mem::take(&mut argument.attrs)
             .into_iter()
-            .filter(|attr| !path_eq_single(&attr.path, "graphql"))
+            .filter(|attr| !path_eq_single(&attr.path, "graphql") && !attr.path.is_ident("doc"))
             .collect();
 
         let attr = Attr::from_attrs("graphql", &orig_attrs)
@@ -416,6 +622,9 @@ impl OnMethod {
         if attr.executor.is_some() {
             return Some(Self::Executor);
         }
+        if attr.args.is_some() {
+            return Some(Self::Args(Box::new(argument.ty.as_ref().clone())));
+        }
         if let syn::Pat::Ident(name) = &*argument.pat {
             let arg = match name.ident.unraw().to_string().as_str() {
                 "context" | "ctx" | "_context" | "_ctx" => {
@@ -462,8 +671,21 @@ impl OnMethod {
         Some(Self::Regular(Box::new(OnField {
             name,
             ty: argument.ty.as_ref().clone(),
-            description: attr.description.as_ref().map(|d| d.as_ref().value()),
+            description: attr
+                .description
+                .as_ref()
+                .map(|d| d.as_ref().value())
+                .or_else(|| get_doc_comment(&orig_attrs).map(SpanContainer::into_inner)),
+            deprecated: attr
+                .deprecated
+                .as_ref()
+                .map(|d| d.as_ref().as_ref().map(syn::LitStr::value)),
             default: attr.default.as_ref().map(|v| v.as_ref().clone()),
+            default_with_context: attr
+                .default_with_context
+                .as_ref()
+                .map(|f| f.as_ref().clone()),
+            validate: attr.validate.map(SpanContainer::into_inner),
         })))
     }
 }
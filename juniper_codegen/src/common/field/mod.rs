@@ -5,9 +5,12 @@
 
 pub(crate) mod arg;
 
-use proc_macro2::TokenStream;
+use std::{iter, time::Duration};
+
+use proc_macro2::{Span, TokenStream};
 use quote::{quote, quote_spanned};
 use syn::{
+    ext::IdentExt as _,
     parse::{Parse, ParseStream},
     parse_quote,
     spanned::Spanned as _,
@@ -22,7 +25,10 @@ use crate::{
         },
         scalar,
     },
-    util::{filter_attrs, get_deprecated, get_doc_comment, span_container::SpanContainer},
+    result::GraphQLScope,
+    util::{
+        filter_attrs, get_deprecated, get_doc_comment, span_container::SpanContainer, RenameRule,
+    },
 };
 
 pub(crate) use self::arg::OnMethod as MethodArgument;
@@ -64,6 +70,58 @@ pub(crate) struct Attr {
     ///
     /// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
     pub(crate) ignore: Option<SpanContainer<syn::Ident>>,
+
+    /// Explicitly specified path of a predicate function, which makes this
+    /// [GraphQL field][1] being resolved as `null` whenever returns `true`.
+    ///
+    /// The predicate accepts the object value and its context:
+    /// `fn(&Self, &Context) -> bool`.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
+    pub(crate) skip_if: Option<SpanContainer<syn::ExprPath>>,
+
+    /// Explicitly specified marker indicating that this [GraphQL field][1]
+    /// contains personally identifiable information (PII).
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
+    pub(crate) pii: Option<SpanContainer<syn::Ident>>,
+
+    /// Explicitly specified cost of resolving this [GraphQL field][1], used by
+    /// the query cost analysis.
+    ///
+    /// Either an expression of the fixed cost, added to the cost of the
+    /// selection set, or a closure computing the total cost from the field
+    /// arguments and the cost of the selection set:
+    /// `|&Arguments<S>, usize| -> usize`.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
+    pub(crate) complexity: Option<SpanContainer<syn::Expr>>,
+
+    /// Explicitly specified [`Duration`] after which the asynchronous
+    /// resolution of this [GraphQL field][1] is aborted with a `TIMEOUT`
+    /// error.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
+    pub(crate) timeout: Option<SpanContainer<Duration>>,
+
+    /// Explicitly specified [`Retry`] policy of the asynchronous resolution of
+    /// this [GraphQL field][1].
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
+    pub(crate) retry: Option<SpanContainer<Retry>>,
+
+    /// Explicitly specified additional names of this [GraphQL field][1],
+    /// resolved the same way.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
+    pub(crate) aliases: Vec<SpanContainer<Alias>>,
+
+    /// Explicitly specified replacement of this [GraphQL field][1], making it
+    /// [deprecated][2] with the reason pointing to the replacement.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
+    /// [2]: https://spec.graphql.org/June2018/#sec-Deprecation
+    pub(crate) replaced_by: Option<SpanContainer<Replacement>>,
 }
 
 impl Parse for Attr {
@@ -104,6 +162,72 @@ impl Parse for Attr {
                     .ignore
                     .replace(SpanContainer::new(ident.span(), None, ident.clone()))
                     .none_or_else(|_| err::dup_arg(&ident))?,
+                "skip_if" => {
+                    input.parse::<token::Eq>()?;
+                    let path = if input.peek(syn::LitStr) {
+                        input.parse::<syn::LitStr>()?.parse::<syn::ExprPath>()?
+                    } else {
+                        input.parse::<syn::ExprPath>()?
+                    };
+                    out.skip_if
+                        .replace(SpanContainer::new(ident.span(), Some(path.span()), path))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
+                "pii" => out
+                    .pii
+                    .replace(SpanContainer::new(ident.span(), None, ident.clone()))
+                    .none_or_else(|_| err::dup_arg(&ident))?,
+                "complexity" => {
+                    input.parse::<token::Eq>()?;
+                    let expr = input.parse::<syn::Expr>()?;
+                    out.complexity
+                        .replace(SpanContainer::new(ident.span(), Some(expr.span()), expr))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
+                "timeout" => {
+                    input.parse::<token::Eq>()?;
+                    let lit = input.parse::<syn::LitStr>()?;
+                    let timeout = parse_duration(&lit)?;
+                    out.timeout
+                        .replace(SpanContainer::new(ident.span(), Some(lit.span()), timeout))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
+                "retry" => {
+                    let inner;
+                    let paren = syn::parenthesized!(inner in input);
+                    let retry = inner.parse::<Retry>()?;
+                    out.retry
+                        .replace(SpanContainer::new(ident.span(), Some(paren.span), retry))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
+                "alias" => {
+                    let (span, alias) = if input.is_next::<token::Eq>() {
+                        input.parse::<token::Eq>()?;
+                        let name = input.parse::<syn::LitStr>()?;
+                        let alias = Alias {
+                            name: name.value(),
+                            hidden: false,
+                        };
+                        (name.span(), alias)
+                    } else {
+                        let inner;
+                        let paren = syn::parenthesized!(inner in input);
+                        (paren.span, inner.parse::<Alias>()?)
+                    };
+                    out.aliases
+                        .push(SpanContainer::new(ident.span(), Some(span), alias));
+                }
+                "replaced_by" => {
+                    input.parse::<token::Eq>()?;
+                    let replacement = input.parse::<Replacement>()?;
+                    out.replaced_by
+                        .replace(SpanContainer::new(
+                            ident.span(),
+                            Some(replacement.span()),
+                            replacement,
+                        ))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
                 name => {
                     return Err(err::unknown_arg(&ident, name));
                 }
@@ -123,6 +247,13 @@ impl Attr {
             description: try_merge_opt!(description: self, another),
             deprecated: try_merge_opt!(deprecated: self, another),
             ignore: try_merge_opt!(ignore: self, another),
+            skip_if: try_merge_opt!(skip_if: self, another),
+            pii: try_merge_opt!(pii: self, another),
+            complexity: try_merge_opt!(complexity: self, another),
+            timeout: try_merge_opt!(timeout: self, another),
+            retry: try_merge_opt!(retry: self, another),
+            aliases: self.aliases.into_iter().chain(another.aliases).collect(),
+            replaced_by: try_merge_opt!(replaced_by: self, another),
         })
     }
 
@@ -136,7 +267,17 @@ impl Attr {
             .try_fold(Self::default(), |prev, curr| prev.try_merge(curr?))?;
 
         if let Some(ignore) = &attr.ignore {
-            if attr.name.is_some() || attr.description.is_some() || attr.deprecated.is_some() {
+            if attr.name.is_some()
+                || attr.description.is_some()
+                || attr.deprecated.is_some()
+                || attr.skip_if.is_some()
+                || attr.pii.is_some()
+                || attr.complexity.is_some()
+                || attr.timeout.is_some()
+                || attr.retry.is_some()
+                || !attr.aliases.is_empty()
+                || attr.replaced_by.is_some()
+            {
                 return Err(syn::Error::new(
                     ignore.span(),
                     "`ignore` attribute argument is not composable with any other arguments",
@@ -144,6 +285,15 @@ impl Attr {
             }
         }
 
+        if let Some(replaced_by) = &attr.replaced_by {
+            if attr.deprecated.is_some() {
+                return Err(syn::Error::new(
+                    replaced_by.span_ident(),
+                    "`replaced_by` attribute argument is not composable with `deprecated` one",
+                ));
+            }
+        }
+
         if attr.description.is_none() {
             attr.description = get_doc_comment(attrs).map(|sc| {
                 let span = sc.span_ident();
@@ -160,6 +310,157 @@ impl Attr {
 
         Ok(attr)
     }
+
+    /// Returns the [deprecation][2] of this [GraphQL field][1], either the
+    /// explicitly specified one, or pointing to its `replaced_by` replacement,
+    /// named according to the given [`RenameRule`].
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
+    /// [2]: https://spec.graphql.org/June2018/#sec-Deprecation
+    #[must_use]
+    pub(crate) fn deprecation(&self, renaming: &RenameRule) -> Option<Option<String>> {
+        if let Some(replaced_by) = &self.replaced_by {
+            let name = match replaced_by.as_ref() {
+                Replacement::Name(name) => name.value(),
+                Replacement::Ident(ident) => renaming.apply(&ident.unraw().to_string()),
+            };
+            return Some(Some(format!("Use `{}` instead.", name)));
+        }
+        self.deprecated
+            .as_deref()
+            .map(|d| d.as_ref().map(syn::LitStr::value))
+    }
+}
+
+/// Replacement of a deprecated [GraphQL field][1], specified with a
+/// `replaced_by = ...` attribute argument.
+///
+/// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
+#[derive(Debug)]
+pub(crate) enum Replacement {
+    /// Name of the replacement [GraphQL field][1] in GraphQL schema, used as
+    /// it is.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
+    Name(syn::LitStr),
+
+    /// Ident of the Rust method (or struct field) representing the
+    /// replacement [GraphQL field][1], renamed as any other field.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
+    Ident(syn::Ident),
+}
+
+impl Replacement {
+    /// Returns the [`Span`] of this [`Replacement`].
+    fn span(&self) -> Span {
+        match self {
+            Self::Name(name) => name.span(),
+            Self::Ident(ident) => ident.span(),
+        }
+    }
+}
+
+impl Parse for Replacement {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        if input.peek(syn::LitStr) {
+            input.parse().map(Self::Name)
+        } else {
+            input.parse_any_ident().map(Self::Ident)
+        }
+    }
+}
+
+/// Policy of retrying the asynchronous resolution of a [GraphQL field][1],
+/// specified with a `retry(times = ..., backoff = "...")` attribute argument.
+///
+/// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Retry {
+    /// Number of times to retry the resolution once it fails.
+    pub(crate) times: u32,
+
+    /// [`Duration`] to wait for before each retry.
+    pub(crate) backoff: Option<Duration>,
+}
+
+impl Parse for Retry {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let span = input.span();
+        let (mut times, mut backoff) = (None, None);
+        while !input.is_empty() {
+            let ident = input.parse::<syn::Ident>()?;
+            input.parse::<token::Eq>()?;
+            let is_dup = match ident.to_string().as_str() {
+                "times" => times
+                    .replace(input.parse::<syn::LitInt>()?.base10_parse::<u32>()?)
+                    .is_some(),
+                "backoff" => backoff
+                    .replace(parse_duration(&input.parse::<syn::LitStr>()?)?)
+                    .is_some(),
+                name => return Err(err::unknown_arg(&ident, name)),
+            };
+            if is_dup {
+                return Err(err::dup_arg(&ident));
+            }
+            input.try_parse::<token::Comma>()?;
+        }
+        Ok(Self {
+            times: times.ok_or_else(|| {
+                syn::Error::new(
+                    span,
+                    "`retry` attribute argument requires `times` to be specified",
+                )
+            })?,
+            backoff,
+        })
+    }
+}
+
+/// Additional name of a [GraphQL field][1], specified with an
+/// `alias = "..."` or `alias(name = "...", hidden)` attribute argument.
+///
+/// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
+#[derive(Clone, Debug)]
+pub(crate) struct Alias {
+    /// Name of this [`Alias`] in GraphQL schema.
+    pub(crate) name: String,
+
+    /// Indicator whether this [`Alias`] is hidden from introspection.
+    pub(crate) hidden: bool,
+}
+
+impl Parse for Alias {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let span = input.span();
+        let (mut name, mut hidden) = (None, false);
+        while !input.is_empty() {
+            let ident = input.parse::<syn::Ident>()?;
+            match ident.to_string().as_str() {
+                "name" => {
+                    input.parse::<token::Eq>()?;
+                    if name
+                        .replace(input.parse::<syn::LitStr>()?.value())
+                        .is_some()
+                    {
+                        return Err(err::dup_arg(&ident));
+                    }
+                }
+                "hidden" => hidden = true,
+                arg => return Err(err::unknown_arg(&ident, arg)),
+            }
+            input.try_parse::<token::Comma>()?;
+        }
+        Ok(Self {
+            name: name.ok_or_else(|| {
+                syn::Error::new(
+                    span,
+                    "`alias` attribute argument requires `name` to be specified",
+                )
+            })?,
+            hidden,
+        })
+    }
 }
 
 /// Representation of a [GraphQL field][1] for code generation.
@@ -219,6 +520,42 @@ pub(crate) struct Definition {
     ///
     /// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
     pub(crate) is_async: bool,
+
+    /// Path of a predicate function, which makes this [GraphQL field][1] being
+    /// resolved as `null` whenever returns `true`.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
+    pub(crate) skip_if: Option<syn::ExprPath>,
+
+    /// Indicator whether this [GraphQL field][1] is tagged as containing
+    /// personally identifiable information (PII).
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
+    pub(crate) pii: bool,
+
+    /// Cost of resolving this [GraphQL field][1], either fixed or computed by
+    /// a closure, used by the query cost analysis.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
+    pub(crate) complexity: Option<syn::Expr>,
+
+    /// [`Duration`] after which the asynchronous resolution of this
+    /// [GraphQL field][1] is aborted with a `TIMEOUT` error.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
+    pub(crate) timeout: Option<Duration>,
+
+    /// [`Retry`] policy of the asynchronous resolution of this
+    /// [GraphQL field][1].
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
+    pub(crate) retry: Option<Retry>,
+
+    /// Additional names of this [GraphQL field][1], registered in GraphQL
+    /// schema as deprecated fields resolved the same way.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
+    pub(crate) aliases: Vec<Alias>,
 }
 
 impl Definition {
@@ -297,7 +634,48 @@ impl Definition {
         &self,
         extract_stream_type: Option<&scalar::Type>,
     ) -> TokenStream {
-        let (name, ty) = (&self.name, &self.ty);
+        self.meta_tokens(
+            &self.name,
+            self.deprecated.as_ref(),
+            false,
+            extract_stream_type,
+        )
+    }
+
+    /// Returns generated code for the [`GraphQLType::meta`] method, which
+    /// registers the [`Alias`]es of this [GraphQL field][1] in [`Registry`].
+    ///
+    /// [`Alias`]es are registered as deprecated in favor of this
+    /// [GraphQL field][1], unless it's deprecated itself.
+    ///
+    /// [`GraphQLType::meta`]: juniper::GraphQLType::meta
+    /// [`Registry`]: juniper::Registry
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
+    #[must_use]
+    pub(crate) fn method_aliases_meta_tokens(&self) -> Vec<TokenStream> {
+        let deprecated = self
+            .deprecated
+            .clone()
+            .unwrap_or_else(|| Some(format!("Use `{}` instead.", self.name)));
+        self.aliases
+            .iter()
+            .map(|alias| self.meta_tokens(&alias.name, Some(&deprecated), alias.hidden, None))
+            .collect()
+    }
+
+    /// Returns generated code registering this [GraphQL field][1] in
+    /// [`Registry`] under the given `name`.
+    ///
+    /// [`Registry`]: juniper::Registry
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
+    fn meta_tokens(
+        &self,
+        name: &str,
+        deprecated: Option<&Option<String>>,
+        hidden: bool,
+        extract_stream_type: Option<&scalar::Type>,
+    ) -> TokenStream {
+        let ty = &self.ty;
         let mut ty = quote! { #ty };
         if let Some(scalar) = extract_stream_type {
             ty = quote! {
@@ -310,7 +688,7 @@ impl Definition {
             .as_ref()
             .map(|desc| quote! { .description(#desc) });
 
-        let deprecated = self.deprecated.as_ref().map(|reason| {
+        let deprecated = deprecated.map(|reason| {
             let reason = reason
                 .as_ref()
                 .map(|rsn| quote! { Some(#rsn) })
@@ -318,6 +696,13 @@ impl Definition {
             quote! { .deprecated(#reason) }
         });
 
+        let pii = self.pii.then(|| quote! { .pii() });
+        let hidden = hidden.then(|| quote! { .hidden() });
+        let complexity = self.complexity.as_ref().map(|expr| match expr {
+            syn::Expr::Closure(_) => quote! { .complexity_fn(#expr) },
+            _ => quote! { .complexity(#expr) },
+        });
+
         let args = self
             .arguments
             .iter()
@@ -328,6 +713,9 @@ impl Definition {
                 #( #args )*
                 #description
                 #deprecated
+                #pii
+                #hidden
+                #complexity
         }
     }
 
@@ -399,12 +787,103 @@ impl Definition {
     }
 }
 
+/// Emits "`skip_if` is not supported" error pointing to the given `skip_if`
+/// attribute argument of a [GraphQL field][1] in the given `scope`.
+///
+/// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
+#[must_use]
+pub(crate) fn err_unsupported_skip_if<T>(
+    scope: &GraphQLScope,
+    skip_if: &SpanContainer<syn::ExprPath>,
+) -> Option<T> {
+    scope
+        .custom(
+            skip_if.span_ident(),
+            "fields don't support `skip_if` attribute argument",
+        )
+        .note("`skip_if` is supported on query and mutation fields of GraphQL objects only".into())
+        .emit();
+    None
+}
+
+/// Emits "`arg` is not supported" error pointing to the given `span` of an
+/// attribute argument of a [GraphQL field][1] in the given `scope`, which is
+/// supported on `async` query and mutation fields of GraphQL objects only.
+///
+/// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
+#[must_use]
+pub(crate) fn err_unsupported_async_only<T>(
+    scope: &GraphQLScope,
+    span: Span,
+    arg: &str,
+) -> Option<T> {
+    scope
+        .custom(
+            span,
+            format!("fields don't support `{}` attribute argument", arg),
+        )
+        .note(format!(
+            "`{}` is supported on `async` query and mutation fields of GraphQL objects only",
+            arg,
+        ))
+        .emit();
+    None
+}
+
+/// Parses a [`Duration`] from the given string literal, like `"500ms"` or
+/// `"2s"`.
+fn parse_duration(lit: &syn::LitStr) -> syn::Result<Duration> {
+    let parse = |s: &str| {
+        let unit_at = s.find(|c: char| !c.is_ascii_digit())?;
+        let (value, unit) = s.split_at(unit_at);
+        let value = value.parse::<u64>().ok()?;
+        Some(match unit {
+            "ms" => Duration::from_millis(value),
+            "s" => Duration::from_secs(value),
+            "m" => Duration::from_secs(value.checked_mul(60)?),
+            "h" => Duration::from_secs(value.checked_mul(60 * 60)?),
+            _ => return None,
+        })
+    };
+    parse(&lit.value()).ok_or_else(|| {
+        syn::Error::new(
+            lit.span(),
+            "invalid duration, expected an integer followed by one of `ms`, `s`, `m` or `h` \
+             units, like `2s`",
+        )
+    })
+}
+
 /// Checks whether all [GraphQL fields][1] fields have different names.
 ///
 /// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
 #[must_use]
 pub(crate) fn all_different(fields: &[Definition]) -> bool {
-    let mut names: Vec<_> = fields.iter().map(|f| &f.name).collect();
+    let mut names: Vec<_> = fields
+        .iter()
+        .flat_map(|f| iter::once(&f.name).chain(f.aliases.iter().map(|a| &a.name)))
+        .collect();
+    let len = names.len();
+    names.sort_unstable();
     names.dedup();
-    names.len() == fields.len()
+    names.len() == len
+}
+
+/// Emits "`alias` is not supported" error pointing to the given `alias`
+/// attribute argument of a [GraphQL field][1] in the given `scope`.
+///
+/// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
+#[must_use]
+pub(crate) fn err_unsupported_alias<T>(
+    scope: &GraphQLScope,
+    alias: &SpanContainer<Alias>,
+) -> Option<T> {
+    scope
+        .custom(
+            alias.span_ident(),
+            "fields don't support `alias` attribute argument",
+        )
+        .note("`alias` is supported on query and mutation fields of GraphQL objects only".into())
+        .emit();
+    None
 }
@@ -45,3 +45,11 @@ pub(crate) fn async_resolving_code(ty: Option<&syn::Type>) -> TokenStream {
         }))
     }
 }
+
+/// Generates code constructing the given [`Duration`].
+///
+/// [`Duration`]: std::time::Duration
+pub(crate) fn duration(d: std::time::Duration) -> TokenStream {
+    let (secs, nanos) = (d.as_secs(), d.subsec_nanos());
+    quote! { ::std::time::Duration::new(#secs, #nanos) }
+}
@@ -0,0 +1,160 @@
+//! Common functions, definitions and extensions for parsing and code generation
+//! of `#[graphql(validate(...))]` attribute argument, placed on
+//! [GraphQL arguments][1] and [GraphQL input object][2] fields.
+//!
+//! [1]: https://spec.graphql.org/June2018/#sec-Language.Arguments
+//! [2]: https://spec.graphql.org/June2018/#sec-Input-Objects
+
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{
+    parse::{Parse, ParseStream},
+    token,
+};
+
+use crate::common::parse::{attr::err, ParseBufferExt as _};
+
+/// Inclusive bounds of a `range(min = ..., max = ...)` or
+/// `length(min = ..., max = ...)` constraint.
+#[derive(Debug, Default)]
+pub(crate) struct Bounds {
+    /// Lower bound, if any.
+    pub(crate) min: Option<syn::Expr>,
+
+    /// Upper bound, if any.
+    pub(crate) max: Option<syn::Expr>,
+}
+
+impl Parse for Bounds {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let mut out = Self::default();
+        while !input.is_empty() {
+            let ident = input.parse::<syn::Ident>()?;
+            input.parse::<token::Eq>()?;
+            let bound = match ident.to_string().as_str() {
+                "min" => &mut out.min,
+                "max" => &mut out.max,
+                name => return Err(err::unknown_arg(&ident, name)),
+            };
+            if bound.replace(input.parse::<syn::Expr>()?).is_some() {
+                return Err(err::dup_arg(&ident));
+            }
+            input.try_parse::<token::Comma>()?;
+        }
+        if out.min.is_none() && out.max.is_none() {
+            return Err(input.error("expected at least one of `min` or `max` bounds"));
+        }
+        Ok(out)
+    }
+}
+
+/// Constraints of `#[graphql(validate(...))]` attribute argument to check an
+/// input value against.
+#[derive(Debug, Default)]
+pub(crate) struct Constraints {
+    /// `range(min = ..., max = ...)` constraint of a numeric value.
+    pub(crate) range: Option<Bounds>,
+
+    /// `length(min = ..., max = ...)` constraint of a string or a list value.
+    pub(crate) length: Option<Bounds>,
+
+    /// `regex = "..."` constraint of a string value.
+    pub(crate) regex: Option<syn::LitStr>,
+}
+
+impl Parse for Constraints {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let mut out = Self::default();
+        while !input.is_empty() {
+            let ident = input.parse::<syn::Ident>()?;
+            let is_dup = match ident.to_string().as_str() {
+                "range" | "length" => {
+                    let inner;
+                    let _ = syn::parenthesized!(inner in input);
+                    let bounds = inner.parse::<Bounds>()?;
+                    if ident == "range" {
+                        out.range.replace(bounds).is_some()
+                    } else {
+                        out.length.replace(bounds).is_some()
+                    }
+                }
+                "regex" => {
+                    input.parse::<token::Eq>()?;
+                    let lit = input.parse::<syn::LitStr>()?;
+                    regex::Regex::new(&lit.value()).map_err(|err| {
+                        // Syntax errors are rendered over multiple lines, with
+                        // the description being the last one.
+                        let err = err.to_string();
+                        let desc = err.lines().last().unwrap_or_default();
+                        let desc = desc.trim_start_matches("error: ");
+                        syn::Error::new(lit.span(), format!("Invalid regex: {}", desc))
+                    })?;
+                    out.regex.replace(lit).is_some()
+                }
+                name => return Err(err::unknown_arg(&ident, name)),
+            };
+            if is_dup {
+                return Err(err::dup_arg(&ident));
+            }
+            input.try_parse::<token::Comma>()?;
+        }
+        Ok(out)
+    }
+}
+
+impl Constraints {
+    /// Returns generated code checking the value behind the `val` reference
+    /// against these [`Constraints`], and returning a [`FieldError`] from the
+    /// current function with `?` operator if the check fails.
+    ///
+    /// [`FieldError`]: juniper::FieldError
+    #[must_use]
+    pub(crate) fn checks_tokens(
+        &self,
+        val: &TokenStream,
+        name: &str,
+        scalar: &impl ToTokens,
+    ) -> TokenStream {
+        let helper = quote! { ::juniper::macros::helper::validate };
+        let bound = |b: &Option<syn::Expr>, ty: TokenStream| {
+            b.as_ref()
+                .map(|b| quote! { Some((#b) as #ty) })
+                .unwrap_or_else(|| quote! { None })
+        };
+        let err = |constraint: &str| {
+            quote! {
+                |m| #helper::err_invalid::<#scalar>(#name, #constraint, m)
+            }
+        };
+
+        let range = self.range.as_ref().map(|r| {
+            let (min, max) = (bound(&r.min, quote! { f64 }), bound(&r.max, quote! { f64 }));
+            let err = err("range");
+            quote! {
+                #helper::ValidateRange::validate_range(#val, #min, #max).map_err(#err)?;
+            }
+        });
+        let length = self.length.as_ref().map(|l| {
+            let (min, max) = (
+                bound(&l.min, quote! { usize }),
+                bound(&l.max, quote! { usize }),
+            );
+            let err = err("length");
+            quote! {
+                #helper::ValidateLength::validate_length(#val, #min, #max).map_err(#err)?;
+            }
+        });
+        let regex = self.regex.as_ref().map(|re| {
+            let err = err("regex");
+            quote! {
+                #helper::ValidateRegex::validate_regex(#val, #re).map_err(#err)?;
+            }
+        });
+
+        quote! {
+            #range
+            #length
+            #regex
+        }
+    }
+}
@@ -28,6 +28,7 @@ pub fn impl_enum(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Result<Toke
         .map(SpanContainer::into_inner)
         .unwrap_or_else(|| ident.unraw().to_string());
 
+    let mut db_names = Vec::new();
     let fields = variants
         .into_iter()
         .filter_map(|field| {
@@ -57,6 +58,19 @@ pub fn impl_enum(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Result<Toke
 
             let resolver_code = quote!( #ident::#field_name );
 
+            db_names.push(DbName {
+                variant: resolver_code.clone(),
+                is_explicit: field_attrs.db_rename.is_some(),
+                span: field_attrs
+                    .db_rename
+                    .as_ref()
+                    .map_or_else(|| field_name.span(), SpanContainer::span_ident),
+                name: field_attrs
+                    .db_rename
+                    .clone()
+                    .map_or_else(|| name.clone(), SpanContainer::into_inner),
+            });
+
             let _type = match field.fields {
                 Fields::Unit => syn::parse_str(&field_name.to_string()).unwrap(),
                 _ => {
@@ -88,6 +102,13 @@ pub fn impl_enum(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Result<Toke
                 );
             }
 
+            if let Some(validate) = field_attrs.validate {
+                error.unsupported_attribute_within(
+                    validate.span_ident(),
+                    UnsupportedAttribute::Validate,
+                );
+            }
+
             Some(util::GraphQLTypeDefinitionField {
                 name,
                 _type,
@@ -98,6 +119,7 @@ pub fn impl_enum(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Result<Toke
                 is_type_inferred: true,
                 is_async: false,
                 default: None,
+                validate: None,
                 span,
             })
         })
@@ -124,6 +146,14 @@ pub fn impl_enum(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Result<Toke
         error.unsupported_attribute(scalar.span_ident(), UnsupportedAttribute::Scalar);
     }
 
+    if let Some(deny) = attrs.deny_unknown_fields {
+        error.unsupported_attribute(deny.span_ident(), UnsupportedAttribute::DenyUnknownFields);
+    }
+
+    if let Some(depth) = attrs.max_depth {
+        error.unsupported_attribute(depth.span_ident(), UnsupportedAttribute::MaxDepth);
+    }
+
     if !attrs.is_internal && name.starts_with("__") {
         error.no_double_underscore(if let Some(name) = attrs.name {
             name.span_ident()
@@ -147,7 +177,87 @@ pub fn impl_enum(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Result<Toke
         include_type_generics: true,
         generic_scalar: true,
         no_async: attrs.no_async.is_some(),
+        deny_unknown_fields: false,
+        max_depth: None,
     };
 
-    Ok(definition.into_enum_tokens())
+    let db_mapping = if db_names.iter().any(|n| n.is_explicit) {
+        let mut seen = std::collections::HashSet::new();
+        for n in &db_names {
+            if !seen.insert(&n.name) {
+                error.emit_custom(
+                    n.span,
+                    format!(
+                        "variants must have unique `db_rename` names, `{}` is duplicated",
+                        n.name
+                    ),
+                );
+            }
+        }
+        proc_macro_error::abort_if_dirty();
+
+        Some(impl_db_mapping(ident, &db_names))
+    } else {
+        None
+    };
+
+    let mut out = definition.into_enum_tokens();
+    out.extend(db_mapping);
+    Ok(out)
+}
+
+/// Name of an enum variant in external storage, specified via
+/// `#[graphql(db_rename = "...")]` attribute argument, or defaulted to its
+/// GraphQL name.
+struct DbName {
+    /// Path to the enum variant.
+    variant: TokenStream,
+
+    /// Name of the variant in external storage.
+    name: String,
+
+    /// Indicator whether the [`DbName::name`] was specified explicitly.
+    is_explicit: bool,
+
+    /// [`Span`] to report errors about this [`DbName`] with.
+    ///
+    /// [`Span`]: proc_macro2::Span
+    span: proc_macro2::Span,
+}
+
+/// Generates [`FromStr`] and [`AsRef`]`<str>` implementations mapping the enum
+/// variants from/to their external storage names.
+///
+/// [`FromStr`]: std::str::FromStr
+fn impl_db_mapping(ident: &syn::Ident, db_names: &[DbName]) -> TokenStream {
+    let from_str_arms = db_names.iter().map(|DbName { variant, name, .. }| {
+        quote! { #name => ::std::result::Result::Ok(#variant), }
+    });
+    let as_ref_arms = db_names.iter().map(|DbName { variant, name, .. }| {
+        quote! { #variant => #name, }
+    });
+    let err_msg = format!("Unknown `{}` value: {{}}", ident);
+
+    quote! {
+        #[automatically_derived]
+        impl ::std::str::FromStr for #ident {
+            type Err = ::std::string::String;
+
+            fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+                match s {
+                    #( #from_str_arms )*
+                    _ => ::std::result::Result::Err(::std::format!(#err_msg, s)),
+                }
+            }
+        }
+
+        #[automatically_derived]
+        impl ::std::convert::AsRef<str> for #ident {
+            fn as_ref(&self) -> &str {
+                match self {
+                    #( #as_ref_arms )*
+                }
+            }
+        }
+    }
 }
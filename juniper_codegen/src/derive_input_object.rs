@@ -60,11 +60,9 @@ pub fn impl_input_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Res
                 error.unsupported_attribute_within(span.span(), UnsupportedAttribute::Skip)
             }
 
-            if let Some(span) = field_attrs.deprecation {
-                error.unsupported_attribute_within(
-                    span.span_ident(),
-                    UnsupportedAttribute::Deprecation,
-                )
+            if let Some(span) = field_attrs.db_rename {
+                error
+                    .unsupported_attribute_within(span.span_ident(), UnsupportedAttribute::DbRename)
             }
 
             if name.starts_with("__") {
@@ -89,11 +87,12 @@ pub fn impl_input_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Res
                 _type: field.ty,
                 args: Vec::new(),
                 description: field_attrs.description.map(SpanContainer::into_inner),
-                deprecation: None,
+                deprecation: field_attrs.deprecation.map(SpanContainer::into_inner),
                 resolver_code,
                 is_type_inferred: true,
                 is_async: false,
                 default,
+                validate: field_attrs.validate.map(SpanContainer::into_inner),
                 span,
             })
         })
@@ -145,6 +144,8 @@ pub fn impl_input_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Res
         include_type_generics: true,
         generic_scalar: true,
         no_async: attrs.no_async.is_some(),
+        deny_unknown_fields: attrs.deny_unknown_fields.is_some(),
+        max_depth: attrs.max_depth.map(SpanContainer::into_inner),
     };
 
     Ok(definition.into_input_object_tokens())
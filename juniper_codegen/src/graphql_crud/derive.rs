@@ -0,0 +1,126 @@
+//! Code generation for `#[derive(GraphQLCrud)]` macro.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, ToTokens as _};
+use syn::{ext::IdentExt as _, parse_quote, spanned::Spanned as _};
+
+use crate::{
+    result::GraphQLScope,
+    util::{span_container::SpanContainer, to_upper_snake_case},
+};
+
+use super::{Attr, Definition, Field, FieldAttr};
+
+/// [`GraphQLScope`] of errors for `#[derive(GraphQLCrud)]` macro.
+const ERR: GraphQLScope = GraphQLScope::CrudDerive;
+
+/// Expands `#[derive(GraphQLCrud)]` macro into generated code.
+pub fn expand(input: TokenStream) -> syn::Result<TokenStream> {
+    let ast = syn::parse2::<syn::DeriveInput>(input)?;
+    let attr = Attr::from_attrs("graphql_crud", &ast.attrs)?;
+
+    let data = if let syn::Data::Struct(data) = &ast.data {
+        data
+    } else {
+        return Err(ERR.custom_error(ast.span(), "can only be derived for structs"));
+    };
+    let fields = if let syn::Fields::Named(fields) = &data.fields {
+        &fields.named
+    } else {
+        return Err(ERR.custom_error(
+            ast.ident.span(),
+            "can only be derived for structs with named fields",
+        ));
+    };
+    if !ast.generics.params.is_empty() {
+        return Err(ERR.custom_error(
+            ast.generics.span(),
+            "can only be derived for structs without generics",
+        ));
+    }
+
+    let context = attr.context.map(SpanContainer::into_inner).ok_or_else(|| {
+        ERR.custom_error(
+            ast.ident.span(),
+            "requires a `context` implementing `CrudStorage` to be specified via \
+                 `#[graphql_crud(context = <type>)]` attribute",
+        )
+    })?;
+
+    let fields = fields
+        .iter()
+        .map(|f| Ok((f, FieldAttr::from_attrs("graphql_crud", &f.attrs)?)))
+        .collect::<syn::Result<Vec<_>>>()?;
+    let explicit_id = fields.iter().any(|(_, attr)| attr.id.is_some());
+
+    let mut id = None;
+    let mut input_fields = vec![];
+    for (f, field_attr) in fields {
+        let ident = f.ident.clone().unwrap();
+
+        let is_id = if explicit_id {
+            field_attr.id.is_some()
+        } else {
+            ident.unraw() == "id"
+        };
+        if is_id {
+            if id.replace(f.ty.clone()).is_some() {
+                ERR.emit_custom(f.span(), "allows only a single `id` field");
+            }
+            continue;
+        }
+        if field_attr.skip.is_some() {
+            continue;
+        }
+
+        input_fields.push(Field {
+            ident,
+            ty: f.ty.clone(),
+            docs: f
+                .attrs
+                .iter()
+                .filter(|a| a.path.is_ident("doc"))
+                .cloned()
+                .collect(),
+        });
+    }
+
+    proc_macro_error::abort_if_dirty();
+
+    let id_ty = id.ok_or_else(|| {
+        ERR.custom_error(
+            ast.ident.span(),
+            "requires an `id` field, or a field marked with `#[graphql_crud(id)]` attribute",
+        )
+    })?;
+
+    let singular = format_ident!(
+        "{}",
+        to_upper_snake_case(&ast.ident.unraw().to_string()).to_lowercase(),
+    );
+    let plural = attr
+        .plural
+        .map(SpanContainer::into_inner)
+        .unwrap_or_else(|| format_ident!("{}s", singular));
+    if plural == singular {
+        return Err(ERR.custom_error(
+            plural.span(),
+            "requires the `plural` name to differ from the singular one",
+        ));
+    }
+
+    Ok(Definition {
+        ident: ast.ident.clone(),
+        vis: ast.vis.clone(),
+        singular,
+        plural,
+        id_ty,
+        fields: input_fields,
+        context,
+        scalar: attr
+            .scalar
+            .map(SpanContainer::into_inner)
+            .unwrap_or_else(|| parse_quote! { ::juniper::DefaultScalarValue }),
+    }
+    .into_token_stream())
+}
@@ -0,0 +1,420 @@
+//! Code generation for CRUD fields of an entity, backed by a
+//! [`juniper::CrudStorage`].
+
+pub mod derive;
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    parse::{Parse, ParseStream},
+    spanned::Spanned as _,
+    token,
+};
+
+use crate::{
+    common::parse::{
+        attr::{err, OptionExt as _},
+        ParseBufferExt as _,
+    },
+    util::{filter_attrs, span_container::SpanContainer},
+};
+
+/// Available arguments behind `#[graphql_crud]` attribute placed on an entity
+/// struct, when generating code for its CRUD fields.
+#[derive(Debug, Default)]
+struct Attr {
+    /// Explicitly specified type of [`Context`] implementing the
+    /// [`CrudStorage`] of the entity.
+    ///
+    /// [`Context`]: juniper::Context
+    /// [`CrudStorage`]: juniper::CrudStorage
+    context: Option<SpanContainer<syn::Type>>,
+
+    /// Explicitly specified type of [`ScalarValue`] to generate the CRUD
+    /// fields with.
+    ///
+    /// If [`None`], then [`DefaultScalarValue`] is used.
+    ///
+    /// [`DefaultScalarValue`]: juniper::DefaultScalarValue
+    /// [`ScalarValue`]: juniper::ScalarValue
+    scalar: Option<SpanContainer<syn::Type>>,
+
+    /// Explicitly specified plural of the `snake_cased` entity name, used for
+    /// naming its list field.
+    ///
+    /// If [`None`], then `s` is appended to the entity name.
+    plural: Option<SpanContainer<syn::Ident>>,
+}
+
+impl Parse for Attr {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let mut out = Self::default();
+        while !input.is_empty() {
+            let ident = input.parse_any_ident()?;
+            match ident.to_string().as_str() {
+                "context" | "Context" => {
+                    input.parse::<token::Eq>()?;
+                    let ctx = input.parse::<syn::Type>()?;
+                    out.context
+                        .replace(SpanContainer::new(ident.span(), Some(ctx.span()), ctx))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
+                "scalar" | "Scalar" | "ScalarValue" => {
+                    input.parse::<token::Eq>()?;
+                    let scl = input.parse::<syn::Type>()?;
+                    out.scalar
+                        .replace(SpanContainer::new(ident.span(), Some(scl.span()), scl))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
+                "plural" => {
+                    input.parse::<token::Eq>()?;
+                    let lit = input.parse::<syn::LitStr>()?;
+                    let plural = lit.parse::<syn::Ident>()?;
+                    out.plural
+                        .replace(SpanContainer::new(ident.span(), Some(lit.span()), plural))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
+                name => {
+                    return Err(err::unknown_arg(&ident, name));
+                }
+            }
+            input.try_parse::<token::Comma>()?;
+        }
+        Ok(out)
+    }
+}
+
+impl Attr {
+    /// Tries to merge two [`Attr`]s into a single one, reporting about
+    /// duplicates, if any.
+    fn try_merge(self, mut another: Self) -> syn::Result<Self> {
+        Ok(Self {
+            context: try_merge_opt!(context: self, another),
+            scalar: try_merge_opt!(scalar: self, another),
+            plural: try_merge_opt!(plural: self, another),
+        })
+    }
+
+    /// Parses [`Attr`] from the given multiple `name`d [`syn::Attribute`]s
+    /// placed on a type definition.
+    fn from_attrs(name: &str, attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        filter_attrs(name, attrs)
+            .map(|attr| attr.parse_args())
+            .try_fold(Self::default(), |prev, curr| prev.try_merge(curr?))
+    }
+}
+
+/// Available arguments behind `#[graphql_crud]` attribute placed on a field of
+/// an entity struct.
+#[derive(Debug, Default)]
+struct FieldAttr {
+    /// Explicitly specified marker of the field being the ID of the entity.
+    ///
+    /// If absent for all the fields, then the field named `id` is used.
+    id: Option<SpanContainer<syn::Ident>>,
+
+    /// Explicitly specified marker of the field to be omitted from the
+    /// generated input objects.
+    skip: Option<SpanContainer<syn::Ident>>,
+}
+
+impl Parse for FieldAttr {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let mut out = Self::default();
+        while !input.is_empty() {
+            let ident = input.parse_any_ident()?;
+            match ident.to_string().as_str() {
+                "id" => out
+                    .id
+                    .replace(SpanContainer::new(ident.span(), None, ident.clone()))
+                    .none_or_else(|_| err::dup_arg(&ident))?,
+                "ignore" | "skip" => out
+                    .skip
+                    .replace(SpanContainer::new(ident.span(), None, ident.clone()))
+                    .none_or_else(|_| err::dup_arg(&ident))?,
+                name => {
+                    return Err(err::unknown_arg(&ident, name));
+                }
+            }
+            input.try_parse::<token::Comma>()?;
+        }
+        Ok(out)
+    }
+}
+
+impl FieldAttr {
+    /// Tries to merge two [`FieldAttr`]s into a single one, reporting about
+    /// duplicates, if any.
+    fn try_merge(self, mut another: Self) -> syn::Result<Self> {
+        Ok(Self {
+            id: try_merge_opt!(id: self, another),
+            skip: try_merge_opt!(skip: self, another),
+        })
+    }
+
+    /// Parses [`FieldAttr`] from the given multiple `name`d
+    /// [`syn::Attribute`]s placed on a struct field.
+    fn from_attrs(name: &str, attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        filter_attrs(name, attrs)
+            .map(|attr| attr.parse_args())
+            .try_fold(Self::default(), |prev, curr| prev.try_merge(curr?))
+    }
+}
+
+/// Field of an entity to be set via the generated input objects.
+struct Field {
+    /// Rust name of this field.
+    ident: syn::Ident,
+
+    /// Rust type of this field.
+    ty: syn::Type,
+
+    /// Doc comments of this field, becoming the descriptions of the input
+    /// object fields.
+    docs: Vec<syn::Attribute>,
+}
+
+/// Definition of CRUD fields of an entity for code generation.
+struct Definition {
+    /// Rust type of the entity.
+    ident: syn::Ident,
+
+    /// Visibility of the entity, being the visibility of the generated types.
+    vis: syn::Visibility,
+
+    /// `snake_cased` name of the entity, naming its single-entity fields.
+    singular: syn::Ident,
+
+    /// Plural of the `snake_cased` name of the entity, naming its list field.
+    plural: syn::Ident,
+
+    /// Rust type of the ID of the entity.
+    id_ty: syn::Type,
+
+    /// Fields of the entity to be set via the generated input objects.
+    fields: Vec<Field>,
+
+    /// Rust type of [`Context`] implementing the [`CrudStorage`] of the entity.
+    ///
+    /// [`Context`]: juniper::Context
+    /// [`CrudStorage`]: juniper::CrudStorage
+    context: syn::Type,
+
+    /// Rust type of [`ScalarValue`] to generate the CRUD fields with.
+    ///
+    /// [`ScalarValue`]: juniper::ScalarValue
+    scalar: syn::Type,
+}
+
+impl ToTokens for Definition {
+    fn to_tokens(&self, into: &mut TokenStream) {
+        self.impl_crud_tokens().to_tokens(into);
+        self.input_objects_tokens().to_tokens(into);
+        self.query_tokens().to_tokens(into);
+        self.mutation_tokens().to_tokens(into);
+    }
+}
+
+impl Definition {
+    /// Returns the Rust types of the generated create and update input objects.
+    fn input_idents(&self) -> (syn::Ident, syn::Ident) {
+        (
+            format_ident!("{}CreateInput", self.ident),
+            format_ident!("{}UpdateInput", self.ident),
+        )
+    }
+
+    /// Returns generated code implementing [`Crud`] trait for the entity.
+    ///
+    /// [`Crud`]: juniper::Crud
+    #[must_use]
+    fn impl_crud_tokens(&self) -> TokenStream {
+        let ident = &self.ident;
+        let id_ty = &self.id_ty;
+        let (create, update) = self.input_idents();
+
+        quote! {
+            #[automatically_derived]
+            impl ::juniper::Crud for #ident {
+                type Id = #id_ty;
+                type CreateInput = #create;
+                type UpdateInput = #update;
+            }
+        }
+    }
+
+    /// Returns generated code of the input objects creating and updating the
+    /// entity.
+    #[must_use]
+    fn input_objects_tokens(&self) -> TokenStream {
+        let (vis, scalar) = (&self.vis, &self.scalar);
+        let (create, update) = self.input_idents();
+        let create_doc = format!("Input for creating a new `{}`.", self.ident);
+        let update_doc = format!(
+            "Input for updating a `{}`, changing only the specified fields.",
+            self.ident,
+        );
+
+        let create_fields = self.fields.iter().map(|f| {
+            let (ident, ty, docs) = (&f.ident, &f.ty, &f.docs);
+            quote! {
+                #( #docs )*
+                pub #ident: #ty,
+            }
+        });
+        let update_fields = self.fields.iter().map(|f| {
+            let (ident, docs) = (&f.ident, &f.docs);
+            let ty = match option_inner(&f.ty) {
+                Some(inner) => quote! { ::juniper::Nullable<#inner> },
+                None => {
+                    let ty = &f.ty;
+                    quote! { ::std::option::Option<#ty> }
+                }
+            };
+            quote! {
+                #( #docs )*
+                pub #ident: #ty,
+            }
+        });
+
+        quote! {
+            #[doc = #create_doc]
+            #[derive(::juniper::GraphQLInputObject)]
+            #[graphql(scalar = #scalar)]
+            #vis struct #create {
+                #( #create_fields )*
+            }
+
+            #[doc = #update_doc]
+            #[derive(::juniper::GraphQLInputObject)]
+            #[graphql(scalar = #scalar)]
+            #vis struct #update {
+                #( #update_fields )*
+            }
+        }
+    }
+
+    /// Returns generated code of the query object with the fields reading the
+    /// entity.
+    #[must_use]
+    fn query_tokens(&self) -> TokenStream {
+        let (ident, vis, id_ty) = (&self.ident, &self.vis, &self.id_ty);
+        let (context, scalar) = (&self.context, &self.scalar);
+        let (singular, plural) = (&self.singular, &self.plural);
+        let query = format_ident!("{}CrudQuery", ident);
+
+        let query_doc = format!("Query fields of `{}`.", ident);
+        let get_doc = format!("Returns the `{}` with the given ID, if any.", ident);
+        let list_doc = format!(
+            "Returns `{}`s, skipping the `offset` first ones and returning no \
+             more than `first` ones, if specified.",
+            ident,
+        );
+
+        quote! {
+            #[doc = #query_doc]
+            #[derive(Clone, Copy, Debug, Default)]
+            #vis struct #query;
+
+            #[::juniper::graphql_object(context = #context, scalar = #scalar)]
+            impl #query {
+                #[doc = #get_doc]
+                fn #singular(
+                    context: &#context,
+                    id: #id_ty,
+                ) -> ::juniper::FieldResult<::std::option::Option<#ident>, #scalar> {
+                    ::juniper::CrudStorage::<#ident, #scalar>::get(context, id)
+                }
+
+                #[doc = #list_doc]
+                fn #plural(
+                    context: &#context,
+                    first: ::std::option::Option<i32>,
+                    offset: ::std::option::Option<i32>,
+                ) -> ::juniper::FieldResult<::std::vec::Vec<#ident>, #scalar> {
+                    ::juniper::CrudStorage::<#ident, #scalar>::list(context, first, offset)
+                }
+            }
+        }
+    }
+
+    /// Returns generated code of the mutation object with the fields creating,
+    /// updating and deleting the entity.
+    #[must_use]
+    fn mutation_tokens(&self) -> TokenStream {
+        let (ident, vis, id_ty) = (&self.ident, &self.vis, &self.id_ty);
+        let (context, scalar) = (&self.context, &self.scalar);
+        let mutation = format_ident!("{}CrudMutation", ident);
+        let (create_input, update_input) = self.input_idents();
+        let create = format_ident!("create_{}", self.singular);
+        let update = format_ident!("update_{}", self.singular);
+        let delete = format_ident!("delete_{}", self.singular);
+
+        let mutation_doc = format!("Mutation fields of `{}`.", ident);
+        let create_doc = format!("Creates a new `{}`.", ident);
+        let update_doc = format!(
+            "Updates the `{}` with the given ID, returning `null` if there is no \
+             such one.",
+            ident,
+        );
+        let delete_doc = format!(
+            "Deletes the `{}` with the given ID, returning whether it existed.",
+            ident,
+        );
+
+        quote! {
+            #[doc = #mutation_doc]
+            #[derive(Clone, Copy, Debug, Default)]
+            #vis struct #mutation;
+
+            #[::juniper::graphql_object(context = #context, scalar = #scalar)]
+            impl #mutation {
+                #[doc = #create_doc]
+                fn #create(
+                    context: &#context,
+                    input: #create_input,
+                ) -> ::juniper::FieldResult<#ident, #scalar> {
+                    ::juniper::CrudStorage::<#ident, #scalar>::create(context, input)
+                }
+
+                #[doc = #update_doc]
+                fn #update(
+                    context: &#context,
+                    id: #id_ty,
+                    input: #update_input,
+                ) -> ::juniper::FieldResult<::std::option::Option<#ident>, #scalar> {
+                    ::juniper::CrudStorage::<#ident, #scalar>::update(context, id, input)
+                }
+
+                #[doc = #delete_doc]
+                fn #delete(
+                    context: &#context,
+                    id: #id_ty,
+                ) -> ::juniper::FieldResult<bool, #scalar> {
+                    ::juniper::CrudStorage::<#ident, #scalar>::delete(context, id)
+                }
+            }
+        }
+    }
+}
+
+/// Returns the inner type of the given `ty`, if it's an [`Option`].
+fn option_inner(ty: &syn::Type) -> Option<&syn::Type> {
+    let path = match ty {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => path,
+        _ => return None,
+    };
+    let seg = path.segments.last()?;
+    if seg.ident != "Option" {
+        return None;
+    }
+    match &seg.arguments {
+        syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 => {
+            match args.args.first()? {
+                syn::GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,146 @@
+//! Code generation for `#[derive(GraphQLDirective)]` macro.
+
+use std::collections::HashSet;
+
+use proc_macro2::TokenStream;
+use quote::ToTokens as _;
+use syn::{ext::IdentExt as _, parse_quote, spanned::Spanned as _};
+
+use crate::{
+    common::{field, scalar},
+    result::GraphQLScope,
+    util::{span_container::SpanContainer, RenameRule},
+};
+
+use super::{default_name, Attr, Definition};
+
+/// [`GraphQLScope`] of errors for `#[derive(GraphQLDirective)]` macro.
+const ERR: GraphQLScope = GraphQLScope::DirectiveDerive;
+
+/// Expands `#[derive(GraphQLDirective)]` macro into generated code.
+pub fn expand(input: TokenStream) -> syn::Result<TokenStream> {
+    let ast = syn::parse2::<syn::DeriveInput>(input)?;
+    let attr = Attr::from_attrs("graphql", &ast.attrs)?;
+
+    let data = if let syn::Data::Struct(data) = &ast.data {
+        data
+    } else {
+        return Err(ERR.custom_error(ast.span(), "can only be derived for structs"));
+    };
+
+    let renaming = attr
+        .rename_arguments
+        .as_deref()
+        .copied()
+        .unwrap_or(RenameRule::CamelCase);
+
+    let arguments = match &data.fields {
+        syn::Fields::Unit => None,
+        syn::Fields::Named(fields) => Some(
+            fields
+                .named
+                .iter()
+                .filter_map(|f| parse_argument(f, &renaming))
+                .collect::<Vec<_>>(),
+        ),
+        syn::Fields::Unnamed(_) => {
+            return Err(ERR.custom_error(
+                ast.span(),
+                "can only be derived for unit structs or structs with named fields",
+            ))
+        }
+    };
+
+    proc_macro_error::abort_if_dirty();
+
+    let name = attr
+        .name
+        .as_deref()
+        .cloned()
+        .unwrap_or_else(|| default_name(&ast.ident));
+    if name.starts_with("__") {
+        ERR.no_double_underscore(
+            attr.name
+                .as_ref()
+                .map(SpanContainer::span_ident)
+                .unwrap_or_else(|| ast.ident.span()),
+        );
+    }
+
+    if let Some(arguments) = &arguments {
+        let mut names = HashSet::new();
+        for (ident, arg) in arguments {
+            let name = arg.as_regular().map(|arg| arg.name.as_str());
+            if !names.insert(name) {
+                ERR.emit_custom(
+                    ident.span(),
+                    "does not allow arguments with the same name, \
+                     possibly renamed via the #[graphql] attribute",
+                );
+            }
+        }
+    }
+
+    proc_macro_error::abort_if_dirty();
+
+    Ok(Definition {
+        name,
+        ident: ast.ident.clone(),
+        generics: ast.generics.clone(),
+        description: attr.description.as_deref().cloned(),
+        arguments,
+        transform: attr
+            .transform
+            .as_deref()
+            .cloned()
+            .unwrap_or_else(|| parse_quote! { Self::transform }),
+        scalar: scalar::Type::parse(attr.scalar.as_deref(), &ast.generics),
+    }
+    .into_token_stream())
+}
+
+/// Parses a [directive][1] argument out of the given struct `field`.
+///
+/// Returns [`None`] if parsing fails and emits parsing errors.
+///
+/// [1]: https://spec.graphql.org/October2021#ExecutableDirectiveLocation
+fn parse_argument(
+    field: &syn::Field,
+    renaming: &RenameRule,
+) -> Option<(syn::Ident, field::arg::OnMethod)> {
+    let ident = field.ident.clone()?;
+
+    let mut arg = syn::PatType {
+        attrs: field.attrs.clone(),
+        pat: Box::new(parse_quote! { #ident }),
+        colon_token: Default::default(),
+        ty: Box::new(field.ty.clone()),
+    };
+    let arg = field::arg::OnMethod::parse(&mut arg, renaming, &ERR)?;
+
+    match &arg {
+        field::arg::OnMethod::Regular(a) if a.default_with_context.is_none() => {
+            Some((ident, arg))
+        }
+        field::arg::OnMethod::Regular(_) => {
+            ERR.custom(
+                field.span(),
+                "`default_with_context` is not supported on directive arguments",
+            )
+            .emit();
+            None
+        }
+        _ => {
+            ERR.custom(
+                field.span(),
+                format!(
+                    "field `{}` cannot be used as a context, an executor or arguments \
+                     of a directive",
+                    ident.unraw(),
+                ),
+            )
+            .emit();
+            None
+        }
+    }
+}
@@ -0,0 +1,325 @@
+//! Code generation for custom [executable directives][1].
+//!
+//! [1]: https://spec.graphql.org/October2021#ExecutableDirectiveLocation
+
+pub mod derive;
+
+use std::convert::TryInto as _;
+
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{
+    ext::IdentExt as _,
+    parse::{Parse, ParseStream},
+    parse_quote,
+    spanned::Spanned as _,
+    token,
+};
+
+use crate::{
+    common::{
+        field,
+        parse::{
+            attr::{err, OptionExt as _},
+            ParseBufferExt as _,
+        },
+        scalar,
+    },
+    util::{filter_attrs, get_doc_comment, span_container::SpanContainer, RenameRule},
+};
+
+/// Available arguments behind `#[graphql]` attribute when generating code for
+/// a custom [executable directive][1].
+///
+/// [1]: https://spec.graphql.org/October2021#ExecutableDirectiveLocation
+#[derive(Debug, Default)]
+struct Attr {
+    /// Explicitly specified name of this [directive][1] in GraphQL schema.
+    ///
+    /// If [`None`], then `camelCased` Rust type name is used by default.
+    ///
+    /// [1]: https://spec.graphql.org/October2021#ExecutableDirectiveLocation
+    name: Option<SpanContainer<String>>,
+
+    /// Explicitly specified description of this [directive][1].
+    ///
+    /// If [`None`], then Rust doc comment is used as description, if any.
+    ///
+    /// [1]: https://spec.graphql.org/October2021#ExecutableDirectiveLocation
+    description: Option<SpanContainer<String>>,
+
+    /// Explicitly specified type (or type parameter with its bounds) of
+    /// [`ScalarValue`] to implement this [directive][1] with.
+    ///
+    /// If [`None`], then generated code will be generic over any
+    /// [`ScalarValue`] type.
+    ///
+    /// [`ScalarValue`]: juniper::ScalarValue
+    /// [1]: https://spec.graphql.org/October2021#ExecutableDirectiveLocation
+    scalar: Option<SpanContainer<scalar::AttrValue>>,
+
+    /// Explicitly specified [`RenameRule`] for all arguments of this
+    /// [directive][1].
+    ///
+    /// If [`None`], then the [`RenameRule::CamelCase`] rule will be applied by
+    /// default.
+    ///
+    /// [1]: https://spec.graphql.org/October2021#ExecutableDirectiveLocation
+    rename_arguments: Option<SpanContainer<RenameRule>>,
+
+    /// Explicitly specified function to be used as
+    /// [`GraphQLDirective::resolve`] implementation.
+    ///
+    /// If [`None`], then `Self::transform` function is used.
+    ///
+    /// [`GraphQLDirective::resolve`]: juniper::GraphQLDirective::resolve
+    transform: Option<SpanContainer<syn::ExprPath>>,
+}
+
+impl Parse for Attr {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let mut out = Self::default();
+        while !input.is_empty() {
+            let ident = input.parse_any_ident()?;
+            match ident.to_string().as_str() {
+                "name" => {
+                    input.parse::<token::Eq>()?;
+                    let name = input.parse::<syn::LitStr>()?;
+                    out.name
+                        .replace(SpanContainer::new(
+                            ident.span(),
+                            Some(name.span()),
+                            name.value(),
+                        ))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
+                "desc" | "description" => {
+                    input.parse::<token::Eq>()?;
+                    let desc = input.parse::<syn::LitStr>()?;
+                    out.description
+                        .replace(SpanContainer::new(
+                            ident.span(),
+                            Some(desc.span()),
+                            desc.value(),
+                        ))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
+                "scalar" | "Scalar" | "ScalarValue" => {
+                    input.parse::<token::Eq>()?;
+                    let scl = input.parse::<scalar::AttrValue>()?;
+                    out.scalar
+                        .replace(SpanContainer::new(ident.span(), Some(scl.span()), scl))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
+                "rename_all" => {
+                    input.parse::<token::Eq>()?;
+                    let val = input.parse::<syn::LitStr>()?;
+                    out.rename_arguments
+                        .replace(SpanContainer::new(
+                            ident.span(),
+                            Some(val.span()),
+                            val.try_into()?,
+                        ))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
+                "transform_with" => {
+                    input.parse::<token::Eq>()?;
+                    let path = input.parse::<syn::ExprPath>()?;
+                    out.transform
+                        .replace(SpanContainer::new(ident.span(), Some(path.span()), path))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
+                name => {
+                    return Err(err::unknown_arg(&ident, name));
+                }
+            }
+            input.try_parse::<token::Comma>()?;
+        }
+        Ok(out)
+    }
+}
+
+impl Attr {
+    /// Tries to merge two [`Attr`]s into a single one, reporting about
+    /// duplicates, if any.
+    fn try_merge(self, mut another: Self) -> syn::Result<Self> {
+        Ok(Self {
+            name: try_merge_opt!(name: self, another),
+            description: try_merge_opt!(description: self, another),
+            scalar: try_merge_opt!(scalar: self, another),
+            rename_arguments: try_merge_opt!(rename_arguments: self, another),
+            transform: try_merge_opt!(transform: self, another),
+        })
+    }
+
+    /// Parses [`Attr`] from the given multiple `name`d [`syn::Attribute`]s
+    /// placed on a type definition.
+    fn from_attrs(name: &str, attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut attr = filter_attrs(name, attrs)
+            .map(|attr| attr.parse_args())
+            .try_fold(Self::default(), |prev, curr| prev.try_merge(curr?))?;
+
+        if attr.description.is_none() {
+            attr.description = get_doc_comment(attrs);
+        }
+
+        Ok(attr)
+    }
+}
+
+/// Definition of a custom [executable directive][1] for code generation.
+///
+/// [1]: https://spec.graphql.org/October2021#ExecutableDirectiveLocation
+struct Definition {
+    /// Name of this [directive][1] in GraphQL schema.
+    ///
+    /// [1]: https://spec.graphql.org/October2021#ExecutableDirectiveLocation
+    name: String,
+
+    /// Rust type that this [directive][1] is represented with.
+    ///
+    /// [1]: https://spec.graphql.org/October2021#ExecutableDirectiveLocation
+    ident: syn::Ident,
+
+    /// Generics of the Rust type that this [directive][1] is implemented for.
+    ///
+    /// [1]: https://spec.graphql.org/October2021#ExecutableDirectiveLocation
+    generics: syn::Generics,
+
+    /// Description of this [directive][1] to put into GraphQL schema.
+    ///
+    /// [1]: https://spec.graphql.org/October2021#ExecutableDirectiveLocation
+    description: Option<String>,
+
+    /// Arguments of this [directive][1] along with the Rust struct fields
+    /// they're stored in, or [`None`] if the Rust type is a unit struct.
+    ///
+    /// [1]: https://spec.graphql.org/October2021#ExecutableDirectiveLocation
+    arguments: Option<Vec<(syn::Ident, field::arg::OnMethod)>>,
+
+    /// Function transforming resolved field values with this [directive][1].
+    ///
+    /// [1]: https://spec.graphql.org/October2021#ExecutableDirectiveLocation
+    transform: syn::ExprPath,
+
+    /// [`ScalarValue`] parametrization to generate [`GraphQLDirective`]
+    /// implementation with for this [directive][1].
+    ///
+    /// [`GraphQLDirective`]: juniper::GraphQLDirective
+    /// [`ScalarValue`]: juniper::ScalarValue
+    /// [1]: https://spec.graphql.org/October2021#ExecutableDirectiveLocation
+    scalar: scalar::Type,
+}
+
+impl ToTokens for Definition {
+    fn to_tokens(&self, into: &mut TokenStream) {
+        self.impl_graphql_directive_tokens().to_tokens(into);
+    }
+}
+
+impl Definition {
+    /// Returns generated code implementing [`GraphQLDirective`] trait for this
+    /// [directive][1].
+    ///
+    /// [`GraphQLDirective`]: juniper::GraphQLDirective
+    /// [1]: https://spec.graphql.org/October2021#ExecutableDirectiveLocation
+    #[must_use]
+    fn impl_graphql_directive_tokens(&self) -> TokenStream {
+        let ident = &self.ident;
+        let name = &self.name;
+        let scalar = &self.scalar;
+        let transform = &self.transform;
+
+        let description = self
+            .description
+            .as_ref()
+            .map(|desc| quote! { .description(#desc) });
+
+        let arguments = self.arguments.as_deref().unwrap_or_default();
+        let meta_arguments = arguments
+            .iter()
+            .filter_map(|(_, arg)| arg.method_meta_tokens());
+
+        let construct = self.arguments.as_ref().map_or_else(
+            || quote! { Self },
+            |arguments| {
+                let fields = arguments.iter().map(|(field, arg)| {
+                    let val = arg.method_resolve_field_tokens(scalar, false);
+                    quote! { #field: #val }
+                });
+                quote! { Self { #( #fields ),* } }
+            },
+        );
+
+        let mut generics = self.generics.clone();
+        if scalar.is_implicit_generic() {
+            generics.params.push(parse_quote! { #scalar });
+        }
+        if scalar.is_generic() {
+            generics
+                .make_where_clause()
+                .predicates
+                .push(parse_quote! { #scalar: ::juniper::ScalarValue });
+        }
+        if let Some(bound) = scalar.bounds() {
+            generics.make_where_clause().predicates.push(bound);
+        }
+        let (impl_gens, _, where_clause) = generics.split_for_impl();
+        let (_, ty_gens, _) = self.generics.split_for_impl();
+
+        quote! {
+            #[automatically_derived]
+            impl#impl_gens ::juniper::GraphQLDirective<#scalar> for #ident#ty_gens
+                #where_clause
+            {
+                fn name() -> &'static str {
+                    #name
+                }
+
+                fn meta<'r>(
+                    registry: &mut ::juniper::Registry<'r, #scalar>,
+                ) -> ::juniper::DirectiveType<'r, #scalar>
+                where
+                    #scalar: 'r,
+                {
+                    let info = &();
+                    ::juniper::DirectiveType::new(
+                        #name,
+                        &[::juniper::DirectiveLocation::Field],
+                        &[],
+                        false,
+                    )
+                    #( #meta_arguments )*
+                    #description
+                }
+
+                fn from_arguments(
+                    args: &::juniper::Arguments<#scalar>,
+                ) -> ::juniper::FieldResult<Self, #scalar> {
+                    Ok(#construct)
+                }
+
+                fn resolve(
+                    self,
+                    value: ::juniper::Value<#scalar>,
+                ) -> ::juniper::FieldResult<::juniper::Value<#scalar>, #scalar> {
+                    #transform(self, value)
+                        .map_err(::juniper::IntoFieldError::<#scalar>::into_field_error)
+                }
+            }
+        }
+    }
+}
+
+/// Parses the name of the [directive][1] from the given Rust type `ident`,
+/// converting it into `camelCase`.
+///
+/// [1]: https://spec.graphql.org/October2021#ExecutableDirectiveLocation
+fn default_name(ident: &syn::Ident) -> String {
+    let name = ident.unraw().to_string();
+    let mut chars = name.chars();
+    chars
+        .next()
+        .map(|c| c.to_lowercase().chain(chars).collect())
+        .unwrap_or_default()
+}
@@ -163,6 +163,22 @@ fn parse_trait_method(
         return None;
     }
 
+    if let Some(skip_if) = &attr.skip_if {
+        return field::err_unsupported_skip_if(&ERR, skip_if);
+    }
+
+    if let Some(timeout) = &attr.timeout {
+        return field::err_unsupported_async_only(&ERR, timeout.span_ident(), "timeout");
+    }
+
+    if let Some(retry) = &attr.retry {
+        return field::err_unsupported_async_only(&ERR, retry.span_ident(), "retry");
+    }
+
+    if let Some(alias) = attr.aliases.first() {
+        return field::err_unsupported_alias(&ERR, alias);
+    }
+
     if method.default.is_some() {
         return err_default_impl_block(&method.default);
     }
@@ -199,10 +215,7 @@ fn parse_trait_method(
     ty.lifetimes_anonymized();
 
     let description = attr.description.as_ref().map(|d| d.as_ref().value());
-    let deprecated = attr
-        .deprecated
-        .as_deref()
-        .map(|d| d.as_ref().map(syn::LitStr::value));
+    let deprecated = attr.deprecation(renaming);
 
     Some(field::Definition {
         name,
@@ -213,6 +226,12 @@ fn parse_trait_method(
         arguments: Some(arguments),
         has_receiver: method.sig.receiver().is_some(),
         is_async: method.sig.asyncness.is_some(),
+        skip_if: None,
+        pii: attr.pii.is_some(),
+        complexity: attr.complexity.map(SpanContainer::into_inner),
+        timeout: None,
+        retry: None,
+        aliases: vec![],
     })
 }
 
@@ -341,6 +360,22 @@ fn parse_struct_field(field: &mut syn::Field, renaming: &RenameRule) -> Option<f
         return None;
     }
 
+    if let Some(skip_if) = &attr.skip_if {
+        return field::err_unsupported_skip_if(&ERR, skip_if);
+    }
+
+    if let Some(timeout) = &attr.timeout {
+        return field::err_unsupported_async_only(&ERR, timeout.span_ident(), "timeout");
+    }
+
+    if let Some(retry) = &attr.retry {
+        return field::err_unsupported_async_only(&ERR, retry.span_ident(), "retry");
+    }
+
+    if let Some(alias) = attr.aliases.first() {
+        return field::err_unsupported_alias(&ERR, alias);
+    }
+
     let name = attr
         .name
         .as_ref()
@@ -360,10 +395,7 @@ fn parse_struct_field(field: &mut syn::Field, renaming: &RenameRule) -> Option<f
     ty.lifetimes_anonymized();
 
     let description = attr.description.as_ref().map(|d| d.as_ref().value());
-    let deprecated = attr
-        .deprecated
-        .as_deref()
-        .map(|d| d.as_ref().map(syn::LitStr::value));
+    let deprecated = attr.deprecation(renaming);
 
     Some(field::Definition {
         name,
@@ -374,6 +406,12 @@ fn parse_struct_field(field: &mut syn::Field, renaming: &RenameRule) -> Option<f
         arguments: None,
         has_receiver: false,
         is_async: false,
+        skip_if: None,
+        pii: attr.pii.is_some(),
+        complexity: attr.complexity.map(SpanContainer::into_inner),
+        timeout: None,
+        retry: None,
+        aliases: vec![],
     })
 }
 
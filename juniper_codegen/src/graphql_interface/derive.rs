@@ -122,6 +122,22 @@ fn parse_field(field: &syn::Field, renaming: &RenameRule) -> Option<field::Defin
         return None;
     }
 
+    if let Some(skip_if) = &attr.skip_if {
+        return field::err_unsupported_skip_if(&ERR, skip_if);
+    }
+
+    if let Some(timeout) = &attr.timeout {
+        return field::err_unsupported_async_only(&ERR, timeout.span_ident(), "timeout");
+    }
+
+    if let Some(retry) = &attr.retry {
+        return field::err_unsupported_async_only(&ERR, retry.span_ident(), "retry");
+    }
+
+    if let Some(alias) = attr.aliases.first() {
+        return field::err_unsupported_alias(&ERR, alias);
+    }
+
     let name = attr
         .name
         .as_ref()
@@ -141,10 +157,7 @@ fn parse_field(field: &syn::Field, renaming: &RenameRule) -> Option<field::Defin
     ty.lifetimes_anonymized();
 
     let description = attr.description.as_ref().map(|d| d.as_ref().value());
-    let deprecated = attr
-        .deprecated
-        .as_deref()
-        .map(|d| d.as_ref().map(syn::LitStr::value));
+    let deprecated = attr.deprecation(renaming);
 
     Some(field::Definition {
         name,
@@ -155,5 +168,11 @@ fn parse_field(field: &syn::Field, renaming: &RenameRule) -> Option<field::Defin
         arguments: None,
         has_receiver: false,
         is_async: false,
+        skip_if: None,
+        pii: attr.pii.is_some(),
+        complexity: attr.complexity.map(SpanContainer::into_inner),
+        timeout: None,
+        retry: None,
+        aliases: vec![],
     })
 }
@@ -16,7 +16,7 @@ use crate::{
     util::{path_eq_single, span_container::SpanContainer, RenameRule},
 };
 
-use super::{Attr, Definition, Query};
+use super::{has_non_scalar_generics, Attr, Definition, Query};
 
 /// [`GraphQLScope`] of errors for `#[graphql_object]` macro.
 const ERR: GraphQLScope = GraphQLScope::ObjectAttr;
@@ -67,6 +67,33 @@ where
 
     let scalar = scalar::Type::parse(attr.scalar.as_deref(), &ast.generics);
 
+    let is_part = attr.part.is_some();
+    if is_part || !attr.parts.is_empty() {
+        if TypeId::of::<Operation>() != TypeId::of::<Query>() {
+            ERR.emit_custom(
+                type_span,
+                "`part` and `parts` attribute arguments are supported on GraphQL objects only",
+            );
+        } else if has_non_scalar_generics(&ast.generics, &scalar) {
+            ERR.emit_custom(
+                ast.generics.span(),
+                "`part` and `parts` attribute arguments are not supported on generic types",
+            );
+        }
+    }
+    if let Some(part) = &attr.part {
+        if attr.name.is_some() || !attr.interfaces.is_empty() || !attr.parts.is_empty() {
+            ERR.custom(
+                part.span_ident(),
+                "`part` attribute argument cannot be combined with `name`, `impl` or `parts` ones",
+            )
+            .note(String::from(
+                "specify them on the main `impl` block of the GraphQL object instead",
+            ))
+            .emit();
+        }
+    }
+
     proc_macro_error::abort_if_dirty();
 
     let renaming = attr
@@ -90,12 +117,20 @@ where
 
     proc_macro_error::abort_if_dirty();
 
-    if fields.is_empty() {
+    if fields.is_empty() && attr.parts.is_empty() {
         ERR.emit_custom(type_span, "must have at least one field");
     }
     if !field::all_different(&fields) {
         ERR.emit_custom(type_span, "must have a different name for each field");
     }
+    if let Some(part) = attr.part.as_ref() {
+        if fields.iter().any(|f| !f.aliases.is_empty()) {
+            ERR.emit_custom(
+                part.span_ident(),
+                "`part` impl blocks don't support `alias` attribute argument on their fields",
+            );
+        }
+    }
 
     proc_macro_error::abort_if_dirty();
 
@@ -112,7 +147,19 @@ where
                 })
             })
         })
-        .unwrap_or_else(|| parse_quote! { () });
+        .unwrap_or_else(|| {
+            if is_part {
+                let (ty, scalar) = (&ast.self_ty, &scalar);
+                parse_quote! { <#ty as ::juniper::GraphQLValue<#scalar>>::Context }
+            } else {
+                parse_quote! { () }
+            }
+        });
+
+    // Sorting is required to preserve/guarantee the order of parts' fields
+    // registered in schema.
+    let mut parts: Vec<_> = attr.parts.iter().map(|ty| ty.as_ref().clone()).collect();
+    parts.sort_unstable_by_key(|ty| quote!(#ty).to_string());
 
     let generated_code = Definition::<Operation> {
         name,
@@ -127,6 +174,8 @@ where
             .iter()
             .map(|ty| ty.as_ref().clone())
             .collect(),
+        part: attr.part.map(SpanContainer::into_inner),
+        parts,
         _operation: PhantomData,
     };
 
@@ -165,6 +214,23 @@ fn parse_field(
         return err_no_sync_resolvers(&method.sig);
     }
 
+    if let Some(skip_if) = attr.skip_if.as_ref().filter(|_| async_only) {
+        return field::err_unsupported_skip_if(&ERR, skip_if);
+    }
+
+    if let Some(alias) = attr.aliases.first().filter(|_| async_only) {
+        return field::err_unsupported_alias(&ERR, alias);
+    }
+
+    if async_only || method.sig.asyncness.is_none() {
+        if let Some(timeout) = &attr.timeout {
+            return field::err_unsupported_async_only(&ERR, timeout.span_ident(), "timeout");
+        }
+        if let Some(retry) = &attr.retry {
+            return field::err_unsupported_async_only(&ERR, retry.span_ident(), "retry");
+        }
+    }
+
     let method_ident = &method.sig.ident;
 
     let name = attr
@@ -217,10 +283,7 @@ fn parse_field(
     ty.lifetimes_anonymized();
 
     let description = attr.description.as_ref().map(|d| d.as_ref().value());
-    let deprecated = attr
-        .deprecated
-        .as_deref()
-        .map(|d| d.as_ref().map(syn::LitStr::value));
+    let deprecated = attr.deprecation(renaming);
 
     Some(field::Definition {
         name,
@@ -231,6 +294,16 @@ fn parse_field(
         arguments: Some(arguments),
         has_receiver: method.sig.receiver().is_some(),
         is_async: method.sig.asyncness.is_some(),
+        skip_if: attr.skip_if.map(SpanContainer::into_inner),
+        pii: attr.pii.is_some(),
+        complexity: attr.complexity.map(SpanContainer::into_inner),
+        timeout: attr.timeout.map(SpanContainer::into_inner),
+        retry: attr.retry.map(SpanContainer::into_inner),
+        aliases: attr
+            .aliases
+            .into_iter()
+            .map(SpanContainer::into_inner)
+            .collect(),
     })
 }
 
@@ -4,7 +4,7 @@ use std::marker::PhantomData;
 
 use proc_macro2::TokenStream;
 use proc_macro_error::ResultExt as _;
-use quote::ToTokens;
+use quote::{quote, ToTokens};
 use syn::{ext::IdentExt as _, parse_quote, spanned::Spanned as _};
 
 use crate::{
@@ -13,7 +13,7 @@ use crate::{
     util::{span_container::SpanContainer, RenameRule},
 };
 
-use super::{Attr, Definition, Query};
+use super::{has_non_scalar_generics, Attr, Definition, Query};
 
 /// [`GraphQLScope`] of errors for `#[derive(GraphQLObject)]` macro.
 const ERR: GraphQLScope = GraphQLScope::ObjectDerive;
@@ -56,6 +56,19 @@ fn expand_struct(ast: syn::DeriveInput) -> syn::Result<Definition<Query>> {
 
     let scalar = scalar::Type::parse(attr.scalar.as_deref(), &ast.generics);
 
+    if let Some(part) = &attr.part {
+        ERR.emit_custom(
+            part.span_ident(),
+            "`part` attribute argument is supported on `impl` blocks only",
+        );
+    }
+    if !attr.parts.is_empty() && has_non_scalar_generics(&ast.generics, &scalar) {
+        ERR.emit_custom(
+            ast.generics.span(),
+            "`parts` attribute argument is not supported on generic types",
+        );
+    }
+
     proc_macro_error::abort_if_dirty();
 
     let renaming = attr
@@ -88,6 +101,11 @@ fn expand_struct(ast: syn::DeriveInput) -> syn::Result<Definition<Query>> {
 
     proc_macro_error::abort_if_dirty();
 
+    // Sorting is required to preserve/guarantee the order of parts' fields
+    // registered in schema.
+    let mut parts: Vec<_> = attr.parts.iter().map(|ty| ty.as_ref().clone()).collect();
+    parts.sort_unstable_by_key(|ty| quote!(#ty).to_string());
+
     Ok(Definition {
         name,
         ty,
@@ -104,6 +122,8 @@ fn expand_struct(ast: syn::DeriveInput) -> syn::Result<Definition<Query>> {
             .iter()
             .map(|ty| ty.as_ref().clone())
             .collect(),
+        part: None,
+        parts,
         _operation: PhantomData,
     })
 }
@@ -121,6 +141,14 @@ fn parse_field(field: &syn::Field, renaming: &RenameRule) -> Option<field::Defin
         return None;
     }
 
+    if let Some(timeout) = &attr.timeout {
+        return field::err_unsupported_async_only(&ERR, timeout.span_ident(), "timeout");
+    }
+
+    if let Some(retry) = &attr.retry {
+        return field::err_unsupported_async_only(&ERR, retry.span_ident(), "retry");
+    }
+
     let field_ident = field.ident.as_ref().unwrap();
 
     let name = attr
@@ -142,10 +170,7 @@ fn parse_field(field: &syn::Field, renaming: &RenameRule) -> Option<field::Defin
     ty.lifetimes_anonymized();
 
     let description = attr.description.as_ref().map(|d| d.as_ref().value());
-    let deprecated = attr
-        .deprecated
-        .as_deref()
-        .map(|d| d.as_ref().map(syn::LitStr::value));
+    let deprecated = attr.deprecation(renaming);
 
     Some(field::Definition {
         name,
@@ -156,5 +181,15 @@ fn parse_field(field: &syn::Field, renaming: &RenameRule) -> Option<field::Defin
         arguments: None,
         has_receiver: false,
         is_async: false,
+        skip_if: attr.skip_if.map(SpanContainer::into_inner),
+        pii: attr.pii.is_some(),
+        complexity: attr.complexity.map(SpanContainer::into_inner),
+        timeout: None,
+        retry: None,
+        aliases: attr
+            .aliases
+            .into_iter()
+            .map(SpanContainer::into_inner)
+            .collect(),
     })
 }
@@ -88,6 +88,23 @@ pub(crate) struct Attr {
     /// [1]: https://spec.graphql.org/June2018/#sec-Objects
     pub(crate) rename_fields: Option<SpanContainer<RenameRule>>,
 
+    /// Explicitly specified marker type of the part of a [GraphQL object][1]
+    /// fields defined by this `impl` block.
+    ///
+    /// If [`Some`], then this `impl` block doesn't define a
+    /// [GraphQL object][1] on its own, but should be listed in the [`parts`]
+    /// of the one.
+    ///
+    /// [`parts`]: Self::parts
+    /// [1]: https://spec.graphql.org/June2018/#sec-Objects
+    pub(crate) part: Option<SpanContainer<syn::Ident>>,
+
+    /// Explicitly specified marker types of the parts of this
+    /// [GraphQL object][1] fields defined in separate `impl` blocks.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Objects
+    pub(crate) parts: HashSet<SpanContainer<syn::Type>>,
+
     /// Indicator whether the generated code is intended to be used only inside
     /// the [`juniper`] library.
     pub(crate) is_internal: bool,
@@ -158,6 +175,25 @@ impl Parse for Attr {
                         ))
                         .none_or_else(|_| err::dup_arg(&ident))?;
                 }
+                "part" => {
+                    input.parse::<token::Eq>()?;
+                    let part = input.parse::<syn::Ident>()?;
+                    out.part
+                        .replace(SpanContainer::new(ident.span(), Some(part.span()), part))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
+                "parts" => {
+                    input.parse::<token::Eq>()?;
+                    for part in input.parse_maybe_wrapped_and_punctuated::<
+                        syn::Type, token::Bracket, token::Comma,
+                    >()? {
+                        let part_span = part.span();
+                        out
+                            .parts
+                            .replace(SpanContainer::new(ident.span(), Some(part_span), part))
+                            .none_or_else(|_| err::dup_arg(part_span))?;
+                    }
+                }
                 "internal" => {
                     out.is_internal = true;
                 }
@@ -182,6 +218,8 @@ impl Attr {
             scalar: try_merge_opt!(scalar: self, another),
             interfaces: try_merge_hashset!(interfaces: self, another => span_joined),
             rename_fields: try_merge_opt!(rename_fields: self, another),
+            part: try_merge_opt!(part: self, another),
+            parts: try_merge_hashset!(parts: self, another => span_joined),
             is_internal: self.is_internal || another.is_internal,
         })
     }
@@ -201,6 +239,17 @@ impl Attr {
     }
 }
 
+/// Checks whether the given `generics` contain any parameters besides the
+/// explicitly generic `scalar`, which cannot be used along with [`Attr::part`]
+/// or [`Attr::parts`].
+#[must_use]
+pub(crate) fn has_non_scalar_generics(generics: &syn::Generics, scalar: &scalar::Type) -> bool {
+    generics.params.iter().any(|p| match (p, scalar) {
+        (syn::GenericParam::Type(tp), scalar::Type::ExplicitGeneric(ident)) => tp.ident != *ident,
+        _ => true,
+    })
+}
+
 /// Definition of [GraphQL object][1] for code generation.
 ///
 /// [1]: https://spec.graphql.org/June2018/#sec-Objects
@@ -257,6 +306,19 @@ pub(crate) struct Definition<Operation: ?Sized> {
     /// [2]: https://spec.graphql.org/June2018/#sec-Interfaces
     pub(crate) interfaces: HashSet<syn::Type>,
 
+    /// Marker type of the part of a [GraphQL object][1] fields this
+    /// [`Definition`] should generate code for, instead of a whole
+    /// [GraphQL object][1].
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Objects
+    pub(crate) part: Option<syn::Ident>,
+
+    /// Marker types of the parts of this [GraphQL object][1] fields defined in
+    /// separate `impl` blocks.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Objects
+    pub(crate) parts: Vec<syn::Type>,
+
     /// [GraphQL operation][1] this [`Definition`] should generate code for.
     ///
     /// Either [GraphQL query][2] or [GraphQL subscription][3].
@@ -348,6 +410,7 @@ impl<Operation: ?Sized + 'static> Definition<Operation> {
             .map(|f| f.method_mark_tokens(coerce_result, scalar));
 
         let interface_tys = self.interfaces.iter();
+        let parts = self.parts.iter();
 
         quote! {
             #[automatically_derived]
@@ -356,6 +419,7 @@ impl<Operation: ?Sized + 'static> Definition<Operation> {
                 fn mark() {
                     #( #fields_marks )*
                     #( <#interface_tys as ::juniper::marker::IsOutputType<#scalar>>::mark(); )*
+                    #( <Self as ::juniper::macros::helper::ObjectPart<#scalar, #parts>>::mark(); )*
                 }
             }
         }
@@ -378,6 +442,28 @@ impl<Operation: ?Sized + 'static> Definition<Operation> {
         let fields = self.fields.iter().map(|f| &f.name);
         let interfaces = self.interfaces.iter();
 
+        let names = if self.parts.is_empty() {
+            quote! { &[#(#fields),*] }
+        } else {
+            // Nested `const`s cannot use generic parameters of the outer item,
+            // so the default `ScalarValue` is used here, as the parts' field
+            // names don't depend on it anyway.
+            let const_scalar = self.scalar.default_ty();
+            let parts = self.parts.iter();
+            quote! {{
+                const NAMES: &[::juniper::macros::reflect::Names] = &[
+                    &[#(#fields),*],
+                    #( <#ty as ::juniper::macros::helper::ObjectPart<
+                        #const_scalar, #parts,
+                    >>::NAMES, )*
+                ];
+                const LEN: usize = ::juniper::macros::reflect::names_len(NAMES);
+                const CONCAT: [::juniper::macros::reflect::Name; LEN] =
+                    ::juniper::macros::reflect::concat_names(NAMES);
+                &CONCAT
+            }}
+        };
+
         quote! {
             #[automatically_derived]
             impl#impl_generics ::juniper::macros::reflect::BaseType<#scalar>
@@ -418,7 +504,7 @@ impl<Operation: ?Sized + 'static> Definition<Operation> {
                 for #ty
                 #where_clause
             {
-                const NAMES: ::juniper::macros::reflect::Names = &[#(#fields),*];
+                const NAMES: ::juniper::macros::reflect::Names = #names;
             }
         }
     }
@@ -445,7 +531,12 @@ impl<Operation: ?Sized + 'static> Definition<Operation> {
         let fields_meta = self
             .fields
             .iter()
-            .map(|f| f.method_meta_tokens(extract_stream_type.then(|| scalar)));
+            .map(|f| f.method_meta_tokens(extract_stream_type.then_some(scalar)))
+            .chain(
+                self.fields
+                    .iter()
+                    .flat_map(field::Definition::method_aliases_meta_tokens),
+            );
 
         // Sorting is required to preserve/guarantee the order of interfaces registered in schema.
         let mut interface_tys: Vec<_> = self.interfaces.iter().collect();
@@ -461,6 +552,18 @@ impl<Operation: ?Sized + 'static> Definition<Operation> {
             }
         });
 
+        let parts_fields = (!self.parts.is_empty()).then(|| {
+            let parts = self.parts.iter();
+            quote! {
+                let mut fields = ::std::vec::Vec::from(fields);
+                #( fields.extend(
+                    <Self as ::juniper::macros::helper::ObjectPart<#scalar, #parts>>::meta_fields(
+                        info, registry,
+                    ),
+                ); )*
+            }
+        });
+
         quote! {
             #[automatically_derived]
             impl#impl_generics ::juniper::GraphQLType<#scalar> for #ty #where_clause
@@ -478,6 +581,7 @@ impl<Operation: ?Sized + 'static> Definition<Operation> {
                     let fields = [
                         #( #fields_meta, )*
                     ];
+                    #parts_fields
                     registry.build_object_type::<#ty>(info, &fields)
                         #description
                         #interfaces
@@ -495,6 +599,15 @@ struct Query;
 
 impl ToTokens for Definition<Query> {
     fn to_tokens(&self, into: &mut TokenStream) {
+        if self.part.is_some() {
+            self.impl_object_part_tokens().to_tokens(into);
+            self.impl_async_object_part_tokens().to_tokens(into);
+            self.impl_field_meta_tokens().to_tokens(into);
+            self.impl_field_tokens().to_tokens(into);
+            self.impl_async_field_tokens().to_tokens(into);
+            return;
+        }
+
         self.impl_graphql_object_tokens().to_tokens(into);
         self.impl_output_type_tokens().to_tokens(into);
         self.impl_graphql_type_tokens().to_tokens(into);
@@ -543,6 +656,12 @@ impl Definition<Query> {
         //    quote! { ::juniper::sa::assert_type_ne_all!(#( #interface_tys ),*); }
         //});
 
+        // Fields defined in the same `impl` block are checked to be unique by
+        // the macro itself already.
+        let all_fields_unique = (!self.parts.is_empty()).then(|| {
+            quote! { ::juniper::assert_unique_fields!(#const_scalar, #ty); }
+        });
+
         quote! {
             #[automatically_derived]
             impl#impl_generics ::juniper::marker::GraphQLObject<#scalar> for #ty #where_clause
@@ -552,6 +671,7 @@ impl Definition<Query> {
                     ::juniper::assert_implemented_for!(
                         #const_scalar, #ty, #(#const_interface_tys),*
                     );
+                    #all_fields_unique
                 }
             }
         }
@@ -574,23 +694,47 @@ impl Definition<Query> {
             .map(|field| {
                 let (name, ty) = (&field.name, field.ty.clone());
 
-                let arguments = field
-                    .arguments
-                    .as_ref()
-                    .iter()
-                    .flat_map(|vec| vec.iter())
-                    .filter_map(|arg| match arg {
+                let args = field.arguments.iter().flatten();
+                let arguments = if args
+                    .clone()
+                    .any(|arg| matches!(arg, field::MethodArgument::Args(_)))
+                {
+                    // Nested `const`s cannot use generic parameters of the outer
+                    // item, so the default `ScalarValue` is used here, as the
+                    // argument types don't depend on it anyway.
+                    let const_scalar = self.scalar.default_ty();
+                    let args = args.filter_map(|arg| match arg {
                         field::MethodArgument::Regular(arg) => {
                             let (name, ty) = (&arg.name, &arg.ty);
-                            Some(quote! {(
+                            Some(quote! {&[(
                                 #name,
-                                <#ty as ::juniper::macros::reflect::BaseType<#scalar>>::NAME,
-                                <#ty as ::juniper::macros::reflect::WrappedType<#scalar>>::VALUE,
-                            )})
+                                <#ty as ::juniper::macros::reflect::BaseType<#const_scalar>>::NAME,
+                                <#ty as ::juniper::macros::reflect::WrappedType<#const_scalar>>::VALUE,
+                            )]})
                         }
+                        field::MethodArgument::Args(ty) => Some(quote! {
+                            <#ty as ::juniper::macros::reflect::InputFields<#const_scalar>>::FIELDS
+                        }),
                         field::MethodArgument::Executor | field::MethodArgument::Context(_) => None,
-                    })
-                    .collect::<Vec<_>>();
+                    });
+                    quote! {{
+                        const ARGS: &[::juniper::macros::reflect::Arguments] = &[#(#args,)*];
+                        const LEN: usize = ::juniper::macros::reflect::arguments_len(ARGS);
+                        const CONCAT: [::juniper::macros::reflect::Argument; LEN] =
+                            ::juniper::macros::reflect::concat_arguments(ARGS);
+                        &CONCAT
+                    }}
+                } else {
+                    let args = args.filter_map(field::MethodArgument::as_regular).map(|arg| {
+                        let (name, ty) = (&arg.name, &arg.ty);
+                        quote! {(
+                            #name,
+                            <#ty as ::juniper::macros::reflect::BaseType<#scalar>>::NAME,
+                            <#ty as ::juniper::macros::reflect::WrappedType<#scalar>>::VALUE,
+                        )}
+                    });
+                    quote! { &[#(#args,)*] }
+                };
 
                 quote! {
                     #[allow(deprecated, non_snake_case)]
@@ -611,7 +755,7 @@ impl Definition<Query> {
                             ::juniper::macros::reflect::Name,
                             ::juniper::macros::reflect::Type,
                             ::juniper::macros::reflect::WrappedValue,
-                        )] = &[#(#arguments,)*];
+                        )] = #arguments;
                     }
                 }
             })
@@ -668,6 +812,14 @@ impl Definition<Query> {
                     }
                 };
 
+                let skip = field.skip_if.as_ref().map(|pred| {
+                    quote! {
+                        if #pred(self, executor.context()) {
+                            return Ok(::juniper::Value::null());
+                        }
+                    }
+                });
+
                 quote! {
                     #[allow(deprecated, non_snake_case)]
                     #[automatically_derived]
@@ -683,6 +835,7 @@ impl Definition<Query> {
                             args: &::juniper::Arguments<#scalar>,
                             executor: &::juniper::Executor<Self::Context, #scalar>,
                         ) -> ::juniper::ExecutionResult<#scalar> {
+                            #skip
                             #resolve
                         }
                     }
@@ -729,6 +882,44 @@ impl Definition<Query> {
 
                 let resolving_code = gen::async_resolving_code(Some(&res_ty));
 
+                let skip = field.skip_if.as_ref().map(|pred| {
+                    quote! {
+                        if #pred(self, executor.context()) {
+                            return Box::pin(::juniper::futures::future::ready(
+                                Ok(::juniper::Value::null()),
+                            ));
+                        }
+                    }
+                });
+
+                let mut resolve = quote! {
+                    let fut = #res;
+                    #resolving_code
+                };
+                if let Some(timeout) = field.timeout {
+                    let timeout = gen::duration(timeout);
+                    resolve = quote! {
+                        ::juniper::macros::helper::with_timeout({ #resolve }, #timeout)
+                    };
+                }
+                if let Some(retry) = field.retry {
+                    let times = retry.times;
+                    let backoff = retry
+                        .backoff
+                        .map(|b| {
+                            let b = gen::duration(b);
+                            quote! { Some(#b) }
+                        })
+                        .unwrap_or_else(|| quote! { None });
+                    resolve = quote! {
+                        ::juniper::macros::helper::with_retry(
+                            move || { #resolve },
+                            #times,
+                            #backoff,
+                        )
+                    };
+                }
+
                 quote! {
                     #[allow(deprecated, non_snake_case)]
                     #[automatically_derived]
@@ -744,8 +935,8 @@ impl Definition<Query> {
                             args: &'b ::juniper::Arguments<#scalar>,
                             executor: &'b ::juniper::Executor<Self::Context, #scalar>,
                         ) -> ::juniper::BoxFuture<'b, ::juniper::ExecutionResult<#scalar>> {
-                            let fut = #res;
-                            #resolving_code
+                            #skip
+                            #resolve
                         }
                     }
                 }
@@ -771,8 +962,9 @@ impl Definition<Query> {
 
         let fields_resolvers = self.fields.iter().map(|f| {
             let name = &f.name;
+            let aliases = f.aliases.iter().map(|a| &a.name);
             quote! {
-                #name => {
+                #name #( | #aliases )* => {
                     ::juniper::macros::reflect::Field::<
                         #scalar,
                         { ::juniper::macros::reflect::fnv1a128(#name) }
@@ -781,9 +973,30 @@ impl Definition<Query> {
             }
         });
 
+        let parts_resolvers = self.parts.iter().map(|part| {
+            quote! {
+                if <Self as ::juniper::macros::helper::ObjectPart<#scalar, #part>>::NAMES
+                    .contains(&field)
+                {
+                    return <Self as ::juniper::macros::helper::ObjectPart<#scalar, #part>>
+                        ::resolve_field(self, info, field, args, executor);
+                }
+            }
+        });
+
         let no_field_err =
             field::Definition::method_resolve_field_err_no_field_tokens(scalar, &ty_name);
 
+        // Generic types cannot be guaranteed to be `'static`, as required by
+        // `Any`.
+        let as_any = self.generics.params.is_empty().then(|| {
+            quote! {
+                fn as_any(&self) -> Option<&(dyn ::std::any::Any + Sync)> {
+                    Some(self)
+                }
+            }
+        });
+
         quote! {
             #[allow(deprecated)]
             #[automatically_derived]
@@ -805,7 +1018,10 @@ impl Definition<Query> {
                 ) -> ::juniper::ExecutionResult<#scalar> {
                     match field {
                         #( #fields_resolvers )*
-                        _ => #no_field_err,
+                        _ => {
+                            #( #parts_resolvers )*
+                            #no_field_err
+                        }
                     }
                 }
 
@@ -816,6 +1032,8 @@ impl Definition<Query> {
                 ) -> String {
                     #name.to_string()
                 }
+
+                #as_any
             }
         }
     }
@@ -835,8 +1053,9 @@ impl Definition<Query> {
 
         let fields_resolvers = self.fields.iter().map(|f| {
             let name = &f.name;
+            let aliases = f.aliases.iter().map(|a| &a.name);
             quote! {
-                #name => {
+                #name #( | #aliases )* => {
                     ::juniper::macros::reflect::AsyncField::<
                         #scalar,
                         { ::juniper::macros::reflect::fnv1a128(#name) }
@@ -845,6 +1064,17 @@ impl Definition<Query> {
             }
         });
 
+        let parts_resolvers = self.parts.iter().map(|part| {
+            quote! {
+                if <Self as ::juniper::macros::helper::ObjectPart<#scalar, #part>>::NAMES
+                    .contains(&field)
+                {
+                    return <Self as ::juniper::macros::helper::AsyncObjectPart<#scalar, #part>>
+                        ::resolve_field_async(self, info, field, args, executor);
+                }
+            }
+        });
+
         let no_field_err =
             field::Definition::method_resolve_field_err_no_field_tokens(scalar, &ty_name);
 
@@ -862,13 +1092,151 @@ impl Definition<Query> {
                 ) -> ::juniper::BoxFuture<'b, ::juniper::ExecutionResult<#scalar>> {
                     match field {
                         #( #fields_resolvers )*
-                        _ => Box::pin(async move { #no_field_err }),
+                        _ => {
+                            #( #parts_resolvers )*
+                            Box::pin(async move { #no_field_err })
+                        }
                     }
                 }
             }
         }
     }
 
+    /// Returns generated code declaring the marker type of this part of a
+    /// [GraphQL object][1] fields, and implementing [`ObjectPart`] trait for
+    /// it.
+    ///
+    /// [`ObjectPart`]: juniper::macros::helper::ObjectPart
+    /// [1]: https://spec.graphql.org/June2018/#sec-Objects
+    #[must_use]
+    fn impl_object_part_tokens(&self) -> Option<TokenStream> {
+        let part = self.part.as_ref()?;
+        let scalar = &self.scalar;
+
+        let (impl_generics, where_clause) = self.impl_generics(false);
+        let ty = &self.ty;
+        let ty_name = ty.to_token_stream().to_string();
+
+        let doc = format!(
+            "Marker of the part of the `{}` GraphQL object fields.",
+            self.name,
+        );
+
+        let names = self.fields.iter().map(|f| &f.name);
+        let fields_marks = self
+            .fields
+            .iter()
+            .map(|f| f.method_mark_tokens(false, scalar));
+        let fields_meta = self.fields.iter().map(|f| f.method_meta_tokens(None));
+        let fields_resolvers = self.fields.iter().map(|f| {
+            let name = &f.name;
+            quote! {
+                #name => {
+                    ::juniper::macros::reflect::Field::<
+                        #scalar,
+                        { ::juniper::macros::reflect::fnv1a128(#name) }
+                    >::call(self, info, args, executor)
+                }
+            }
+        });
+
+        let no_field_err =
+            field::Definition::method_resolve_field_err_no_field_tokens(scalar, &ty_name);
+
+        Some(quote! {
+            #[doc = #doc]
+            #[derive(Clone, Copy, Debug, Default)]
+            pub struct #part;
+
+            #[allow(deprecated)]
+            #[automatically_derived]
+            impl#impl_generics ::juniper::macros::helper::ObjectPart<#scalar, #part> for #ty
+                #where_clause
+            {
+                const NAMES: ::juniper::macros::reflect::Names = &[#(#names),*];
+
+                fn mark() {
+                    #( #fields_marks )*
+                }
+
+                fn meta_fields<'r>(
+                    info: &Self::TypeInfo,
+                    registry: &mut ::juniper::Registry<'r, #scalar>
+                ) -> ::std::vec::Vec<::juniper::meta::Field<'r, #scalar>>
+                where #scalar: 'r,
+                {
+                    ::std::vec![
+                        #( #fields_meta, )*
+                    ]
+                }
+
+                fn resolve_field(
+                    &self,
+                    info: &Self::TypeInfo,
+                    field: &str,
+                    args: &::juniper::Arguments<#scalar>,
+                    executor: &::juniper::Executor<Self::Context, #scalar>,
+                ) -> ::juniper::ExecutionResult<#scalar> {
+                    match field {
+                        #( #fields_resolvers )*
+                        _ => #no_field_err,
+                    }
+                }
+            }
+        })
+    }
+
+    /// Returns generated code implementing [`AsyncObjectPart`] trait for this
+    /// part of a [GraphQL object][1] fields.
+    ///
+    /// [`AsyncObjectPart`]: juniper::macros::helper::AsyncObjectPart
+    /// [1]: https://spec.graphql.org/June2018/#sec-Objects
+    #[must_use]
+    fn impl_async_object_part_tokens(&self) -> Option<TokenStream> {
+        let part = self.part.as_ref()?;
+        let scalar = &self.scalar;
+
+        let (impl_generics, where_clause) = self.impl_generics(true);
+        let ty = &self.ty;
+        let ty_name = ty.to_token_stream().to_string();
+
+        let fields_resolvers = self.fields.iter().map(|f| {
+            let name = &f.name;
+            quote! {
+                #name => {
+                    ::juniper::macros::reflect::AsyncField::<
+                        #scalar,
+                        { ::juniper::macros::reflect::fnv1a128(#name) }
+                    >::call(self, info, args, executor)
+                }
+            }
+        });
+
+        let no_field_err =
+            field::Definition::method_resolve_field_err_no_field_tokens(scalar, &ty_name);
+
+        Some(quote! {
+            #[allow(deprecated, non_snake_case)]
+            #[automatically_derived]
+            impl#impl_generics ::juniper::macros::helper::AsyncObjectPart<#scalar, #part> for #ty
+                #where_clause
+            {
+                fn resolve_field_async<'b>(
+                    &'b self,
+                    info: &'b Self::TypeInfo,
+                    field: &'b str,
+                    args: &'b ::juniper::Arguments<#scalar>,
+                    executor: &'b ::juniper::Executor<Self::Context, #scalar>,
+                ) -> ::juniper::BoxFuture<'b, ::juniper::ExecutionResult<#scalar>> {
+                    match field {
+                        #( #fields_resolvers )*
+                        _ => Box::pin(async move { #no_field_err }),
+                    }
+                }
+            }
+        })
+    }
+
     /// Returns generated code implementing [`AsDynGraphQLValue`] trait for this
     /// [GraphQL object][1].
     ///
@@ -23,6 +23,7 @@ impl ToTokens for Definition<Subscription> {
         self.impl_graphql_value_tokens().to_tokens(into);
         self.impl_graphql_subscription_value_tokens()
             .to_tokens(into);
+        self.impl_reflection_traits_tokens().to_tokens(into);
     }
 }
 
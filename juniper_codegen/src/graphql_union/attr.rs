@@ -145,6 +145,13 @@ fn parse_variant_from_trait_method(
         return None;
     }
 
+    let cfg = attr.cfg.map(SpanContainer::into_inner);
+    if let Some(pred) = &cfg {
+        // The method is excluded along with the variant, as it usually refers
+        // to a type being conditionally compiled too.
+        method.attrs.push(parse_quote! { #[cfg(#pred)] });
+    }
+
     let method_span = method.sig.span();
     let method_ident = &method.sig.ident;
 
@@ -216,5 +223,6 @@ fn parse_variant_from_trait_method(
         resolver_code,
         resolver_check,
         context: method_context_ty,
+        cfg,
     })
 }
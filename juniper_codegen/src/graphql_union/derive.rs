@@ -168,6 +168,7 @@ fn parse_variant_from_enum_variant(
         resolver_code,
         resolver_check,
         context: None,
+        cfg: attr.cfg.map(SpanContainer::into_inner),
     })
 }
 
@@ -208,6 +208,15 @@ struct VariantAttr {
     ///
     /// [1]: https://spec.graphql.org/June2018/#sec-Unions
     external_resolver: Option<SpanContainer<syn::ExprPath>>,
+
+    /// Explicitly specified [configuration predicate][0] conditionally
+    /// including this variant into [GraphQL union][1].
+    ///
+    /// If absent, then the variant is always included.
+    ///
+    /// [0]: https://doc.rust-lang.org/reference/conditional-compilation.html
+    /// [1]: https://spec.graphql.org/June2018/#sec-Unions
+    cfg: Option<SpanContainer<TokenStream>>,
 }
 
 impl Parse for VariantAttr {
@@ -227,6 +236,20 @@ impl Parse for VariantAttr {
                         .replace(SpanContainer::new(ident.span(), Some(rslvr.span()), rslvr))
                         .none_or_else(|_| err::dup_arg(&ident))?
                 }
+                "cfg" => {
+                    let inner;
+                    let _ = syn::parenthesized!(inner in input);
+                    let pred = inner.parse::<TokenStream>()?;
+                    if pred.is_empty() {
+                        return Err(syn::Error::new(
+                            ident.span(),
+                            "expected configuration predicate, e.g. `cfg(feature = \"beta\")`",
+                        ));
+                    }
+                    out.cfg
+                        .replace(SpanContainer::new(ident.span(), Some(pred.span()), pred))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
                 name => {
                     return Err(err::unknown_arg(&ident, name));
                 }
@@ -244,6 +267,7 @@ impl VariantAttr {
         Ok(Self {
             ignore: try_merge_opt!(ignore: self, another),
             external_resolver: try_merge_opt!(external_resolver: self, another),
+            cfg: try_merge_opt!(cfg: self, another),
         })
     }
 
@@ -417,18 +441,31 @@ impl Definition {
 
         let (impl_generics, ty_full, where_clause) = self.impl_generics(false);
 
-        let variant_tys: Vec<_> = self.variants.iter().map(|var| &var.ty).collect();
-        let all_variants_unique = (variant_tys.len() > 1).then(|| {
-            quote! { ::juniper::sa::assert_type_ne_all!(#( #variant_tys ),*); }
+        // Conditionally included variants are checked for uniqueness at the
+        // macro expansion time only.
+        let unconditional_tys: Vec<_> = self
+            .variants
+            .iter()
+            .filter(|var| var.cfg.is_none())
+            .map(|var| &var.ty)
+            .collect();
+        let all_variants_unique = (unconditional_tys.len() > 1).then(|| {
+            quote! { ::juniper::sa::assert_type_ne_all!(#( #unconditional_tys ),*); }
         });
 
+        let variant_tys = self.variants.iter().map(|var| &var.ty);
+        let variant_cfgs = self.variants.iter().map(VariantDefinition::cfg_attr);
+
         quote! {
             #[automatically_derived]
             impl#impl_generics ::juniper::marker::GraphQLUnion<#scalar> for #ty_full #where_clause
             {
                 fn mark() {
                     #all_variants_unique
-                    #( <#variant_tys as ::juniper::marker::GraphQLObject<#scalar>>::mark(); )*
+                    #(
+                        #variant_cfgs
+                        <#variant_tys as ::juniper::marker::GraphQLObject<#scalar>>::mark();
+                    )*
                 }
             }
         }
@@ -446,13 +483,17 @@ impl Definition {
         let (impl_generics, ty_full, where_clause) = self.impl_generics(false);
 
         let variant_tys = self.variants.iter().map(|var| &var.ty);
+        let variant_cfgs = self.variants.iter().map(VariantDefinition::cfg_attr);
 
         quote! {
             #[automatically_derived]
             impl#impl_generics ::juniper::marker::IsOutputType<#scalar> for #ty_full #where_clause
             {
                 fn mark() {
-                    #( <#variant_tys as ::juniper::marker::IsOutputType<#scalar>>::mark(); )*
+                    #(
+                        #variant_cfgs
+                        <#variant_tys as ::juniper::marker::IsOutputType<#scalar>>::mark();
+                    )*
                 }
             }
         }
@@ -476,6 +517,7 @@ impl Definition {
             .map(|desc| quote! { .description(#desc) });
 
         let variant_tys = self.variants.iter().map(|var| &var.ty);
+        let variant_cfgs = self.variants.iter().map(VariantDefinition::cfg_attr);
 
         quote! {
             #[automatically_derived]
@@ -492,7 +534,7 @@ impl Definition {
                 where #scalar: 'r,
                 {
                     let types = [
-                        #( registry.get_type::<#variant_tys>(info), )*
+                        #( #variant_cfgs registry.get_type::<#variant_tys>(info), )*
                     ];
                     registry.build_union_type::<#ty_full>(info, &types)
                         #description
@@ -623,6 +665,7 @@ impl Definition {
         let scalar = &self.scalar;
         let name = &self.name;
         let variants = self.variants.iter().map(|var| &var.ty);
+        let variant_cfgs = self.variants.iter().map(VariantDefinition::cfg_attr);
         let (impl_generics, ty, where_clause) = self.impl_generics(false);
 
         quote! {
@@ -641,7 +684,10 @@ impl Definition {
             {
                 const NAMES: ::juniper::macros::reflect::Types = &[
                     <Self as ::juniper::macros::reflect::BaseType<#scalar>>::NAME,
-                    #(<#variants as ::juniper::macros::reflect::BaseType<#scalar>>::NAME),*
+                    #(
+                        #variant_cfgs
+                        <#variants as ::juniper::macros::reflect::BaseType<#scalar>>::NAME
+                    ),*
                 ];
             }
 
@@ -685,9 +731,23 @@ struct VariantDefinition {
     /// [`Context`]: juniper::Context
     /// [1]: https://spec.graphql.org/June2018/#sec-Unions
     context: Option<syn::Type>,
+
+    /// [Configuration predicate][0] conditionally including this
+    /// [GraphQL union][1] variant, if any.
+    ///
+    /// [0]: https://doc.rust-lang.org/reference/conditional-compilation.html
+    /// [1]: https://spec.graphql.org/June2018/#sec-Unions
+    cfg: Option<TokenStream>,
 }
 
 impl VariantDefinition {
+    /// Returns the `#[cfg]` attribute conditionally including the generated
+    /// code of this [`VariantDefinition`], if any.
+    #[must_use]
+    fn cfg_attr(&self) -> Option<TokenStream> {
+        self.cfg.as_ref().map(|pred| quote! { #[cfg(#pred)] })
+    }
+
     /// Returns generated code for the [`GraphQLValue::concrete_type_name`][0]
     /// method, which returns name of the underlying GraphQL type contained in
     /// this [`VariantDefinition`].
@@ -697,8 +757,10 @@ impl VariantDefinition {
     fn method_concrete_type_name_tokens(&self, scalar: &scalar::Type) -> TokenStream {
         let ty = &self.ty;
         let check = &self.resolver_check;
+        let cfg = self.cfg_attr();
 
         quote! {
+            #cfg
             if #check {
                 return <#ty as ::juniper::GraphQLType<#scalar>>::name(info)
                     .unwrap()
@@ -718,8 +780,10 @@ impl VariantDefinition {
         let ty_name = ty.to_token_stream().to_string();
         let expr = &self.resolver_code;
         let resolving_code = gen::sync_resolving_code();
+        let cfg = self.cfg_attr();
 
         quote! {
+            #cfg
             if type_name == <#ty as ::juniper::GraphQLType<#scalar>>::name(info)
                 .ok_or_else(|| ::juniper::macros::helper::err_unnamed_type(#ty_name))?
             {
@@ -741,8 +805,10 @@ impl VariantDefinition {
         let ty_name = ty.to_token_stream().to_string();
         let expr = &self.resolver_code;
         let resolving_code = gen::async_resolving_code(None);
+        let cfg = self.cfg_attr();
 
         quote! {
+            #cfg
             match <#ty as ::juniper::GraphQLType<#scalar>>::name(info) {
                 Some(name) => {
                     if type_name == name {
@@ -793,6 +859,7 @@ fn emerge_union_variants_from_attr(
                 resolver_code,
                 resolver_check,
                 context: None,
+                cfg: None,
             })
         }
     }
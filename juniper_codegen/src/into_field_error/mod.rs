@@ -0,0 +1,446 @@
+//! Code generation for `#[derive(IntoFieldError)]` macro.
+
+use std::collections::HashSet;
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::{
+    ext::IdentExt as _,
+    parse::{Parse, ParseStream},
+    parse_quote,
+    spanned::Spanned as _,
+    token,
+};
+
+use crate::{
+    common::parse::{
+        attr::{err, OptionExt as _},
+        ParseBufferExt as _,
+    },
+    result::GraphQLScope,
+    util::{filter_attrs, span_container::SpanContainer},
+};
+
+/// [`GraphQLScope`] of errors for `#[derive(IntoFieldError)]` macro.
+const ERR: GraphQLScope = GraphQLScope::IntoFieldErrorDerive;
+
+/// Expands `#[derive(IntoFieldError)]` macro into generated code.
+pub fn expand_derive(input: TokenStream) -> syn::Result<TokenStream> {
+    let ast = syn::parse2::<syn::DeriveInput>(input)?;
+
+    let data_enum = match ast.data {
+        syn::Data::Enum(e) => e,
+        _ => return Err(ERR.custom_error(ast.span(), "can only be derived for enums")),
+    };
+
+    let variants = data_enum
+        .variants
+        .into_iter()
+        .map(|var| {
+            let attr = VariantAttr::from_attrs("graphql", &var.attrs)?;
+            Variant::new(var, attr)
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(Definition {
+        ident: ast.ident,
+        generics: ast.generics,
+        variants,
+    }
+    .into_token_stream())
+}
+
+/// Available arguments behind `#[graphql]` attribute placed on an enum
+/// variant, when generating code for `#[derive(IntoFieldError)]`.
+#[derive(Debug, Default)]
+struct VariantAttr {
+    /// Explicitly specified format string of the [`FieldError::message()`],
+    /// referring the variant fields by their names or indices.
+    ///
+    /// If [`None`], then the [`Display`] implementation of the enum is used.
+    ///
+    /// [`Display`]: std::fmt::Display
+    /// [`FieldError::message()`]: juniper::FieldError::message
+    message: Option<SpanContainer<syn::LitStr>>,
+
+    /// Explicitly specified `code` of the [`FieldError::extensions()`].
+    ///
+    /// [`FieldError::extensions()`]: juniper::FieldError::extensions
+    code: Option<SpanContainer<syn::LitStr>>,
+
+    /// Explicitly specified additional [`FieldError::extensions()`], as
+    /// expressions referring the variant fields by their names (or `_0`,
+    /// `_1`, ... for unnamed ones).
+    ///
+    /// [`FieldError::extensions()`]: juniper::FieldError::extensions
+    extensions: Vec<(syn::Ident, syn::Expr)>,
+
+    /// Explicitly specified marker of the variant delegating to the
+    /// [`IntoFieldError`] implementation of its single field.
+    ///
+    /// [`IntoFieldError`]: juniper::IntoFieldError
+    transparent: Option<SpanContainer<syn::Ident>>,
+}
+
+impl Parse for VariantAttr {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let mut out = Self::default();
+        while !input.is_empty() {
+            let ident = input.parse_any_ident()?;
+            match ident.to_string().as_str() {
+                "message" => {
+                    input.parse::<token::Eq>()?;
+                    let lit = input.parse::<syn::LitStr>()?;
+                    out.message
+                        .replace(SpanContainer::new(ident.span(), Some(lit.span()), lit))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
+                "code" => {
+                    input.parse::<token::Eq>()?;
+                    let lit = input.parse::<syn::LitStr>()?;
+                    out.code
+                        .replace(SpanContainer::new(ident.span(), Some(lit.span()), lit))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
+                "extensions" => {
+                    let content;
+                    syn::parenthesized!(content in input);
+                    while !content.is_empty() {
+                        let key = content.parse_any_ident()?;
+                        content.parse::<token::Eq>()?;
+                        let expr = content.parse::<syn::Expr>()?;
+                        out.extensions.push((key, expr));
+                        content.try_parse::<token::Comma>()?;
+                    }
+                }
+                "transparent" => out
+                    .transparent
+                    .replace(SpanContainer::new(ident.span(), None, ident.clone()))
+                    .none_or_else(|_| err::dup_arg(&ident))?,
+                name => {
+                    return Err(err::unknown_arg(&ident, name));
+                }
+            }
+            input.try_parse::<token::Comma>()?;
+        }
+        Ok(out)
+    }
+}
+
+impl VariantAttr {
+    /// Tries to merge two [`VariantAttr`]s into a single one, reporting about
+    /// duplicates, if any.
+    fn try_merge(mut self, mut another: Self) -> syn::Result<Self> {
+        self.extensions.append(&mut another.extensions);
+        Ok(Self {
+            message: try_merge_opt!(message: self, another),
+            code: try_merge_opt!(code: self, another),
+            extensions: self.extensions,
+            transparent: try_merge_opt!(transparent: self, another),
+        })
+    }
+
+    /// Parses [`VariantAttr`] from the given multiple `name`d
+    /// [`syn::Attribute`]s placed on an enum variant.
+    fn from_attrs(name: &str, attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        filter_attrs(name, attrs)
+            .map(|attr| attr.parse_args())
+            .try_fold(Self::default(), |prev, curr| prev.try_merge(curr?))
+    }
+}
+
+/// Representation of an enum variant for code generation.
+struct Variant {
+    /// [`syn::Ident`] of this variant.
+    ident: syn::Ident,
+
+    /// Members of this variant's fields, along with the names of their
+    /// bindings.
+    fields: Vec<(syn::Member, syn::Ident)>,
+
+    /// Conversion of this variant into a [`FieldError`].
+    ///
+    /// [`FieldError`]: juniper::FieldError
+    kind: VariantKind,
+}
+
+/// Possible conversions of a [`Variant`] into a [`FieldError`].
+///
+/// [`FieldError`]: juniper::FieldError
+enum VariantKind {
+    /// Delegating to the [`IntoFieldError`] implementation of the single
+    /// field.
+    ///
+    /// [`IntoFieldError`]: juniper::IntoFieldError
+    Transparent,
+
+    /// Constructing a new [`FieldError`].
+    ///
+    /// [`FieldError`]: juniper::FieldError
+    Error {
+        /// Generated code of the [`FieldError::message()`].
+        ///
+        /// [`FieldError::message()`]: juniper::FieldError::message
+        message: TokenStream,
+
+        /// Keys of the [`FieldError::extensions()`] along with the generated
+        /// code of their values.
+        ///
+        /// [`FieldError::extensions()`]: juniper::FieldError::extensions
+        extensions: Vec<(String, TokenStream)>,
+    },
+}
+
+impl Variant {
+    /// Validates the given [`syn::Variant`] with its parsed [`VariantAttr`]
+    /// and prepares it for code generation.
+    fn new(var: syn::Variant, attr: VariantAttr) -> syn::Result<Self> {
+        let fields = var
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(i, f)| match &f.ident {
+                Some(ident) => (ident.clone().into(), ident.clone()),
+                None => (
+                    syn::Index {
+                        index: i as u32,
+                        span: f.span(),
+                    }
+                    .into(),
+                    format_ident!("_{}", i),
+                ),
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(transparent) = &attr.transparent {
+            if attr.message.is_some() || attr.code.is_some() || !attr.extensions.is_empty() {
+                return Err(ERR.custom_error(
+                    transparent.span_ident(),
+                    "variant marked with `#[graphql(transparent)]` cannot specify its \
+                     `message`, `code` or `extensions`",
+                ));
+            }
+            if fields.len() != 1 {
+                return Err(ERR.custom_error(
+                    var.ident.span(),
+                    "variant marked with `#[graphql(transparent)]` must have exactly one field",
+                ));
+            }
+            return Ok(Self {
+                ident: var.ident,
+                fields,
+                kind: VariantKind::Transparent,
+            });
+        }
+
+        let message = match &attr.message {
+            Some(msg) => format_message(msg, &fields)?,
+            None => quote_spanned! { var.ident.span() =>
+                ::std::string::ToString::to_string(&self)
+            },
+        };
+
+        let mut keys = HashSet::new();
+        let mut extensions = vec![];
+        if let Some(code) = &attr.code {
+            keys.insert("code".to_owned());
+            extensions.push(("code".to_owned(), code.to_token_stream()));
+        }
+        for (key, expr) in &attr.extensions {
+            let name = key.unraw().to_string();
+            if !keys.insert(name.clone()) {
+                return Err(err::dup_arg(key));
+            }
+            extensions.push((name, expr.to_token_stream()));
+        }
+
+        Ok(Self {
+            ident: var.ident,
+            fields,
+            kind: VariantKind::Error {
+                message,
+                extensions,
+            },
+        })
+    }
+
+    /// Returns generated code of the match arm converting this [`Variant`]
+    /// into a [`FieldError`].
+    ///
+    /// [`FieldError`]: juniper::FieldError
+    fn match_arm(&self, scalar: &syn::Ident) -> TokenStream {
+        let ident = &self.ident;
+
+        match &self.kind {
+            VariantKind::Transparent => {
+                let (member, _) = &self.fields[0];
+                quote! {
+                    Self::#ident { #member: __inner } => {
+                        ::juniper::IntoFieldError::<#scalar>::into_field_error(__inner)
+                    }
+                }
+            }
+            VariantKind::Error {
+                message,
+                extensions,
+            } => {
+                let fields = self
+                    .fields
+                    .iter()
+                    .map(|(member, binding)| quote! { #member: ref #binding });
+                let extensions = if extensions.is_empty() {
+                    quote! { ::juniper::Value::null() }
+                } else {
+                    let len = extensions.len();
+                    let fields = extensions.iter().map(|(key, value)| {
+                        quote! {
+                            __extensions.add_field(#key, ::juniper::Value::<#scalar>::from(#value));
+                        }
+                    });
+                    quote! {{
+                        let mut __extensions = ::juniper::Object::with_capacity(#len);
+                        #( #fields )*
+                        ::juniper::Value::object(__extensions)
+                    }}
+                };
+
+                quote! {
+                    #[allow(unused_variables)]
+                    Self::#ident { #( #fields, )* } => {
+                        ::juniper::FieldError::new(#message, #extensions)
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns generated code formatting the given `message`, which refers the
+/// variant `fields` either by their names or by their indices.
+fn format_message(
+    message: &syn::LitStr,
+    fields: &[(syn::Member, syn::Ident)],
+) -> syn::Result<TokenStream> {
+    let value = message.value();
+    let mut out = String::with_capacity(value.len());
+    let mut args = Vec::<(syn::Ident, &syn::Ident)>::new();
+
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        out.push(c);
+        if c != '{' {
+            continue;
+        }
+        if chars.peek() == Some(&'{') {
+            out.push(chars.next().unwrap());
+            continue;
+        }
+
+        let mut name = String::new();
+        while let Some(&c) = chars.peek() {
+            if c == ':' || c == '}' {
+                break;
+            }
+            name.push(c);
+            chars.next();
+        }
+        if name.is_empty() {
+            return Err(ERR.custom_error(
+                message.span(),
+                "`message` must refer the variant fields by their names or indices explicitly",
+            ));
+        }
+        let binding = fields
+            .iter()
+            .find(|(member, _)| match member {
+                syn::Member::Named(ident) => ident.unraw() == name,
+                syn::Member::Unnamed(index) => index.index.to_string() == name,
+            })
+            .map(|(_, binding)| binding)
+            .ok_or_else(|| {
+                ERR.custom_error(
+                    message.span(),
+                    format!(
+                        "`message` refers to `{{{}}}`, which is not a field of the variant",
+                        name,
+                    ),
+                )
+            })?;
+
+        // Raw identifiers cannot be used as named arguments of `format!()`,
+        // so the arguments are named after the bindings with a prefix.
+        let arg = format_ident!("__{}", binding.unraw());
+        out.push_str(&arg.to_string());
+        if !args.iter().any(|(a, _)| *a == arg) {
+            args.push((arg, binding));
+        }
+    }
+
+    let fmt = syn::LitStr::new(&out, message.span());
+    let args = args.iter().map(|(arg, binding)| quote! { #arg = #binding });
+    Ok(quote! {
+        ::std::format!(#fmt, #( #args ),*)
+    })
+}
+
+/// Definition of an [`IntoFieldError`] implementation for code generation.
+///
+/// [`IntoFieldError`]: juniper::IntoFieldError
+struct Definition {
+    /// [`syn::Ident`] of the enum to implement [`IntoFieldError`] for.
+    ///
+    /// [`IntoFieldError`]: juniper::IntoFieldError
+    ident: syn::Ident,
+
+    /// [`syn::Generics`] of the enum to implement [`IntoFieldError`] for.
+    ///
+    /// [`IntoFieldError`]: juniper::IntoFieldError
+    generics: syn::Generics,
+
+    /// [`Variant`]s of the enum to implement [`IntoFieldError`] for.
+    ///
+    /// [`IntoFieldError`]: juniper::IntoFieldError
+    variants: Vec<Variant>,
+}
+
+impl ToTokens for Definition {
+    fn to_tokens(&self, into: &mut TokenStream) {
+        self.impl_into_field_error_tokens().to_tokens(into);
+    }
+}
+
+impl Definition {
+    /// Returns generated code implementing [`IntoFieldError`] for any
+    /// [`ScalarValue`].
+    ///
+    /// [`IntoFieldError`]: juniper::IntoFieldError
+    /// [`ScalarValue`]: juniper::ScalarValue
+    fn impl_into_field_error_tokens(&self) -> TokenStream {
+        let ident = &self.ident;
+        let scalar: syn::Ident = parse_quote! { __S };
+
+        let mut generics = self.generics.clone();
+        generics.params.push(parse_quote! { #scalar });
+        generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote! { #scalar: ::juniper::ScalarValue });
+        let (impl_gens, _, where_clause) = generics.split_for_impl();
+        let (_, ty_gens, _) = self.generics.split_for_impl();
+
+        let arms = self.variants.iter().map(|v| v.match_arm(&scalar));
+
+        quote! {
+            #[automatically_derived]
+            impl#impl_gens ::juniper::IntoFieldError<#scalar> for #ident#ty_gens
+                #where_clause
+            {
+                fn into_field_error(self) -> ::juniper::FieldError<#scalar> {
+                    match self {
+                        #( #arms )*
+                    }
+                }
+            }
+        }
+    }
+}
@@ -104,11 +104,14 @@ mod derive_enum;
 mod derive_input_object;
 
 mod common;
+mod graphql_crud;
+mod graphql_directive;
 mod graphql_interface;
 mod graphql_object;
 mod graphql_scalar;
 mod graphql_subscription;
 mod graphql_union;
+mod into_field_error;
 mod scalar_value;
 
 use proc_macro::TokenStream;
@@ -450,6 +453,230 @@ pub fn derive_scalar(input: TokenStream) -> TokenStream {
         .into()
 }
 
+/// `#[derive(GraphQLDirective)]` macro for deriving a custom
+/// [executable directive][0] transforming resolved values of the
+/// [GraphQL fields][1] it's placed on in a query.
+///
+/// Fields of the struct represent the directive arguments and support the same
+/// `#[graphql(...)]` attribute arguments as the arguments of
+/// [`#[graphql_object]`][2] fields (except `default_with_context`). The
+/// transformation is performed by the `Self::transform` function, which may
+/// be replaced via `#[graphql(transform_with = <path>)]` attribute.
+///
+/// The derived directive should be registered in a schema with
+/// `RootNode::with_directive()` method.
+///
+/// ```rust
+/// # use juniper::{
+/// #     execute_sync, graphql_object, graphql_value, graphql_vars,
+/// #     EmptyMutation, EmptySubscription, GraphQLDirective, RootNode,
+/// #     ScalarValue, Value,
+/// # };
+/// #
+/// /// Converts a string into the upper case.
+/// #[derive(GraphQLDirective)]
+/// struct Uppercase {
+///     /// Number of leading characters to convert (the whole string, if
+///     /// omitted).
+///     first: Option<i32>,
+/// }
+///
+/// impl Uppercase {
+///     fn transform<S: ScalarValue>(self, value: Value<S>) -> Result<Value<S>, String> {
+///         let s = value
+///             .as_scalar()
+///             .and_then(ScalarValue::as_str)
+///             .ok_or_else(|| format!("Expected `String`, found: {}", value))?;
+///         let n = self.first.map_or(s.len(), |n| n.max(0) as usize);
+///         let upper = s.chars().take(n).flat_map(char::to_uppercase);
+///         Ok(Value::scalar(upper.chain(s.chars().skip(n)).collect::<String>()))
+///     }
+/// }
+///
+/// struct Query;
+///
+/// #[graphql_object]
+/// impl Query {
+///     fn hello() -> &'static str {
+///         "hello"
+///     }
+/// }
+///
+/// let schema = RootNode::new(
+///     Query,
+///     EmptyMutation::<()>::new(),
+///     EmptySubscription::<()>::new(),
+/// )
+/// .with_directive::<Uppercase>();
+///
+/// assert_eq!(
+///     execute_sync(
+///         "{ hello @uppercase a: hello @uppercase(first: 1) }",
+///         None,
+///         &schema,
+///         &graphql_vars! {},
+///         &(),
+///     ),
+///     Ok((graphql_value!({"hello": "HELLO", "a": "Hello"}), vec![])),
+/// );
+/// ```
+///
+/// # Custom name and description
+///
+/// By default, the directive name is the `camelCased` name of the struct, and
+/// its description is taken from the Rust doc comment. Both may be specified
+/// explicitly via `#[graphql(name = "...", description = "...")]` attribute.
+///
+/// # Custom `ScalarValue`
+///
+/// By default, this macro generates code, which is generic over a
+/// [`ScalarValue`] type. Concrete [`ScalarValue`] type may be specified via
+/// `#[graphql(scalar = <type>)]` attribute.
+///
+/// [`ScalarValue`]: juniper::ScalarValue
+/// [0]: https://spec.graphql.org/October2021#ExecutableDirectiveLocation
+/// [1]: https://spec.graphql.org/October2021#sec-Language.Fields
+/// [2]: macro@crate::graphql_object
+#[proc_macro_error]
+#[proc_macro_derive(GraphQLDirective, attributes(graphql))]
+pub fn derive_directive(input: TokenStream) -> TokenStream {
+    graphql_directive::derive::expand(input.into())
+        .unwrap_or_abort()
+        .into()
+}
+
+/// `#[derive(GraphQLCrud)]` macro for generating CRUD fields of an entity
+/// struct, backed by a [`CrudStorage`] implemented by the [`Context`].
+///
+/// For an entity `User`, it generates:
+/// - `UserCreateInput` [GraphQL input object][0] with all the entity fields but
+///   the ID;
+/// - `UserUpdateInput` [GraphQL input object][0] with all the entity fields but
+///   the ID being optional (with [`Nullable`] for the [`Option`]al ones), so
+///   only the specified ones are updated;
+/// - `UserCrudQuery` [GraphQL object][1] with `user(id)` and
+///   `users(first, offset)` fields;
+/// - `UserCrudMutation` [GraphQL object][1] with `createUser(input)`,
+///   `updateUser(id, input)` and `deleteUser(id)` fields.
+///
+/// The generated objects are meant to be used as a query and a mutation root,
+/// or to be merged into them via [`MergedObject`]. The entity itself should be
+/// a [GraphQL object][1] on its own.
+///
+/// ```rust
+/// use std::{collections::HashMap, sync::Mutex};
+///
+/// use juniper::{
+///     graphql_value, graphql_vars, Context, CrudStorage, EmptySubscription, FieldResult,
+///     GraphQLCrud, GraphQLObject, RootNode,
+/// };
+///
+/// #[derive(Clone, GraphQLCrud, GraphQLObject)]
+/// #[graphql_crud(context = Database, plural = "people")]
+/// struct Person {
+///     id: i32,
+///     /// Full name of the person.
+///     name: String,
+/// }
+///
+/// #[derive(Default)]
+/// struct Database(Mutex<HashMap<i32, Person>>);
+///
+/// impl Context for Database {}
+///
+/// impl CrudStorage<Person> for Database {
+///     fn get(&self, id: i32) -> FieldResult<Option<Person>> {
+///         Ok(self.0.lock().unwrap().get(&id).cloned())
+///     }
+///
+///     fn list(&self, first: Option<i32>, offset: Option<i32>) -> FieldResult<Vec<Person>> {
+///         let mut people = self.0.lock().unwrap().values().cloned().collect::<Vec<_>>();
+///         people.sort_by_key(|p| p.id);
+///         let offset = offset.unwrap_or(0).max(0) as usize;
+///         let first = first.map_or(usize::MAX, |n| n.max(0) as usize);
+///         Ok(people.into_iter().skip(offset).take(first).collect())
+///     }
+///
+///     fn create(&self, input: PersonCreateInput) -> FieldResult<Person> {
+///         let mut people = self.0.lock().unwrap();
+///         let person = Person { id: people.len() as i32 + 1, name: input.name };
+///         people.insert(person.id, person.clone());
+///         Ok(person)
+///     }
+///
+///     fn update(&self, id: i32, input: PersonUpdateInput) -> FieldResult<Option<Person>> {
+///         Ok(self.0.lock().unwrap().get_mut(&id).map(|p| {
+///             if let Some(name) = input.name {
+///                 p.name = name;
+///             }
+///             p.clone()
+///         }))
+///     }
+///
+///     fn delete(&self, id: i32) -> FieldResult<bool> {
+///         Ok(self.0.lock().unwrap().remove(&id).is_some())
+///     }
+/// }
+///
+/// let schema = RootNode::new(
+///     PersonCrudQuery,
+///     PersonCrudMutation,
+///     EmptySubscription::<Database>::new(),
+/// );
+/// let db = Database::default();
+///
+/// let res = juniper::execute_sync(
+///     r#"mutation { createPerson(input: {name: "Ann"}) { id } }"#,
+///     None,
+///     &schema,
+///     &graphql_vars! {},
+///     &db,
+/// );
+/// assert_eq!(res, Ok((graphql_value!({"createPerson": {"id": 1}}), vec![])));
+///
+/// let res = juniper::execute_sync("{ people { name } }", None, &schema, &graphql_vars! {}, &db);
+/// assert_eq!(res, Ok((graphql_value!({"people": [{"name": "Ann"}]}), vec![])));
+/// ```
+///
+/// # ID
+///
+/// The field named `id` is used as the ID of the entity by default. Another
+/// field may be marked as the ID via `#[graphql_crud(id)]` attribute.
+///
+/// # Skipping fields
+///
+/// Fields not to be set via the input objects (computed ones, or timestamps,
+/// for example) may be skipped via `#[graphql_crud(skip)]` attribute.
+///
+/// # Naming
+///
+/// The fields are named after the `camelCased` entity name, with `s` appended
+/// for the list field. A different plural may be specified via
+/// `#[graphql_crud(plural = "...")]` attribute, as a `snake_cased` name.
+///
+/// # Custom `ScalarValue`
+///
+/// By default, the generated code uses the [`DefaultScalarValue`]. Another
+/// [`ScalarValue`] type may be specified via
+/// `#[graphql_crud(scalar = <type>)]` attribute, so the [`CrudStorage`] should
+/// be implemented for it.
+///
+/// [`Context`]: juniper::Context
+/// [`CrudStorage`]: juniper::CrudStorage
+/// [`DefaultScalarValue`]: juniper::DefaultScalarValue
+/// [`MergedObject`]: juniper::MergedObject
+/// [`Nullable`]: juniper::Nullable
+/// [`ScalarValue`]: juniper::ScalarValue
+/// [0]: https://spec.graphql.org/June2018/#sec-Input-Objects
+/// [1]: https://spec.graphql.org/June2018/#sec-Objects
+#[proc_macro_error]
+#[proc_macro_derive(GraphQLCrud, attributes(graphql_crud))]
+pub fn derive_crud(input: TokenStream) -> TokenStream {
+    graphql_crud::derive::expand(input.into())
+        .unwrap_or_abort()
+        .into()
+}
+
 /// `#[graphql_scalar]` macro.is interchangeable with
 /// `#[derive(`[`GraphQLScalar`]`)]` macro, and is used for deriving a
 /// [GraphQL scalar][0] implementation.
@@ -552,6 +779,11 @@ pub fn graphql_scalar(attr: TokenStream, body: TokenStream) -> TokenStream {
 /// `as_bool` attribute argumentes (names correspond to [`ScalarValue`] required
 /// methods).
 ///
+/// The optional `coerce_float` attribute argument overrides the provided
+/// [`ScalarValue::coerce_float()`] method, so only the marked variants are
+/// accepted as [`Float`] inputs. By default, integer values are accepted too,
+/// so marking only float variants enables a strict mode rejecting them.
+///
 /// ```rust
 /// # use std::{fmt, convert::TryInto as _};
 /// #
@@ -643,7 +875,9 @@ pub fn graphql_scalar(attr: TokenStream, body: TokenStream) -> TokenStream {
 /// }
 /// ```
 ///
+/// [`Float`]: https://spec.graphql.org/June2018/#sec-Float
 /// [`ScalarValue`]: juniper::ScalarValue
+/// [`ScalarValue::coerce_float()`]: juniper::ScalarValue::coerce_float
 #[proc_macro_error]
 #[proc_macro_derive(ScalarValue, attributes(value))]
 pub fn derive_scalar_value(input: TokenStream) -> TokenStream {
@@ -652,6 +886,73 @@ pub fn derive_scalar_value(input: TokenStream) -> TokenStream {
         .into()
 }
 
+/// `#[derive(IntoFieldError)]` macro for deriving an [`IntoFieldError`]
+/// implementation for an error enum, converting each of its variants into a
+/// [`FieldError`] for any [`ScalarValue`].
+///
+/// By default, the [`FieldError::message()`] is the [`Display`] representation
+/// of the variant, and it has no [`FieldError::extensions()`]. They're
+/// specified per variant with the `#[graphql]` attribute:
+/// - `message = "..."` is a format string of the [`FieldError::message()`],
+///   referring the variant fields by their names (`{email}`) or indices
+///   (`{0}`), with the usual [`format!`] specifiers (`{0:?}`);
+/// - `code = "..."` adds the `code` [`FieldError::extensions()`];
+/// - `extensions(key = <expr>, ...)` adds other [`FieldError::extensions()`],
+///   with the `<expr>` convertible into a [`Value`] and referring the variant
+///   fields by reference, under their names (`email`) or as `_0`, `_1`, ...
+///   for unnamed ones;
+/// - `transparent` delegates to the [`IntoFieldError`] implementation of the
+///   single variant field.
+///
+/// ```rust
+/// use juniper::{graphql_value, FieldError, IntoFieldError};
+///
+/// #[derive(Debug, IntoFieldError)]
+/// enum UserError {
+///     #[graphql(message = "User {0} not found", code = "NOT_FOUND")]
+///     NotFound(i32),
+///
+///     #[graphql(
+///         message = "Invalid email: {email:?}",
+///         code = "INVALID_INPUT",
+///         extensions(field = "email", length = email.len() as i32),
+///     )]
+///     InvalidEmail { email: String },
+///
+///     #[graphql(transparent)]
+///     Other(FieldError),
+/// }
+///
+/// let err: FieldError = UserError::NotFound(42).into_field_error();
+/// assert_eq!(err.message(), "User 42 not found");
+/// assert_eq!(err.extensions(), &graphql_value!({"code": "NOT_FOUND"}));
+///
+/// let err: FieldError = UserError::InvalidEmail { email: "me@".into() }.into_field_error();
+/// assert_eq!(err.message(), r#"Invalid email: "me@""#);
+/// assert_eq!(
+///     err.extensions(),
+///     &graphql_value!({"code": "INVALID_INPUT", "field": "email", "length": 3}),
+/// );
+///
+/// let err: FieldError = UserError::Other("Unavailable".into()).into_field_error();
+/// assert_eq!(err, FieldError::from("Unavailable"));
+/// ```
+///
+/// [`Display`]: std::fmt::Display
+/// [`FieldError`]: juniper::FieldError
+/// [`FieldError::extensions()`]: juniper::FieldError::extensions
+/// [`FieldError::message()`]: juniper::FieldError::message
+/// [`IntoFieldError`]: juniper::IntoFieldError
+/// [`ScalarValue`]: juniper::ScalarValue
+/// [`Value`]: juniper::Value
+#[proc_macro_error]
+#[proc_macro_derive(IntoFieldError, attributes(graphql))]
+pub fn derive_into_field_error(input: TokenStream) -> TokenStream {
+    into_field_error::expand_derive(input.into())
+        .unwrap_or_abort()
+        .into()
+}
+
 /// `#[graphql_interface]` macro for generating a [GraphQL interface][1]
 /// implementation for traits and its implementers.
 ///
@@ -820,6 +1121,36 @@ pub fn derive_scalar_value(input: TokenStream) -> TokenStream {
 /// }
 /// ```
 ///
+/// # Conditional implementers
+///
+/// Multiple `for` attribute's arguments are merged together, so, to list
+/// some implementers only under some [conditional compilation][5] predicate
+/// (like an optional cargo feature), specify them via regular `#[cfg_attr]`
+/// attribute.
+///
+/// ```rust
+/// # use juniper::{graphql_interface, GraphQLObject};
+/// #
+/// #[graphql_interface(for = Human)]
+/// #[cfg_attr(feature = "droids", graphql_interface(for = Droid))]
+/// trait Character {
+///     fn id(&self) -> &str;
+/// }
+///
+/// #[derive(GraphQLObject)]
+/// #[graphql(impl = CharacterValue)]
+/// struct Human {
+///     id: String,
+/// }
+///
+/// #[cfg(feature = "droids")]
+/// #[derive(GraphQLObject)]
+/// #[graphql(impl = CharacterValue)]
+/// struct Droid {
+///     id: String,
+/// }
+/// ```
+///
 /// # Custom context
 ///
 /// By default, the generated implementation tries to infer [`Context`] type from signatures of
@@ -958,6 +1289,7 @@ pub fn derive_scalar_value(input: TokenStream) -> TokenStream {
 /// [2]: https://doc.rust-lang.org/stable/reference/items/traits.html#object-safety
 /// [3]: https://doc.rust-lang.org/stable/reference/types/trait-object.html
 /// [4]: https://doc.rust-lang.org/stable/std/primitive.unit.html
+/// [5]: https://doc.rust-lang.org/stable/reference/conditional-compilation.html
 #[proc_macro_error]
 #[proc_macro_attribute]
 pub fn graphql_interface(attr: TokenStream, body: TokenStream) -> TokenStream {
@@ -1104,6 +1436,154 @@ pub fn derive_interface(body: TokenStream) -> TokenStream {
 /// }
 /// ```
 ///
+/// # Conditionally skipping struct fields
+///
+/// To resolve a struct field as `null` depending on the object value or the
+/// [`Context`] (for example, to expose it to some tenants only), use a
+/// `skip_if` attribute's argument, specifying a path to a
+/// `fn(&Self, &Context) -> bool` predicate. The field should be nullable, as
+/// otherwise the `null` propagates to its parent, as usual.
+///
+/// ```
+/// # use juniper::GraphQLObject;
+/// #
+/// struct Tenant {
+///     beta: bool,
+/// }
+///
+/// impl juniper::Context for Tenant {}
+///
+/// #[derive(GraphQLObject)]
+/// #[graphql(context = Tenant)]
+/// struct Human {
+///     id: String,
+///     #[graphql(skip_if = "not_beta")]
+///     home_planet: Option<String>,
+/// }
+///
+/// fn not_beta(_: &Human, tenant: &Tenant) -> bool {
+///     !tenant.beta
+/// }
+/// ```
+///
+/// # Tagging PII fields
+///
+/// To tag a struct field as containing personally identifiable information,
+/// use a `pii` attribute's argument. It doesn't affect the execution, but the
+/// tagged fields selected by an operation are reported to the
+/// [`MetricsSink`], and may be stripped from a logged response with the
+/// [`PiiFields::strip()`] method.
+///
+/// ```
+/// # use juniper::GraphQLObject;
+/// #
+/// #[derive(GraphQLObject)]
+/// struct Human {
+///     id: String,
+///     #[graphql(pii)]
+///     email: String,
+/// }
+/// ```
+///
+/// # Declaring fields cost
+///
+/// To make a struct field cost more than `1` in the query cost analysis
+/// (checked against [`Limits::max_cost()`]), use a `complexity` attribute's
+/// argument with the fixed cost of the field, added to the cost of its
+/// selection set.
+///
+/// ```
+/// # use juniper::GraphQLObject;
+/// #
+/// #[derive(GraphQLObject)]
+/// struct Human {
+///     id: String,
+///     #[graphql(complexity = 10)]
+///     credit_score: i32,
+/// }
+/// ```
+///
+/// # Aliasing renamed fields
+///
+/// To keep a renamed struct field available under its legacy name(s) for the
+/// old clients, use an `alias` attribute's argument (repeatable). The alias
+/// resolves the same way as the field, and is registered in the schema as
+/// deprecated in favor of it. Use the `alias(name = "...", hidden)` form to
+/// hide the alias from introspection completely.
+///
+/// ```
+/// # use juniper::GraphQLObject;
+/// #
+/// #[derive(GraphQLObject)]
+/// struct Human {
+///     id: String,
+///     #[graphql(alias = "homePlanet", alias(name = "planetName", hidden))]
+///     planet: String,
+/// }
+/// ```
+///
+/// # Splitting into parts
+///
+/// Additional fields may be defined for the struct in separate
+/// [`#[graphql_object(part = ...)]`][2] `impl` blocks, listed in a `parts`
+/// attribute's argument.
+///
+/// ```
+/// # use juniper::{graphql_object, GraphQLObject};
+/// #
+/// #[derive(GraphQLObject)]
+/// #[graphql(parts = HumanFriends)]
+/// struct Human {
+///     id: String,
+/// }
+///
+/// #[graphql_object(part = HumanFriends)]
+/// impl Human {
+///     fn friends_count(&self) -> i32 {
+///         0
+///     }
+/// }
+/// ```
+///
+/// # Borrowing fields
+///
+/// The struct may have lifetime parameters, so its fields may borrow `&str`s,
+/// slices (like `&[String]` or `&[&str]`) and other [GraphQL objects][1] from
+/// the [`Context`] or the parent value, instead of cloning them into owned
+/// ones. The returned object is resolved before the borrowed data goes away.
+///
+/// ```
+/// # use juniper::{graphql_object, GraphQLObject};
+/// #
+/// struct Database {
+///     names: Vec<String>,
+/// }
+///
+/// impl juniper::Context for Database {}
+///
+/// #[derive(GraphQLObject)]
+/// #[graphql(context = Database)]
+/// struct Human<'a> {
+///     name: &'a str,
+///     aliases: &'a [String],
+///     nickname: Option<&'a str>,
+/// }
+///
+/// struct Query;
+///
+/// #[graphql_object(context = Database)]
+/// impl Query {
+///     fn human(context: &Database) -> Option<Human<'_>> {
+///         let (name, aliases) = context.names.split_first()?;
+///         Some(Human {
+///             name,
+///             aliases,
+///             nickname: None,
+///         })
+///     }
+/// }
+/// ```
+///
 /// # Custom `ScalarValue`
 ///
 /// By default, `#[derive(GraphQLObject)]` macro generates code, which is
@@ -1130,8 +1610,13 @@ pub fn derive_interface(body: TokenStream) -> TokenStream {
 /// }
 /// ```
 ///
+/// [`Context`]: juniper::Context
+/// [`Limits::max_cost()`]: juniper::Limits::max_cost
+/// [`MetricsSink`]: juniper::MetricsSink
+/// [`PiiFields::strip()`]: juniper::PiiFields::strip
 /// [`ScalarValue`]: juniper::ScalarValue
 /// [1]: https://spec.graphql.org/June2018/#sec-Objects
+/// [2]: macro@crate::graphql_object
 #[proc_macro_error]
 #[proc_macro_derive(GraphQLObject, attributes(graphql))]
 pub fn derive_object(body: TokenStream) -> TokenStream {
@@ -1234,11 +1719,20 @@ pub fn derive_object(body: TokenStream) -> TokenStream {
 ///
 /// A field of [GraphQL object][1] may be deprecated by specifying a
 /// `deprecated` attribute's argument, or with regular Rust `#[deprecated]`
-/// attribute.
+/// attribute. A field argument may be deprecated with a `deprecated`
+/// attribute's argument only, and, as required by spec, should be either
+/// nullable or have a default value. A `replaced_by` attribute's argument,
+/// naming the method replacing the field, deprecates the field with the
+/// ``"Use `replacement` instead."`` reason (the replacement is named according
+/// to the [renaming policy](#renaming-policy), unless given as a string
+/// literal). See also [`graphql_forward!`] for generating such fields.
 ///
 /// The default value of a field argument may be specified with a `default`
 /// attribute argument (if no exact value is specified then [`Default::default`]
-/// is used).
+/// is used). If the default value depends on the [`Context`], then a
+/// `default_with_context` attribute argument may be used instead, naming a
+/// function of `fn(&Context) -> T` signature, which is called whenever the
+/// argument is omitted or `null` (such argument is nullable in GraphQL schema).
 ///
 /// ```
 /// # use juniper::graphql_object;
@@ -1262,6 +1756,8 @@ pub fn derive_object(body: TokenStream) -> TokenStream {
 ///         // A default can be any valid expression that yields the right type.
 ///         #[graphql(default = 5)]
 ///         num: i32,
+///         #[graphql(deprecated = "Use `number` instead.")]
+///         old_num: Option<i32>,
 ///     ) -> &str {
 ///         "Don't use me!"
 ///     }
@@ -1278,6 +1774,8 @@ pub fn derive_object(body: TokenStream) -> TokenStream {
 ///     #[deprecated]
 ///     fn id(
 ///         &self,
+///         // Doc comments also work on arguments.
+///         /// Arbitrary number.
 ///         // If expression is not specified then `Default::default()` is used.
 ///         #[graphql(default)] num: i32,
 ///     ) -> &str {
@@ -1319,6 +1817,67 @@ pub fn derive_object(body: TokenStream) -> TokenStream {
 /// }
 /// ```
 ///
+/// # Arguments struct
+///
+/// Instead of declaring each field argument as a separate method parameter,
+/// it's possible to declare a single parameter with an `args` attribute's
+/// argument, being a [`GraphQLInputObject`]. Its fields become the field
+/// arguments in GraphQL schema (along with their names, descriptions and
+/// defaults), while the input object itself is not registered.
+///
+/// ```
+/// # use juniper::{graphql_object, GraphQLInputObject};
+/// #
+/// #[derive(GraphQLInputObject)]
+/// struct SearchArgs {
+///     text: String,
+///     #[graphql(default = "10")]
+///     limit: i32,
+///     offset: Option<i32>,
+/// }
+///
+/// struct Query;
+///
+/// #[graphql_object]
+/// impl Query {
+///     // NOTICE: In the generated GraphQL schema this field will be available
+///     //         as `search(text: String!, limit: Int = 10, offset: Int)`.
+///     fn search(#[graphql(args)] args: SearchArgs) -> Vec<String> {
+///         let _ = (args.limit, args.offset);
+///         vec![args.text]
+///     }
+/// }
+/// ```
+///
+/// # Validating arguments
+///
+/// Field arguments (as well as [`GraphQLInputObject`] fields) may be checked
+/// against simple constraints with a `validate` attribute's argument before
+/// the resolver is called:
+/// - `range(min = ..., max = ...)` for numbers;
+/// - `length(min = ..., max = ...)` for strings and lists;
+/// - `regex = "..."` for strings (requires `regex` Cargo feature of `juniper`).
+///
+/// `null` values always pass the checks. A failed check results in a
+/// [`FieldError`] having `input` and `constraint` extensions.
+///
+/// ```
+/// # use juniper::graphql_object;
+/// #
+/// struct Query;
+///
+/// #[graphql_object]
+/// impl Query {
+///     fn users(
+///         #[graphql(validate(range(min = 1, max = 100)))] first: i32,
+///         #[graphql(validate(length(min = 3)))] name_prefix: Option<String>,
+///     ) -> Vec<String> {
+///         # let _ = (first, name_prefix);
+///         vec![]
+///     }
+/// }
+/// ```
+///
 /// # Ignoring methods
 ///
 /// To omit some method to be assumed as a [GraphQL object][1] field and ignore
@@ -1340,6 +1899,199 @@ pub fn derive_object(body: TokenStream) -> TokenStream {
 /// }
 /// ```
 ///
+/// # Conditionally skipping methods
+///
+/// To resolve a [GraphQL object][1] field as `null` without calling its method
+/// depending on the object value or the [`Context`], use a `skip_if`
+/// attribute's argument, specifying a path to a `fn(&Self, &Context) -> bool`
+/// predicate. The field should be nullable, as otherwise the `null` propagates
+/// to its parent, as usual. Not supported for subscriptions.
+///
+/// ```
+/// # use juniper::graphql_object;
+/// #
+/// struct Tenant {
+///     beta: bool,
+/// }
+///
+/// impl juniper::Context for Tenant {}
+///
+/// struct Human(String);
+///
+/// #[graphql_object(context = Tenant)]
+/// impl Human {
+///     fn id(&self) -> &str {
+///         &self.0
+///     }
+///
+///     #[graphql(skip_if = Self::not_beta)]
+///     fn home_planet(&self) -> Option<&str> {
+///         Some("Mars")
+///     }
+/// }
+///
+/// impl Human {
+///     fn not_beta(&self, tenant: &Tenant) -> bool {
+///         !tenant.beta
+///     }
+/// }
+/// ```
+///
+/// # Tagging PII fields
+///
+/// To tag a [GraphQL object][1] field as containing personally identifiable
+/// information, use a `pii` attribute's argument. It doesn't affect the
+/// execution, but the tagged fields selected by an operation are reported to
+/// the [`MetricsSink`], and may be stripped from a logged response with the
+/// [`PiiFields::strip()`] method. Fields of [GraphQL interfaces][2] may be
+/// tagged the same way.
+///
+/// ```
+/// # use juniper::graphql_object;
+/// #
+/// struct Human(String);
+///
+/// #[graphql_object]
+/// impl Human {
+///     #[graphql(pii)]
+///     fn email(&self) -> &str {
+///         &self.0
+///     }
+/// }
+/// ```
+///
+/// # Declaring fields cost
+///
+/// By default, each field costs `1` plus the cost of its selection set in the
+/// query cost analysis, rejecting the operations exceeding the
+/// [`Limits::max_cost()`] before executing them. To declare the cost of a
+/// field, use a `complexity` attribute's argument, being either an expression
+/// of the fixed cost of the field (added to the cost of its selection set), or
+/// a closure computing the total cost of the field from its [`Arguments`] and
+/// the cost of its selection set. Fields of [GraphQL interfaces][2] may
+/// declare their cost the same way.
+///
+/// ```
+/// # use juniper::graphql_object;
+/// #
+/// struct User;
+///
+/// #[graphql_object]
+/// impl User {
+///     #[graphql(complexity = 5)]
+///     fn karma() -> i32 {
+///         42
+///     }
+///
+///     #[graphql(complexity = |args, child| {
+///         let first = args.get::<i32>("first").ok().flatten().unwrap_or(10);
+///         first.max(0) as usize * child
+///     })]
+///     fn friends(#[graphql(default = 10)] first: i32) -> Vec<User> {
+///         (0..first).map(|_| User).collect()
+///     }
+/// }
+/// ```
+///
+/// # Timing out methods
+///
+/// To limit the time an `async` method may take to resolve, use a `timeout`
+/// attribute's argument, specifying a duration like `"500ms"`, `"2s"`, `"1m"`
+/// or `"1h"`. Once it elapses, the field is resolved with an error carrying a
+/// `TIMEOUT` code in its extensions, at the path of this field. Applies to
+/// asynchronous execution only. Not supported for subscriptions.
+///
+/// ```
+/// # use juniper::graphql_object;
+/// #
+/// struct Human;
+///
+/// #[graphql_object]
+/// impl Human {
+///     #[graphql(timeout = "2s")]
+///     async fn home_planet() -> Option<String> {
+///         // Querying some slow external service...
+///         Some("Mars".into())
+///     }
+/// }
+/// ```
+///
+/// # Retrying methods
+///
+/// To retry an `async` method backed by an unreliable upstream service once it
+/// returns an error, use a `retry` attribute's argument, specifying the number
+/// of retries with `times`, and, optionally, a duration to wait before each
+/// retry with `backoff`. If all the attempts fail, the field is resolved with
+/// the error of the last one, having the total number of attempts in its
+/// `attempts` extension. Combined with `timeout`, each attempt is timed out
+/// separately. Applies to asynchronous execution only. Not supported for
+/// subscriptions.
+///
+/// ```
+/// # use juniper::{graphql_object, FieldResult};
+/// #
+/// struct Human;
+///
+/// #[graphql_object]
+/// impl Human {
+///     #[graphql(retry(times = 2, backoff = "50ms"))]
+///     async fn home_planet() -> FieldResult<String> {
+///         // Querying some flaky external service...
+///         Ok("Mars".into())
+///     }
+/// }
+/// ```
+///
+/// # Splitting into parts
+///
+/// Fields of a huge [GraphQL object][1] may be split by domain areas into
+/// multiple `impl` blocks (possibly placed in different modules), each marked
+/// with a `part` attribute's argument naming the marker type to declare for it.
+/// The main `impl` block (or the [`GraphQLObject`] derive) lists these marker
+/// types in a `parts` attribute's argument, and merges their fields into the
+/// single [GraphQL object][1].
+///
+/// All the parts should use the same [`Context`] and [`ScalarValue`] as the
+/// main `impl` block, and the [`Context`] of the main one is assumed if cannot
+/// be inferred. Not supported for subscriptions and generic types.
+///
+/// Defining a field with the same name in more than one part (or in a part
+/// and the main `impl` block) fails to compile.
+///
+/// ```
+/// # use juniper::graphql_object;
+/// #
+/// struct Database;
+///
+/// impl juniper::Context for Database {}
+///
+/// struct User {
+///     id: i32,
+/// }
+///
+/// #[graphql_object(context = Database, parts = [UserPayments, UserOrders])]
+/// impl User {
+///     fn id(&self) -> i32 {
+///         self.id
+///     }
+/// }
+///
+/// // May be placed in another module as well.
+/// #[graphql_object(part = UserPayments)]
+/// impl User {
+///     fn balance(&self) -> i32 {
+///         self.id * 100
+///     }
+/// }
+///
+/// #[graphql_object(part = UserOrders)]
+/// impl User {
+///     async fn orders_count(&self, context: &Database) -> i32 {
+///         0
+///     }
+/// }
+/// ```
+///
 /// # Custom context
 ///
 /// By default, the generated implementation tries to infer [`Context`] type
@@ -1379,6 +2131,44 @@ pub fn derive_object(body: TokenStream) -> TokenStream {
 /// }
 /// ```
 ///
+/// # Borrowing fields
+///
+/// The `impl` block may be generic over lifetimes, so the methods may return
+/// `&str`s, slices and other [GraphQL objects][1] borrowed from the parent
+/// value (or the [`Context`], as shown above) without cloning them, both in
+/// synchronous and `async` methods.
+///
+/// ```
+/// # use juniper::graphql_object;
+/// #
+/// struct Article<'a> {
+///     title: &'a str,
+///     comments: Vec<Comment<'a>>,
+/// }
+///
+/// struct Comment<'a> {
+///     text: &'a str,
+/// }
+///
+/// #[graphql_object]
+/// impl<'a> Article<'a> {
+///     fn title(&self) -> &'a str {
+///         self.title
+///     }
+///
+///     async fn comments(&self) -> &[Comment<'a>] {
+///         &self.comments
+///     }
+/// }
+///
+/// #[graphql_object]
+/// impl<'a> Comment<'a> {
+///     fn text(&self) -> &'a str {
+///         self.text
+///     }
+/// }
+/// ```
+///
 /// # Using `Executor`
 ///
 /// If an [`Executor`] is required in a method to resolve a [GraphQL object][1]
@@ -1450,13 +2240,22 @@ pub fn derive_object(body: TokenStream) -> TokenStream {
 /// }
 /// ```
 ///
+/// [`Arguments`]: juniper::Arguments
 /// [`Context`]: juniper::Context
 /// [`Executor`]: juniper::Executor
+/// [`FieldError`]: juniper::FieldError
+/// [`GraphQLInputObject`]: juniper::GraphQLInputObject
+/// [`GraphQLObject`]: derive@crate::GraphQLObject
 /// [`GraphQLType`]: juniper::GraphQLType
 /// [`GraphQLValue`]: juniper::GraphQLValue
+/// [`graphql_forward!`]: juniper::graphql_forward
+/// [`Limits::max_cost()`]: juniper::Limits::max_cost
+/// [`MetricsSink`]: juniper::MetricsSink
+/// [`PiiFields::strip()`]: juniper::PiiFields::strip
 /// [`ScalarValue`]: juniper::ScalarValue
 /// [0]: https://spec.graphql.org/June2018
 /// [1]: https://spec.graphql.org/June2018/#sec-Objects
+/// [2]: https://spec.graphql.org/June2018/#sec-Interfaces
 #[proc_macro_error]
 #[proc_macro_attribute]
 pub fn graphql_object(attr: TokenStream, body: TokenStream) -> TokenStream {
@@ -1699,6 +2498,38 @@ pub fn graphql_subscription(attr: TokenStream, body: TokenStream) -> TokenStream
 /// }
 /// ```
 ///
+/// # Conditional enum variants
+///
+/// To include an enum variant into the GraphQL schema only under some
+/// [conditional compilation][5] predicate (like an optional cargo feature),
+/// use a `cfg` attribute's argument directly on that variant. Unlike placing a
+/// regular `#[cfg]` attribute on it, this keeps the variant in the Rust enum,
+/// so, like an ignored one, it should _never_ be returned from resolvers when
+/// the predicate doesn't hold.
+///
+/// ```
+/// # use juniper::{GraphQLObject, GraphQLUnion};
+/// #
+/// # #[derive(GraphQLObject)]
+/// # struct Human {
+/// #     id: String,
+/// #     home_planet: String,
+/// # }
+/// #
+/// # #[derive(GraphQLObject)]
+/// # struct Droid {
+/// #     id: String,
+/// #     primary_function: String,
+/// # }
+/// #
+/// #[derive(GraphQLUnion)]
+/// enum Character {
+///     Human(Human),
+///     #[graphql(cfg(feature = "droids"))]
+///     Droid(Droid),
+/// }
+/// ```
+///
 /// # External resolver functions
 ///
 /// To use a custom logic for resolving a [GraphQL union][1] variant, an external resolver function
@@ -1818,6 +2649,7 @@ pub fn graphql_subscription(attr: TokenStream, body: TokenStream) -> TokenStream
 /// [`ScalarValue`]: juniper::ScalarValue
 /// [1]: https://spec.graphql.org/June2018/#sec-Unions
 /// [4]: https://doc.rust-lang.org/stable/std/primitive.unit.html
+/// [5]: https://doc.rust-lang.org/stable/reference/conditional-compilation.html
 #[proc_macro_error]
 #[proc_macro_derive(GraphQLUnion, attributes(graphql))]
 pub fn derive_union(body: TokenStream) -> TokenStream {
@@ -2041,6 +2873,42 @@ pub fn derive_union(body: TokenStream) -> TokenStream {
 /// # }
 /// ```
 ///
+/// # Conditional trait methods
+///
+/// To include a [GraphQL union][1] variant only under some
+/// [conditional compilation][5] predicate (like an optional cargo feature),
+/// use a `cfg` attribute's argument directly on the trait method resolving
+/// it. The trait method itself is conditionally compiled under the same
+/// predicate.
+///
+/// ```
+/// # use juniper::{graphql_union, GraphQLObject};
+/// #
+/// # #[derive(GraphQLObject)]
+/// # struct Human {
+/// #     id: String,
+/// #     home_planet: String,
+/// # }
+/// #
+/// #[cfg(feature = "droids")]
+/// #[derive(GraphQLObject)]
+/// struct Droid {
+///     id: String,
+///     primary_function: String,
+/// }
+///
+/// #[graphql_union]
+/// trait Character {
+///     fn as_human(&self) -> Option<&Human> { None }
+///     #[graphql(cfg(feature = "droids"))]
+///     fn as_droid(&self) -> Option<&Droid> { None }
+/// }
+/// #
+/// # impl Character for Human {
+/// #     fn as_human(&self) -> Option<&Human> { Some(self) }
+/// # }
+/// ```
+///
 /// # External resolver functions
 ///
 /// It's not mandatory to use trait methods as [GraphQL union][1] variant resolvers, and instead
@@ -2110,6 +2978,7 @@ pub fn derive_union(body: TokenStream) -> TokenStream {
 /// [2]: https://doc.rust-lang.org/stable/reference/items/traits.html#object-safety
 /// [3]: https://doc.rust-lang.org/stable/reference/types/trait-object.html
 /// [4]: https://doc.rust-lang.org/stable/std/primitive.unit.html
+/// [5]: https://doc.rust-lang.org/stable/reference/conditional-compilation.html
 #[proc_macro_error]
 #[proc_macro_attribute]
 pub fn graphql_union(attr: TokenStream, body: TokenStream) -> TokenStream {
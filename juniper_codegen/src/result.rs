@@ -9,6 +9,9 @@ use std::fmt;
 pub const SPEC_URL: &str = "https://spec.graphql.org/June2018/";
 
 pub enum GraphQLScope {
+    CrudDerive,
+    DirectiveDerive,
+    IntoFieldErrorDerive,
     InterfaceAttr,
     InterfaceDerive,
     ObjectAttr,
@@ -25,6 +28,9 @@ pub enum GraphQLScope {
 impl GraphQLScope {
     pub fn spec_section(&self) -> &str {
         match self {
+            Self::CrudDerive => "#sec-Objects",
+            Self::DirectiveDerive => "#sec-Language.Directives",
+            Self::IntoFieldErrorDerive => "#sec-Errors",
             Self::InterfaceAttr | Self::InterfaceDerive => "#sec-Interfaces",
             Self::ObjectAttr | Self::ObjectDerive => "#sec-Objects",
             Self::ScalarAttr | Self::ScalarDerive => "#sec-Scalars",
@@ -39,6 +45,9 @@ impl GraphQLScope {
 impl fmt::Display for GraphQLScope {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let name = match self {
+            Self::CrudDerive => "CRUD object",
+            Self::DirectiveDerive => "directive",
+            Self::IntoFieldErrorDerive => "field error",
             Self::InterfaceAttr | Self::InterfaceDerive => "interface",
             Self::ObjectAttr | Self::ObjectDerive => "object",
             Self::ScalarAttr | Self::ScalarDerive => "scalar",
@@ -57,8 +66,11 @@ pub enum UnsupportedAttribute {
     Skip,
     Interface,
     Scalar,
-    Deprecation,
     Default,
+    Validate,
+    DbRename,
+    DenyUnknownFields,
+    MaxDepth,
 }
 
 impl GraphQLScope {
@@ -148,6 +148,9 @@ enum Method {
 
     /// `#[value(as_bool)]`.
     AsBool,
+
+    /// `#[value(coerce_float)]`.
+    CoerceFloat,
 }
 
 /// Available arguments behind `#[value]` attribute when generating code for an
@@ -167,6 +170,7 @@ impl Parse for VariantAttr {
                 "as_string" => Method::AsString,
                 "into_string" => Method::IntoString,
                 "as_bool" => Method::AsBool,
+                "coerce_float" => Method::CoerceFloat,
                 name => {
                     return Err(err::unknown_arg(&ident, name));
                 }
@@ -279,21 +283,31 @@ impl Definition {
                 quote! { fn as_bool(&self) -> Option<bool> },
                 quote! { bool::from(*v) },
             ),
+            (
+                Method::CoerceFloat,
+                quote! { fn coerce_float(&self) -> Option<f64> },
+                quote! { f64::from(*v) },
+            ),
         ];
-        let methods = methods.iter().map(|(m, sig, def)| {
+        let methods = methods.iter().filter_map(|(m, sig, def)| {
+            // `coerce_float()` has a default implementation, so is overridden
+            // only if explicitly requested.
+            if *m == Method::CoerceFloat && !self.methods.contains_key(m) {
+                return None;
+            }
             let arms = self.methods.get(m).into_iter().flatten().map(|v| {
                 let arm = v.match_arm();
                 let call = v.expr.as_ref().map_or(def.clone(), |f| quote! { #f(v) });
                 quote! { #arm => Some(#call), }
             });
-            quote! {
+            Some(quote! {
                 #sig {
                     match self {
                         #(#arms)*
                         _ => None,
                     }
                 }
-            }
+            })
         });
 
         quote! {
@@ -18,7 +18,7 @@ use syn::{
     token, Attribute, Ident, Lit, Meta, MetaList, MetaNameValue, NestedMeta,
 };
 
-use crate::common::parse::ParseBufferExt as _;
+use crate::common::{parse::ParseBufferExt as _, validate::Constraints};
 
 /// Compares a path to a one-segment string value,
 /// return true if equal.
@@ -302,6 +302,8 @@ pub struct ObjectAttributes {
     pub no_async: Option<SpanContainer<()>>,
     pub is_internal: bool,
     pub rename: Option<RenameRule>,
+    pub deny_unknown_fields: Option<SpanContainer<()>>,
+    pub max_depth: Option<SpanContainer<usize>>,
 }
 
 impl Parse for ObjectAttributes {
@@ -367,6 +369,18 @@ impl Parse for ObjectAttributes {
                     input.parse::<token::Eq>()?;
                     output.rename = Some(input.parse::<RenameRule>()?);
                 }
+                "deny_unknown_fields" => {
+                    output.deny_unknown_fields = Some(SpanContainer::new(ident.span(), None, ()));
+                }
+                "max_depth" => {
+                    input.parse::<token::Eq>()?;
+                    let val = input.parse::<syn::LitInt>()?;
+                    output.max_depth = Some(SpanContainer::new(
+                        ident.span(),
+                        Some(val.span()),
+                        val.base10_parse()?,
+                    ));
+                }
                 _ => {
                     return Err(syn::Error::new(ident.span(), "unknown attribute"));
                 }
@@ -458,6 +472,8 @@ enum FieldAttribute {
     Skip(SpanContainer<syn::Ident>),
     Arguments(HashMap<String, FieldAttributeArgument>),
     Default(Box<SpanContainer<Option<syn::Expr>>>),
+    Validate(Box<SpanContainer<Constraints>>),
+    DbRename(SpanContainer<syn::LitStr>),
 }
 
 impl Parse for FieldAttribute {
@@ -532,6 +548,25 @@ impl Parse for FieldAttribute {
 
                 Ok(FieldAttribute::Default(Box::new(default_expr)))
             }
+            "db_rename" => {
+                input.parse::<token::Eq>()?;
+                let lit = input.parse::<syn::LitStr>()?;
+                Ok(FieldAttribute::DbRename(SpanContainer::new(
+                    ident.span(),
+                    Some(lit.span()),
+                    lit,
+                )))
+            }
+            "validate" => {
+                let inner;
+                syn::parenthesized!(inner in input);
+                let constraints = inner.parse::<Constraints>()?;
+                Ok(FieldAttribute::Validate(Box::new(SpanContainer::new(
+                    ident.span(),
+                    None,
+                    constraints,
+                ))))
+            }
             _ => Err(syn::Error::new(ident.span(), "unknown attribute")),
         }
     }
@@ -548,6 +583,10 @@ pub struct FieldAttributes {
     pub arguments: HashMap<String, FieldAttributeArgument>,
     /// Only relevant for object input objects.
     pub default: Option<SpanContainer<Option<syn::Expr>>>,
+    /// Only relevant for object input objects.
+    pub(crate) validate: Option<SpanContainer<Constraints>>,
+    /// Only relevant for enums.
+    pub db_rename: Option<SpanContainer<String>>,
 }
 
 impl Parse for FieldAttributes {
@@ -576,6 +615,12 @@ impl Parse for FieldAttributes {
                 FieldAttribute::Default(expr) => {
                     output.default = Some(*expr);
                 }
+                FieldAttribute::Validate(constraints) => {
+                    output.validate = Some(*constraints);
+                }
+                FieldAttribute::DbRename(name) => {
+                    output.db_rename = Some(name.map(|val| val.value()));
+                }
             }
         }
 
@@ -633,6 +678,7 @@ pub struct GraphQLTypeDefinitionField {
     pub is_type_inferred: bool,
     pub is_async: bool,
     pub default: Option<TokenStream>,
+    pub(crate) validate: Option<Constraints>,
     pub span: Span,
 }
 
@@ -675,6 +721,12 @@ pub struct GraphQLTypeDefiniton {
     pub generic_scalar: bool,
     // FIXME: make this redundant.
     pub no_async: bool,
+    // Indicates whether the input object should reject unknown fields when
+    // converted from an `InputValue`, instead of ignoring them.
+    pub deny_unknown_fields: bool,
+    // Maximum nesting depth of the input object when converted from an
+    // `InputValue`, if other than the default one.
+    pub max_depth: Option<usize>,
 }
 
 impl GraphQLTypeDefiniton {
@@ -762,7 +814,7 @@ impl GraphQLTypeDefiniton {
 
             quote!(
                 &#resolver_code =>
-                    ::juniper::InputValue::scalar(#variant_name.to_string()),
+                    ::juniper::InputValue::enum_value(#variant_name),
             )
         });
 
@@ -996,9 +1048,13 @@ impl GraphQLTypeDefiniton {
                     None => quote! {},
                 };
 
+                let checks = field.validate.as_ref().map(|constraints| {
+                    constraints.checks_tokens(&quote! { &v }, field_name, &scalar)
+                });
+
                 quote!(
                     #field_ident: {
-                        match obj.get(#field_name) {
+                        let v = match obj.get(#field_name) {
                             #from_input_default
                             Some(ref v) => {
                                 ::juniper::FromInputValue::<#scalar>::from_input_value(v)
@@ -1008,12 +1064,31 @@ impl GraphQLTypeDefiniton {
                                 ::juniper::FromInputValue::<#scalar>::from_implicit_null()
                                     .map_err(::juniper::IntoFieldError::into_field_error)?
                             },
-                        }
+                        };
+                        #checks
+                        v
                     },
                 )
             })
             .collect::<Vec<_>>();
 
+        let max_depth = self.max_depth.as_ref().map_or_else(
+            || quote! { ::juniper::macros::helper::DEFAULT_MAX_INPUT_DEPTH },
+            |depth| quote! { #depth },
+        );
+
+        let unknown_fields_check = self.deny_unknown_fields.then(|| {
+            let name = &self.name;
+            let field_names = self.fields.iter().map(|field| &field.name);
+            quote! {
+                ::juniper::macros::helper::deny_unknown_fields::<#scalar>(
+                    #name,
+                    &[#( #field_names ),*],
+                    obj.keys().copied(),
+                )?;
+            }
+        });
+
         let to_inputs = self
             .fields
             .iter()
@@ -1080,6 +1155,30 @@ impl GraphQLTypeDefiniton {
             }
         });
 
+        // Fields may be of the types not implementing the reflection (like the
+        // ones implemented manually), so the reflection of this input object is
+        // implemented only for the scalars the reflection of all its fields is.
+        let mut reflect_generics = self.generics.clone();
+        reflect_generics.params.push(parse_quote!(__R));
+        let reflect_where = reflect_generics.make_where_clause();
+        for field in &self.fields {
+            let field_ty = &field._type;
+            reflect_where.predicates.push(parse_quote! {
+                #field_ty: ::juniper::macros::reflect::BaseType<__R>
+                    + ::juniper::macros::reflect::WrappedType<__R>
+            });
+        }
+        let (reflect_impl_generics, _, reflect_where) = reflect_generics.split_for_impl();
+
+        let reflect_fields = self.fields.iter().map(|field| {
+            let (field_name, field_ty) = (&field.name, &field._type);
+            quote! {(
+                #field_name,
+                <#field_ty as ::juniper::macros::reflect::BaseType<__R>>::NAME,
+                <#field_ty as ::juniper::macros::reflect::WrappedType<__R>>::VALUE,
+            ),}
+        });
+
         let mut body = quote!(
             impl#impl_generics ::juniper::marker::IsInputType<#scalar> for #ty #type_generics_tokens
                 #where_clause {
@@ -1129,11 +1228,16 @@ impl GraphQLTypeDefiniton {
                 fn from_input_value(
                     value: &::juniper::InputValue<#scalar>
                 ) -> Result<Self, Self::Error> {
+                    let _depth = ::juniper::macros::helper::InputDepthGuard::enter::<#scalar>(
+                        #name,
+                        #max_depth,
+                    )?;
                     let obj = value
                         .to_object_value()
                         .ok_or_else(|| ::juniper::FieldError::<#scalar>::from(
                             format!("Expected input object, found: {}", value))
                         )?;
+                    #unknown_fields_check
                     Ok(#ty {
                         #( #from_inputs )*
                     })
@@ -1171,6 +1275,15 @@ impl GraphQLTypeDefiniton {
             {
                 const VALUE: ::juniper::macros::reflect::WrappedValue = 1;
             }
+
+            impl#reflect_impl_generics ::juniper::macros::reflect::InputFields<__R>
+                for #ty #type_generics_tokens
+                #reflect_where
+            {
+                const FIELDS: ::juniper::macros::reflect::Arguments = &[
+                    #( #reflect_fields )*
+                ];
+            }
         );
 
         if !self.no_async {
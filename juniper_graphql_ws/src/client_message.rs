@@ -18,6 +18,12 @@ pub struct StartPayload<S> {
 
     /// The optional operation name (required if the document contains multiple operations).
     pub operation_name: Option<String>,
+
+    /// The optional protocol extensions, like the `traceparent` of a [`TraceContext`].
+    ///
+    /// [`TraceContext`]: crate::TraceContext
+    #[serde(default, deserialize_with = "default_for_null")]
+    pub extensions: Variables<S>,
 }
 
 /// ClientMessage defines the message types that clients can send.
@@ -83,6 +89,7 @@ mod test {
                     query: "query MyQuery { __typename }".to_string(),
                     variables: graphql_vars! {"foo": "bar"},
                     operation_name: Some("MyQuery".to_string()),
+                    extensions: graphql_vars! {"traceparent": "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01"},
                 },
             },
             serde_json::from_str(
@@ -91,7 +98,10 @@ mod test {
                 "variables": {
                     "foo": "bar"
                 },
-                "operationName": "MyQuery"
+                "operationName": "MyQuery",
+                "extensions": {
+                    "traceparent": "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01"
+                }
             }}"##
             )
             .unwrap(),
@@ -104,6 +114,7 @@ mod test {
                     query: "query MyQuery { __typename }".to_string(),
                     variables: graphql_vars! {},
                     operation_name: None,
+                    extensions: graphql_vars! {},
                 },
             },
             serde_json::from_str(
@@ -136,6 +147,7 @@ mod test {
             query: "query".into(),
             variables: graphql_vars! {},
             operation_name: None,
+            extensions: graphql_vars! {},
         };
 
         assert_eq!(expected, payload);
@@ -11,8 +11,16 @@ pub use server_message::*;
 mod schema;
 pub use schema::*;
 
+mod shutdown;
+pub use shutdown::*;
+
+mod trace_context;
+pub use trace_context::*;
+
 mod utils;
 
+pub use juniper_subscriptions::{EventLayer, EventStream};
+
 use std::{
     collections::HashMap,
     convert::{Infallible, TryInto},
@@ -31,21 +39,30 @@ use juniper::{
         task::{Context, Poll, Waker},
         Sink, Stream,
     },
-    GraphQLError, RuleError, ScalarValue, Variables,
+    OperationError, RequestError, RuleError, ScalarValue, Variables,
 };
+use tracing::Instrument as _;
+
+use self::shutdown::ShutdownSignal;
 
 struct ExecutionParams<S: Schema> {
     start_payload: StartPayload<S::ScalarValue>,
     config: Arc<ConnectionConfig<S::Context>>,
     schema: S,
+    span: tracing::Span,
+    event_layers: EventLayers<S>,
 }
 
+/// EventLayers transform the events of every subscription of a connection, in order.
+type EventLayers<S> = Vec<Arc<dyn EventLayer<<S as Schema>::Context, <S as Schema>::ScalarValue>>>;
+
 /// ConnectionConfig is used to configure the connection once the client sends the ConnectionInit
 /// message.
 pub struct ConnectionConfig<CtxT> {
     context: CtxT,
     max_in_flight_operations: usize,
     keep_alive_interval: Duration,
+    trace_context: Option<TraceContext>,
 }
 
 impl<CtxT> ConnectionConfig<CtxT> {
@@ -55,6 +72,7 @@ impl<CtxT> ConnectionConfig<CtxT> {
             context,
             max_in_flight_operations: 0,
             keep_alive_interval: Duration::from_secs(15),
+            trace_context: None,
         }
     }
 
@@ -74,6 +92,15 @@ impl<CtxT> ConnectionConfig<CtxT> {
         self.keep_alive_interval = interval;
         self
     }
+
+    /// Specifies the trace context of the connection, attached to the spans of its operations
+    /// unless a "start" message carries its own one in `extensions`. By default, the trace context
+    /// of the HTTP upgrade request is used, if provided via `Connection::with_trace_context()`.
+    #[must_use]
+    pub fn with_trace_context(mut self, trace_context: TraceContext) -> Self {
+        self.trace_context = Some(trace_context);
+        self
+    }
 }
 
 impl<S: ScalarValue, CtxT: Unpin + Send + 'static> Init<S, CtxT> for ConnectionConfig<CtxT> {
@@ -129,12 +156,20 @@ where
 
 enum ConnectionState<S: Schema, I: Init<S::ScalarValue, S::Context>> {
     /// PreInit is the state before a ConnectionInit message has been accepted.
-    PreInit { init: I, schema: S },
+    PreInit {
+        init: I,
+        schema: S,
+        trace_context: Option<TraceContext>,
+        shutdown: Option<ShutdownSignal>,
+        event_layers: EventLayers<S>,
+    },
     /// Active is the state after a ConnectionInit message has been accepted.
     Active {
         config: Arc<ConnectionConfig<S::Context>>,
         stoppers: HashMap<String, oneshot::Sender<()>>,
         schema: S,
+        shutdown: Option<ShutdownSignal>,
+        event_layers: EventLayers<S>,
     },
     /// Terminated is the state after a ConnectionInit message has been rejected.
     Terminated,
@@ -152,9 +187,19 @@ impl<S: Schema, I: Init<S::ScalarValue, S::Context>> ConnectionState<S, I> {
         }
 
         match self {
-            Self::PreInit { init, schema } => match msg {
+            Self::PreInit {
+                init,
+                schema,
+                trace_context,
+                shutdown,
+                event_layers,
+            } => match msg {
                 ClientMessage::ConnectionInit { payload } => match init.init(payload).await {
-                    Ok(config) => {
+                    Ok(mut config) => {
+                        if config.trace_context.is_none() {
+                            config.trace_context = trace_context;
+                        }
+
                         let keep_alive_interval = config.keep_alive_interval;
 
                         let mut s = stream::iter(vec![Reaction::ServerMessage(
@@ -185,6 +230,8 @@ impl<S: Schema, I: Init<S::ScalarValue, S::Context>> ConnectionState<S, I> {
                                 config: Arc::new(config),
                                 stoppers: HashMap::new(),
                                 schema,
+                                shutdown,
+                                event_layers,
                             },
                             s,
                         )
@@ -202,12 +249,23 @@ impl<S: Schema, I: Init<S::ScalarValue, S::Context>> ConnectionState<S, I> {
                         .boxed(),
                     ),
                 },
-                _ => (Self::PreInit { init, schema }, stream::empty().boxed()),
+                _ => (
+                    Self::PreInit {
+                        init,
+                        schema,
+                        trace_context,
+                        shutdown,
+                        event_layers,
+                    },
+                    stream::empty().boxed(),
+                ),
             },
             Self::Active {
                 config,
                 mut stoppers,
                 schema,
+                shutdown,
+                event_layers,
             } => {
                 let reactions = match msg {
                     ClientMessage::Start { id, payload } => {
@@ -219,16 +277,31 @@ impl<S: Schema, I: Init<S::ScalarValue, S::Context>> ConnectionState<S, I> {
                             // Go ahead and prune canceled stoppers before adding a new one.
                             stoppers.retain(|_, tx| !tx.is_canceled());
 
-                            if config.max_in_flight_operations > 0
+                            if matches!(&shutdown, Some(s) if s.is_shutting_down()) {
+                                // The server is shutting down, so no new operations are accepted.
+                                stream::iter(vec![
+                                    Reaction::ServerMessage(ServerMessage::Error {
+                                        id: id.clone(),
+                                        payload: RequestError::Validation(vec![RuleError::new(
+                                            "Server is shutting down.",
+                                            &[],
+                                        )])
+                                        .into(),
+                                    }),
+                                    Reaction::ServerMessage(ServerMessage::Complete { id }),
+                                ])
+                                .boxed()
+                            } else if config.max_in_flight_operations > 0
                                 && stoppers.len() >= config.max_in_flight_operations
                             {
                                 // Too many in-flight operations. Just send back a validation error.
                                 stream::iter(vec![
                                     Reaction::ServerMessage(ServerMessage::Error {
                                         id: id.clone(),
-                                        payload: GraphQLError::ValidationError(vec![
-                                            RuleError::new("Too many in-flight operations.", &[]),
-                                        ])
+                                        payload: RequestError::Validation(vec![RuleError::new(
+                                            "Too many in-flight operations.",
+                                            &[],
+                                        )])
                                         .into(),
                                     }),
                                     Reaction::ServerMessage(ServerMessage::Complete { id }),
@@ -241,19 +314,33 @@ impl<S: Schema, I: Init<S::ScalarValue, S::Context>> ConnectionState<S, I> {
 
                                 // Create the operation stream. This stream will emit Data and Error
                                 // messages, but will not emit Complete – that part is up to us.
+                                let span = operation_span(
+                                    &id,
+                                    TraceContext::from_extensions(&payload.extensions)
+                                        .as_ref()
+                                        .or(config.trace_context.as_ref()),
+                                );
                                 let s = Self::start(
                                     id.clone(),
                                     ExecutionParams {
                                         start_payload: payload,
                                         config: config.clone(),
                                         schema: schema.clone(),
+                                        span,
+                                        event_layers: event_layers.clone(),
                                     },
                                 )
                                 .into_stream()
                                 .flatten();
 
                                 // Combine this with our oneshot channel so that the stream ends if the
-                                // oneshot is ever fired.
+                                // oneshot is ever fired, or once the server has been drained.
+                                let rx = match &shutdown {
+                                    Some(signal) => {
+                                        future::select(rx, signal.drained()).map(drop).boxed()
+                                    }
+                                    None => rx.map(drop).boxed(),
+                                };
                                 let s = stream::unfold((rx, s.boxed()), |(rx, mut s)| async move {
                                     let next = match future::select(rx, s.next()).await {
                                         Either::Left(_) => None,
@@ -283,6 +370,8 @@ impl<S: Schema, I: Init<S::ScalarValue, S::Context>> ConnectionState<S, I> {
                         config,
                         stoppers,
                         schema,
+                        shutdown,
+                        event_layers,
                     },
                     reactions,
                 )
@@ -306,6 +395,7 @@ impl<S: Schema, I: Init<S::ScalarValue, S::Context>> ConnectionState<S, I> {
             &params.start_payload.variables,
             &params.config.context,
         )
+        .instrument(params.span.clone())
         .await
         {
             Ok((data, errors)) => {
@@ -315,7 +405,7 @@ impl<S: Schema, I: Init<S::ScalarValue, S::Context>> ConnectionState<S, I> {
                 })
                 .into_stream();
             }
-            Err(GraphQLError::IsSubscription) => {}
+            Err(RequestError::Operation(OperationError::IsSubscription)) => {}
             Err(e) => {
                 return Reaction::ServerMessage(ServerMessage::Error {
                     id: id.clone(),
@@ -331,6 +421,19 @@ impl<S: Schema, I: Init<S::ScalarValue, S::Context>> ConnectionState<S, I> {
     }
 }
 
+/// Creates the span of an operation, carrying the client's trace context, if any. The spans of the
+/// subscription events are nested into it.
+fn operation_span(id: &str, trace_context: Option<&TraceContext>) -> tracing::Span {
+    tracing::info_span!(
+        "graphql_ws.operation",
+        id,
+        trace_id = trace_context.map(TraceContext::trace_id),
+        parent_id = trace_context.map(TraceContext::parent_id),
+        sampled = trace_context.map(TraceContext::is_sampled),
+        tracestate = trace_context.and_then(TraceContext::tracestate),
+    )
+}
+
 struct InterruptableStream<S> {
     stream: S,
     rx: oneshot::Receiver<()>,
@@ -360,7 +463,7 @@ enum SubscriptionStartState<S: Schema> {
             'static,
             Result<
                 juniper_subscriptions::Connection<'static, S::ScalarValue>,
-                GraphQLError<'static>,
+                RequestError<'static>,
             >,
         >,
     },
@@ -369,6 +472,8 @@ enum SubscriptionStartState<S: Schema> {
     Streaming {
         id: String,
         stream: juniper_subscriptions::Connection<'static, S::ScalarValue>,
+        /// The span of the event being currently resolved.
+        event_span: Option<tracing::Span>,
     },
     /// Terminated is the state once we're all done.
     Terminated,
@@ -401,6 +506,7 @@ impl<S: Schema> Stream for SubscriptionStart<S> {
             let inner = self.get_unchecked_mut();
             (&inner.params, &mut inner.state)
         };
+        let _entered = params.span.enter();
 
         loop {
             match state {
@@ -408,20 +514,23 @@ impl<S: Schema> Stream for SubscriptionStart<S> {
                     // XXX: resolve_into_stream returns a Future that references the execution
                     // parameters, and the returned stream also references them. We can guarantee
                     // that everything has the same lifetime in this self-referential struct.
-                    let params = Arc::as_ptr(params);
+                    let params = unsafe { &*Arc::as_ptr(params) };
+                    let (event_layers, context) = (&params.event_layers, &params.config.context);
                     *state = SubscriptionStartState::ResolvingIntoStream {
                         id: id.clone(),
-                        future: unsafe {
-                            juniper::resolve_into_stream(
-                                &(*params).start_payload.query,
-                                (*params).start_payload.operation_name.as_deref(),
-                                (*params).schema.root_node(),
-                                &(*params).start_payload.variables,
-                                &(*params).config.context,
-                            )
-                        }
-                        .map_ok(|(stream, errors)| {
-                            juniper_subscriptions::Connection::from_stream(stream, errors)
+                        future: juniper::resolve_into_stream(
+                            &params.start_payload.query,
+                            params.start_payload.operation_name.as_deref(),
+                            params.schema.root_node(),
+                            &params.start_payload.variables,
+                            &params.config.context,
+                        )
+                        .map_ok(move |(stream, errors)| {
+                            let conn =
+                                juniper_subscriptions::Connection::from_stream(stream, errors);
+                            event_layers
+                                .iter()
+                                .fold(conn, |conn, layer| conn.layered(&**layer, context))
                         })
                         .boxed(),
                     };
@@ -435,6 +544,7 @@ impl<S: Schema> Stream for SubscriptionStart<S> {
                             *state = SubscriptionStartState::Streaming {
                                 id: id.clone(),
                                 stream,
+                                event_span: None,
                             }
                         }
                         Err(e) => {
@@ -454,8 +564,13 @@ impl<S: Schema> Stream for SubscriptionStart<S> {
                 SubscriptionStartState::Streaming {
                     ref id,
                     ref mut stream,
-                } => match Pin::new(stream).poll_next(cx) {
+                    ref mut event_span,
+                } => match event_span
+                    .get_or_insert_with(|| tracing::info_span!("graphql_ws.event", id = %id))
+                    .in_scope(|| Pin::new(stream).poll_next(cx))
+                {
                     Poll::Ready(Some(output)) => {
+                        *event_span = None;
                         return Poll::Ready(Some(Reaction::ServerMessage(ServerMessage::Data {
                             id: id.clone(),
                             payload: DataPayload {
@@ -493,6 +608,10 @@ pub struct Connection<S: Schema, I: Init<S::ScalarValue, S::Context>> {
     reactions: SelectAll<BoxStream<'static, Reaction<S>>>,
     stream_waker: Option<Waker>,
     sink_state: ConnectionSinkState<S, I>,
+    /// Resolves once the server has been drained, if the connection is attached to a
+    /// `ShutdownCoordinator`.
+    drained: Option<BoxFuture<'static, ()>>,
+    is_drained: bool,
 }
 
 impl<S, I> Connection<S, I>
@@ -514,10 +633,74 @@ where
             reactions: SelectAll::new(),
             stream_waker: None,
             sink_state: ConnectionSinkState::Ready {
-                state: ConnectionState::PreInit { init, schema },
+                state: ConnectionState::PreInit {
+                    init,
+                    schema,
+                    trace_context: None,
+                    shutdown: None,
+                    event_layers: Vec::new(),
+                },
             },
+            drained: None,
+            is_drained: false,
         }
     }
+
+    /// Specifies the trace context extracted from the HTTP upgrade request (see
+    /// `TraceContext::from_headers()`). It's used for the operations of this connection, unless
+    /// overridden by the `ConnectionConfig` or the `extensions` of a "start" message.
+    #[must_use]
+    pub fn with_trace_context(mut self, trace_context: Option<TraceContext>) -> Self {
+        if let ConnectionSinkState::Ready {
+            state:
+                ConnectionState::PreInit {
+                    trace_context: ref mut tc,
+                    ..
+                },
+        } = self.sink_state
+        {
+            *tc = trace_context;
+        }
+        self
+    }
+
+    /// Attaches this connection to the given `ShutdownCoordinator`. Once it initiates a shutdown,
+    /// new operations are rejected, and after its drain period the active ones are completed and
+    /// this connection ends.
+    #[must_use]
+    pub fn with_shutdown(mut self, coordinator: &ShutdownCoordinator) -> Self {
+        if let ConnectionSinkState::Ready {
+            state: ConnectionState::PreInit {
+                ref mut shutdown, ..
+            },
+        } = self.sink_state
+        {
+            let signal = coordinator.signal();
+            self.drained = Some(signal.drained());
+            *shutdown = Some(signal);
+        }
+        self
+    }
+
+    /// Adds the `EventLayer` transforming the events of every subscription of this connection
+    /// before they're sent to the client, in the order the layers are added.
+    #[must_use]
+    pub fn with_event_layer(
+        mut self,
+        layer: impl EventLayer<S::Context, S::ScalarValue> + 'static,
+    ) -> Self {
+        if let ConnectionSinkState::Ready {
+            state:
+                ConnectionState::PreInit {
+                    ref mut event_layers,
+                    ..
+                },
+        } = self.sink_state
+        {
+            event_layers.push(Arc::new(layer));
+        }
+        self
+    }
 }
 
 impl<S, I, T> Sink<T> for Connection<S, I>
@@ -602,6 +785,15 @@ where
             return Poll::Ready(None);
         }
 
+        if let Some(drained) = &mut self.drained {
+            if drained.as_mut().poll(cx).is_ready() {
+                // The active operations are being stopped at the same moment, so just flush their
+                // "complete" messages, and end once nothing else is ready.
+                self.drained = None;
+                self.is_drained = true;
+            }
+        }
+
         // Poll the reactions for new outgoing messages.
         if !self.reactions.is_empty() {
             match Pin::new(&mut self.reactions).poll_next(cx) {
@@ -618,6 +810,10 @@ where
                 _ => (),
             }
         }
+
+        if self.is_drained {
+            return Poll::Ready(None);
+        }
         Poll::Pending
     }
 }
@@ -715,6 +911,7 @@ mod test {
                 query: "{context}".to_string(),
                 variables: graphql_vars! {},
                 operation_name: None,
+                extensions: graphql_vars! {},
             },
         })
         .await
@@ -760,6 +957,7 @@ mod test {
                 query: "subscription Foo {context}".to_string(),
                 variables: graphql_vars! {},
                 operation_name: None,
+                extensions: graphql_vars! {},
             },
         })
         .await
@@ -782,6 +980,7 @@ mod test {
                 query: "subscription Bar {context}".to_string(),
                 variables: graphql_vars! {},
                 operation_name: None,
+                extensions: graphql_vars! {},
             },
         })
         .await
@@ -812,6 +1011,65 @@ mod test {
         );
     }
 
+    /// Replaces the data of every event with the context, multiplied by the factor.
+    struct MultiplyContext(i32);
+
+    impl EventLayer<Context, DefaultScalarValue> for MultiplyContext {
+        fn layer<'a>(
+            &'a self,
+            ctx: &'a Context,
+            events: EventStream<'a, DefaultScalarValue>,
+        ) -> EventStream<'a, DefaultScalarValue> {
+            events
+                .map(move |mut out| {
+                    out.data = graphql_value!({"context": (ctx.0 * self.0)});
+                    out
+                })
+                .boxed()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_event_layers() {
+        let mut conn = Connection::new(
+            new_test_schema(),
+            ConnectionConfig::new(Context(1)).with_keep_alive_interval(Duration::from_secs(0)),
+        )
+        .with_event_layer(MultiplyContext(2))
+        .with_event_layer(MultiplyContext(3));
+
+        conn.send(ClientMessage::ConnectionInit {
+            payload: graphql_vars! {},
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(ServerMessage::ConnectionAck, conn.next().await.unwrap());
+
+        conn.send(ClientMessage::Start {
+            id: "foo".to_string(),
+            payload: StartPayload {
+                query: "subscription Foo {context}".to_string(),
+                variables: graphql_vars! {},
+                operation_name: None,
+                extensions: graphql_vars! {},
+            },
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(
+            ServerMessage::Data {
+                id: "foo".to_string(),
+                payload: DataPayload {
+                    data: graphql_value!({"context": 3}),
+                    errors: vec![],
+                },
+            },
+            conn.next().await.unwrap()
+        );
+    }
+
     #[tokio::test]
     async fn test_init_params_ok() {
         let mut conn = Connection::new(new_test_schema(), |params: Variables| async move {
@@ -874,17 +1132,71 @@ mod test {
                 query: "subscription Foo {never}".to_string(),
                 variables: graphql_vars! {},
                 operation_name: None,
+                extensions: graphql_vars! {},
+            },
+        })
+        .await
+        .unwrap();
+
+        conn.send(ClientMessage::Start {
+            id: "bar".to_string(),
+            payload: StartPayload {
+                query: "subscription Bar {never}".to_string(),
+                variables: graphql_vars! {},
+                operation_name: None,
+                extensions: graphql_vars! {},
+            },
+        })
+        .await
+        .unwrap();
+
+        match conn.next().await.unwrap() {
+            ServerMessage::Error { id, .. } => {
+                assert_eq!(id, "bar");
+            }
+            msg @ _ => panic!("expected error, got: {:?}", msg),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_graceful_shutdown() {
+        let coordinator = ShutdownCoordinator::new();
+        let mut conn = Connection::new(
+            new_test_schema(),
+            ConnectionConfig::new(Context(1)).with_keep_alive_interval(Duration::from_secs(0)),
+        )
+        .with_shutdown(&coordinator);
+
+        conn.send(ClientMessage::ConnectionInit {
+            payload: graphql_vars! {},
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(ServerMessage::ConnectionAck, conn.next().await.unwrap());
+
+        conn.send(ClientMessage::Start {
+            id: "foo".to_string(),
+            payload: StartPayload {
+                query: "subscription Foo {never}".to_string(),
+                variables: graphql_vars! {},
+                operation_name: None,
+                extensions: graphql_vars! {},
             },
         })
         .await
         .unwrap();
 
+        let shutdown = coordinator.shutdown(Duration::from_millis(50));
+        assert!(coordinator.is_shutting_down());
+
         conn.send(ClientMessage::Start {
             id: "bar".to_string(),
             payload: StartPayload {
                 query: "subscription Bar {never}".to_string(),
                 variables: graphql_vars! {},
                 operation_name: None,
+                extensions: graphql_vars! {},
             },
         })
         .await
@@ -896,6 +1208,25 @@ mod test {
             }
             msg @ _ => panic!("expected error, got: {:?}", msg),
         }
+        assert_eq!(
+            ServerMessage::Complete {
+                id: "bar".to_string(),
+            },
+            conn.next().await.unwrap()
+        );
+
+        assert_eq!(
+            ServerMessage::Complete {
+                id: "foo".to_string(),
+            },
+            conn.next().await.unwrap()
+        );
+        assert!(conn.next().await.is_none());
+
+        drop(conn);
+        tokio::time::timeout(Duration::from_secs(1), shutdown)
+            .await
+            .expect("shutdown should resolve once the connection is dropped");
     }
 
     #[tokio::test]
@@ -919,6 +1250,7 @@ mod test {
                 query: "asd".to_string(),
                 variables: graphql_vars! {},
                 operation_name: None,
+                extensions: graphql_vars! {},
             },
         })
         .await
@@ -928,7 +1260,7 @@ mod test {
             ServerMessage::Error { id, payload } => {
                 assert_eq!(id, "foo");
                 match payload.graphql_error() {
-                    GraphQLError::ParseError(Spanning {
+                    RequestError::Parse(Spanning {
                         item: ParseError::UnexpectedToken(Token::Name("asd")),
                         ..
                     }) => {}
@@ -982,6 +1314,7 @@ mod test {
                 query: "{context}".to_string(),
                 variables: graphql_vars! {},
                 operation_name: None,
+                extensions: graphql_vars! {},
             },
         })
         .await
@@ -1022,6 +1355,7 @@ mod test {
                 query: "subscription Foo {error}".to_string(),
                 variables: graphql_vars! {},
                 operation_name: None,
+                extensions: graphql_vars! {},
             },
         })
         .await
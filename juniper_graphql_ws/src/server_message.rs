@@ -1,6 +1,6 @@
 use std::{any::Any, fmt, marker::PhantomPinned, mem};
 
-use juniper::{ExecutionError, GraphQLError, Value};
+use juniper::{ExecutionError, RequestError, Value};
 use serde::{Serialize, Serializer};
 
 /// The payload for errors that are not associated with a GraphQL operation.
@@ -27,12 +27,12 @@ pub struct DataPayload<S> {
 
 /// A payload for errors that can happen before execution. Errors that happen during execution are
 /// instead sent to the client via `DataPayload`. `ErrorPayload` is a wrapper for an owned
-/// `GraphQLError`.
+/// `RequestError`.
 // XXX: Think carefully before deriving traits. This is self-referential (error references
 // _execution_params).
 pub struct ErrorPayload {
     _execution_params: Option<Box<dyn Any + Send>>,
-    error: GraphQLError<'static>,
+    error: RequestError<'static>,
     _marker: PhantomPinned,
 }
 
@@ -41,7 +41,7 @@ impl ErrorPayload {
     /// execution_params and that execution_params has not been modified or moved.
     pub(crate) unsafe fn new_unchecked(
         execution_params: Box<dyn Any + Send>,
-        error: GraphQLError<'_>,
+        error: RequestError<'_>,
     ) -> Self {
         Self {
             _execution_params: Some(execution_params),
@@ -50,8 +50,8 @@ impl ErrorPayload {
         }
     }
 
-    /// Returns the contained GraphQLError.
-    pub fn graphql_error<'a>(&'a self) -> &GraphQLError<'a> {
+    /// Returns the contained [`RequestError`].
+    pub fn graphql_error<'a>(&'a self) -> &RequestError<'a> {
         &self.error
     }
 }
@@ -77,8 +77,8 @@ impl Serialize for ErrorPayload {
     }
 }
 
-impl From<GraphQLError<'static>> for ErrorPayload {
-    fn from(error: GraphQLError<'static>) -> Self {
+impl From<RequestError<'static>> for ErrorPayload {
+    fn from(error: RequestError<'static>) -> Self {
         Self {
             _execution_params: None,
             error,
@@ -87,6 +87,13 @@ impl From<GraphQLError<'static>> for ErrorPayload {
     }
 }
 
+#[allow(deprecated)]
+impl From<juniper::GraphQLError<'static>> for ErrorPayload {
+    fn from(error: juniper::GraphQLError<'static>) -> Self {
+        RequestError::from(error).into()
+    }
+}
+
 /// ServerMessage defines the message types that servers can send.
 #[derive(Debug, Serialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -132,7 +139,7 @@ pub enum ServerMessage<S> {
 
 #[cfg(test)]
 mod test {
-    use juniper::{graphql_value, DefaultScalarValue};
+    use juniper::{graphql_value, DefaultScalarValue, OperationError};
 
     use super::*;
 
@@ -170,7 +177,7 @@ mod test {
         assert_eq!(
             serde_json::to_string(&ServerMessage::Error {
                 id: "foo".to_string(),
-                payload: GraphQLError::UnknownOperationName.into(),
+                payload: RequestError::Operation(OperationError::UnknownOperationName).into(),
             })
             .unwrap(),
             r##"{"type":"error","id":"foo","payload":[{"message":"Unknown operation"}]}"##,
@@ -0,0 +1,114 @@
+use std::{sync::Arc, time::Duration};
+
+use juniper::futures::future::{self, BoxFuture, Future, FutureExt as _};
+use tokio::{sync::watch, time::Instant};
+
+/// ShutdownCoordinator gracefully shuts down all the connections attached to it (via
+/// `Connection::with_shutdown()`), so deploys don't drop clients in the middle of a message.
+///
+/// Once shut down, the connections reject new operations and let the active ones run for a drain
+/// period. Then they send a "complete" message for each of the active operations and end, so the
+/// server integrations close their WebSockets.
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use juniper_graphql_ws::ShutdownCoordinator;
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let shutdown = ShutdownCoordinator::new();
+/// assert!(!shutdown.is_shutting_down());
+///
+/// // Resolves once all the attached connections have ended.
+/// shutdown.shutdown(Duration::from_secs(5)).await;
+/// assert!(shutdown.is_shutting_down());
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ShutdownCoordinator {
+    deadline: Arc<watch::Sender<Option<Instant>>>,
+}
+
+impl Default for ShutdownCoordinator {
+    fn default() -> Self {
+        let (tx, _) = watch::channel(None);
+        Self {
+            deadline: Arc::new(tx),
+        }
+    }
+}
+
+impl ShutdownCoordinator {
+    /// Constructs a new coordinator without any connections attached.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Initiates a graceful shutdown of all the attached connections, letting their active
+    /// operations run for the `drain` period. Shutting down again may only shorten the drain
+    /// period.
+    ///
+    /// The returned future resolves once all the attached connections have ended, so it may be
+    /// awaited before stopping the server.
+    pub fn shutdown(&self, drain: Duration) -> impl Future<Output = ()> + Send + 'static {
+        let deadline = Instant::now() + drain;
+        self.deadline.send_if_modified(|d| match d {
+            Some(d) if *d <= deadline => false,
+            _ => {
+                *d = Some(deadline);
+                true
+            }
+        });
+
+        let tx = self.deadline.clone();
+        async move { tx.closed().await }
+    }
+
+    /// Indicates whether a shutdown has been initiated.
+    pub fn is_shutting_down(&self) -> bool {
+        self.deadline.borrow().is_some()
+    }
+
+    /// Returns a new signal of a shutdown, attaching its holder to this coordinator.
+    pub(crate) fn signal(&self) -> ShutdownSignal {
+        ShutdownSignal(self.deadline.subscribe())
+    }
+}
+
+/// ShutdownSignal is held by a connection attached to a `ShutdownCoordinator`.
+#[derive(Clone, Debug)]
+pub(crate) struct ShutdownSignal(watch::Receiver<Option<Instant>>);
+
+impl ShutdownSignal {
+    /// Indicates whether a shutdown has been initiated.
+    pub(crate) fn is_shutting_down(&self) -> bool {
+        self.0.borrow().is_some()
+    }
+
+    /// Returns a future resolving once the drain period of an initiated shutdown has elapsed.
+    pub(crate) fn drained(&self) -> BoxFuture<'static, ()> {
+        let mut rx = self.0.clone();
+        async move {
+            loop {
+                let deadline = *rx.borrow_and_update();
+                match deadline {
+                    Some(deadline) => tokio::select! {
+                        _ = tokio::time::sleep_until(deadline) => return,
+                        // The drain period may be shortened by shutting down again.
+                        res = rx.changed() => if res.is_err() {
+                            return tokio::time::sleep_until(deadline).await;
+                        },
+                    },
+                    None => {
+                        if rx.changed().await.is_err() {
+                            // The coordinator is gone, so no shutdown is going to happen.
+                            return future::pending().await;
+                        }
+                    }
+                }
+            }
+        }
+        .boxed()
+    }
+}
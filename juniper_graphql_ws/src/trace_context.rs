@@ -0,0 +1,178 @@
+use std::fmt;
+
+use juniper::{ScalarValue, Variables};
+
+/// TraceContext is a [W3C Trace Context][1] propagated from the client, either via the headers of
+/// the HTTP upgrade request or via the `extensions` of a "start" message. It's attached to the
+/// spans of the operations and their subscription events, so distributed traces cover push traffic
+/// too.
+///
+/// ```
+/// use juniper_graphql_ws::TraceContext;
+///
+/// let ctx = TraceContext::from_headers(|name| match name {
+///     TraceContext::TRACEPARENT_HEADER => {
+///         Some("00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01")
+///     }
+///     _ => None,
+/// })
+/// .unwrap();
+///
+/// assert_eq!(ctx.trace_id(), "0af7651916cd43dd8448eb211c80319c");
+/// assert_eq!(ctx.parent_id(), "b7ad6b7169203331");
+/// assert!(ctx.is_sampled());
+/// ```
+///
+/// [1]: https://www.w3.org/TR/trace-context
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TraceContext {
+    trace_id: String,
+    parent_id: String,
+    flags: u8,
+    tracestate: Option<String>,
+}
+
+impl TraceContext {
+    /// Name of the header (or `extensions` entry) holding the `traceparent`.
+    pub const TRACEPARENT_HEADER: &'static str = "traceparent";
+
+    /// Name of the header (or `extensions` entry) holding the vendor-specific `tracestate`.
+    pub const TRACESTATE_HEADER: &'static str = "tracestate";
+
+    /// Parses the value of a `traceparent` header. Returns `None` if it's malformed.
+    pub fn parse(traceparent: &str) -> Option<Self> {
+        let mut parts = traceparent.trim().split('-');
+        let version = parts.next().filter(|v| is_hex(v, 2) && *v != "ff")?;
+        let trace_id = parts.next().filter(|id| is_non_zero_hex(id, 32))?;
+        let parent_id = parts.next().filter(|id| is_non_zero_hex(id, 16))?;
+        let flags = parts.next().filter(|f| is_hex(f, 2))?;
+        // Version 00 has no more fields, while future versions may append some.
+        if version == "00" && parts.next().is_some() {
+            return None;
+        }
+        Some(Self {
+            trace_id: trace_id.into(),
+            parent_id: parent_id.into(),
+            flags: u8::from_str_radix(flags, 16).ok()?,
+            tracestate: None,
+        })
+    }
+
+    /// Extracts the [`TraceContext`] from the request headers, looked up by their lowercase names
+    /// via the provided `header` function.
+    pub fn from_headers<'h>(header: impl Fn(&str) -> Option<&'h str>) -> Option<Self> {
+        let ctx = Self::parse(header(Self::TRACEPARENT_HEADER)?)?;
+        Some(match header(Self::TRACESTATE_HEADER) {
+            Some(state) => ctx.with_tracestate(state),
+            None => ctx,
+        })
+    }
+
+    /// Extracts the [`TraceContext`] from the `extensions` of a "start" message, holding it as
+    /// `traceparent` and `tracestate` string entries.
+    pub fn from_extensions<S: ScalarValue>(extensions: &Variables<S>) -> Option<Self> {
+        Self::from_headers(|name| extensions.get(name)?.as_string_value())
+    }
+
+    /// Attaches the vendor-specific `tracestate` to this [`TraceContext`].
+    #[must_use]
+    pub fn with_tracestate(mut self, tracestate: impl Into<String>) -> Self {
+        let tracestate = tracestate.into();
+        self.tracestate = (!tracestate.trim().is_empty()).then_some(tracestate);
+        self
+    }
+
+    /// Returns the ID of the whole trace, as 32 lowercase hex digits.
+    pub fn trace_id(&self) -> &str {
+        &self.trace_id
+    }
+
+    /// Returns the ID of the client's span, as 16 lowercase hex digits.
+    pub fn parent_id(&self) -> &str {
+        &self.parent_id
+    }
+
+    /// Indicates whether the client has recorded the trace.
+    pub fn is_sampled(&self) -> bool {
+        self.flags & 0x01 != 0
+    }
+
+    /// Returns the vendor-specific `tracestate`, if any.
+    pub fn tracestate(&self) -> Option<&str> {
+        self.tracestate.as_deref()
+    }
+}
+
+/// Formats this [`TraceContext`] as a version `00` `traceparent` header value.
+impl fmt::Display for TraceContext {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "00-{}-{}-{:02x}",
+            self.trace_id, self.parent_id, self.flags
+        )
+    }
+}
+
+fn is_hex(s: &str, len: usize) -> bool {
+    s.len() == len && s.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+fn is_non_zero_hex(s: &str, len: usize) -> bool {
+    is_hex(s, len) && s.bytes().any(|b| b != b'0')
+}
+
+#[cfg(test)]
+mod test {
+    use juniper::{graphql_vars, DefaultScalarValue, Variables};
+
+    use super::TraceContext;
+
+    const TRACEPARENT: &str = "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01";
+
+    #[test]
+    fn test_parse() {
+        let ctx = TraceContext::parse(TRACEPARENT).unwrap();
+        assert_eq!(ctx.trace_id(), "0af7651916cd43dd8448eb211c80319c");
+        assert_eq!(ctx.parent_id(), "b7ad6b7169203331");
+        assert!(ctx.is_sampled());
+        assert_eq!(ctx.tracestate(), None);
+        assert_eq!(ctx.to_string(), TRACEPARENT);
+
+        let ctx =
+            TraceContext::parse("01-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-00-future")
+                .unwrap();
+        assert!(!ctx.is_sampled());
+
+        for value in [
+            "",
+            "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331",
+            "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01-extra",
+            "ff-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01",
+            "00-0AF7651916CD43DD8448EB211C80319C-b7ad6b7169203331-01",
+            "00-00000000000000000000000000000000-b7ad6b7169203331-01",
+            "00-0af7651916cd43dd8448eb211c80319c-0000000000000000-01",
+            "00-0af7651916cd43dd8448eb211c80319c-b7ad6b716920333-01",
+        ] {
+            assert_eq!(TraceContext::parse(value), None, "value: {}", value);
+        }
+    }
+
+    #[test]
+    fn test_from_extensions() {
+        let extensions: Variables<DefaultScalarValue> = graphql_vars! {
+            "traceparent": (TRACEPARENT),
+            "tracestate": "congo=t61rcWkgMzE",
+        };
+        let ctx = TraceContext::from_extensions(&extensions).unwrap();
+        assert_eq!(ctx.parent_id(), "b7ad6b7169203331");
+        assert_eq!(ctx.tracestate(), Some("congo=t61rcWkgMzE"));
+
+        let extensions: Variables<DefaultScalarValue> = graphql_vars! {"traceparent": 1};
+        assert_eq!(TraceContext::from_extensions(&extensions), None);
+        assert_eq!(
+            TraceContext::from_extensions::<DefaultScalarValue>(&graphql_vars! {}),
+            None,
+        );
+    }
+}
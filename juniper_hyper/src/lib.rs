@@ -7,8 +7,12 @@ use hyper::{
     Body, Method, Request, Response, StatusCode,
 };
 use juniper::{
-    http::{GraphQLBatchRequest, GraphQLRequest as JuniperGraphQLRequest, GraphQLRequest},
-    GraphQLSubscriptionType, GraphQLType, GraphQLTypeAsync, InputValue, RootNode, ScalarValue,
+    http::{
+        ETag, GraphQLBatchRequest, GraphQLRequest as JuniperGraphQLRequest, GraphQLRequest,
+        RequestDeadline,
+    },
+    GraphQLSubscriptionType, GraphQLType, GraphQLTypeAsync, InputValue, Limits, RootNode,
+    ScalarValue,
 };
 use serde_json::error::Error as SerdeError;
 use url::form_urlencoded;
@@ -28,8 +32,10 @@ where
     CtxT: Sync,
     S: ScalarValue + Send + Sync,
 {
+    let cache = CacheConditions::of(&req);
+    let limits = request_limits(root_node.schema.limits(), &req);
     match parse_req(req).await {
-        Ok(req) => execute_request_sync(root_node, context, req).await,
+        Ok(req) => execute_request_sync(root_node, context, req, cache, limits).await,
         Err(resp) => resp,
     }
 }
@@ -49,8 +55,10 @@ where
     CtxT: Sync,
     S: ScalarValue + Send + Sync,
 {
+    let cache = CacheConditions::of(&req);
+    let limits = request_limits(root_node.schema.limits(), &req);
     match parse_req(req).await {
-        Ok(req) => execute_request(root_node, context, req).await,
+        Ok(req) => execute_request(root_node, context, req, cache, limits).await,
         Err(resp) => resp,
     }
 }
@@ -154,6 +162,8 @@ async fn execute_request_sync<CtxT, QueryT, MutationT, SubscriptionT, S>(
     root_node: Arc<RootNode<'static, QueryT, MutationT, SubscriptionT, S>>,
     context: Arc<CtxT>,
     request: GraphQLBatchRequest<S>,
+    cache: Option<CacheConditions>,
+    limits: Limits,
 ) -> Response<Body>
 where
     QueryT: GraphQLType<S, Context = CtxT>,
@@ -165,26 +175,20 @@ where
     CtxT: Sync,
     S: ScalarValue + Send + Sync,
 {
-    let res = request.execute_sync(&*root_node, &context);
-    let body = Body::from(serde_json::to_string_pretty(&res).unwrap());
-    let code = if res.is_ok() {
-        StatusCode::OK
-    } else {
-        StatusCode::BAD_REQUEST
-    };
-    let mut resp = new_response(code);
-    resp.headers_mut().insert(
-        header::CONTENT_TYPE,
-        HeaderValue::from_static("application/json"),
-    );
-    *resp.body_mut() = body;
-    resp
+    let res = request.execute_sync_with_limits(&*root_node, &context, &limits);
+    new_graphql_response(
+        serde_json::to_string_pretty(&res).unwrap(),
+        res.is_ok(),
+        cache.filter(|_| !res.has_errors()),
+    )
 }
 
 async fn execute_request<CtxT, QueryT, MutationT, SubscriptionT, S>(
     root_node: Arc<RootNode<'static, QueryT, MutationT, SubscriptionT, S>>,
     context: Arc<CtxT>,
     request: GraphQLBatchRequest<S>,
+    cache: Option<CacheConditions>,
+    limits: Limits,
 ) -> Response<Body>
 where
     QueryT: GraphQLTypeAsync<S, Context = CtxT>,
@@ -196,19 +200,70 @@ where
     CtxT: Sync,
     S: ScalarValue + Send + Sync,
 {
-    let res = request.execute(&*root_node, &context).await;
-    let body = Body::from(serde_json::to_string_pretty(&res).unwrap());
-    let code = if res.is_ok() {
-        StatusCode::OK
+    let res = request
+        .execute_with_limits(&*root_node, &context, &limits)
+        .await;
+    new_graphql_response(
+        serde_json::to_string_pretty(&res).unwrap(),
+        res.is_ok(),
+        cache.filter(|_| !res.has_errors()),
+    )
+}
+
+/// Returns the given schema `limits` with the deadline propagated in the
+/// headers of the given request applied.
+fn request_limits(limits: &Limits, req: &Request<Body>) -> Limits {
+    RequestDeadline::from_headers(|name| req.headers().get(name).and_then(|v| v.to_str().ok()))
+        .map_or(*limits, |deadline| deadline.limits(*limits))
+}
+
+/// Conditions of a `GET` request allowing to cache its response.
+struct CacheConditions {
+    if_none_match: Option<HeaderValue>,
+}
+
+impl CacheConditions {
+    /// Extracts the [`CacheConditions`] of the given request, if it's a `GET`
+    /// one.
+    fn of(req: &Request<Body>) -> Option<Self> {
+        (req.method() == Method::GET).then(|| Self {
+            if_none_match: req.headers().get(header::IF_NONE_MATCH).cloned(),
+        })
+    }
+}
+
+/// Creates a new [`Response`] with the given serialized GraphQL response
+/// `body`.
+///
+/// Successful responses to `GET` requests are tagged with an [`ETag`], and
+/// replaced with an empty `304 Not Modified` response if the client already
+/// has them cached. The `cache` conditions shouldn't be provided for responses
+/// having errors, so a transient error isn't cached by the client.
+fn new_graphql_response(
+    body: String,
+    is_ok: bool,
+    cache: Option<CacheConditions>,
+) -> Response<Body> {
+    if !is_ok {
+        return new_json_response(StatusCode::BAD_REQUEST, body);
+    }
+    let cache = match cache {
+        Some(cache) => cache,
+        None => return new_json_response(StatusCode::OK, body),
+    };
+
+    let etag = ETag::of(body.as_bytes());
+    let if_none_match = cache.if_none_match.as_ref().and_then(|v| v.to_str().ok());
+    let is_cached = matches!(if_none_match, Some(v) if etag.matches(v));
+    let mut resp = if is_cached {
+        new_response(StatusCode::NOT_MODIFIED)
     } else {
-        StatusCode::BAD_REQUEST
+        new_json_response(StatusCode::OK, body)
     };
-    let mut resp = new_response(code);
     resp.headers_mut().insert(
-        header::CONTENT_TYPE,
-        HeaderValue::from_static("application/json"),
+        header::ETAG,
+        HeaderValue::from_str(&etag.to_string()).expect("`ETag` is a valid header value"),
     );
-    *resp.body_mut() = body;
     resp
 }
 
@@ -267,6 +322,16 @@ fn new_response(code: StatusCode) -> Response<Body> {
     r
 }
 
+fn new_json_response(code: StatusCode, body: String) -> Response<Body> {
+    let mut resp = new_response(code);
+    resp.headers_mut().insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/json"),
+    );
+    *resp.body_mut() = Body::from(body);
+    resp
+}
+
 fn new_html_response(code: StatusCode) -> Response<Body> {
     let mut resp = new_response(code);
     resp.headers_mut().insert(
@@ -443,6 +508,120 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn propagates_deadline_from_headers() {
+        let root_node = Arc::new(RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        ));
+        let db = Arc::new(Database::new());
+
+        for is_sync in [false, true] {
+            let req = hyper::Request::post("/graphql")
+                .header("content-type", "application/graphql")
+                .header("grpc-timeout", "0n")
+                .body(Body::from("{ hero { name } }"))
+                .unwrap();
+            let resp = if is_sync {
+                super::graphql_sync(root_node.clone(), db.clone(), req).await
+            } else {
+                super::graphql(root_node.clone(), db.clone(), req).await
+            };
+
+            let body = hyper::body::to_bytes(resp.into_body()).await.unwrap();
+            let body = String::from_utf8(body.to_vec()).unwrap();
+            assert!(body.contains("Operation timed out"), "body: {}", body);
+        }
+    }
+
+    #[tokio::test]
+    async fn doesnt_tag_get_responses_with_errors() {
+        let root_node = Arc::new(RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        ));
+        let db = Arc::new(Database::new());
+
+        for is_sync in [false, true] {
+            let req = hyper::Request::get("/graphql?query=%7B%20hero%20%7B%20name%20%7D%20%7D")
+                .header("grpc-timeout", "0n")
+                .body(Body::empty())
+                .unwrap();
+            let resp = if is_sync {
+                super::graphql_sync(root_node.clone(), db.clone(), req).await
+            } else {
+                super::graphql(root_node.clone(), db.clone(), req).await
+            };
+
+            assert_eq!(resp.status(), StatusCode::OK);
+            assert!(resp.headers().get(hyper::header::ETAG).is_none());
+        }
+    }
+
+    #[test]
+    fn tags_get_responses_with_etag() {
+        use hyper::header::{self, HeaderValue};
+
+        use super::{new_graphql_response, CacheConditions};
+
+        let body = r#"{"data":{"hero":{"name":"R2-D2"}}}"#;
+
+        let resp = new_graphql_response(body.into(), true, None);
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert!(resp.headers().get(header::ETAG).is_none());
+
+        let resp = new_graphql_response(
+            body.into(),
+            false,
+            Some(CacheConditions {
+                if_none_match: None,
+            }),
+        );
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        assert!(resp.headers().get(header::ETAG).is_none());
+
+        let resp = new_graphql_response(
+            body.into(),
+            true,
+            Some(CacheConditions {
+                if_none_match: None,
+            }),
+        );
+        assert_eq!(resp.status(), StatusCode::OK);
+        let etag = resp.headers().get(header::ETAG).unwrap().clone();
+
+        let resp = new_graphql_response(
+            body.into(),
+            true,
+            Some(CacheConditions {
+                if_none_match: Some(etag.clone()),
+            }),
+        );
+        assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(resp.headers().get(header::ETAG), Some(&etag));
+
+        let resp = new_graphql_response(
+            "{}".into(),
+            true,
+            Some(CacheConditions {
+                if_none_match: Some(etag.clone()),
+            }),
+        );
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_ne!(resp.headers().get(header::ETAG), Some(&etag));
+
+        let resp = new_graphql_response(
+            body.into(),
+            true,
+            Some(CacheConditions {
+                if_none_match: Some(HeaderValue::from_static("*")),
+            }),
+        );
+        assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+    }
+
     #[tokio::test]
     async fn test_hyper_integration() {
         run_hyper_integration(false).await
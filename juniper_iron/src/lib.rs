@@ -11,8 +11,9 @@ use iron::{
     status,
 };
 use juniper::{
-    http, http::GraphQLBatchRequest, DefaultScalarValue, GraphQLType, InputValue, RootNode,
-    ScalarValue,
+    http,
+    http::{ETag, GraphQLBatchRequest, RequestDeadline},
+    DefaultScalarValue, GraphQLType, InputValue, Limits, RootNode, ScalarValue,
 };
 use serde_json::error::Error as SerdeError;
 use urlencoded::{UrlDecodingError, UrlEncodedQuery};
@@ -155,16 +156,32 @@ where
         &self,
         context: &CtxT,
         request: GraphQLBatchRequest<S>,
+        if_none_match: Option<Option<String>>,
+        limits: &Limits,
     ) -> IronResult<Response> {
-        let response = request.execute_sync(&self.root_node, context);
+        let response = request.execute_sync_with_limits(&self.root_node, context, limits);
         let content_type = "application/json".parse::<Mime>().unwrap();
         let json = serde_json::to_string_pretty(&response).unwrap();
-        let status = if response.is_ok() {
-            status::Ok
+        if !response.is_ok() {
+            return Ok(Response::with((content_type, status::BadRequest, json)));
+        }
+        let if_none_match = match if_none_match {
+            Some(if_none_match) if !response.has_errors() => if_none_match,
+            _ => return Ok(Response::with((content_type, status::Ok, json))),
+        };
+
+        // Successful responses to `GET` requests are cacheable, so are tagged
+        // to be served as `304 Not Modified` once the client has them cached.
+        // Responses having errors are not, as the errors may be transient.
+        let etag = ETag::of(json.as_bytes());
+        let mut resp = if matches!(if_none_match, Some(v) if etag.matches(&v)) {
+            Response::with(status::NotModified)
         } else {
-            status::BadRequest
+            Response::with((content_type, status::Ok, json))
         };
-        Ok(Response::with((content_type, status, json)))
+        resp.headers
+            .set_raw("ETag", vec![etag.to_string().into_bytes()]);
+        Ok(resp)
     }
 }
 
@@ -208,6 +225,22 @@ where
     fn handle(&self, req: &mut Request) -> IronResult<Response> {
         let context = (self.context_factory)(req)?;
 
+        // Only the responses to `GET` requests are cacheable.
+        let if_none_match = (req.method == method::Get).then(|| {
+            req.headers
+                .get_raw("If-None-Match")
+                .and_then(|v| String::from_utf8(v.join(&b", "[..])).ok())
+        });
+
+        let deadline = RequestDeadline::from_headers(|name| {
+            req.headers
+                .get_raw(name)
+                .and_then(|v| v.first())
+                .and_then(|v| std::str::from_utf8(v).ok())
+        });
+        let limits = self.root_node.schema.limits();
+        let limits = deadline.map_or(*limits, |deadline| deadline.limits(*limits));
+
         let graphql_request = match req.method {
             method::Get => self.handle_get(req)?,
             method::Post => match req.headers.get::<ContentType>().map(ContentType::deref) {
@@ -221,7 +254,7 @@ where
             _ => return Ok(Response::with(status::MethodNotAllowed)),
         };
 
-        self.execute_sync(&context, graphql_request)
+        self.execute_sync(&context, graphql_request, if_none_match, &limits)
     }
 }
 
@@ -365,6 +398,57 @@ mod tests {
         http_tests::run_http_test_suite(&integration);
     }
 
+    #[test]
+    fn test_get_honors_if_none_match() {
+        let url = fixup_url("/?query={hero{name}}");
+
+        let resp = request::get(&url, Headers::new(), &make_handler()).unwrap();
+        assert_eq!(resp.status, Some(status::Ok));
+        let etag = resp.headers.get_raw("ETag").unwrap()[0].clone();
+
+        let mut headers = Headers::new();
+        headers.set_raw("If-None-Match", vec![etag.clone()]);
+        let resp = request::get(&url, headers, &make_handler()).unwrap();
+        assert_eq!(resp.status, Some(status::NotModified));
+        assert_eq!(resp.headers.get_raw("ETag").unwrap()[0], etag);
+
+        let mut headers = Headers::new();
+        headers.set_raw("If-None-Match", vec![b"\"0000000000000000\"".to_vec()]);
+        let resp = request::get(&url, headers, &make_handler()).unwrap();
+        assert_eq!(resp.status, Some(status::Ok));
+        assert_eq!(
+            response::extract_body_to_string(resp),
+            r#"{
+  "data": {
+    "hero": {
+      "name": "R2-D2"
+    }
+  }
+}"#,
+        );
+    }
+
+    #[test]
+    fn test_get_doesnt_tag_responses_with_errors() {
+        let url = fixup_url("/?query={hero{name}}");
+
+        let mut headers = Headers::new();
+        headers.set_raw("grpc-timeout", vec![b"0n".to_vec()]);
+        let resp = request::get(&url, headers, &make_handler()).unwrap();
+        assert_eq!(resp.status, Some(status::Ok));
+        assert!(resp.headers.get_raw("ETag").is_none());
+    }
+
+    #[test]
+    fn test_propagates_deadline_from_headers() {
+        let mut headers = Headers::new();
+        headers.set_raw("grpc-timeout", vec![b"0n".to_vec()]);
+        let resp = request::get(&fixup_url("/?query={hero{name}}"), headers, &make_handler());
+
+        let body = response::extract_body_to_string(resp.unwrap());
+        assert!(body.contains("Operation timed out"), "body: {}", body);
+    }
+
     fn context_factory(_: &mut Request) -> IronResult<Database> {
         Ok(Database::new())
     }
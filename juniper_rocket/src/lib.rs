@@ -1,20 +1,21 @@
 #![doc = include_str!("../README.md")]
 
-use std::{borrow::Cow, io::Cursor};
+use std::{borrow::Cow, convert::Infallible, io::Cursor};
 
 use rocket::{
     data::{self, FromData, ToByteUnit},
     form::{error::ErrorKind, DataField, Error, Errors, FromForm, Options, ValueField},
-    http::{ContentType, Status},
+    http::{ContentType, Method, Status},
     outcome::Outcome::{Failure, Forward, Success},
+    request::{self, FromRequest},
     response::{self, content, Responder, Response},
     Data, Request,
 };
 
 use juniper::{
-    http::{self, GraphQLBatchRequest},
+    http::{self, ETag, GraphQLBatchRequest, RequestDeadline},
     DefaultScalarValue, FieldError, GraphQLSubscriptionType, GraphQLType, GraphQLTypeAsync,
-    InputValue, RootNode, ScalarValue,
+    InputValue, Limits, RootNode, ScalarValue,
 };
 
 /// Simple wrapper around an incoming GraphQL request
@@ -42,6 +43,51 @@ impl<S: ScalarValue> AsMut<GraphQLBatchRequest<S>> for GraphQLRequest<S> {
 /// Simple wrapper around the result of executing a GraphQL query
 pub struct GraphQLResponse(pub Status, pub String);
 
+/// Request guard extracting the deadline propagated from an upstream service
+/// in the `x-request-deadline` or `grpc-timeout` request headers.
+///
+/// See [`RequestDeadline`] for details.
+///
+/// ```
+/// # use rocket::State;
+/// #
+/// # use juniper::tests::fixtures::starwars::schema::{Database, Query};
+/// # use juniper::{EmptyMutation, EmptySubscription, RootNode};
+/// #
+/// # type Schema = RootNode<'static, Query, EmptyMutation<Database>, EmptySubscription<Database>>;
+/// #
+/// #[rocket::get("/graphql?<request..>")]
+/// fn get_graphql_handler(
+///     context: &State<Database>,
+///     deadline: juniper_rocket::Deadline,
+///     request: juniper_rocket::GraphQLRequest,
+///     schema: &State<Schema>,
+/// ) -> juniper_rocket::GraphQLResponse {
+///     let limits = deadline.limits(schema.schema.limits());
+///     request.execute_sync_with_limits(&*schema, &*context, &limits)
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Deadline(pub Option<RequestDeadline>);
+
+impl Deadline {
+    /// Returns the given schema `limits` with this [`Deadline`] applied.
+    pub fn limits(&self, limits: &Limits) -> Limits {
+        self.0.map_or(*limits, |deadline| deadline.limits(*limits))
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Deadline {
+    type Error = Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        Success(Deadline(RequestDeadline::from_headers(|name| {
+            req.headers().get_one(name)
+        })))
+    }
+}
+
 /// Generate an HTML page containing GraphiQL
 pub fn graphiql_source(
     graphql_endpoint_url: &str,
@@ -79,7 +125,23 @@ where
         MutationT: GraphQLType<S, Context = CtxT>,
         SubscriptionT: GraphQLType<S, Context = CtxT>,
     {
-        let response = self.0.execute_sync(root_node, context);
+        self.execute_sync_with_limits(root_node, context, root_node.schema.limits())
+    }
+
+    /// Synchronously execute an incoming GraphQL query, overriding the default
+    /// [`Limits`] of the schema.
+    pub fn execute_sync_with_limits<CtxT, QueryT, MutationT, SubscriptionT>(
+        &self,
+        root_node: &RootNode<QueryT, MutationT, SubscriptionT, S>,
+        context: &CtxT,
+        limits: &Limits,
+    ) -> GraphQLResponse
+    where
+        QueryT: GraphQLType<S, Context = CtxT>,
+        MutationT: GraphQLType<S, Context = CtxT>,
+        SubscriptionT: GraphQLType<S, Context = CtxT>,
+    {
+        let response = self.0.execute_sync_with_limits(root_node, context, limits);
         let status = if response.is_ok() {
             Status::Ok
         } else {
@@ -106,7 +168,29 @@ where
         CtxT: Sync,
         S: Send + Sync,
     {
-        let response = self.0.execute(root_node, context).await;
+        self.execute_with_limits(root_node, context, root_node.schema.limits())
+            .await
+    }
+
+    /// Asynchronously execute an incoming GraphQL query, overriding the default
+    /// [`Limits`] of the schema.
+    pub async fn execute_with_limits<CtxT, QueryT, MutationT, SubscriptionT>(
+        &self,
+        root_node: &RootNode<'_, QueryT, MutationT, SubscriptionT, S>,
+        context: &CtxT,
+        limits: &Limits,
+    ) -> GraphQLResponse
+    where
+        QueryT: GraphQLTypeAsync<S, Context = CtxT>,
+        QueryT::TypeInfo: Sync,
+        MutationT: GraphQLTypeAsync<S, Context = CtxT>,
+        MutationT::TypeInfo: Sync,
+        SubscriptionT: GraphQLSubscriptionType<S, Context = CtxT>,
+        SubscriptionT::TypeInfo: Sync,
+        CtxT: Sync,
+        S: Send + Sync,
+    {
+        let response = self.0.execute_with_limits(root_node, context, limits).await;
         let status = if response.is_ok() {
             Status::Ok
         } else {
@@ -335,9 +419,29 @@ where
 }
 
 impl<'r, 'o: 'r> Responder<'r, 'o> for GraphQLResponse {
-    fn respond_to(self, _req: &'r Request<'_>) -> response::Result<'o> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
         let GraphQLResponse(status, body) = self;
 
+        // Successful responses to `GET` requests are cacheable, so are tagged
+        // to be served as `304 Not Modified` once the client has them cached.
+        // Responses having errors are not, as the errors may be transient.
+        if req.method() == Method::Get && status == Status::Ok && !has_errors(&body) {
+            let etag = ETag::of(body.as_bytes());
+            let if_none_match = req.headers().get_one("If-None-Match");
+            if matches!(if_none_match, Some(v) if etag.matches(v)) {
+                return Response::build()
+                    .status(Status::NotModified)
+                    .raw_header("ETag", etag.to_string())
+                    .ok();
+            }
+            return Response::build()
+                .header(ContentType::new("application", "json"))
+                .raw_header("ETag", etag.to_string())
+                .status(status)
+                .sized_body(body.len(), Cursor::new(body))
+                .ok();
+        }
+
         Response::build()
             .header(ContentType::new("application", "json"))
             .status(status)
@@ -346,6 +450,16 @@ impl<'r, 'o: 'r> Responder<'r, 'o> for GraphQLResponse {
     }
 }
 
+/// Indicates whether the given serialized GraphQL response `body` (either a
+/// single or a batch one) has any errors.
+fn has_errors(body: &str) -> bool {
+    match serde_json::from_str(body) {
+        Ok(serde_json::Value::Array(resps)) => resps.iter().any(|r| r.get("errors").is_some()),
+        Ok(resp) => resp.get("errors").is_some(),
+        Err(_) => true,
+    }
+}
+
 #[cfg(test)]
 mod fromform_tests {
     use super::*;
@@ -588,6 +702,59 @@ mod tests {
         assert_eq!(resp.await.status_code, 200);
     }
 
+    #[rocket::async_test]
+    async fn test_get_honors_if_none_match() {
+        use rocket::http::{Header, Status};
+
+        let client = Client::untracked(make_rocket())
+            .await
+            .expect("valid rocket");
+        let uri = "/?query=%7B%20hero%20%7B%20name%20%7D%20%7D";
+
+        let resp = client.get(uri).dispatch().await;
+        assert_eq!(resp.status(), Status::Ok);
+        let etag = resp.headers().get_one("ETag").unwrap().to_owned();
+
+        let resp = client
+            .get(uri)
+            .header(Header::new("If-None-Match", etag.clone()))
+            .dispatch()
+            .await;
+        assert_eq!(resp.status(), Status::NotModified);
+        assert_eq!(resp.headers().get_one("ETag"), Some(etag.as_str()));
+
+        let resp = client
+            .get(uri)
+            .header(Header::new("If-None-Match", "\"0000000000000000\""))
+            .dispatch()
+            .await;
+        assert_eq!(resp.status(), Status::Ok);
+        assert_eq!(
+            resp.into_string().await.unwrap(),
+            r#"{"data":{"hero":{"name":"R2-D2"}}}"#,
+        );
+    }
+
+    #[rocket::async_test]
+    async fn test_get_doesnt_tag_responses_with_errors() {
+        use rocket::http::Status;
+
+        #[get("/")]
+        fn get_graphql_error_handler() -> super::GraphQLResponse {
+            super::GraphQLResponse(
+                Status::Ok,
+                r#"{"data":null,"errors":[{"message":"Timed out"}]}"#.into(),
+            )
+        }
+
+        let rocket = make_rocket_without_routes().mount("/", routes![get_graphql_error_handler]);
+        let client = Client::untracked(rocket).await.expect("valid rocket");
+
+        let resp = client.get("/").dispatch().await;
+        assert_eq!(resp.status(), Status::Ok);
+        assert_eq!(resp.headers().get_one("ETag"), None);
+    }
+
     fn make_rocket() -> Rocket<Build> {
         make_rocket_without_routes().mount("/", routes![post_graphql_handler, get_graphql_handler])
     }
@@ -0,0 +1,52 @@
+//! Transformation of the events of subscriptions.
+
+use std::{pin::Pin, sync::Arc};
+
+use futures::Stream;
+use juniper::ExecutionOutput;
+
+/// Stream of the events of a subscription, resolved into whole responses.
+pub type EventStream<'a, S> = Pin<Box<dyn Stream<Item = ExecutionOutput<S>> + Send + 'a>>;
+
+/// Layer transforming the events of a subscription, applied between its
+/// resolver stream and the transport encoding.
+///
+/// Applied to every single subscription, so may keep a per-subscription state
+/// (like a sequence number of events), re-check permissions of the context on
+/// each event, or debounce/throttle them.
+///
+/// ```
+/// # use juniper::ExecutionOutput;
+/// # use juniper_subscriptions::{EventLayer, EventStream};
+/// # use futures::{future, StreamExt as _};
+/// #
+/// struct Context {
+///     is_banned: bool,
+/// }
+///
+/// /// Stops delivering events once the user is banned.
+/// struct StopOnBan;
+///
+/// impl<S: Send + 'static> EventLayer<Context, S> for StopOnBan {
+///     fn layer<'a>(&'a self, ctx: &'a Context, events: EventStream<'a, S>) -> EventStream<'a, S> {
+///         events.take_while(move |_| future::ready(!ctx.is_banned)).boxed()
+///     }
+/// }
+/// ```
+pub trait EventLayer<CtxT, S>: Send + Sync {
+    /// Wraps the `events` stream of a subscription with the provided
+    /// `context`.
+    fn layer<'a>(&'a self, context: &'a CtxT, events: EventStream<'a, S>) -> EventStream<'a, S>;
+}
+
+impl<CtxT, S, T: EventLayer<CtxT, S> + ?Sized> EventLayer<CtxT, S> for Arc<T> {
+    fn layer<'a>(&'a self, context: &'a CtxT, events: EventStream<'a, S>) -> EventStream<'a, S> {
+        (**self).layer(context, events)
+    }
+}
+
+impl<CtxT, S, T: EventLayer<CtxT, S> + ?Sized> EventLayer<CtxT, S> for Box<T> {
+    fn layer<'a>(&'a self, context: &'a CtxT, events: EventStream<'a, S>) -> EventStream<'a, S> {
+        (**self).layer(context, events)
+    }
+}
@@ -2,6 +2,10 @@
 #![deny(missing_docs)]
 #![deny(warnings)]
 
+mod layer;
+mod limits;
+pub mod polling;
+
 use std::{
     iter::FromIterator,
     pin::Pin,
@@ -10,14 +14,32 @@ use std::{
 
 use futures::{future, stream, FutureExt as _, Stream, StreamExt as _, TryFutureExt as _};
 use juniper::{
-    http::GraphQLRequest, BoxFuture, ExecutionError, ExecutionOutput, GraphQLError,
-    GraphQLSubscriptionType, GraphQLTypeAsync, Object, ScalarValue, SubscriptionConnection,
-    SubscriptionCoordinator, Value, ValuesStream,
+    http::GraphQLRequest, BoxFuture, ExecutionError, ExecutionOutput, GraphQLSubscriptionType,
+    GraphQLTypeAsync, Object, ScalarValue, SubscriptionConnection, SubscriptionCoordinator, Value,
+    ValuesStream,
+};
+
+use self::limits::{ActiveSubscriptions, Permit};
+
+pub use self::{
+    layer::{EventLayer, EventStream},
+    limits::{LimitExceeded, SubscribeError},
 };
 
+/// Function extracting the key of a subscription from its context, for
+/// limiting the number of active subscriptions per key.
+type KeyFn<CtxT> = Box<dyn Fn(&CtxT) -> Option<String> + Send + Sync>;
+
 /// Simple [`SubscriptionCoordinator`] implementation:
 /// - contains the schema
 /// - handles subscription start
+/// - limits the number of active subscriptions, in total and per key
+/// - transforms the events of subscriptions with [`EventLayer`]s
+///
+/// The number of active subscriptions is recorded into the [`MetricsSink`] of
+/// the schema, if any, every time a subscription is started or finished.
+///
+/// [`MetricsSink`]: juniper::MetricsSink
 pub struct Coordinator<'a, QueryT, MutationT, SubscriptionT, CtxT, S>
 where
     QueryT: GraphQLTypeAsync<S, Context = CtxT> + Send,
@@ -30,6 +52,10 @@ where
     S: ScalarValue + Send + Sync,
 {
     root_node: juniper::RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+    max_active: Option<usize>,
+    max_active_per_key: Option<(usize, KeyFn<CtxT>)>,
+    active: ActiveSubscriptions,
+    event_layers: Vec<Box<dyn EventLayer<CtxT, S>>>,
 }
 
 impl<'a, QueryT, MutationT, SubscriptionT, CtxT, S>
@@ -46,7 +72,59 @@ where
 {
     /// Builds new [`Coordinator`] with specified `root_node`
     pub fn new(root_node: juniper::RootNode<'a, QueryT, MutationT, SubscriptionT, S>) -> Self {
-        Self { root_node }
+        Self {
+            root_node,
+            max_active: None,
+            max_active_per_key: None,
+            active: ActiveSubscriptions::default(),
+            event_layers: Vec::new(),
+        }
+    }
+
+    /// Adds the [`EventLayer`] transforming the events of every subscription.
+    ///
+    /// Layers are applied in the order they're added, so the last added one
+    /// is the closest to the transport.
+    pub fn event_layer(mut self, layer: impl EventLayer<CtxT, S> + 'static) -> Self {
+        self.event_layers.push(Box::new(layer));
+        self
+    }
+
+    /// Limits the number of active subscriptions to the given `max`,
+    /// rejecting new ones with [`LimitExceeded::Global`] error once exhausted.
+    ///
+    /// Unlimited by default.
+    pub fn max_active(mut self, max: usize) -> Self {
+        self.max_active = Some(max);
+        self
+    }
+
+    /// Limits the number of active subscriptions sharing the same key (like a
+    /// user ID) to the given `max`, rejecting new ones with
+    /// [`LimitExceeded::PerKey`] error once exhausted.
+    ///
+    /// The key is extracted from the context of a subscription by the provided
+    /// `key` function. Subscriptions without a key are limited by
+    /// [`Coordinator::max_active()`] only.
+    ///
+    /// Unlimited by default.
+    pub fn max_active_per_key<F>(mut self, max: usize, key: F) -> Self
+    where
+        F: Fn(&CtxT) -> Option<String> + Send + Sync + 'static,
+    {
+        self.max_active_per_key = Some((max, Box::new(key)));
+        self
+    }
+
+    /// Returns the number of currently active subscriptions.
+    pub fn active(&self) -> usize {
+        self.active.total()
+    }
+
+    /// Returns the number of currently active subscriptions with the given
+    /// `key`.
+    pub fn active_per_key(&self, key: &str) -> usize {
+        self.active.per_key(key)
     }
 }
 
@@ -64,15 +142,39 @@ where
 {
     type Connection = Connection<'a, S>;
 
-    type Error = GraphQLError<'a>;
+    type Error = SubscribeError<'a>;
 
     fn subscribe(
         &'a self,
         req: &'a GraphQLRequest<S>,
         context: &'a CtxT,
     ) -> BoxFuture<'a, Result<Self::Connection, Self::Error>> {
+        let (max_per_key, key) = match &self.max_active_per_key {
+            Some((max, key)) => (Some(*max), key(context)),
+            None => (None, None),
+        };
+        let permit = match self.active.acquire(
+            key,
+            self.max_active,
+            max_per_key,
+            self.root_node.schema.executor_options().sink(),
+        ) {
+            Ok(permit) => permit,
+            Err(e) => return future::err(e.into()).boxed(),
+        };
+
         juniper::http::resolve_into_stream(req, &self.root_node, context)
-            .map_ok(|(stream, errors)| Connection::from_stream(stream, errors))
+            .map_ok(move |(stream, errors)| {
+                let mut conn = self
+                    .event_layers
+                    .iter()
+                    .fold(Connection::from_stream(stream, errors), |conn, layer| {
+                        conn.layered(layer, context)
+                    });
+                conn.permit = Some(permit);
+                conn
+            })
+            .map_err(Into::into)
             .boxed()
     }
 }
@@ -89,7 +191,8 @@ where
 /// [`Value::Object`] - waits while each field of the [`Object`] is returned, then yields the whole object
 /// `Value::Object<Value::Object<_>>` - returns [`Value::Null`] if [`Value::Object`] consists of sub-objects
 pub struct Connection<'a, S> {
-    stream: Pin<Box<dyn Stream<Item = ExecutionOutput<S>> + Send + 'a>>,
+    stream: EventStream<'a, S>,
+    permit: Option<Permit<'a>>,
 }
 
 impl<'a, S> Connection<'a, S>
@@ -100,8 +203,19 @@ where
     pub fn from_stream(stream: Value<ValuesStream<'a, S>>, errors: Vec<ExecutionError<S>>) -> Self {
         Self {
             stream: whole_responses_stream(stream, errors),
+            permit: None,
         }
     }
+
+    /// Applies the given [`EventLayer`] with the provided `context` to the
+    /// events of this [`Connection`].
+    pub fn layered<CtxT, L>(mut self, layer: &'a L, context: &'a CtxT) -> Self
+    where
+        L: EventLayer<CtxT, S> + ?Sized,
+    {
+        self.stream = layer.layer(context, self.stream);
+        self
+    }
 }
 
 impl<'a, S> SubscriptionConnection<S> for Connection<'a, S> where S: ScalarValue + Send + Sync + 'a {}
@@ -114,7 +228,7 @@ where
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
         // this is safe as stream is only mutated here and is not moved anywhere
-        let Connection { stream } = unsafe { self.get_unchecked_mut() };
+        let Connection { stream, .. } = unsafe { self.get_unchecked_mut() };
         let stream = unsafe { Pin::new_unchecked(stream) };
         stream.poll_next(cx)
     }
@@ -131,7 +245,7 @@ where
 fn whole_responses_stream<'a, S>(
     stream: Value<ValuesStream<'a, S>>,
     errors: Vec<ExecutionError<S>>,
-) -> Pin<Box<dyn Stream<Item = ExecutionOutput<S>> + Send + 'a>>
+) -> EventStream<'a, S>
 where
     S: ScalarValue + Send + Sync + 'a,
 {
@@ -392,3 +506,256 @@ mod whole_responses_stream {
         assert_eq!(result, expected);
     }
 }
+
+#[cfg(test)]
+mod coordinator_limits {
+    use std::sync::{Arc, Mutex};
+
+    use futures::{stream, Stream};
+    use juniper::{
+        graphql_object, graphql_subscription, DefaultScalarValue, EmptyMutation, ExecutorOptions,
+        MetricsSink, OperationMeasure, OperationType, RootNode,
+    };
+
+    use super::*;
+
+    struct Context(Option<&'static str>);
+
+    impl juniper::Context for Context {}
+
+    struct Query;
+
+    #[graphql_object(context = Context)]
+    impl Query {
+        fn ping() -> bool {
+            true
+        }
+    }
+
+    struct Subscription;
+
+    #[graphql_subscription(context = Context)]
+    impl Subscription {
+        async fn ping() -> Pin<Box<dyn Stream<Item = bool> + Send>> {
+            Box::pin(stream::pending())
+        }
+    }
+
+    type Schema = RootNode<'static, Query, EmptyMutation<Context>, Subscription>;
+
+    fn request() -> GraphQLRequest<DefaultScalarValue> {
+        GraphQLRequest::new("subscription { ping }".into(), None, None)
+    }
+
+    #[tokio::test]
+    async fn rejects_subscriptions_over_global_limit() {
+        let coordinator =
+            Coordinator::new(Schema::new(Query, EmptyMutation::new(), Subscription)).max_active(2);
+        let (req, ctx) = (request(), Context(None));
+
+        let first = coordinator.subscribe(&req, &ctx).await.unwrap();
+        let _second = coordinator.subscribe(&req, &ctx).await.unwrap();
+        assert_eq!(coordinator.active(), 2);
+
+        match coordinator.subscribe(&req, &ctx).await {
+            Err(SubscribeError::LimitExceeded(e)) => {
+                assert_eq!(e, LimitExceeded::Global { max: 2 });
+                assert_eq!(e.code(), "SUBSCRIPTION_LIMIT_EXCEEDED");
+            }
+            _ => panic!("expected `LimitExceeded::Global` error"),
+        }
+
+        drop(first);
+        assert_eq!(coordinator.active(), 1);
+        assert!(coordinator.subscribe(&req, &ctx).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn rejects_subscriptions_over_per_key_quota() {
+        let coordinator = Coordinator::new(Schema::new(Query, EmptyMutation::new(), Subscription))
+            .max_active_per_key(1, |ctx: &Context| ctx.0.map(Into::into));
+        let req = request();
+        let (alice, bob, anon) = (Context(Some("alice")), Context(Some("bob")), Context(None));
+
+        let _alice = coordinator.subscribe(&req, &alice).await.unwrap();
+        let _bob = coordinator.subscribe(&req, &bob).await.unwrap();
+        let _anon1 = coordinator.subscribe(&req, &anon).await.unwrap();
+        let _anon2 = coordinator.subscribe(&req, &anon).await.unwrap();
+
+        match coordinator.subscribe(&req, &alice).await {
+            Err(SubscribeError::LimitExceeded(e)) => assert_eq!(
+                e,
+                LimitExceeded::PerKey {
+                    key: "alice".into(),
+                    max: 1,
+                },
+            ),
+            _ => panic!("expected `LimitExceeded::PerKey` error"),
+        }
+        assert_eq!(coordinator.active(), 4);
+        assert_eq!(coordinator.active_per_key("alice"), 1);
+    }
+
+    #[tokio::test]
+    async fn releases_slot_of_failed_subscription() {
+        let coordinator =
+            Coordinator::new(Schema::new(Query, EmptyMutation::new(), Subscription)).max_active(1);
+        let req = GraphQLRequest::new("subscription { unknown }".into(), None, None);
+
+        assert!(matches!(
+            coordinator.subscribe(&req, &Context(None)).await,
+            Err(SubscribeError::Request(_)),
+        ));
+        assert_eq!(coordinator.active(), 0);
+    }
+
+    type Gauge = (usize, Option<(String, usize)>);
+
+    #[derive(Debug, Default)]
+    struct Gauges(Mutex<Vec<Gauge>>);
+
+    impl MetricsSink for Gauges {
+        fn record_measure(&self, _: Option<&str>, _: OperationType, _: &OperationMeasure) {}
+
+        fn record_active_subscriptions(&self, active: usize, key: Option<(&str, usize)>) {
+            self.0
+                .lock()
+                .unwrap()
+                .push((active, key.map(|(k, n)| (k.into(), n))));
+        }
+    }
+
+    #[tokio::test]
+    async fn records_gauges_into_metrics_sink() {
+        let gauges = Arc::new(Gauges::default());
+        let coordinator = Coordinator::new(
+            Schema::new(Query, EmptyMutation::new(), Subscription)
+                .with_executor_options(ExecutorOptions::new().metrics_sink(Arc::clone(&gauges))),
+        )
+        .max_active_per_key(2, |ctx: &Context| ctx.0.map(Into::into));
+        let req = request();
+        let (alice, anon) = (Context(Some("alice")), Context(None));
+
+        let first = coordinator.subscribe(&req, &alice).await.unwrap();
+        let second = coordinator.subscribe(&req, &anon).await.unwrap();
+        drop(first);
+        drop(second);
+
+        assert_eq!(
+            *gauges.0.lock().unwrap(),
+            [
+                (1, Some(("alice".into(), 1))),
+                (2, None),
+                (1, Some(("alice".into(), 0))),
+                (0, None),
+            ],
+        );
+    }
+}
+
+#[cfg(test)]
+mod coordinator_event_layers {
+    use futures::{future, stream, Stream};
+    use juniper::{
+        graphql_object, graphql_subscription, graphql_value, DefaultScalarValue, EmptyMutation,
+        RootNode,
+    };
+
+    use super::*;
+
+    struct Context {
+        hide_even: bool,
+    }
+
+    impl juniper::Context for Context {}
+
+    struct Query;
+
+    #[graphql_object(context = Context)]
+    impl Query {
+        fn ping() -> bool {
+            true
+        }
+    }
+
+    struct Subscription;
+
+    #[graphql_subscription(context = Context)]
+    impl Subscription {
+        async fn counter() -> Pin<Box<dyn Stream<Item = i32> + Send>> {
+            Box::pin(stream::iter(1..=4))
+        }
+    }
+
+    type Schema = RootNode<'static, Query, EmptyMutation<Context>, Subscription>;
+
+    struct HideEven;
+
+    impl EventLayer<Context, DefaultScalarValue> for HideEven {
+        fn layer<'a>(
+            &'a self,
+            ctx: &'a Context,
+            events: EventStream<'a, DefaultScalarValue>,
+        ) -> EventStream<'a, DefaultScalarValue> {
+            events
+                .filter(move |out| {
+                    let is_even = out.data == graphql_value!({"counter": 2})
+                        || out.data == graphql_value!({"counter": 4});
+                    future::ready(!(ctx.hide_even && is_even))
+                })
+                .boxed()
+        }
+    }
+
+    struct Sequence;
+
+    impl EventLayer<Context, DefaultScalarValue> for Sequence {
+        fn layer<'a>(
+            &'a self,
+            _: &'a Context,
+            events: EventStream<'a, DefaultScalarValue>,
+        ) -> EventStream<'a, DefaultScalarValue> {
+            events
+                .enumerate()
+                .map(|(n, mut out)| {
+                    if let Some(obj) = out.data.as_mut_object_value() {
+                        obj.add_field("seq", Value::scalar(n as i32));
+                    }
+                    out
+                })
+                .boxed()
+        }
+    }
+
+    #[tokio::test]
+    async fn applies_layers_in_order_per_subscription() {
+        let coordinator = Coordinator::new(Schema::new(Query, EmptyMutation::new(), Subscription))
+            .event_layer(HideEven)
+            .event_layer(Sequence);
+        let req = GraphQLRequest::new("subscription { counter }".into(), None, None);
+
+        for (ctx, expected) in [
+            (
+                Context { hide_even: true },
+                vec![
+                    graphql_value!({"counter": 1, "seq": 0}),
+                    graphql_value!({"counter": 3, "seq": 1}),
+                ],
+            ),
+            (
+                Context { hide_even: false },
+                vec![
+                    graphql_value!({"counter": 1, "seq": 0}),
+                    graphql_value!({"counter": 2, "seq": 1}),
+                    graphql_value!({"counter": 3, "seq": 2}),
+                    graphql_value!({"counter": 4, "seq": 3}),
+                ],
+            ),
+        ] {
+            let conn = coordinator.subscribe(&req, &ctx).await.unwrap();
+            let data = conn.map(|out| out.data).collect::<Vec<_>>().await;
+
+            assert_eq!(data, expected);
+        }
+    }
+}
@@ -0,0 +1,202 @@
+//! Limits of active subscriptions of a [`Coordinator`].
+//!
+//! [`Coordinator`]: crate::Coordinator
+
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Mutex, PoisonError},
+};
+
+use juniper::{MetricsSink, RequestError};
+
+/// Error of subscribing via a [`Coordinator`].
+///
+/// [`Coordinator`]: crate::Coordinator
+#[derive(Debug)]
+pub enum SubscribeError<'a> {
+    /// The subscription request couldn't be resolved into a stream.
+    Request(RequestError<'a>),
+
+    /// There are too many active subscriptions already.
+    LimitExceeded(LimitExceeded),
+}
+
+impl<'a> fmt::Display for SubscribeError<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Request(e) => write!(f, "{}", e),
+            Self::LimitExceeded(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl<'a> std::error::Error for SubscribeError<'a> {}
+
+impl<'a> From<RequestError<'a>> for SubscribeError<'a> {
+    fn from(e: RequestError<'a>) -> Self {
+        Self::Request(e)
+    }
+}
+
+impl<'a> From<LimitExceeded> for SubscribeError<'a> {
+    fn from(e: LimitExceeded) -> Self {
+        Self::LimitExceeded(e)
+    }
+}
+
+/// Limit of active subscriptions being exhausted.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LimitExceeded {
+    /// There are `max` active subscriptions already.
+    Global {
+        /// Maximum allowed number of active subscriptions.
+        max: usize,
+    },
+
+    /// There are `max` active subscriptions with the same `key` already.
+    PerKey {
+        /// Key of the rejected subscription.
+        key: String,
+
+        /// Maximum allowed number of active subscriptions with the same key.
+        max: usize,
+    },
+}
+
+impl LimitExceeded {
+    /// Returns the machine-readable code of this [`LimitExceeded`] error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Global { .. } => "SUBSCRIPTION_LIMIT_EXCEEDED",
+            Self::PerKey { .. } => "SUBSCRIPTION_QUOTA_EXCEEDED",
+        }
+    }
+}
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Global { max } => write!(
+                f,
+                "Too many active subscriptions, the maximum allowed number is {}",
+                max,
+            ),
+            Self::PerKey { key, max } => write!(
+                f,
+                "Too many active subscriptions of `{}`, the maximum allowed number is {}",
+                key, max,
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LimitExceeded {}
+
+/// Counters of active subscriptions, in total and per key.
+#[derive(Debug, Default)]
+pub(crate) struct ActiveSubscriptions {
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    total: usize,
+    per_key: HashMap<String, usize>,
+}
+
+impl ActiveSubscriptions {
+    /// Returns the number of active subscriptions, in total.
+    pub(crate) fn total(&self) -> usize {
+        self.lock().total
+    }
+
+    /// Returns the number of active subscriptions with the given `key`.
+    pub(crate) fn per_key(&self, key: &str) -> usize {
+        self.lock().per_key.get(key).copied().unwrap_or_default()
+    }
+
+    /// Registers a new active subscription with the given `key`, unless it
+    /// exceeds the `max` number of active subscriptions in total or the
+    /// `max_per_key` number of ones with the same `key`.
+    ///
+    /// The subscription stays active until the returned [`Permit`] is dropped.
+    /// The new numbers of active subscriptions are recorded into the provided
+    /// [`MetricsSink`], if any.
+    pub(crate) fn acquire<'a>(
+        &'a self,
+        key: Option<String>,
+        max: Option<usize>,
+        max_per_key: Option<usize>,
+        sink: Option<&'a dyn MetricsSink>,
+    ) -> Result<Permit<'a>, LimitExceeded> {
+        let (total, per_key) = {
+            let mut state = self.lock();
+            if let Some(max) = max.filter(|max| state.total >= *max) {
+                return Err(LimitExceeded::Global { max });
+            }
+            let per_key = match &key {
+                Some(key) => {
+                    let count = state.per_key.get(key).copied().unwrap_or_default();
+                    if let Some(max) = max_per_key.filter(|max| count >= *max) {
+                        return Err(LimitExceeded::PerKey {
+                            key: key.clone(),
+                            max,
+                        });
+                    }
+                    state.per_key.insert(key.clone(), count + 1);
+                    count + 1
+                }
+                None => 0,
+            };
+            state.total += 1;
+            (state.total, per_key)
+        };
+
+        if let Some(sink) = sink {
+            sink.record_active_subscriptions(total, key.as_deref().map(|k| (k, per_key)));
+        }
+
+        Ok(Permit {
+            active: self,
+            key,
+            sink,
+        })
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Registration of an active subscription in [`ActiveSubscriptions`], lasting
+/// until dropped.
+pub(crate) struct Permit<'a> {
+    active: &'a ActiveSubscriptions,
+    key: Option<String>,
+    sink: Option<&'a dyn MetricsSink>,
+}
+
+impl<'a> Drop for Permit<'a> {
+    fn drop(&mut self) {
+        let (total, per_key) = {
+            let mut state = self.active.lock();
+            state.total -= 1;
+            let per_key = self.key.as_ref().map_or(0, |key| {
+                let count = state.per_key.get_mut(key).map_or(0, |count| {
+                    *count -= 1;
+                    *count
+                });
+                if count == 0 {
+                    state.per_key.remove(key);
+                }
+                count
+            });
+            (state.total, per_key)
+        };
+
+        if let Some(sink) = self.sink {
+            sink.record_active_subscriptions(total, self.key.as_deref().map(|k| (k, per_key)));
+        }
+    }
+}
@@ -0,0 +1,665 @@
+//! Long-polling transport of subscriptions, for environments where neither
+//! WebSockets nor SSE are available.
+//!
+//! The server buffers the events of every subscription under its ID, while
+//! the client repeatedly polls them with a cursor, acknowledging the events
+//! it has already received. Subscriptions not polled for longer than their
+//! TTL are dropped.
+//!
+//! Subscriptions are started via a [`Coordinator`], so its limits and
+//! [`EventLayer`]s apply to them the same way as to the ones served over any
+//! other transport.
+//!
+//! [`EventLayer`]: crate::EventLayer
+
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+use futures::{
+    channel::{mpsc, oneshot},
+    future::{self, BoxFuture},
+    lock::Mutex as AsyncMutex,
+    stream, FutureExt as _, SinkExt as _, StreamExt as _,
+};
+use juniper::{
+    http::GraphQLRequest, ExecutionError, ExecutionOutput, FieldError, GraphQLSubscriptionType,
+    GraphQLTypeAsync, ScalarValue, SubscriptionCoordinator as _, Value,
+};
+use serde::Serialize;
+
+use crate::{Coordinator, EventStream, LimitExceeded, SubscribeError};
+
+/// Registry of subscriptions served over long-polling, buffering their events
+/// until acknowledged by a client.
+///
+/// Runtime-agnostic: the events of a subscription are pulled from its stream
+/// only while it's being polled with [`PollingSubscriptions::poll()`], which
+/// waits for the next event if there are no buffered ones. So, an HTTP
+/// integration should limit the waiting with a timeout of its runtime, and
+/// respond with an empty [`Batch`] once it's elapsed. No events are lost in
+/// such case.
+pub struct PollingSubscriptions<S> {
+    ttl: Duration,
+    max_batch_size: usize,
+    subscriptions: Mutex<HashMap<String, Arc<Entry<S>>>>,
+}
+
+impl<S> fmt::Debug for PollingSubscriptions<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PollingSubscriptions")
+            .field("ttl", &self.ttl)
+            .field("max_batch_size", &self.max_batch_size)
+            .field("len", &self.lock().len())
+            .finish()
+    }
+}
+
+/// Subscription registered in [`PollingSubscriptions`].
+struct Entry<S> {
+    /// Moment the subscription was polled last time.
+    last_polled: Mutex<Instant>,
+
+    /// Buffered events and the stream they're pulled from.
+    state: AsyncMutex<State<S>>,
+}
+
+struct State<S> {
+    /// Stream of the events, being `None` once ended.
+    events: Option<EventStream<'static, S>>,
+
+    /// Events not acknowledged by the client yet.
+    buffer: VecDeque<ExecutionOutput<S>>,
+
+    /// Cursor of the first event in the `buffer`.
+    cursor: u64,
+}
+
+impl<S> State<S> {
+    /// Returns the cursor following the last buffered event.
+    fn next_cursor(&self) -> u64 {
+        self.cursor + self.buffer.len() as u64
+    }
+}
+
+impl<S> PollingSubscriptions<S>
+where
+    S: ScalarValue + Send + Sync + 'static,
+{
+    /// Creates new [`PollingSubscriptions`], dropping subscriptions not
+    /// polled for longer than the given `ttl`.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            max_batch_size: 100,
+            subscriptions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Limits the number of events returned in a single [`Batch`].
+    ///
+    /// `100` by default.
+    pub fn max_batch_size(mut self, max: usize) -> Self {
+        self.max_batch_size = max.max(1);
+        self
+    }
+
+    /// Starts a new subscription resolving the given `request` with the
+    /// provided `context` via the `coordinator`, and returns its ID.
+    ///
+    /// The subscription holds its slot in the limits of the `coordinator`
+    /// until it ends, or is stopped or expired. If the `request` cannot be
+    /// resolved, the subscription yields a single event with the error.
+    ///
+    /// The ID consists of 128 random bits, so it can't be guessed by clients
+    /// other than the one which started the subscription.
+    ///
+    /// # Errors
+    ///
+    /// If the subscription exceeds the limits of active subscriptions of the
+    /// `coordinator`.
+    pub async fn start<QueryT, MutationT, SubscriptionT, CtxT>(
+        &self,
+        coordinator: &Arc<Coordinator<'static, QueryT, MutationT, SubscriptionT, CtxT, S>>,
+        request: GraphQLRequest<S>,
+        context: CtxT,
+    ) -> Result<String, LimitExceeded>
+    where
+        QueryT: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+        QueryT::TypeInfo: Send + Sync,
+        MutationT: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+        MutationT::TypeInfo: Send + Sync,
+        SubscriptionT: GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+        SubscriptionT::TypeInfo: Send + Sync,
+        CtxT: Send + Sync + 'static,
+    {
+        let events = resolve_into_owned_stream(Arc::clone(coordinator), request, context).await?;
+        Ok(self.register(events))
+    }
+
+    /// Registers a new subscription buffering the given `events`, and returns
+    /// its ID.
+    fn register(&self, events: EventStream<'static, S>) -> String {
+        self.prune();
+
+        let id = random_id();
+        let entry = Entry {
+            last_polled: Mutex::new(Instant::now()),
+            state: AsyncMutex::new(State {
+                events: Some(events),
+                buffer: VecDeque::new(),
+                cursor: 0,
+            }),
+        };
+        self.lock().insert(id.clone(), Arc::new(entry));
+        id
+    }
+
+    /// Polls the events of the subscription with the given `id`, following
+    /// the given `cursor`.
+    ///
+    /// The `cursor` acknowledges all the events preceding it, so they're not
+    /// returned anymore. Polling with the same `cursor` again returns the
+    /// same buffered events (plus the new ones, if any), so no events are lost
+    /// if a response doesn't reach the client. Polling starts with the cursor
+    /// of `0`, and the next one is returned in the [`Batch`].
+    ///
+    /// Waits for the next event, if there are no buffered ones. Once the
+    /// subscription has ended and all its events have been acknowledged, it's
+    /// removed.
+    pub async fn poll(&self, id: &str, cursor: u64) -> Result<Batch<S>, PollError> {
+        self.prune();
+
+        let entry = self
+            .lock()
+            .get(id)
+            .cloned()
+            .ok_or(PollError::UnknownSubscription)?;
+        entry.touch();
+
+        let mut state = entry.state.lock().await;
+        entry.touch();
+
+        let next = state.next_cursor();
+        if cursor < state.cursor || cursor > next {
+            return Err(PollError::InvalidCursor {
+                cursor,
+                expected: state.cursor..=next,
+            });
+        }
+        for _ in state.cursor..cursor {
+            state.buffer.pop_front();
+        }
+        state.cursor = cursor;
+
+        if state.buffer.is_empty() {
+            if let Some(events) = &mut state.events {
+                match events.next().await {
+                    Some(event) => state.buffer.push_back(event),
+                    None => state.events = None,
+                }
+            }
+        }
+        while state.buffer.len() < self.max_batch_size {
+            let events = match &mut state.events {
+                Some(events) => events,
+                None => break,
+            };
+            match events.next().now_or_never() {
+                Some(Some(event)) => state.buffer.push_back(event),
+                Some(None) => state.events = None,
+                None => break,
+            }
+        }
+        entry.touch();
+
+        let complete = state.events.is_none();
+        if complete && state.buffer.is_empty() {
+            self.lock().remove(id);
+        }
+
+        Ok(Batch {
+            events: state
+                .buffer
+                .iter()
+                .take(self.max_batch_size)
+                .cloned()
+                .collect(),
+            cursor: state.cursor + state.buffer.len().min(self.max_batch_size) as u64,
+            complete: complete && state.buffer.len() <= self.max_batch_size,
+        })
+    }
+
+    /// Stops the subscription with the given `id`, dropping its buffered
+    /// events.
+    ///
+    /// Returns `false` if there is no such subscription.
+    pub fn stop(&self, id: &str) -> bool {
+        self.lock().remove(id).is_some()
+    }
+
+    /// Returns the number of registered subscriptions.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Indicates whether there are no registered subscriptions.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Drops the subscriptions not polled for longer than the TTL.
+    ///
+    /// Called on every [`PollingSubscriptions::start()`] and
+    /// [`PollingSubscriptions::poll()`] automatically, releasing the slots of
+    /// the dropped subscriptions in the limits of their [`Coordinator`].
+    pub fn prune(&self) {
+        let ttl = self.ttl;
+        self.lock().retain(|_, entry| {
+            // Subscriptions being polled right now are never expired.
+            entry.state.try_lock().is_none() || entry.last_polled().elapsed() <= ttl
+        });
+    }
+}
+
+impl<S> PollingSubscriptions<S> {
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Arc<Entry<S>>>> {
+        self.subscriptions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<S> Entry<S> {
+    fn touch(&self) {
+        *self
+            .last_polled
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Instant::now();
+    }
+
+    fn last_polled(&self) -> Instant {
+        *self
+            .last_polled
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Events of a subscription returned by [`PollingSubscriptions::poll()`].
+#[derive(Debug, Serialize)]
+pub struct Batch<S> {
+    /// Events following the polled cursor.
+    #[serde(bound(serialize = "S: ScalarValue"))]
+    pub events: Vec<ExecutionOutput<S>>,
+
+    /// Cursor to poll the next events with, acknowledging these ones.
+    pub cursor: u64,
+
+    /// Indicates whether the subscription has ended, so these are its last
+    /// events.
+    pub complete: bool,
+}
+
+/// Generates a new unguessable subscription ID, hex-encoding 128 random bits.
+fn random_id() -> String {
+    let mut bytes = [0; 16];
+    getrandom::getrandom(&mut bytes).expect("failed to generate random subscription ID");
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Error of polling a subscription with [`PollingSubscriptions::poll()`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PollError {
+    /// There is no subscription with the polled ID, as it has been stopped,
+    /// expired or completed already.
+    UnknownSubscription,
+
+    /// The polled cursor is out of the range of the `expected` ones.
+    InvalidCursor {
+        /// Polled cursor.
+        cursor: u64,
+
+        /// Range of the valid cursors.
+        expected: std::ops::RangeInclusive<u64>,
+    },
+}
+
+impl fmt::Display for PollError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownSubscription => write!(f, "Unknown subscription"),
+            Self::InvalidCursor { cursor, expected } => write!(
+                f,
+                "Invalid cursor {}, expected one from {} to {}",
+                cursor,
+                expected.start(),
+                expected.end(),
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PollError {}
+
+/// Resolves the subscription `request` via the `coordinator` with the given
+/// `context` into an [`EventStream`] owning all of them, so it may be
+/// registered in [`PollingSubscriptions`].
+///
+/// The stream holds the slot of the subscription in the limits of the
+/// `coordinator` until it ends or is dropped. If the `request` cannot be
+/// resolved, the stream yields a single event with the error.
+///
+/// # Errors
+///
+/// If the subscription exceeds the limits of active subscriptions of the
+/// `coordinator`.
+pub async fn resolve_into_owned_stream<QueryT, MutationT, SubscriptionT, CtxT, S>(
+    coordinator: Arc<Coordinator<'static, QueryT, MutationT, SubscriptionT, CtxT, S>>,
+    request: GraphQLRequest<S>,
+    context: CtxT,
+) -> Result<EventStream<'static, S>, LimitExceeded>
+where
+    QueryT: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    QueryT::TypeInfo: Send + Sync,
+    MutationT: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    MutationT::TypeInfo: Send + Sync,
+    SubscriptionT: GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+    SubscriptionT::TypeInfo: Send + Sync,
+    CtxT: Send + Sync + 'static,
+    S: ScalarValue + Send + Sync + 'static,
+{
+    // The events are resolved borrowing the owned values inside the `driver`,
+    // and forwarded out of it via a channel. Whether the subscription is
+    // accepted by the `coordinator` is reported before any of its events.
+    let (status_tx, mut status_rx) = oneshot::channel();
+    let (mut tx, mut rx) = mpsc::channel(0);
+    let driver = async move {
+        let mut events = match coordinator.subscribe(&request, &context).await {
+            Ok(conn) => {
+                let _ = status_tx.send(Ok(()));
+                conn
+            }
+            Err(SubscribeError::LimitExceeded(e)) => {
+                let _ = status_tx.send(Err(e));
+                return;
+            }
+            Err(SubscribeError::Request(e)) => {
+                let _ = status_tx.send(Ok(()));
+                let error = ExecutionError::at_origin(FieldError::new(e, Value::null()));
+                let _ = tx
+                    .send(ExecutionOutput {
+                        data: Value::null(),
+                        errors: vec![error],
+                    })
+                    .await;
+                return;
+            }
+        };
+        while let Some(event) = events.next().await {
+            if tx.send(event).await.is_err() {
+                break;
+            }
+        }
+    };
+
+    let mut driver = Some(driver.boxed());
+    future::poll_fn(|cx| {
+        poll_driver(&mut driver, cx);
+        status_rx.poll_unpin(cx)
+    })
+    .await
+    // The status is always reported before the `driver` is finished.
+    .unwrap_or(Ok(()))?;
+
+    // The `driver` is polled first, so the events it has become ready to
+    // forward are received right away.
+    Ok(stream::poll_fn(move |cx| {
+        poll_driver(&mut driver, cx);
+        rx.poll_next_unpin(cx)
+    })
+    .boxed())
+}
+
+/// Polls the given `driver` future, dropping it once it's finished.
+fn poll_driver(driver: &mut Option<BoxFuture<'static, ()>>, cx: &mut std::task::Context<'_>) {
+    if let Some(fut) = driver {
+        if fut.poll_unpin(cx).is_ready() {
+            *driver = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{pin::Pin, sync::Arc, time::Duration};
+
+    use futures::{stream, Stream};
+    use juniper::{
+        graphql_object, graphql_subscription, graphql_value, DefaultScalarValue, EmptyMutation,
+        RootNode,
+    };
+
+    use crate::{EventLayer, EventStream};
+
+    use super::*;
+
+    struct Query;
+
+    #[graphql_object]
+    impl Query {
+        fn ping() -> bool {
+            true
+        }
+    }
+
+    struct Subscription;
+
+    #[graphql_subscription]
+    impl Subscription {
+        async fn counter() -> Pin<Box<dyn Stream<Item = i32> + Send>> {
+            Box::pin(stream::iter(1..=3))
+        }
+
+        async fn never() -> Pin<Box<dyn Stream<Item = i32> + Send>> {
+            Box::pin(stream::pending())
+        }
+    }
+
+    type Schema = RootNode<'static, Query, EmptyMutation, Subscription>;
+
+    type TestCoordinator =
+        Arc<Coordinator<'static, Query, EmptyMutation, Subscription, (), DefaultScalarValue>>;
+
+    fn coordinator() -> TestCoordinator {
+        Arc::new(Coordinator::new(Schema::new(
+            Query,
+            EmptyMutation::new(),
+            Subscription,
+        )))
+    }
+
+    fn request(query: &str) -> GraphQLRequest<DefaultScalarValue> {
+        GraphQLRequest::new(query.into(), None, None)
+    }
+
+    async fn start(subs: &PollingSubscriptions<DefaultScalarValue>, query: &str) -> String {
+        subs.start(&coordinator(), request(query), ())
+            .await
+            .unwrap()
+    }
+
+    fn data(batch: &Batch<DefaultScalarValue>) -> Vec<Value<DefaultScalarValue>> {
+        batch.events.iter().map(|e| e.data.clone()).collect()
+    }
+
+    #[tokio::test]
+    async fn redelivers_unacknowledged_events() {
+        let subs = PollingSubscriptions::new(Duration::from_secs(60)).max_batch_size(2);
+        let id = start(&subs, "subscription { counter }").await;
+
+        let batch = subs.poll(&id, 0).await.unwrap();
+        assert_eq!(
+            data(&batch),
+            [
+                graphql_value!({"counter": 1}),
+                graphql_value!({"counter": 2}),
+            ],
+        );
+        assert_eq!((batch.cursor, batch.complete), (2, false));
+
+        // The response is lost, so the client polls with the same cursor.
+        let batch = subs.poll(&id, 0).await.unwrap();
+        assert_eq!(batch.cursor, 2);
+
+        let batch = subs.poll(&id, 2).await.unwrap();
+        assert_eq!(data(&batch), [graphql_value!({"counter": 3})]);
+        assert_eq!((batch.cursor, batch.complete), (3, true));
+
+        let batch = subs.poll(&id, 3).await.unwrap();
+        assert!(batch.events.is_empty());
+        assert!(batch.complete);
+        assert!(subs.is_empty());
+        assert_eq!(
+            subs.poll(&id, 3).await.unwrap_err(),
+            PollError::UnknownSubscription,
+        );
+    }
+
+    #[tokio::test]
+    async fn generates_unguessable_ids() {
+        let subs = PollingSubscriptions::new(Duration::from_secs(60));
+        let first = start(&subs, "subscription { counter }").await;
+        let second = start(&subs, "subscription { counter }").await;
+
+        assert_eq!(first.len(), 32);
+        assert_ne!(first, second);
+        assert_eq!(
+            subs.poll("0", 0).await.unwrap_err(),
+            PollError::UnknownSubscription,
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_cursor() {
+        let subs = PollingSubscriptions::new(Duration::from_secs(60));
+        let id = start(&subs, "subscription { counter }").await;
+
+        assert_eq!(
+            subs.poll(&id, 1).await.unwrap_err(),
+            PollError::InvalidCursor {
+                cursor: 1,
+                expected: 0..=0,
+            },
+        );
+    }
+
+    #[tokio::test]
+    async fn reports_request_error_as_event() {
+        let subs = PollingSubscriptions::new(Duration::from_secs(60));
+        let id = start(&subs, "subscription { unknown }").await;
+
+        let batch = subs.poll(&id, 0).await.unwrap();
+        assert_eq!(batch.events.len(), 1);
+        assert_eq!(batch.events[0].data, graphql_value!(null));
+        assert_eq!(batch.events[0].errors.len(), 1);
+        assert!(batch.complete);
+    }
+
+    #[tokio::test]
+    async fn expires_subscriptions_not_polled_within_ttl() {
+        let coordinator = coordinator();
+        let subs = PollingSubscriptions::new(Duration::from_millis(10));
+        let id = subs
+            .start(&coordinator, request("subscription { never }"), ())
+            .await
+            .unwrap();
+
+        // Timed out waiting, while no events are lost.
+        assert!(
+            tokio::time::timeout(Duration::from_millis(20), subs.poll(&id, 0))
+                .await
+                .is_err()
+        );
+        assert_eq!(subs.len(), 1);
+        assert_eq!(coordinator.active(), 1);
+
+        std::thread::sleep(Duration::from_millis(20));
+        subs.prune();
+        assert!(subs.is_empty());
+        assert_eq!(coordinator.active(), 0);
+    }
+
+    #[tokio::test]
+    async fn holds_coordinator_slots_until_dropped() {
+        let coordinator = Arc::new(
+            Coordinator::new(Schema::new(Query, EmptyMutation::new(), Subscription)).max_active(1),
+        );
+        let subs = PollingSubscriptions::new(Duration::from_secs(60));
+
+        let id = subs
+            .start(&coordinator, request("subscription { never }"), ())
+            .await
+            .unwrap();
+        assert_eq!(coordinator.active(), 1);
+        assert_eq!(
+            subs.start(&coordinator, request("subscription { never }"), ())
+                .await
+                .unwrap_err(),
+            LimitExceeded::Global { max: 1 },
+        );
+        assert_eq!(subs.len(), 1);
+
+        assert!(subs.stop(&id));
+        assert_eq!(coordinator.active(), 0);
+        assert!(subs
+            .start(&coordinator, request("subscription { never }"), ())
+            .await
+            .is_ok());
+    }
+
+    struct Mark;
+
+    impl EventLayer<(), DefaultScalarValue> for Mark {
+        fn layer<'a>(
+            &'a self,
+            _: &'a (),
+            events: EventStream<'a, DefaultScalarValue>,
+        ) -> EventStream<'a, DefaultScalarValue> {
+            events
+                .map(|mut out| {
+                    if let Some(obj) = out.data.as_mut_object_value() {
+                        obj.add_field("layered", Value::scalar(true));
+                    }
+                    out
+                })
+                .boxed()
+        }
+    }
+
+    #[tokio::test]
+    async fn applies_coordinator_event_layers() {
+        let coordinator = Arc::new(
+            Coordinator::new(Schema::new(Query, EmptyMutation::new(), Subscription))
+                .event_layer(Mark),
+        );
+        let subs = PollingSubscriptions::new(Duration::from_secs(60));
+        let id = subs
+            .start(&coordinator, request("subscription { counter }"), ())
+            .await
+            .unwrap();
+
+        let batch = subs.poll(&id, 0).await.unwrap();
+        assert_eq!(
+            data(&batch),
+            [
+                graphql_value!({"counter": 1, "layered": true}),
+                graphql_value!({"counter": 2, "layered": true}),
+                graphql_value!({"counter": 3, "layered": true}),
+            ],
+        );
+    }
+}
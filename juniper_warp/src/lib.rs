@@ -8,11 +8,11 @@ use std::{collections::HashMap, str, sync::Arc};
 use anyhow::anyhow;
 use futures::{FutureExt as _, TryFutureExt};
 use juniper::{
-    http::{GraphQLBatchRequest, GraphQLRequest},
-    ScalarValue,
+    http::{ETag, GraphQLBatchRequest, GraphQLRequest, RequestDeadline},
+    Limits, ScalarValue,
 };
 use tokio::task;
-use warp::{body, filters::BoxedFilter, http, hyper::body::Bytes, query, Filter};
+use warp::{body, filters::BoxedFilter, header, http, hyper::body::Bytes, query, Filter};
 
 /// Make a filter for graphql queries/mutations.
 ///
@@ -84,34 +84,37 @@ where
     S: ScalarValue + Send + Sync + 'static,
 {
     let schema = Arc::new(schema);
+    let limits = limits_filter(*schema.schema.limits());
     let post_json_schema = schema.clone();
     let post_graphql_schema = schema.clone();
 
-    let handle_post_json_request = move |context: CtxT, req: GraphQLBatchRequest<S>| {
-        let schema = post_json_schema.clone();
-        async move {
-            let resp = req.execute(&schema, &context).await;
-
-            Ok::<_, warp::Rejection>(build_response(
-                serde_json::to_vec(&resp)
-                    .map(|json| (json, resp.is_ok()))
-                    .map_err(Into::into),
-            ))
-        }
-    };
+    let handle_post_json_request =
+        move |context: CtxT, req: GraphQLBatchRequest<S>, limits: Limits| {
+            let schema = post_json_schema.clone();
+            async move {
+                let resp = req.execute_with_limits(&schema, &context, &limits).await;
+
+                Ok::<_, warp::Rejection>(build_response(
+                    serde_json::to_vec(&resp)
+                        .map(|json| (json, resp.is_ok()))
+                        .map_err(Into::into),
+                ))
+            }
+        };
     let post_json_filter = warp::post()
         .and(context_extractor.clone())
         .and(body::json())
+        .and(limits.clone())
         .and_then(handle_post_json_request);
 
-    let handle_post_graphql_request = move |context: CtxT, body: Bytes| {
+    let handle_post_graphql_request = move |context: CtxT, body: Bytes, limits: Limits| {
         let schema = post_graphql_schema.clone();
         async move {
             let query = str::from_utf8(body.as_ref())
                 .map_err(|e| anyhow!("Request body query is not a valid UTF-8 string: {}", e))?;
             let req = GraphQLRequest::new(query.into(), None, None);
 
-            let resp = req.execute(&schema, &context).await;
+            let resp = req.execute_with_limits(&schema, &context, &limits).await;
 
             Ok((serde_json::to_vec(&resp)?, resp.is_ok()))
         }
@@ -120,9 +123,13 @@ where
     let post_graphql_filter = warp::post()
         .and(context_extractor.clone())
         .and(body::bytes())
+        .and(limits.clone())
         .and_then(handle_post_graphql_request);
 
-    let handle_get_request = move |context: CtxT, mut qry: HashMap<String, String>| {
+    let handle_get_request = move |context: CtxT,
+                                   mut qry: HashMap<String, String>,
+                                   if_none_match: Option<String>,
+                                   limits: Limits| {
         let schema = schema.clone();
         async move {
             let req = GraphQLRequest::new(
@@ -134,15 +141,19 @@ where
                     .transpose()?,
             );
 
-            let resp = req.execute(&schema, &context).await;
+            let resp = req.execute_with_limits(&schema, &context, &limits).await;
 
-            Ok((serde_json::to_vec(&resp)?, resp.is_ok()))
+            Ok((serde_json::to_vec(&resp)?, resp.is_ok(), !resp.has_errors()))
         }
-        .then(|res| async move { Ok::<_, warp::Rejection>(build_response(res)) })
+        .then(|res| async move {
+            Ok::<_, warp::Rejection>(build_get_response(res, if_none_match.as_deref()))
+        })
     };
     let get_filter = warp::get()
         .and(context_extractor)
         .and(query::query())
+        .and(header::optional("if-none-match"))
+        .and(limits)
         .and_then(handle_get_request);
 
     get_filter
@@ -166,28 +177,31 @@ where
     S: ScalarValue + Send + Sync + 'static,
 {
     let schema = Arc::new(schema);
+    let limits = limits_filter(*schema.schema.limits());
     let post_json_schema = schema.clone();
     let post_graphql_schema = schema.clone();
 
-    let handle_post_json_request = move |context: CtxT, req: GraphQLBatchRequest<S>| {
-        let schema = post_json_schema.clone();
-        async move {
-            let res = task::spawn_blocking(move || {
-                let resp = req.execute_sync(&schema, &context);
-                Ok((serde_json::to_vec(&resp)?, resp.is_ok()))
-            })
-            .await?;
+    let handle_post_json_request =
+        move |context: CtxT, req: GraphQLBatchRequest<S>, limits: Limits| {
+            let schema = post_json_schema.clone();
+            async move {
+                let res = task::spawn_blocking(move || {
+                    let resp = req.execute_sync_with_limits(&schema, &context, &limits);
+                    Ok((serde_json::to_vec(&resp)?, resp.is_ok()))
+                })
+                .await?;
 
-            Ok(build_response(res))
-        }
-        .map_err(|e: task::JoinError| warp::reject::custom(JoinError(e)))
-    };
+                Ok(build_response(res))
+            }
+            .map_err(|e: task::JoinError| warp::reject::custom(JoinError(e)))
+        };
     let post_json_filter = warp::post()
         .and(context_extractor.clone())
         .and(body::json())
+        .and(limits.clone())
         .and_then(handle_post_json_request);
 
-    let handle_post_graphql_request = move |context: CtxT, body: Bytes| {
+    let handle_post_graphql_request = move |context: CtxT, body: Bytes, limits: Limits| {
         let schema = post_graphql_schema.clone();
         async move {
             let res = task::spawn_blocking(move || {
@@ -195,7 +209,7 @@ where
                     .map_err(|e| anyhow!("Request body is not a valid UTF-8 string: {}", e))?;
                 let req = GraphQLRequest::new(query.into(), None, None);
 
-                let resp = req.execute_sync(&schema, &context);
+                let resp = req.execute_sync_with_limits(&schema, &context, &limits);
                 Ok((serde_json::to_vec(&resp)?, resp.is_ok()))
             })
             .await?;
@@ -207,9 +221,13 @@ where
     let post_graphql_filter = warp::post()
         .and(context_extractor.clone())
         .and(body::bytes())
+        .and(limits.clone())
         .and_then(handle_post_graphql_request);
 
-    let handle_get_request = move |context: CtxT, mut qry: HashMap<String, String>| {
+    let handle_get_request = move |context: CtxT,
+                                   mut qry: HashMap<String, String>,
+                                   if_none_match: Option<String>,
+                                   limits: Limits| {
         let schema = schema.clone();
         async move {
             let res = task::spawn_blocking(move || {
@@ -223,18 +241,20 @@ where
                         .transpose()?,
                 );
 
-                let resp = req.execute_sync(&schema, &context);
-                Ok((serde_json::to_vec(&resp)?, resp.is_ok()))
+                let resp = req.execute_sync_with_limits(&schema, &context, &limits);
+                Ok((serde_json::to_vec(&resp)?, resp.is_ok(), !resp.has_errors()))
             })
             .await?;
 
-            Ok(build_response(res))
+            Ok(build_get_response(res, if_none_match.as_deref()))
         }
         .map_err(|e: task::JoinError| warp::reject::custom(JoinError(e)))
     };
     let get_filter = warp::get()
         .and(context_extractor)
         .and(query::query())
+        .and(header::optional("if-none-match"))
+        .and(limits)
         .and_then(handle_get_request);
 
     get_filter
@@ -245,6 +265,17 @@ where
         .boxed()
 }
 
+/// Makes a filter extracting the given schema `limits` with the deadline
+/// propagated in the request headers applied.
+fn limits_filter(limits: Limits) -> BoxedFilter<(Limits,)> {
+    header::headers_cloned()
+        .map(move |headers: http::HeaderMap| {
+            RequestDeadline::from_headers(|name| headers.get(name).and_then(|v| v.to_str().ok()))
+                .map_or(limits, |deadline| deadline.limits(limits))
+        })
+        .boxed()
+}
+
 /// Error raised by `tokio_threadpool` if the thread pool has been shutdown.
 ///
 /// Wrapper type is needed as inner type does not implement `warp::reject::Reject`.
@@ -267,6 +298,36 @@ fn build_response(response: Result<(Vec<u8>, bool), anyhow::Error>) -> http::Res
     }
 }
 
+/// Builds the response to a `GET` request, tagging a successful one without
+/// errors (so being cacheable) with an [`ETag`], and replacing it with an empty
+/// `304 Not Modified` response if the client already has it cached, according
+/// to the given `If-None-Match` header.
+fn build_get_response(
+    response: Result<(Vec<u8>, bool, bool), anyhow::Error>,
+    if_none_match: Option<&str>,
+) -> http::Response<Vec<u8>> {
+    match response {
+        Ok((body, true, true)) => {
+            let etag = ETag::of(&body);
+            if matches!(if_none_match, Some(v) if etag.matches(v)) {
+                http::Response::builder()
+                    .status(http::StatusCode::NOT_MODIFIED)
+                    .header("etag", etag.to_string())
+                    .body(Vec::new())
+                    .expect("response is valid")
+            } else {
+                http::Response::builder()
+                    .status(200)
+                    .header("content-type", "application/json")
+                    .header("etag", etag.to_string())
+                    .body(body)
+                    .expect("response is valid")
+            }
+        }
+        res => build_response(res.map(|(body, is_ok, _)| (body, is_ok))),
+    }
+}
+
 /// Create a filter that replies with an HTML page containing GraphiQL. This does not handle routing, so you can mount it on any endpoint.
 ///
 /// For example:
@@ -355,7 +416,7 @@ pub mod subscriptions {
         },
         GraphQLSubscriptionType, GraphQLTypeAsync, RootNode, ScalarValue,
     };
-    use juniper_graphql_ws::{ArcSchema, ClientMessage, Connection, Init};
+    use juniper_graphql_ws::{ArcSchema, ClientMessage, Connection, Init, ShutdownCoordinator};
 
     struct Message(warp::ws::Message);
 
@@ -413,6 +474,67 @@ pub mod subscriptions {
         root_node: Arc<RootNode<'static, Query, Mutation, Subscription, S>>,
         init: I,
     ) -> Result<(), Error>
+    where
+        Query: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+        Query::TypeInfo: Send + Sync,
+        Mutation: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+        Mutation::TypeInfo: Send + Sync,
+        Subscription: GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+        Subscription::TypeInfo: Send + Sync,
+        CtxT: Unpin + Send + Sync + 'static,
+        S: ScalarValue + Send + Sync + 'static,
+        I: Init<S, CtxT> + Send,
+    {
+        serve_connection(websocket, Connection::new(ArcSchema(root_node), init)).await
+    }
+
+    /// Serves the graphql-ws protocol over a WebSocket connection, gracefully shutting it down
+    /// along with the given `juniper_graphql_ws::ShutdownCoordinator`.
+    ///
+    /// Once a shutdown is initiated, new operations are rejected, and after the drain period the
+    /// active ones are completed and the WebSocket is closed. Initiating it from the signal future
+    /// of `warp::Server::bind_with_graceful_shutdown()` prevents deploys from dropping clients in
+    /// the middle of a message:
+    ///
+    /// ```ignore
+    /// let shutdown = juniper_graphql_ws::ShutdownCoordinator::new();
+    /// let (_, server) = warp::serve(routes).bind_with_graceful_shutdown(addr, {
+    ///     let shutdown = shutdown.clone();
+    ///     async move {
+    ///         tokio::signal::ctrl_c().await.unwrap();
+    ///         shutdown.shutdown(Duration::from_secs(10)).await;
+    ///     }
+    /// });
+    /// ```
+    ///
+    /// See `serve_graphql_ws()` for the description of the `init` argument.
+    pub async fn serve_graphql_ws_with_shutdown<Query, Mutation, Subscription, CtxT, S, I>(
+        websocket: warp::ws::WebSocket,
+        root_node: Arc<RootNode<'static, Query, Mutation, Subscription, S>>,
+        init: I,
+        shutdown: &ShutdownCoordinator,
+    ) -> Result<(), Error>
+    where
+        Query: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+        Query::TypeInfo: Send + Sync,
+        Mutation: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+        Mutation::TypeInfo: Send + Sync,
+        Subscription: GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+        Subscription::TypeInfo: Send + Sync,
+        CtxT: Unpin + Send + Sync + 'static,
+        S: ScalarValue + Send + Sync + 'static,
+        I: Init<S, CtxT> + Send,
+    {
+        let conn = Connection::new(ArcSchema(root_node), init).with_shutdown(shutdown);
+        serve_connection(websocket, conn).await
+    }
+
+    /// Forwards messages between the WebSocket and the graphql-ws `Connection` until either of
+    /// them ends.
+    async fn serve_connection<Query, Mutation, Subscription, CtxT, S, I>(
+        websocket: warp::ws::WebSocket,
+        conn: Connection<ArcSchema<Query, Mutation, Subscription, CtxT, S>, I>,
+    ) -> Result<(), Error>
     where
         Query: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
         Query::TypeInfo: Send + Sync,
@@ -425,7 +547,7 @@ pub mod subscriptions {
         I: Init<S, CtxT> + Send,
     {
         let (ws_tx, ws_rx) = websocket.split();
-        let (s_tx, s_rx) = Connection::new(ArcSchema(root_node), init).split();
+        let (s_tx, s_rx) = conn.split();
 
         let ws_rx = ws_rx.map(|r| r.map(Message));
         let s_rx = s_rx.map(|msg| {
@@ -591,6 +713,114 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn graphql_handler_honors_if_none_match_on_get() {
+        use juniper::{
+            tests::fixtures::starwars::schema::{Database, Query},
+            EmptyMutation, EmptySubscription, RootNode,
+        };
+
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        );
+
+        let state = warp::any().map(Database::new);
+        let filter = warp::path("graphql2").and(make_graphql_filter(schema, state.boxed()));
+
+        let path = "/graphql2?query=%7B%20hero%20%7B%20name%20%7D%20%7D";
+        let response = request().method("GET").path(path).reply(&filter).await;
+
+        assert_eq!(response.status(), http::StatusCode::OK);
+        let etag = response.headers().get("etag").unwrap().clone();
+
+        let response = request()
+            .method("GET")
+            .path(path)
+            .header("if-none-match", etag.clone())
+            .reply(&filter)
+            .await;
+
+        assert_eq!(response.status(), http::StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers().get("etag"), Some(&etag));
+        assert!(response.body().is_empty());
+
+        let response = request()
+            .method("GET")
+            .path(path)
+            .header("if-none-match", "\"0000000000000000\"")
+            .reply(&filter)
+            .await;
+
+        assert_eq!(response.status(), http::StatusCode::OK);
+        assert_eq!(
+            String::from_utf8(response.body().to_vec()).unwrap(),
+            r#"{"data":{"hero":{"name":"R2-D2"}}}"#,
+        );
+    }
+
+    #[tokio::test]
+    async fn graphql_handler_doesnt_tag_get_responses_with_errors() {
+        use juniper::{
+            tests::fixtures::starwars::schema::{Database, Query},
+            EmptyMutation, EmptySubscription, RootNode,
+        };
+
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        );
+
+        let state = warp::any().map(Database::new);
+        let filter = warp::path("graphql2").and(make_graphql_filter(schema, state.boxed()));
+
+        let response = request()
+            .method("GET")
+            .path("/graphql2?query=%7B%20hero%20%7B%20name%20%7D%20%7D")
+            .header("grpc-timeout", "0n")
+            .reply(&filter)
+            .await;
+
+        assert_eq!(response.status(), http::StatusCode::OK);
+        assert!(response.headers().get("etag").is_none());
+    }
+
+    #[tokio::test]
+    async fn graphql_handler_propagates_deadline_from_headers() {
+        use juniper::{
+            tests::fixtures::starwars::schema::{Database, Query},
+            EmptyMutation, EmptySubscription, RootNode,
+        };
+
+        for is_sync in [false, true] {
+            let schema = RootNode::new(
+                Query,
+                EmptyMutation::<Database>::new(),
+                EmptySubscription::<Database>::new(),
+            );
+            let state = warp::any().map(Database::new).boxed();
+            let filter = warp::path("graphql2").and(if is_sync {
+                make_graphql_filter_sync(schema, state)
+            } else {
+                make_graphql_filter(schema, state)
+            });
+
+            let response = request()
+                .method("POST")
+                .path("/graphql2")
+                .header("content-type", "application/json")
+                .header("grpc-timeout", "0n")
+                .body(r#"{ "query": "{ hero { name } }" }"#)
+                .reply(&filter)
+                .await;
+
+            let body = String::from_utf8(response.body().to_vec()).unwrap();
+            assert!(body.contains("Operation timed out"), "body: {}", body);
+        }
+    }
+
     #[tokio::test]
     async fn batch_requests_work() {
         use juniper::{
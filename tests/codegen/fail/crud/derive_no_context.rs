@@ -0,0 +1,7 @@
+#[derive(juniper::GraphQLCrud)]
+struct User {
+    id: i32,
+    name: String,
+}
+
+fn main() {}
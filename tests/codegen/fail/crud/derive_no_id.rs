@@ -0,0 +1,11 @@
+struct Database;
+
+impl juniper::Context for Database {}
+
+#[derive(juniper::GraphQLCrud)]
+#[graphql_crud(context = Database)]
+struct User {
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+struct Database;
+
+impl juniper::Context for Database {}
+
+#[derive(juniper::GraphQLCrud)]
+#[graphql_crud(context = Database, plural = "sheep")]
+struct Sheep {
+    id: i32,
+}
+
+fn main() {}
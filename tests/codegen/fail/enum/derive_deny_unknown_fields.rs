@@ -0,0 +1,7 @@
+#[derive(juniper::GraphQLEnum)]
+#[graphql(deny_unknown_fields)]
+pub enum Test {
+    A,
+}
+
+fn main() {}
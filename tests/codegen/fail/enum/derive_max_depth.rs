@@ -0,0 +1,7 @@
+#[derive(juniper::GraphQLEnum)]
+#[graphql(max_depth = 3)]
+pub enum Test {
+    A,
+}
+
+fn main() {}
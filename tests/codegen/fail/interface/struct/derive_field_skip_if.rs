@@ -0,0 +1,10 @@
+use juniper::GraphQLInterface;
+
+#[derive(GraphQLInterface)]
+struct Character {
+    id: String,
+    #[graphql(skip_if = "is_hidden")]
+    name: Option<String>,
+}
+
+fn main() {}
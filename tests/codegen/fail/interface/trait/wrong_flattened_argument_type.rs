@@ -0,0 +1,24 @@
+use juniper::{graphql_interface, graphql_object, GraphQLInputObject};
+
+#[derive(GraphQLInputObject)]
+struct IdArgs {
+    is_present: i32,
+}
+
+pub struct ObjA {
+    id: String,
+}
+
+#[graphql_object(impl = CharacterValue)]
+impl ObjA {
+    fn id(&self, #[graphql(args)] _args: IdArgs) -> &str {
+        &self.id
+    }
+}
+
+#[graphql_interface(for = ObjA)]
+trait Character {
+    fn id(&self, is_present: bool) -> &str;
+}
+
+fn main() {}
@@ -0,0 +1,4 @@
+#[derive(juniper::IntoFieldError)]
+struct Error;
+
+fn main() {}
@@ -0,0 +1,7 @@
+#[derive(juniper::IntoFieldError)]
+enum Error {
+    #[graphql(transparent)]
+    Wrapped(String, i32),
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+#[derive(juniper::IntoFieldError)]
+enum Error {
+    #[graphql(message = "Not found: {id}")]
+    NotFound(i32),
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use juniper::graphql_object;
+
+struct ObjA;
+
+#[graphql_object]
+impl ObjA {
+    fn echo(#[graphql(validate(regex = "[0-9"))] code: String) -> String {
+        code
+    }
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use juniper::graphql_object;
+
+struct ObjA;
+
+#[graphql_object]
+impl ObjA {
+    #[graphql(retry(backoff = "50ms"))]
+    async fn id(&self) -> &str {
+        "funA"
+    }
+}
+
+fn main() {}
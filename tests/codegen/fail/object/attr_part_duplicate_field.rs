@@ -0,0 +1,27 @@
+use juniper::graphql_object;
+
+struct Obj;
+
+#[graphql_object(parts = [ObjPartA, ObjPartB])]
+impl Obj {
+    fn id(&self) -> &str {
+        "funA"
+    }
+}
+
+#[graphql_object(part = ObjPartA)]
+impl Obj {
+    fn name(&self) -> &str {
+        "funA"
+    }
+}
+
+#[graphql_object(part = ObjPartB)]
+impl Obj {
+    #[graphql(name = "name")]
+    fn another_name(&self) -> &str {
+        "funB"
+    }
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+use juniper::graphql_object;
+
+struct Obj;
+
+#[graphql_object(parts = ObjPart)]
+impl Obj {
+    fn id(&self) -> &str {
+        "funA"
+    }
+}
+
+#[graphql_object(part = ObjPart, name = "Part")]
+impl Obj {
+    fn name(&self) -> &str {
+        "funB"
+    }
+}
+
+fn main() {}
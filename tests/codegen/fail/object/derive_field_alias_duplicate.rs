@@ -0,0 +1,10 @@
+use juniper::GraphQLObject;
+
+#[derive(GraphQLObject)]
+struct ObjA {
+    id: String,
+    #[graphql(alias = "id")]
+    id2: String,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use std::pin::Pin;
+
+use juniper::graphql_subscription;
+
+type Stream<'a, I> = Pin<Box<dyn futures::Stream<Item = I> + Send + 'a>>;
+
+struct ObjA;
+
+#[graphql_subscription]
+impl ObjA {
+    #[graphql(skip_if = Self::is_hidden)]
+    async fn id(&self) -> Stream<'static, bool> {
+        Box::pin(futures::stream::once(futures::future::ready(true)))
+    }
+}
+
+fn main() {}
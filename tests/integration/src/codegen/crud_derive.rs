@@ -0,0 +1,248 @@
+//! Tests for `#[derive(GraphQLCrud)]` macro.
+
+use std::{collections::BTreeMap, sync::Mutex};
+
+use juniper::{
+    execute, graphql_object, graphql_value, graphql_vars, Context, CrudStorage, EmptySubscription,
+    FieldError, FieldResult, GraphQLCrud, GraphQLObject, MergedObject, Nullable, RootNode,
+};
+
+/// A blog post.
+#[derive(Clone, Debug, GraphQLCrud, GraphQLObject)]
+#[graphql_crud(context = Database)]
+struct BlogPost {
+    #[graphql_crud(id)]
+    slug: String,
+    /// Title of the post.
+    title: String,
+    subtitle: Option<String>,
+    #[graphql_crud(skip)]
+    views: i32,
+}
+
+#[derive(Default)]
+struct Database {
+    posts: Mutex<BTreeMap<String, BlogPost>>,
+}
+
+impl Context for Database {}
+
+impl CrudStorage<BlogPost> for Database {
+    fn get(&self, slug: String) -> FieldResult<Option<BlogPost>> {
+        Ok(self.posts.lock().unwrap().get(&slug).cloned())
+    }
+
+    fn list(&self, first: Option<i32>, offset: Option<i32>) -> FieldResult<Vec<BlogPost>> {
+        let posts = self.posts.lock().unwrap();
+        Ok(posts
+            .values()
+            .skip(offset.unwrap_or(0) as usize)
+            .take(first.map_or(usize::MAX, |n| n as usize))
+            .cloned()
+            .collect())
+    }
+
+    fn create(&self, input: BlogPostCreateInput) -> FieldResult<BlogPost> {
+        let slug = input.title.to_lowercase().replace(' ', "-");
+        let mut posts = self.posts.lock().unwrap();
+        if posts.contains_key(&slug) {
+            return Err(FieldError::from("already exists"));
+        }
+        let post = BlogPost {
+            slug: slug.clone(),
+            title: input.title,
+            subtitle: input.subtitle,
+            views: 0,
+        };
+        posts.insert(slug, post.clone());
+        Ok(post)
+    }
+
+    fn update(&self, slug: String, input: BlogPostUpdateInput) -> FieldResult<Option<BlogPost>> {
+        Ok(self.posts.lock().unwrap().get_mut(&slug).map(|post| {
+            if let Some(title) = input.title {
+                post.title = title;
+            }
+            match input.subtitle {
+                Nullable::ImplicitNull => {}
+                Nullable::ExplicitNull => post.subtitle = None,
+                Nullable::Some(subtitle) => post.subtitle = Some(subtitle),
+            }
+            post.clone()
+        }))
+    }
+
+    fn delete(&self, slug: String) -> FieldResult<bool> {
+        Ok(self.posts.lock().unwrap().remove(&slug).is_some())
+    }
+}
+
+struct Query;
+
+#[graphql_object(context = Database)]
+impl Query {
+    fn version() -> i32 {
+        1
+    }
+}
+
+type Schema = RootNode<
+    'static,
+    MergedObject<Query, BlogPostCrudQuery>,
+    BlogPostCrudMutation,
+    EmptySubscription<Database>,
+>;
+
+fn schema() -> Schema {
+    RootNode::new(
+        MergedObject(Query, BlogPostCrudQuery),
+        BlogPostCrudMutation,
+        EmptySubscription::new(),
+    )
+}
+
+#[tokio::test]
+async fn creates_and_reads() {
+    let schema = schema();
+    let db = Database::default();
+
+    assert_eq!(
+        execute(
+            r#"mutation {
+                a: createBlogPost(input: {title: "Hello World"}) { slug }
+                b: createBlogPost(input: {title: "Bye", subtitle: "Sub"}) { slug }
+            }"#,
+            None,
+            &schema,
+            &graphql_vars! {},
+            &db,
+        )
+        .await,
+        Ok((
+            graphql_value!({"a": {"slug": "hello-world"}, "b": {"slug": "bye"}}),
+            vec![],
+        )),
+    );
+    assert_eq!(
+        execute(
+            r#"{
+                version
+                blogPost(id: "bye") { title subtitle views }
+                missing: blogPost(id: "none") { title }
+                blogPosts(first: 1, offset: 1) { slug }
+            }"#,
+            None,
+            &schema,
+            &graphql_vars! {},
+            &db,
+        )
+        .await,
+        Ok((
+            graphql_value!({
+                "version": 1,
+                "blogPost": {"title": "Bye", "subtitle": "Sub", "views": 0},
+                "missing": null,
+                "blogPosts": [{"slug": "hello-world"}],
+            }),
+            vec![],
+        )),
+    );
+}
+
+#[tokio::test]
+async fn updates_only_specified_fields() {
+    let schema = schema();
+    let db = Database::default();
+    db.create(BlogPostCreateInput {
+        title: "Post".into(),
+        subtitle: Some("Sub".into()),
+    })
+    .unwrap();
+
+    assert_eq!(
+        execute(
+            r#"mutation {
+                a: updateBlogPost(id: "post", input: {title: "New"}) { title subtitle }
+                b: updateBlogPost(id: "post", input: {subtitle: null}) { title subtitle }
+                c: updateBlogPost(id: "none", input: {}) { title }
+            }"#,
+            None,
+            &schema,
+            &graphql_vars! {},
+            &db,
+        )
+        .await,
+        Ok((
+            graphql_value!({
+                "a": {"title": "New", "subtitle": "Sub"},
+                "b": {"title": "New", "subtitle": null},
+                "c": null,
+            }),
+            vec![],
+        )),
+    );
+}
+
+#[tokio::test]
+async fn deletes() {
+    let schema = schema();
+    let db = Database::default();
+    db.create(BlogPostCreateInput {
+        title: "Post".into(),
+        subtitle: None,
+    })
+    .unwrap();
+
+    assert_eq!(
+        execute(
+            r#"mutation { a: deleteBlogPost(id: "post") b: deleteBlogPost(id: "post") }"#,
+            None,
+            &schema,
+            &graphql_vars! {},
+            &db,
+        )
+        .await,
+        Ok((graphql_value!({"a": true, "b": false}), vec![])),
+    );
+}
+
+#[tokio::test]
+async fn generates_input_objects() {
+    let schema = schema();
+    let db = Database::default();
+
+    assert_eq!(
+        execute(
+            r#"{
+                create: __type(name: "BlogPostCreateInput") {
+                    inputFields { name description type { kind } }
+                }
+                update: __type(name: "BlogPostUpdateInput") {
+                    inputFields { name type { kind } }
+                }
+            }"#,
+            None,
+            &schema,
+            &graphql_vars! {},
+            &db,
+        )
+        .await,
+        Ok((
+            graphql_value!({
+                "create": {"inputFields": [
+                    {
+                        "name": "title",
+                        "description": "Title of the post.",
+                        "type": {"kind": "NON_NULL"},
+                    },
+                    {"name": "subtitle", "description": null, "type": {"kind": "SCALAR"}},
+                ]},
+                "update": {"inputFields": [
+                    {"name": "title", "type": {"kind": "SCALAR"}},
+                    {"name": "subtitle", "type": {"kind": "SCALAR"}},
+                ]},
+            }),
+            vec![],
+        )),
+    );
+}
@@ -50,6 +50,15 @@ enum OverrideDocEnum {
     Foo,
 }
 
+#[derive(GraphQLEnum, Debug, PartialEq)]
+enum DbRenamedEnum {
+    #[graphql(db_rename = "in_progress")]
+    InProgress,
+    #[graphql(name = "OVER", db_rename = "done")]
+    Done,
+    Unmapped,
+}
+
 #[derive(GraphQLEnum)]
 #[graphql(context = CustomContext, noasync)]
 enum ContextEnum {
@@ -74,13 +83,13 @@ fn test_derived_enum() {
     // Test no rename variant.
     assert_eq!(
         <_ as ToInputValue>::to_input_value(&NoRenameEnum::AnotherVariant),
-        graphql_input_value!("AnotherVariant"),
+        graphql_input_value!(AnotherVariant),
     );
 
     // Test Regular variant.
     assert_eq!(
         <_ as ToInputValue>::to_input_value(&SomeEnum::Regular),
-        graphql_input_value!("REGULAR"),
+        graphql_input_value!(REGULAR),
     );
     assert_eq!(
         FromInputValue::<DefaultScalarValue>::from_input_value(&graphql_input_value!(REGULAR)),
@@ -90,7 +99,7 @@ fn test_derived_enum() {
     // Test FULL variant.
     assert_eq!(
         <_ as ToInputValue>::to_input_value(&SomeEnum::Full),
-        graphql_input_value!("FULL"),
+        graphql_input_value!(FULL),
     );
     assert_eq!(
         FromInputValue::<DefaultScalarValue>::from_input_value(&graphql_input_value!(FULL)),
@@ -131,3 +140,28 @@ fn test_doc_custom_context() {
     test_context(ContextEnum::A);
     // test_context(OverrideDocEnum::Foo); does not work
 }
+
+#[test]
+fn test_db_rename() {
+    assert_eq!("in_progress".parse(), Ok(DbRenamedEnum::InProgress));
+    assert_eq!("done".parse(), Ok(DbRenamedEnum::Done));
+    assert_eq!("UNMAPPED".parse(), Ok(DbRenamedEnum::Unmapped));
+    assert_eq!(
+        "OVER".parse::<DbRenamedEnum>(),
+        Err("Unknown `DbRenamedEnum` value: OVER".into()),
+    );
+
+    assert_eq!(DbRenamedEnum::InProgress.as_ref(), "in_progress");
+    assert_eq!(DbRenamedEnum::Done.as_ref(), "done");
+    assert_eq!(DbRenamedEnum::Unmapped.as_ref(), "UNMAPPED");
+
+    // GraphQL names are unaffected.
+    assert_eq!(
+        <_ as ToInputValue>::to_input_value(&DbRenamedEnum::Done),
+        graphql_input_value!(OVER),
+    );
+    assert_eq!(
+        FromInputValue::<DefaultScalarValue>::from_input_value(&graphql_input_value!(IN_PROGRESS)),
+        Ok(DbRenamedEnum::InProgress),
+    );
+}
@@ -1,7 +1,9 @@
 use fnv::FnvHashMap;
 use juniper::{
-    graphql_input_value, marker, DefaultScalarValue, FieldError, FromInputValue,
-    GraphQLInputObject, GraphQLType, GraphQLValue, InputValue, Registry, ToInputValue,
+    graphql_input_value, graphql_value, marker,
+    meta::{DeprecationStatus, MetaType},
+    DefaultScalarValue, FieldError, FromInputValue, GraphQLInputObject, GraphQLType, GraphQLValue,
+    InputValue, Registry, ToInputValue,
 };
 
 #[derive(GraphQLInputObject, Debug, PartialEq)]
@@ -52,6 +54,25 @@ struct OverrideDocComment {
     regular_field: bool,
 }
 
+#[derive(GraphQLInputObject, Debug, PartialEq)]
+#[graphql(deny_unknown_fields)]
+struct Strict {
+    regular_field: String,
+    other: Option<bool>,
+}
+
+#[derive(GraphQLInputObject, Debug, PartialEq)]
+#[graphql(max_depth = 3)]
+struct Filter {
+    name: Option<String>,
+    and: Option<Vec<Filter>>,
+}
+
+#[derive(GraphQLInputObject, Debug, PartialEq)]
+struct Recursive {
+    inner: Option<Box<Recursive>>,
+}
+
 #[derive(Debug, PartialEq)]
 struct Fake;
 
@@ -106,6 +127,25 @@ struct WithLifetime<'a> {
     regular_field: &'a Fake,
 }
 
+#[derive(GraphQLInputObject, Debug, PartialEq)]
+#[graphql(scalar = DefaultScalarValue)]
+struct Validated {
+    #[graphql(validate(length(min = 1, max = 8), regex = "^[a-z]+$"))]
+    login: String,
+    #[graphql(validate(range(min = 0, max = 150)))]
+    age: Option<i32>,
+}
+
+#[derive(GraphQLInputObject, Debug, PartialEq)]
+#[graphql(scalar = DefaultScalarValue)]
+struct Deprecated {
+    id: String,
+    #[graphql(deprecated = "Use `id` instead.")]
+    legacy_id: Option<String>,
+    #[graphql(deprecated)]
+    flag: Option<bool>,
+}
+
 #[test]
 fn test_derived_input_object() {
     assert_eq!(
@@ -169,6 +209,161 @@ fn test_derived_input_object() {
     );
 }
 
+#[test]
+fn test_validated_input_object() {
+    assert_eq!(
+        Validated::from_input_value(&graphql_input_value!({"login": "abc", "age": 42})),
+        Ok(Validated {
+            login: "abc".into(),
+            age: Some(42),
+        }),
+    );
+    assert_eq!(
+        Validated::from_input_value(&graphql_input_value!({"login": "abc"})),
+        Ok(Validated {
+            login: "abc".into(),
+            age: None,
+        }),
+    );
+
+    assert_eq!(
+        Validated::from_input_value(&graphql_input_value!({"login": ""})),
+        Err(FieldError::new(
+            "Invalid value for `login`: length must be at least 1, found: 0",
+            graphql_value!({"input": "login", "constraint": "length"}),
+        )),
+    );
+    assert_eq!(
+        Validated::from_input_value(&graphql_input_value!({"login": "ABC"})),
+        Err(FieldError::new(
+            "Invalid value for `login`: value must match `^[a-z]+$` pattern",
+            graphql_value!({"input": "login", "constraint": "regex"}),
+        )),
+    );
+    assert_eq!(
+        Validated::from_input_value(&graphql_input_value!({"login": "abc", "age": 200})),
+        Err(FieldError::new(
+            "Invalid value for `age`: value must be at most 150, found: 200",
+            graphql_value!({"input": "age", "constraint": "range"}),
+        )),
+    );
+}
+
+#[test]
+fn test_deprecated_input_object_fields() {
+    let mut registry: Registry = Registry::new(FnvHashMap::default());
+    let meta = Deprecated::meta(&(), &mut registry);
+    let fields = match meta {
+        MetaType::InputObject(meta) => meta.input_fields,
+        _ => panic!("expected input object meta"),
+    };
+
+    assert_eq!(
+        fields
+            .iter()
+            .map(|f| (f.name.as_str(), f.deprecation_status.clone()))
+            .collect::<Vec<_>>(),
+        vec![
+            ("id", DeprecationStatus::Current),
+            (
+                "legacyId",
+                DeprecationStatus::Deprecated(Some("Use `id` instead.".into())),
+            ),
+            ("flag", DeprecationStatus::Deprecated(None)),
+        ],
+    );
+
+    assert_eq!(
+        Deprecated::from_input_value(&graphql_input_value!({"id": "1", "legacyId": "2"})),
+        Ok(Deprecated {
+            id: "1".into(),
+            legacy_id: Some("2".into()),
+            flag: None,
+        }),
+    );
+}
+
+#[test]
+fn test_unknown_input_object_fields() {
+    let input: InputValue = graphql_input_value!({"regularField": "a", "secret": true});
+
+    assert_eq!(
+        <NoRenameInput as FromInputValue>::from_input_value(&graphql_input_value!({
+            "regular_field": "a",
+            "secret": true,
+        })),
+        Ok(NoRenameInput {
+            regular_field: "a".into(),
+        }),
+    );
+    assert_eq!(
+        Strict::from_input_value(&input),
+        Err(FieldError::new(
+            "Unknown field `secret` of `Strict` input object",
+            graphql_value!({"unknownField": "secret"}),
+        )),
+    );
+    assert_eq!(
+        <Strict as FromInputValue>::from_input_value(&graphql_input_value!({"regularField": "a"})),
+        Ok(Strict {
+            regular_field: "a".into(),
+            other: None,
+        }),
+    );
+}
+
+#[test]
+fn test_input_object_max_depth() {
+    let nested = |depth| {
+        (1..depth).fold(graphql_input_value!({}), |inner: InputValue, _| {
+            InputValue::object(
+                vec![("and", InputValue::list(vec![inner]))]
+                    .into_iter()
+                    .collect(),
+            )
+        })
+    };
+
+    assert_eq!(
+        <Filter as FromInputValue>::from_input_value(&nested(3))
+            .unwrap()
+            .and
+            .unwrap()[0]
+            .and
+            .as_ref()
+            .unwrap()
+            .len(),
+        1,
+    );
+    assert_eq!(
+        <Filter as FromInputValue>::from_input_value(&nested(4)),
+        Err(FieldError::new(
+            "`Filter` input object is nested too deeply, maximum depth is 3",
+            graphql_value!({"maxDepth": 3}),
+        )),
+    );
+
+    // Depth is released after both successful and failed conversions.
+    assert!(<Filter as FromInputValue>::from_input_value(&nested(3)).is_ok());
+}
+
+#[test]
+fn test_input_object_default_max_depth() {
+    let nested = |depth| {
+        (1..depth).fold(graphql_input_value!({}), |inner: InputValue, _| {
+            InputValue::object(vec![("inner", inner)].into_iter().collect())
+        })
+    };
+
+    assert!(<Recursive as FromInputValue>::from_input_value(&nested(64)).is_ok());
+    assert_eq!(
+        <Recursive as FromInputValue>::from_input_value(&nested(65))
+            .unwrap_err()
+            .message(),
+        "`Recursive` input object is nested too deeply, maximum depth is 64",
+    );
+}
+
 #[test]
 fn test_doc_comment() {
     let mut registry: Registry = Registry::new(FnvHashMap::default());
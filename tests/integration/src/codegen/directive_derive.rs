@@ -0,0 +1,237 @@
+//! Tests for `#[derive(GraphQLDirective)]` macro.
+
+use juniper::{
+    execute, graphql_object, graphql_value, graphql_vars, FieldError, GraphQLDirective,
+    GraphQLObject, ScalarValue, Value,
+};
+
+use crate::util::schema;
+
+/// Converts a string into the upper case.
+#[derive(GraphQLDirective)]
+struct Uppercase;
+
+impl Uppercase {
+    fn transform<S: ScalarValue>(self, value: Value<S>) -> Result<Value<S>, String> {
+        value
+            .as_scalar()
+            .and_then(ScalarValue::as_str)
+            .map(|s| Value::scalar(s.to_uppercase()))
+            .ok_or_else(|| format!("Expected `String`, found: {}", value))
+    }
+}
+
+#[derive(GraphQLDirective)]
+#[graphql(name = "cut", transform_with = cut)]
+struct Truncate {
+    /// Maximum length of the string.
+    #[graphql(default = 3)]
+    max_len: i32,
+    suffix: Option<String>,
+}
+
+fn cut<S: ScalarValue>(d: Truncate, value: Value<S>) -> Result<Value<S>, FieldError<S>> {
+    let s = value
+        .as_scalar()
+        .and_then(ScalarValue::as_str)
+        .ok_or_else(|| FieldError::from("Expected `String`"))?;
+    let mut cut = s.chars().take(d.max_len as usize).collect::<String>();
+    if let Some(suffix) = d.suffix {
+        cut.push_str(&suffix);
+    }
+    Ok(Value::scalar(cut))
+}
+
+#[derive(GraphQLObject)]
+struct Human {
+    name: String,
+    age: i32,
+}
+
+struct QueryRoot;
+
+#[graphql_object]
+impl QueryRoot {
+    fn hello() -> &'static str {
+        "hello"
+    }
+
+    fn humans() -> Vec<Human> {
+        vec![
+            Human {
+                name: "luke".into(),
+                age: 19,
+            },
+            Human {
+                name: "leia".into(),
+                age: 19,
+            },
+        ]
+    }
+
+    async fn async_hello() -> &'static str {
+        "hello"
+    }
+}
+
+#[tokio::test]
+async fn transforms_field_value() {
+    const DOC: &str = r#"{
+        hello @uppercase
+        raw: hello
+        asyncHello @uppercase
+    }"#;
+
+    let schema = schema(QueryRoot)
+        .with_directive::<Uppercase>()
+        .with_directive::<Truncate>();
+
+    assert_eq!(
+        execute(DOC, None, &schema, &graphql_vars! {}, &()).await,
+        Ok((
+            graphql_value!({"hello": "HELLO", "raw": "hello", "asyncHello": "HELLO"}),
+            vec![],
+        )),
+    );
+}
+
+#[tokio::test]
+async fn transforms_nested_field_values() {
+    const DOC: &str = r#"{
+        humans { name @uppercase }
+    }"#;
+
+    let schema = schema(QueryRoot).with_directive::<Uppercase>();
+
+    assert_eq!(
+        execute(DOC, None, &schema, &graphql_vars! {}, &()).await,
+        Ok((
+            graphql_value!({"humans": [{"name": "LUKE"}, {"name": "LEIA"}]}),
+            vec![],
+        )),
+    );
+}
+
+#[tokio::test]
+async fn applies_directives_in_order() {
+    const DOC: &str = r#"query($suffix: String) {
+        a: hello @cut @uppercase
+        b: hello @uppercase @cut(maxLen: 1, suffix: $suffix)
+    }"#;
+
+    let schema = schema(QueryRoot)
+        .with_directive::<Uppercase>()
+        .with_directive::<Truncate>();
+
+    assert_eq!(
+        execute(DOC, None, &schema, &graphql_vars! {"suffix": "..."}, &()).await,
+        Ok((graphql_value!({"a": "HEL", "b": "H..."}), vec![])),
+    );
+}
+
+#[tokio::test]
+async fn reports_transformation_error() {
+    const DOC: &str = r#"{
+        humans { age @uppercase }
+    }"#;
+
+    let schema = schema(QueryRoot).with_directive::<Uppercase>();
+
+    let (res, errs) = execute(DOC, None, &schema, &graphql_vars! {}, &())
+        .await
+        .unwrap();
+
+    assert_eq!(res, graphql_value!(null));
+    assert_eq!(errs.len(), 1);
+    assert_eq!(errs[0].error().message(), "Expected `String`, found: 19");
+}
+
+#[tokio::test]
+async fn fails_validation_when_not_registered() {
+    const DOC: &str = r#"{
+        hello @uppercase
+    }"#;
+
+    let schema = schema(QueryRoot);
+
+    assert!(execute(DOC, None, &schema, &graphql_vars! {}, &())
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn has_directive_introspection() {
+    const DOC: &str = r#"{
+        __schema {
+            directives {
+                name
+                description
+                locations
+                args {
+                    name
+                    description
+                    defaultValue
+                    type { name ofType { name } }
+                }
+            }
+        }
+    }"#;
+
+    let schema = schema(QueryRoot)
+        .with_directive::<Uppercase>()
+        .with_directive::<Truncate>();
+
+    let (res, errs) = execute(DOC, None, &schema, &graphql_vars! {}, &())
+        .await
+        .unwrap();
+    assert_eq!(errs, vec![]);
+
+    let directives = res
+        .as_object_value()
+        .and_then(|o| o.get_field_value("__schema"))
+        .and_then(|s| s.as_object_value())
+        .and_then(|s| s.get_field_value("directives"))
+        .and_then(Value::as_list_value)
+        .unwrap();
+    let find = |name: &str| {
+        directives
+            .iter()
+            .find(|d| {
+                d.as_object_value()
+                    .and_then(|d| d.get_field_value("name"))
+                    .and_then(Value::as_string_value)
+                    == Some(name)
+            })
+            .cloned()
+            .unwrap()
+    };
+
+    assert_eq!(
+        find("uppercase"),
+        graphql_value!({
+            "name": "uppercase",
+            "description": "Converts a string into the upper case.",
+            "locations": ["FIELD"],
+            "args": [],
+        }),
+    );
+    assert_eq!(
+        find("cut"),
+        graphql_value!({
+            "name": "cut",
+            "description": null,
+            "locations": ["FIELD"],
+            "args": [{
+                "name": "maxLen",
+                "description": "Maximum length of the string.",
+                "defaultValue": "3",
+                "type": {"name": "Int", "ofType": null},
+            }, {
+                "name": "suffix",
+                "description": null,
+                "defaultValue": null,
+                "type": {"name": "String", "ofType": null},
+            }],
+        }),
+    );
+}
@@ -480,6 +480,62 @@ mod explicit_alias {
     }
 }
 
+mod conditional_implementers {
+    use super::*;
+
+    #[graphql_interface(for = Human)]
+    #[cfg_attr(all(), graphql_interface(for = Droid))]
+    #[cfg_attr(any(), graphql_interface(for = Ewok))]
+    trait Character {
+        fn id(&self) -> &str;
+    }
+
+    #[derive(GraphQLObject)]
+    #[graphql(impl = CharacterValue)]
+    struct Human {
+        id: String,
+    }
+
+    #[derive(GraphQLObject)]
+    #[graphql(impl = CharacterValue)]
+    struct Droid {
+        id: String,
+    }
+
+    struct QueryRoot;
+
+    #[graphql_object]
+    impl QueryRoot {
+        fn character(&self) -> CharacterValue {
+            Droid { id: "droid-99".into() }.into()
+        }
+    }
+
+    #[tokio::test]
+    async fn excludes_ewok() {
+        const DOC: &str = r#"{
+            __type(name: "Character") {
+                possibleTypes {
+                    name
+                }
+            }
+        }"#;
+
+        let schema = schema(QueryRoot);
+
+        assert_eq!(
+            execute(DOC, None, &schema, &graphql_vars! {}, &()).await,
+            Ok((
+                graphql_value!({"__type": {"possibleTypes": [
+                    {"name": "Droid"},
+                    {"name": "Human"},
+                ]}}),
+                vec![],
+            )),
+        );
+    }
+}
+
 mod trivial_async {
     use super::*;
 
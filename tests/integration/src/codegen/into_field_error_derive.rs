@@ -0,0 +1,223 @@
+//! Tests for `#[derive(IntoFieldError)]` macro.
+
+use std::fmt;
+
+use juniper::{
+    execute, graphql_object, graphql_value, graphql_vars, DefaultScalarValue, EmptyMutation,
+    EmptySubscription, FieldError, IntoFieldError, RootNode, ScalarValue,
+};
+
+use crate::custom_scalar::MyScalarValue;
+
+fn into_field_error<E, S>(e: E) -> FieldError<S>
+where
+    E: IntoFieldError<S>,
+    S: ScalarValue,
+{
+    e.into_field_error()
+}
+
+mod display {
+    use super::*;
+
+    #[derive(Debug, IntoFieldError)]
+    enum Error {
+        Unit,
+        Tuple(i32),
+        #[graphql(code = "NAMED")]
+        Named {
+            name: String,
+        },
+    }
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Self::Unit => write!(f, "unit"),
+                Self::Tuple(n) => write!(f, "tuple {}", n),
+                Self::Named { name } => write!(f, "named {}", name),
+            }
+        }
+    }
+
+    #[test]
+    fn uses_display_message() {
+        assert_eq!(
+            into_field_error::<_, DefaultScalarValue>(Error::Unit),
+            FieldError::new("unit", graphql_value!(null)),
+        );
+        assert_eq!(
+            into_field_error::<_, DefaultScalarValue>(Error::Tuple(1)),
+            FieldError::new("tuple 1", graphql_value!(null)),
+        );
+        assert_eq!(
+            into_field_error::<_, DefaultScalarValue>(Error::Named { name: "n".into() }),
+            FieldError::new("named n", graphql_value!({"code": "NAMED"})),
+        );
+    }
+}
+
+mod message {
+    use super::*;
+
+    #[derive(Debug, IntoFieldError)]
+    enum Error {
+        #[graphql(message = "Not found")]
+        Unit,
+        #[graphql(message = "Between {0} and {1}, not {0:?}: {{escaped}}")]
+        Tuple(i32, i32),
+        #[graphql(message = "Invalid {type} `{value}`")]
+        Named { r#type: &'static str, value: String },
+    }
+
+    #[test]
+    fn formats_fields() {
+        assert_eq!(
+            into_field_error::<_, DefaultScalarValue>(Error::Unit).message(),
+            "Not found",
+        );
+        assert_eq!(
+            into_field_error::<_, DefaultScalarValue>(Error::Tuple(1, 2)).message(),
+            "Between 1 and 2, not 1: {escaped}",
+        );
+        assert_eq!(
+            into_field_error::<_, DefaultScalarValue>(Error::Named {
+                r#type: "email",
+                value: "me@".into(),
+            })
+            .message(),
+            "Invalid email `me@`",
+        );
+    }
+}
+
+mod extensions {
+    use super::*;
+
+    #[derive(Debug, IntoFieldError)]
+    enum Error {
+        #[graphql(message = "Too many", code = "LIMIT", extensions(max = *_0, retry = true))]
+        Limit(i32),
+        #[graphql(
+            message = "Invalid input",
+            extensions(field = field.as_str(), hint = hint.clone()),
+        )]
+        Input { field: String, hint: Option<String> },
+    }
+
+    #[test]
+    fn adds_extensions() {
+        assert_eq!(
+            into_field_error::<_, DefaultScalarValue>(Error::Limit(10)),
+            FieldError::new(
+                "Too many",
+                graphql_value!({"code": "LIMIT", "max": 10, "retry": true}),
+            ),
+        );
+        assert_eq!(
+            into_field_error::<_, DefaultScalarValue>(Error::Input {
+                field: "email".into(),
+                hint: None,
+            }),
+            FieldError::new(
+                "Invalid input",
+                graphql_value!({"field": "email", "hint": null}),
+            ),
+        );
+    }
+
+    #[test]
+    fn supports_custom_scalar() {
+        let err = into_field_error::<_, MyScalarValue>(Error::Limit(10));
+
+        assert_eq!(
+            err.extensions(),
+            &graphql_value!({"code": "LIMIT", "max": 10, "retry": true}),
+        );
+    }
+}
+
+mod transparent {
+    use super::*;
+
+    #[derive(Debug, IntoFieldError)]
+    enum Inner {
+        #[graphql(message = "Inner", code = "INNER")]
+        Error,
+    }
+
+    #[derive(Debug, IntoFieldError)]
+    enum Error {
+        #[graphql(transparent)]
+        Inner(Inner),
+        #[graphql(transparent)]
+        Field { error: FieldError },
+    }
+
+    #[test]
+    fn delegates() {
+        assert_eq!(
+            into_field_error::<_, DefaultScalarValue>(Error::Inner(Inner::Error)),
+            FieldError::new("Inner", graphql_value!({"code": "INNER"})),
+        );
+        assert_eq!(
+            into_field_error::<_, DefaultScalarValue>(Error::Field {
+                error: "Field".into(),
+            }),
+            FieldError::from("Field"),
+        );
+    }
+}
+
+mod generic {
+    use super::*;
+
+    #[derive(Debug, IntoFieldError)]
+    enum Error<T: fmt::Debug> {
+        #[graphql(message = "Invalid {0:?}", code = "INVALID")]
+        Invalid(T),
+    }
+
+    #[test]
+    fn formats_generic_field() {
+        assert_eq!(
+            into_field_error::<_, DefaultScalarValue>(Error::Invalid(vec![1, 2])),
+            FieldError::new("Invalid [1, 2]", graphql_value!({"code": "INVALID"})),
+        );
+    }
+}
+
+mod resolver {
+    use super::*;
+
+    #[derive(Debug, IntoFieldError)]
+    enum UserError {
+        #[graphql(message = "User {0} not found", code = "NOT_FOUND")]
+        NotFound(i32),
+    }
+
+    struct Query;
+
+    #[graphql_object]
+    impl Query {
+        fn user(id: i32) -> Result<Option<String>, UserError> {
+            Err(UserError::NotFound(id))
+        }
+    }
+
+    #[tokio::test]
+    async fn reports_error() {
+        let schema = RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new());
+
+        let (res, errs) = execute("{ user(id: 7) }", None, &schema, &graphql_vars! {}, &())
+            .await
+            .unwrap();
+
+        assert_eq!(res, graphql_value!({"user": null}));
+        assert_eq!(errs.len(), 1);
+        assert_eq!(
+            errs[0].error(),
+            &FieldError::new("User 7 not found", graphql_value!({"code": "NOT_FOUND"})),
+        );
+    }
+}
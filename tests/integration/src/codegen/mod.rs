@@ -1,9 +1,12 @@
+mod crud_derive;
 mod derive_enum;
 mod derive_input_object;
 mod derive_object_with_raw_idents;
+mod directive_derive;
 mod interface_attr_struct;
 mod interface_attr_trait;
 mod interface_derive;
+mod into_field_error_derive;
 mod object_attr;
 mod object_derive;
 mod scalar_attr_derive_input;
@@ -1,9 +1,9 @@
 //! Tests for `#[graphql_object]` macro.
 
 use juniper::{
-    execute, graphql_object, graphql_value, graphql_vars, DefaultScalarValue, EmptyMutation,
-    EmptySubscription, Executor, FieldError, FieldResult, GraphQLInputObject, GraphQLObject,
-    GraphQLType, IntoFieldError, RootNode, ScalarValue,
+    execute, graphql_interface, graphql_object, graphql_value, graphql_vars, DefaultScalarValue,
+    EmptyMutation, EmptySubscription, Executor, FieldError, FieldResult, GraphQLInputObject,
+    GraphQLObject, GraphQLType, IntoFieldError, PathSegment, RootNode, ScalarValue,
 };
 
 fn schema<'q, C, Q>(query_root: Q) -> RootNode<'q, Q, EmptyMutation<C>, EmptySubscription<C>>
@@ -343,6 +343,546 @@ mod ignored_method {
     }
 }
 
+mod conditionally_skipped_method {
+    use super::*;
+
+    struct Tenant {
+        beta: bool,
+    }
+
+    impl juniper::Context for Tenant {}
+
+    struct Human {
+        id: &'static str,
+    }
+
+    #[graphql_object(context = Tenant)]
+    impl Human {
+        fn id(&self) -> &str {
+            self.id
+        }
+
+        #[graphql(skip_if = Self::is_hidden)]
+        fn planet() -> Option<&'static str> {
+            Some("earth")
+        }
+
+        #[graphql(skip_if = Self::is_hidden)]
+        async fn home() -> Option<&'static str> {
+            Some("Mars")
+        }
+    }
+
+    impl Human {
+        fn is_hidden(&self, tenant: &Tenant) -> bool {
+            !tenant.beta || self.id == "hidden"
+        }
+    }
+
+    struct QueryRoot;
+
+    #[graphql_object(context = Tenant)]
+    impl QueryRoot {
+        fn human(id: String) -> Human {
+            Human {
+                id: if id == "hidden" { "hidden" } else { "human-32" },
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn resolves() {
+        const DOC: &str = r#"{
+            human(id: "human-32") {
+                id
+                planet
+                home
+            }
+        }"#;
+
+        let schema = schema(QueryRoot);
+
+        assert_eq!(
+            execute(
+                DOC,
+                None,
+                &schema,
+                &graphql_vars! {},
+                &Tenant { beta: true }
+            )
+            .await,
+            Ok((
+                graphql_value!({"human": {"id": "human-32", "planet": "earth", "home": "Mars"}}),
+                vec![],
+            )),
+        );
+    }
+
+    #[tokio::test]
+    async fn resolves_null_when_skipped() {
+        const DOC: &str = r#"{
+            human(id: "hidden") {
+                id
+                planet
+                home
+            }
+        }"#;
+
+        let schema = schema(QueryRoot);
+
+        assert_eq!(
+            execute(
+                DOC,
+                None,
+                &schema,
+                &graphql_vars! {},
+                &Tenant { beta: true }
+            )
+            .await,
+            Ok((
+                graphql_value!({"human": {"id": "hidden", "planet": null, "home": null}}),
+                vec![],
+            )),
+        );
+    }
+
+    #[test]
+    fn resolves_null_when_skipped_sync() {
+        const DOC: &str = r#"{
+            human(id: "human-32") {
+                planet
+            }
+        }"#;
+
+        let schema = schema(QueryRoot);
+
+        assert_eq!(
+            juniper::execute_sync(
+                DOC,
+                None,
+                &schema,
+                &graphql_vars! {},
+                &Tenant { beta: false }
+            ),
+            Ok((graphql_value!({"human": {"planet": null}}), vec![])),
+        );
+    }
+}
+
+mod split_into_parts {
+    use super::*;
+
+    struct Database {
+        balance: i32,
+    }
+
+    impl juniper::Context for Database {}
+
+    struct Human {
+        id: &'static str,
+    }
+
+    #[graphql_object(context = Database, parts = [payments::HumanPayments, orders::HumanOrders])]
+    impl Human {
+        fn id(&self) -> &str {
+            self.id
+        }
+    }
+
+    mod payments {
+        use super::*;
+
+        #[graphql_object(part = HumanPayments)]
+        impl Human {
+            /// Balance of the human.
+            fn balance(ctx: &Database) -> i32 {
+                ctx.balance
+            }
+
+            fn owner(&self) -> &str {
+                self.id
+            }
+        }
+    }
+
+    mod orders {
+        use super::*;
+
+        #[graphql_object(part = HumanOrders, rename_all = "none")]
+        impl Human {
+            async fn last_orders(limit: i32) -> Vec<i32> {
+                (1..=limit).collect()
+            }
+        }
+    }
+
+    struct QueryRoot;
+
+    #[graphql_object(context = Database)]
+    impl QueryRoot {
+        fn human() -> Human {
+            Human { id: "human-32" }
+        }
+    }
+
+    #[tokio::test]
+    async fn resolves_fields_of_all_parts() {
+        const DOC: &str = r#"{
+            human {
+                id
+                balance
+                owner
+                last_orders(limit: 2)
+            }
+        }"#;
+
+        let schema = schema(QueryRoot);
+
+        assert_eq!(
+            execute(
+                DOC,
+                None,
+                &schema,
+                &graphql_vars! {},
+                &Database { balance: 7 }
+            )
+            .await,
+            Ok((
+                graphql_value!({"human": {
+                    "id": "human-32",
+                    "balance": 7,
+                    "owner": "human-32",
+                    "last_orders": [1, 2],
+                }}),
+                vec![],
+            )),
+        );
+    }
+
+    #[test]
+    fn resolves_sync_fields_of_all_parts() {
+        const DOC: &str = r#"{
+            human {
+                id
+                balance
+            }
+        }"#;
+
+        let schema = schema(QueryRoot);
+
+        assert_eq!(
+            juniper::execute_sync(
+                DOC,
+                None,
+                &schema,
+                &graphql_vars! {},
+                &Database { balance: 7 }
+            ),
+            Ok((
+                graphql_value!({"human": {"id": "human-32", "balance": 7}}),
+                vec![],
+            )),
+        );
+    }
+
+    #[tokio::test]
+    async fn has_fields_of_all_parts() {
+        const DOC: &str = r#"{
+            __type(name: "Human") {
+                fields {
+                    name
+                    description
+                }
+            }
+        }"#;
+
+        let schema = schema(QueryRoot);
+
+        assert_eq!(
+            execute(
+                DOC,
+                None,
+                &schema,
+                &graphql_vars! {},
+                &Database { balance: 7 }
+            )
+            .await,
+            Ok((
+                graphql_value!({"__type": {"fields": [
+                    {"name": "id", "description": null},
+                    {"name": "last_orders", "description": null},
+                    {"name": "balance", "description": "Balance of the human."},
+                    {"name": "owner", "description": null},
+                ]}}),
+                vec![],
+            )),
+        );
+    }
+
+    #[test]
+    fn reflects_fields_of_all_parts() {
+        use juniper::macros::reflect::Fields;
+
+        assert_eq!(
+            <Human as Fields<DefaultScalarValue>>::NAMES,
+            ["id", "last_orders", "balance", "owner"],
+        );
+    }
+}
+
+mod timeout {
+    use std::time::Duration;
+
+    use super::*;
+
+    struct QueryRoot;
+
+    #[graphql_object]
+    impl QueryRoot {
+        #[graphql(timeout = "50ms")]
+        async fn slow() -> Option<i32> {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            Some(1)
+        }
+
+        #[graphql(timeout = "5s")]
+        async fn fast() -> i32 {
+            2
+        }
+    }
+
+    #[tokio::test]
+    async fn resolves_within_timeout() {
+        const DOC: &str = r#"{
+            fast
+        }"#;
+
+        let schema = schema(QueryRoot);
+
+        assert_eq!(
+            execute(DOC, None, &schema, &graphql_vars! {}, &()).await,
+            Ok((graphql_value!({"fast": 2}), vec![])),
+        );
+    }
+
+    #[tokio::test]
+    async fn errors_once_timeout_elapses() {
+        const DOC: &str = r#"{
+            fast
+            slow
+        }"#;
+
+        let schema = schema(QueryRoot);
+
+        let (res, errs) = execute(DOC, None, &schema, &graphql_vars! {}, &())
+            .await
+            .unwrap();
+
+        assert_eq!(res, graphql_value!({"fast": 2, "slow": null}));
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].path(), &[PathSegment::from("slow")]);
+        assert_eq!(
+            errs[0].error().extensions(),
+            &graphql_value!({"code": "TIMEOUT"}),
+        );
+    }
+}
+
+mod retry {
+    use std::sync::atomic::{AtomicI32, Ordering};
+
+    use super::*;
+
+    #[derive(Default)]
+    struct Upstream {
+        calls: AtomicI32,
+    }
+
+    impl juniper::Context for Upstream {}
+
+    impl Upstream {
+        fn call(&self, fails: i32) -> FieldResult<i32> {
+            let n = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            if n <= fails {
+                Err(FieldError::new("Upstream failed", graphql_value!({"n": n})))
+            } else {
+                Ok(n)
+            }
+        }
+    }
+
+    struct QueryRoot;
+
+    #[graphql_object(context = Upstream)]
+    impl QueryRoot {
+        #[graphql(retry(times = 2, backoff = "1ms"))]
+        async fn flaky(fails: i32, ctx: &Upstream) -> FieldResult<i32> {
+            ctx.call(fails)
+        }
+
+        #[graphql(retry(times = 1))]
+        async fn broken() -> FieldResult<i32> {
+            Err("Upstream is down".into())
+        }
+    }
+
+    #[tokio::test]
+    async fn retries_until_succeeds() {
+        const DOC: &str = r#"{
+            flaky(fails: 2)
+        }"#;
+
+        let schema = schema(QueryRoot);
+        let ctx = Upstream::default();
+
+        assert_eq!(
+            execute(DOC, None, &schema, &graphql_vars! {}, &ctx).await,
+            Ok((graphql_value!({"flaky": 3}), vec![])),
+        );
+        assert_eq!(ctx.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn errors_with_attempts_once_exhausted() {
+        const DOC: &str = r#"{
+            flaky(fails: 5)
+        }"#;
+
+        let schema = schema(QueryRoot);
+        let ctx = Upstream::default();
+
+        let (res, errs) = execute(DOC, None, &schema, &graphql_vars! {}, &ctx)
+            .await
+            .unwrap();
+
+        assert_eq!(res, graphql_value!(null));
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].error().message(), "Upstream failed");
+        assert_eq!(
+            errs[0].error().extensions(),
+            &graphql_value!({"n": 3, "attempts": 3}),
+        );
+        assert_eq!(ctx.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn adds_attempts_to_empty_extensions() {
+        const DOC: &str = r#"{
+            broken
+        }"#;
+
+        let schema = schema(QueryRoot);
+
+        let (_, errs) = execute(DOC, None, &schema, &graphql_vars! {}, &Upstream::default())
+            .await
+            .unwrap();
+
+        assert_eq!(errs.len(), 1);
+        assert_eq!(
+            errs[0].error().extensions(),
+            &graphql_value!({"attempts": 2}),
+        );
+    }
+}
+
+mod complexity {
+    use juniper::{execute_with_limits, Limits, RequestError};
+
+    use super::*;
+
+    struct Human;
+
+    #[graphql_object]
+    impl Human {
+        fn id() -> &'static str {
+            "human-32"
+        }
+
+        #[graphql(complexity = 10)]
+        fn home_planet() -> &'static str {
+            "earth"
+        }
+
+        #[graphql(complexity = |args, child| {
+            let first = args.get::<i32>("first").ok().flatten().unwrap_or(1);
+            (first.max(0) as usize) * child
+        })]
+        fn friends(#[graphql(default = 1)] first: i32) -> Vec<Human> {
+            (0..first).map(|_| Human).collect()
+        }
+    }
+
+    struct QueryRoot;
+
+    #[graphql_object]
+    impl QueryRoot {
+        fn human() -> Human {
+            Human
+        }
+    }
+
+    #[tokio::test]
+    async fn executes_within_cost() {
+        const DOC: &str = r#"{
+            human {
+                homePlanet
+                friends(first: 2) {
+                    id
+                }
+            }
+        }"#;
+
+        let schema = schema(QueryRoot);
+        let limits = Limits::new().max_cost(13);
+
+        assert_eq!(
+            execute_with_limits(DOC, None, &schema, &graphql_vars! {}, &(), &limits).await,
+            Ok((
+                graphql_value!({"human": {
+                    "homePlanet": "earth",
+                    "friends": [{"id": "human-32"}, {"id": "human-32"}],
+                }}),
+                vec![],
+            )),
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_exceeding_cost() {
+        const DOC: &str = r#"{
+            human {
+                friends(first: 3) {
+                    homePlanet
+                }
+            }
+        }"#;
+
+        let schema = schema(QueryRoot);
+        let limits = Limits::new().max_cost(30);
+
+        let errors =
+            match execute_with_limits(DOC, None, &schema, &graphql_vars! {}, &(), &limits).await {
+                Err(RequestError::Validation(errors)) => errors,
+                res => panic!("unexpected result: {:?}", res),
+            };
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].rule(), Some("MaxCost"));
+        assert_eq!(errors[0].param("actual"), Some(&graphql_value!(31)));
+    }
+
+    #[tokio::test]
+    async fn is_declared_on_field() {
+        let schema = schema(QueryRoot);
+        let human = schema.schema.concrete_type_by_name("Human").unwrap();
+
+        let declared = |name| human.field_by_name(name).unwrap().complexity.is_some();
+
+        assert!(!declared("id"));
+        assert!(declared("homePlanet"));
+        assert!(declared("friends"));
+    }
+}
+
 mod fallible_method {
     use super::*;
 
@@ -906,6 +1446,88 @@ mod nested_generic_lifetime_async {
     }
 }
 
+mod borrowed_from_parent {
+    use super::*;
+
+    struct Comment<'a> {
+        text: &'a str,
+    }
+
+    #[graphql_object]
+    impl<'a> Comment<'a> {
+        fn text(&self) -> &'a str {
+            self.text
+        }
+    }
+
+    struct Article<'a> {
+        title: &'a str,
+        tags: &'a [String],
+        comments: Vec<Comment<'a>>,
+    }
+
+    #[graphql_object]
+    impl<'a> Article<'a> {
+        fn title(&self) -> &'a str {
+            self.title
+        }
+
+        async fn tags(&self) -> &'a [String] {
+            self.tags
+        }
+
+        async fn comments(&self) -> &[Comment<'a>] {
+            &self.comments
+        }
+    }
+
+    struct QueryRoot {
+        title: String,
+        tags: Vec<String>,
+    }
+
+    #[graphql_object]
+    impl QueryRoot {
+        fn article(&self) -> Article<'_> {
+            Article {
+                title: &self.title,
+                tags: &self.tags,
+                comments: self.tags.iter().map(|text| Comment { text }).collect(),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn resolves_borrowed_fields() {
+        const DOC: &str = r#"{
+            article {
+                title
+                tags
+                comments {
+                    text
+                }
+            }
+        }"#;
+
+        let schema = schema(QueryRoot {
+            title: "Borrowing".into(),
+            tags: vec!["rust".into(), "graphql".into()],
+        });
+
+        assert_eq!(
+            execute(DOC, None, &schema, &graphql_vars! {}, &()).await,
+            Ok((
+                graphql_value!({"article": {
+                    "title": "Borrowing",
+                    "tags": ["rust", "graphql"],
+                    "comments": [{"text": "rust"}, {"text": "graphql"}],
+                }}),
+                vec![],
+            )),
+        );
+    }
+}
+
 mod argument {
     use super::*;
 
@@ -1051,8 +1673,235 @@ mod default_argument {
             format!("{}|{}&{}", arg1, arg2, r#arg3)
         }
 
-        fn info(#[graphql(default = Point { x: 1 })] coord: Point) -> i32 {
-            coord.x
+        fn info(#[graphql(default = Point { x: 1 })] coord: Point) -> i32 {
+            coord.x
+        }
+    }
+
+    struct QueryRoot;
+
+    #[graphql_object]
+    impl QueryRoot {
+        fn human() -> Human {
+            Human
+        }
+    }
+
+    #[tokio::test]
+    async fn resolves_id_field() {
+        let schema = schema(QueryRoot);
+
+        for (input, expected) in &[
+            ("{ human { id } }", "0|second&true"),
+            ("{ human { id(arg1: 1) } }", "1|second&true"),
+            (r#"{ human { id(arg2: "") } }"#, "0|&true"),
+            (r#"{ human { id(arg1: 2, arg2: "") } }"#, "2|&true"),
+            (
+                r#"{ human { id(arg1: 1, arg2: "", arg3: false) } }"#,
+                "1|&false",
+            ),
+        ] {
+            let expected: &str = *expected;
+
+            assert_eq!(
+                execute(*input, None, &schema, &graphql_vars! {}, &()).await,
+                Ok((graphql_value!({"human": {"id": expected}}), vec![])),
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn resolves_info_field() {
+        let schema = schema(QueryRoot);
+
+        for (input, expected) in &[
+            ("{ human { info } }", 1),
+            ("{ human { info(coord: { x: 2 }) } }", 2),
+        ] {
+            let expected: i32 = *expected;
+
+            assert_eq!(
+                execute(*input, None, &schema, &graphql_vars! {}, &()).await,
+                Ok((graphql_value!({"human": {"info": expected}}), vec![])),
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn has_defaults() {
+        const DOC: &str = r#"{
+            __type(name: "Human") {
+                fields {
+                    args {
+                        name
+                        defaultValue
+                        type {
+                            name
+                            ofType {
+                                name
+                            }
+                        }
+                    }
+                }
+            }
+        }"#;
+
+        let schema = schema(QueryRoot);
+
+        assert_eq!(
+            execute(DOC, None, &schema, &graphql_vars! {}, &()).await,
+            Ok((
+                graphql_value!({"__type": {"fields": [{
+                    "args": [{
+                        "name": "arg1",
+                        "defaultValue": "0",
+                        "type": {"name": "Int", "ofType": null},
+                    }, {
+                        "name": "arg2",
+                        "defaultValue": r#""second""#,
+                        "type": {"name": "String", "ofType": null},
+                    }, {
+                        "name": "arg3",
+                        "defaultValue": "true",
+                        "type": {"name": "Boolean", "ofType": null},
+                    }],
+                }, {
+                    "args": [{
+                        "name": "coord",
+                        "defaultValue": "{x: 1}",
+                        "type": {"name": "Point", "ofType": null},
+                    }],
+                }]}}),
+                vec![],
+            )),
+        );
+    }
+}
+
+mod default_argument_with_context {
+    use super::*;
+
+    struct CustomContext {
+        locale: String,
+    }
+
+    impl juniper::Context for CustomContext {}
+
+    fn default_locale(ctx: &CustomContext) -> String {
+        ctx.locale.clone()
+    }
+
+    struct QueryRoot;
+
+    #[graphql_object(context = CustomContext)]
+    impl QueryRoot {
+        fn greeting(#[graphql(default_with_context = "default_locale")] locale: String) -> String {
+            format!("hello|{}", locale)
+        }
+
+        async fn greeting_async(
+            #[graphql(default_with_context = "self::default_locale")] locale: String,
+        ) -> String {
+            format!("hello|{}", locale)
+        }
+    }
+
+    #[tokio::test]
+    async fn resolves() {
+        let schema = schema(QueryRoot);
+        let ctx = CustomContext {
+            locale: "en".into(),
+        };
+
+        for (input, expected) in &[
+            ("{ greeting greetingAsync }", "hello|en"),
+            (
+                r#"{ greeting(locale: "fr") greetingAsync(locale: "fr") }"#,
+                "hello|fr",
+            ),
+            (
+                "{ greeting(locale: null) greetingAsync(locale: null) }",
+                "hello|en",
+            ),
+        ] {
+            let expected: &str = *expected;
+
+            assert_eq!(
+                execute(*input, None, &schema, &graphql_vars! {}, &ctx).await,
+                Ok((
+                    graphql_value!({"greeting": expected, "greetingAsync": expected}),
+                    vec![],
+                )),
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn has_nullable_arg_without_default() {
+        const DOC: &str = r#"{
+            __type(name: "QueryRoot") {
+                fields {
+                    args {
+                        name
+                        defaultValue
+                        type {
+                            name
+                            kind
+                        }
+                    }
+                }
+            }
+        }"#;
+
+        let schema = schema(QueryRoot);
+        let ctx = CustomContext {
+            locale: "en".into(),
+        };
+
+        let arg = graphql_value!({
+            "name": "locale",
+            "defaultValue": null,
+            "type": {"name": "String", "kind": "SCALAR"},
+        });
+        assert_eq!(
+            execute(DOC, None, &schema, &graphql_vars! {}, &ctx).await,
+            Ok((
+                graphql_value!({"__type": {"fields": [
+                    {"args": [(arg.clone())]},
+                    {"args": [(arg)]},
+                ]}}),
+                vec![],
+            )),
+        );
+    }
+}
+
+mod args_struct {
+    use super::*;
+
+    #[derive(GraphQLInputObject)]
+    struct Filter {
+        name: String,
+        #[graphql(default = "10")]
+        limit: i32,
+        offset: Option<i32>,
+    }
+
+    #[graphql_interface(for = Human)]
+    trait Searchable {
+        fn search(&self, name: String, limit: i32, offset: Option<i32>) -> String;
+    }
+
+    struct Human;
+
+    #[graphql_object(impl = SearchableValue)]
+    impl Human {
+        fn search(#[graphql(args)] filter: Filter) -> String {
+            format!("{}|{}|{:?}", filter.name, filter.limit, filter.offset)
+        }
+
+        async fn search_async(#[graphql(args)] filter: Filter, prefix: String) -> String {
+            format!("{}{}|{}", prefix, filter.name, filter.limit)
         }
     }
 
@@ -1063,62 +1912,77 @@ mod default_argument {
         fn human() -> Human {
             Human
         }
+
+        fn searchable() -> SearchableValue {
+            Human.into()
+        }
     }
 
     #[tokio::test]
-    async fn resolves_id_field() {
+    async fn resolves() {
         let schema = schema(QueryRoot);
 
         for (input, expected) in &[
-            ("{ human { id } }", "0|second&true"),
-            ("{ human { id(arg1: 1) } }", "1|second&true"),
-            (r#"{ human { id(arg2: "") } }"#, "0|&true"),
-            (r#"{ human { id(arg1: 2, arg2: "") } }"#, "2|&true"),
+            (r#"{ human { search(name: "a") } }"#, "a|10|None"),
+            (r#"{ human { search(name: "a", limit: 2) } }"#, "a|2|None"),
             (
-                r#"{ human { id(arg1: 1, arg2: "", arg3: false) } }"#,
-                "1|&false",
+                r#"{ human { search(name: "a", offset: 3, limit: 1) } }"#,
+                "a|1|Some(3)",
             ),
         ] {
             let expected: &str = *expected;
 
             assert_eq!(
                 execute(*input, None, &schema, &graphql_vars! {}, &()).await,
-                Ok((graphql_value!({"human": {"id": expected}}), vec![])),
+                Ok((graphql_value!({"human": {"search": expected}}), vec![])),
             );
         }
     }
 
     #[tokio::test]
-    async fn resolves_info_field() {
+    async fn resolves_async() {
+        const DOC: &str = r#"{
+            human {
+                searchAsync(prefix: "> ", name: "b")
+            }
+        }"#;
+
         let schema = schema(QueryRoot);
 
-        for (input, expected) in &[
-            ("{ human { info } }", 1),
-            ("{ human { info(coord: { x: 2 }) } }", 2),
-        ] {
-            let expected: i32 = *expected;
+        assert_eq!(
+            execute(DOC, None, &schema, &graphql_vars! {}, &()).await,
+            Ok((graphql_value!({"human": {"searchAsync": "> b|10"}}), vec![])),
+        );
+    }
 
-            assert_eq!(
-                execute(*input, None, &schema, &graphql_vars! {}, &()).await,
-                Ok((graphql_value!({"human": {"info": expected}}), vec![])),
-            );
-        }
+    #[tokio::test]
+    async fn resolves_via_interface() {
+        const DOC: &str = r#"{
+            searchable {
+                search(name: "c", limit: 5, offset: 1)
+            }
+        }"#;
+
+        let schema = schema(QueryRoot);
+
+        assert_eq!(
+            execute(DOC, None, &schema, &graphql_vars! {}, &()).await,
+            Ok((
+                graphql_value!({"searchable": {"search": "c|5|Some(1)"}}),
+                vec![]
+            )),
+        );
     }
 
     #[tokio::test]
-    async fn has_defaults() {
+    async fn has_flattened_args() {
         const DOC: &str = r#"{
             __type(name: "Human") {
                 fields {
+                    name
                     args {
                         name
                         defaultValue
-                        type {
-                            name
-                            ofType {
-                                name
-                            }
-                        }
                     }
                 }
             }
@@ -1130,30 +1994,112 @@ mod default_argument {
             execute(DOC, None, &schema, &graphql_vars! {}, &()).await,
             Ok((
                 graphql_value!({"__type": {"fields": [{
-                    "args": [{
-                        "name": "arg1",
-                        "defaultValue": "0",
-                        "type": {"name": "Int", "ofType": null},
-                    }, {
-                        "name": "arg2",
-                        "defaultValue": r#""second""#,
-                        "type": {"name": "String", "ofType": null},
-                    }, {
-                        "name": "arg3",
-                        "defaultValue": "true",
-                        "type": {"name": "Boolean", "ofType": null},
-                    }],
+                    "name": "search",
+                    "args": [
+                        {"name": "name", "defaultValue": null},
+                        {"name": "limit", "defaultValue": "10"},
+                        {"name": "offset", "defaultValue": null},
+                    ],
                 }, {
-                    "args": [{
-                        "name": "coord",
-                        "defaultValue": "{x: 1}",
-                        "type": {"name": "Point", "ofType": null},
-                    }],
+                    "name": "searchAsync",
+                    "args": [
+                        {"name": "name", "defaultValue": null},
+                        {"name": "limit", "defaultValue": "10"},
+                        {"name": "offset", "defaultValue": null},
+                        {"name": "prefix", "defaultValue": null},
+                    ],
                 }]}}),
                 vec![],
             )),
         );
     }
+
+    #[test]
+    fn does_not_register_args_type() {
+        let schema = schema(QueryRoot);
+
+        assert!(schema.schema.concrete_type_by_name("Filter").is_none());
+    }
+}
+
+mod validated_argument {
+    use super::*;
+
+    struct QueryRoot;
+
+    #[graphql_object]
+    impl QueryRoot {
+        fn page(
+            #[graphql(validate(range(min = 1, max = 100)))] size: i32,
+            #[graphql(validate(length(max = 3)))] tags: Option<Vec<String>>,
+        ) -> i32 {
+            size + tags.map_or(0, |t| t.len() as i32)
+        }
+
+        async fn echo(#[graphql(validate(regex = "^[0-9]+$"))] code: String) -> String {
+            code
+        }
+    }
+
+    #[tokio::test]
+    async fn resolves_valid() {
+        const DOC: &str = r#"{
+            page(size: 10, tags: ["a", "b"])
+            echo(code: "42")
+        }"#;
+
+        let schema = schema(QueryRoot);
+
+        assert_eq!(
+            execute(DOC, None, &schema, &graphql_vars! {}, &()).await,
+            Ok((graphql_value!({"page": 12, "echo": "42"}), vec![])),
+        );
+    }
+
+    #[tokio::test]
+    async fn errors_on_invalid() {
+        let schema = schema(QueryRoot);
+
+        for (doc, field, input, constraint, msg) in [
+            (
+                "{ page(size: 0) }",
+                "page",
+                "size",
+                "range",
+                "value must be at least 1, found: 0",
+            ),
+            (
+                r#"{ page(size: 1, tags: ["a", "b", "c", "d"]) }"#,
+                "page",
+                "tags",
+                "length",
+                "length must be at most 3, found: 4",
+            ),
+            (
+                r#"{ echo(code: "4a") }"#,
+                "echo",
+                "code",
+                "regex",
+                "value must match `^[0-9]+$` pattern",
+            ),
+        ] {
+            let (res, errs) = execute(doc, None, &schema, &graphql_vars! {}, &())
+                .await
+                .unwrap();
+
+            assert_eq!(res, graphql_value!(null), "{}", doc);
+            assert_eq!(errs.len(), 1, "{}", doc);
+            assert_eq!(errs[0].path(), &[PathSegment::from(field)], "{}", doc);
+            assert_eq!(
+                errs[0].error().message(),
+                format!("Invalid value for `{}`: {}", input, msg),
+            );
+            assert_eq!(
+                errs[0].error().extensions(),
+                &graphql_value!({"input": input, "constraint": constraint}),
+            );
+        }
+    }
 }
 
 mod description_from_doc_comment {
@@ -1168,6 +2114,21 @@ mod description_from_doc_comment {
         fn id() -> &'static str {
             "human-32"
         }
+
+        /// Rust `greet` docs.
+        fn greet(
+            /// Rust `name` docs.
+            /// Multi-line.
+            name: String,
+            /// This is not used as the description.
+            #[graphql(desc = "Explicit `greeting` description.")]
+            greeting: Option<String>,
+            /// Rust `ctx` docs, which are ignored.
+            ctx: &(),
+        ) -> String {
+            let _ = ctx;
+            format!("{}, {}", greeting.as_deref().unwrap_or("Hello"), name)
+        }
     }
 
     struct QueryRoot;
@@ -1186,6 +2147,9 @@ mod description_from_doc_comment {
                 description
                 fields {
                     description
+                    args {
+                        description
+                    }
                 }
             }
         }"#;
@@ -1197,12 +2161,37 @@ mod description_from_doc_comment {
             Ok((
                 graphql_value!({"__type": {
                     "description": "Rust docs.",
-                    "fields": [{"description": "Rust `id` docs."}],
+                    "fields": [{
+                        "description": "Rust `id` docs.",
+                        "args": [],
+                    }, {
+                        "description": "Rust `greet` docs.",
+                        "args": [
+                            {"description": "Rust `name` docs.\nMulti-line."},
+                            {"description": "Explicit `greeting` description."},
+                        ],
+                    }],
                 }}),
                 vec![],
             )),
         );
     }
+
+    #[tokio::test]
+    async fn resolves_documented_arguments() {
+        const DOC: &str = r#"{
+            human {
+                greet(name: "Bob")
+            }
+        }"#;
+
+        let schema = schema(QueryRoot);
+
+        assert_eq!(
+            execute(DOC, None, &schema, &graphql_vars! {}, &()).await,
+            Ok((graphql_value!({"human": {"greet": "Hello, Bob"}}), vec![])),
+        );
+    }
 }
 
 mod deprecation_from_attr {
@@ -1322,6 +2311,64 @@ mod deprecation_from_attr {
     }
 }
 
+mod deprecation_by_replacement {
+    use super::*;
+
+    struct Human;
+
+    #[graphql_object]
+    impl Human {
+        fn home_planet() -> &'static str {
+            "earth"
+        }
+
+        #[graphql(replaced_by = home_planet)]
+        fn planet() -> &'static str {
+            "earth"
+        }
+
+        #[graphql(replaced_by = "homeWorld")]
+        fn world() -> &'static str {
+            "earth"
+        }
+    }
+
+    struct QueryRoot;
+
+    #[graphql_object]
+    impl QueryRoot {
+        fn human() -> Human {
+            Human
+        }
+    }
+
+    #[tokio::test]
+    async fn provides_deprecation_reason() {
+        const DOC: &str = r#"{
+            __type(name: "Human") {
+                fields(includeDeprecated: true) {
+                    name
+                    deprecationReason
+                }
+            }
+        }"#;
+
+        let schema = schema(QueryRoot);
+
+        assert_eq!(
+            execute(DOC, None, &schema, &graphql_vars! {}, &()).await,
+            Ok((
+                graphql_value!({"__type": {"fields": [
+                    {"name": "homePlanet", "deprecationReason": null},
+                    {"name": "planet", "deprecationReason": "Use `homePlanet` instead."},
+                    {"name": "world", "deprecationReason": "Use `homeWorld` instead."},
+                ]}}),
+                vec![],
+            )),
+        );
+    }
+}
+
 mod explicit_name_description_and_deprecation {
     use super::*;
 
@@ -2122,3 +3169,95 @@ mod switched_context {
         );
     }
 }
+
+mod deprecated_argument {
+    use super::*;
+
+    struct Human;
+
+    #[graphql_object]
+    impl Human {
+        fn id(
+            #[graphql(deprecated = "Use `name` instead.")] id: Option<String>,
+            #[graphql(default, deprecated)] legacy: bool,
+            name: Option<String>,
+        ) -> String {
+            let _ = legacy;
+            name.or(id).unwrap_or_default()
+        }
+    }
+
+    #[tokio::test]
+    async fn resolves() {
+        const DOC: &str = r#"{
+            id(id: "human-32")
+        }"#;
+
+        let schema = schema(Human);
+
+        assert_eq!(
+            execute(DOC, None, &schema, &graphql_vars! {}, &()).await,
+            Ok((graphql_value!({"id": "human-32"}), vec![])),
+        );
+    }
+
+    #[tokio::test]
+    async fn hides_deprecated_args_by_default() {
+        const DOC: &str = r#"{
+            __type(name: "Human") {
+                fields {
+                    args {
+                        name
+                    }
+                }
+            }
+        }"#;
+
+        let schema = schema(Human);
+
+        assert_eq!(
+            execute(DOC, None, &schema, &graphql_vars! {}, &()).await,
+            Ok((
+                graphql_value!({"__type": {"fields": [{"args": [{"name": "name"}]}]}}),
+                vec![],
+            )),
+        );
+    }
+
+    #[tokio::test]
+    async fn has_deprecation_info() {
+        const DOC: &str = r#"{
+            __type(name: "Human") {
+                fields {
+                    args(includeDeprecated: true) {
+                        name
+                        isDeprecated
+                        deprecationReason
+                    }
+                }
+            }
+        }"#;
+
+        let schema = schema(Human);
+
+        assert_eq!(
+            execute(DOC, None, &schema, &graphql_vars! {}, &()).await,
+            Ok((
+                graphql_value!({"__type": {"fields": [{"args": [{
+                    "name": "id",
+                    "isDeprecated": true,
+                    "deprecationReason": "Use `name` instead.",
+                }, {
+                    "name": "legacy",
+                    "isDeprecated": true,
+                    "deprecationReason": null,
+                }, {
+                    "name": "name",
+                    "isDeprecated": false,
+                    "deprecationReason": null,
+                }]}]}}),
+                vec![],
+            )),
+        );
+    }
+}
@@ -236,6 +236,293 @@ mod ignored_field {
     }
 }
 
+mod conditionally_skipped_field {
+    use super::*;
+
+    struct Tenant {
+        beta: bool,
+    }
+
+    impl juniper::Context for Tenant {}
+
+    #[derive(GraphQLObject)]
+    #[graphql(context = Tenant)]
+    struct Human {
+        id: &'static str,
+        #[graphql(skip_if = "not_beta")]
+        planet: Option<&'static str>,
+    }
+
+    fn not_beta(_: &Human, tenant: &Tenant) -> bool {
+        !tenant.beta
+    }
+
+    struct QueryRoot;
+
+    #[graphql_object(context = Tenant)]
+    impl QueryRoot {
+        fn human() -> Human {
+            Human {
+                id: "human-32",
+                planet: Some("earth"),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn resolves() {
+        const DOC: &str = r#"{
+            human {
+                id
+                planet
+            }
+        }"#;
+
+        let schema = schema(QueryRoot);
+
+        assert_eq!(
+            execute(
+                DOC,
+                None,
+                &schema,
+                &graphql_vars! {},
+                &Tenant { beta: true }
+            )
+            .await,
+            Ok((
+                graphql_value!({"human": {"id": "human-32", "planet": "earth"}}),
+                vec![],
+            )),
+        );
+    }
+
+    #[tokio::test]
+    async fn resolves_null_when_skipped() {
+        const DOC: &str = r#"{
+            human {
+                id
+                planet
+            }
+        }"#;
+
+        let schema = schema(QueryRoot);
+
+        assert_eq!(
+            execute(
+                DOC,
+                None,
+                &schema,
+                &graphql_vars! {},
+                &Tenant { beta: false }
+            )
+            .await,
+            Ok((
+                graphql_value!({"human": {"id": "human-32", "planet": null}}),
+                vec![],
+            )),
+        );
+    }
+}
+
+mod complexity {
+    use juniper::{execute_with_limits, Limits, RequestError};
+
+    use super::*;
+
+    #[derive(GraphQLObject)]
+    struct Human {
+        id: &'static str,
+        #[graphql(complexity = 5)]
+        home_planet: &'static str,
+    }
+
+    struct QueryRoot;
+
+    #[graphql_object]
+    impl QueryRoot {
+        fn human() -> Human {
+            Human {
+                id: "human-32",
+                home_planet: "earth",
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn counts_field_cost() {
+        const DOC: &str = r#"{
+            human {
+                id
+                homePlanet
+            }
+        }"#;
+
+        let schema = schema(QueryRoot);
+
+        assert_eq!(
+            execute_with_limits(
+                DOC,
+                None,
+                &schema,
+                &graphql_vars! {},
+                &(),
+                &Limits::new().max_cost(7),
+            )
+            .await,
+            Ok((
+                graphql_value!({"human": {"id": "human-32", "homePlanet": "earth"}}),
+                vec![],
+            )),
+        );
+        assert!(matches!(
+            execute_with_limits(
+                DOC,
+                None,
+                &schema,
+                &graphql_vars! {},
+                &(),
+                &Limits::new().max_cost(6),
+            )
+            .await,
+            Err(RequestError::Validation(_)),
+        ));
+    }
+}
+
+mod aliased_field {
+    use super::*;
+
+    #[derive(GraphQLObject)]
+    struct Human {
+        id: &'static str,
+        #[graphql(alias = "homePlanet", alias(name = "planetName", hidden))]
+        planet: &'static str,
+    }
+
+    struct QueryRoot;
+
+    #[graphql_object]
+    impl QueryRoot {
+        fn human() -> Human {
+            Human {
+                id: "human-32",
+                planet: "earth",
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn resolves_aliases() {
+        const DOC: &str = r#"{
+            human {
+                planet
+                homePlanet
+                planetName
+            }
+        }"#;
+
+        let schema = schema(QueryRoot);
+
+        assert_eq!(
+            execute(DOC, None, &schema, &graphql_vars! {}, &()).await,
+            Ok((
+                graphql_value!({"human": {
+                    "planet": "earth",
+                    "homePlanet": "earth",
+                    "planetName": "earth",
+                }}),
+                vec![],
+            )),
+        );
+    }
+
+    #[tokio::test]
+    async fn deprecates_and_hides_aliases() {
+        const DOC: &str = r#"{
+            __type(name: "Human") {
+                fields(includeDeprecated: true) {
+                    name
+                    isDeprecated
+                    deprecationReason
+                }
+            }
+        }"#;
+
+        let schema = schema(QueryRoot);
+
+        assert_eq!(
+            execute(DOC, None, &schema, &graphql_vars! {}, &()).await,
+            Ok((
+                graphql_value!({"__type": {"fields": [
+                    {"name": "id", "isDeprecated": false, "deprecationReason": null},
+                    {"name": "planet", "isDeprecated": false, "deprecationReason": null},
+                    {
+                        "name": "homePlanet",
+                        "isDeprecated": true,
+                        "deprecationReason": "Use `planet` instead.",
+                    },
+                ]}}),
+                vec![],
+            )),
+        );
+    }
+}
+
+mod split_into_parts {
+    use super::*;
+
+    #[derive(GraphQLObject)]
+    #[graphql(parts = HumanFriends)]
+    struct Human {
+        id: &'static str,
+    }
+
+    #[graphql_object(part = HumanFriends)]
+    impl Human {
+        fn friends(&self) -> Vec<String> {
+            vec![format!("{}-friend", self.id)]
+        }
+
+        async fn friends_count() -> i32 {
+            1
+        }
+    }
+
+    struct QueryRoot;
+
+    #[graphql_object]
+    impl QueryRoot {
+        fn human() -> Human {
+            Human { id: "human-32" }
+        }
+    }
+
+    #[tokio::test]
+    async fn resolves() {
+        const DOC: &str = r#"{
+            human {
+                id
+                friends
+                friendsCount
+            }
+        }"#;
+
+        let schema = schema(QueryRoot);
+
+        assert_eq!(
+            execute(DOC, None, &schema, &graphql_vars! {}, &()).await,
+            Ok((
+                graphql_value!({"human": {
+                    "id": "human-32",
+                    "friends": ["human-32-friend"],
+                    "friendsCount": 1,
+                }}),
+                vec![],
+            )),
+        );
+    }
+}
+
 mod generic {
     use super::*;
 
@@ -427,6 +714,87 @@ mod nested_generic_lifetime_async {
     }
 }
 
+mod borrowed_from_context {
+    use super::*;
+
+    struct Database {
+        names: Vec<String>,
+        tags: Vec<&'static str>,
+    }
+
+    impl juniper::Context for Database {}
+
+    #[derive(GraphQLObject)]
+    #[graphql(context = Database)]
+    struct Droid<'a> {
+        name: &'a str,
+    }
+
+    #[derive(GraphQLObject)]
+    #[graphql(context = Database)]
+    struct Human<'a> {
+        name: &'a str,
+        nickname: Option<&'a str>,
+        aliases: &'a [String],
+        tags: &'a [&'static str],
+        droids: Vec<Droid<'a>>,
+        friends: Option<&'a [Option<&'a str>]>,
+    }
+
+    struct QueryRoot;
+
+    #[graphql_object(context = Database)]
+    impl QueryRoot {
+        fn human(ctx: &Database) -> Human<'_> {
+            Human {
+                name: &ctx.names[0],
+                nickname: None,
+                aliases: &ctx.names[1..],
+                tags: &ctx.tags,
+                droids: ctx.names.iter().map(|name| Droid { name }).collect(),
+                friends: None,
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn resolves_borrowed_fields() {
+        const DOC: &str = r#"{
+            human {
+                name
+                nickname
+                aliases
+                tags
+                droids {
+                    name
+                }
+                friends
+            }
+        }"#;
+
+        let schema = schema(QueryRoot);
+        let db = Database {
+            names: vec!["Luke".into(), "Red Five".into()],
+            tags: vec!["jedi"],
+        };
+
+        assert_eq!(
+            execute(DOC, None, &schema, &graphql_vars! {}, &db).await,
+            Ok((
+                graphql_value!({"human": {
+                    "name": "Luke",
+                    "nickname": null,
+                    "aliases": ["Red Five"],
+                    "tags": ["jedi"],
+                    "droids": [{"name": "Luke"}, {"name": "Red Five"}],
+                    "friends": null,
+                }}),
+                vec![],
+            )),
+        );
+    }
+}
+
 mod description_from_doc_comment {
     use super::*;
 
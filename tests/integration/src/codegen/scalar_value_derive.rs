@@ -136,3 +136,129 @@ mod allow_missing_attributes {
             .is_type::<bool>());
     }
 }
+
+mod coerce_float {
+    use juniper::{
+        execute, graphql_object, graphql_value, graphql_vars, parser::SourcePosition,
+        EmptyMutation, EmptySubscription, RequestError, RootNode, RuleError,
+    };
+
+    use super::*;
+
+    #[derive(Clone, Debug, Deserialize, PartialEq, ScalarValue, Serialize)]
+    #[serde(untagged)]
+    pub enum CustomScalarValue {
+        #[value(as_int)]
+        Int(i32),
+        #[value(as_float)]
+        Float(f64),
+        #[value(as_str, as_string, into_string)]
+        String(String),
+        #[value(as_bool)]
+        Boolean(bool),
+    }
+
+    #[derive(Clone, Debug, Deserialize, PartialEq, ScalarValue, Serialize)]
+    #[serde(untagged)]
+    pub enum StrictScalarValue {
+        #[value(as_float, as_int)]
+        Int(i32),
+        #[value(as_float, coerce_float)]
+        Float(f64),
+        #[value(as_str, as_string, into_string)]
+        String(String),
+        #[value(as_bool)]
+        Boolean(bool),
+    }
+
+    struct Query;
+
+    #[graphql_object(scalar = StrictScalarValue)]
+    impl Query {
+        fn half(value: f64) -> f64 {
+            value / 2.0
+        }
+
+        fn round(value: f64) -> i32 {
+            value.round() as i32
+        }
+    }
+
+    fn schema() -> RootNode<'static, Query, EmptyMutation, EmptySubscription, StrictScalarValue> {
+        RootNode::new_with_scalar_value(Query, EmptyMutation::new(), EmptySubscription::new())
+    }
+
+    #[test]
+    fn accepts_int_by_default() {
+        assert_eq!(CustomScalarValue::Int(1).as_float(), None);
+        assert_eq!(CustomScalarValue::Int(1).coerce_float(), Some(1.0));
+        assert_eq!(CustomScalarValue::Float(0.5).coerce_float(), Some(0.5));
+        assert_eq!(CustomScalarValue::Boolean(true).coerce_float(), None);
+    }
+
+    #[test]
+    fn accepts_marked_variants_only() {
+        assert_eq!(StrictScalarValue::Int(1).as_float(), Some(1.0));
+        assert_eq!(StrictScalarValue::Int(1).coerce_float(), None);
+        assert_eq!(StrictScalarValue::Float(0.5).coerce_float(), Some(0.5));
+    }
+
+    #[tokio::test]
+    async fn accepts_float_input() {
+        assert_eq!(
+            execute(
+                "{ half(value: 3.0) }",
+                None,
+                &schema(),
+                &graphql_vars! {},
+                &()
+            )
+            .await,
+            Ok((graphql_value!({"half": 1.5}), vec![])),
+        );
+        assert_eq!(
+            execute(
+                "query q($v: Float!) { half(value: $v) }",
+                None,
+                &schema(),
+                &graphql_vars! {"v": 3.0},
+                &(),
+            )
+            .await,
+            Ok((graphql_value!({"half": 1.5}), vec![])),
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_int_input() {
+        assert_eq!(
+            execute(
+                "{ half(value: 3) }",
+                None,
+                &schema(),
+                &graphql_vars! {},
+                &()
+            )
+            .await,
+            Err(RequestError::Validation(vec![RuleError::new(
+                r#"Invalid value for argument "value", expected type "Float!""#,
+                &[SourcePosition::new(14, 0, 14)],
+            )])),
+        );
+        assert_eq!(
+            execute(
+                "query q($v: Float!) { half(value: $v) }",
+                None,
+                &schema(),
+                &graphql_vars! {"v": 3},
+                &(),
+            )
+            .await,
+            Err(RequestError::VariableCoercion(vec![RuleError::new(
+                "Variable \"$v\" got invalid value. Expected input scalar `Float`. \
+                 Got: `3`. Details: Expected `Float`, found: 3.",
+                &[SourcePosition::new(8, 0, 8)],
+            )])),
+        );
+    }
+}
@@ -1092,6 +1092,101 @@ mod ignored_method {
     }
 }
 
+mod conditional_method {
+    use super::*;
+
+    #[graphql_union]
+    trait Character {
+        fn as_human(&self) -> Option<&Human> {
+            None
+        }
+        #[graphql(cfg(all()))]
+        fn as_droid(&self) -> Option<&Droid> {
+            None
+        }
+        #[graphql(cfg(any()))]
+        fn as_ewok(&self) -> Option<&Ewok> {
+            None
+        }
+    }
+
+    impl Character for Droid {
+        fn as_droid(&self) -> Option<&Droid> {
+            Some(&self)
+        }
+    }
+
+    type DynCharacter<'a> = dyn Character + Send + Sync + 'a;
+
+    struct QueryRoot;
+
+    #[graphql_object]
+    impl QueryRoot {
+        fn character(&self) -> Box<DynCharacter<'_>> {
+            Box::new(Droid {
+                id: "droid-99".to_string(),
+                primary_function: "run".to_string(),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn resolves_droid() {
+        const DOC: &str = r#"{
+            character {
+                ... on Droid {
+                    droidId: id
+                    primaryFunction
+                }
+            }
+        }"#;
+
+        let schema = schema(QueryRoot);
+
+        assert_eq!(
+            execute(DOC, None, &schema, &graphql_vars! {}, &()).await,
+            Ok((
+                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}}),
+                vec![],
+            )),
+        );
+    }
+
+    #[tokio::test]
+    async fn excludes_ewok() {
+        const DOC: &str = r#"{
+            __type(name: "Character") {
+                possibleTypes {
+                    name
+                }
+            }
+        }"#;
+
+        let schema = schema(QueryRoot);
+
+        assert_eq!(
+            execute(DOC, None, &schema, &graphql_vars! {}, &()).await,
+            Ok((
+                graphql_value!({"__type": {"possibleTypes": [
+                    {"name": "Human"},
+                    {"name": "Droid"},
+                ]}}),
+                vec![],
+            )),
+        );
+    }
+
+    #[test]
+    fn reflects_included_variants_only() {
+        use juniper::macros::reflect::BaseSubTypes;
+
+        assert_eq!(
+            <DynCharacter<'_> as BaseSubTypes<DefaultScalarValue>>::NAMES,
+            ["Character", "Human", "Droid"],
+        );
+    }
+}
+
 mod external_resolver {
     use super::*;
 
@@ -1016,6 +1016,87 @@ mod ignored_enum_variants {
     }
 }
 
+mod conditional_enum_variants {
+    use super::*;
+
+    #[derive(GraphQLUnion)]
+    enum Character {
+        A(Human),
+        #[graphql(cfg(all()))]
+        B(Droid),
+        #[graphql(cfg(any()))]
+        C(Ewok),
+    }
+
+    struct QueryRoot;
+
+    #[graphql_object]
+    impl QueryRoot {
+        fn character(&self) -> Character {
+            Character::B(Droid {
+                id: "droid-99".to_string(),
+                primary_function: "run".to_string(),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn resolves_droid() {
+        const DOC: &str = r#"{
+            character {
+                ... on Droid {
+                    droidId: id
+                    primaryFunction
+                }
+            }
+        }"#;
+
+        let schema = schema(QueryRoot);
+
+        assert_eq!(
+            execute(DOC, None, &schema, &graphql_vars! {}, &()).await,
+            Ok((
+                graphql_value!({"character": {"droidId": "droid-99", "primaryFunction": "run"}}),
+                vec![],
+            )),
+        );
+    }
+
+    #[tokio::test]
+    async fn excludes_ewok() {
+        const DOC: &str = r#"{
+            __type(name: "Character") {
+                possibleTypes {
+                    name
+                }
+            }
+        }"#;
+
+        let schema = schema(QueryRoot);
+
+        assert_eq!(
+            execute(DOC, None, &schema, &graphql_vars! {}, &()).await,
+            Ok((
+                graphql_value!({"__type": {"possibleTypes": [
+                    {"name": "Human"},
+                    {"name": "Droid"},
+                ]}}),
+                vec![],
+            )),
+        );
+    }
+
+    #[test]
+    fn reflects_included_variants_only() {
+        use juniper::macros::reflect::BaseSubTypes;
+
+        assert_eq!(
+            <Character as BaseSubTypes<DefaultScalarValue>>::NAMES,
+            ["Character", "Human", "Droid"],
+        );
+    }
+}
+
 mod external_resolver_enum {
     use super::*;
 
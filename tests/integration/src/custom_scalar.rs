@@ -2,12 +2,12 @@ use std::{convert::TryInto as _, fmt, pin::Pin};
 
 use futures::{stream, Stream};
 use juniper::{
-    execute, graphql_input_value, graphql_object, graphql_scalar, graphql_subscription,
-    graphql_vars,
+    execute, from_input_value, from_value, graphql_input_value, graphql_object, graphql_scalar,
+    graphql_subscription, graphql_vars,
     parser::{ParseError, ScalarToken, Token},
     serde::{de, Deserialize, Deserializer, Serialize},
-    EmptyMutation, FieldResult, InputValue, Object, ParseScalarResult, RootNode, ScalarValue,
-    Value, Variables,
+    to_input_value, to_value, EmptyMutation, FieldResult, InputValue, Object, ParseScalarResult,
+    RootNode, ScalarValue, Value, Variables,
 };
 
 #[derive(Clone, Debug, PartialEq, ScalarValue, Serialize)]
@@ -219,3 +219,19 @@ fn deserialize_variable() {
         }),
     );
 }
+
+#[test]
+fn round_trips_long_without_json() {
+    let long = i64::from(i32::MAX) + 42;
+
+    let input: InputValue<MyScalarValue> = to_input_value(&[long]).unwrap();
+    assert_eq!(
+        input,
+        graphql_input_value!([(InputValue::<_>::scalar(long))])
+    );
+    assert_eq!(from_input_value::<Vec<i64>, _>(input).unwrap(), vec![long]);
+
+    let value: Value<MyScalarValue> = to_value(&long).unwrap();
+    assert_eq!(value, Value::scalar(long));
+    assert_eq!(from_value::<i64, _>(value).unwrap(), long);
+}
@@ -4,7 +4,7 @@
 use futures::stream;
 use juniper::{
     execute, graphql_object, graphql_subscription, graphql_value, graphql_vars,
-    resolve_into_stream, GraphQLError, RootNode,
+    resolve_into_stream, RequestError, RootNode,
 };
 
 pub struct Query;
@@ -101,7 +101,7 @@ async fn subscription_typename() {
     let schema = RootNode::new(Query, Mutation, Subscription);
 
     match resolve_into_stream(query, None, &schema, &graphql_vars! {}, &()).await {
-        Err(GraphQLError::ValidationError(mut errors)) => {
+        Err(RequestError::Validation(mut errors)) => {
             assert_eq!(errors.len(), 1);
 
             let err = errors.pop().unwrap();
@@ -115,7 +115,7 @@ async fn subscription_typename() {
             assert_eq!(err.locations()[0].line(), 0);
             assert_eq!(err.locations()[0].column(), 15);
         }
-        _ => panic!("Expected ValidationError"),
+        _ => panic!("Expected validation error"),
     };
 }
 
@@ -126,7 +126,7 @@ async fn explicit_subscription_typename() {
     let schema = RootNode::new(Query, Mutation, Subscription);
 
     match resolve_into_stream(query, None, &schema, &graphql_vars! {}, &()).await {
-        Err(GraphQLError::ValidationError(mut errors)) => {
+        Err(RequestError::Validation(mut errors)) => {
             assert_eq!(errors.len(), 1);
 
             let err = errors.pop().unwrap();
@@ -140,6 +140,6 @@ async fn explicit_subscription_typename() {
             assert_eq!(err.locations()[0].line(), 0);
             assert_eq!(err.locations()[0].column(), 28);
         }
-        _ => panic!("Expected ValidationError"),
+        _ => panic!("Expected validation error"),
     };
 }
@@ -39,7 +39,7 @@ pub(crate) mod util {
     use futures::StreamExt as _;
     use juniper::{
         graphql_value, DefaultScalarValue, EmptyMutation, EmptySubscription, ExecutionError,
-        GraphQLError, GraphQLType, RootNode, ScalarValue, Value, ValuesStream,
+        GraphQLType, RequestError, RootNode, ScalarValue, Value, ValuesStream,
     };
 
     pub(crate) fn schema<'q, C, Q>(
@@ -73,8 +73,8 @@ pub(crate) mod util {
     /// [`juniper::resolve_into_stream()`] and transforms it into a regular
     /// [`Value`].
     pub(crate) async fn extract_next<'a, S: ScalarValue>(
-        input: Result<(Value<ValuesStream<'a, S>>, Vec<ExecutionError<S>>), GraphQLError<'a>>,
-    ) -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>> {
+        input: Result<(Value<ValuesStream<'a, S>>, Vec<ExecutionError<S>>), RequestError<'a>>,
+    ) -> Result<(Value<S>, Vec<ExecutionError<S>>), RequestError<'a>> {
         let (stream, errs) = input?;
         if !errs.is_empty() {
             return Ok((Value::Null, errs));
@@ -70,6 +70,7 @@ async fn query_document_can_be_pre_parsed() {
         root_node,
         &graphql_vars! {},
         &Context {},
+        root_node.schema.limits(),
     )
     .await
     .unwrap();